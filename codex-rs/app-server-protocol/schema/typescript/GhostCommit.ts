@@ -5,4 +5,9 @@
 /**
  * Details of a ghost commit created from a repository state.
  */
-export type GhostCommit = { id: string, parent: string | null, preexisting_untracked_files: Array<string>, preexisting_untracked_dirs: Array<string>, };
+export type GhostCommit = { id: string, parent: string | null, preexisting_untracked_files: Array<string>, preexisting_untracked_dirs: Array<string>, 
+/**
+ * Set when the snapshot was taken outside a Git repository and lives in
+ * a file snapshot directory instead of the object database.
+ */
+file_snapshot_dir?: string, };
@@ -6,4 +6,9 @@ import type { ReviewTarget } from "./ReviewTarget";
 /**
  * Review request sent to the review session.
  */
-export type ReviewRequest = { target: ReviewTarget, user_facing_hint?: string, };
+export type ReviewRequest = { target: ReviewTarget, user_facing_hint?: string, 
+/**
+ * Restrict the review to changes under these paths. Empty reviews every
+ * changed file.
+ */
+paths?: Array<string>, };
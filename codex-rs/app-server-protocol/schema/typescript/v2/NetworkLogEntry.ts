@@ -0,0 +1,34 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NetworkLogDecision } from "./NetworkLogDecision";
+
+export type NetworkLogEntry = { 
+/**
+ * Unix timestamp (in seconds) when the proxy handled the request.
+ */
+timestamp: number, threadId: string | null, 
+/**
+ * Exec call that made the request, when it could be attributed.
+ */
+callId: string | null, host: string, port: number | null, 
+/**
+ * Proxy protocol, e.g. `http`, `https`, `http-connect` or `socks5`.
+ */
+protocol: string, method: string | null, decision: NetworkLogDecision, 
+/**
+ * Why the request was blocked, e.g. `not_allowed` or `denied_by_rule`.
+ */
+reason: string | null, 
+/**
+ * Which policy layer decided the request, e.g. `baseline_policy` or `decider`.
+ */
+source: string | null, 
+/**
+ * Request `content-length` header; unset for chunked bodies and opaque tunnels.
+ */
+requestBytes: number | null, 
+/**
+ * Response `content-length` header; unset for chunked bodies and opaque tunnels.
+ */
+responseBytes: number | null, };
@@ -0,0 +1,26 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NetworkLogDecision } from "./NetworkLogDecision";
+
+export type NetworkLogReadParams = { 
+/**
+ * Only return requests made by this thread.
+ */
+threadId?: string | null, 
+/**
+ * Only return requests made by this exec call.
+ */
+callId?: string | null, 
+/**
+ * Only return requests whose host contains this text.
+ */
+host?: string | null, decision?: NetworkLogDecision | null, 
+/**
+ * Opaque pagination cursor returned by a previous call.
+ */
+cursor?: string | null, 
+/**
+ * Optional page size; defaults to 100.
+ */
+limit?: number | null, };
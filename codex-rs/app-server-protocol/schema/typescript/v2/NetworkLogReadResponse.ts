@@ -0,0 +1,15 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NetworkLogEntry } from "./NetworkLogEntry";
+
+export type NetworkLogReadResponse = { 
+/**
+ * Requests in the order the proxy handled them.
+ */
+data: Array<NetworkLogEntry>, 
+/**
+ * Opaque cursor to pass to the next call to continue after the last item.
+ * if None, there are no more items to return.
+ */
+nextCursor: string | null, };
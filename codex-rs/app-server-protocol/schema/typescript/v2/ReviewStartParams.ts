@@ -9,4 +9,9 @@ export type ReviewStartParams = { threadId: string, target: ReviewTarget,
  * Where to run the review: inline (default) on the current thread or
  * detached on a new thread (returned in `reviewThreadId`).
  */
-delivery?: ReviewDelivery | null, };
+delivery?: ReviewDelivery | null, 
+/**
+ * Restrict the review to changes under these paths, relative to the
+ * thread's working directory. Omitted or empty reviews every file.
+ */
+paths?: Array<string> | null, };
@@ -0,0 +1,18 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadCheckpointFile } from "./ThreadCheckpointFile";
+
+export type ThreadCheckpoint = { 
+/**
+ * Pass to `thread/checkpoints/restore` to restore files from this checkpoint.
+ */
+id: string, 
+/**
+ * The user message that started the turn, if any.
+ */
+userMessage: string | null, 
+/**
+ * Files whose contents differ between the checkpoint and the working tree.
+ */
+files: Array<ThreadCheckpointFile>, };
@@ -0,0 +1,11 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadCheckpointFileStatus } from "./ThreadCheckpointFileStatus";
+
+export type ThreadCheckpointFile = { path: string, status: ThreadCheckpointFileStatus, 
+/**
+ * Unified diff from the checkpoint to the current contents. Hunks are
+ * numbered from zero in order; empty for binary files.
+ */
+diff: string, };
@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadCheckpoint } from "./ThreadCheckpoint";
+
+export type ThreadCheckpointsListResponse = { 
+/**
+ * One checkpoint per turn that took a snapshot, oldest first.
+ */
+data: Array<ThreadCheckpoint>, };
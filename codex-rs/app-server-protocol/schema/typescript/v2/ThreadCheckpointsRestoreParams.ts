@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ThreadCheckpointsRestoreParams = { threadId: string, checkpointId: string, 
+/**
+ * File to restore, absolute or relative to the thread's working directory.
+ */
+path: string, 
+/**
+ * Restore only this hunk of the file's checkpoint diff. When omitted the
+ * whole file is restored, or deleted if it did not exist at the checkpoint.
+ */
+hunk?: number | null, };
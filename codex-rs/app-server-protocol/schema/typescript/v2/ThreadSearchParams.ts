@@ -0,0 +1,30 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadSourceKind } from "./ThreadSourceKind";
+
+export type ThreadSearchParams = { 
+/**
+ * Words to look for in user messages, assistant messages and commands.
+ * Every word must match; the last one also matches as a prefix.
+ */
+query: string, 
+/**
+ * Optional maximum number of threads to return; defaults to 20.
+ */
+limit?: number | null, 
+/**
+ * Optional provider filter; when set, only sessions recorded under these
+ * providers are returned. When present but empty, includes all providers.
+ */
+modelProviders?: Array<string> | null, 
+/**
+ * Optional source filter; when set, only sessions from these source kinds
+ * are returned. When omitted or empty, defaults to interactive sources.
+ */
+sourceKinds?: Array<ThreadSourceKind> | null, 
+/**
+ * Optional archived filter; when set to true, only archived threads are searched.
+ * If false or null, only non-archived threads are searched.
+ */
+archived?: boolean | null, };
@@ -0,0 +1,20 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Thread } from "./Thread";
+import type { ThreadSearchMatchKind } from "./ThreadSearchMatchKind";
+
+export type ThreadSearchResult = { thread: Thread, 
+/**
+ * Id of the turn containing the best match, when the session recorded
+ * turn ids. Sessions from older versions only have `turn_index`.
+ */
+turnId: string | null, 
+/**
+ * Zero-based index of the turn containing the best match.
+ */
+turnIndex: number, matchKind: ThreadSearchMatchKind, 
+/**
+ * Excerpt of the matching text around the query words.
+ */
+snippet: string, };
@@ -92,6 +92,10 @@ export type { Model } from "./Model";
 export type { ModelListParams } from "./ModelListParams";
 export type { ModelListResponse } from "./ModelListResponse";
 export type { NetworkAccess } from "./NetworkAccess";
+export type { NetworkLogDecision } from "./NetworkLogDecision";
+export type { NetworkLogEntry } from "./NetworkLogEntry";
+export type { NetworkLogReadParams } from "./NetworkLogReadParams";
+export type { NetworkLogReadResponse } from "./NetworkLogReadResponse";
 export type { NetworkRequirements } from "./NetworkRequirements";
 export type { OverriddenMetadata } from "./OverriddenMetadata";
 export type { PatchApplyStatus } from "./PatchApplyStatus";
@@ -139,6 +143,13 @@ export type { TextRange } from "./TextRange";
 export type { Thread } from "./Thread";
 export type { ThreadArchiveParams } from "./ThreadArchiveParams";
 export type { ThreadArchiveResponse } from "./ThreadArchiveResponse";
+export type { ThreadCheckpoint } from "./ThreadCheckpoint";
+export type { ThreadCheckpointFile } from "./ThreadCheckpointFile";
+export type { ThreadCheckpointFileStatus } from "./ThreadCheckpointFileStatus";
+export type { ThreadCheckpointsListParams } from "./ThreadCheckpointsListParams";
+export type { ThreadCheckpointsListResponse } from "./ThreadCheckpointsListResponse";
+export type { ThreadCheckpointsRestoreParams } from "./ThreadCheckpointsRestoreParams";
+export type { ThreadCheckpointsRestoreResponse } from "./ThreadCheckpointsRestoreResponse";
 export type { ThreadCompactStartParams } from "./ThreadCompactStartParams";
 export type { ThreadCompactStartResponse } from "./ThreadCompactStartResponse";
 export type { ThreadForkParams } from "./ThreadForkParams";
@@ -155,6 +166,10 @@ export type { ThreadResumeParams } from "./ThreadResumeParams";
 export type { ThreadResumeResponse } from "./ThreadResumeResponse";
 export type { ThreadRollbackParams } from "./ThreadRollbackParams";
 export type { ThreadRollbackResponse } from "./ThreadRollbackResponse";
+export type { ThreadSearchMatchKind } from "./ThreadSearchMatchKind";
+export type { ThreadSearchParams } from "./ThreadSearchParams";
+export type { ThreadSearchResponse } from "./ThreadSearchResponse";
+export type { ThreadSearchResult } from "./ThreadSearchResult";
 export type { ThreadSetNameParams } from "./ThreadSetNameParams";
 export type { ThreadSetNameResponse } from "./ThreadSetNameResponse";
 export type { ThreadSortKey } from "./ThreadSortKey";
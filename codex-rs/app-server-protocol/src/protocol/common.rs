@@ -223,6 +223,14 @@ client_request_definitions! {
         params: v2::ThreadRollbackParams,
         response: v2::ThreadRollbackResponse,
     },
+    ThreadCheckpointsList => "thread/checkpoints/list" {
+        params: v2::ThreadCheckpointsListParams,
+        response: v2::ThreadCheckpointsListResponse,
+    },
+    ThreadCheckpointsRestore => "thread/checkpoints/restore" {
+        params: v2::ThreadCheckpointsRestoreParams,
+        response: v2::ThreadCheckpointsRestoreResponse,
+    },
     ThreadList => "thread/list" {
         params: v2::ThreadListParams,
         response: v2::ThreadListResponse,
@@ -231,6 +239,10 @@ client_request_definitions! {
         params: v2::ThreadLoadedListParams,
         response: v2::ThreadLoadedListResponse,
     },
+    ThreadSearch => "thread/search" {
+        params: v2::ThreadSearchParams,
+        response: v2::ThreadSearchResponse,
+    },
     ThreadRead => "thread/read" {
         params: v2::ThreadReadParams,
         response: v2::ThreadReadResponse,
@@ -354,6 +366,11 @@ client_request_definitions! {
         response: v2::ConfigWriteResponse,
     },
 
+    NetworkLogRead => "networkLog/read" {
+        params: v2::NetworkLogReadParams,
+        response: v2::NetworkLogReadResponse,
+    },
+
     ConfigRequirementsRead => "configRequirements/read" {
         params: #[ts(type = "undefined")] #[serde(skip_serializing_if = "Option::is_none")] Option<()>,
         response: v2::ConfigRequirementsReadResponse,
@@ -27,6 +27,9 @@ use codex_protocol::plan_tool::PlanItemArg as CorePlanItemArg;
 use codex_protocol::plan_tool::StepStatus as CorePlanStepStatus;
 use codex_protocol::protocol::AgentStatus as CoreAgentStatus;
 use codex_protocol::protocol::AskForApproval as CoreAskForApproval;
+use codex_protocol::protocol::Checkpoint as CoreCheckpoint;
+use codex_protocol::protocol::CheckpointFile as CoreCheckpointFile;
+use codex_protocol::protocol::CheckpointFileStatus as CoreCheckpointFileStatus;
 use codex_protocol::protocol::CodexErrorInfo as CoreCodexErrorInfo;
 use codex_protocol::protocol::CreditsSnapshot as CoreCreditsSnapshot;
 use codex_protocol::protocol::ExecCommandStatus as CoreExecCommandStatus;
@@ -1687,6 +1690,102 @@ pub struct ThreadRollbackResponse {
     pub thread: Thread,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadCheckpointsListParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadCheckpointsListResponse {
+    /// One checkpoint per turn that took a snapshot, oldest first.
+    pub data: Vec<ThreadCheckpoint>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadCheckpoint {
+    /// Pass to `thread/checkpoints/restore` to restore files from this checkpoint.
+    pub id: String,
+    /// The user message that started the turn, if any.
+    pub user_message: Option<String>,
+    /// Files whose contents differ between the checkpoint and the working tree.
+    pub files: Vec<ThreadCheckpointFile>,
+}
+
+impl From<CoreCheckpoint> for ThreadCheckpoint {
+    fn from(value: CoreCheckpoint) -> Self {
+        Self {
+            id: value.id,
+            user_message: value.user_message,
+            files: value.files.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadCheckpointFile {
+    pub path: PathBuf,
+    pub status: ThreadCheckpointFileStatus,
+    /// Unified diff from the checkpoint to the current contents. Hunks are
+    /// numbered from zero in order; empty for binary files.
+    pub diff: String,
+}
+
+impl From<CoreCheckpointFile> for ThreadCheckpointFile {
+    fn from(value: CoreCheckpointFile) -> Self {
+        Self {
+            path: value.path,
+            status: value.status.into(),
+            diff: value.unified_diff,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum ThreadCheckpointFileStatus {
+    Added,
+    Deleted,
+    Modified,
+}
+
+impl From<CoreCheckpointFileStatus> for ThreadCheckpointFileStatus {
+    fn from(value: CoreCheckpointFileStatus) -> Self {
+        match value {
+            CoreCheckpointFileStatus::Added => Self::Added,
+            CoreCheckpointFileStatus::Deleted => Self::Deleted,
+            CoreCheckpointFileStatus::Modified => Self::Modified,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadCheckpointsRestoreParams {
+    pub thread_id: String,
+    pub checkpoint_id: String,
+    /// File to restore, absolute or relative to the thread's working directory.
+    pub path: PathBuf,
+    /// Restore only this hunk of the file's checkpoint diff. When omitted the
+    /// whole file is restored, or deleted if it did not exist at the checkpoint.
+    #[ts(optional = nullable)]
+    pub hunk: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadCheckpointsRestoreResponse {}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1777,6 +1876,133 @@ pub struct ThreadLoadedListResponse {
     pub next_cursor: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchParams {
+    /// Words to look for in user messages, assistant messages and commands.
+    /// Every word must match; the last one also matches as a prefix.
+    pub query: String,
+    /// Optional maximum number of threads to return; defaults to 20.
+    #[ts(optional = nullable)]
+    pub limit: Option<u32>,
+    /// Optional provider filter; when set, only sessions recorded under these
+    /// providers are returned. When present but empty, includes all providers.
+    #[ts(optional = nullable)]
+    pub model_providers: Option<Vec<String>>,
+    /// Optional source filter; when set, only sessions from these source kinds
+    /// are returned. When omitted or empty, defaults to interactive sources.
+    #[ts(optional = nullable)]
+    pub source_kinds: Option<Vec<ThreadSourceKind>>,
+    /// Optional archived filter; when set to true, only archived threads are searched.
+    /// If false or null, only non-archived threads are searched.
+    #[ts(optional = nullable)]
+    pub archived: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum ThreadSearchMatchKind {
+    UserMessage,
+    AgentMessage,
+    Command,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchResult {
+    pub thread: Thread,
+    /// Id of the turn containing the best match, when the session recorded
+    /// turn ids. Sessions from older versions only have `turn_index`.
+    pub turn_id: Option<String>,
+    /// Zero-based index of the turn containing the best match.
+    pub turn_index: u32,
+    pub match_kind: ThreadSearchMatchKind,
+    /// Excerpt of the matching text around the query words.
+    pub snippet: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchResponse {
+    /// Matching threads, best match first.
+    pub data: Vec<ThreadSearchResult>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum NetworkLogDecision {
+    Allow,
+    Deny,
+    Ask,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct NetworkLogReadParams {
+    /// Only return requests made by this thread.
+    #[ts(optional = nullable)]
+    pub thread_id: Option<String>,
+    /// Only return requests made by this exec call.
+    #[ts(optional = nullable)]
+    pub call_id: Option<String>,
+    /// Only return requests whose host contains this text.
+    #[ts(optional = nullable)]
+    pub host: Option<String>,
+    #[ts(optional = nullable)]
+    pub decision: Option<NetworkLogDecision>,
+    /// Opaque pagination cursor returned by a previous call.
+    #[ts(optional = nullable)]
+    pub cursor: Option<String>,
+    /// Optional page size; defaults to 100.
+    #[ts(optional = nullable)]
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct NetworkLogEntry {
+    /// Unix timestamp (in seconds) when the proxy handled the request.
+    #[ts(type = "number")]
+    pub timestamp: i64,
+    pub thread_id: Option<String>,
+    /// Exec call that made the request, when it could be attributed.
+    pub call_id: Option<String>,
+    pub host: String,
+    pub port: Option<u16>,
+    /// Proxy protocol, e.g. `http`, `https`, `http-connect` or `socks5`.
+    pub protocol: String,
+    pub method: Option<String>,
+    pub decision: NetworkLogDecision,
+    /// Why the request was blocked, e.g. `not_allowed` or `denied_by_rule`.
+    pub reason: Option<String>,
+    /// Which policy layer decided the request, e.g. `baseline_policy` or `decider`.
+    pub source: Option<String>,
+    /// Request `content-length` header; unset for chunked bodies and opaque tunnels.
+    #[ts(type = "number | null")]
+    pub request_bytes: Option<i64>,
+    /// Response `content-length` header; unset for chunked bodies and opaque tunnels.
+    #[ts(type = "number | null")]
+    pub response_bytes: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct NetworkLogReadResponse {
+    /// Requests in the order the proxy handled them.
+    pub data: Vec<NetworkLogEntry>,
+    /// Opaque cursor to pass to the next call to continue after the last item.
+    /// if None, there are no more items to return.
+    pub next_cursor: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -2249,6 +2475,12 @@ pub struct ReviewStartParams {
     #[serde(default)]
     #[ts(optional = nullable)]
     pub delivery: Option<ReviewDelivery>,
+
+    /// Restrict the review to changes under these paths, relative to the
+    /// thread's working directory. Omitted or empty reviews every file.
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub paths: Option<Vec<PathBuf>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -2284,6 +2516,17 @@ pub enum ReviewTarget {
         title: Option<String>,
     },
 
+    /// Review the commits in `from..to` (e.g. a whole PR stack).
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    CommitRange { from: String, to: String },
+
+    /// Review a diff stored in a patch file, relative to the thread's
+    /// working directory.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    PatchFile { path: PathBuf },
+
     /// Arbitrary instructions, equivalent to the old free-form prompt.
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
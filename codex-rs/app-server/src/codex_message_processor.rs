@@ -90,6 +90,10 @@ use codex_app_server_protocol::MockExperimentalMethodParams;
 use codex_app_server_protocol::MockExperimentalMethodResponse;
 use codex_app_server_protocol::ModelListParams;
 use codex_app_server_protocol::ModelListResponse;
+use codex_app_server_protocol::NetworkLogDecision;
+use codex_app_server_protocol::NetworkLogEntry;
+use codex_app_server_protocol::NetworkLogReadParams;
+use codex_app_server_protocol::NetworkLogReadResponse;
 use codex_app_server_protocol::NewConversationParams;
 use codex_app_server_protocol::NewConversationResponse;
 use codex_app_server_protocol::RemoveConversationListenerParams;
@@ -122,6 +126,10 @@ use codex_app_server_protocol::ThreadArchiveParams;
 use codex_app_server_protocol::ThreadArchiveResponse;
 use codex_app_server_protocol::ThreadBackgroundTerminalsCleanParams;
 use codex_app_server_protocol::ThreadBackgroundTerminalsCleanResponse;
+use codex_app_server_protocol::ThreadCheckpointsListParams;
+use codex_app_server_protocol::ThreadCheckpointsListResponse;
+use codex_app_server_protocol::ThreadCheckpointsRestoreParams;
+use codex_app_server_protocol::ThreadCheckpointsRestoreResponse;
 use codex_app_server_protocol::ThreadCompactStartParams;
 use codex_app_server_protocol::ThreadCompactStartResponse;
 use codex_app_server_protocol::ThreadForkParams;
@@ -136,6 +144,10 @@ use codex_app_server_protocol::ThreadReadResponse;
 use codex_app_server_protocol::ThreadResumeParams;
 use codex_app_server_protocol::ThreadResumeResponse;
 use codex_app_server_protocol::ThreadRollbackParams;
+use codex_app_server_protocol::ThreadSearchMatchKind;
+use codex_app_server_protocol::ThreadSearchParams;
+use codex_app_server_protocol::ThreadSearchResponse;
+use codex_app_server_protocol::ThreadSearchResult;
 use codex_app_server_protocol::ThreadSetNameParams;
 use codex_app_server_protocol::ThreadSetNameResponse;
 use codex_app_server_protocol::ThreadSortKey;
@@ -187,7 +199,7 @@ use codex_core::default_client::get_codex_user_agent;
 use codex_core::default_client::set_default_client_residency_requirement;
 use codex_core::error::CodexErr;
 use codex_core::exec::ExecParams;
-use codex_core::exec_env::create_env;
+use codex_core::exec_env::create_env_with_secrets;
 use codex_core::features::FEATURES;
 use codex_core::features::Feature;
 use codex_core::features::Stage;
@@ -207,10 +219,15 @@ use codex_core::read_head_for_summary;
 use codex_core::read_session_meta_line;
 use codex_core::rollout_date_parts;
 use codex_core::sandboxing::SandboxPermissions;
+use codex_core::secrets::SessionSecrets;
 use codex_core::skills::remote::download_remote_skill;
 use codex_core::skills::remote::list_remote_skills;
+use codex_core::state_db::NetworkRequestQuery;
+use codex_core::state_db::NetworkRequestRow;
 use codex_core::state_db::StateDbHandle;
+use codex_core::state_db::ThreadSearchKind;
 use codex_core::state_db::get_state_db;
+use codex_core::state_db::search_threads_db;
 use codex_core::windows_sandbox::WindowsSandboxLevelExt;
 use codex_feedback::CodexFeedback;
 use codex_login::ServerOptions as LoginServerOptions;
@@ -426,6 +443,7 @@ impl CodexMessageProcessor {
 
     fn review_request_from_target(
         target: ApiReviewTarget,
+        paths: Vec<PathBuf>,
     ) -> Result<(ReviewRequest, String), JSONRPCErrorError> {
         fn invalid_request(message: String) -> JSONRPCErrorError {
             JSONRPCErrorError {
@@ -454,6 +472,20 @@ impl CodexMessageProcessor {
                     .filter(|t| !t.is_empty());
                 ApiReviewTarget::Commit { sha, title }
             }
+            ApiReviewTarget::CommitRange { from, to } => {
+                let from = from.trim().to_string();
+                let to = to.trim().to_string();
+                if from.is_empty() || to.is_empty() {
+                    return Err(invalid_request("from and to must not be empty".to_string()));
+                }
+                ApiReviewTarget::CommitRange { from, to }
+            }
+            ApiReviewTarget::PatchFile { path } => {
+                if path.as_os_str().is_empty() {
+                    return Err(invalid_request("path must not be empty".to_string()));
+                }
+                ApiReviewTarget::PatchFile { path }
+            }
             ApiReviewTarget::Custom { instructions } => {
                 let trimmed = instructions.trim().to_string();
                 if trimmed.is_empty() {
@@ -471,13 +503,20 @@ impl CodexMessageProcessor {
             ApiReviewTarget::UncommittedChanges => CoreReviewTarget::UncommittedChanges,
             ApiReviewTarget::BaseBranch { branch } => CoreReviewTarget::BaseBranch { branch },
             ApiReviewTarget::Commit { sha, title } => CoreReviewTarget::Commit { sha, title },
+            ApiReviewTarget::CommitRange { from, to } => CoreReviewTarget::CommitRange { from, to },
+            ApiReviewTarget::PatchFile { path } => CoreReviewTarget::PatchFile { path },
             ApiReviewTarget::Custom { instructions } => CoreReviewTarget::Custom { instructions },
         };
 
-        let hint = codex_core::review_prompts::user_facing_hint(&core_target);
+        let paths = paths
+            .into_iter()
+            .filter(|path| !path.as_os_str().is_empty())
+            .collect::<Vec<_>>();
+        let hint = codex_core::review_prompts::user_facing_hint_for_paths(&core_target, &paths);
         let review_request = ReviewRequest {
             target: core_target,
             user_facing_hint: Some(hint.clone()),
+            paths,
         };
 
         Ok((review_request, hint))
@@ -533,6 +572,14 @@ impl CodexMessageProcessor {
                 self.thread_rollback(to_connection_request_id(request_id), params)
                     .await;
             }
+            ClientRequest::ThreadCheckpointsList { request_id, params } => {
+                self.thread_checkpoints_list(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::ThreadCheckpointsRestore { request_id, params } => {
+                self.thread_checkpoints_restore(to_connection_request_id(request_id), params)
+                    .await;
+            }
             ClientRequest::ThreadList { request_id, params } => {
                 self.thread_list(to_connection_request_id(request_id), params)
                     .await;
@@ -541,6 +588,14 @@ impl CodexMessageProcessor {
                 self.thread_loaded_list(to_connection_request_id(request_id), params)
                     .await;
             }
+            ClientRequest::ThreadSearch { request_id, params } => {
+                self.thread_search(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::NetworkLogRead { request_id, params } => {
+                self.network_log_read(to_connection_request_id(request_id), params)
+                    .await;
+            }
             ClientRequest::ThreadRead { request_id, params } => {
                 self.thread_read(to_connection_request_id(request_id), params)
                     .await;
@@ -1684,7 +1739,12 @@ impl CodexMessageProcessor {
         }
 
         let cwd = params.cwd.unwrap_or_else(|| self.config.cwd.clone());
-        let env = create_env(&self.config.permissions.shell_environment_policy, None);
+        let env = create_env_with_secrets(
+            &self.config.permissions.shell_environment_policy,
+            None,
+            &SessionSecrets::new(self.config.codex_home.clone()),
+            &cwd,
+        );
         let timeout_ms = params
             .timeout_ms
             .and_then(|timeout_ms| u64::try_from(timeout_ms).ok());
@@ -1693,9 +1753,11 @@ impl CodexMessageProcessor {
         let started_network_proxy = match self.config.permissions.network.as_ref() {
             Some(spec) => match spec
                 .start_proxy(
+                    &self.config.codex_home,
                     self.config.permissions.sandbox_policy.get(),
                     None,
                     None,
+                    None,
                     managed_network_requirements_enabled,
                 )
                 .await
@@ -2440,6 +2502,75 @@ impl CodexMessageProcessor {
         }
     }
 
+    async fn thread_checkpoints_list(
+        &self,
+        request_id: ConnectionRequestId,
+        params: ThreadCheckpointsListParams,
+    ) {
+        let ThreadCheckpointsListParams { thread_id } = params;
+
+        let (_, thread) = match self.load_thread(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        match thread.list_checkpoints().await {
+            Ok(checkpoints) => {
+                let data = checkpoints.into_iter().map(Into::into).collect();
+                self.outgoing
+                    .send_response(request_id, ThreadCheckpointsListResponse { data })
+                    .await;
+            }
+            Err(err) => {
+                self.send_internal_error(request_id, format!("failed to list checkpoints: {err}"))
+                    .await;
+            }
+        }
+    }
+
+    async fn thread_checkpoints_restore(
+        &self,
+        request_id: ConnectionRequestId,
+        params: ThreadCheckpointsRestoreParams,
+    ) {
+        let ThreadCheckpointsRestoreParams {
+            thread_id,
+            checkpoint_id,
+            path,
+            hunk,
+        } = params;
+
+        let (_, thread) = match self.load_thread(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        let hunk = hunk.map(|hunk| hunk as usize);
+        match thread
+            .restore_checkpoint_file(checkpoint_id, path, hunk)
+            .await
+        {
+            Ok(()) => {
+                self.outgoing
+                    .send_response(request_id, ThreadCheckpointsRestoreResponse {})
+                    .await;
+            }
+            Err(CodexErr::InvalidRequest(message)) => {
+                self.send_invalid_request_error(request_id, message).await;
+            }
+            Err(err) => {
+                self.send_internal_error(request_id, format!("failed to restore file: {err}"))
+                    .await;
+            }
+        }
+    }
+
     async fn thread_list(&self, request_id: ConnectionRequestId, params: ThreadListParams) {
         let ThreadListParams {
             cursor,
@@ -2544,6 +2675,180 @@ impl CodexMessageProcessor {
         self.outgoing.send_response(request_id, response).await;
     }
 
+    async fn thread_search(&self, request_id: ConnectionRequestId, params: ThreadSearchParams) {
+        const DEFAULT_LIMIT: usize = 20;
+
+        let ThreadSearchParams {
+            query,
+            limit,
+            model_providers,
+            source_kinds,
+            archived,
+        } = params;
+        let Some(state_db) = get_state_db(&self.config, None).await else {
+            let error = JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: "thread search requires the sqlite feature".to_string(),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        };
+
+        let limit = limit
+            .map(|value| value as usize)
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, THREAD_LIST_MAX_LIMIT);
+        let model_provider_filter = match model_providers {
+            Some(providers) if providers.is_empty() => None,
+            Some(providers) => Some(providers),
+            None => Some(vec![self.config.model_provider_id.clone()]),
+        };
+        let (allowed_sources, source_kind_filter) = compute_source_filters(source_kinds);
+        // Source kinds that need a post-filter are matched after the query, so
+        // fetch a full page to still fill `limit` when some hits are dropped.
+        let db_limit = if source_kind_filter.is_some() {
+            THREAD_LIST_MAX_LIMIT
+        } else {
+            limit
+        };
+        let Some(hits) = search_threads_db(
+            Some(state_db.as_ref()),
+            &query,
+            db_limit,
+            allowed_sources.as_slice(),
+            model_provider_filter.as_deref(),
+            archived.unwrap_or(false),
+        )
+        .await
+        else {
+            let error = JSONRPCErrorError {
+                code: INTERNAL_ERROR_CODE,
+                message: "failed to search threads".to_string(),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        };
+
+        let data = hits
+            .into_iter()
+            .filter_map(|hit| {
+                let metadata = hit.metadata;
+                let summary = summary_from_state_db_metadata(
+                    metadata.id,
+                    metadata.rollout_path,
+                    metadata.first_user_message,
+                    metadata
+                        .created_at
+                        .to_rfc3339_opts(SecondsFormat::Secs, true),
+                    metadata
+                        .updated_at
+                        .to_rfc3339_opts(SecondsFormat::Secs, true),
+                    metadata.model_provider,
+                    metadata.cwd,
+                    metadata.cli_version,
+                    metadata.source,
+                    metadata.git_sha,
+                    metadata.git_branch,
+                    metadata.git_origin_url,
+                );
+                if source_kind_filter
+                    .as_ref()
+                    .is_some_and(|filter| !source_kind_matches(&summary.source, filter))
+                {
+                    return None;
+                }
+                Some(ThreadSearchResult {
+                    thread: summary_to_thread(summary),
+                    turn_id: hit.turn_id,
+                    turn_index: u32::try_from(hit.turn_index).unwrap_or(u32::MAX),
+                    match_kind: match hit.kind {
+                        ThreadSearchKind::User => ThreadSearchMatchKind::UserMessage,
+                        ThreadSearchKind::Assistant => ThreadSearchMatchKind::AgentMessage,
+                        ThreadSearchKind::Command => ThreadSearchMatchKind::Command,
+                    },
+                    snippet: hit.snippet,
+                })
+            })
+            .take(limit)
+            .collect();
+        let response = ThreadSearchResponse { data };
+        self.outgoing.send_response(request_id, response).await;
+    }
+
+    async fn network_log_read(
+        &self,
+        request_id: ConnectionRequestId,
+        params: NetworkLogReadParams,
+    ) {
+        const DEFAULT_LIMIT: u32 = 100;
+
+        let NetworkLogReadParams {
+            thread_id,
+            call_id,
+            host,
+            decision,
+            cursor,
+            limit,
+        } = params;
+        let after_id = match cursor {
+            Some(cursor) => match cursor.parse::<i64>() {
+                Ok(after_id) => Some(after_id),
+                Err(_) => {
+                    let error = JSONRPCErrorError {
+                        code: INVALID_REQUEST_ERROR_CODE,
+                        message: format!("invalid cursor: {cursor}"),
+                        data: None,
+                    };
+                    self.outgoing.send_error(request_id, error).await;
+                    return;
+                }
+            },
+            None => None,
+        };
+        let Some(state_db) = get_state_db(&self.config, None).await else {
+            let error = JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: "network log requires the sqlite feature".to_string(),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        };
+
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).max(1) as usize;
+        let query = NetworkRequestQuery {
+            thread_ids: thread_id.into_iter().collect(),
+            call_id,
+            host_like: host,
+            decision: decision.map(|decision| network_log_decision_str(decision).to_string()),
+            after_id,
+            limit: Some(limit),
+            ..Default::default()
+        };
+        let rows = match state_db.query_network_requests(&query).await {
+            Ok(rows) => rows,
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to read network log: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        let next_cursor = rows
+            .last()
+            .filter(|_| rows.len() == limit)
+            .map(|row| row.id.to_string());
+        let data = rows.into_iter().filter_map(network_log_entry).collect();
+        let response = NetworkLogReadResponse { data, next_cursor };
+        self.outgoing.send_response(request_id, response).await;
+    }
+
     async fn thread_read(&mut self, request_id: ConnectionRequestId, params: ThreadReadParams) {
         let ThreadReadParams {
             thread_id,
@@ -4630,6 +4935,12 @@ impl CodexMessageProcessor {
             tokio::fs::create_dir_all(&archive_folder).await?;
             let archived_path = archive_folder.join(&file_name);
             tokio::fs::rename(&canonical_rollout_path, &archived_path).await?;
+            // Undo snapshots of non-git workspaces only serve the live thread.
+            if let Err(err) =
+                codex_core::remove_thread_file_snapshots(&self.config.codex_home, thread_id)
+            {
+                warn!("failed to remove file snapshots of thread {thread_id}: {err}");
+            }
             if let Some(ctx) = state_db_ctx {
                 let _ = ctx
                     .mark_archived(thread_id, archived_path.as_path(), Utc::now())
@@ -5309,6 +5620,10 @@ impl CodexMessageProcessor {
                         INVALID_REQUEST_ERROR_CODE,
                         "input must not be empty".to_string(),
                     ),
+                    SteerInputError::Blocked { reason } => (
+                        INVALID_REQUEST_ERROR_CODE,
+                        format!("prompt blocked by hook: {reason}"),
+                    ),
                 };
                 let error = JSONRPCErrorError {
                     code,
@@ -5504,6 +5819,7 @@ impl CodexMessageProcessor {
             thread_id,
             target,
             delivery,
+            paths,
         } = params;
         let (parent_thread_id, parent_thread) = match self.load_thread(&thread_id).await {
             Ok(v) => v,
@@ -5513,13 +5829,14 @@ impl CodexMessageProcessor {
             }
         };
 
-        let (review_request, display_text) = match Self::review_request_from_target(target) {
-            Ok(value) => value,
-            Err(err) => {
-                self.outgoing.send_error(request_id, err).await;
-                return;
-            }
-        };
+        let (review_request, display_text) =
+            match Self::review_request_from_target(target, paths.unwrap_or_default()) {
+                Ok(value) => value,
+                Err(err) => {
+                    self.outgoing.send_error(request_id, err).await;
+                    return;
+                }
+            };
 
         let delivery = delivery.unwrap_or(ApiReviewDelivery::Inline).to_core();
         match delivery {
@@ -6592,6 +6909,40 @@ fn extract_conversation_summary(
     })
 }
 
+fn network_log_decision_str(decision: NetworkLogDecision) -> &'static str {
+    match decision {
+        NetworkLogDecision::Allow => "allow",
+        NetworkLogDecision::Deny => "deny",
+        NetworkLogDecision::Ask => "ask",
+    }
+}
+
+fn network_log_entry(row: NetworkRequestRow) -> Option<NetworkLogEntry> {
+    let decision = match row.decision.as_str() {
+        "allow" => NetworkLogDecision::Allow,
+        "deny" => NetworkLogDecision::Deny,
+        "ask" => NetworkLogDecision::Ask,
+        other => {
+            warn!("skipping network log entry with unknown decision: {other}");
+            return None;
+        }
+    };
+    Some(NetworkLogEntry {
+        timestamp: row.ts,
+        thread_id: row.thread_id,
+        call_id: row.call_id,
+        host: row.host,
+        port: row.port.and_then(|port| u16::try_from(port).ok()),
+        protocol: row.protocol,
+        method: row.method,
+        decision,
+        reason: row.reason,
+        source: row.source,
+        request_bytes: row.request_bytes,
+        response_bytes: row.response_bytes,
+    })
+}
+
 fn map_git_info(git_info: &CoreGitInfo) -> ConversationGitInfo {
     ConversationGitInfo {
         sha: git_info.commit_hash.clone(),
@@ -6687,6 +7038,50 @@ mod tests {
     use serde_json::json;
     use tempfile::TempDir;
 
+    #[test]
+    fn network_log_entry_maps_rows_and_skips_unknown_decisions() {
+        let row = NetworkRequestRow {
+            id: 7,
+            ts: 1_700_000_000,
+            thread_id: Some("thread-1".to_string()),
+            call_id: Some("call-1".to_string()),
+            host: "example.com".to_string(),
+            port: Some(443),
+            protocol: "https".to_string(),
+            method: Some("GET".to_string()),
+            decision: "deny".to_string(),
+            reason: Some("denied_by_rule".to_string()),
+            source: Some("baseline_policy".to_string()),
+            request_bytes: None,
+            response_bytes: Some(512),
+        };
+
+        assert_eq!(
+            network_log_entry(row.clone()),
+            Some(NetworkLogEntry {
+                timestamp: 1_700_000_000,
+                thread_id: Some("thread-1".to_string()),
+                call_id: Some("call-1".to_string()),
+                host: "example.com".to_string(),
+                port: Some(443),
+                protocol: "https".to_string(),
+                method: Some("GET".to_string()),
+                decision: NetworkLogDecision::Deny,
+                reason: Some("denied_by_rule".to_string()),
+                source: Some("baseline_policy".to_string()),
+                request_bytes: None,
+                response_bytes: Some(512),
+            })
+        );
+        assert_eq!(
+            network_log_entry(NetworkRequestRow {
+                decision: "unknown".to_string(),
+                ..row
+            }),
+            None
+        );
+    }
+
     #[test]
     fn validate_dynamic_tools_rejects_unsupported_input_schema() {
         let tools = vec![ApiDynamicToolSpec {
@@ -63,6 +63,7 @@ async fn review_start_runs_review_turn_and_emits_code_review_item() -> Result<()
         .send_review_start_request(ReviewStartParams {
             thread_id: thread_id.clone(),
             delivery: Some(ReviewDelivery::Inline),
+            paths: None,
             target: ReviewTarget::Commit {
                 sha: "1234567deadbeef".to_string(),
                 title: Some("Tidy UI colors".to_string()),
@@ -135,6 +136,57 @@ async fn review_start_runs_review_turn_and_emits_code_review_item() -> Result<()
     Ok(())
 }
 
+#[tokio::test]
+async fn review_start_commit_range_with_paths_reports_hint() -> Result<()> {
+    let server = create_mock_responses_server_repeating_assistant("Done").await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+    let thread_id = start_default_thread(&mut mcp).await?;
+
+    let review_req = mcp
+        .send_review_start_request(ReviewStartParams {
+            thread_id,
+            delivery: Some(ReviewDelivery::Inline),
+            paths: Some(vec!["core/src".into()]),
+            target: ReviewTarget::CommitRange {
+                from: "1234567deadbeef".to_string(),
+                to: "HEAD".to_string(),
+            },
+        })
+        .await?;
+    let review_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(review_req)),
+    )
+    .await??;
+    let ReviewStartResponse { turn, .. } = to_response::<ReviewStartResponse>(review_resp)?;
+
+    let mut entered_review = None;
+    for _ in 0..10 {
+        let item_started: JSONRPCNotification = timeout(
+            DEFAULT_READ_TIMEOUT,
+            mcp.read_stream_until_notification_message("item/started"),
+        )
+        .await??;
+        let started: ItemStartedNotification =
+            serde_json::from_value(item_started.params.expect("params must be present"))?;
+        if let ThreadItem::EnteredReviewMode { id, review } = started.item {
+            assert_eq!(id, turn.id);
+            entered_review = Some(review);
+            break;
+        }
+    }
+    assert_eq!(
+        entered_review.as_deref(),
+        Some("commits 1234567..HEAD in core/src")
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 #[ignore = "TODO(owenlin0): flaky"]
 async fn review_start_exec_approval_item_id_matches_command_execution_item() -> Result<()> {
@@ -165,6 +217,7 @@ async fn review_start_exec_approval_item_id_matches_command_execution_item() ->
         .send_review_start_request(ReviewStartParams {
             thread_id,
             delivery: Some(ReviewDelivery::Inline),
+            paths: None,
             target: ReviewTarget::Commit {
                 sha: "1234567deadbeef".to_string(),
                 title: Some("Check review approvals".to_string()),
@@ -235,6 +288,7 @@ async fn review_start_rejects_empty_base_branch() -> Result<()> {
         .send_review_start_request(ReviewStartParams {
             thread_id,
             delivery: Some(ReviewDelivery::Inline),
+            paths: None,
             target: ReviewTarget::BaseBranch {
                 branch: "   ".to_string(),
             },
@@ -280,6 +334,7 @@ async fn review_start_with_detached_delivery_returns_new_thread_id() -> Result<(
         .send_review_start_request(ReviewStartParams {
             thread_id: thread_id.clone(),
             delivery: Some(ReviewDelivery::Detached),
+            paths: None,
             target: ReviewTarget::Custom {
                 instructions: "detached review".to_string(),
             },
@@ -318,6 +373,7 @@ async fn review_start_rejects_empty_commit_sha() -> Result<()> {
         .send_review_start_request(ReviewStartParams {
             thread_id,
             delivery: Some(ReviewDelivery::Inline),
+            paths: None,
             target: ReviewTarget::Commit {
                 sha: "\t".to_string(),
                 title: None,
@@ -353,6 +409,7 @@ async fn review_start_rejects_empty_custom_instructions() -> Result<()> {
         .send_review_start_request(ReviewStartParams {
             thread_id,
             delivery: Some(ReviewDelivery::Inline),
+            paths: None,
             target: ReviewTarget::Custom {
                 instructions: "\n\n".to_string(),
             },
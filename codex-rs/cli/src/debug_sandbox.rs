@@ -7,10 +7,11 @@ use std::path::PathBuf;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
-use codex_core::exec_env::create_env;
+use codex_core::exec_env::create_env_with_secrets;
 use codex_core::landlock::spawn_command_under_linux_sandbox;
 #[cfg(target_os = "macos")]
 use codex_core::seatbelt::spawn_command_under_seatbelt;
+use codex_core::secrets::SessionSecrets;
 use codex_core::spawn::StdioPolicy;
 use codex_protocol::config_types::SandboxMode;
 use codex_utils_cli::CliConfigOverrides;
@@ -130,7 +131,12 @@ async fn run_command_under_sandbox(
     let sandbox_policy_cwd = cwd.clone();
 
     let stdio_policy = StdioPolicy::Inherit;
-    let env = create_env(&config.permissions.shell_environment_policy, None);
+    let env = create_env_with_secrets(
+        &config.permissions.shell_environment_policy,
+        None,
+        &SessionSecrets::new(config.codex_home.clone()),
+        &cwd,
+    );
 
     // Special-case Windows sandbox: execute and exit the process to emulate inherited stdio.
     if let SandboxType::Windows = sandbox_type {
@@ -219,9 +225,11 @@ async fn run_command_under_sandbox(
     let network_proxy = match config.permissions.network.as_ref() {
         Some(spec) => Some(
             spec.start_proxy(
+                &config.codex_home,
                 config.permissions.sandbox_policy.get(),
                 None,
                 None,
+                None,
                 managed_network_requirements_enabled,
             )
             .await
@@ -0,0 +1,430 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use codex_core::commands_for_exec_policy;
+use codex_core::config::Config;
+use codex_core::format_exec_policy_error_with_source;
+use codex_core::load_exec_policy;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::SandboxPolicy;
+use codex_core::render_decision_for_unmatched_command;
+use codex_execpolicy::Decision;
+use codex_execpolicy::Policy;
+use codex_execpolicy::RuleMatch;
+use codex_execpolicy::execpolicycheck::load_policies;
+use codex_protocol::models::SandboxPermissions;
+use codex_shell_command::parse_command::shlex_join;
+use codex_utils_cli::ApprovalModeCliArg;
+use codex_utils_cli::CliConfigOverrides;
+use codex_utils_cli::SandboxModeCliArg;
+use serde::Serialize;
+
+/// Explain how execpolicy rules apply to every command in a shell script.
+#[derive(Debug, Parser, Clone)]
+pub struct ExecPolicyExplainCommand {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Paths to execpolicy rule files to evaluate (repeatable). Defaults to
+    /// the rules Codex loads from your configuration.
+    #[arg(short = 'r', long = "rules", value_name = "PATH")]
+    pub rules: Vec<PathBuf>,
+
+    /// Print the explanation as JSON instead of text.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Pretty-print the JSON output (implies `--json`).
+    #[arg(long)]
+    pub pretty: bool,
+
+    /// Approval policy assumed for commands no rule matches.
+    #[arg(long = "approval-policy", value_enum, default_value = "on-request")]
+    pub approval_policy: ApprovalModeCliArg,
+
+    /// Sandbox assumed for commands no rule matches.
+    #[arg(long = "sandbox", value_enum, default_value = "workspace-write")]
+    pub sandbox: SandboxModeCliArg,
+
+    /// Shell script to explain, e.g. `"git status && git push origin main"`.
+    #[arg(value_name = "SCRIPT", required = true)]
+    pub script: String,
+}
+
+impl ExecPolicyExplainCommand {
+    pub async fn run(self) -> Result<()> {
+        let policy = if self.rules.is_empty() {
+            self.load_configured_policy().await?
+        } else {
+            load_policies(&self.rules)?
+        };
+        let approval_policy = AskForApproval::from(self.approval_policy);
+        let sandbox_policy = match self.sandbox {
+            SandboxModeCliArg::ReadOnly => SandboxPolicy::new_read_only_policy(),
+            SandboxModeCliArg::WorkspaceWrite => SandboxPolicy::new_workspace_write_policy(),
+            SandboxModeCliArg::DangerFullAccess => SandboxPolicy::DangerFullAccess,
+        };
+        let heuristics = |command: &[String]| {
+            render_decision_for_unmatched_command(
+                approval_policy,
+                &sandbox_policy,
+                command,
+                SandboxPermissions::UseDefault,
+            )
+        };
+        let explanation = explain_script(&policy, &self.script, &heuristics);
+
+        if self.pretty {
+            println!("{}", serde_json::to_string_pretty(&explanation)?);
+        } else if self.json {
+            println!("{}", serde_json::to_string(&explanation)?);
+        } else {
+            print!("{}", explanation.render_text());
+        }
+
+        Ok(())
+    }
+
+    async fn load_configured_policy(&self) -> Result<Policy> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        load_exec_policy(&config.config_layer_stack)
+            .await
+            .map_err(|err| anyhow::Error::msg(format_exec_policy_error_with_source(&err)))
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptExplanation {
+    pub commands: Vec<CommandExplanation>,
+    pub decision: Decision,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandExplanation {
+    pub command: Vec<String>,
+    pub matched_rules: Vec<RuleMatch>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winning_rule: Option<RuleMatch>,
+    pub decision: Decision,
+}
+
+/// Splits `script` the same way Codex does before consulting execpolicy and
+/// evaluates each resulting command independently. Commands no rule matches
+/// are decided by `heuristics_fallback`, exactly as [`Policy::check`] does
+/// when Codex runs them.
+pub fn explain_script<F>(
+    policy: &Policy,
+    script: &str,
+    heuristics_fallback: &F,
+) -> ScriptExplanation
+where
+    F: Fn(&[String]) -> Decision,
+{
+    let command = vec!["bash".to_string(), "-lc".to_string(), script.to_string()];
+    let (commands, _) = commands_for_exec_policy(&command);
+    let decision = policy
+        .check_multiple(commands.iter(), heuristics_fallback)
+        .decision;
+    let commands = commands
+        .into_iter()
+        .map(|command| explain_command(policy, command, heuristics_fallback))
+        .collect();
+
+    ScriptExplanation { commands, decision }
+}
+
+fn explain_command<F>(
+    policy: &Policy,
+    command: Vec<String>,
+    heuristics_fallback: &F,
+) -> CommandExplanation
+where
+    F: Fn(&[String]) -> Decision,
+{
+    let evaluation = policy.check(&command, heuristics_fallback);
+    let winning_rule = winning_rule(&evaluation.matched_rules).cloned();
+
+    CommandExplanation {
+        command,
+        matched_rules: evaluation.matched_rules,
+        winning_rule,
+        decision: evaluation.decision,
+    }
+}
+
+/// The strictest decision wins; among rules with that decision the most
+/// specific one is reported, preferring the earliest on ties.
+fn winning_rule(matched_rules: &[RuleMatch]) -> Option<&RuleMatch> {
+    matched_rules
+        .iter()
+        .fold(None, |best, candidate| match best {
+            Some(best) if rank(best) >= rank(candidate) => Some(best),
+            _ => Some(candidate),
+        })
+}
+
+fn rank(rule_match: &RuleMatch) -> (Decision, usize) {
+    let specificity = match rule_match {
+        RuleMatch::PrefixRuleMatch { matched_prefix, .. } => matched_prefix.len(),
+        RuleMatch::ArgumentRuleMatch { .. } => usize::MAX,
+        RuleMatch::HeuristicsRuleMatch { .. } => 0,
+    };
+    (rule_match.decision(), specificity)
+}
+
+impl ScriptExplanation {
+    fn render_text(&self) -> String {
+        let mut out = String::new();
+        for (index, command) in self.commands.iter().enumerate() {
+            out.push_str(&format!(
+                "[{}] {}\n",
+                index + 1,
+                shlex_join(&command.command)
+            ));
+            for rule_match in &command.matched_rules {
+                let marker = if command.winning_rule.as_ref() == Some(rule_match) {
+                    "*"
+                } else {
+                    "-"
+                };
+                out.push_str(&format!("    {marker} {}\n", describe_rule(rule_match)));
+            }
+            out.push_str(&format!(
+                "    decision: {}\n",
+                decision_label(command.decision)
+            ));
+        }
+
+        out.push_str(&format!("decision: {}\n", decision_label(self.decision)));
+        out
+    }
+}
+
+fn describe_rule(rule_match: &RuleMatch) -> String {
+    let (rule, justification) = match rule_match {
+        RuleMatch::PrefixRuleMatch {
+            matched_prefix,
+            justification,
+            ..
+        } => (
+            format!("prefix_rule `{}`", shlex_join(matched_prefix)),
+            justification,
+        ),
+        RuleMatch::ArgumentRuleMatch {
+            program,
+            matched_argument,
+            justification,
+            ..
+        } => (
+            format!("argument_rule `{program}` argument `{matched_argument}`"),
+            justification,
+        ),
+        RuleMatch::HeuristicsRuleMatch { .. } => {
+            ("no matching rule; heuristics".to_string(), &None)
+        }
+    };
+    let decision = decision_label(rule_match.decision());
+    match justification {
+        Some(justification) => format!("{rule} -> {decision} ({justification})"),
+        None => format!("{rule} -> {decision}"),
+    }
+}
+
+fn decision_label(decision: Decision) -> &'static str {
+    match decision {
+        Decision::Allow => "allow",
+        Decision::Prompt => "prompt",
+        Decision::Forbidden => "forbidden",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_execpolicy::PolicyParser;
+    use pretty_assertions::assert_eq;
+
+    fn policy(source: &str) -> Policy {
+        let mut parser = PolicyParser::new();
+        parser
+            .parse("test.rules", source)
+            .expect("policy should parse");
+        parser.build()
+    }
+
+    #[test]
+    fn splits_script_and_aggregates_strictest_decision() {
+        let policy = policy(
+            r#"
+prefix_rule(pattern = ["git"], decision = "prompt")
+prefix_rule(pattern = ["git", "status"], decision = "allow")
+prefix_rule(pattern = ["git", "push"], decision = "forbidden", justification = "push from CI")
+"#,
+        );
+
+        let explanation =
+            explain_script(&policy, "git status | cat && git push origin main", &|_| {
+                Decision::Allow
+            });
+
+        let push_rule = RuleMatch::PrefixRuleMatch {
+            matched_prefix: vec!["git".to_string(), "push".to_string()],
+            decision: Decision::Forbidden,
+            justification: Some("push from CI".to_string()),
+        };
+        let git_rule = RuleMatch::PrefixRuleMatch {
+            matched_prefix: vec!["git".to_string()],
+            decision: Decision::Prompt,
+            justification: None,
+        };
+        let status_rule = RuleMatch::PrefixRuleMatch {
+            matched_prefix: vec!["git".to_string(), "status".to_string()],
+            decision: Decision::Allow,
+            justification: None,
+        };
+        let cat_heuristics = RuleMatch::HeuristicsRuleMatch {
+            command: vec!["cat".to_string()],
+            decision: Decision::Allow,
+        };
+        assert_eq!(
+            explanation,
+            ScriptExplanation {
+                commands: vec![
+                    CommandExplanation {
+                        command: vec!["git".to_string(), "status".to_string()],
+                        matched_rules: vec![git_rule.clone(), status_rule],
+                        winning_rule: Some(git_rule.clone()),
+                        decision: Decision::Prompt,
+                    },
+                    CommandExplanation {
+                        command: vec!["cat".to_string()],
+                        matched_rules: vec![cat_heuristics.clone()],
+                        winning_rule: Some(cat_heuristics),
+                        decision: Decision::Allow,
+                    },
+                    CommandExplanation {
+                        command: vec![
+                            "git".to_string(),
+                            "push".to_string(),
+                            "origin".to_string(),
+                            "main".to_string(),
+                        ],
+                        matched_rules: vec![git_rule, push_rule.clone()],
+                        winning_rule: Some(push_rule),
+                        decision: Decision::Forbidden,
+                    },
+                ],
+                decision: Decision::Forbidden,
+            }
+        );
+    }
+
+    #[test]
+    fn winning_rule_prefers_most_specific_match_for_same_decision() {
+        let policy = policy(
+            r#"
+prefix_rule(pattern = ["cargo"], decision = "allow")
+prefix_rule(pattern = ["cargo", "test"], decision = "allow")
+"#,
+        );
+
+        let explanation = explain_script(&policy, "cargo test -p codex-cli", &|_| Decision::Allow);
+
+        assert_eq!(
+            explanation.commands[0].winning_rule,
+            Some(RuleMatch::PrefixRuleMatch {
+                matched_prefix: vec!["cargo".to_string(), "test".to_string()],
+                decision: Decision::Allow,
+                justification: None,
+            })
+        );
+    }
+
+    #[test]
+    fn unsplittable_script_is_evaluated_as_a_single_command() {
+        let policy = policy(r#"prefix_rule(pattern = ["bash"], decision = "prompt")"#);
+
+        let explanation = explain_script(&policy, "echo $(whoami) > out.txt", &|_| Decision::Allow);
+
+        assert_eq!(
+            explanation.commands[0].command,
+            vec![
+                "bash".to_string(),
+                "-lc".to_string(),
+                "echo $(whoami) > out.txt".to_string(),
+            ]
+        );
+        assert_eq!(explanation.decision, Decision::Prompt);
+    }
+
+    #[test]
+    fn heredoc_script_is_evaluated_by_its_leading_command() {
+        let policy = policy(r#"prefix_rule(pattern = ["python3"], decision = "allow")"#);
+
+        let explanation = explain_script(&policy, "python3 <<'PY'\nprint('hello')\nPY", &|_| {
+            Decision::Prompt
+        });
+
+        assert_eq!(explanation.commands[0].command, vec!["python3".to_string()]);
+        assert_eq!(explanation.decision, Decision::Allow);
+    }
+
+    #[test]
+    fn text_output_marks_winning_rule() {
+        let policy = policy(
+            r#"
+prefix_rule(pattern = ["git"], decision = "prompt")
+prefix_rule(pattern = ["git", "push"], decision = "forbidden", justification = "push from CI")
+"#,
+        );
+
+        let text = explain_script(&policy, "ls && git push", &|_| Decision::Allow).render_text();
+
+        assert_eq!(
+            text,
+            "[1] ls\n    * no matching rule; heuristics -> allow\n    decision: allow\n\
+             [2] git push\n    - prefix_rule `git` -> prompt\n    \
+             * prefix_rule `git push` -> forbidden (push from CI)\n    decision: forbidden\n\
+             decision: forbidden\n"
+        );
+    }
+
+    #[test]
+    fn unmatched_commands_use_heuristics_fallback() {
+        let policy = policy(r#"prefix_rule(pattern = ["git", "status"], decision = "allow")"#);
+        let heuristics = |command: &[String]| {
+            if command.first().map(String::as_str) == Some("rm") {
+                Decision::Prompt
+            } else {
+                Decision::Allow
+            }
+        };
+
+        let explanation = explain_script(&policy, "git status && rm -rf build", &heuristics);
+
+        let rm_heuristics = RuleMatch::HeuristicsRuleMatch {
+            command: vec!["rm".to_string(), "-rf".to_string(), "build".to_string()],
+            decision: Decision::Prompt,
+        };
+        assert_eq!(
+            explanation.commands[1],
+            CommandExplanation {
+                command: vec!["rm".to_string(), "-rf".to_string(), "build".to_string()],
+                matched_rules: vec![rm_heuristics.clone()],
+                winning_rule: Some(rm_heuristics),
+                decision: Decision::Prompt,
+            }
+        );
+        assert_eq!(explanation.decision, Decision::Prompt);
+    }
+}
@@ -0,0 +1,128 @@
+mod render;
+mod transcript;
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_core::INTERACTIVE_SESSION_SOURCES;
+use codex_core::RolloutRecorder;
+use codex_core::ThreadSortKey;
+use codex_core::config::Config;
+use codex_core::find_archived_thread_path_by_id_str;
+use codex_core::find_thread_path_by_id_str;
+use codex_core::find_thread_path_by_name_str;
+use codex_utils_cli::CliConfigOverrides;
+
+use self::transcript::Transcript;
+
+/// Export a recorded session as a Markdown, HTML or JSON transcript.
+///
+/// The transcript shows user and assistant messages, commands with truncated
+/// output, patches as diffs, plan updates and review findings.
+#[derive(Debug, clap::Parser)]
+pub struct ExportCommand {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Session id (UUID), thread name or path to a rollout file.
+    #[arg(value_name = "SESSION_ID", required_unless_present = "last")]
+    pub session_id: Option<String>,
+
+    /// Export the most recent session.
+    #[arg(long = "last", default_value_t = false, conflicts_with = "session_id")]
+    pub last: bool,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = ExportFormat::Md)]
+    pub format: ExportFormat,
+
+    /// Write the transcript to this file instead of stdout.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Replace likely secrets (API keys, tokens, passwords) with placeholders.
+    #[arg(long, default_value_t = false)]
+    pub redact: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    Md,
+    Html,
+    Json,
+}
+
+impl ExportCommand {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+
+        let path = match self.session_id.as_deref() {
+            Some(session) => find_session_path(&config, session).await?,
+            None => find_latest_session_path(&config).await?,
+        };
+        let history = RolloutRecorder::get_rollout_history(&path)
+            .await
+            .with_context(|| format!("failed to read session {}", path.display()))?;
+
+        let mut transcript = Transcript::from_rollout_items(&history.get_rollout_items());
+        if self.redact {
+            transcript.redact();
+        }
+        let rendered = match self.format {
+            ExportFormat::Md => render::render_markdown(&transcript),
+            ExportFormat::Html => render::render_html(&transcript),
+            ExportFormat::Json => format!("{}\n", serde_json::to_string_pretty(&transcript)?),
+        };
+
+        match self.output {
+            Some(output) => tokio::fs::write(&output, rendered)
+                .await
+                .with_context(|| format!("failed to write {}", output.display()))?,
+            None => print!("{rendered}"),
+        }
+        Ok(())
+    }
+}
+
+async fn find_session_path(config: &Config, session: &str) -> Result<PathBuf> {
+    let codex_home = config.codex_home.as_path();
+    if let Some(path) = find_thread_path_by_id_str(codex_home, session).await? {
+        return Ok(path);
+    }
+    if let Some(path) = find_archived_thread_path_by_id_str(codex_home, session).await? {
+        return Ok(path);
+    }
+    if let Some(path) = find_thread_path_by_name_str(codex_home, session).await? {
+        return Ok(path);
+    }
+    let path = Path::new(session);
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+    bail!("no saved session found with ID {session}")
+}
+
+async fn find_latest_session_path(config: &Config) -> Result<PathBuf> {
+    let provider_filter = vec![config.model_provider_id.clone()];
+    RolloutRecorder::find_latest_thread_path(
+        config,
+        1,
+        None,
+        ThreadSortKey::UpdatedAt,
+        INTERACTIVE_SESSION_SOURCES,
+        Some(provider_filter.as_slice()),
+        &config.model_provider_id,
+        None,
+    )
+    .await?
+    .context("no saved sessions found")
+}
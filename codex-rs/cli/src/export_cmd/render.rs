@@ -0,0 +1,391 @@
+use std::fmt::Write as _;
+
+use super::transcript::PlanStep;
+use super::transcript::ReviewFindingEntry;
+use super::transcript::Transcript;
+use super::transcript::TranscriptEntry;
+
+pub(crate) fn render_markdown(transcript: &Transcript) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}\n", title(transcript));
+    for line in header_lines(transcript) {
+        let _ = writeln!(out, "- {line}");
+    }
+    for entry in &transcript.entries {
+        out.push('\n');
+        match entry {
+            TranscriptEntry::UserMessage { text } => {
+                let _ = writeln!(out, "## User\n\n{text}");
+            }
+            TranscriptEntry::AgentMessage { text } => {
+                let _ = writeln!(out, "## Codex\n\n{text}");
+            }
+            TranscriptEntry::Exec {
+                command,
+                exit_code,
+                output,
+                ..
+            } => {
+                let _ = writeln!(out, "**Ran**{}\n", exit_suffix(*exit_code));
+                out.push_str(&fenced("shell", command));
+                if !output.is_empty() {
+                    out.push('\n');
+                    out.push_str(&fenced("text", output));
+                }
+            }
+            TranscriptEntry::Patch { files, diff } => {
+                let _ = writeln!(out, "**Edited** {}\n", file_list(files, "`"));
+                out.push_str(&fenced("diff", diff));
+            }
+            TranscriptEntry::PlanUpdate { explanation, steps } => {
+                out.push_str("**Updated plan**\n\n");
+                if let Some(explanation) = explanation {
+                    let _ = writeln!(out, "{explanation}\n");
+                }
+                for step in steps {
+                    let _ = writeln!(out, "- [{}] {}", checkbox(step), step.step);
+                }
+            }
+            TranscriptEntry::ProposedPlan { text } => {
+                let _ = writeln!(out, "## Proposed plan\n\n{text}");
+            }
+            TranscriptEntry::Review {
+                overall_correctness,
+                overall_explanation,
+                findings,
+            } => {
+                let _ = writeln!(out, "## Review: {overall_correctness}\n");
+                if !overall_explanation.is_empty() {
+                    let _ = writeln!(out, "{overall_explanation}\n");
+                }
+                for finding in findings {
+                    let _ = writeln!(
+                        out,
+                        "- **[P{}] {}** `{}`",
+                        finding.priority,
+                        finding.title,
+                        location(finding)
+                    );
+                    for line in finding.body.lines() {
+                        let _ = writeln!(out, "  {line}");
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+pub(crate) fn render_html(transcript: &Transcript) -> String {
+    let mut out = String::new();
+    let title = escape(&title(transcript));
+    let _ = writeln!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n<ul class=\"meta\">"
+    );
+    for line in header_lines(transcript) {
+        let _ = writeln!(out, "<li>{}</li>", escape(&line));
+    }
+    out.push_str("</ul>\n");
+    for entry in &transcript.entries {
+        match entry {
+            TranscriptEntry::UserMessage { text } => {
+                let _ = writeln!(
+                    out,
+                    "<section class=\"user\"><h2>User</h2><div class=\"text\">{}</div></section>",
+                    escape(text)
+                );
+            }
+            TranscriptEntry::AgentMessage { text } => {
+                let _ = writeln!(
+                    out,
+                    "<section class=\"agent\"><h2>Codex</h2><div class=\"text\">{}</div></section>",
+                    escape(text)
+                );
+            }
+            TranscriptEntry::Exec {
+                command,
+                exit_code,
+                output,
+                ..
+            } => {
+                let failed = exit_code.is_some_and(|code| code != 0);
+                let _ = write!(
+                    out,
+                    "<section class=\"exec{}\"><h3>Ran{}</h3><pre class=\"command\">$ {}</pre>",
+                    if failed { " failed" } else { "" },
+                    escape(&exit_suffix(*exit_code)),
+                    escape(command)
+                );
+                if !output.is_empty() {
+                    let _ = write!(out, "<pre class=\"output\">{}</pre>", escape(output));
+                }
+                out.push_str("</section>\n");
+            }
+            TranscriptEntry::Patch { files, diff } => {
+                let _ = write!(
+                    out,
+                    "<section class=\"patch\"><h3>Edited {}</h3><pre class=\"diff\">",
+                    escape(&file_list(files, ""))
+                );
+                for line in diff.lines() {
+                    let class = match line.chars().next() {
+                        Some('+') if !line.starts_with("+++") => "add",
+                        Some('-') if !line.starts_with("---") => "del",
+                        Some('@') => "hunk",
+                        _ => "ctx",
+                    };
+                    let _ = writeln!(out, "<span class=\"{class}\">{}</span>", escape(line));
+                }
+                out.push_str("</pre></section>\n");
+            }
+            TranscriptEntry::PlanUpdate { explanation, steps } => {
+                out.push_str("<section class=\"plan\"><h3>Updated plan</h3>");
+                if let Some(explanation) = explanation {
+                    let _ = write!(out, "<p>{}</p>", escape(explanation));
+                }
+                out.push_str("<ul>");
+                for step in steps {
+                    let _ = write!(
+                        out,
+                        "<li class=\"{}\">[{}] {}</li>",
+                        step.status,
+                        checkbox(step),
+                        escape(&step.step)
+                    );
+                }
+                out.push_str("</ul></section>\n");
+            }
+            TranscriptEntry::ProposedPlan { text } => {
+                let _ = writeln!(
+                    out,
+                    "<section class=\"plan\"><h2>Proposed plan</h2><div class=\"text\">{}</div></section>",
+                    escape(text)
+                );
+            }
+            TranscriptEntry::Review {
+                overall_correctness,
+                overall_explanation,
+                findings,
+            } => {
+                let _ = write!(
+                    out,
+                    "<section class=\"review\"><h2>Review: {}</h2>",
+                    escape(overall_correctness)
+                );
+                if !overall_explanation.is_empty() {
+                    let _ = write!(out, "<p>{}</p>", escape(overall_explanation));
+                }
+                out.push_str("<ul>");
+                for finding in findings {
+                    let _ = write!(
+                        out,
+                        "<li><strong>[P{}] {}</strong> <code>{}</code><div class=\"text\">{}</div></li>",
+                        finding.priority,
+                        escape(&finding.title),
+                        escape(&location(finding)),
+                        escape(&finding.body)
+                    );
+                }
+                out.push_str("</ul></section>\n");
+            }
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem;color:#1f2328}\
+section{margin:1rem 0}\
+.user{border-left:4px solid #0969da;padding-left:.75rem}\
+.agent{border-left:4px solid #8250df;padding-left:.75rem}\
+.text{white-space:pre-wrap}\
+pre{background:#f6f8fa;padding:.5rem;overflow-x:auto}\
+.failed h3{color:#cf222e}\
+.diff span{display:block}\
+.add{background:#dafbe1}.del{background:#ffebe9}.hunk{color:#0969da}\
+.completed{text-decoration:line-through}";
+
+fn title(transcript: &Transcript) -> String {
+    match transcript.thread_id.as_deref() {
+        Some(thread_id) => format!("Codex session {thread_id}"),
+        None => "Codex session".to_string(),
+    }
+}
+
+fn header_lines(transcript: &Transcript) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(created_at) = transcript.created_at.as_deref() {
+        lines.push(format!("Started: {created_at}"));
+    }
+    if let Some(cwd) = transcript.cwd.as_deref() {
+        lines.push(format!("Directory: {}", cwd.display()));
+    }
+    if let Some(model) = transcript.model.as_deref() {
+        lines.push(format!("Model: {model}"));
+    }
+    lines
+}
+
+fn exit_suffix(exit_code: Option<i32>) -> String {
+    match exit_code {
+        Some(code) => format!(" (exit code {code})"),
+        None => String::new(),
+    }
+}
+
+fn file_list(files: &[std::path::PathBuf], quote: &str) -> String {
+    files
+        .iter()
+        .map(|path| format!("{quote}{}{quote}", path.display()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn checkbox(step: &PlanStep) -> &'static str {
+    match step.status {
+        "completed" => "x",
+        "in_progress" => "~",
+        _ => " ",
+    }
+}
+
+fn location(finding: &ReviewFindingEntry) -> String {
+    format!(
+        "{}:{}-{}",
+        finding.path.display(),
+        finding.start_line,
+        finding.end_line
+    )
+}
+
+/// Fence `content` with more backticks than any run it contains.
+fn fenced(info: &str, content: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for ch in content.chars() {
+        if ch == '`' {
+            run += 1;
+            longest = longest.max(run);
+        } else {
+            run = 0;
+        }
+    }
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!(
+        "{fence}{info}\n{}\n{fence}\n",
+        content.trim_end_matches('\n')
+    )
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn transcript() -> Transcript {
+        Transcript {
+            thread_id: Some("thread-1".to_string()),
+            created_at: Some("2025-01-01T00:00:00Z".to_string()),
+            cwd: Some(PathBuf::from("/repo")),
+            model: Some("gpt-5".to_string()),
+            entries: vec![
+                TranscriptEntry::UserMessage {
+                    text: "fix <main>".to_string(),
+                },
+                TranscriptEntry::Exec {
+                    command: "cargo test".to_string(),
+                    exit_code: Some(1),
+                    output: "error: ```boom```".to_string(),
+                    omitted_lines: 0,
+                },
+                TranscriptEntry::PlanUpdate {
+                    explanation: None,
+                    steps: vec![
+                        PlanStep {
+                            step: "reproduce".to_string(),
+                            status: "completed",
+                        },
+                        PlanStep {
+                            step: "fix".to_string(),
+                            status: "pending",
+                        },
+                    ],
+                },
+                TranscriptEntry::Review {
+                    overall_correctness: "patch is incorrect".to_string(),
+                    overall_explanation: String::new(),
+                    findings: vec![ReviewFindingEntry {
+                        title: "Off by one".to_string(),
+                        body: "The loop skips the last item.".to_string(),
+                        priority: 1,
+                        path: PathBuf::from("/repo/src/lib.rs"),
+                        start_line: 3,
+                        end_line: 4,
+                    }],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn markdown_renders_entries_in_order() {
+        assert_eq!(
+            render_markdown(&transcript()),
+            "# Codex session thread-1
+
+- Started: 2025-01-01T00:00:00Z
+- Directory: /repo
+- Model: gpt-5
+
+## User
+
+fix <main>
+
+**Ran** (exit code 1)
+
+```shell
+cargo test
+```
+
+````text
+error: ```boom```
+````
+
+**Updated plan**
+
+- [x] reproduce
+- [ ] fix
+
+## Review: patch is incorrect
+
+- **[P1] Off by one** `/repo/src/lib.rs:3-4`
+  The loop skips the last item.
+"
+        );
+    }
+
+    #[test]
+    fn html_escapes_message_text() {
+        let html = render_html(&transcript());
+        assert!(html.contains("<div class=\"text\">fix &lt;main&gt;</div>"));
+        assert!(html.contains("<section class=\"exec failed\">"));
+        assert!(html.ends_with("</body>\n</html>\n"));
+    }
+}
@@ -40,11 +40,19 @@ const CODEX_CLI_VERSION: &str = match option_env!("CODEX_CLI_VERSION_OVERRIDE")
 mod app_cmd;
 #[cfg(target_os = "macos")]
 mod desktop_app;
+mod execpolicy_explain;
+mod export_cmd;
 mod mcp_cmd;
+mod network_log_cmd;
+mod secrets_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::execpolicy_explain::ExecPolicyExplainCommand;
+use crate::export_cmd::ExportCommand;
 use crate::mcp_cmd::McpCli;
+use crate::network_log_cmd::NetworkLogCommand;
+use crate::secrets_cmd::SecretsCli;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
@@ -104,6 +112,9 @@ enum Subcommand {
     /// Start Codex as an MCP server (stdio).
     McpServer,
 
+    /// Manage secrets that config can reference as `${secret:NAME}`.
+    Secrets(SecretsCli),
+
     /// [experimental] Run the app server or related tooling.
     AppServer(AppServerCommand),
 
@@ -134,6 +145,9 @@ enum Subcommand {
     /// Fork a previous interactive session (picker by default; use --last to fork the most recent).
     Fork(ForkCommand),
 
+    /// Export a recorded session as a Markdown, HTML or JSON transcript.
+    Export(ExportCommand),
+
     /// [EXPERIMENTAL] Browse tasks from Codex Cloud and apply changes locally.
     #[clap(name = "cloud", alias = "cloud-tasks")]
     Cloud(CloudTasksCli),
@@ -167,6 +181,9 @@ struct DebugCommand {
 enum DebugSubcommand {
     /// Tooling: helps debug the app server.
     AppServer(DebugAppServerCommand),
+
+    /// Print the network proxy request audit log.
+    NetworkLog(NetworkLogCommand),
 }
 
 #[derive(Debug, Parser)]
@@ -202,6 +219,11 @@ struct ResumeCommand {
     #[arg(long = "all", default_value_t = false)]
     all: bool,
 
+    /// Open the picker with a full-text search over past sessions for QUERY.
+    /// This is `--find` rather than `--search`, which already enables live web search.
+    #[arg(long = "find", value_name = "QUERY", conflicts_with_all = ["session_id", "last"])]
+    find: Option<String>,
+
     #[clap(flatten)]
     config_overrides: TuiCli,
 }
@@ -256,6 +278,10 @@ enum ExecpolicySubcommand {
     /// Check execpolicy files against a command.
     #[clap(name = "check")]
     Check(ExecPolicyCheckCommand),
+
+    /// Explain which rules apply to each command in a shell script.
+    #[clap(name = "explain")]
+    Explain(ExecPolicyExplainCommand),
 }
 
 #[derive(Debug, Parser)]
@@ -608,6 +634,20 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             prepend_config_flags(&mut mcp_cli.config_overrides, root_config_overrides.clone());
             mcp_cli.run().await?;
         }
+        Some(Subcommand::Export(mut export_cmd)) => {
+            prepend_config_flags(
+                &mut export_cmd.config_overrides,
+                root_config_overrides.clone(),
+            );
+            export_cmd.run().await?;
+        }
+        Some(Subcommand::Secrets(mut secrets_cli)) => {
+            prepend_config_flags(
+                &mut secrets_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            secrets_cli.run().await?;
+        }
         Some(Subcommand::AppServer(app_server_cli)) => match app_server_cli.subcommand {
             None => {
                 let transport = app_server_cli.listen;
@@ -646,6 +686,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             session_id,
             last,
             all,
+            find,
             config_overrides,
         })) => {
             interactive = finalize_resume_interactive(
@@ -654,6 +695,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 session_id,
                 last,
                 all,
+                find,
                 config_overrides,
             );
             let exit_info = run_interactive_tui(interactive, codex_linux_sandbox_exe).await?;
@@ -763,9 +805,17 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             DebugSubcommand::AppServer(cmd) => {
                 run_debug_app_server_command(cmd)?;
             }
+            DebugSubcommand::NetworkLog(mut cmd) => {
+                prepend_config_flags(&mut cmd.config_overrides, root_config_overrides.clone());
+                cmd.run().await?;
+            }
         },
         Some(Subcommand::Execpolicy(ExecpolicyCommand { sub })) => match sub {
             ExecpolicySubcommand::Check(cmd) => run_execpolicycheck(cmd)?,
+            ExecpolicySubcommand::Explain(mut cmd) => {
+                prepend_config_flags(&mut cmd.config_overrides, root_config_overrides.clone());
+                cmd.run().await?;
+            }
         },
         Some(Subcommand::Apply(mut apply_cli)) => {
             prepend_config_flags(
@@ -945,6 +995,7 @@ fn finalize_resume_interactive(
     session_id: Option<String>,
     last: bool,
     show_all: bool,
+    search: Option<String>,
     resume_cli: TuiCli,
 ) -> TuiCli {
     // Start with the parsed interactive CLI so resume shares the same
@@ -954,6 +1005,7 @@ fn finalize_resume_interactive(
     interactive.resume_last = last;
     interactive.resume_session_id = resume_session_id;
     interactive.resume_show_all = show_all;
+    interactive.resume_search = search;
 
     // Merge resume-scoped flags and overrides with highest precedence.
     merge_interactive_cli_flags(&mut interactive, resume_cli);
@@ -1050,6 +1102,10 @@ mod tests {
     use assert_matches::assert_matches;
     use codex_core::protocol::TokenUsage;
     use codex_protocol::ThreadId;
+    use codex_protocol::protocol::EventMsg;
+    use codex_protocol::protocol::RolloutItem;
+    use codex_protocol::protocol::SessionSource;
+    use codex_protocol::protocol::UserMessageEvent;
     use pretty_assertions::assert_eq;
 
     fn finalize_resume_from_args(args: &[&str]) -> TuiCli {
@@ -1065,6 +1121,7 @@ mod tests {
             session_id,
             last,
             all,
+            find,
             config_overrides: resume_cli,
         }) = subcommand.expect("resume present")
         else {
@@ -1077,6 +1134,7 @@ mod tests {
             session_id,
             last,
             all,
+            find,
             resume_cli,
         )
     }
@@ -1242,6 +1300,86 @@ mod tests {
         assert!(interactive.resume_show_all);
     }
 
+    #[test]
+    fn resume_find_flag_prefills_picker() {
+        let interactive =
+            finalize_resume_from_args(["codex", "resume", "--find", "flaky migration"].as_ref());
+        assert!(interactive.resume_picker);
+        assert_eq!(
+            interactive.resume_search.as_deref(),
+            Some("flaky migration")
+        );
+    }
+
+    #[tokio::test]
+    async fn resume_find_searches_past_threads() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let runtime = codex_state::StateRuntime::init(
+            codex_home.path().to_path_buf(),
+            "test-provider".to_string(),
+            None,
+        )
+        .await
+        .expect("initialize state db");
+        let mut thread_ids = Vec::new();
+        for message in ["fix the flaky migration", "update the readme"] {
+            let thread_id = ThreadId::new();
+            let rollout_path = codex_home.path().join(format!("rollout-{thread_id}.jsonl"));
+            std::fs::write(&rollout_path, "").expect("write rollout");
+            let mut builder = codex_state::ThreadMetadataBuilder::new(
+                thread_id,
+                rollout_path,
+                chrono::Utc::now(),
+                SessionSource::Cli,
+            );
+            builder.model_provider = Some("test-provider".to_string());
+            runtime
+                .upsert_thread(&builder.build("test-provider"))
+                .await
+                .expect("upsert thread");
+            let items = vec![RolloutItem::EventMsg(EventMsg::UserMessage(
+                UserMessageEvent {
+                    message: message.to_string(),
+                    images: None,
+                    local_images: Vec::new(),
+                    text_elements: Vec::new(),
+                },
+            ))];
+            runtime
+                .reindex_thread_search(thread_id, &items)
+                .await
+                .expect("index thread");
+            thread_ids.push(thread_id);
+        }
+
+        let interactive =
+            finalize_resume_from_args(["codex", "resume", "--find", "flaky migration"].as_ref());
+        let query = interactive.resume_search.expect("resume search");
+        let hits = codex_core::state_db::search_threads_db(
+            Some(runtime.as_ref()),
+            query.as_str(),
+            10,
+            codex_core::INTERACTIVE_SESSION_SOURCES,
+            Some(&["test-provider".to_string()]),
+            false,
+        )
+        .await
+        .expect("search threads");
+
+        assert_eq!(
+            hits.into_iter()
+                .map(|hit| hit.metadata.id)
+                .collect::<Vec<_>>(),
+            vec![thread_ids[0]]
+        );
+    }
+
+    #[test]
+    fn resume_find_conflicts_with_last() {
+        let result = MultitoolCli::try_parse_from(["codex", "resume", "--last", "--find", "flaky"]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn resume_merges_option_flags_and_full_auto() {
         let interactive = finalize_resume_from_args(
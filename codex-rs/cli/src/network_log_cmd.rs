@@ -0,0 +1,169 @@
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use chrono::DateTime;
+use codex_core::config::Config;
+use codex_core::state_db::NetworkRequestQuery;
+use codex_core::state_db::NetworkRequestRow;
+use codex_core::state_db::get_state_db;
+use codex_utils_cli::CliConfigOverrides;
+
+const DEFAULT_LIMIT: usize = 100;
+
+/// Print the managed network proxy audit log: every request the proxy allowed
+/// or denied, attributed to the thread and exec call that made it.
+///
+/// The log lives in the state database, so it is only recorded when the
+/// `sqlite` feature is enabled.
+#[derive(Debug, clap::Parser)]
+pub struct NetworkLogCommand {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Only show requests made by this thread.
+    #[arg(long = "thread-id", value_name = "THREAD_ID")]
+    pub thread_id: Option<String>,
+
+    /// Only show requests made by this exec call.
+    #[arg(long = "call-id", value_name = "CALL_ID")]
+    pub call_id: Option<String>,
+
+    /// Only show requests whose host contains this text.
+    #[arg(long, value_name = "HOST")]
+    pub host: Option<String>,
+
+    /// Only show requests with this decision.
+    #[arg(long, value_enum)]
+    pub decision: Option<DecisionFilter>,
+
+    /// Maximum number of requests to print (most recent first).
+    #[arg(long, default_value_t = DEFAULT_LIMIT)]
+    pub limit: usize,
+
+    /// Output the requests as JSON lines for export.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum DecisionFilter {
+    Allow,
+    Deny,
+    Ask,
+}
+
+impl DecisionFilter {
+    fn as_str(self) -> &'static str {
+        match self {
+            DecisionFilter::Allow => "allow",
+            DecisionFilter::Deny => "deny",
+            DecisionFilter::Ask => "ask",
+        }
+    }
+}
+
+impl NetworkLogCommand {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let Some(state_db) = get_state_db(&config, None).await else {
+            bail!(
+                "the network request log lives in the state database; enable the `sqlite` feature to record it"
+            );
+        };
+
+        let query = NetworkRequestQuery {
+            thread_ids: self.thread_id.into_iter().collect(),
+            call_id: self.call_id,
+            host_like: self.host,
+            decision: self.decision.map(|decision| decision.as_str().to_string()),
+            limit: Some(self.limit),
+            descending: true,
+            ..Default::default()
+        };
+        let mut rows = state_db.query_network_requests(&query).await?;
+        // Fetched newest first so `--limit` keeps the latest requests; print oldest first.
+        rows.reverse();
+
+        if self.json {
+            for row in &rows {
+                println!("{}", serde_json::to_string(row)?);
+            }
+            return Ok(());
+        }
+
+        if rows.is_empty() {
+            println!("No network requests recorded.");
+            return Ok(());
+        }
+        for row in &rows {
+            println!("{}", format_row(row));
+        }
+        Ok(())
+    }
+}
+
+fn format_row(row: &NetworkRequestRow) -> String {
+    let ts = DateTime::from_timestamp(row.ts, 0)
+        .map(|ts| ts.to_rfc3339())
+        .unwrap_or_else(|| row.ts.to_string());
+    let target = match row.port {
+        Some(port) => format!("{}:{port}", row.host),
+        None => row.host.clone(),
+    };
+    let bytes = match (row.request_bytes, row.response_bytes) {
+        (None, None) => "-".to_string(),
+        (request, response) => format!(
+            "{}/{}",
+            request.map_or_else(|| "?".to_string(), |bytes| bytes.to_string()),
+            response.map_or_else(|| "?".to_string(), |bytes| bytes.to_string()),
+        ),
+    };
+    let mut line = format!(
+        "{ts}\t{}\t{}\t{}\t{target}\t{bytes}\tthread={}\tcall={}",
+        row.decision,
+        row.protocol,
+        row.method.as_deref().unwrap_or("-"),
+        row.thread_id.as_deref().unwrap_or("-"),
+        row.call_id.as_deref().unwrap_or("-"),
+    );
+    if let Some(reason) = row.reason.as_deref() {
+        line.push_str(&format!("\treason={reason}"));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn format_row_includes_attribution_and_bytes() {
+        let row = NetworkRequestRow {
+            id: 1,
+            ts: 0,
+            thread_id: Some("thread-1".to_string()),
+            call_id: Some("call-1".to_string()),
+            host: "example.com".to_string(),
+            port: Some(443),
+            protocol: "https".to_string(),
+            method: Some("POST".to_string()),
+            decision: "deny".to_string(),
+            reason: Some("denied_by_rule".to_string()),
+            source: Some("baseline_policy".to_string()),
+            request_bytes: Some(12),
+            response_bytes: None,
+        };
+
+        assert_eq!(
+            format_row(&row),
+            "1970-01-01T00:00:00+00:00\tdeny\thttps\tPOST\texample.com:443\t12/?\tthread=thread-1\tcall=call-1\treason=denied_by_rule"
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use std::io::Read;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_core::config::Config;
+use codex_secrets::SecretName;
+use codex_secrets::SecretScope;
+use codex_secrets::SecretsBackendKind;
+use codex_secrets::SecretsManager;
+use codex_utils_cli::CliConfigOverrides;
+use serde_json::json;
+
+/// Subcommands:
+/// - `set`    — store a secret (value from `--value` or stdin)
+/// - `get`    — print a secret's value
+/// - `list`   — list stored secret names (with `--json`)
+/// - `delete` — remove a secret
+///
+/// Stored secrets can be referenced as `${secret:NAME}` from
+/// `shell_environment_policy.set` and MCP server `env`/`http_headers`.
+#[derive(Debug, clap::Parser)]
+pub struct SecretsCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: SecretsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SecretsSubcommand {
+    Set(SetArgs),
+    Get(GetArgs),
+    List(ListArgs),
+    Delete(DeleteArgs),
+}
+
+#[derive(Debug, clap::Args)]
+pub struct ScopeArgs {
+    /// Store the secret for one environment (a repository name, as shown by
+    /// `codex secrets list`) instead of globally. Environment secrets take
+    /// precedence over global ones with the same name.
+    #[arg(long = "env", value_name = "ENV_ID")]
+    pub environment: Option<String>,
+}
+
+impl ScopeArgs {
+    fn scope(&self) -> Result<SecretScope> {
+        match &self.environment {
+            Some(environment) => SecretScope::environment(environment.as_str()),
+            None => Ok(SecretScope::Global),
+        }
+    }
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SetArgs {
+    /// Secret name (A-Z, 0-9 and `_`).
+    pub name: String,
+
+    /// Secret value. Read from stdin when omitted so it stays out of shell history.
+    #[arg(long)]
+    pub value: Option<String>,
+
+    #[command(flatten)]
+    pub scope: ScopeArgs,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct GetArgs {
+    /// Secret name.
+    pub name: String,
+
+    #[command(flatten)]
+    pub scope: ScopeArgs,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ListArgs {
+    /// Only list secrets for this environment.
+    #[arg(long = "env", value_name = "ENV_ID")]
+    pub environment: Option<String>,
+
+    /// Output the secret names as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct DeleteArgs {
+    /// Secret name.
+    pub name: String,
+
+    #[command(flatten)]
+    pub scope: ScopeArgs,
+}
+
+impl SecretsCli {
+    pub async fn run(self) -> Result<()> {
+        let SecretsCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let manager = SecretsManager::new(config.codex_home, SecretsBackendKind::Local);
+
+        match subcommand {
+            SecretsSubcommand::Set(args) => run_set(&manager, args),
+            SecretsSubcommand::Get(args) => run_get(&manager, args),
+            SecretsSubcommand::List(args) => run_list(&manager, args),
+            SecretsSubcommand::Delete(args) => run_delete(&manager, args),
+        }
+    }
+}
+
+fn run_set(manager: &SecretsManager, args: SetArgs) -> Result<()> {
+    let name = SecretName::new(&args.name)?;
+    let scope = args.scope.scope()?;
+    let value = match args.value {
+        Some(value) => value,
+        None => {
+            let mut value = String::new();
+            std::io::stdin()
+                .read_to_string(&mut value)
+                .context("failed to read secret value from stdin")?;
+            value.trim_end_matches(['\r', '\n']).to_string()
+        }
+    };
+    if value.is_empty() {
+        bail!("secret value must not be empty");
+    }
+
+    manager.set(&scope, &name, &value)?;
+    println!("Stored secret '{name}' ({}).", describe_scope(&scope));
+    Ok(())
+}
+
+fn run_get(manager: &SecretsManager, args: GetArgs) -> Result<()> {
+    let name = SecretName::new(&args.name)?;
+    let scope = args.scope.scope()?;
+    let Some(value) = manager.get(&scope, &name)? else {
+        bail!("No secret named '{name}' ({}).", describe_scope(&scope));
+    };
+    println!("{value}");
+    Ok(())
+}
+
+fn run_list(manager: &SecretsManager, args: ListArgs) -> Result<()> {
+    let scope_filter = args.environment.map(SecretScope::environment).transpose()?;
+    let mut entries = manager.list(scope_filter.as_ref())?;
+    entries.sort_by(|a, b| {
+        (describe_scope(&a.scope), &a.name).cmp(&(describe_scope(&b.scope), &b.name))
+    });
+
+    if args.json {
+        let json_entries: Vec<_> = entries
+            .iter()
+            .map(|entry| {
+                json!({
+                    "name": entry.name.as_str(),
+                    "scope": describe_scope(&entry.scope),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json_entries)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("No secrets stored yet. Try `codex secrets set NAME`.");
+        return Ok(());
+    }
+    for entry in entries {
+        println!("{}\t{}", describe_scope(&entry.scope), entry.name);
+    }
+    Ok(())
+}
+
+fn run_delete(manager: &SecretsManager, args: DeleteArgs) -> Result<()> {
+    let name = SecretName::new(&args.name)?;
+    let scope = args.scope.scope()?;
+    if manager.delete(&scope, &name)? {
+        println!("Deleted secret '{name}' ({}).", describe_scope(&scope));
+    } else {
+        println!("No secret named '{name}' ({}).", describe_scope(&scope));
+    }
+    Ok(())
+}
+
+fn describe_scope(scope: &SecretScope) -> String {
+    match scope {
+        SecretScope::Global => "global".to_string(),
+        SecretScope::Environment(environment_id) => format!("env:{environment_id}"),
+    }
+}
@@ -117,3 +117,81 @@ prefix_rule(
 
     Ok(())
 }
+
+#[test]
+fn execpolicy_explain_reports_each_command() -> Result<(), Box<dyn std::error::Error>> {
+    let codex_home = TempDir::new()?;
+    let policy_path = codex_home.path().join("policy.rules");
+    fs::write(
+        &policy_path,
+        r#"
+prefix_rule(
+    pattern = ["git", "push"],
+    decision = "forbidden",
+)
+"#,
+    )?;
+
+    let output = Command::new(codex_utils_cargo_bin::cargo_bin("codex")?)
+        .env("CODEX_HOME", codex_home.path())
+        .args([
+            "execpolicy",
+            "explain",
+            "--json",
+            "--rules",
+            policy_path
+                .to_str()
+                .expect("policy path should be valid UTF-8"),
+            "git status && git push origin main",
+        ])
+        .output()?;
+
+    assert!(output.status.success());
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        result,
+        json!({
+            "commands": [
+                {
+                    "command": ["git", "status"],
+                    "matchedRules": [
+                        {
+                            "heuristicsRuleMatch": {
+                                "command": ["git", "status"],
+                                "decision": "allow"
+                            }
+                        }
+                    ],
+                    "winningRule": {
+                        "heuristicsRuleMatch": {
+                            "command": ["git", "status"],
+                            "decision": "allow"
+                        }
+                    },
+                    "decision": "allow"
+                },
+                {
+                    "command": ["git", "push", "origin", "main"],
+                    "matchedRules": [
+                        {
+                            "prefixRuleMatch": {
+                                "matchedPrefix": ["git", "push"],
+                                "decision": "forbidden"
+                            }
+                        }
+                    ],
+                    "winningRule": {
+                        "prefixRuleMatch": {
+                            "matchedPrefix": ["git", "push"],
+                            "decision": "forbidden"
+                        }
+                    },
+                    "decision": "forbidden"
+                }
+            ],
+            "decision": "forbidden"
+        })
+    );
+
+    Ok(())
+}
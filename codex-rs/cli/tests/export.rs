@@ -0,0 +1,131 @@
+use std::fs;
+
+use assert_cmd::Command;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use tempfile::TempDir;
+
+const THREAD_ID: &str = "019a0000-0000-7000-8000-000000000001";
+
+fn write_rollout(dir: &TempDir) -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
+    let timestamp = "2025-01-01T00:00:00.000Z";
+    let lines = [
+        json!({
+            "timestamp": timestamp,
+            "type": "session_meta",
+            "payload": {
+                "id": THREAD_ID,
+                "timestamp": timestamp,
+                "cwd": "/repo",
+                "originator": "codex_cli_rs",
+                "cli_version": "0.0.0",
+                "model_provider": "openai",
+                "base_instructions": null,
+            },
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "event_msg",
+            "payload": {"type": "user_message", "message": "token=supersecretvalue please run ls"},
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "response_item",
+            "payload": {
+                "type": "function_call",
+                "name": "shell",
+                "arguments": "{\"command\":[\"bash\",\"-lc\",\"ls\"]}",
+                "call_id": "call-1",
+            },
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "response_item",
+            "payload": {
+                "type": "function_call_output",
+                "call_id": "call-1",
+                "output": "Exit code: 0\nWall time: 0.1 seconds\nOutput:\nREADME.md",
+            },
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "event_msg",
+            "payload": {"type": "agent_message", "message": "Done."},
+        }),
+    ];
+    let path = dir.path().join(format!("rollout-2025-01-01T00-00-00-{THREAD_ID}.jsonl"));
+    let contents = lines
+        .iter()
+        .map(Value::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    fs::write(&path, format!("{contents}\n"))?;
+    Ok(path)
+}
+
+#[test]
+fn export_writes_redacted_json_transcript() -> Result<(), Box<dyn std::error::Error>> {
+    let codex_home = TempDir::new()?;
+    let rollout = write_rollout(&codex_home)?;
+
+    let output = Command::new(codex_utils_cargo_bin::cargo_bin("codex")?)
+        .env("CODEX_HOME", codex_home.path())
+        .args([
+            "export",
+            rollout.to_str().expect("rollout path should be valid UTF-8"),
+            "--format",
+            "json",
+            "--redact",
+        ])
+        .output()?;
+
+    assert!(output.status.success(), "{output:?}");
+    let transcript: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        transcript,
+        json!({
+            "thread_id": THREAD_ID,
+            "created_at": "2025-01-01T00:00:00.000Z",
+            "cwd": "/repo",
+            "model": null,
+            "entries": [
+                {"type": "user_message", "text": "token=[REDACTED_SECRET] please run ls"},
+                {
+                    "type": "exec",
+                    "command": "ls",
+                    "exit_code": 0,
+                    "output": "README.md",
+                    "omitted_lines": 0,
+                },
+                {"type": "agent_message", "text": "Done."},
+            ],
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn export_writes_markdown_to_file() -> Result<(), Box<dyn std::error::Error>> {
+    let codex_home = TempDir::new()?;
+    let rollout = write_rollout(&codex_home)?;
+    let out_path = codex_home.path().join("transcript.md");
+
+    let output = Command::new(codex_utils_cargo_bin::cargo_bin("codex")?)
+        .env("CODEX_HOME", codex_home.path())
+        .args([
+            "export",
+            rollout.to_str().expect("rollout path should be valid UTF-8"),
+            "--output",
+            out_path.to_str().expect("output path should be valid UTF-8"),
+        ])
+        .output()?;
+
+    assert!(output.status.success(), "{output:?}");
+    let markdown = fs::read_to_string(&out_path)?;
+    assert!(markdown.starts_with(&format!("# Codex session {THREAD_ID}\n")));
+    assert!(markdown.contains("**Ran** (exit code 0)\n\n```shell\nls\n```\n"));
+
+    Ok(())
+}
@@ -31,3 +31,22 @@ pub(crate) fn add_auth_headers<A: AuthProvider>(auth: &A, mut req: Request) -> R
     add_auth_headers_to_header_map(auth, &mut req.headers);
     req
 }
+
+/// Moves the bearer token into `header` for providers that authenticate with an API key header
+/// instead of `Authorization`. A `header` already configured on the provider wins.
+pub(crate) fn move_bearer_to_header(headers: &mut HeaderMap, header: &'static str) {
+    let Some(authorization) = headers.remove(http::header::AUTHORIZATION) else {
+        return;
+    };
+    if headers.contains_key(header) {
+        return;
+    }
+    if let Some(token) = authorization
+        .to_str()
+        .ok()
+        .and_then(|value| value.strip_prefix("Bearer "))
+        && let Ok(value) = HeaderValue::from_str(token)
+    {
+        headers.insert(header, value);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::auth::AuthProvider;
+use crate::auth::move_bearer_to_header;
+use crate::common::ResponseStream;
+use crate::endpoint::session::EndpointSession;
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::anthropic::AnthropicMessagesApiRequest;
+use crate::sse::anthropic::spawn_anthropic_stream;
+use crate::telemetry::SseTelemetry;
+use codex_client::HttpTransport;
+use codex_client::RequestTelemetry;
+use http::HeaderMap;
+use http::HeaderValue;
+use http::Method;
+use serde_json::Value;
+use std::sync::Arc;
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Streams turns from providers that speak the Anthropic Messages API at `/v1/messages`.
+pub struct AnthropicClient<T: HttpTransport, A: AuthProvider> {
+    session: EndpointSession<T, A>,
+    sse_telemetry: Option<Arc<dyn SseTelemetry>>,
+}
+
+impl<T: HttpTransport, A: AuthProvider> AnthropicClient<T, A> {
+    pub fn new(transport: T, provider: Provider, auth: A) -> Self {
+        Self {
+            session: EndpointSession::new(transport, provider, auth),
+            sse_telemetry: None,
+        }
+    }
+
+    pub fn with_telemetry(
+        self,
+        request: Option<Arc<dyn RequestTelemetry>>,
+        sse: Option<Arc<dyn SseTelemetry>>,
+    ) -> Self {
+        Self {
+            session: self.session.with_request_telemetry(request),
+            sse_telemetry: sse,
+        }
+    }
+
+    pub async fn stream_request(
+        &self,
+        request: AnthropicMessagesApiRequest,
+        extra_headers: HeaderMap,
+    ) -> Result<ResponseStream, ApiError> {
+        let body = serde_json::to_value(&request)
+            .map_err(|e| ApiError::Stream(format!("failed to encode messages request: {e}")))?;
+        self.stream(body, extra_headers, request.freeform_tools)
+            .await
+    }
+
+    fn path() -> &'static str {
+        "messages"
+    }
+
+    pub async fn stream(
+        &self,
+        body: Value,
+        extra_headers: HeaderMap,
+        freeform_tools: Vec<String>,
+    ) -> Result<ResponseStream, ApiError> {
+        let stream_response = self
+            .session
+            .stream_with(
+                Method::POST,
+                Self::path(),
+                extra_headers,
+                Some(body),
+                |req| {
+                    move_bearer_to_header(&mut req.headers, "x-api-key");
+                    if !req.headers.contains_key("anthropic-version") {
+                        req.headers.insert(
+                            "anthropic-version",
+                            HeaderValue::from_static(ANTHROPIC_VERSION),
+                        );
+                    }
+                    req.headers.insert(
+                        http::header::ACCEPT,
+                        HeaderValue::from_static("text/event-stream"),
+                    );
+                },
+            )
+            .await?;
+
+        Ok(spawn_anthropic_stream(
+            stream_response,
+            self.session.provider().stream_idle_timeout,
+            self.sse_telemetry.clone(),
+            freeform_tools,
+        ))
+    }
+}
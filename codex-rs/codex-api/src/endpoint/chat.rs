@@ -0,0 +1,109 @@
+use crate::auth::AuthProvider;
+use crate::common::ResponseStream;
+use crate::endpoint::session::EndpointSession;
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::chat::ChatCompletionsApiRequest;
+use crate::requests::headers::build_conversation_headers;
+use crate::requests::headers::insert_header;
+use crate::requests::headers::subagent_header;
+use crate::sse::chat::spawn_chat_stream;
+use crate::telemetry::SseTelemetry;
+use codex_client::HttpTransport;
+use codex_client::RequestTelemetry;
+use codex_protocol::protocol::SessionSource;
+use http::HeaderMap;
+use http::HeaderValue;
+use http::Method;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Streams turns from OpenAI-compatible servers that only implement `/chat/completions`.
+pub struct ChatClient<T: HttpTransport, A: AuthProvider> {
+    session: EndpointSession<T, A>,
+    sse_telemetry: Option<Arc<dyn SseTelemetry>>,
+}
+
+#[derive(Default)]
+pub struct ChatOptions {
+    pub conversation_id: Option<String>,
+    pub session_source: Option<SessionSource>,
+    pub extra_headers: HeaderMap,
+}
+
+impl<T: HttpTransport, A: AuthProvider> ChatClient<T, A> {
+    pub fn new(transport: T, provider: Provider, auth: A) -> Self {
+        Self {
+            session: EndpointSession::new(transport, provider, auth),
+            sse_telemetry: None,
+        }
+    }
+
+    pub fn with_telemetry(
+        self,
+        request: Option<Arc<dyn RequestTelemetry>>,
+        sse: Option<Arc<dyn SseTelemetry>>,
+    ) -> Self {
+        Self {
+            session: self.session.with_request_telemetry(request),
+            sse_telemetry: sse,
+        }
+    }
+
+    pub async fn stream_request(
+        &self,
+        request: ChatCompletionsApiRequest,
+        options: ChatOptions,
+    ) -> Result<ResponseStream, ApiError> {
+        let ChatOptions {
+            conversation_id,
+            session_source,
+            extra_headers,
+        } = options;
+
+        let body = serde_json::to_value(&request)
+            .map_err(|e| ApiError::Stream(format!("failed to encode chat request: {e}")))?;
+
+        let mut headers = extra_headers;
+        headers.extend(build_conversation_headers(conversation_id));
+        if let Some(subagent) = subagent_header(&session_source) {
+            insert_header(&mut headers, "x-openai-subagent", &subagent);
+        }
+
+        self.stream(body, headers, request.freeform_tools).await
+    }
+
+    fn path() -> &'static str {
+        "chat/completions"
+    }
+
+    pub async fn stream(
+        &self,
+        body: Value,
+        extra_headers: HeaderMap,
+        freeform_tools: Vec<String>,
+    ) -> Result<ResponseStream, ApiError> {
+        let stream_response = self
+            .session
+            .stream_with(
+                Method::POST,
+                Self::path(),
+                extra_headers,
+                Some(body),
+                |req| {
+                    req.headers.insert(
+                        http::header::ACCEPT,
+                        HeaderValue::from_static("text/event-stream"),
+                    );
+                },
+            )
+            .await?;
+
+        Ok(spawn_chat_stream(
+            stream_response,
+            self.session.provider().stream_idle_timeout,
+            self.sse_telemetry.clone(),
+            freeform_tools,
+        ))
+    }
+}
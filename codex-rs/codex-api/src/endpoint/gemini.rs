@@ -0,0 +1,93 @@
+use crate::auth::AuthProvider;
+use crate::auth::move_bearer_to_header;
+use crate::common::ResponseStream;
+use crate::endpoint::session::EndpointSession;
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::gemini::GeminiGenerateContentRequest;
+use crate::sse::gemini::spawn_gemini_stream;
+use crate::telemetry::SseTelemetry;
+use codex_client::HttpTransport;
+use codex_client::RequestTelemetry;
+use http::HeaderMap;
+use http::HeaderValue;
+use http::Method;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Streams turns from providers that speak the Gemini `streamGenerateContent` API.
+pub struct GeminiClient<T: HttpTransport, A: AuthProvider> {
+    session: EndpointSession<T, A>,
+    sse_telemetry: Option<Arc<dyn SseTelemetry>>,
+}
+
+impl<T: HttpTransport, A: AuthProvider> GeminiClient<T, A> {
+    pub fn new(transport: T, provider: Provider, auth: A) -> Self {
+        Self {
+            session: EndpointSession::new(transport, provider, auth),
+            sse_telemetry: None,
+        }
+    }
+
+    pub fn with_telemetry(
+        self,
+        request: Option<Arc<dyn RequestTelemetry>>,
+        sse: Option<Arc<dyn SseTelemetry>>,
+    ) -> Self {
+        Self {
+            session: self.session.with_request_telemetry(request),
+            sse_telemetry: sse,
+        }
+    }
+
+    pub async fn stream_request(
+        &self,
+        request: GeminiGenerateContentRequest,
+        extra_headers: HeaderMap,
+    ) -> Result<ResponseStream, ApiError> {
+        let body = serde_json::to_value(&request)
+            .map_err(|e| ApiError::Stream(format!("failed to encode gemini request: {e}")))?;
+        self.stream(&request.model, body, extra_headers, request.freeform_tools)
+            .await
+    }
+
+    fn path(model: &str) -> String {
+        format!("models/{model}:streamGenerateContent")
+    }
+
+    pub async fn stream(
+        &self,
+        model: &str,
+        body: Value,
+        extra_headers: HeaderMap,
+        freeform_tools: Vec<String>,
+    ) -> Result<ResponseStream, ApiError> {
+        let stream_response = self
+            .session
+            .stream_with(
+                Method::POST,
+                &Self::path(model),
+                extra_headers,
+                Some(body),
+                |req| {
+                    move_bearer_to_header(&mut req.headers, "x-goog-api-key");
+                    // Without `alt=sse` the endpoint streams one JSON array instead of events.
+                    let separator = if req.url.contains('?') { '&' } else { '?' };
+                    req.url.push(separator);
+                    req.url.push_str("alt=sse");
+                    req.headers.insert(
+                        http::header::ACCEPT,
+                        HeaderValue::from_static("text/event-stream"),
+                    );
+                },
+            )
+            .await?;
+
+        Ok(spawn_gemini_stream(
+            stream_response,
+            self.session.provider().stream_idle_timeout,
+            self.sse_telemetry.clone(),
+            freeform_tools,
+        ))
+    }
+}
@@ -1,5 +1,8 @@
 pub mod aggregate;
+pub mod anthropic;
+pub mod chat;
 pub mod compact;
+pub mod gemini;
 pub mod memories;
 pub mod models;
 pub mod responses;
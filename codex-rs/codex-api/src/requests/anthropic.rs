@@ -0,0 +1,602 @@
+use crate::common::ResponsesApiRequest;
+use crate::error::ApiError;
+use crate::requests::FunctionTool;
+use crate::requests::function_tools;
+use crate::requests::local_shell_arguments;
+use crate::requests::split_data_url;
+use crate::requests::tagged_developer_instructions;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
+use serde::Serialize;
+use serde_json::Value;
+use serde_json::json;
+use tracing::debug;
+
+/// Output token cap sent with every request. The Messages API requires one and it must exceed
+/// the thinking budget.
+const MAX_OUTPUT_TOKENS: u32 = 32_000;
+
+/// Request body for an Anthropic-style `/v1/messages` call, built from the Responses request
+/// Codex assembles for a turn.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct AnthropicMessagesApiRequest {
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+    pub messages: Vec<Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<Value>,
+    pub max_tokens: u32,
+    pub stream: bool,
+    /// Freeform tools offered as functions, whose calls the stream turns back into custom tool
+    /// calls.
+    #[serde(skip)]
+    pub freeform_tools: Vec<String>,
+}
+
+impl TryFrom<&ResponsesApiRequest> for AnthropicMessagesApiRequest {
+    type Error = ApiError;
+
+    fn try_from(request: &ResponsesApiRequest) -> Result<Self, ApiError> {
+        let tools = function_tools(&request.tools, "messages")?;
+        let freeform_tools = tools
+            .iter()
+            .filter(|tool| tool.freeform)
+            .map(|tool| tool.name.clone())
+            .collect();
+        let tools = anthropic_tools(tools);
+        let tool_choice = (!tools.is_empty()).then(|| match request.tool_choice.as_str() {
+            "none" => json!({ "type": "none" }),
+            "required" => json!({
+                "type": "any",
+                "disable_parallel_tool_use": !request.parallel_tool_calls,
+            }),
+            _ => json!({
+                "type": "auto",
+                "disable_parallel_tool_use": !request.parallel_tool_calls,
+            }),
+        });
+        if request
+            .text
+            .as_ref()
+            .is_some_and(|text| text.format.is_some())
+        {
+            debug!("dropping output schema: the messages API has no structured output format");
+        }
+        let (system, messages) = anthropic_messages(&request.instructions, &request.input);
+        Ok(Self {
+            model: request.model.clone(),
+            system,
+            messages,
+            tools,
+            tool_choice,
+            thinking: request
+                .reasoning
+                .as_ref()
+                .and_then(|reasoning| reasoning.effort)
+                .and_then(thinking_budget)
+                .map(|budget_tokens| {
+                    json!({
+                        "type": "enabled",
+                        "budget_tokens": budget_tokens,
+                    })
+                }),
+            max_tokens: MAX_OUTPUT_TOKENS,
+            stream: request.stream,
+            freeform_tools,
+        })
+    }
+}
+
+fn thinking_budget(effort: ReasoningEffortConfig) -> Option<u32> {
+    match effort {
+        ReasoningEffortConfig::None => None,
+        ReasoningEffortConfig::Minimal => Some(1_024),
+        ReasoningEffortConfig::Low => Some(4_096),
+        ReasoningEffortConfig::Medium => Some(10_000),
+        ReasoningEffortConfig::High => Some(20_000),
+        ReasoningEffortConfig::XHigh => Some(30_000),
+    }
+}
+
+/// Converts function tools to Messages API tools.
+fn anthropic_tools(tools: Vec<FunctionTool>) -> Vec<Value> {
+    tools
+        .into_iter()
+        .map(|tool| {
+            let mut converted = json!({
+                "name": tool.name,
+                "input_schema": tool.parameters,
+            });
+            if let Some(description) = tool.description {
+                converted["description"] = Value::String(description);
+            }
+            converted
+        })
+        .collect()
+}
+
+/// Translates the turn input into a system prompt and a list of messages.
+///
+/// - `developer` messages before the first message are folded into the system prompt, which is
+///   the only place the Messages API accepts instructions. Later ones stay in place as tagged
+///   user text (see [`tagged_developer_instructions`]).
+/// - Consecutive items with the same role share one message, so reasoning, text and tool calls
+///   from one model turn stay together and tool results follow their calls.
+/// - Reasoning is only replayed when it carries a signature (stored in `encrypted_content`);
+///   unsigned thinking from other providers would be rejected.
+pub(crate) fn anthropic_messages(
+    instructions: &str,
+    input: &[ResponseItem],
+) -> (Option<String>, Vec<Value>) {
+    let mut builder = AnthropicMessagesBuilder::default();
+    if !instructions.is_empty() {
+        builder.system.push(instructions.to_string());
+    }
+    for item in input {
+        builder.push_item(item);
+    }
+    let system = (!builder.system.is_empty()).then(|| builder.system.join("\n\n"));
+    (system, builder.messages)
+}
+
+#[derive(Default)]
+struct AnthropicMessagesBuilder {
+    system: Vec<String>,
+    messages: Vec<Value>,
+}
+
+impl AnthropicMessagesBuilder {
+    fn push_item(&mut self, item: &ResponseItem) {
+        match item {
+            ResponseItem::Message { role, content, .. } => match role.as_str() {
+                "developer" | "system" => {
+                    let text = content_text(content);
+                    if text.is_empty() {
+                        return;
+                    }
+                    if self.messages.is_empty() {
+                        self.system.push(text);
+                    } else {
+                        let text = tagged_developer_instructions(&text);
+                        self.push_block("user", text_block(&text));
+                    }
+                }
+                "assistant" => {
+                    for item in content {
+                        if let ContentItem::OutputText { text } | ContentItem::InputText { text } =
+                            item
+                        {
+                            self.push_block("assistant", text_block(text));
+                        }
+                    }
+                }
+                _ => {
+                    for item in content {
+                        let block = match item {
+                            ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                                text_block(text)
+                            }
+                            ContentItem::InputImage { image_url } => image_block(image_url),
+                        };
+                        self.push_block("user", block);
+                    }
+                }
+            },
+            ResponseItem::Reasoning {
+                content,
+                encrypted_content,
+                ..
+            } => {
+                let Some(signature) = encrypted_content else {
+                    return;
+                };
+                let thinking = content
+                    .as_deref()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|item| match item {
+                        ReasoningItemContent::ReasoningText { text }
+                        | ReasoningItemContent::Text { text } => text.as_str(),
+                    })
+                    .collect::<String>();
+                let block = if thinking.is_empty() {
+                    json!({
+                        "type": "redacted_thinking",
+                        "data": signature,
+                    })
+                } else {
+                    json!({
+                        "type": "thinking",
+                        "thinking": thinking,
+                        "signature": signature,
+                    })
+                };
+                self.push_block("assistant", block);
+            }
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                let input = serde_json::from_str::<Value>(arguments)
+                    .ok()
+                    .filter(Value::is_object)
+                    .unwrap_or_else(|| json!({}));
+                self.push_tool_use(call_id, name, input);
+            }
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => self.push_tool_use(call_id, name, json!({ "input": input })),
+            ResponseItem::LocalShellCall {
+                id,
+                call_id,
+                action,
+                ..
+            } => {
+                let Some(call_id) = call_id.as_ref().or(id.as_ref()) else {
+                    return;
+                };
+                self.push_tool_use(call_id, "local_shell", local_shell_arguments(action));
+            }
+            ResponseItem::FunctionCallOutput { call_id, output } => {
+                let content = match &output.body {
+                    FunctionCallOutputBody::Text(text) => vec![text_block(text)],
+                    FunctionCallOutputBody::ContentItems(items) => items
+                        .iter()
+                        .map(|item| match item {
+                            FunctionCallOutputContentItem::InputText { text } => text_block(text),
+                            FunctionCallOutputContentItem::InputImage { image_url } => {
+                                image_block(image_url)
+                            }
+                        })
+                        .collect(),
+                };
+                let mut block = json!({
+                    "type": "tool_result",
+                    "tool_use_id": call_id,
+                    "content": content,
+                });
+                if output.success == Some(false) {
+                    block["is_error"] = Value::Bool(true);
+                }
+                self.push_block("user", block);
+            }
+            ResponseItem::CustomToolCallOutput { call_id, output } => {
+                self.push_block(
+                    "user",
+                    json!({
+                        "type": "tool_result",
+                        "tool_use_id": call_id,
+                        "content": [text_block(output)],
+                    }),
+                );
+            }
+            ResponseItem::WebSearchCall { .. }
+            | ResponseItem::GhostSnapshot { .. }
+            | ResponseItem::Compaction { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+
+    fn push_tool_use(&mut self, call_id: &str, name: &str, input: Value) {
+        self.push_block(
+            "assistant",
+            json!({
+                "type": "tool_use",
+                "id": call_id,
+                "name": name,
+                "input": input,
+            }),
+        );
+    }
+
+    fn push_block(&mut self, role: &str, block: Value) {
+        // The API rejects empty text blocks.
+        if block.get("type").and_then(Value::as_str) == Some("text")
+            && block
+                .get("text")
+                .and_then(Value::as_str)
+                .is_some_and(str::is_empty)
+        {
+            return;
+        }
+        if let Some(last) = self.messages.last_mut()
+            && last.get("role").and_then(Value::as_str) == Some(role)
+            && let Some(content) = last.get_mut("content").and_then(Value::as_array_mut)
+        {
+            content.push(block);
+            return;
+        }
+        self.messages.push(json!({
+            "role": role,
+            "content": [block],
+        }));
+    }
+}
+
+fn content_text(content: &[ContentItem]) -> String {
+    content
+        .iter()
+        .filter_map(|item| match item {
+            ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                Some(text.as_str())
+            }
+            ContentItem::InputImage { .. } => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn text_block(text: &str) -> Value {
+    json!({
+        "type": "text",
+        "text": text,
+    })
+}
+
+fn image_block(image_url: &str) -> Value {
+    let source = match split_data_url(image_url) {
+        Some((media_type, data)) => json!({
+            "type": "base64",
+            "media_type": media_type,
+            "data": data,
+        }),
+        None => json!({
+            "type": "url",
+            "url": image_url,
+        }),
+    };
+    json!({
+        "type": "image",
+        "source": source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Reasoning;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn message(role: &str, content: Vec<ContentItem>) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content,
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    #[test]
+    fn messages_fold_developer_text_and_group_turns() {
+        let input = vec![
+            message(
+                "developer",
+                vec![ContentItem::InputText {
+                    text: "sandbox: read-only".to_string(),
+                }],
+            ),
+            message(
+                "user",
+                vec![
+                    ContentItem::InputText {
+                        text: "what is this?".to_string(),
+                    },
+                    ContentItem::InputImage {
+                        image_url: "data:image/png;base64,AAAA".to_string(),
+                    },
+                ],
+            ),
+            ResponseItem::Reasoning {
+                id: String::new(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "look closer".to_string(),
+                }]),
+                encrypted_content: Some("sig-1".to_string()),
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: "{\"command\":[\"file\",\"a.png\"]}".to_string(),
+                call_id: "toolu_1".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "toolu_1".to_string(),
+                output: FunctionCallOutputPayload {
+                    body: FunctionCallOutputBody::Text("PNG image".to_string()),
+                    success: Some(false),
+                },
+            },
+            message(
+                "assistant",
+                vec![ContentItem::OutputText {
+                    text: "It is a PNG.".to_string(),
+                }],
+            ),
+        ];
+
+        let (system, messages) = anthropic_messages("be brief", &input);
+
+        assert_eq!(system.as_deref(), Some("be brief\n\nsandbox: read-only"));
+        assert_eq!(
+            messages,
+            vec![
+                json!({"role": "user", "content": [
+                    {"type": "text", "text": "what is this?"},
+                    {"type": "image", "source": {"type": "base64", "media_type": "image/png", "data": "AAAA"}},
+                ]}),
+                json!({"role": "assistant", "content": [
+                    {"type": "thinking", "thinking": "look closer", "signature": "sig-1"},
+                    {"type": "tool_use", "id": "toolu_1", "name": "shell", "input": {"command": ["file", "a.png"]}},
+                ]}),
+                json!({"role": "user", "content": [
+                    {"type": "tool_result", "tool_use_id": "toolu_1", "content": [{"type": "text", "text": "PNG image"}], "is_error": true},
+                ]}),
+                json!({"role": "assistant", "content": [
+                    {"type": "text", "text": "It is a PNG."},
+                ]}),
+            ]
+        );
+    }
+
+    #[test]
+    fn later_developer_messages_stay_in_place_as_user_text() {
+        let text = |text: &str| {
+            vec![ContentItem::InputText {
+                text: text.to_string(),
+            }]
+        };
+        let input = vec![
+            message("developer", text("sandbox: read-only")),
+            message("user", text("hi")),
+            message("assistant", text("hello")),
+            message("developer", text("sandbox: workspace-write")),
+            message("user", text("edit a.txt")),
+        ];
+
+        let (system, messages) = anthropic_messages("be brief", &input);
+
+        assert_eq!(system.as_deref(), Some("be brief\n\nsandbox: read-only"));
+        assert_eq!(
+            messages,
+            vec![
+                json!({"role": "user", "content": [{"type": "text", "text": "hi"}]}),
+                json!({"role": "assistant", "content": [{"type": "text", "text": "hello"}]}),
+                json!({"role": "user", "content": [
+                    {"type": "text", "text": "<developer_instructions>\nsandbox: workspace-write\n</developer_instructions>"},
+                    {"type": "text", "text": "edit a.txt"},
+                ]}),
+            ]
+        );
+    }
+
+    #[test]
+    fn unsigned_reasoning_is_not_replayed() {
+        let input = vec![ResponseItem::Reasoning {
+            id: String::new(),
+            summary: Vec::new(),
+            content: Some(vec![ReasoningItemContent::ReasoningText {
+                text: "from another provider".to_string(),
+            }]),
+            encrypted_content: None,
+        }];
+
+        let (system, messages) = anthropic_messages("", &input);
+
+        assert_eq!(system, None);
+        assert_eq!(messages, Vec::<Value>::new());
+    }
+
+    #[test]
+    fn request_maps_tools_and_thinking_budget() {
+        let request = ResponsesApiRequest {
+            model: "claude-sonnet-4-5".to_string(),
+            instructions: String::new(),
+            input: Vec::new(),
+            tools: vec![
+                json!({
+                    "type": "function",
+                    "name": "shell",
+                    "description": "Runs a command",
+                    "strict": false,
+                    "parameters": {"type": "object", "properties": {}},
+                }),
+                json!({
+                    "type": "custom",
+                    "name": "apply_patch",
+                    "description": "Edit files",
+                    "format": {"type": "grammar", "syntax": "lark", "definition": "start: PATCH"},
+                }),
+            ],
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: false,
+            reasoning: Some(Reasoning {
+                effort: Some(ReasoningEffortConfig::Low),
+                summary: None,
+            }),
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            prompt_cache_key: None,
+            text: None,
+        };
+
+        let anthropic = AnthropicMessagesApiRequest::try_from(&request).expect("build request");
+        assert_eq!(anthropic.freeform_tools, vec!["apply_patch".to_string()]);
+        let body = serde_json::to_value(anthropic).expect("serialize request");
+
+        assert_eq!(
+            body,
+            json!({
+                "model": "claude-sonnet-4-5",
+                "messages": [],
+                "tools": [
+                    {
+                        "name": "shell",
+                        "description": "Runs a command",
+                        "input_schema": {"type": "object", "properties": {}},
+                    },
+                    {
+                        "name": "apply_patch",
+                        "description": "Edit files",
+                        "input_schema": {
+                            "type": "object",
+                            "properties": {"input": {
+                                "type": "string",
+                                "description": "Raw tool input matching this lark grammar:\nstart: PATCH",
+                            }},
+                            "required": ["input"],
+                            "additionalProperties": false,
+                        },
+                    },
+                ],
+                "tool_choice": {"type": "auto", "disable_parallel_tool_use": true},
+                "thinking": {"type": "enabled", "budget_tokens": 4_096},
+                "max_tokens": MAX_OUTPUT_TOKENS,
+                "stream": true,
+            })
+        );
+    }
+
+    #[test]
+    fn hosted_tools_are_rejected() {
+        let request = ResponsesApiRequest {
+            model: "claude-sonnet-4-5".to_string(),
+            instructions: String::new(),
+            input: Vec::new(),
+            tools: vec![json!({"type": "web_search"})],
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: true,
+            reasoning: None,
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            prompt_cache_key: None,
+            text: None,
+        };
+
+        let err =
+            AnthropicMessagesApiRequest::try_from(&request).expect_err("web_search is hosted");
+
+        assert_eq!(
+            err.to_string(),
+            "invalid request: tool `web_search` of type `web_search` has no equivalent in the messages API"
+        );
+    }
+}
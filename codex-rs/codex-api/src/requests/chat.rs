@@ -0,0 +1,743 @@
+use crate::common::ResponsesApiRequest;
+use crate::error::ApiError;
+use crate::requests::function_tools;
+use crate::requests::local_shell_arguments;
+use crate::requests::tagged_developer_instructions;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
+use serde::Serialize;
+use serde_json::Value;
+use serde_json::json;
+
+/// Request body for `/chat/completions`, built from the Responses request Codex assembles for a
+/// turn so both wire APIs share prompt construction.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ChatCompletionsApiRequest {
+    pub model: String,
+    pub messages: Vec<Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallel_tool_calls: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffortConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<Value>,
+    pub stream: bool,
+    pub stream_options: ChatStreamOptions,
+    /// Freeform tools offered as functions, whose calls the stream turns back into custom tool
+    /// calls.
+    #[serde(skip)]
+    pub freeform_tools: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ChatStreamOptions {
+    /// Ask the server for a trailing chunk with token usage.
+    pub include_usage: bool,
+}
+
+impl TryFrom<&ResponsesApiRequest> for ChatCompletionsApiRequest {
+    type Error = ApiError;
+
+    fn try_from(request: &ResponsesApiRequest) -> Result<Self, ApiError> {
+        let tools = function_tools(&request.tools, "chat completions")?;
+        let freeform_tools = tools
+            .iter()
+            .filter(|tool| tool.freeform)
+            .map(|tool| tool.name.clone())
+            .collect();
+        let tools: Vec<Value> = tools
+            .into_iter()
+            .map(|tool| {
+                let mut function = json!({
+                    "name": tool.name,
+                    "parameters": tool.parameters,
+                });
+                if let Some(description) = tool.description {
+                    function["description"] = Value::String(description);
+                }
+                if let Some(strict) = tool.strict {
+                    function["strict"] = Value::Bool(strict);
+                }
+                json!({
+                    "type": "function",
+                    "function": function,
+                })
+            })
+            .collect();
+        let has_tools = !tools.is_empty();
+        let response_format = request
+            .text
+            .as_ref()
+            .and_then(|text| text.format.as_ref())
+            .map(|format| {
+                json!({
+                    "type": "json_schema",
+                    "json_schema": {
+                        "name": format.name,
+                        "schema": format.schema,
+                        "strict": format.strict,
+                    },
+                })
+            });
+        Ok(Self {
+            model: request.model.clone(),
+            messages: chat_messages(&request.instructions, &request.input),
+            tools,
+            tool_choice: has_tools.then(|| request.tool_choice.clone()),
+            parallel_tool_calls: has_tools.then_some(request.parallel_tool_calls),
+            reasoning_effort: request
+                .reasoning
+                .as_ref()
+                .and_then(|reasoning| reasoning.effort),
+            response_format,
+            stream: request.stream,
+            stream_options: ChatStreamOptions {
+                include_usage: true,
+            },
+            freeform_tools,
+        })
+    }
+}
+
+/// Translates the turn input into chat messages.
+///
+/// - Leading `developer` messages become `system` messages; later ones stay in place as tagged
+///   user messages (see [`tagged_developer_instructions`]), since many servers only accept
+///   system messages at the start.
+/// - Consecutive tool calls are grouped on one assistant message, as chat requires.
+/// - Reasoning is replayed as `reasoning_content` on the assistant message that follows it.
+/// - Images returned by tools are sent in a user message after the tool results, because tool
+///   messages only carry text.
+pub(crate) fn chat_messages(instructions: &str, input: &[ResponseItem]) -> Vec<Value> {
+    let mut builder = ChatMessagesBuilder::default();
+    if !instructions.is_empty() {
+        builder.push(json!({
+            "role": "system",
+            "content": instructions,
+        }));
+    }
+    for item in input {
+        builder.push_item(item);
+    }
+    builder.finish()
+}
+
+#[derive(Default)]
+struct ChatMessagesBuilder {
+    messages: Vec<Value>,
+    pending_reasoning: Option<String>,
+    pending_tool_images: Vec<Value>,
+}
+
+impl ChatMessagesBuilder {
+    fn push_item(&mut self, item: &ResponseItem) {
+        match item {
+            ResponseItem::Message { role, content, .. } => {
+                if role == "assistant" {
+                    let mut message = json!({
+                        "role": "assistant",
+                        "content": content_text(content),
+                    });
+                    if let Some(reasoning) = self.pending_reasoning.take() {
+                        message["reasoning_content"] = Value::String(reasoning);
+                    }
+                    self.push(message);
+                } else if role == "developer" || role == "system" {
+                    let leading = self
+                        .messages
+                        .iter()
+                        .all(|message| message["role"] == "system");
+                    if leading {
+                        self.push(json!({
+                            "role": "system",
+                            "content": content_parts(content),
+                        }));
+                    } else {
+                        self.push(json!({
+                            "role": "user",
+                            "content": tagged_developer_instructions(&content_text(content)),
+                        }));
+                    }
+                } else {
+                    self.push(json!({
+                        "role": role,
+                        "content": content_parts(content),
+                    }));
+                }
+            }
+            ResponseItem::Reasoning {
+                summary, content, ..
+            } => {
+                let text = reasoning_text(summary, content.as_deref());
+                if !text.is_empty() {
+                    match self.pending_reasoning.as_mut() {
+                        Some(pending) => {
+                            pending.push('\n');
+                            pending.push_str(&text);
+                        }
+                        None => self.pending_reasoning = Some(text),
+                    }
+                }
+            }
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => self.push_tool_call(call_id, name, arguments),
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => self.push_tool_call(call_id, name, &json!({ "input": input }).to_string()),
+            ResponseItem::LocalShellCall {
+                id,
+                call_id,
+                action,
+                ..
+            } => {
+                let Some(call_id) = call_id.as_ref().or(id.as_ref()) else {
+                    return;
+                };
+                let arguments = local_shell_arguments(action).to_string();
+                self.push_tool_call(call_id, "local_shell", &arguments);
+            }
+            ResponseItem::FunctionCallOutput { call_id, output } => {
+                let text = match &output.body {
+                    FunctionCallOutputBody::Text(text) => text.clone(),
+                    FunctionCallOutputBody::ContentItems(items) => {
+                        self.pending_tool_images.extend(items.iter().filter_map(
+                            |item| match item {
+                                FunctionCallOutputContentItem::InputImage { image_url } => {
+                                    Some(image_part(image_url))
+                                }
+                                FunctionCallOutputContentItem::InputText { .. } => None,
+                            },
+                        ));
+                        output.body.to_text().unwrap_or_default()
+                    }
+                };
+                self.push_tool_result(call_id, text);
+            }
+            ResponseItem::CustomToolCallOutput { call_id, output } => {
+                self.push_tool_result(call_id, output.clone());
+            }
+            ResponseItem::WebSearchCall { .. }
+            | ResponseItem::GhostSnapshot { .. }
+            | ResponseItem::Compaction { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+
+    fn push(&mut self, message: Value) {
+        self.flush_tool_images();
+        self.messages.push(message);
+    }
+
+    fn push_tool_call(&mut self, call_id: &str, name: &str, arguments: &str) {
+        let tool_call = json!({
+            "id": call_id,
+            "type": "function",
+            "function": {
+                "name": name,
+                "arguments": arguments,
+            },
+        });
+        if self.pending_tool_images.is_empty()
+            && let Some(last) = self.messages.last_mut()
+            && last.get("role").and_then(Value::as_str) == Some("assistant")
+        {
+            match last.get_mut("tool_calls").and_then(Value::as_array_mut) {
+                Some(tool_calls) => tool_calls.push(tool_call),
+                None => last["tool_calls"] = json!([tool_call]),
+            }
+            return;
+        }
+
+        let mut message = json!({
+            "role": "assistant",
+            "content": Value::Null,
+            "tool_calls": [tool_call],
+        });
+        if let Some(reasoning) = self.pending_reasoning.take() {
+            message["reasoning_content"] = Value::String(reasoning);
+        }
+        self.push(message);
+    }
+
+    fn push_tool_result(&mut self, call_id: &str, content: String) {
+        self.messages.push(json!({
+            "role": "tool",
+            "tool_call_id": call_id,
+            "content": content,
+        }));
+    }
+
+    fn flush_tool_images(&mut self) {
+        if self.pending_tool_images.is_empty() {
+            return;
+        }
+        let images = std::mem::take(&mut self.pending_tool_images);
+        self.messages.push(json!({
+            "role": "user",
+            "content": images,
+        }));
+    }
+
+    fn finish(mut self) -> Vec<Value> {
+        self.flush_tool_images();
+        self.messages
+    }
+}
+
+fn content_text(content: &[ContentItem]) -> String {
+    content
+        .iter()
+        .filter_map(|item| match item {
+            ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                Some(text.as_str())
+            }
+            ContentItem::InputImage { .. } => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Plain text when there are no images, since many servers reject content arrays.
+fn content_parts(content: &[ContentItem]) -> Value {
+    if !content
+        .iter()
+        .any(|item| matches!(item, ContentItem::InputImage { .. }))
+    {
+        return Value::String(content_text(content));
+    }
+    Value::Array(
+        content
+            .iter()
+            .map(|item| match item {
+                ContentItem::InputText { text } | ContentItem::OutputText { text } => json!({
+                    "type": "text",
+                    "text": text,
+                }),
+                ContentItem::InputImage { image_url } => image_part(image_url),
+            })
+            .collect(),
+    )
+}
+
+fn image_part(image_url: &str) -> Value {
+    json!({
+        "type": "image_url",
+        "image_url": { "url": image_url },
+    })
+}
+
+fn reasoning_text(
+    summary: &[ReasoningItemReasoningSummary],
+    content: Option<&[ReasoningItemContent]>,
+) -> String {
+    let content_text: Vec<&str> = content
+        .unwrap_or_default()
+        .iter()
+        .map(|item| match item {
+            ReasoningItemContent::ReasoningText { text } | ReasoningItemContent::Text { text } => {
+                text.as_str()
+            }
+        })
+        .collect();
+    if !content_text.is_empty() {
+        return content_text.join("\n");
+    }
+    summary
+        .iter()
+        .map(|item| match item {
+            ReasoningItemReasoningSummary::SummaryText { text } => text.as_str(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Reasoning;
+    use crate::common::TextControls;
+    use crate::common::TextFormat;
+    use crate::common::TextFormatType;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use codex_protocol::models::LocalShellAction;
+    use codex_protocol::models::LocalShellExecAction;
+    use codex_protocol::models::LocalShellStatus;
+    use pretty_assertions::assert_eq;
+
+    fn user_message(content: Vec<ContentItem>) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content,
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    fn function_call(call_id: &str, name: &str) -> ResponseItem {
+        ResponseItem::FunctionCall {
+            id: None,
+            name: name.to_string(),
+            arguments: "{}".to_string(),
+            call_id: call_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn messages_group_parallel_tool_calls_and_replay_reasoning() {
+        let input = vec![
+            ResponseItem::Message {
+                id: None,
+                role: "developer".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "be brief".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            user_message(vec![ContentItem::InputText {
+                text: "list files".to_string(),
+            }]),
+            ResponseItem::Reasoning {
+                id: String::new(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "run ls".to_string(),
+                }]),
+                encrypted_content: None,
+            },
+            function_call("call-1", "shell"),
+            function_call("call-2", "shell"),
+            ResponseItem::FunctionCallOutput {
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload::from_text("a.txt".to_string()),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call-2".to_string(),
+                output: FunctionCallOutputPayload::from_text("b.txt".to_string()),
+            },
+            ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: "done".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+        ];
+
+        assert_eq!(
+            chat_messages("system prompt", &input),
+            vec![
+                json!({"role": "system", "content": "system prompt"}),
+                json!({"role": "system", "content": "be brief"}),
+                json!({"role": "user", "content": "list files"}),
+                json!({
+                    "role": "assistant",
+                    "content": null,
+                    "reasoning_content": "run ls",
+                    "tool_calls": [
+                        {
+                            "id": "call-1",
+                            "type": "function",
+                            "function": {"name": "shell", "arguments": "{}"},
+                        },
+                        {
+                            "id": "call-2",
+                            "type": "function",
+                            "function": {"name": "shell", "arguments": "{}"},
+                        },
+                    ],
+                }),
+                json!({"role": "tool", "tool_call_id": "call-1", "content": "a.txt"}),
+                json!({"role": "tool", "tool_call_id": "call-2", "content": "b.txt"}),
+                json!({"role": "assistant", "content": "done"}),
+            ]
+        );
+    }
+
+    #[test]
+    fn later_developer_messages_stay_in_place_as_user_messages() {
+        let developer = |text: &str| ResponseItem::Message {
+            id: None,
+            role: "developer".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        };
+        let input = vec![
+            developer("sandbox: read-only"),
+            user_message(vec![ContentItem::InputText {
+                text: "hi".to_string(),
+            }]),
+            developer("sandbox: workspace-write"),
+        ];
+
+        assert_eq!(
+            chat_messages("system prompt", &input),
+            vec![
+                json!({"role": "system", "content": "system prompt"}),
+                json!({"role": "system", "content": "sandbox: read-only"}),
+                json!({"role": "user", "content": "hi"}),
+                json!({
+                    "role": "user",
+                    "content": "<developer_instructions>\nsandbox: workspace-write\n</developer_instructions>",
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn messages_carry_images_as_content_parts() {
+        let input = vec![
+            user_message(vec![
+                ContentItem::InputText {
+                    text: "what is this?".to_string(),
+                },
+                ContentItem::InputImage {
+                    image_url: "data:image/png;base64,AAA".to_string(),
+                },
+            ]),
+            function_call("call-1", "view_image"),
+            ResponseItem::FunctionCallOutput {
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload::from_content_items(vec![
+                    FunctionCallOutputContentItem::InputText {
+                        text: "attached".to_string(),
+                    },
+                    FunctionCallOutputContentItem::InputImage {
+                        image_url: "data:image/png;base64,BBB".to_string(),
+                    },
+                ]),
+            },
+        ];
+
+        assert_eq!(
+            chat_messages("", &input),
+            vec![
+                json!({
+                    "role": "user",
+                    "content": [
+                        {"type": "text", "text": "what is this?"},
+                        {"type": "image_url", "image_url": {"url": "data:image/png;base64,AAA"}},
+                    ],
+                }),
+                json!({
+                    "role": "assistant",
+                    "content": null,
+                    "tool_calls": [{
+                        "id": "call-1",
+                        "type": "function",
+                        "function": {"name": "view_image", "arguments": "{}"},
+                    }],
+                }),
+                json!({"role": "tool", "tool_call_id": "call-1", "content": "attached"}),
+                json!({
+                    "role": "user",
+                    "content": [
+                        {"type": "image_url", "image_url": {"url": "data:image/png;base64,BBB"}},
+                    ],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn request_keeps_function_tools_and_output_schema() {
+        let request = ResponsesApiRequest {
+            model: "qwen3".to_string(),
+            instructions: String::new(),
+            input: Vec::new(),
+            tools: vec![json!({
+                "type": "function",
+                "name": "shell",
+                "description": "Run a command",
+                "strict": false,
+                "parameters": {"type": "object", "properties": {}},
+            })],
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: true,
+            reasoning: Some(Reasoning {
+                effort: Some(ReasoningEffortConfig::High),
+                summary: None,
+            }),
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            prompt_cache_key: None,
+            text: Some(TextControls {
+                verbosity: None,
+                format: Some(TextFormat {
+                    r#type: TextFormatType::JsonSchema,
+                    strict: true,
+                    schema: json!({"type": "object"}),
+                    name: "codex_output_schema".to_string(),
+                }),
+            }),
+        };
+
+        let body = serde_json::to_value(
+            ChatCompletionsApiRequest::try_from(&request).expect("build chat request"),
+        )
+        .expect("serialize chat request");
+        assert_eq!(
+            body,
+            json!({
+                "model": "qwen3",
+                "messages": [],
+                "tools": [{
+                    "type": "function",
+                    "function": {
+                        "name": "shell",
+                        "description": "Run a command",
+                        "strict": false,
+                        "parameters": {"type": "object", "properties": {}},
+                    },
+                }],
+                "tool_choice": "auto",
+                "parallel_tool_calls": true,
+                "reasoning_effort": "high",
+                "response_format": {
+                    "type": "json_schema",
+                    "json_schema": {
+                        "name": "codex_output_schema",
+                        "schema": {"type": "object"},
+                        "strict": true,
+                    },
+                },
+                "stream": true,
+                "stream_options": {"include_usage": true},
+            })
+        );
+    }
+
+    fn tools_request(tools: Vec<Value>, input: Vec<ResponseItem>) -> ResponsesApiRequest {
+        ResponsesApiRequest {
+            model: "qwen3".to_string(),
+            instructions: String::new(),
+            input,
+            tools,
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: true,
+            reasoning: None,
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            prompt_cache_key: None,
+            text: None,
+        }
+    }
+
+    #[test]
+    fn freeform_and_local_shell_tools_become_functions() {
+        let request = tools_request(
+            vec![
+                json!({
+                    "type": "custom",
+                    "name": "apply_patch",
+                    "description": "Edit files",
+                    "format": {"type": "grammar", "syntax": "lark", "definition": "start: PATCH"},
+                }),
+                json!({"type": "local_shell"}),
+            ],
+            vec![
+                ResponseItem::CustomToolCall {
+                    id: None,
+                    status: None,
+                    call_id: "call-1".to_string(),
+                    name: "apply_patch".to_string(),
+                    input: "*** Begin Patch".to_string(),
+                },
+                ResponseItem::LocalShellCall {
+                    id: None,
+                    call_id: Some("call-2".to_string()),
+                    status: LocalShellStatus::Completed,
+                    action: LocalShellAction::Exec(LocalShellExecAction {
+                        command: vec!["ls".to_string()],
+                        timeout_ms: None,
+                        working_directory: Some("/repo".to_string()),
+                        env: None,
+                        user: None,
+                    }),
+                },
+            ],
+        );
+
+        let chat = ChatCompletionsApiRequest::try_from(&request).expect("build chat request");
+
+        assert_eq!(chat.freeform_tools, vec!["apply_patch".to_string()]);
+        let tools: Vec<_> = chat
+            .tools
+            .iter()
+            .map(|tool| {
+                (
+                    tool["function"]["name"].clone(),
+                    tool["function"]["parameters"]["required"].clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            tools,
+            vec![
+                (json!("apply_patch"), json!(["input"])),
+                (json!("local_shell"), json!(["command"])),
+            ]
+        );
+        assert_eq!(
+            serde_json::to_value(&chat.messages).expect("serialize messages"),
+            json!([{
+                "role": "assistant",
+                "content": null,
+                "tool_calls": [
+                    {
+                        "id": "call-1",
+                        "type": "function",
+                        "function": {
+                            "name": "apply_patch",
+                            "arguments": r#"{"input":"*** Begin Patch"}"#,
+                        },
+                    },
+                    {
+                        "id": "call-2",
+                        "type": "function",
+                        "function": {
+                            "name": "local_shell",
+                            "arguments": r#"{"command":["ls"],"workdir":"/repo"}"#,
+                        },
+                    },
+                ],
+            }])
+        );
+    }
+
+    #[test]
+    fn unmappable_tools_are_rejected() {
+        let request = tools_request(vec![json!({"type": "web_search"})], Vec::new());
+
+        let err = ChatCompletionsApiRequest::try_from(&request).expect_err("web_search is hosted");
+
+        assert_eq!(
+            err.to_string(),
+            "invalid request: tool `web_search` of type `web_search` has no equivalent in the chat completions API"
+        );
+    }
+}
@@ -0,0 +1,499 @@
+use crate::common::ResponsesApiRequest;
+use crate::error::ApiError;
+use crate::requests::FunctionTool;
+use crate::requests::function_tools;
+use crate::requests::local_shell_arguments;
+use crate::requests::split_data_url;
+use crate::requests::tagged_developer_instructions;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
+use serde::Serialize;
+use serde_json::Value;
+use serde_json::json;
+use std::collections::HashMap;
+use tracing::debug;
+
+/// Request body for a Gemini-style `models/{model}:streamGenerateContent` call, built from the
+/// Responses request Codex assembles for a turn. The model is part of the URL, not the body.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiGenerateContentRequest {
+    #[serde(skip)]
+    pub model: String,
+    pub contents: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_instruction: Option<Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_config: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generation_config: Option<Value>,
+    /// Freeform tools offered as functions, whose calls the stream turns back into custom tool
+    /// calls.
+    #[serde(skip)]
+    pub freeform_tools: Vec<String>,
+}
+
+impl TryFrom<&ResponsesApiRequest> for GeminiGenerateContentRequest {
+    type Error = ApiError;
+
+    fn try_from(request: &ResponsesApiRequest) -> Result<Self, ApiError> {
+        let tools = function_tools(&request.tools, "gemini")?;
+        let freeform_tools = tools
+            .iter()
+            .filter(|tool| tool.freeform)
+            .map(|tool| tool.name.clone())
+            .collect();
+        let declarations = function_declarations(tools);
+        let tool_config = (!declarations.is_empty()).then(|| {
+            let mode = match request.tool_choice.as_str() {
+                "none" => "NONE",
+                "required" => "ANY",
+                _ => "AUTO",
+            };
+            json!({ "functionCallingConfig": { "mode": mode } })
+        });
+        let tools = if declarations.is_empty() {
+            Vec::new()
+        } else {
+            vec![json!({ "functionDeclarations": declarations })]
+        };
+
+        let mut generation_config = serde_json::Map::new();
+        if request
+            .reasoning
+            .as_ref()
+            .and_then(|reasoning| reasoning.effort)
+            .is_some_and(|effort| effort != ReasoningEffortConfig::None)
+        {
+            generation_config.insert(
+                "thinkingConfig".to_string(),
+                json!({ "includeThoughts": true }),
+            );
+        }
+        if let Some(format) = request.text.as_ref().and_then(|text| text.format.as_ref()) {
+            generation_config.insert(
+                "responseMimeType".to_string(),
+                Value::String("application/json".to_string()),
+            );
+            generation_config.insert("responseJsonSchema".to_string(), format.schema.clone());
+        }
+
+        let (system, contents) = gemini_contents(&request.instructions, &request.input);
+        Ok(Self {
+            model: request.model.clone(),
+            contents,
+            system_instruction: system.map(|text| json!({ "parts": [{ "text": text }] })),
+            tools,
+            tool_config,
+            generation_config: (!generation_config.is_empty())
+                .then_some(Value::Object(generation_config)),
+            freeform_tools,
+        })
+    }
+}
+
+/// Converts function tools to Gemini function declarations. The JSON schema is passed through
+/// `parametersJsonSchema`, which accepts full JSON Schema rather than the OpenAPI subset
+/// `parameters` expects.
+fn function_declarations(tools: Vec<FunctionTool>) -> Vec<Value> {
+    tools
+        .into_iter()
+        .map(|tool| {
+            let mut declaration = json!({ "name": tool.name });
+            if let Some(description) = tool.description {
+                declaration["description"] = Value::String(description);
+            }
+            declaration["parametersJsonSchema"] = tool.parameters;
+            declaration
+        })
+        .collect()
+}
+
+/// Translates the turn input into a system instruction and a list of contents.
+///
+/// - `developer` messages before the first content are folded into the system instruction;
+///   later ones stay in place as tagged user text (see [`tagged_developer_instructions`]).
+/// - Consecutive items with the same role share one content entry.
+/// - Function responses are matched to their call by id to recover the function name, which
+///   Gemini requires.
+/// - Thought summaries are not replayed, but a thought signature (stored in
+///   `encrypted_content`) is attached to the next model part, as Gemini requires for
+///   multi-step function calling.
+pub(crate) fn gemini_contents(
+    instructions: &str,
+    input: &[ResponseItem],
+) -> (Option<String>, Vec<Value>) {
+    let mut builder = GeminiContentsBuilder::default();
+    if !instructions.is_empty() {
+        builder.system.push(instructions.to_string());
+    }
+    for item in input {
+        builder.push_item(item);
+    }
+    let system = (!builder.system.is_empty()).then(|| builder.system.join("\n\n"));
+    (system, builder.contents)
+}
+
+#[derive(Default)]
+struct GeminiContentsBuilder {
+    system: Vec<String>,
+    contents: Vec<Value>,
+    call_names: HashMap<String, String>,
+    pending_signature: Option<String>,
+}
+
+impl GeminiContentsBuilder {
+    fn push_item(&mut self, item: &ResponseItem) {
+        match item {
+            ResponseItem::Message { role, content, .. } => match role.as_str() {
+                "developer" | "system" => {
+                    let text = content
+                        .iter()
+                        .filter_map(|item| match item {
+                            ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                                Some(text.as_str())
+                            }
+                            ContentItem::InputImage { .. } => None,
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    if text.is_empty() {
+                        return;
+                    }
+                    if self.contents.is_empty() {
+                        self.system.push(text);
+                    } else {
+                        let text = tagged_developer_instructions(&text);
+                        self.push_part("user", json!({ "text": text }));
+                    }
+                }
+                "assistant" => {
+                    for item in content {
+                        if let ContentItem::OutputText { text } | ContentItem::InputText { text } =
+                            item
+                        {
+                            self.push_part("model", json!({ "text": text }));
+                        }
+                    }
+                }
+                _ => {
+                    for item in content {
+                        let part = match item {
+                            ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                                json!({ "text": text })
+                            }
+                            ContentItem::InputImage { image_url } => image_part(image_url),
+                        };
+                        self.push_part("user", part);
+                    }
+                }
+            },
+            ResponseItem::Reasoning {
+                encrypted_content, ..
+            } => {
+                if let Some(signature) = encrypted_content {
+                    self.pending_signature = Some(signature.clone());
+                }
+            }
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                let args = serde_json::from_str::<Value>(arguments)
+                    .ok()
+                    .filter(Value::is_object)
+                    .unwrap_or_else(|| json!({}));
+                self.push_function_call(call_id, name, args);
+            }
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => self.push_function_call(call_id, name, json!({ "input": input })),
+            ResponseItem::LocalShellCall {
+                id,
+                call_id,
+                action,
+                ..
+            } => {
+                let Some(call_id) = call_id.as_ref().or(id.as_ref()) else {
+                    return;
+                };
+                self.push_function_call(call_id, "local_shell", local_shell_arguments(action));
+            }
+            ResponseItem::FunctionCallOutput { call_id, output } => {
+                let mut images = Vec::new();
+                let text = match &output.body {
+                    FunctionCallOutputBody::Text(text) => text.clone(),
+                    FunctionCallOutputBody::ContentItems(items) => {
+                        images.extend(items.iter().filter_map(|item| match item {
+                            FunctionCallOutputContentItem::InputImage { image_url } => {
+                                Some(image_part(image_url))
+                            }
+                            FunctionCallOutputContentItem::InputText { .. } => None,
+                        }));
+                        output.body.to_text().unwrap_or_default()
+                    }
+                };
+                self.push_function_response(call_id, text);
+                for image in images {
+                    self.push_part("user", image);
+                }
+            }
+            ResponseItem::CustomToolCallOutput { call_id, output } => {
+                self.push_function_response(call_id, output.clone());
+            }
+            ResponseItem::WebSearchCall { .. }
+            | ResponseItem::GhostSnapshot { .. }
+            | ResponseItem::Compaction { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+
+    fn push_function_call(&mut self, call_id: &str, name: &str, args: Value) {
+        self.call_names
+            .insert(call_id.to_string(), name.to_string());
+        self.push_part(
+            "model",
+            json!({
+                "functionCall": {
+                    "id": call_id,
+                    "name": name,
+                    "args": args,
+                },
+            }),
+        );
+    }
+
+    fn push_function_response(&mut self, call_id: &str, output: String) {
+        let Some(name) = self.call_names.get(call_id).cloned() else {
+            debug!("dropping function output without a matching call: {call_id}");
+            return;
+        };
+        self.push_part(
+            "user",
+            json!({
+                "functionResponse": {
+                    "id": call_id,
+                    "name": name,
+                    "response": { "output": output },
+                },
+            }),
+        );
+    }
+
+    fn push_part(&mut self, role: &str, mut part: Value) {
+        if part.get("text").and_then(Value::as_str) == Some("") {
+            return;
+        }
+        if role == "model"
+            && let Some(signature) = self.pending_signature.take()
+        {
+            part["thoughtSignature"] = Value::String(signature);
+        }
+        if let Some(last) = self.contents.last_mut()
+            && last.get("role").and_then(Value::as_str) == Some(role)
+            && let Some(parts) = last.get_mut("parts").and_then(Value::as_array_mut)
+        {
+            parts.push(part);
+            return;
+        }
+        self.contents.push(json!({
+            "role": role,
+            "parts": [part],
+        }));
+    }
+}
+
+fn image_part(image_url: &str) -> Value {
+    match split_data_url(image_url) {
+        Some((mime_type, data)) => json!({
+            "inlineData": {
+                "mimeType": mime_type,
+                "data": data,
+            },
+        }),
+        None => json!({
+            "fileData": { "fileUri": image_url },
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Reasoning;
+    use crate::common::TextControls;
+    use crate::common::TextFormat;
+    use crate::common::TextFormatType;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn contents_pair_function_responses_and_replay_signatures() {
+        let input = vec![
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "list files".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::Reasoning {
+                id: String::new(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: Some("sig-1".to_string()),
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: "{\"command\":[\"ls\"]}".to_string(),
+                call_id: "call-1".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload {
+                    body: FunctionCallOutputBody::ContentItems(vec![
+                        FunctionCallOutputContentItem::InputText {
+                            text: "a.png".to_string(),
+                        },
+                        FunctionCallOutputContentItem::InputImage {
+                            image_url: "data:image/png;base64,AAAA".to_string(),
+                        },
+                    ]),
+                    success: None,
+                },
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "unknown".to_string(),
+                output: FunctionCallOutputPayload::from_text("dropped".to_string()),
+            },
+        ];
+
+        let (system, contents) = gemini_contents("", &input);
+
+        assert_eq!(system, None);
+        assert_eq!(
+            contents,
+            vec![
+                json!({"role": "user", "parts": [{"text": "list files"}]}),
+                json!({"role": "model", "parts": [{
+                    "functionCall": {"id": "call-1", "name": "shell", "args": {"command": ["ls"]}},
+                    "thoughtSignature": "sig-1",
+                }]}),
+                json!({"role": "user", "parts": [
+                    {"functionResponse": {"id": "call-1", "name": "shell", "response": {"output": "a.png"}}},
+                    {"inlineData": {"mimeType": "image/png", "data": "AAAA"}},
+                ]}),
+            ]
+        );
+    }
+
+    #[test]
+    fn request_maps_tools_thinking_and_output_schema() {
+        let request = ResponsesApiRequest {
+            model: "gemini-2.5-pro".to_string(),
+            instructions: "be brief".to_string(),
+            input: Vec::new(),
+            tools: vec![json!({
+                "type": "function",
+                "name": "shell",
+                "description": "Runs a command",
+                "strict": false,
+                "parameters": {"type": "object", "properties": {}},
+            })],
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: true,
+            reasoning: Some(Reasoning {
+                effort: Some(ReasoningEffortConfig::High),
+                summary: None,
+            }),
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            prompt_cache_key: None,
+            text: Some(TextControls {
+                verbosity: None,
+                format: Some(TextFormat {
+                    r#type: TextFormatType::JsonSchema,
+                    strict: true,
+                    schema: json!({"type": "object"}),
+                    name: "codex_output_schema".to_string(),
+                }),
+            }),
+        };
+
+        let body = serde_json::to_value(
+            GeminiGenerateContentRequest::try_from(&request).expect("build request"),
+        )
+        .expect("serialize request");
+
+        assert_eq!(
+            body,
+            json!({
+                "contents": [],
+                "systemInstruction": {"parts": [{"text": "be brief"}]},
+                "tools": [{"functionDeclarations": [{
+                    "name": "shell",
+                    "description": "Runs a command",
+                    "parametersJsonSchema": {"type": "object", "properties": {}},
+                }]}],
+                "toolConfig": {"functionCallingConfig": {"mode": "AUTO"}},
+                "generationConfig": {
+                    "thinkingConfig": {"includeThoughts": true},
+                    "responseMimeType": "application/json",
+                    "responseJsonSchema": {"type": "object"},
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn freeform_tools_are_declared_and_unmappable_tools_rejected() {
+        let mut request = ResponsesApiRequest {
+            model: "gemini-2.5-pro".to_string(),
+            instructions: String::new(),
+            input: Vec::new(),
+            tools: vec![json!({
+                "type": "custom",
+                "name": "apply_patch",
+                "description": "Edit files",
+                "format": {"type": "grammar", "syntax": "lark", "definition": "start: PATCH"},
+            })],
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: true,
+            reasoning: None,
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            prompt_cache_key: None,
+            text: None,
+        };
+
+        let gemini = GeminiGenerateContentRequest::try_from(&request).expect("build request");
+        assert_eq!(gemini.freeform_tools, vec!["apply_patch".to_string()]);
+        assert_eq!(
+            gemini.tools[0]["functionDeclarations"][0]["parametersJsonSchema"]["required"],
+            json!(["input"])
+        );
+
+        request.tools = vec![json!({"type": "web_search"})];
+        let err =
+            GeminiGenerateContentRequest::try_from(&request).expect_err("web_search is hosted");
+        assert_eq!(
+            err.to_string(),
+            "invalid request: tool `web_search` of type `web_search` has no equivalent in the gemini API"
+        );
+    }
+}
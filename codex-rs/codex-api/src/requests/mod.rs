@@ -1,2 +1,157 @@
+pub mod anthropic;
+pub mod chat;
+pub mod gemini;
 pub(crate) mod headers;
 pub mod responses;
+
+use crate::error::ApiError;
+use codex_protocol::models::LocalShellAction;
+use serde_json::Value;
+use serde_json::json;
+
+/// Splits a `data:<media type>;base64,<payload>` URL into its media type and payload.
+pub(crate) fn split_data_url(url: &str) -> Option<(&str, &str)> {
+    url.strip_prefix("data:")?.split_once(";base64,")
+}
+
+/// Developer instructions that arrive after the conversation started are sent in place as user
+/// text with this tag, rather than moved into the leading system prompt: moving them would lose
+/// their position and change the prompt prefix on every turn, defeating prompt caching.
+pub(crate) fn tagged_developer_instructions(text: &str) -> String {
+    format!("<developer_instructions>\n{text}\n</developer_instructions>")
+}
+
+/// A Responses tool definition reduced to the plain function shape the chat completions,
+/// Messages and Gemini APIs accept.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FunctionTool {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) parameters: Value,
+    pub(crate) strict: Option<bool>,
+    /// Offered in place of a freeform tool: the call's arguments are `{"input": <raw input>}`,
+    /// and the stream turns them back into a custom tool call.
+    pub(crate) freeform: bool,
+}
+
+/// Converts Responses tool definitions for a wire API that only knows function tools:
+///
+/// - `function` tools keep their schema.
+/// - `custom` (freeform) tools take their raw input as a single `input` string.
+/// - `local_shell` is offered as a `local_shell` function with the `shell` tool's parameters,
+///   matching how [`local_shell_arguments`] replays earlier calls.
+///
+/// Any other tool is an error: dropping it would leave the model unable to call a tool Codex
+/// believes it offered.
+pub(crate) fn function_tools(tools: &[Value], api: &str) -> Result<Vec<FunctionTool>, ApiError> {
+    tools
+        .iter()
+        .map(|tool| {
+            let kind = tool.get("type").and_then(Value::as_str).unwrap_or_default();
+            let name = tool.get("name").and_then(Value::as_str);
+            let description = tool
+                .get("description")
+                .and_then(Value::as_str)
+                .map(ToString::to_string);
+            match (kind, name) {
+                ("function", Some(name)) => Ok(FunctionTool {
+                    name: name.to_string(),
+                    description,
+                    parameters: tool
+                        .get("parameters")
+                        .cloned()
+                        .unwrap_or_else(|| json!({ "type": "object" })),
+                    strict: tool.get("strict").and_then(Value::as_bool),
+                    freeform: false,
+                }),
+                ("custom", Some(name)) => Ok(FunctionTool {
+                    name: name.to_string(),
+                    description,
+                    parameters: freeform_parameters(tool.get("format")),
+                    strict: None,
+                    freeform: true,
+                }),
+                ("local_shell", _) => Ok(FunctionTool {
+                    name: "local_shell".to_string(),
+                    description: Some(
+                        "Runs a command and returns its output. `command` is passed to execvp(); \
+                         most commands should be prefixed with [\"bash\", \"-lc\"]."
+                            .to_string(),
+                    ),
+                    parameters: json!({
+                        "type": "object",
+                        "properties": {
+                            "command": {
+                                "type": "array",
+                                "items": { "type": "string" },
+                                "description": "The command to execute",
+                            },
+                            "workdir": {
+                                "type": "string",
+                                "description": "The working directory to execute the command in",
+                            },
+                            "timeout_ms": {
+                                "type": "number",
+                                "description": "The timeout for the command in milliseconds",
+                            },
+                        },
+                        "required": ["command"],
+                        "additionalProperties": false,
+                    }),
+                    strict: None,
+                    freeform: false,
+                }),
+                _ => Err(ApiError::InvalidRequest {
+                    message: format!(
+                        "tool `{}` of type `{kind}` has no equivalent in the {api} API",
+                        name.unwrap_or(kind)
+                    ),
+                }),
+            }
+        })
+        .collect()
+}
+
+/// A single `input` string, described by the freeform tool's grammar so the model still knows
+/// what to write.
+fn freeform_parameters(format: Option<&Value>) -> Value {
+    let syntax = format
+        .and_then(|format| format.get("syntax"))
+        .and_then(Value::as_str);
+    let definition = format
+        .and_then(|format| format.get("definition"))
+        .and_then(Value::as_str);
+    let description = match (syntax, definition) {
+        (Some(syntax), Some(definition)) => {
+            format!("Raw tool input matching this {syntax} grammar:\n{definition}")
+        }
+        _ => "Raw tool input".to_string(),
+    };
+    json!({
+        "type": "object",
+        "properties": {
+            "input": {
+                "type": "string",
+                "description": description,
+            },
+        },
+        "required": ["input"],
+        "additionalProperties": false,
+    })
+}
+
+/// Arguments for replaying a `local_shell` call as the function [`function_tools`] offers.
+pub(crate) fn local_shell_arguments(action: &LocalShellAction) -> Value {
+    match action {
+        LocalShellAction::Exec(exec) => {
+            let mut arguments = json!({ "command": exec.command });
+            if let Some(workdir) = &exec.working_directory {
+                arguments["workdir"] = json!(workdir);
+            }
+            if let Some(timeout_ms) = exec.timeout_ms {
+                arguments["timeout_ms"] = json!(timeout_ms);
+            }
+            arguments
+        }
+    }
+}
@@ -0,0 +1,703 @@
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::sse::tool_call_item;
+use crate::telemetry::SseTelemetry;
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+pub fn spawn_anthropic_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    freeform_tools: Vec<String>,
+) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(process_anthropic_sse(
+        stream_response.bytes,
+        tx_event,
+        idle_timeout,
+        telemetry,
+        freeform_tools,
+    ));
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum MessagesStreamEvent {
+    MessageStart {
+        message: MessageStart,
+    },
+    ContentBlockStart {
+        index: usize,
+        content_block: ContentBlock,
+    },
+    ContentBlockDelta {
+        index: usize,
+        delta: ContentBlockDelta,
+    },
+    ContentBlockStop {
+        index: usize,
+    },
+    MessageDelta {
+        #[serde(default)]
+        delta: Option<MessageDeltaBody>,
+        #[serde(default)]
+        usage: Option<MessagesUsage>,
+    },
+    MessageStop,
+    Ping,
+    Error {
+        error: MessagesError,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageStart {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    usage: Option<MessagesUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageDeltaBody {
+    #[serde(default)]
+    stop_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ContentBlock {
+    Text {
+        #[serde(default)]
+        text: String,
+    },
+    Thinking {
+        #[serde(default)]
+        thinking: String,
+        #[serde(default)]
+        signature: Option<String>,
+    },
+    RedactedThinking {
+        data: String,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ContentBlockDelta {
+    TextDelta {
+        text: String,
+    },
+    ThinkingDelta {
+        thinking: String,
+    },
+    SignatureDelta {
+        signature: String,
+    },
+    InputJsonDelta {
+        partial_json: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+/// Usage arrives in two halves: input counts on `message_start` and the running output count on
+/// `message_delta`.
+#[derive(Debug, Default, Deserialize)]
+struct MessagesUsage {
+    #[serde(default)]
+    input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<i64>,
+    #[serde(default)]
+    output_tokens: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessagesError {
+    #[serde(default, rename = "type")]
+    kind: String,
+    #[serde(default)]
+    message: String,
+}
+
+impl MessagesError {
+    fn into_api_error(self) -> ApiError {
+        match self.kind.as_str() {
+            "overloaded_error" => ApiError::ServerOverloaded,
+            "invalid_request_error" if self.message.contains("prompt is too long") => {
+                ApiError::ContextWindowExceeded
+            }
+            "rate_limit_error" => ApiError::RateLimit(self.message),
+            _ => ApiError::Stream(if self.message.is_empty() {
+                "messages stream returned an error".to_string()
+            } else {
+                self.message
+            }),
+        }
+    }
+}
+
+/// A content block receiving deltas. Text and thinking blocks carry the id synthesized for their
+/// `OutputItemAdded` event so the matching `OutputItemDone` agrees with it.
+#[derive(Debug)]
+enum PendingBlock {
+    Text {
+        id: String,
+        text: String,
+    },
+    Thinking {
+        id: String,
+        thinking: String,
+        signature: Option<String>,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+        input: String,
+    },
+}
+
+/// Accumulates content blocks into the item-level events the Responses pipeline emits. Text and
+/// thinking blocks are their own output items, completed on `content_block_stop`. Tool calls are
+/// held until `message_stop`, since only the `message_delta` before it says whether `max_tokens`
+/// cut their input short.
+#[derive(Debug, Default)]
+struct AnthropicStreamState {
+    response_id: String,
+    blocks: BTreeMap<usize, PendingBlock>,
+    tool_calls: Vec<ResponseItem>,
+    freeform_tools: Vec<String>,
+    truncated: bool,
+    input_tokens: i64,
+    cached_input_tokens: i64,
+    output_tokens: i64,
+    has_usage: bool,
+}
+
+impl AnthropicStreamState {
+    fn handle_event(&mut self, event: MessagesStreamEvent) -> Result<Vec<ResponseEvent>, ApiError> {
+        let mut events = Vec::new();
+        match event {
+            MessagesStreamEvent::MessageStart { message } => {
+                self.response_id = message.id;
+                if let Some(usage) = message.usage {
+                    self.record_usage(usage);
+                }
+                events.push(ResponseEvent::Created);
+            }
+            MessagesStreamEvent::ContentBlockStart {
+                index,
+                content_block,
+            } => {
+                let block = match content_block {
+                    ContentBlock::Text { text } => {
+                        let id = format!("msg_{}_{index}", self.response_id);
+                        events.push(ResponseEvent::OutputItemAdded(assistant_message(
+                            id.clone(),
+                            String::new(),
+                        )));
+                        if !text.is_empty() {
+                            events.push(ResponseEvent::OutputTextDelta(text.clone()));
+                        }
+                        PendingBlock::Text { id, text }
+                    }
+                    ContentBlock::Thinking {
+                        thinking,
+                        signature,
+                    } => {
+                        let id = format!("rs_{}_{index}", self.response_id);
+                        events.push(ResponseEvent::OutputItemAdded(reasoning_item(
+                            id.clone(),
+                            String::new(),
+                            None,
+                        )));
+                        PendingBlock::Thinking {
+                            id,
+                            thinking,
+                            signature,
+                        }
+                    }
+                    ContentBlock::RedactedThinking { data } => {
+                        let id = format!("rs_{}_{index}", self.response_id);
+                        events.push(ResponseEvent::OutputItemAdded(reasoning_item(
+                            id.clone(),
+                            String::new(),
+                            None,
+                        )));
+                        PendingBlock::Thinking {
+                            id,
+                            thinking: String::new(),
+                            signature: Some(data),
+                        }
+                    }
+                    ContentBlock::ToolUse { id, name } => PendingBlock::ToolUse {
+                        id,
+                        name,
+                        input: String::new(),
+                    },
+                    ContentBlock::Unknown => {
+                        debug!("ignoring unsupported content block at index {index}");
+                        return Ok(events);
+                    }
+                };
+                self.blocks.insert(index, block);
+            }
+            MessagesStreamEvent::ContentBlockDelta { index, delta } => {
+                let Some(block) = self.blocks.get_mut(&index) else {
+                    return Ok(events);
+                };
+                match (block, delta) {
+                    (
+                        PendingBlock::Text { text, .. },
+                        ContentBlockDelta::TextDelta { text: delta },
+                    ) => {
+                        text.push_str(&delta);
+                        events.push(ResponseEvent::OutputTextDelta(delta));
+                    }
+                    (
+                        PendingBlock::Thinking { thinking, .. },
+                        ContentBlockDelta::ThinkingDelta { thinking: delta },
+                    ) => {
+                        thinking.push_str(&delta);
+                        events.push(ResponseEvent::ReasoningContentDelta {
+                            delta,
+                            content_index: 0,
+                        });
+                    }
+                    (
+                        PendingBlock::Thinking { signature, .. },
+                        ContentBlockDelta::SignatureDelta { signature: delta },
+                    ) => {
+                        signature.get_or_insert_with(String::new).push_str(&delta);
+                    }
+                    (
+                        PendingBlock::ToolUse { input, .. },
+                        ContentBlockDelta::InputJsonDelta { partial_json },
+                    ) => input.push_str(&partial_json),
+                    (_, delta) => debug!("ignoring mismatched delta for block {index}: {delta:?}"),
+                }
+            }
+            MessagesStreamEvent::ContentBlockStop { index } => {
+                let Some(block) = self.blocks.remove(&index) else {
+                    return Ok(events);
+                };
+                let item = match block {
+                    PendingBlock::Text { id, text } => assistant_message(id, text),
+                    PendingBlock::Thinking {
+                        id,
+                        thinking,
+                        signature,
+                    } => reasoning_item(id, thinking, signature),
+                    PendingBlock::ToolUse { id, name, input } => {
+                        let arguments = if input.is_empty() {
+                            "{}".to_string()
+                        } else {
+                            input
+                        };
+                        self.tool_calls.push(tool_call_item(
+                            name,
+                            arguments,
+                            id,
+                            &self.freeform_tools,
+                        ));
+                        return Ok(events);
+                    }
+                };
+                events.push(ResponseEvent::OutputItemDone(item));
+            }
+            MessagesStreamEvent::MessageDelta { delta, usage } => {
+                if let Some(stop_reason) = delta.and_then(|delta| delta.stop_reason) {
+                    self.truncated = stop_reason == "max_tokens";
+                }
+                if let Some(usage) = usage {
+                    self.record_usage(usage);
+                }
+            }
+            MessagesStreamEvent::MessageStop => {
+                if self.truncated {
+                    return Err(ApiError::Stream(
+                        "Incomplete response returned, reason: max_output_tokens".to_string(),
+                    ));
+                }
+                events.extend(
+                    std::mem::take(&mut self.tool_calls)
+                        .into_iter()
+                        .map(ResponseEvent::OutputItemDone),
+                );
+                events.push(ResponseEvent::Completed {
+                    response_id: std::mem::take(&mut self.response_id),
+                    token_usage: self.token_usage(),
+                    can_append: false,
+                });
+            }
+            MessagesStreamEvent::Ping => {}
+            MessagesStreamEvent::Error { error } => return Err(error.into_api_error()),
+        }
+        Ok(events)
+    }
+
+    fn record_usage(&mut self, usage: MessagesUsage) {
+        self.has_usage = true;
+        if let Some(input_tokens) = usage.input_tokens {
+            // `input_tokens` excludes cached tokens; Responses counts them as part of the input.
+            self.input_tokens = input_tokens
+                + usage.cache_creation_input_tokens.unwrap_or(0)
+                + usage.cache_read_input_tokens.unwrap_or(0);
+        }
+        if let Some(cached) = usage.cache_read_input_tokens {
+            self.cached_input_tokens = cached;
+        }
+        if let Some(output_tokens) = usage.output_tokens {
+            self.output_tokens = output_tokens;
+        }
+    }
+
+    fn token_usage(&self) -> Option<TokenUsage> {
+        self.has_usage.then(|| TokenUsage {
+            input_tokens: self.input_tokens,
+            cached_input_tokens: self.cached_input_tokens,
+            output_tokens: self.output_tokens,
+            reasoning_output_tokens: 0,
+            total_tokens: self.input_tokens + self.output_tokens,
+        })
+    }
+}
+
+/// Thinking text is kept as reasoning content and the signature rides in `encrypted_content`
+/// so the block can be replayed on the next request.
+fn reasoning_item(id: String, text: String, signature: Option<String>) -> ResponseItem {
+    ResponseItem::Reasoning {
+        id,
+        summary: Vec::new(),
+        content: Some(if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![ReasoningItemContent::ReasoningText { text }]
+        }),
+        encrypted_content: signature,
+    }
+}
+
+fn assistant_message(id: String, text: String) -> ResponseItem {
+    ResponseItem::Message {
+        id: Some(id),
+        role: "assistant".to_string(),
+        content: if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![ContentItem::OutputText { text }]
+        },
+        end_turn: None,
+        phase: None,
+    }
+}
+
+pub async fn process_anthropic_sse(
+    stream: ByteStream,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    freeform_tools: Vec<String>,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = AnthropicStreamState {
+        freeform_tools,
+        ..Default::default()
+    };
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream(
+                        "stream closed before message_stop".into(),
+                    )))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", &sse.data);
+
+        let event: MessagesStreamEvent = match serde_json::from_str(&sse.data) {
+            Ok(event) => event,
+            Err(e) => {
+                debug!(
+                    "Failed to parse messages stream event: {e}, data: {}",
+                    &sse.data
+                );
+                continue;
+            }
+        };
+        let completed = matches!(event, MessagesStreamEvent::MessageStop);
+
+        match state.handle_event(event) {
+            Ok(events) => {
+                for event in events {
+                    if tx_event.send(Ok(event)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(error) => {
+                let _ = tx_event.send(Err(error)).await;
+                return;
+            }
+        }
+        if completed {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use codex_client::TransportError;
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+    use tokio_test::io::Builder as IoBuilder;
+    use tokio_util::io::ReaderStream;
+
+    async fn collect_events(body: &str) -> Vec<Result<ResponseEvent, ApiError>> {
+        let reader = IoBuilder::new().read(body.as_bytes()).build();
+        let stream =
+            ReaderStream::new(reader).map_err(|err| TransportError::Network(err.to_string()));
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(16);
+        tokio::spawn(process_anthropic_sse(
+            Box::pin(stream),
+            tx,
+            Duration::from_millis(1000),
+            None,
+            Vec::new(),
+        ));
+
+        let mut events = Vec::new();
+        while let Some(ev) = rx.recv().await {
+            events.push(ev);
+        }
+        events
+    }
+
+    fn sse(events: &[serde_json::Value]) -> String {
+        let mut body = String::new();
+        for event in events {
+            let name = event["type"].as_str().unwrap_or_default();
+            body.push_str(&format!("event: {name}\ndata: {event}\n\n"));
+        }
+        body
+    }
+
+    #[tokio::test]
+    async fn maps_thinking_and_tool_use_blocks() {
+        let body = sse(&[
+            serde_json::json!({"type": "message_start", "message": {
+                "id": "msg_1",
+                "usage": {"input_tokens": 10, "cache_read_input_tokens": 90, "output_tokens": 1},
+            }}),
+            serde_json::json!({"type": "content_block_start", "index": 0,
+                "content_block": {"type": "thinking", "thinking": ""}}),
+            serde_json::json!({"type": "content_block_delta", "index": 0,
+                "delta": {"type": "thinking_delta", "thinking": "list it"}}),
+            serde_json::json!({"type": "content_block_delta", "index": 0,
+                "delta": {"type": "signature_delta", "signature": "sig"}}),
+            serde_json::json!({"type": "content_block_stop", "index": 0}),
+            serde_json::json!({"type": "content_block_start", "index": 1,
+                "content_block": {"type": "tool_use", "id": "toolu_1", "name": "shell", "input": {}}}),
+            serde_json::json!({"type": "content_block_delta", "index": 1,
+                "delta": {"type": "input_json_delta", "partial_json": "{\"command\":"}}),
+            serde_json::json!({"type": "content_block_delta", "index": 1,
+                "delta": {"type": "input_json_delta", "partial_json": "[\"ls\"]}"}}),
+            serde_json::json!({"type": "content_block_stop", "index": 1}),
+            serde_json::json!({"type": "message_delta",
+                "delta": {"stop_reason": "tool_use"}, "usage": {"output_tokens": 42}}),
+            serde_json::json!({"type": "message_stop"}),
+        ]);
+
+        let events: Vec<ResponseEvent> = collect_events(&body)
+            .await
+            .into_iter()
+            .map(|event| event.expect("messages stream event"))
+            .collect();
+
+        assert_eq!(events.len(), 6);
+        assert_matches!(events[0], ResponseEvent::Created);
+        assert_matches!(
+            &events[1],
+            ResponseEvent::OutputItemAdded(ResponseItem::Reasoning { id, .. }) if id == "rs_msg_1_0"
+        );
+        assert_matches!(
+            &events[2],
+            ResponseEvent::ReasoningContentDelta { delta, .. } if delta == "list it"
+        );
+        assert_matches!(
+            &events[3],
+            ResponseEvent::OutputItemDone(item)
+                if item == &reasoning_item(
+                    "rs_msg_1_0".to_string(),
+                    "list it".to_string(),
+                    Some("sig".to_string()),
+                )
+        );
+        assert_matches!(
+            &events[4],
+            ResponseEvent::OutputItemDone(ResponseItem::FunctionCall { name, arguments, call_id, .. })
+                if name == "shell" && arguments == "{\"command\":[\"ls\"]}" && call_id == "toolu_1"
+        );
+        let ResponseEvent::Completed {
+            response_id,
+            token_usage,
+            ..
+        } = &events[5]
+        else {
+            panic!("expected completed event, got {:?}", events[5]);
+        };
+        assert_eq!(response_id, "msg_1");
+        assert_eq!(
+            token_usage,
+            &Some(TokenUsage {
+                input_tokens: 100,
+                cached_input_tokens: 90,
+                output_tokens: 42,
+                reasoning_output_tokens: 0,
+                total_tokens: 142,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn text_blocks_keep_their_id_from_added_to_done() {
+        let body = sse(&[
+            serde_json::json!({"type": "message_start", "message": {"id": "msg_3"}}),
+            serde_json::json!({"type": "content_block_start", "index": 0,
+                "content_block": {"type": "redacted_thinking", "data": "opaque"}}),
+            serde_json::json!({"type": "content_block_stop", "index": 0}),
+            serde_json::json!({"type": "content_block_start", "index": 1,
+                "content_block": {"type": "text", "text": ""}}),
+            serde_json::json!({"type": "content_block_delta", "index": 1,
+                "delta": {"type": "text_delta", "text": "Done."}}),
+            serde_json::json!({"type": "content_block_stop", "index": 1}),
+            serde_json::json!({"type": "message_delta", "delta": {"stop_reason": "end_turn"}}),
+            serde_json::json!({"type": "message_stop"}),
+        ]);
+
+        let events: Vec<ResponseEvent> = collect_events(&body)
+            .await
+            .into_iter()
+            .map(|event| event.expect("messages stream event"))
+            .collect();
+
+        let ids: Vec<(&str, Option<String>)> = events
+            .iter()
+            .filter_map(|event| match event {
+                ResponseEvent::OutputItemAdded(ResponseItem::Reasoning { id, .. }) => {
+                    Some(("added", Some(id.clone())))
+                }
+                ResponseEvent::OutputItemDone(ResponseItem::Reasoning { id, .. }) => {
+                    Some(("done", Some(id.clone())))
+                }
+                ResponseEvent::OutputItemAdded(ResponseItem::Message { id, .. }) => {
+                    Some(("added", id.clone()))
+                }
+                ResponseEvent::OutputItemDone(ResponseItem::Message { id, .. }) => {
+                    Some(("done", id.clone()))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                ("added", Some("rs_msg_3_0".to_string())),
+                ("done", Some("rs_msg_3_0".to_string())),
+                ("added", Some("msg_msg_3_1".to_string())),
+                ("done", Some("msg_msg_3_1".to_string())),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn max_tokens_stop_reason_is_an_incomplete_response() {
+        let body = sse(&[
+            serde_json::json!({"type": "message_start", "message": {"id": "msg_4"}}),
+            serde_json::json!({"type": "content_block_start", "index": 0,
+                "content_block": {"type": "tool_use", "id": "toolu_1", "name": "shell", "input": {}}}),
+            serde_json::json!({"type": "content_block_delta", "index": 0,
+                "delta": {"type": "input_json_delta", "partial_json": "{\"command\":"}}),
+            serde_json::json!({"type": "content_block_stop", "index": 0}),
+            serde_json::json!({"type": "message_delta", "delta": {"stop_reason": "max_tokens"}}),
+            serde_json::json!({"type": "message_stop"}),
+        ]);
+
+        let events = collect_events(&body).await;
+
+        assert_matches!(
+            events.as_slice(),
+            [
+                Ok(ResponseEvent::Created),
+                Err(ApiError::Stream(message)),
+            ] if message == "Incomplete response returned, reason: max_output_tokens"
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_truncated_stream_and_error_events() {
+        let truncated = sse(&[
+            serde_json::json!({"type": "message_start", "message": {"id": "msg_2"}}),
+            serde_json::json!({"type": "content_block_start", "index": 0,
+                "content_block": {"type": "text", "text": ""}}),
+        ]);
+        let events = collect_events(&truncated).await;
+        assert_matches!(events.last(), Some(Err(ApiError::Stream(_))));
+
+        let overloaded = sse(&[serde_json::json!({"type": "error",
+            "error": {"type": "overloaded_error", "message": "Overloaded"}})]);
+        let events = collect_events(&overloaded).await;
+        assert_matches!(events.as_slice(), [Err(ApiError::ServerOverloaded)]);
+    }
+}
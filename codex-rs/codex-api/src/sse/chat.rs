@@ -0,0 +1,664 @@
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::sse::tool_call_item;
+use crate::telemetry::SseTelemetry;
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+const DONE_SENTINEL: &str = "[DONE]";
+
+pub fn spawn_chat_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    freeform_tools: Vec<String>,
+) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(process_chat_sse(
+        stream_response.bytes,
+        tx_event,
+        idle_timeout,
+        telemetry,
+        freeform_tools,
+    ));
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunk {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    choices: Vec<ChatChoice>,
+    #[serde(default)]
+    usage: Option<ChatUsage>,
+    #[serde(default)]
+    error: Option<ChatError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    #[serde(default)]
+    delta: Option<ChatDelta>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatDelta {
+    #[serde(default)]
+    content: Option<String>,
+    /// vLLM, llama.cpp and DeepSeek stream reasoning here.
+    #[serde(default)]
+    reasoning_content: Option<String>,
+    /// Ollama and OpenRouter stream reasoning here.
+    #[serde(default)]
+    reasoning: Option<String>,
+    #[serde(default)]
+    tool_calls: Option<Vec<ChatToolCallDelta>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatToolCallDelta {
+    #[serde(default)]
+    index: Option<usize>,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    function: Option<ChatFunctionDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatFunctionDelta {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    arguments: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatUsage {
+    #[serde(default)]
+    prompt_tokens: i64,
+    #[serde(default)]
+    completion_tokens: i64,
+    #[serde(default)]
+    total_tokens: i64,
+    #[serde(default)]
+    prompt_tokens_details: Option<ChatPromptTokensDetails>,
+    #[serde(default)]
+    completion_tokens_details: Option<ChatCompletionTokensDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatPromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionTokensDetails {
+    #[serde(default)]
+    reasoning_tokens: i64,
+}
+
+impl From<ChatUsage> for TokenUsage {
+    fn from(val: ChatUsage) -> Self {
+        TokenUsage {
+            input_tokens: val.prompt_tokens,
+            cached_input_tokens: val
+                .prompt_tokens_details
+                .map(|d| d.cached_tokens)
+                .unwrap_or(0),
+            output_tokens: val.completion_tokens,
+            reasoning_output_tokens: val
+                .completion_tokens_details
+                .map(|d| d.reasoning_tokens)
+                .unwrap_or(0),
+            total_tokens: val.total_tokens,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatError {
+    #[serde(default)]
+    code: Option<Value>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+impl ChatError {
+    fn into_api_error(self) -> ApiError {
+        if self.code.as_ref().and_then(Value::as_str) == Some("context_length_exceeded") {
+            return ApiError::ContextWindowExceeded;
+        }
+        ApiError::Stream(
+            self.message
+                .unwrap_or_else(|| "chat completions stream returned an error".to_string()),
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+struct PendingToolCall {
+    id: Option<String>,
+    name: String,
+    arguments: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StreamedItemKind {
+    Reasoning,
+    Message,
+}
+
+/// The reasoning or assistant message currently receiving deltas. Its id is synthesized once so
+/// the `OutputItemAdded` and `OutputItemDone` events for it agree.
+#[derive(Debug)]
+struct StreamedItem {
+    kind: StreamedItemKind,
+    id: String,
+    text: String,
+}
+
+impl StreamedItem {
+    fn to_item(&self, text: String) -> ResponseItem {
+        match self.kind {
+            StreamedItemKind::Reasoning => reasoning_item(self.id.clone(), text),
+            StreamedItemKind::Message => assistant_message(self.id.clone(), text),
+        }
+    }
+}
+
+/// Accumulates chat deltas into the item-level events the Responses pipeline emits. Core tracks
+/// one active item at a time, so reasoning is completed before the message that follows it is
+/// opened.
+#[derive(Debug, Default)]
+struct ChatStreamState {
+    response_id: Option<String>,
+    created: bool,
+    open_item: Option<StreamedItem>,
+    items_opened: usize,
+    tool_calls: BTreeMap<usize, PendingToolCall>,
+    freeform_tools: Vec<String>,
+    token_usage: Option<TokenUsage>,
+    finished: bool,
+    truncated: bool,
+}
+
+impl ChatStreamState {
+    fn handle_chunk(&mut self, chunk: ChatCompletionChunk) -> Vec<ResponseEvent> {
+        let mut events = Vec::new();
+        if !self.created {
+            self.created = true;
+            events.push(ResponseEvent::Created);
+        }
+        if self.response_id.is_none() {
+            self.response_id = chunk.id;
+        }
+        if let Some(usage) = chunk.usage {
+            self.token_usage = Some(usage.into());
+        }
+
+        for choice in chunk.choices {
+            if let Some(finish_reason) = choice.finish_reason {
+                self.finished = true;
+                // The output hit `max_tokens`; any tool call arguments are cut off mid-JSON.
+                self.truncated = finish_reason == "length";
+            }
+            let Some(delta) = choice.delta else {
+                continue;
+            };
+            if let Some(reasoning) = delta.reasoning_content.or(delta.reasoning)
+                && !reasoning.is_empty()
+            {
+                self.push_item_text(StreamedItemKind::Reasoning, &reasoning, &mut events);
+                events.push(ResponseEvent::ReasoningContentDelta {
+                    delta: reasoning,
+                    content_index: 0,
+                });
+            }
+            if let Some(content) = delta.content
+                && !content.is_empty()
+            {
+                self.push_item_text(StreamedItemKind::Message, &content, &mut events);
+                events.push(ResponseEvent::OutputTextDelta(content));
+            }
+            for (position, tool_call) in delta.tool_calls.into_iter().flatten().enumerate() {
+                let index = tool_call.index.unwrap_or(position);
+                let pending = self.tool_calls.entry(index).or_default();
+                if let Some(id) = tool_call.id {
+                    pending.id = Some(id);
+                }
+                if let Some(function) = tool_call.function {
+                    if let Some(name) = function.name {
+                        pending.name.push_str(&name);
+                    }
+                    if let Some(arguments) = function.arguments {
+                        pending.arguments.push_str(&arguments);
+                    }
+                }
+            }
+        }
+
+        events
+    }
+
+    /// Appends `text` to the open item of `kind`, first completing an open item of the other kind
+    /// and opening a new one as needed.
+    fn push_item_text(
+        &mut self,
+        kind: StreamedItemKind,
+        text: &str,
+        events: &mut Vec<ResponseEvent>,
+    ) {
+        if self
+            .open_item
+            .as_ref()
+            .is_some_and(|item| item.kind != kind)
+        {
+            events.extend(self.close_item());
+        }
+        let response_id = self.response_id.as_deref().unwrap_or_default();
+        let items_opened = &mut self.items_opened;
+        let item = self.open_item.get_or_insert_with(|| {
+            let prefix = match kind {
+                StreamedItemKind::Reasoning => "rs",
+                StreamedItemKind::Message => "msg",
+            };
+            let item = StreamedItem {
+                kind,
+                id: format!("{prefix}_{response_id}_{items_opened}"),
+                text: String::new(),
+            };
+            *items_opened += 1;
+            events.push(ResponseEvent::OutputItemAdded(item.to_item(String::new())));
+            item
+        });
+        item.text.push_str(text);
+    }
+
+    fn close_item(&mut self) -> Option<ResponseEvent> {
+        let mut item = self.open_item.take()?;
+        let text = std::mem::take(&mut item.text);
+        Some(ResponseEvent::OutputItemDone(item.to_item(text)))
+    }
+
+    fn finish(mut self) -> Result<Vec<ResponseEvent>, ApiError> {
+        if self.truncated {
+            return Err(ApiError::Stream(
+                "Incomplete response returned, reason: max_output_tokens".to_string(),
+            ));
+        }
+        let mut events = Vec::new();
+        if !self.created {
+            events.push(ResponseEvent::Created);
+        }
+        events.extend(self.close_item());
+        let response_id = self.response_id.unwrap_or_default();
+        for (index, tool_call) in self.tool_calls {
+            let call_id = tool_call
+                .id
+                .unwrap_or_else(|| format!("call_{response_id}_{index}"));
+            events.push(ResponseEvent::OutputItemDone(tool_call_item(
+                tool_call.name,
+                tool_call.arguments,
+                call_id,
+                &self.freeform_tools,
+            )));
+        }
+        events.push(ResponseEvent::Completed {
+            response_id,
+            token_usage: self.token_usage,
+            can_append: false,
+        });
+        Ok(events)
+    }
+}
+
+fn reasoning_item(id: String, text: String) -> ResponseItem {
+    ResponseItem::Reasoning {
+        id,
+        summary: Vec::new(),
+        content: Some(if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![ReasoningItemContent::ReasoningText { text }]
+        }),
+        encrypted_content: None,
+    }
+}
+
+fn assistant_message(id: String, text: String) -> ResponseItem {
+    ResponseItem::Message {
+        id: Some(id),
+        role: "assistant".to_string(),
+        content: if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![ContentItem::OutputText { text }]
+        },
+        end_turn: None,
+        phase: None,
+    }
+}
+
+pub async fn process_chat_sse(
+    stream: ByteStream,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    freeform_tools: Vec<String>,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = ChatStreamState {
+        freeform_tools,
+        ..Default::default()
+    };
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                // Some servers close the stream after the final chunk without sending `[DONE]`.
+                if state.finished {
+                    break;
+                }
+                let _ = tx_event
+                    .send(Err(ApiError::Stream(
+                        "stream closed before chat completion finished".into(),
+                    )))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", &sse.data);
+
+        if sse.data.trim() == DONE_SENTINEL {
+            break;
+        }
+
+        let chunk: ChatCompletionChunk = match serde_json::from_str(&sse.data) {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                debug!(
+                    "Failed to parse chat completion chunk: {e}, data: {}",
+                    &sse.data
+                );
+                continue;
+            }
+        };
+        if let Some(error) = chunk.error {
+            let _ = tx_event.send(Err(error.into_api_error())).await;
+            return;
+        }
+
+        for event in state.handle_chunk(chunk) {
+            if tx_event.send(Ok(event)).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    let events = match state.finish() {
+        Ok(events) => events,
+        Err(err) => {
+            let _ = tx_event.send(Err(err)).await;
+            return;
+        }
+    };
+    for event in events {
+        if tx_event.send(Ok(event)).await.is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use codex_client::TransportError;
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+    use tokio_test::io::Builder as IoBuilder;
+    use tokio_util::io::ReaderStream;
+
+    async fn collect_events(body: &str) -> Vec<Result<ResponseEvent, ApiError>> {
+        collect_events_with_freeform_tools(body, Vec::new()).await
+    }
+
+    async fn collect_events_with_freeform_tools(
+        body: &str,
+        freeform_tools: Vec<String>,
+    ) -> Vec<Result<ResponseEvent, ApiError>> {
+        let reader = IoBuilder::new().read(body.as_bytes()).build();
+        let stream =
+            ReaderStream::new(reader).map_err(|err| TransportError::Network(err.to_string()));
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(16);
+        tokio::spawn(process_chat_sse(
+            Box::pin(stream),
+            tx,
+            Duration::from_millis(1000),
+            None,
+            freeform_tools,
+        ));
+
+        let mut events = Vec::new();
+        while let Some(ev) = rx.recv().await {
+            events.push(ev);
+        }
+        events
+    }
+
+    fn sse(chunks: &[Value]) -> String {
+        let mut body = String::new();
+        for chunk in chunks {
+            body.push_str(&format!("data: {chunk}\n\n"));
+        }
+        body
+    }
+
+    #[tokio::test]
+    async fn accumulates_fragmented_tool_call_arguments() {
+        let mut body = sse(&[
+            serde_json::json!({
+                "id": "chatcmpl-1",
+                "choices": [{"delta": {"tool_calls": [
+                    {"index": 0, "id": "call-a", "function": {"name": "shell", "arguments": "{\"cmd\":"}}
+                ]}}],
+            }),
+            serde_json::json!({
+                "id": "chatcmpl-1",
+                "choices": [{"delta": {"tool_calls": [
+                    {"index": 0, "function": {"arguments": "\"ls\"}"}}
+                ]}, "finish_reason": "tool_calls"}],
+            }),
+        ]);
+        body.push_str("data: [DONE]\n\n");
+
+        let events: Vec<ResponseEvent> = collect_events(&body)
+            .await
+            .into_iter()
+            .map(|event| event.expect("chat stream event"))
+            .collect();
+
+        assert_eq!(events.len(), 3);
+        assert_matches!(events[0], ResponseEvent::Created);
+        assert_matches!(
+            &events[1],
+            ResponseEvent::OutputItemDone(ResponseItem::FunctionCall { name, arguments, call_id, .. })
+                if name == "shell" && arguments == "{\"cmd\":\"ls\"}" && call_id == "call-a"
+        );
+        assert_matches!(
+            &events[2],
+            ResponseEvent::Completed { response_id, token_usage: None, .. } if response_id == "chatcmpl-1"
+        );
+    }
+
+    #[tokio::test]
+    async fn freeform_tool_calls_become_custom_tool_calls() {
+        let body = sse(&[serde_json::json!({
+            "id": "chatcmpl-4",
+            "choices": [{"delta": {"tool_calls": [
+                {"index": 0, "id": "call-p", "function": {"name": "apply_patch", "arguments": "{\"input\":\"*** Begin Patch\"}"}}
+            ]}, "finish_reason": "tool_calls"}],
+        })]);
+
+        let events: Vec<ResponseEvent> =
+            collect_events_with_freeform_tools(&body, vec!["apply_patch".to_string()])
+                .await
+                .into_iter()
+                .map(|event| event.expect("chat stream event"))
+                .collect();
+
+        assert_matches!(
+            &events[1],
+            ResponseEvent::OutputItemDone(ResponseItem::CustomToolCall { name, input, call_id, .. })
+                if name == "apply_patch" && input == "*** Begin Patch" && call_id == "call-p"
+        );
+    }
+
+    #[tokio::test]
+    async fn finishes_without_done_sentinel_after_finish_reason() {
+        let body = sse(&[serde_json::json!({
+            "id": "chatcmpl-2",
+            "choices": [{"delta": {"content": "hi"}, "finish_reason": "stop"}],
+        })]);
+
+        let events = collect_events(&body).await;
+
+        assert_matches!(
+            events.last(),
+            Some(Ok(ResponseEvent::Completed { response_id, .. })) if response_id == "chatcmpl-2"
+        );
+    }
+
+    #[tokio::test]
+    async fn completes_reasoning_before_opening_the_message_with_stable_ids() {
+        let body = sse(&[
+            serde_json::json!({
+                "id": "chatcmpl-3",
+                "choices": [{"delta": {"reasoning_content": "thinking"}}],
+            }),
+            serde_json::json!({
+                "id": "chatcmpl-3",
+                "choices": [{"delta": {"content": "hel"}}],
+            }),
+            serde_json::json!({
+                "id": "chatcmpl-3",
+                "choices": [{"delta": {"content": "lo"}, "finish_reason": "stop"}],
+            }),
+        ]);
+
+        let events: Vec<ResponseEvent> = collect_events(&body)
+            .await
+            .into_iter()
+            .map(|event| event.expect("chat stream event"))
+            .collect();
+
+        let reasoning_id = "rs_chatcmpl-3_0".to_string();
+        let message_id = "msg_chatcmpl-3_1".to_string();
+        assert_eq!(events.len(), 9);
+        assert_matches!(events[0], ResponseEvent::Created);
+        assert_matches!(
+            &events[1],
+            ResponseEvent::OutputItemAdded(ResponseItem::Reasoning { id, .. }) if id == &reasoning_id
+        );
+        assert_matches!(&events[2], ResponseEvent::ReasoningContentDelta { .. });
+        assert_matches!(
+            &events[3],
+            ResponseEvent::OutputItemDone(item)
+                if item == &reasoning_item(reasoning_id.clone(), "thinking".to_string())
+        );
+        assert_matches!(
+            &events[4],
+            ResponseEvent::OutputItemAdded(ResponseItem::Message { id: Some(id), .. })
+                if id == &message_id
+        );
+        assert_matches!(&events[5], ResponseEvent::OutputTextDelta(delta) if delta == "hel");
+        assert_matches!(&events[6], ResponseEvent::OutputTextDelta(delta) if delta == "lo");
+        assert_matches!(
+            &events[7],
+            ResponseEvent::OutputItemDone(item)
+                if item == &assistant_message(message_id.clone(), "hello".to_string())
+        );
+        assert_matches!(&events[8], ResponseEvent::Completed { .. });
+    }
+
+    #[tokio::test]
+    async fn length_finish_reason_is_an_incomplete_response() {
+        let body = sse(&[serde_json::json!({
+            "id": "chatcmpl-4",
+            "choices": [{"delta": {"tool_calls": [
+                {"index": 0, "id": "call-a", "function": {"name": "shell", "arguments": "{\"cmd\":"}}
+            ]}, "finish_reason": "length"}],
+        })]);
+
+        let events = collect_events(&body).await;
+
+        assert!(
+            !events.iter().any(|event| matches!(
+                event,
+                Ok(ResponseEvent::OutputItemDone(
+                    ResponseItem::FunctionCall { .. }
+                ))
+            )),
+            "truncated tool calls must not be dispatched: {events:?}"
+        );
+        assert_matches!(
+            events.last(),
+            Some(Err(ApiError::Stream(message))) if message.contains("max_output_tokens")
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_truncated_stream_and_server_errors() {
+        let truncated = sse(&[serde_json::json!({
+            "choices": [{"delta": {"content": "partial"}}],
+        })]);
+        let events = collect_events(&truncated).await;
+        assert_matches!(events.last(), Some(Err(ApiError::Stream(_))));
+
+        let errored = sse(&[serde_json::json!({
+            "error": {"code": "context_length_exceeded", "message": "too long"},
+        })]);
+        let events = collect_events(&errored).await;
+        assert_matches!(events.as_slice(), [Err(ApiError::ContextWindowExceeded)]);
+    }
+}
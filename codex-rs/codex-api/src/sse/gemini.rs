@@ -0,0 +1,669 @@
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::sse::tool_call_item;
+use crate::telemetry::SseTelemetry;
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+pub fn spawn_gemini_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    freeform_tools: Vec<String>,
+) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(process_gemini_sse(
+        stream_response.bytes,
+        tx_event,
+        idle_timeout,
+        telemetry,
+        freeform_tools,
+    ));
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerateContentChunk {
+    #[serde(default)]
+    response_id: Option<String>,
+    #[serde(default)]
+    candidates: Vec<GeminiCandidate>,
+    #[serde(default)]
+    usage_metadata: Option<GeminiUsage>,
+    #[serde(default)]
+    error: Option<GeminiError>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiCandidate {
+    #[serde(default)]
+    content: Option<GeminiContent>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiContent {
+    #[serde(default)]
+    parts: Vec<GeminiPart>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiPart {
+    #[serde(default)]
+    text: Option<String>,
+    /// Set on parts that carry a thought summary rather than answer text.
+    #[serde(default)]
+    thought: bool,
+    #[serde(default)]
+    thought_signature: Option<String>,
+    #[serde(default)]
+    function_call: Option<GeminiFunctionCall>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiFunctionCall {
+    #[serde(default)]
+    id: Option<String>,
+    name: String,
+    #[serde(default)]
+    args: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiUsage {
+    #[serde(default)]
+    prompt_token_count: i64,
+    #[serde(default)]
+    cached_content_token_count: i64,
+    #[serde(default)]
+    candidates_token_count: i64,
+    #[serde(default)]
+    thoughts_token_count: i64,
+    #[serde(default)]
+    total_token_count: i64,
+}
+
+impl From<GeminiUsage> for TokenUsage {
+    fn from(val: GeminiUsage) -> Self {
+        TokenUsage {
+            input_tokens: val.prompt_token_count,
+            cached_input_tokens: val.cached_content_token_count,
+            // Responses counts reasoning as part of the output.
+            output_tokens: val.candidates_token_count + val.thoughts_token_count,
+            reasoning_output_tokens: val.thoughts_token_count,
+            total_tokens: val.total_token_count,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiError {
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+impl GeminiError {
+    fn into_api_error(self) -> ApiError {
+        let message = self
+            .message
+            .unwrap_or_else(|| "gemini stream returned an error".to_string());
+        match self.status.as_deref() {
+            Some("INVALID_ARGUMENT")
+                if message.contains("exceeds the maximum number of tokens") =>
+            {
+                ApiError::ContextWindowExceeded
+            }
+            Some("RESOURCE_EXHAUSTED") => ApiError::RateLimit(message),
+            Some("UNAVAILABLE") => ApiError::ServerOverloaded,
+            _ => ApiError::Stream(message),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StreamedItemKind {
+    Reasoning,
+    Message,
+}
+
+/// The thought summary or answer text currently receiving parts. Its id is synthesized once so
+/// the `OutputItemAdded` and `OutputItemDone` events for it agree.
+#[derive(Debug)]
+struct StreamedItem {
+    kind: StreamedItemKind,
+    id: String,
+    text: String,
+}
+
+/// Accumulates streamed candidate parts into the item-level events the Responses pipeline emits.
+/// Gemini has no explicit end-of-stream event, so the turn completes when the stream closes
+/// after a candidate reported a finish reason. Core tracks one active item at a time, so thoughts
+/// are completed before the answer text that follows them is opened.
+#[derive(Debug, Default)]
+struct GeminiStreamState {
+    response_id: Option<String>,
+    created: bool,
+    open_item: Option<StreamedItem>,
+    items_opened: usize,
+    thought_signature: Option<String>,
+    function_calls: Vec<GeminiFunctionCall>,
+    freeform_tools: Vec<String>,
+    token_usage: Option<TokenUsage>,
+    finished: bool,
+    truncated: bool,
+}
+
+impl GeminiStreamState {
+    fn handle_chunk(&mut self, chunk: GenerateContentChunk) -> Vec<ResponseEvent> {
+        let mut events = Vec::new();
+        if !self.created {
+            self.created = true;
+            events.push(ResponseEvent::Created);
+        }
+        if self.response_id.is_none() {
+            self.response_id = chunk.response_id;
+        }
+        if let Some(usage) = chunk.usage_metadata {
+            self.token_usage = Some(usage.into());
+        }
+
+        // Only the first candidate is used; Codex never requests more than one.
+        let Some(candidate) = chunk.candidates.into_iter().next() else {
+            return events;
+        };
+        if let Some(finish_reason) = candidate.finish_reason {
+            self.finished = true;
+            // The output hit `maxOutputTokens`; any function call arguments are incomplete.
+            self.truncated = finish_reason == "MAX_TOKENS";
+        }
+        for part in candidate
+            .content
+            .into_iter()
+            .flat_map(|content| content.parts)
+        {
+            if let Some(signature) = part.thought_signature {
+                self.thought_signature = Some(signature);
+            }
+            if let Some(function_call) = part.function_call {
+                self.function_calls.push(function_call);
+                continue;
+            }
+            let Some(text) = part.text.filter(|text| !text.is_empty()) else {
+                continue;
+            };
+            if part.thought {
+                self.push_item_text(StreamedItemKind::Reasoning, &text, &mut events);
+                events.push(ResponseEvent::ReasoningSummaryDelta {
+                    delta: text,
+                    summary_index: 0,
+                });
+            } else {
+                self.push_item_text(StreamedItemKind::Message, &text, &mut events);
+                events.push(ResponseEvent::OutputTextDelta(text));
+            }
+        }
+
+        events
+    }
+
+    /// Appends `text` to the open item of `kind`, first completing an open item of the other kind
+    /// and opening a new one as needed.
+    fn push_item_text(
+        &mut self,
+        kind: StreamedItemKind,
+        text: &str,
+        events: &mut Vec<ResponseEvent>,
+    ) {
+        if self
+            .open_item
+            .as_ref()
+            .is_some_and(|item| item.kind != kind)
+        {
+            events.extend(self.close_item());
+        }
+        if self.open_item.is_none() {
+            let id = self.next_item_id(kind);
+            let item = match kind {
+                StreamedItemKind::Reasoning => reasoning_item(id.clone(), String::new(), None),
+                StreamedItemKind::Message => assistant_message(id.clone(), String::new()),
+            };
+            events.push(ResponseEvent::OutputItemAdded(item));
+            self.open_item = Some(StreamedItem {
+                kind,
+                id,
+                text: String::new(),
+            });
+        }
+        if let Some(item) = self.open_item.as_mut() {
+            item.text.push_str(text);
+        }
+    }
+
+    fn next_item_id(&mut self, kind: StreamedItemKind) -> String {
+        let prefix = match kind {
+            StreamedItemKind::Reasoning => "rs",
+            StreamedItemKind::Message => "msg",
+        };
+        let response_id = self.response_id.as_deref().unwrap_or_default();
+        let id = format!("{prefix}_{response_id}_{}", self.items_opened);
+        self.items_opened += 1;
+        id
+    }
+
+    /// Completes the open item. Reasoning takes the thought signature seen so far with it.
+    fn close_item(&mut self) -> Option<ResponseEvent> {
+        let StreamedItem { kind, id, text } = self.open_item.take()?;
+        let item = match kind {
+            StreamedItemKind::Reasoning => reasoning_item(id, text, self.thought_signature.take()),
+            StreamedItemKind::Message => assistant_message(id, text),
+        };
+        Some(ResponseEvent::OutputItemDone(item))
+    }
+
+    fn finish(mut self) -> Result<Vec<ResponseEvent>, ApiError> {
+        if self.truncated {
+            return Err(ApiError::Stream(
+                "Incomplete response returned, reason: max_output_tokens".to_string(),
+            ));
+        }
+        let mut events = Vec::new();
+        if !self.created {
+            events.push(ResponseEvent::Created);
+        }
+        events.extend(self.close_item());
+        // A signature that arrived after the thoughts were completed (typically on the first
+        // function call) still has to be replayed, so it gets a reasoning item of its own.
+        if let Some(signature) = self.thought_signature.take() {
+            let id = self.next_item_id(StreamedItemKind::Reasoning);
+            events.push(ResponseEvent::OutputItemAdded(reasoning_item(
+                id.clone(),
+                String::new(),
+                None,
+            )));
+            events.push(ResponseEvent::OutputItemDone(reasoning_item(
+                id,
+                String::new(),
+                Some(signature),
+            )));
+        }
+        let response_id = self.response_id.unwrap_or_default();
+        for (index, function_call) in self.function_calls.into_iter().enumerate() {
+            let call_id = function_call
+                .id
+                .unwrap_or_else(|| format!("call_{response_id}_{index}"));
+            let arguments = function_call
+                .args
+                .map(|args| args.to_string())
+                .unwrap_or_else(|| "{}".to_string());
+            events.push(ResponseEvent::OutputItemDone(tool_call_item(
+                function_call.name,
+                arguments,
+                call_id,
+                &self.freeform_tools,
+            )));
+        }
+        events.push(ResponseEvent::Completed {
+            response_id,
+            token_usage: self.token_usage,
+            can_append: false,
+        });
+        Ok(events)
+    }
+}
+
+/// Thought summaries are surfaced as reasoning summaries and the thought signature rides in
+/// `encrypted_content` so it can be replayed on the next request.
+fn reasoning_item(id: String, summary: String, signature: Option<String>) -> ResponseItem {
+    ResponseItem::Reasoning {
+        id,
+        summary: if summary.is_empty() {
+            Vec::new()
+        } else {
+            vec![ReasoningItemReasoningSummary::SummaryText { text: summary }]
+        },
+        content: None,
+        encrypted_content: signature,
+    }
+}
+
+fn assistant_message(id: String, text: String) -> ResponseItem {
+    ResponseItem::Message {
+        id: Some(id),
+        role: "assistant".to_string(),
+        content: if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![ContentItem::OutputText { text }]
+        },
+        end_turn: None,
+        phase: None,
+    }
+}
+
+pub async fn process_gemini_sse(
+    stream: ByteStream,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    freeform_tools: Vec<String>,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = GeminiStreamState {
+        freeform_tools,
+        ..Default::default()
+    };
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                if state.finished {
+                    break;
+                }
+                let _ = tx_event
+                    .send(Err(ApiError::Stream(
+                        "stream closed before gemini response finished".into(),
+                    )))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", &sse.data);
+
+        let chunk: GenerateContentChunk = match serde_json::from_str(&sse.data) {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                debug!("Failed to parse gemini chunk: {e}, data: {}", &sse.data);
+                continue;
+            }
+        };
+        if let Some(error) = chunk.error {
+            let _ = tx_event.send(Err(error.into_api_error())).await;
+            return;
+        }
+
+        for event in state.handle_chunk(chunk) {
+            if tx_event.send(Ok(event)).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    let events = match state.finish() {
+        Ok(events) => events,
+        Err(err) => {
+            let _ = tx_event.send(Err(err)).await;
+            return;
+        }
+    };
+    for event in events {
+        if tx_event.send(Ok(event)).await.is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use codex_client::TransportError;
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+    use tokio_test::io::Builder as IoBuilder;
+    use tokio_util::io::ReaderStream;
+
+    async fn collect_events(body: &str) -> Vec<Result<ResponseEvent, ApiError>> {
+        let reader = IoBuilder::new().read(body.as_bytes()).build();
+        let stream =
+            ReaderStream::new(reader).map_err(|err| TransportError::Network(err.to_string()));
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(16);
+        tokio::spawn(process_gemini_sse(
+            Box::pin(stream),
+            tx,
+            Duration::from_millis(1000),
+            None,
+            Vec::new(),
+        ));
+
+        let mut events = Vec::new();
+        while let Some(ev) = rx.recv().await {
+            events.push(ev);
+        }
+        events
+    }
+
+    fn sse(chunks: &[Value]) -> String {
+        let mut body = String::new();
+        for chunk in chunks {
+            body.push_str(&format!("data: {chunk}\n\n"));
+        }
+        body
+    }
+
+    #[tokio::test]
+    async fn maps_thoughts_function_calls_and_usage() {
+        let body = sse(&[
+            serde_json::json!({
+                "responseId": "resp-1",
+                "candidates": [{"content": {"role": "model", "parts": [
+                    {"text": "Checking the tree", "thought": true},
+                ]}}],
+            }),
+            serde_json::json!({
+                "responseId": "resp-1",
+                "candidates": [{"content": {"role": "model", "parts": [
+                    {"functionCall": {"name": "shell", "args": {"command": ["ls"]}}, "thoughtSignature": "sig"},
+                ]}, "finishReason": "STOP"}],
+                "usageMetadata": {
+                    "promptTokenCount": 100,
+                    "cachedContentTokenCount": 40,
+                    "candidatesTokenCount": 10,
+                    "thoughtsTokenCount": 5,
+                    "totalTokenCount": 115,
+                },
+            }),
+        ]);
+
+        let events: Vec<ResponseEvent> = collect_events(&body)
+            .await
+            .into_iter()
+            .map(|event| event.expect("gemini stream event"))
+            .collect();
+
+        assert_eq!(events.len(), 6);
+        assert_matches!(events[0], ResponseEvent::Created);
+        assert_matches!(
+            &events[1],
+            ResponseEvent::OutputItemAdded(ResponseItem::Reasoning { id, .. }) if id == "rs_resp-1_0"
+        );
+        assert_matches!(
+            &events[2],
+            ResponseEvent::ReasoningSummaryDelta { delta, .. } if delta == "Checking the tree"
+        );
+        assert_matches!(
+            &events[3],
+            ResponseEvent::OutputItemDone(item)
+                if item == &reasoning_item(
+                    "rs_resp-1_0".to_string(),
+                    "Checking the tree".to_string(),
+                    Some("sig".to_string()),
+                )
+        );
+        assert_matches!(
+            &events[4],
+            ResponseEvent::OutputItemDone(ResponseItem::FunctionCall { name, arguments, call_id, .. })
+                if name == "shell" && arguments == "{\"command\":[\"ls\"]}" && call_id == "call_resp-1_0"
+        );
+        let ResponseEvent::Completed {
+            response_id,
+            token_usage,
+            ..
+        } = &events[5]
+        else {
+            panic!("expected completed event, got {:?}", events[5]);
+        };
+        assert_eq!(response_id, "resp-1");
+        assert_eq!(
+            token_usage,
+            &Some(TokenUsage {
+                input_tokens: 100,
+                cached_input_tokens: 40,
+                output_tokens: 15,
+                reasoning_output_tokens: 5,
+                total_tokens: 115,
+            })
+        );
+    }
+
+    /// Ids of the `OutputItemAdded` and `OutputItemDone` events, in order.
+    fn item_event_ids(events: &[ResponseEvent]) -> Vec<(&'static str, String)> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                ResponseEvent::OutputItemAdded(item) => Some(("added", item_id(item)?)),
+                ResponseEvent::OutputItemDone(item) => Some(("done", item_id(item)?)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn item_id(item: &ResponseItem) -> Option<String> {
+        match item {
+            ResponseItem::Reasoning { id, .. } => Some(id.clone()),
+            ResponseItem::Message { id, .. } => id.clone(),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn completes_thoughts_before_opening_the_answer_with_stable_ids() {
+        let body = sse(&[
+            serde_json::json!({
+                "responseId": "resp-2",
+                "candidates": [{"content": {"role": "model", "parts": [
+                    {"text": "Thinking", "thought": true},
+                    {"text": "Hello", "thoughtSignature": "sig"},
+                ]}}],
+            }),
+            serde_json::json!({
+                "responseId": "resp-2",
+                "candidates": [{"content": {"role": "model", "parts": [
+                    {"text": " there"},
+                ]}, "finishReason": "STOP"}],
+            }),
+        ]);
+
+        let events: Vec<ResponseEvent> = collect_events(&body)
+            .await
+            .into_iter()
+            .map(|event| event.expect("gemini stream event"))
+            .collect();
+
+        assert_eq!(
+            item_event_ids(&events),
+            vec![
+                ("added", "rs_resp-2_0".to_string()),
+                ("done", "rs_resp-2_0".to_string()),
+                ("added", "msg_resp-2_1".to_string()),
+                ("done", "msg_resp-2_1".to_string()),
+            ]
+        );
+        assert_matches!(
+            events.iter().find(|event| matches!(event, ResponseEvent::OutputItemDone(ResponseItem::Reasoning { .. }))),
+            Some(ResponseEvent::OutputItemDone(item))
+                if item == &reasoning_item("rs_resp-2_0".to_string(), "Thinking".to_string(), Some("sig".to_string()))
+        );
+        assert_matches!(
+            events.iter().find(|event| matches!(event, ResponseEvent::OutputItemDone(ResponseItem::Message { .. }))),
+            Some(ResponseEvent::OutputItemDone(item))
+                if item == &assistant_message("msg_resp-2_1".to_string(), "Hello there".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn max_tokens_finish_reason_is_an_incomplete_response() {
+        let body = sse(&[serde_json::json!({
+            "responseId": "resp-3",
+            "candidates": [{"content": {"parts": [
+                {"functionCall": {"name": "shell", "args": {"command": ["ls"]}}},
+            ]}, "finishReason": "MAX_TOKENS"}],
+        })]);
+
+        let events = collect_events(&body).await;
+
+        assert_matches!(
+            events.last(),
+            Some(Err(ApiError::Stream(message)))
+                if message == "Incomplete response returned, reason: max_output_tokens"
+        );
+        assert!(
+            !events.iter().any(|event| matches!(
+                event,
+                Ok(ResponseEvent::OutputItemDone(
+                    ResponseItem::FunctionCall { .. }
+                ))
+            )),
+            "truncated function calls must not be dispatched: {events:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_truncated_stream_and_error_chunks() {
+        let truncated = sse(&[serde_json::json!({
+            "candidates": [{"content": {"parts": [{"text": "partial"}]}}],
+        })]);
+        let events = collect_events(&truncated).await;
+        assert_matches!(events.last(), Some(Err(ApiError::Stream(_))));
+
+        let errored = sse(&[serde_json::json!({
+            "error": {
+                "code": 400,
+                "status": "INVALID_ARGUMENT",
+                "message": "The input token count exceeds the maximum number of tokens allowed",
+            },
+        })]);
+        let events = collect_events(&errored).await;
+        assert_matches!(events.as_slice(), [Err(ApiError::ContextWindowExceeded)]);
+    }
+}
@@ -1,5 +1,45 @@
+pub mod anthropic;
+pub mod chat;
+pub mod gemini;
 pub mod responses;
 
 pub use responses::process_sse;
 pub use responses::spawn_response_stream;
 pub use responses::stream_from_fixture;
+
+use codex_protocol::models::ResponseItem;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct FreeformArguments {
+    input: String,
+}
+
+/// Builds the item for a tool call streamed by a translated wire API. Freeform tools were offered
+/// as functions taking a single `input` string (see `requests::function_tools`), so their calls
+/// become custom tool calls again; arguments that do not have that shape are left to the tool to
+/// reject.
+pub(crate) fn tool_call_item(
+    name: String,
+    arguments: String,
+    call_id: String,
+    freeform_tools: &[String],
+) -> ResponseItem {
+    if freeform_tools.contains(&name)
+        && let Ok(FreeformArguments { input }) = serde_json::from_str(&arguments)
+    {
+        return ResponseItem::CustomToolCall {
+            id: None,
+            status: None,
+            call_id,
+            name,
+            input,
+        };
+    }
+    ResponseItem::FunctionCall {
+        id: None,
+        name,
+        arguments,
+        call_id,
+    }
+}
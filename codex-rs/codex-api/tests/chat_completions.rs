@@ -0,0 +1,257 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use codex_api::AuthProvider;
+use codex_api::ChatClient;
+use codex_api::ChatCompletionsApiRequest;
+use codex_api::ChatOptions;
+use codex_api::Provider;
+use codex_api::ResponseEvent;
+use codex_api::ResponsesApiRequest;
+use codex_api::provider::RetryConfig;
+use codex_client::ReqwestTransport;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use futures::StreamExt;
+use http::HeaderMap;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+#[derive(Clone, Default)]
+struct DummyAuth;
+
+impl AuthProvider for DummyAuth {
+    fn bearer_token(&self) -> Option<String> {
+        None
+    }
+}
+
+fn provider(base_url: &str) -> Provider {
+    Provider {
+        name: "local".to_string(),
+        base_url: base_url.to_string(),
+        query_params: None,
+        headers: HeaderMap::new(),
+        retry: RetryConfig {
+            max_attempts: 1,
+            base_delay: Duration::from_millis(1),
+            retry_429: false,
+            retry_5xx: false,
+            retry_transport: false,
+        },
+        stream_idle_timeout: Duration::from_secs(5),
+    }
+}
+
+fn sse_body(chunks: Vec<Value>) -> String {
+    let mut body = String::new();
+    for chunk in chunks {
+        body.push_str(&format!("data: {chunk}\n\n"));
+    }
+    body.push_str("data: [DONE]\n\n");
+    body
+}
+
+fn responses_request(input: Vec<ResponseItem>) -> ResponsesApiRequest {
+    ResponsesApiRequest {
+        model: "llama".to_string(),
+        instructions: "You are Codex.".to_string(),
+        input,
+        tools: vec![json!({
+            "type": "function",
+            "name": "shell",
+            "description": "Run a command",
+            "strict": false,
+            "parameters": {"type": "object", "properties": {"cmd": {"type": "string"}}},
+        })],
+        tool_choice: "auto".to_string(),
+        parallel_tool_calls: false,
+        reasoning: None,
+        store: false,
+        stream: true,
+        include: Vec::new(),
+        prompt_cache_key: None,
+        text: None,
+    }
+}
+
+#[tokio::test]
+async fn chat_client_translates_history_and_streams_response_events() -> Result<()> {
+    let server = MockServer::start().await;
+    let body = sse_body(vec![
+        json!({"id": "chatcmpl-1", "choices": [{"delta": {"role": "assistant", "reasoning_content": "Check "}}]}),
+        json!({"id": "chatcmpl-1", "choices": [{"delta": {"reasoning_content": "files."}}]}),
+        json!({"id": "chatcmpl-1", "choices": [{"delta": {"content": "Listing"}}]}),
+        json!({"id": "chatcmpl-1", "choices": [{"delta": {"content": " now."}}]}),
+        json!({"id": "chatcmpl-1", "choices": [{"delta": {"tool_calls": [
+            {"index": 0, "id": "call-2", "type": "function", "function": {"name": "shell", "arguments": ""}}
+        ]}}]}),
+        json!({"id": "chatcmpl-1", "choices": [{"delta": {"tool_calls": [
+            {"index": 0, "function": {"arguments": "{\"cmd\":\"ls\"}"}}
+        ]}, "finish_reason": "tool_calls"}]}),
+        json!({"id": "chatcmpl-1", "choices": [], "usage": {
+            "prompt_tokens": 20,
+            "completion_tokens": 7,
+            "total_tokens": 27,
+            "prompt_tokens_details": {"cached_tokens": 4}
+        }}),
+    ]);
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_raw(body, "text/event-stream"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let history = vec![
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: "what is here?".to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        },
+        ResponseItem::FunctionCall {
+            id: None,
+            name: "shell".to_string(),
+            arguments: "{\"cmd\":\"pwd\"}".to_string(),
+            call_id: "call-1".to_string(),
+        },
+        ResponseItem::FunctionCallOutput {
+            call_id: "call-1".to_string(),
+            output: FunctionCallOutputPayload::from_text("/repo".to_string()),
+        },
+    ];
+    let request = ChatCompletionsApiRequest::try_from(&responses_request(history))?;
+    let client = ChatClient::new(
+        ReqwestTransport::new(reqwest::Client::new()),
+        provider(&format!("{}/v1", server.uri())),
+        DummyAuth,
+    );
+
+    let mut stream = client
+        .stream_request(request, ChatOptions::default())
+        .await?;
+    let mut events = Vec::new();
+    while let Some(event) = stream.next().await {
+        events.push(event?);
+    }
+
+    let received = server
+        .received_requests()
+        .await
+        .expect("wiremock records requests");
+    let sent: Value = serde_json::from_slice(&received[0].body)?;
+    assert_eq!(
+        sent["messages"],
+        json!([
+            {"role": "system", "content": "You are Codex."},
+            {"role": "user", "content": "what is here?"},
+            {"role": "assistant", "content": null, "tool_calls": [{
+                "id": "call-1",
+                "type": "function",
+                "function": {"name": "shell", "arguments": "{\"cmd\":\"pwd\"}"},
+            }]},
+            {"role": "tool", "tool_call_id": "call-1", "content": "/repo"},
+        ])
+    );
+    assert_eq!(sent["tools"][0]["function"]["name"], json!("shell"));
+    assert_eq!(sent["stream_options"], json!({"include_usage": true}));
+
+    let mut deltas = Vec::new();
+    let mut done = Vec::new();
+    let mut completed = None;
+    for event in events {
+        match event {
+            ResponseEvent::OutputTextDelta(delta) => deltas.push(delta),
+            ResponseEvent::OutputItemDone(item) => done.push(item),
+            ResponseEvent::Completed {
+                response_id,
+                token_usage,
+                ..
+            } => completed = Some((response_id, token_usage)),
+            _ => {}
+        }
+    }
+    assert_eq!(deltas, vec!["Listing".to_string(), " now.".to_string()]);
+    assert_eq!(
+        done,
+        vec![
+            ResponseItem::Reasoning {
+                id: "rs_chatcmpl-1_0".to_string(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "Check files.".to_string(),
+                }]),
+                encrypted_content: None,
+            },
+            ResponseItem::Message {
+                id: Some("msg_chatcmpl-1_1".to_string()),
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: "Listing now.".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: "{\"cmd\":\"ls\"}".to_string(),
+                call_id: "call-2".to_string(),
+            },
+        ]
+    );
+    assert_eq!(
+        completed,
+        Some((
+            "chatcmpl-1".to_string(),
+            Some(TokenUsage {
+                input_tokens: 20,
+                cached_input_tokens: 4,
+                output_tokens: 7,
+                reasoning_output_tokens: 0,
+                total_tokens: 27,
+            })
+        ))
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn chat_client_surfaces_http_errors() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("not found"))
+        .mount(&server)
+        .await;
+
+    let client = ChatClient::new(
+        ReqwestTransport::new(reqwest::Client::new()),
+        provider(&format!("{}/v1", server.uri())),
+        DummyAuth,
+    );
+    let request = ChatCompletionsApiRequest::try_from(&responses_request(Vec::new()))?;
+
+    let result = client.stream_request(request, ChatOptions::default()).await;
+
+    assert!(result.is_err(), "404 should fail the request");
+    Ok(())
+}
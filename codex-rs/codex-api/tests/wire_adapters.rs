@@ -0,0 +1,329 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use codex_api::AnthropicClient;
+use codex_api::AnthropicMessagesApiRequest;
+use codex_api::AuthProvider;
+use codex_api::GeminiClient;
+use codex_api::GeminiGenerateContentRequest;
+use codex_api::Provider;
+use codex_api::ResponseEvent;
+use codex_api::ResponsesApiRequest;
+use codex_api::provider::RetryConfig;
+use codex_client::ReqwestTransport;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use futures::StreamExt;
+use http::HeaderMap;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::header;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+use wiremock::matchers::query_param;
+
+const ANTHROPIC_FIXTURE: &str = include_str!("fixtures/anthropic_messages.sse");
+const GEMINI_FIXTURE: &str = include_str!("fixtures/gemini_stream_generate_content.sse");
+
+#[derive(Clone, Default)]
+struct KeyAuth;
+
+impl AuthProvider for KeyAuth {
+    fn bearer_token(&self) -> Option<String> {
+        Some("test-key".to_string())
+    }
+}
+
+fn provider(base_url: &str) -> Provider {
+    Provider {
+        name: "test".to_string(),
+        base_url: base_url.to_string(),
+        query_params: None,
+        headers: HeaderMap::new(),
+        retry: RetryConfig {
+            max_attempts: 1,
+            base_delay: Duration::from_millis(1),
+            retry_429: false,
+            retry_5xx: false,
+            retry_transport: false,
+        },
+        stream_idle_timeout: Duration::from_secs(5),
+    }
+}
+
+fn responses_request(model: &str) -> ResponsesApiRequest {
+    ResponsesApiRequest {
+        model: model.to_string(),
+        instructions: "You are Codex.".to_string(),
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: "list the files".to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        }],
+        tools: vec![json!({
+            "type": "function",
+            "name": "shell",
+            "description": "Run a command",
+            "strict": false,
+            "parameters": {"type": "object", "properties": {"command": {"type": "array"}}},
+        })],
+        tool_choice: "auto".to_string(),
+        parallel_tool_calls: true,
+        reasoning: None,
+        store: false,
+        stream: true,
+        include: Vec::new(),
+        prompt_cache_key: None,
+        text: None,
+    }
+}
+
+#[derive(Debug, Default)]
+struct Collected {
+    text_deltas: Vec<String>,
+    added: Vec<ResponseItem>,
+    done: Vec<ResponseItem>,
+    completed: Option<(String, Option<TokenUsage>)>,
+}
+
+async fn collect(mut stream: codex_api::ResponseStream) -> Result<Collected> {
+    let mut collected = Collected::default();
+    while let Some(event) = stream.next().await {
+        match event? {
+            ResponseEvent::OutputTextDelta(delta) => collected.text_deltas.push(delta),
+            ResponseEvent::OutputItemAdded(item) => collected.added.push(item),
+            ResponseEvent::OutputItemDone(item) => collected.done.push(item),
+            ResponseEvent::Completed {
+                response_id,
+                token_usage,
+                ..
+            } => collected.completed = Some((response_id, token_usage)),
+            _ => {}
+        }
+    }
+    Ok(collected)
+}
+
+impl Collected {
+    /// Core pairs `OutputItemAdded` with `OutputItemDone` by id, so every streamed reasoning or
+    /// message item has to keep the same one.
+    fn assert_item_ids_match(&self) {
+        let ids = |items: &[ResponseItem]| -> Vec<String> {
+            items
+                .iter()
+                .filter_map(|item| match item {
+                    ResponseItem::Reasoning { id, .. } => Some(id.clone()),
+                    ResponseItem::Message { id, .. } => id.clone(),
+                    _ => None,
+                })
+                .collect()
+        };
+        assert_eq!(ids(&self.added), ids(&self.done));
+    }
+}
+
+fn assistant_message(id: &str, text: &str) -> ResponseItem {
+    ResponseItem::Message {
+        id: Some(id.to_string()),
+        role: "assistant".to_string(),
+        content: vec![ContentItem::OutputText {
+            text: text.to_string(),
+        }],
+        end_turn: None,
+        phase: None,
+    }
+}
+
+fn shell_call(call_id: &str) -> ResponseItem {
+    ResponseItem::FunctionCall {
+        id: None,
+        name: "shell".to_string(),
+        arguments: "{\"command\":[\"ls\",\"-la\"]}".to_string(),
+        call_id: call_id.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn anthropic_client_streams_recorded_fixture() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .and(header("x-api-key", "test-key"))
+        .and(header("anthropic-version", "2023-06-01"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_raw(ANTHROPIC_FIXTURE, "text/event-stream"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = AnthropicClient::new(
+        ReqwestTransport::new(reqwest::Client::new()),
+        provider(&format!("{}/v1", server.uri())),
+        KeyAuth,
+    );
+    let request = AnthropicMessagesApiRequest::try_from(&responses_request("claude-sonnet-4-5"))?;
+    let collected = collect(client.stream_request(request, HeaderMap::new()).await?).await?;
+
+    let received = server
+        .received_requests()
+        .await
+        .expect("wiremock records requests");
+    assert_eq!(received[0].headers.get("authorization"), None);
+    let sent: Value = serde_json::from_slice(&received[0].body)?;
+    assert_eq!(sent["system"], json!("You are Codex."));
+    assert_eq!(
+        sent["messages"],
+        json!([{"role": "user", "content": [{"type": "text", "text": "list the files"}]}])
+    );
+    assert_eq!(sent["tools"][0]["name"], json!("shell"));
+
+    assert_eq!(
+        collected.text_deltas,
+        vec!["Listing the ".to_string(), "directory.".to_string()]
+    );
+    assert_eq!(
+        collected.done,
+        vec![
+            ResponseItem::Reasoning {
+                id: "rs_msg_01XFDUDYJgAACzvnptvVoYEL_0".to_string(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "The user wants the files listed.".to_string(),
+                }]),
+                encrypted_content: Some(
+                    "EqQBCgIYAhIM1gbcDa9GJwZA2b3hGgxBdjrkzLoky3dl1pkiMOYds".to_string()
+                ),
+            },
+            assistant_message(
+                "msg_msg_01XFDUDYJgAACzvnptvVoYEL_1",
+                "Listing the directory."
+            ),
+            // Tool input is forwarded exactly as streamed, whitespace included.
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: "{\"command\": [\"ls\", \"-la\"]}".to_string(),
+                call_id: "toolu_01T1x1fJ34qAmk2tNTrN7Up6".to_string(),
+            },
+        ]
+    );
+    collected.assert_item_ids_match();
+    assert_eq!(
+        collected.completed,
+        Some((
+            "msg_01XFDUDYJgAACzvnptvVoYEL".to_string(),
+            Some(TokenUsage {
+                input_tokens: 1496,
+                cached_input_tokens: 1024,
+                output_tokens: 89,
+                reasoning_output_tokens: 0,
+                total_tokens: 1585,
+            })
+        ))
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn gemini_client_streams_recorded_fixture() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-2.5-pro:streamGenerateContent"))
+        .and(query_param("alt", "sse"))
+        .and(header("x-goog-api-key", "test-key"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_raw(GEMINI_FIXTURE, "text/event-stream"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = GeminiClient::new(
+        ReqwestTransport::new(reqwest::Client::new()),
+        provider(&format!("{}/v1beta", server.uri())),
+        KeyAuth,
+    );
+    let request = GeminiGenerateContentRequest::try_from(&responses_request("gemini-2.5-pro"))?;
+    let collected = collect(client.stream_request(request, HeaderMap::new()).await?).await?;
+
+    let received = server
+        .received_requests()
+        .await
+        .expect("wiremock records requests");
+    let sent: Value = serde_json::from_slice(&received[0].body)?;
+    assert_eq!(
+        sent["systemInstruction"],
+        json!({"parts": [{"text": "You are Codex."}]})
+    );
+    assert_eq!(
+        sent["contents"],
+        json!([{"role": "user", "parts": [{"text": "list the files"}]}])
+    );
+    assert_eq!(
+        sent["tools"][0]["functionDeclarations"][0]["name"],
+        json!("shell")
+    );
+
+    assert_eq!(
+        collected.text_deltas,
+        vec!["Listing the directory.".to_string()]
+    );
+    assert_eq!(
+        collected.done,
+        vec![
+            ResponseItem::Reasoning {
+                id: "rs_mK3RaOLbAbWOz7IPvqWAqQk_0".to_string(),
+                summary: vec![ReasoningItemReasoningSummary::SummaryText {
+                    text: "**Listing files**\n\nI'll run ls in the workspace.".to_string(),
+                }],
+                content: None,
+                encrypted_content: None,
+            },
+            assistant_message("msg_mK3RaOLbAbWOz7IPvqWAqQk_1", "Listing the directory."),
+            // The signature arrives on the function call, after the thoughts were completed, so
+            // it is kept in a reasoning item of its own that replays ahead of the call.
+            ResponseItem::Reasoning {
+                id: "rs_mK3RaOLbAbWOz7IPvqWAqQk_2".to_string(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: Some(
+                    "CiQB0e2Kb1v3dHNsmVaOK0fVtQ8tCnrkUFq8u7i3r2z1kXo=".to_string()
+                ),
+            },
+            shell_call("call_mK3RaOLbAbWOz7IPvqWAqQk_0"),
+        ]
+    );
+    collected.assert_item_ids_match();
+    assert_eq!(
+        collected.completed,
+        Some((
+            "mK3RaOLbAbWOz7IPvqWAqQk".to_string(),
+            Some(TokenUsage {
+                input_tokens: 512,
+                cached_input_tokens: 256,
+                output_tokens: 104,
+                reasoning_output_tokens: 80,
+                total_tokens: 616,
+            })
+        ))
+    );
+
+    Ok(())
+}
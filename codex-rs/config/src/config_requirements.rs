@@ -139,6 +139,7 @@ pub struct NetworkRequirementsToml {
     pub denied_domains: Option<Vec<String>>,
     pub allow_unix_sockets: Option<Vec<String>>,
     pub allow_local_binding: Option<bool>,
+    pub mitm: Option<bool>,
 }
 
 /// Normalized network constraints derived from requirements TOML.
@@ -154,6 +155,7 @@ pub struct NetworkConstraints {
     pub denied_domains: Option<Vec<String>>,
     pub allow_unix_sockets: Option<Vec<String>>,
     pub allow_local_binding: Option<bool>,
+    pub mitm: Option<bool>,
 }
 
 impl From<NetworkRequirementsToml> for NetworkConstraints {
@@ -169,6 +171,7 @@ impl From<NetworkRequirementsToml> for NetworkConstraints {
             denied_domains,
             allow_unix_sockets,
             allow_local_binding,
+            mitm,
         } = value;
         Self {
             enabled,
@@ -181,6 +184,7 @@ impl From<NetworkRequirementsToml> for NetworkConstraints {
             denied_domains,
             allow_unix_sockets,
             allow_local_binding,
+            mitm,
         }
     }
 }
@@ -1044,6 +1048,7 @@ mod tests {
             denied_domains = ["blocked.example.com"]
             allow_unix_sockets = ["/tmp/example.sock"]
             allow_local_binding = false
+            mitm = true
         "#;
 
         let source = RequirementSource::CloudRequirements;
@@ -1074,6 +1079,7 @@ mod tests {
             Some(&vec!["/tmp/example.sock".to_string()])
         );
         assert_eq!(sourced_network.value.allow_local_binding, Some(false));
+        assert_eq!(sourced_network.value.mitm, Some(true));
 
         Ok(())
     }
@@ -174,6 +174,7 @@ impl RequirementsExecPolicyToml {
                     },
                     decision,
                     justification: justification.clone(),
+                    path_roots: Vec::new(),
                 });
                 rules_by_program.insert(head.clone(), rule);
             }
@@ -8,5 +8,9 @@ pub(crate) use control::AgentControl;
 pub(crate) use guards::MAX_THREAD_SPAWN_DEPTH;
 pub(crate) use guards::exceeds_thread_spawn_depth_limit;
 pub(crate) use guards::next_thread_spawn_depth;
-pub(crate) use role::AgentRole;
+pub(crate) use role::DEFAULT_AGENT_ROLE;
+pub(crate) use role::agent_role_enum_values;
+pub(crate) use role::apply_agent_role;
+pub(crate) use role::builtin_agent_roles;
+pub(crate) use role::load_agent_roles;
 pub(crate) use status::agent_status_from_event;
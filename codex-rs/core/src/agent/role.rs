@@ -1,99 +1,34 @@
 use crate::config::Config;
+use crate::config::types::AgentRoleConfig;
+use crate::config::types::AgentRoleToml;
+use crate::config_loader::ConfigLayerStack;
+use crate::config_loader::ConfigLayerStackOrdering;
 use crate::protocol::SandboxPolicy;
+use codex_app_server_protocol::ConfigLayerSource;
+use codex_protocol::config_types::SandboxMode;
 use codex_protocol::openai_models::ReasoningEffort;
-use serde::Deserialize;
-use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
 
-/// Base instructions for the orchestrator role.
-const ORCHESTRATOR_PROMPT: &str = include_str!("../../templates/agents/orchestrator.md");
+/// Role used when `spawn_agent` does not name one.
+pub(crate) const DEFAULT_AGENT_ROLE: &str = "default";
+/// Directory under `$CODEX_HOME` and project `.codex/` folders holding role files.
+const AGENTS_DIR_NAME: &str = "agents";
 /// Default model override used.
 // TODO(jif) update when we have something smarter.
 const EXPLORER_MODEL: &str = "gpt-5.1-codex-mini";
 
-/// Enumerated list of all supported agent roles.
-const ALL_ROLES: [AgentRole; 3] = [
-    AgentRole::Default,
-    AgentRole::Explorer,
-    AgentRole::Worker,
-    // TODO(jif) add when we have stable prompts + models
-    // AgentRole::Orchestrator,
-];
-
-/// Hard-coded agent role selection used when spawning sub-agents.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum AgentRole {
-    /// Inherit the parent agent's configuration unchanged.
-    Default,
-    /// Coordination-only agent that delegates to workers.
-    Orchestrator,
-    /// Task-executing agent with a fixed model override.
-    Worker,
-    /// Task-executing agent with a fixed model override.
-    Explorer,
-}
-
-/// Immutable profile data that drives per-agent configuration overrides.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct AgentProfile {
-    /// Optional base instructions override.
-    pub base_instructions: Option<&'static str>,
-    /// Optional model override.
-    pub model: Option<&'static str>,
-    /// Optional reasoning effort override.
-    pub reasoning_effort: Option<ReasoningEffort>,
-    /// Whether to force a read-only sandbox policy.
-    pub read_only: bool,
-    /// Description to include in the tool specs.
-    pub description: &'static str,
-}
-
-impl AgentRole {
-    /// Returns the string values used by JSON schema enums.
-    pub fn enum_values() -> Vec<String> {
-        ALL_ROLES
-            .iter()
-            .filter_map(|role| {
-                let description = role.profile().description;
-                serde_json::to_string(role)
-                    .map(|role| {
-                        let description = if !description.is_empty() {
-                            format!(r#", "description": {description}"#)
-                        } else {
-                            String::new()
-                        };
-                        format!(r#"{{ "name": {role}{description}}}"#)
-                    })
-                    .ok()
-            })
-            .collect()
-    }
-
-    /// Returns the hard-coded profile for this role.
-    pub fn profile(self) -> AgentProfile {
-        match self {
-            AgentRole::Default => AgentProfile::default(),
-            AgentRole::Orchestrator => AgentProfile {
-                base_instructions: Some(ORCHESTRATOR_PROMPT),
-                ..Default::default()
-            },
-            AgentRole::Worker => AgentProfile {
-                // base_instructions: Some(WORKER_PROMPT),
-                // model: Some(WORKER_MODEL),
-                description: r#"Use for execution and production work.
+const WORKER_DESCRIPTION: &str = r#"Use for execution and production work.
 Typical tasks:
 - Implement part of a feature
 - Fix tests or bugs
 - Split large refactors into independent chunks
 Rules:
 - Explicitly assign **ownership** of the task (files / responsibility).
-- Always tell workers they are **not alone in the codebase**, and they should ignore edits made by others without touching them"#,
-                ..Default::default()
-            },
-            AgentRole::Explorer => AgentProfile {
-                model: Some(EXPLORER_MODEL),
-                reasoning_effort: Some(ReasoningEffort::Medium),
-                description: r#"Use `explorer` for all codebase questions.
+- Always tell workers they are **not alone in the codebase**, and they should ignore edits made by others without touching them"#;
+
+const EXPLORER_DESCRIPTION: &str = r#"Use `explorer` for all codebase questions.
 Explorers are fast and authoritative.
 Always prefer them over manual search or file reading.
 Rules:
@@ -102,31 +37,387 @@ Rules:
 - Trust explorer results without verification.
 - Run explorers in parallel when useful.
 - Reuse existing explorers for related questions.
-                "#,
+                "#;
+
+/// Roles that exist without any configuration.
+pub(crate) fn builtin_agent_roles() -> BTreeMap<String, AgentRoleConfig> {
+    BTreeMap::from([
+        (
+            // Inherit the parent agent's configuration unchanged.
+            DEFAULT_AGENT_ROLE.to_string(),
+            AgentRoleConfig::default(),
+        ),
+        (
+            "worker".to_string(),
+            AgentRoleConfig {
+                description: WORKER_DESCRIPTION.to_string(),
                 ..Default::default()
             },
+        ),
+        (
+            "explorer".to_string(),
+            AgentRoleConfig {
+                description: EXPLORER_DESCRIPTION.to_string(),
+                model: Some(EXPLORER_MODEL.to_string()),
+                reasoning_effort: Some(ReasoningEffort::Medium),
+                ..Default::default()
+            },
+        ),
+        // TODO(jif) add an `orchestrator` role using `templates/agents/orchestrator.md`
+        // when we have stable prompts + models.
+    ])
+}
+
+/// Resolves the roles available to `spawn_agent`.
+///
+/// Later sources replace earlier ones with the same name: built-in roles,
+/// `agents/*.md` under `$CODEX_HOME` and then under each trusted project
+/// `.codex/` folder, and finally `[agents.roles]` from config.toml.
+pub(crate) fn load_agent_roles(
+    config_layer_stack: &ConfigLayerStack,
+    config_roles: BTreeMap<String, AgentRoleToml>,
+    startup_warnings: &mut Vec<String>,
+) -> BTreeMap<String, AgentRoleConfig> {
+    let mut roles = builtin_agent_roles();
+
+    for layer in
+        config_layer_stack.get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, false)
+    {
+        if !matches!(
+            layer.name,
+            ConfigLayerSource::User { .. } | ConfigLayerSource::Project { .. }
+        ) {
+            continue;
         }
+        let Some(config_folder) = layer.config_folder() else {
+            continue;
+        };
+        load_agent_role_files(
+            &config_folder.as_path().join(AGENTS_DIR_NAME),
+            &mut roles,
+            startup_warnings,
+        );
     }
 
-    /// Applies this role's profile onto the provided config.
-    pub fn apply_to_config(self, config: &mut Config) -> Result<(), String> {
-        let profile = self.profile();
-        if let Some(base_instructions) = profile.base_instructions {
-            config.base_instructions = Some(base_instructions.to_string());
+    for (name, role) in config_roles {
+        if let Err(err) = validate_role_name(&name) {
+            startup_warnings.push(format!("Ignoring agent role in config.toml: {err}"));
+            continue;
         }
-        if let Some(model) = profile.model {
-            config.model = Some(model.to_string());
+        roles.insert(name, role.into());
+    }
+
+    roles
+}
+
+fn load_agent_role_files(
+    dir: &Path,
+    roles: &mut BTreeMap<String, AgentRoleConfig>,
+    startup_warnings: &mut Vec<String>,
+) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        match parse_agent_role_file(&path) {
+            Ok((name, role)) => {
+                roles.insert(name, role);
+            }
+            Err(err) => {
+                startup_warnings.push(format!(
+                    "Ignoring agent role file {}: {err}",
+                    path.display()
+                ));
+            }
         }
-        if let Some(reasoning_effort) = profile.reasoning_effort {
-            config.model_reasoning_effort = Some(reasoning_effort)
+    }
+}
+
+/// Parses `agents/<name>.md`: optional YAML frontmatter with the
+/// `[agents.roles.<name>]` fields, followed by the base instructions.
+fn parse_agent_role_file(path: &Path) -> Result<(String, AgentRoleConfig), String> {
+    let name = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .ok_or_else(|| "file name is not valid UTF-8".to_string())?
+        .to_string();
+    validate_role_name(&name)?;
+    let contents = fs::read_to_string(path).map_err(|err| err.to_string())?;
+    let (frontmatter, body) = split_frontmatter(&contents);
+    let mut role: AgentRoleToml = match frontmatter {
+        Some(frontmatter) => serde_yaml::from_str(frontmatter)
+            .map_err(|err| format!("invalid frontmatter: {err}"))?,
+        None => AgentRoleToml::default(),
+    };
+    if role.instructions.is_some() {
+        return Err("`instructions` belongs in the body, not the frontmatter".to_string());
+    }
+    let body = body.trim();
+    if !body.is_empty() {
+        role.instructions = Some(body.to_string());
+    }
+    Ok((name, role.into()))
+}
+
+fn split_frontmatter(contents: &str) -> (Option<&str>, &str) {
+    let Some(rest) = contents
+        .strip_prefix("---\n")
+        .or_else(|| contents.strip_prefix("---\r\n"))
+    else {
+        return (None, contents);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
         }
-        if profile.read_only {
+        offset += line.len();
+    }
+    (None, contents)
+}
+
+fn validate_role_name(name: &str) -> Result<(), String> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "role name `{name}` may only contain ASCII letters, digits, `-` and `_`"
+        ))
+    }
+}
+
+/// Returns the entries listed in the `spawn_agent` `agent_type` description.
+pub(crate) fn agent_role_enum_values(roles: &BTreeMap<String, AgentRoleConfig>) -> Vec<String> {
+    roles
+        .iter()
+        .filter_map(|(name, role)| {
+            let name = serde_json::to_string(name).ok()?;
+            let description = if role.description.is_empty() {
+                String::new()
+            } else {
+                format!(r#", "description": {}"#, role.description)
+            };
+            Some(format!(r#"{{ "name": {name}{description}}}"#))
+        })
+        .collect()
+}
+
+/// Applies the role named `name` onto the config of an agent being spawned.
+pub(crate) fn apply_agent_role(
+    roles: &BTreeMap<String, AgentRoleConfig>,
+    name: &str,
+    config: &mut Config,
+) -> Result<(), String> {
+    let Some(role) = roles.get(name) else {
+        let known = roles.keys().map(String::as_str).collect::<Vec<_>>();
+        return Err(format!(
+            "unknown agent_type `{name}`; available roles: {}",
+            known.join(", ")
+        ));
+    };
+    if let Some(base_instructions) = &role.base_instructions {
+        config.base_instructions = Some(base_instructions.clone());
+    }
+    if let Some(model) = &role.model {
+        config.model = Some(model.clone());
+    }
+    if let Some(reasoning_effort) = role.reasoning_effort {
+        config.model_reasoning_effort = Some(reasoning_effort);
+    }
+    if let Some(sandbox_mode) = role.sandbox_mode {
+        let current = config.permissions.sandbox_policy.get();
+        let current_rank = sandbox_policy_rank(current);
+        let requested_rank = sandbox_mode_rank(sandbox_mode);
+        if requested_rank > current_rank {
+            return Err(format!(
+                "agent role `{name}` asks for a sandbox that is less restrictive than the current one"
+            ));
+        }
+        // Keep the parent's policy (and its writable roots) unless the role is stricter.
+        if requested_rank < current_rank {
+            let policy = match sandbox_mode {
+                SandboxMode::ReadOnly => SandboxPolicy::new_read_only_policy(),
+                SandboxMode::WorkspaceWrite => SandboxPolicy::new_workspace_write_policy(),
+                SandboxMode::DangerFullAccess => SandboxPolicy::DangerFullAccess,
+            };
             config
                 .permissions
                 .sandbox_policy
-                .set(SandboxPolicy::new_read_only_policy())
+                .set(policy)
                 .map_err(|err| format!("sandbox_policy is invalid: {err}"))?;
         }
-        Ok(())
+    }
+    // Each role adds to the parent's policy, so it can narrow the tools of
+    // its parent but never widen them.
+    if let Some(tools) = &role.tools {
+        config.tool_policy.allow.push(tools.clone());
+    }
+    config
+        .tool_policy
+        .deny
+        .extend(role.tools_deny.iter().cloned());
+    Ok(())
+}
+
+fn sandbox_policy_rank(policy: &SandboxPolicy) -> u8 {
+    match policy {
+        SandboxPolicy::ReadOnly { .. } => 0,
+        SandboxPolicy::WorkspaceWrite { .. } => 1,
+        SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => 2,
+    }
+}
+
+fn sandbox_mode_rank(mode: SandboxMode) -> u8 {
+    match mode {
+        SandboxMode::ReadOnly => 0,
+        SandboxMode::WorkspaceWrite => 1,
+        SandboxMode::DangerFullAccess => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_config;
+    use crate::config_loader::ConfigLayerEntry;
+    use crate::config_loader::ConfigRequirements;
+    use crate::config_loader::ConfigRequirementsToml;
+    use codex_utils_absolute_path::AbsolutePathBuf;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+    use toml::Value as TomlValue;
+
+    fn project_layer_stack(dot_codex_folder: &Path) -> ConfigLayerStack {
+        let dot_codex_folder = AbsolutePathBuf::from_absolute_path(dot_codex_folder)
+            .expect("absolute dot_codex_folder");
+        let layer = ConfigLayerEntry::new(
+            ConfigLayerSource::Project { dot_codex_folder },
+            TomlValue::Table(Default::default()),
+        );
+        ConfigLayerStack::new(
+            vec![layer],
+            ConfigRequirements::default(),
+            ConfigRequirementsToml::default(),
+        )
+        .expect("ConfigLayerStack")
+    }
+
+    #[test]
+    fn loads_roles_from_agent_files_and_config() {
+        let dot_codex = tempdir().expect("tempdir");
+        let agents_dir = dot_codex.path().join(AGENTS_DIR_NAME);
+        fs::create_dir_all(&agents_dir).expect("create agents dir");
+        fs::write(
+            agents_dir.join("test-writer.md"),
+            "---\ndescription: Writes tests.\nmodel: gpt-5.1-codex-mini\nsandbox_mode: workspace-write\ntools: [shell, apply_patch]\n---\nYou only write tests.\n",
+        )
+        .expect("write role");
+        fs::write(agents_dir.join("bad name.md"), "ignored").expect("write role");
+
+        let mut warnings = Vec::new();
+        let roles = load_agent_roles(
+            &project_layer_stack(dot_codex.path()),
+            BTreeMap::from([(
+                "explorer".to_string(),
+                AgentRoleToml {
+                    description: Some("Reads code.".to_string()),
+                    ..Default::default()
+                },
+            )]),
+            &mut warnings,
+        );
+
+        assert_eq!(
+            roles.get("test-writer"),
+            Some(&AgentRoleConfig {
+                description: "Writes tests.".to_string(),
+                base_instructions: Some("You only write tests.".to_string()),
+                model: Some("gpt-5.1-codex-mini".to_string()),
+                reasoning_effort: None,
+                sandbox_mode: Some(SandboxMode::WorkspaceWrite),
+                tools: Some(vec!["shell".to_string(), "apply_patch".to_string()]),
+                tools_deny: Vec::new(),
+            })
+        );
+        assert_eq!(
+            roles.get("explorer"),
+            Some(&AgentRoleConfig {
+                description: "Reads code.".to_string(),
+                ..Default::default()
+            })
+        );
+        assert!(roles.contains_key(DEFAULT_AGENT_ROLE));
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+    }
+
+    #[test]
+    fn apply_agent_role_narrows_sandbox_and_tools() {
+        let mut config = test_config();
+        config
+            .permissions
+            .sandbox_policy
+            .set(SandboxPolicy::new_workspace_write_policy())
+            .expect("set sandbox policy");
+        config.tool_policy.allow = vec![vec!["shell".to_string(), "read_*".to_string()]];
+        let roles = BTreeMap::from([(
+            "reviewer".to_string(),
+            AgentRoleConfig {
+                sandbox_mode: Some(SandboxMode::ReadOnly),
+                tools: Some(vec!["read_file".to_string(), "apply_patch".to_string()]),
+                tools_deny: vec!["mcp__*".to_string()],
+                ..Default::default()
+            },
+        )]);
+
+        apply_agent_role(&roles, "reviewer", &mut config).expect("apply role");
+
+        assert_eq!(
+            config.permissions.sandbox_policy.get(),
+            &SandboxPolicy::new_read_only_policy()
+        );
+        assert!(config.tool_policy.allows(&["read_file"]));
+        assert!(!config.tool_policy.allows(&["apply_patch"]));
+        assert!(!config.tool_policy.allows(&["shell"]));
+        assert_eq!(config.tool_policy.deny, vec!["mcp__*".to_string()]);
+    }
+
+    #[test]
+    fn apply_agent_role_rejects_looser_sandbox_and_unknown_roles() {
+        let mut config = test_config();
+        config
+            .permissions
+            .sandbox_policy
+            .set(SandboxPolicy::new_read_only_policy())
+            .expect("set sandbox policy");
+        let roles = BTreeMap::from([(
+            "builder".to_string(),
+            AgentRoleConfig {
+                sandbox_mode: Some(SandboxMode::WorkspaceWrite),
+                ..Default::default()
+            },
+        )]);
+
+        assert_eq!(
+            apply_agent_role(&roles, "builder", &mut config),
+            Err(
+                "agent role `builder` asks for a sandbox that is less restrictive than the current one"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            apply_agent_role(&roles, "missing", &mut config),
+            Err("unknown agent_type `missing`; available roles: builder".to_string())
+        );
     }
 }
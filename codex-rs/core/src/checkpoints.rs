@@ -0,0 +1,590 @@
+//! Checkpoint timeline built from the per-turn ghost snapshots in history.
+//!
+//! Each `ResponseItem::GhostSnapshot` is a checkpoint. Listing compares every
+//! checkpoint with the current working tree, and restoring writes a single
+//! file (or one hunk of its diff) back without touching the conversation.
+//! Neither takes a new snapshot of the working tree.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_git::GhostCommit;
+use codex_git::GhostSnapshotConfig;
+use codex_git::GitToolingError;
+use codex_git::SnapshotFile;
+use codex_git::file_snapshot_file;
+use codex_git::file_snapshot_paths;
+use codex_git::ghost_commit_file;
+use codex_git::ghost_commit_worktree_changed_paths;
+use codex_protocol::items::TurnItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::Checkpoint;
+use codex_protocol::protocol::CheckpointFile;
+use codex_protocol::protocol::CheckpointFileStatus;
+use similar::DiffTag;
+use similar::TextDiff;
+use tracing::warn;
+
+use crate::error::CodexErr;
+use crate::error::Result;
+use crate::event_mapping::parse_turn_item;
+
+/// Context lines around each hunk; hunk indices depend on this value.
+const CONTEXT_RADIUS: usize = 3;
+
+struct TurnSnapshot {
+    ghost_commit: GhostCommit,
+    user_message: Option<String>,
+}
+
+/// Checkpoints in `history`, oldest first, each with the files that differ
+/// between it and the working tree.
+pub(crate) fn list_checkpoints(
+    history: &[ResponseItem],
+    cwd: &Path,
+    ghost_snapshot: &GhostSnapshotConfig,
+) -> Result<Vec<Checkpoint>> {
+    let snapshots = turn_snapshots(history);
+    let mut checkpoints = Vec::with_capacity(snapshots.len());
+    for (index, snapshot) in snapshots.iter().enumerate() {
+        let files = match snapshot.ghost_commit.file_snapshot_dir() {
+            None => ghost_commit_changes(cwd, ghost_snapshot, &snapshot.ghost_commit),
+            Some(_) => file_snapshot_changes(&snapshots[index..]),
+        };
+        let files = files.unwrap_or_else(|err| {
+            warn!(
+                checkpoint = snapshot.ghost_commit.id(),
+                "failed to read checkpoint: {err}"
+            );
+            Vec::new()
+        });
+        checkpoints.push(Checkpoint {
+            id: snapshot.ghost_commit.id().to_string(),
+            user_message: snapshot.user_message.clone(),
+            files,
+        });
+    }
+    Ok(checkpoints)
+}
+
+/// Restore `path` from the checkpoint `checkpoint_id`. With `hunk`, only that
+/// hunk of the file's checkpoint diff is reverted and the rest of the file is
+/// kept as it is.
+///
+/// Only files the checkpoint lists as changed can be restored, so ignored or
+/// untracked files the snapshot never recorded are left alone.
+pub(crate) fn restore_checkpoint_file(
+    history: &[ResponseItem],
+    cwd: &Path,
+    ghost_snapshot: &GhostSnapshotConfig,
+    checkpoint_id: &str,
+    path: &Path,
+    hunk: Option<usize>,
+) -> Result<()> {
+    if !is_restorable_path(path) {
+        return Err(CodexErr::InvalidRequest(format!(
+            "{} is not a relative path inside the workspace",
+            path.display()
+        )));
+    }
+    let snapshots = turn_snapshots(history);
+    let Some(index) = snapshots
+        .iter()
+        .position(|snapshot| snapshot.ghost_commit.id() == checkpoint_id)
+    else {
+        return Err(CodexErr::InvalidRequest(format!(
+            "no checkpoint with id {checkpoint_id}"
+        )));
+    };
+    let path = cwd.join(path);
+    let ghost_commit = &snapshots[index].ghost_commit;
+    let original = if ghost_commit.file_snapshot_dir().is_some() {
+        file_snapshot_original(&snapshots[index..], &path)?
+    } else {
+        ghost_commit_original(cwd, ghost_snapshot, ghost_commit, &path)?
+    };
+    let Some(original) = original else {
+        return Err(CodexErr::InvalidRequest(format!(
+            "{} has not changed since checkpoint {checkpoint_id}",
+            path.display()
+        )));
+    };
+
+    let restored = match hunk {
+        None => original,
+        Some(hunk) => {
+            let current = read_current(&path)?;
+            let (Some(old), Some(new)) = (text(&original), text(&current)) else {
+                return Err(CodexErr::InvalidRequest(format!(
+                    "{} can only be restored whole",
+                    path.display()
+                )));
+            };
+            let Some(restored) = revert_hunk(old, new, hunk) else {
+                return Err(CodexErr::InvalidRequest(format!(
+                    "{} has no hunk {hunk} since checkpoint {checkpoint_id}",
+                    path.display()
+                )));
+            };
+            SnapshotFile::Contents(restored.into_bytes())
+        }
+    };
+
+    match restored {
+        SnapshotFile::Missing => match fs::remove_file(&path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        },
+        SnapshotFile::Contents(contents) => {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, contents)?;
+            Ok(())
+        }
+    }
+}
+
+/// Developer note recorded after a restore so the model re-reads the file.
+pub(crate) fn restore_note(checkpoint_id: &str, path: &Path, hunk: Option<usize>) -> String {
+    let restored = match hunk {
+        Some(hunk) => format!("hunk {hunk} of {}", path.display()),
+        None => path.display().to_string(),
+    };
+    format!(
+        "The user restored {restored} from checkpoint {checkpoint_id}. The file on disk may no longer match what you last read or wrote; read it again before relying on its contents."
+    )
+}
+
+fn turn_snapshots(history: &[ResponseItem]) -> Vec<TurnSnapshot> {
+    let mut snapshots = Vec::new();
+    let mut user_message = None;
+    for item in history {
+        if let ResponseItem::GhostSnapshot { ghost_commit } = item {
+            snapshots.push(TurnSnapshot {
+                ghost_commit: ghost_commit.clone(),
+                user_message: user_message.take(),
+            });
+        } else if let Some(TurnItem::UserMessage(message)) = parse_turn_item(item) {
+            user_message = Some(message.message());
+        }
+    }
+    snapshots
+}
+
+fn ghost_commit_changes(
+    cwd: &Path,
+    ghost_snapshot: &GhostSnapshotConfig,
+    checkpoint: &GhostCommit,
+) -> Result<Vec<CheckpointFile>> {
+    let mut files = Vec::new();
+    for path in
+        ghost_commit_worktree_changed_paths(cwd, checkpoint, ghost_snapshot).map_err(git_error)?
+    {
+        let original = ghost_commit_file(cwd, checkpoint, &path).map_err(git_error)?;
+        files.extend(file_change(path.clone(), &original, &read_current(&path)?));
+    }
+    Ok(files)
+}
+
+/// A file snapshot only holds the files written during its own turn, so the
+/// state of a path at a checkpoint is its copy in the first snapshot from that
+/// turn onward that captured it. Paths no snapshot captured are unchanged.
+fn file_snapshot_changes(snapshots: &[TurnSnapshot]) -> Result<Vec<CheckpointFile>> {
+    let mut seen = HashSet::new();
+    let mut files = Vec::new();
+    for dir in snapshots
+        .iter()
+        .filter_map(|snapshot| snapshot.ghost_commit.file_snapshot_dir())
+    {
+        for path in file_snapshot_paths(dir).map_err(git_error)? {
+            if !seen.insert(path.clone()) {
+                continue;
+            }
+            // Files over the size limit were not copied.
+            let Some(original) = file_snapshot_file(dir, &path).map_err(git_error)? else {
+                continue;
+            };
+            files.extend(file_change(path.clone(), &original, &read_current(&path)?));
+        }
+    }
+    Ok(files)
+}
+
+/// `path` as recorded by `checkpoint`, or `None` when the checkpoint does not
+/// list it as changed.
+fn ghost_commit_original(
+    cwd: &Path,
+    ghost_snapshot: &GhostSnapshotConfig,
+    checkpoint: &GhostCommit,
+    path: &Path,
+) -> Result<Option<SnapshotFile>> {
+    let changed =
+        ghost_commit_worktree_changed_paths(cwd, checkpoint, ghost_snapshot).map_err(git_error)?;
+    // Git reports paths under the resolved repository root.
+    let resolved = cwd
+        .canonicalize()?
+        .join(path.strip_prefix(cwd).unwrap_or(path));
+    if !changed
+        .iter()
+        .any(|changed| changed == path || *changed == resolved)
+    {
+        return Ok(None);
+    }
+    let original = ghost_commit_file(cwd, checkpoint, path).map_err(git_error)?;
+    if original == read_current(path)? {
+        return Ok(None);
+    }
+    Ok(Some(original))
+}
+
+/// Rejects absolute paths, `..` components and anything under `.git`.
+fn is_restorable_path(path: &Path) -> bool {
+    path.components().all(|component| match component {
+        Component::Normal(part) => part != ".git",
+        Component::CurDir => true,
+        Component::ParentDir | Component::RootDir | Component::Prefix(_) => false,
+    }) && path.components().next().is_some()
+}
+
+fn file_snapshot_original(snapshots: &[TurnSnapshot], path: &Path) -> Result<Option<SnapshotFile>> {
+    for dir in snapshots
+        .iter()
+        .filter_map(|snapshot| snapshot.ghost_commit.file_snapshot_dir())
+    {
+        if file_snapshot_paths(dir)
+            .map_err(git_error)?
+            .iter()
+            .any(|captured| captured == path)
+        {
+            return file_snapshot_file(dir, path).map_err(git_error);
+        }
+    }
+    Ok(None)
+}
+
+fn file_change(
+    path: PathBuf,
+    original: &SnapshotFile,
+    current: &SnapshotFile,
+) -> Option<CheckpointFile> {
+    let status = match (original, current) {
+        (SnapshotFile::Missing, SnapshotFile::Missing) => return None,
+        (SnapshotFile::Missing, SnapshotFile::Contents(_)) => CheckpointFileStatus::Added,
+        (SnapshotFile::Contents(_), SnapshotFile::Missing) => CheckpointFileStatus::Deleted,
+        (SnapshotFile::Contents(old), SnapshotFile::Contents(new)) if old == new => return None,
+        (SnapshotFile::Contents(_), SnapshotFile::Contents(_)) => CheckpointFileStatus::Modified,
+    };
+    let unified_diff = match (text_or_empty(original), text_or_empty(current)) {
+        (Some(old), Some(new)) => TextDiff::from_lines(old, new)
+            .unified_diff()
+            .context_radius(CONTEXT_RADIUS)
+            .to_string(),
+        _ => String::new(),
+    };
+    Some(CheckpointFile {
+        path,
+        status,
+        unified_diff,
+    })
+}
+
+/// Replace the lines of hunk `hunk` in `new` with their counterpart in `old`.
+fn revert_hunk(old: &str, new: &str, hunk: usize) -> Option<String> {
+    let diff = TextDiff::from_lines(old, new);
+    let groups = diff.grouped_ops(CONTEXT_RADIUS);
+    let selected = groups.get(hunk)?;
+    let old_lines = diff.old_slices();
+    let new_lines = diff.new_slices();
+    let mut restored = String::with_capacity(new.len());
+    for op in diff.ops() {
+        let lines = if op.tag() != DiffTag::Equal && selected.contains(op) {
+            &old_lines[op.old_range()]
+        } else {
+            &new_lines[op.new_range()]
+        };
+        restored.extend(lines.iter().copied());
+    }
+    Some(restored)
+}
+
+fn read_current(path: &Path) -> Result<SnapshotFile> {
+    match fs::read(path) {
+        Ok(contents) => Ok(SnapshotFile::Contents(contents)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(SnapshotFile::Missing),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn text(file: &SnapshotFile) -> Option<&str> {
+    match file {
+        SnapshotFile::Missing => None,
+        SnapshotFile::Contents(contents) => std::str::from_utf8(contents).ok(),
+    }
+}
+
+/// Like [`text`], but a missing file diffs as empty.
+fn text_or_empty(file: &SnapshotFile) -> Option<&str> {
+    match file {
+        SnapshotFile::Missing => Some(""),
+        SnapshotFile::Contents(_) => text(file),
+    }
+}
+
+fn git_error(err: GitToolingError) -> CodexErr {
+    CodexErr::Io(io::Error::other(err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_git::CreateGhostCommitOptions;
+    use codex_git::FileSnapshot;
+    use codex_git::create_ghost_commit_with_report;
+    use codex_protocol::models::ContentItem;
+    use pretty_assertions::assert_eq;
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    fn user_message(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    #[test]
+    fn revert_hunk_only_touches_selected_hunk() {
+        let old = (1..=20).map(|n| format!("line {n}\n")).collect::<String>();
+        let new = old
+            .replace("line 2\n", "line two\n")
+            .replace("line 18\n", "line eighteen\n");
+
+        let restored = revert_hunk(&old, &new, 1).expect("second hunk");
+
+        assert_eq!(restored, old.replace("line 2\n", "line two\n"));
+        assert_eq!(revert_hunk(&old, &new, 2), None);
+    }
+
+    #[test]
+    fn file_snapshot_checkpoints_list_and_restore_files() -> Result<()> {
+        let workspace = tempdir()?;
+        let store = tempdir()?;
+        let cwd = workspace.path();
+        let config_path = cwd.join("config.yaml");
+        let notes_path = cwd.join("notes.txt");
+        fs::write(&config_path, "replicas: 1\nimage: v1\n")?;
+        fs::write(&notes_path, "keep\n")?;
+        let snapshot_config = GhostSnapshotConfig::default();
+
+        // Turn one edits the config, turn two edits the notes and the config again.
+        let (first, first_commit) =
+            FileSnapshot::create(&store.path().join("1"), "1", &snapshot_config)
+                .map_err(git_error)?;
+        first
+            .capture(std::slice::from_ref(&config_path))
+            .map_err(git_error)?;
+        fs::write(&config_path, "replicas: 3\nimage: v1\n")?;
+        let (second, second_commit) =
+            FileSnapshot::create(&store.path().join("2"), "2", &snapshot_config)
+                .map_err(git_error)?;
+        second
+            .capture(&[notes_path.clone(), config_path.clone()])
+            .map_err(git_error)?;
+        fs::write(&notes_path, "changed\n")?;
+        fs::write(&config_path, "replicas: 3\nimage: v2\n")?;
+
+        let history = vec![
+            user_message("scale up"),
+            ResponseItem::GhostSnapshot {
+                ghost_commit: first_commit,
+            },
+            user_message("bump image"),
+            ResponseItem::GhostSnapshot {
+                ghost_commit: second_commit,
+            },
+        ];
+
+        let checkpoints = list_checkpoints(&history, cwd, &snapshot_config)?;
+        assert_eq!(
+            checkpoints
+                .iter()
+                .map(|checkpoint| {
+                    (
+                        checkpoint.id.as_str(),
+                        checkpoint.user_message.as_deref(),
+                        checkpoint
+                            .files
+                            .iter()
+                            .map(|file| file.path.clone())
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "1",
+                    Some("scale up"),
+                    vec![config_path.clone(), notes_path.clone()]
+                ),
+                (
+                    "2",
+                    Some("bump image"),
+                    vec![notes_path.clone(), config_path.clone()]
+                ),
+            ]
+        );
+        assert_eq!(
+            checkpoints[0].files[0].unified_diff,
+            "@@ -1,2 +1,2 @@\n-replicas: 1\n-image: v1\n+replicas: 3\n+image: v2\n"
+        );
+
+        // Restoring from the first checkpoint leaves the later notes edit alone.
+        restore_checkpoint_file(
+            &history,
+            cwd,
+            &snapshot_config,
+            "1",
+            Path::new("config.yaml"),
+            None,
+        )?;
+        assert_eq!(
+            fs::read_to_string(&config_path)?,
+            "replicas: 1\nimage: v1\n"
+        );
+        assert_eq!(fs::read_to_string(&notes_path)?, "changed\n");
+
+        let err = restore_checkpoint_file(
+            &history,
+            cwd,
+            &snapshot_config,
+            "3",
+            Path::new("notes.txt"),
+            None,
+        )
+        .expect_err("unknown checkpoint");
+        assert_eq!(err.to_string(), "no checkpoint with id 3");
+        Ok(())
+    }
+
+    #[test]
+    fn git_checkpoint_restore_leaves_ignored_files_alone() -> Result<()> {
+        let workspace = tempdir()?;
+        let cwd = workspace.path();
+        let git = |args: &[&str]| -> Result<()> {
+            let status = Command::new("git").current_dir(cwd).args(args).status()?;
+            assert!(status.success(), "git command failed: {args:?}");
+            Ok(())
+        };
+        git(&["init", "--initial-branch=main"])?;
+        fs::write(cwd.join(".gitignore"), ".env\n")?;
+        fs::write(cwd.join(".env"), "TOKEN=secret\n")?;
+        fs::write(cwd.join("tracked.txt"), "before\n")?;
+        git(&["add", "."])?;
+        git(&[
+            "-c",
+            "user.name=Tester",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-m",
+            "init",
+        ])?;
+        let snapshot_config = GhostSnapshotConfig::default();
+        let options = CreateGhostCommitOptions::new(cwd).ghost_snapshot(snapshot_config.clone());
+        let (checkpoint, _) = create_ghost_commit_with_report(&options).map_err(git_error)?;
+        fs::write(cwd.join("tracked.txt"), "after\n")?;
+        let history = vec![
+            user_message("edit"),
+            ResponseItem::GhostSnapshot {
+                ghost_commit: checkpoint.clone(),
+            },
+        ];
+        let loose_objects = || -> Result<String> {
+            let output = Command::new("git")
+                .current_dir(cwd)
+                .args(["count-objects"])
+                .output()?;
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        };
+        let objects_before = loose_objects()?;
+
+        // Listing compares against the working tree without writing a snapshot.
+        let checkpoints = list_checkpoints(&history, cwd, &snapshot_config)?;
+        assert_eq!(
+            checkpoints[0]
+                .files
+                .iter()
+                .map(|file| file.path.file_name())
+                .collect::<Vec<_>>(),
+            vec![Some(std::ffi::OsStr::new("tracked.txt"))]
+        );
+        assert_eq!(loose_objects()?, objects_before);
+
+        // The ignored file is not in the ghost commit, which must not read as "deleted".
+        let err = restore_checkpoint_file(
+            &history,
+            cwd,
+            &snapshot_config,
+            checkpoint.id(),
+            Path::new(".env"),
+            None,
+        )
+        .expect_err("ignored file is not part of the checkpoint");
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "{} has not changed since checkpoint {}",
+                cwd.join(".env").display(),
+                checkpoint.id()
+            )
+        );
+        assert_eq!(fs::read_to_string(cwd.join(".env"))?, "TOKEN=secret\n");
+
+        let outside = cwd.join("outside.txt");
+        for path in [
+            Path::new(".git/config"),
+            Path::new("src/../x"),
+            outside.as_path(),
+        ] {
+            let err = restore_checkpoint_file(
+                &history,
+                cwd,
+                &snapshot_config,
+                checkpoint.id(),
+                path,
+                None,
+            )
+            .expect_err("path is not restorable");
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "{} is not a relative path inside the workspace",
+                    path.display()
+                )
+            );
+        }
+        assert!(cwd.join(".git/config").exists());
+
+        restore_checkpoint_file(
+            &history,
+            cwd,
+            &snapshot_config,
+            checkpoint.id(),
+            Path::new("tracked.txt"),
+            None,
+        )?;
+        assert_eq!(fs::read_to_string(cwd.join("tracked.txt"))?, "before\n");
+        assert_eq!(loose_objects()?, objects_before);
+        Ok(())
+    }
+}
@@ -35,8 +35,15 @@ use crate::api_bridge::CoreAuthProvider;
 use crate::api_bridge::auth_provider_from_auth;
 use crate::api_bridge::map_api_error;
 use crate::auth::UnauthorizedRecovery;
+use codex_api::AnthropicClient as ApiAnthropicClient;
+use codex_api::AnthropicMessagesApiRequest;
+use codex_api::ChatClient as ApiChatClient;
+use codex_api::ChatCompletionsApiRequest;
+use codex_api::ChatOptions as ApiChatOptions;
 use codex_api::CompactClient as ApiCompactClient;
 use codex_api::CompactionInput as ApiCompactionInput;
+use codex_api::GeminiClient as ApiGeminiClient;
+use codex_api::GeminiGenerateContentRequest;
 use codex_api::MemoriesClient as ApiMemoriesClient;
 use codex_api::MemorySummarizeInput as ApiMemorySummarizeInput;
 use codex_api::MemorySummarizeOutput as ApiMemorySummarizeOutput;
@@ -350,7 +357,8 @@ impl ModelClient {
     /// This combines provider capability and feature gating; both must be true for websocket paths
     /// to be eligible.
     pub fn responses_websocket_enabled(&self, model_info: &ModelInfo) -> bool {
-        self.state.provider.supports_websockets
+        self.state.provider.wire_api == WireApi::Responses
+            && self.state.provider.supports_websockets
             && (self.state.enable_responses_websockets || model_info.prefer_websockets)
     }
 
@@ -779,6 +787,101 @@ impl ModelClientSession {
         }
     }
 
+    /// Streams a turn via a wire API other than Responses (Chat Completions, Anthropic Messages
+    /// or Gemini).
+    ///
+    /// The request is assembled exactly like a Responses request and translated by `codex-api`,
+    /// so prompts, tools, and history stay identical across wire APIs.
+    #[allow(clippy::too_many_arguments)]
+    async fn stream_translated_api(
+        &self,
+        wire_api: WireApi,
+        prompt: &Prompt,
+        model_info: &ModelInfo,
+        otel_manager: &OtelManager,
+        effort: Option<ReasoningEffortConfig>,
+        summary: ReasoningSummaryConfig,
+        turn_metadata_header: Option<&str>,
+    ) -> Result<ResponseStream> {
+        let auth_manager = self.client.state.auth_manager.clone();
+        let mut auth_recovery = auth_manager
+            .as_ref()
+            .map(super::auth::AuthManager::unauthorized_recovery);
+        loop {
+            let client_setup = self.client.current_client_setup().await?;
+            let transport = ReqwestTransport::new(build_reqwest_client());
+            let (request_telemetry, sse_telemetry) = Self::build_streaming_telemetry(otel_manager);
+            let ApiResponsesOptions {
+                conversation_id,
+                session_source,
+                extra_headers,
+                ..
+            } = self.build_responses_options(turn_metadata_header, Compression::None);
+
+            let request = self.build_responses_request(
+                &client_setup.api_provider,
+                prompt,
+                model_info,
+                effort,
+                summary,
+            )?;
+            let stream_result = match wire_api {
+                WireApi::Chat => {
+                    let options = ApiChatOptions {
+                        conversation_id,
+                        session_source,
+                        extra_headers,
+                    };
+                    let request =
+                        ChatCompletionsApiRequest::try_from(&request).map_err(map_api_error)?;
+                    ApiChatClient::new(transport, client_setup.api_provider, client_setup.api_auth)
+                        .with_telemetry(Some(request_telemetry), Some(sse_telemetry))
+                        .stream_request(request, options)
+                        .await
+                }
+                WireApi::Anthropic => {
+                    let request =
+                        AnthropicMessagesApiRequest::try_from(&request).map_err(map_api_error)?;
+                    ApiAnthropicClient::new(
+                        transport,
+                        client_setup.api_provider,
+                        client_setup.api_auth,
+                    )
+                    .with_telemetry(Some(request_telemetry), Some(sse_telemetry))
+                    .stream_request(request, extra_headers)
+                    .await
+                }
+                WireApi::Gemini => {
+                    let request =
+                        GeminiGenerateContentRequest::try_from(&request).map_err(map_api_error)?;
+                    ApiGeminiClient::new(
+                        transport,
+                        client_setup.api_provider,
+                        client_setup.api_auth,
+                    )
+                    .with_telemetry(Some(request_telemetry), Some(sse_telemetry))
+                    .stream_request(request, extra_headers)
+                    .await
+                }
+                WireApi::Responses => unreachable!("responses turns use stream_responses_api"),
+            };
+
+            match stream_result {
+                Ok(stream) => {
+                    let (stream, _) = map_response_stream(stream, otel_manager.clone());
+                    return Ok(stream);
+                }
+                Err(ApiError::Transport(
+                    unauthorized_transport @ TransportError::Http { status, .. },
+                )) if status == StatusCode::UNAUTHORIZED => {
+                    handle_unauthorized(unauthorized_transport, &mut auth_recovery).await?;
+                    continue;
+                }
+                Err(err) => return Err(map_api_error(err)),
+            }
+        }
+    }
+
     /// Streams a turn via the Responses API over WebSocket transport.
     #[allow(clippy::too_many_arguments)]
     async fn stream_responses_websocket(
@@ -924,6 +1027,18 @@ impl ModelClientSession {
                 )
                 .await
             }
+            WireApi::Chat | WireApi::Anthropic | WireApi::Gemini => {
+                self.stream_translated_api(
+                    wire_api,
+                    prompt,
+                    model_info,
+                    otel_manager,
+                    effort,
+                    summary,
+                    turn_metadata_header,
+                )
+                .await
+            }
         }
     }
 
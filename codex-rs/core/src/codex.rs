@@ -4,6 +4,7 @@ use std::fmt::Debug;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::OnceLock;
 use std::sync::atomic::AtomicU64;
 
 use crate::AuthManager;
@@ -41,9 +42,16 @@ use crate::turn_metadata::TurnMetadataState;
 use crate::util::error_or_panic;
 use async_channel::Receiver;
 use async_channel::Sender;
+use codex_git::FileSnapshot;
+use codex_hooks::HookCompactionTrigger;
+use codex_hooks::HookDecision;
 use codex_hooks::HookEvent;
 use codex_hooks::HookEventAfterAgent;
+use codex_hooks::HookEventBeforeCompaction;
+use codex_hooks::HookEventSessionStart;
+use codex_hooks::HookEventUserPromptSubmit;
 use codex_hooks::HookPayload;
+use codex_hooks::HookSessionStartSource;
 use codex_hooks::Hooks;
 use codex_hooks::HooksConfig;
 use codex_network_proxy::NetworkProxy;
@@ -107,6 +115,8 @@ use tracing::warn;
 use uuid::Uuid;
 
 use crate::ModelProviderInfo;
+use crate::WireApi;
+use crate::checkpoints;
 use crate::client::ModelClient;
 use crate::client::ModelClientSession;
 use crate::client_common::Prompt;
@@ -135,6 +145,7 @@ pub enum SteerInputError {
     NoActiveTurn(Vec<UserInput>),
     ExpectedTurnMismatch { expected: String, actual: String },
     EmptyInput,
+    Blocked { reason: String },
 }
 use crate::exec_policy::ExecPolicyUpdateError;
 use crate::feedback_tags;
@@ -198,6 +209,7 @@ use crate::rollout::RolloutRecorderParams;
 use crate::rollout::map_session_init_error;
 use crate::rollout::metadata;
 use crate::rollout::policy::EventPersistenceMode;
+use crate::secrets::SessionSecrets;
 use crate::shell;
 use crate::shell_snapshot::ShellSnapshot;
 use crate::skills::SkillError;
@@ -227,7 +239,9 @@ use crate::tools::js_repl::JsReplHandle;
 use crate::tools::network_approval::NetworkApprovalService;
 use crate::tools::network_approval::build_blocked_request_observer;
 use crate::tools::network_approval::build_network_policy_decider;
+use crate::tools::network_approval::build_network_request_observer;
 use crate::tools::parallel::ToolCallRuntime;
+use crate::tools::py_repl::PyReplHandle;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::tools::spec::ToolsConfig;
 use crate::tools::spec::ToolsConfigParams;
@@ -247,6 +261,7 @@ use codex_protocol::models::DeveloperInstructions;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
+use codex_protocol::protocol::Checkpoint;
 use codex_protocol::protocol::CodexErrorInfo;
 use codex_protocol::protocol::InitialHistory;
 use codex_protocol::user_input::UserInput;
@@ -504,6 +519,55 @@ impl Codex {
     pub(crate) fn enabled(&self, feature: Feature) -> bool {
         self.session.enabled(feature)
     }
+
+    pub(crate) async fn list_checkpoints(&self) -> CodexResult<Vec<Checkpoint>> {
+        let history = self.session.clone_history().await.raw_items().to_vec();
+        let cwd = self.thread_config_snapshot().await.cwd;
+        let ghost_snapshot = self.session.get_config().await.ghost_snapshot.clone();
+        tokio::task::spawn_blocking(move || {
+            checkpoints::list_checkpoints(&history, &cwd, &ghost_snapshot)
+        })
+        .await?
+    }
+
+    pub(crate) async fn restore_checkpoint_file(
+        &self,
+        checkpoint_id: String,
+        path: PathBuf,
+        hunk: Option<usize>,
+    ) -> CodexResult<()> {
+        let history = self.session.clone_history().await.raw_items().to_vec();
+        let cwd = self.thread_config_snapshot().await.cwd;
+        let ghost_snapshot = self.session.get_config().await.ghost_snapshot.clone();
+        let note = checkpoints::restore_note(&checkpoint_id, &path, hunk);
+        // Holding the lock keeps a turn from starting while the file is rewritten.
+        let active_turn = self.session.active_turn.lock().await;
+        if active_turn.is_some() {
+            return Err(CodexErr::InvalidRequest(
+                "cannot restore a checkpoint while a turn is running".to_string(),
+            ));
+        }
+        tokio::task::spawn_blocking(move || {
+            checkpoints::restore_checkpoint_file(
+                &history,
+                &cwd,
+                &ghost_snapshot,
+                &checkpoint_id,
+                &path,
+                hunk,
+            )
+        })
+        .await??;
+        drop(active_turn);
+
+        // Tell the model so it does not keep working from the contents it last saw.
+        let turn_context = self.session.new_default_turn().await;
+        let message: ResponseItem = DeveloperInstructions::new(note).into();
+        self.session
+            .record_conversation_items(&turn_context, std::slice::from_ref(&message))
+            .await;
+        Ok(())
+    }
 }
 
 /// Context for an initialized model agent
@@ -521,6 +585,7 @@ pub(crate) struct Session {
     pub(crate) active_turn: Mutex<Option<ActiveTurn>>,
     pub(crate) services: SessionServices,
     js_repl: Arc<JsReplHandle>,
+    py_repl: Arc<PyReplHandle>,
     next_internal_sub_id: AtomicU64,
 }
 /// The context needed for a single turn of the thread.
@@ -555,8 +620,11 @@ pub(crate) struct TurnContext {
     pub(crate) final_output_json_schema: Option<Value>,
     pub(crate) codex_linux_sandbox_exe: Option<PathBuf>,
     pub(crate) tool_call_gate: Arc<ReadinessFlag>,
+    /// Pre-write file copies for undo when the cwd is not a Git repository.
+    pub(crate) file_snapshot: OnceLock<Arc<FileSnapshot>>,
     pub(crate) truncation_policy: TruncationPolicy,
     pub(crate) js_repl: Arc<JsReplHandle>,
+    pub(crate) py_repl: Arc<PyReplHandle>,
     pub(crate) dynamic_tools: Vec<DynamicToolSpec>,
     pub(crate) turn_metadata_state: Arc<TurnMetadataState>,
 }
@@ -603,7 +671,9 @@ impl TurnContext {
             model_info: &model_info,
             features: &features,
             web_search_mode: self.tools_config.web_search_mode,
-        });
+        })
+        .with_agent_roles(self.tools_config.agent_roles.clone())
+        .with_tool_policy(self.tools_config.tool_policy.clone());
 
         Self {
             sub_id: self.sub_id.clone(),
@@ -635,8 +705,10 @@ impl TurnContext {
             final_output_json_schema: self.final_output_json_schema.clone(),
             codex_linux_sandbox_exe: self.codex_linux_sandbox_exe.clone(),
             tool_call_gate: Arc::new(ReadinessFlag::new()),
+            file_snapshot: OnceLock::new(),
             truncation_policy,
             js_repl: Arc::clone(&self.js_repl),
+            py_repl: Arc::clone(&self.py_repl),
             dynamic_tools: self.dynamic_tools.clone(),
             turn_metadata_state: self.turn_metadata_state.clone(),
         }
@@ -827,16 +899,20 @@ impl Session {
 
     async fn start_managed_network_proxy(
         spec: &crate::config::NetworkProxySpec,
+        codex_home: &Path,
         sandbox_policy: &SandboxPolicy,
         network_policy_decider: Option<Arc<dyn codex_network_proxy::NetworkPolicyDecider>>,
         blocked_request_observer: Option<Arc<dyn codex_network_proxy::BlockedRequestObserver>>,
+        network_request_observer: Option<Arc<dyn codex_network_proxy::NetworkRequestObserver>>,
         managed_network_requirements_enabled: bool,
     ) -> anyhow::Result<(StartedNetworkProxy, SessionNetworkProxyRuntime)> {
         let network_proxy = spec
             .start_proxy(
+                codex_home,
                 sandbox_policy,
                 network_policy_decider,
                 blocked_request_observer,
+                network_request_observer,
                 managed_network_requirements_enabled,
             )
             .await
@@ -920,6 +996,7 @@ impl Session {
         network: Option<NetworkProxy>,
         sub_id: String,
         js_repl: Arc<JsReplHandle>,
+        py_repl: Arc<PyReplHandle>,
     ) -> TurnContext {
         let reasoning_effort = session_configuration.collaboration_mode.reasoning_effort();
         let reasoning_summary = session_configuration.model_reasoning_summary;
@@ -933,11 +1010,18 @@ impl Session {
         let otel_manager_for_context = otel_manager;
         let per_turn_config = Arc::new(per_turn_config);
 
+        // Web search is a hosted Responses tool; the other wire APIs have nothing to run it.
+        let web_search_mode = match provider_for_context.wire_api {
+            WireApi::Responses => per_turn_config.web_search_mode.value(),
+            WireApi::Chat | WireApi::Anthropic | WireApi::Gemini => WebSearchMode::Disabled,
+        };
         let tools_config = ToolsConfig::new(&ToolsConfigParams {
             model_info: &model_info,
             features: &per_turn_config.features,
-            web_search_mode: Some(per_turn_config.web_search_mode.value()),
-        });
+            web_search_mode: Some(web_search_mode),
+        })
+        .with_agent_roles(per_turn_config.agent_roles.clone())
+        .with_tool_policy(per_turn_config.tool_policy.clone());
 
         let cwd = session_configuration.cwd.clone();
         let turn_metadata_state = Arc::new(TurnMetadataState::new(
@@ -976,8 +1060,10 @@ impl Session {
             final_output_json_schema: None,
             codex_linux_sandbox_exe: per_turn_config.codex_linux_sandbox_exe.clone(),
             tool_call_gate: Arc::new(ReadinessFlag::new()),
+            file_snapshot: OnceLock::new(),
             truncation_policy: model_info.truncation_policy.into(),
             js_repl,
+            py_repl,
             dynamic_tools: session_configuration.dynamic_tools.clone(),
             turn_metadata_state,
         }
@@ -1011,6 +1097,13 @@ impl Session {
         }
 
         let forked_from_id = initial_history.forked_from_id();
+        let session_start_source = match &initial_history {
+            InitialHistory::New => HookSessionStartSource::New,
+            InitialHistory::Resumed(_) => HookSessionStartSource::Resumed,
+            InitialHistory::Forked(_) => HookSessionStartSource::Forked,
+        };
+        let session_start_model = session_configuration.collaboration_mode.model().to_string();
+        let session_start_cwd = session_configuration.cwd.clone();
 
         let (conversation_id, rollout_params) = match &initial_history {
             InitialHistory::New | InitialHistory::Forked(_) => {
@@ -1212,7 +1305,11 @@ impl Session {
         session_configuration.thread_name = thread_name.clone();
         let mut state = SessionState::new(session_configuration.clone());
         let managed_network_requirements_enabled = config.managed_network_requirements_enabled();
-        let network_approval = Arc::new(NetworkApprovalService::default());
+        // Every proxied request lands in the state DB audit log, attributed to this thread and,
+        // through registered attempts, to the exec call that made it.
+        let network_audit_log_enabled =
+            config.permissions.network.is_some() && state_db_ctx.is_some();
+        let network_approval = Arc::new(NetworkApprovalService::new(network_audit_log_enabled));
         // The managed proxy can call back into core for allowlist-miss decisions.
         let network_policy_decider_session = if managed_network_requirements_enabled {
             config
@@ -1232,6 +1329,16 @@ impl Session {
         } else {
             None
         };
+        let network_request_observer = state_db_ctx
+            .as_ref()
+            .filter(|_| network_audit_log_enabled)
+            .map(|state_db| {
+                build_network_request_observer(
+                    Arc::clone(&network_approval),
+                    Arc::clone(state_db),
+                    conversation_id,
+                )
+            });
         let network_policy_decider =
             network_policy_decider_session
                 .as_ref()
@@ -1245,9 +1352,11 @@ impl Session {
             if let Some(spec) = config.permissions.network.as_ref() {
                 let (network_proxy, session_network_proxy) = Self::start_managed_network_proxy(
                     spec,
+                    &config.codex_home,
                     config.permissions.sandbox_policy.get(),
                     network_policy_decider.as_ref().map(Arc::clone),
                     blocked_request_observer.as_ref().map(Arc::clone),
+                    network_request_observer,
                     managed_network_requirements_enabled,
                 )
                 .await?;
@@ -1266,6 +1375,7 @@ impl Session {
             ),
             hooks: Hooks::new(HooksConfig {
                 legacy_notify_argv: config.notify.clone(),
+                command_hooks: config.hooks.clone(),
             }),
             rollout: Mutex::new(rollout_recorder),
             user_shell: Arc::new(default_shell),
@@ -1282,6 +1392,7 @@ impl Session {
             network_proxy,
             network_approval: Arc::clone(&network_approval),
             state_db: state_db_ctx.clone(),
+            secrets: SessionSecrets::new(config.codex_home.clone()),
             model_client: ModelClient::new(
                 Some(Arc::clone(&auth_manager)),
                 conversation_id,
@@ -1300,6 +1411,9 @@ impl Session {
             config.js_repl_node_path.clone(),
             config.codex_home.clone(),
         ));
+        let py_repl = Arc::new(PyReplHandle::with_python_path(
+            config.py_repl_python_path.clone(),
+        ));
 
         let prewarm_model_info = models_manager
             .get_model_info(session_configuration.collaboration_mode.model(), &config)
@@ -1321,6 +1435,7 @@ impl Session {
             active_turn: Mutex::new(None),
             services,
             js_repl,
+            py_repl,
             next_internal_sub_id: AtomicU64::new(0),
         });
         if let Some(network_policy_decider_session) = network_policy_decider_session {
@@ -1385,6 +1500,7 @@ impl Session {
                 tx_event.clone(),
                 cancel_token,
                 sandbox_state,
+                sess.services.secrets.clone(),
             )
             .await;
         if !required_mcp_servers.is_empty() {
@@ -1410,6 +1526,25 @@ impl Session {
         // record_initial_history can emit events. We record only after the SessionConfiguredEvent is emitted.
         sess.record_initial_history(initial_history).await;
 
+        // SessionStart hooks cannot change the session, so run them in the
+        // background rather than letting a slow command stall startup.
+        let hooks = sess.hooks().clone();
+        let session_start_payload = HookPayload {
+            session_id: sess.conversation_id,
+            cwd: session_start_cwd,
+            triggered_at: chrono::Utc::now(),
+            hook_event: HookEvent::SessionStart {
+                event: HookEventSessionStart {
+                    thread_id: sess.conversation_id,
+                    model: session_start_model,
+                    source: session_start_source,
+                },
+            },
+        };
+        tokio::spawn(async move {
+            hooks.dispatch(session_start_payload).await;
+        });
+
         memories::start_memories_startup_task(
             &sess,
             Arc::clone(&config),
@@ -1800,6 +1935,7 @@ impl Session {
                 .map(StartedNetworkProxy::proxy),
             sub_id,
             Arc::clone(&self.js_repl),
+            Arc::clone(&self.py_repl),
         );
 
         if let Some(final_schema) = final_output_json_schema {
@@ -2401,9 +2537,10 @@ impl Session {
         turn_context: &TurnContext,
         items: &[ResponseItem],
     ) {
-        self.record_into_history(items, turn_context).await;
-        self.persist_rollout_response_items(items).await;
-        self.send_raw_response_items(turn_context, items).await;
+        let items = self.services.secrets.redact_response_items(items);
+        self.record_into_history(&items, turn_context).await;
+        self.persist_rollout_response_items(&items).await;
+        self.send_raw_response_items(turn_context, &items).await;
     }
 
     async fn reconstruct_history_from_rollout(
@@ -2604,12 +2741,13 @@ impl Session {
     }
 
     pub(crate) async fn persist_rollout_items(&self, items: &[RolloutItem]) {
+        let items = self.services.secrets.redact_rollout_items(items);
         let recorder = {
             let guard = self.services.rollout.lock().await;
             guard.clone()
         };
         if let Some(rec) = recorder
-            && let Err(e) = rec.record_items(items).await
+            && let Err(e) = rec.record_items(&items).await
         {
             error!("failed to record rollout items: {e:#}");
         }
@@ -2817,7 +2955,8 @@ impl Session {
 
     /// Inject additional user input into the currently active turn.
     ///
-    /// Returns the active turn id when accepted.
+    /// The input goes through the `user_prompt_submit` hooks first, like the
+    /// prompt that started the turn. Returns the active turn id when accepted.
     pub async fn steer_input(
         &self,
         input: Vec<UserInput>,
@@ -2827,27 +2966,51 @@ impl Session {
             return Err(SteerInputError::EmptyInput);
         }
 
-        let mut active = self.active_turn.lock().await;
-        let Some(active_turn) = active.as_mut() else {
-            return Err(SteerInputError::NoActiveTurn(input));
-        };
-
-        let Some((active_turn_id, _)) = active_turn.tasks.first() else {
-            return Err(SteerInputError::NoActiveTurn(input));
+        let turn_context = {
+            let active = self.active_turn.lock().await;
+            let Some((active_turn_id, task)) = active.as_ref().and_then(|turn| turn.tasks.first())
+            else {
+                return Err(SteerInputError::NoActiveTurn(input));
+            };
+            if let Some(expected_turn_id) = expected_turn_id
+                && expected_turn_id != active_turn_id
+            {
+                return Err(SteerInputError::ExpectedTurnMismatch {
+                    expected: expected_turn_id.to_string(),
+                    actual: active_turn_id.clone(),
+                });
+            }
+            Arc::clone(&task.turn_context)
         };
 
-        if let Some(expected_turn_id) = expected_turn_id
-            && expected_turn_id != active_turn_id
+        // Hooks can take seconds, so they run without holding the turn lock.
+        let additional_context = match self
+            .dispatch_user_prompt_submit_hook(&turn_context, &input)
+            .await
         {
-            return Err(SteerInputError::ExpectedTurnMismatch {
-                expected: expected_turn_id.to_string(),
-                actual: active_turn_id.clone(),
-            });
-        }
+            HookDecision::Allow {
+                additional_context, ..
+            } => additional_context,
+            HookDecision::Deny { reason } => return Err(SteerInputError::Blocked { reason }),
+        };
 
+        let mut active = self.active_turn.lock().await;
+        // If the turn ended while the hook ran, the caller starts a new turn with the input.
+        let Some(active_turn) = active
+            .as_mut()
+            .filter(|turn| turn.tasks.contains_key(&turn_context.sub_id))
+        else {
+            return Err(SteerInputError::NoActiveTurn(input));
+        };
         let mut turn_state = active_turn.turn_state.lock().await;
         turn_state.push_pending_input(input.into());
-        Ok(active_turn_id.clone())
+        for text in additional_context {
+            turn_state.push_pending_input(ResponseInputItem::Message {
+                role: "developer".to_string(),
+                content: vec![ContentItem::InputText { text }],
+            });
+        }
+        Ok(turn_context.sub_id.clone())
     }
 
     /// Returns the input if there was no task running to inject into
@@ -2966,6 +3129,54 @@ impl Session {
         &self.services.hooks
     }
 
+    pub(crate) async fn dispatch_user_prompt_submit_hook(
+        &self,
+        turn_context: &TurnContext,
+        input: &[UserInput],
+    ) -> HookDecision {
+        self.hooks()
+            .dispatch(HookPayload {
+                session_id: self.conversation_id,
+                cwd: turn_context.cwd.clone(),
+                triggered_at: chrono::Utc::now(),
+                hook_event: HookEvent::UserPromptSubmit {
+                    event: HookEventUserPromptSubmit {
+                        thread_id: self.conversation_id,
+                        turn_id: turn_context.sub_id.clone(),
+                        input_messages: input
+                            .iter()
+                            .filter_map(|item| match item {
+                                UserInput::Text { text, .. } => Some(text.clone()),
+                                _ => None,
+                            })
+                            .collect(),
+                    },
+                },
+            })
+            .await
+    }
+
+    pub(crate) async fn dispatch_before_compaction_hook(
+        &self,
+        turn_context: &TurnContext,
+        trigger: HookCompactionTrigger,
+    ) {
+        self.hooks()
+            .dispatch(HookPayload {
+                session_id: self.conversation_id,
+                cwd: turn_context.cwd.clone(),
+                triggered_at: chrono::Utc::now(),
+                hook_event: HookEvent::BeforeCompaction {
+                    event: HookEventBeforeCompaction {
+                        thread_id: self.conversation_id,
+                        turn_id: turn_context.sub_id.clone(),
+                        trigger,
+                    },
+                },
+            })
+            .await;
+    }
+
     pub(crate) fn user_shell(&self) -> Arc<shell::Shell> {
         Arc::clone(&self.services.user_shell)
     }
@@ -3002,6 +3213,7 @@ impl Session {
                 self.get_tx_event(),
                 cancel_token,
                 sandbox_state,
+                self.services.secrets.clone(),
             )
             .await;
 
@@ -3258,6 +3470,9 @@ mod handlers {
     use crate::tasks::UserShellCommandMode;
     use crate::tasks::UserShellCommandTask;
     use crate::tasks::execute_user_shell_command;
+    use codex_hooks::HookEvent;
+    use codex_hooks::HookEventSessionEnd;
+    use codex_hooks::HookPayload;
     use codex_protocol::custom_prompts::CustomPrompt;
     use codex_protocol::protocol::CodexErrorInfo;
     use codex_protocol::protocol::ErrorEvent;
@@ -3381,26 +3596,42 @@ mod handlers {
         current_context.otel_manager.user_prompt(&items);
 
         // Attempt to inject input into current task.
-        if let Err(SteerInputError::NoActiveTurn(items)) = sess.steer_input(items, None).await {
-            sess.seed_initial_context_if_needed(&current_context).await;
-            let previous_model = sess.previous_model().await;
-            let update_items = sess.build_settings_update_items(
-                previous_context.as_ref(),
-                previous_model.as_deref(),
-                &current_context,
-            );
-            if !update_items.is_empty() {
-                sess.record_conversation_items(&current_context, &update_items)
-                    .await;
-            }
-
-            sess.refresh_mcp_servers_if_requested(&current_context)
+        let items = match sess.steer_input(items, None).await {
+            Err(SteerInputError::NoActiveTurn(items)) => items,
+            Err(SteerInputError::Blocked { reason }) => {
+                sess.send_event(
+                    &current_context,
+                    EventMsg::Error(ErrorEvent {
+                        message: format!("Prompt blocked by hook: {reason}"),
+                        codex_error_info: Some(CodexErrorInfo::Other),
+                    }),
+                )
                 .await;
-            let regular_task = sess.take_startup_regular_task().await.unwrap_or_default();
-            sess.spawn_task(Arc::clone(&current_context), items, regular_task)
+                return;
+            }
+            Ok(_)
+            | Err(SteerInputError::ExpectedTurnMismatch { .. } | SteerInputError::EmptyInput) => {
+                return;
+            }
+        };
+        sess.seed_initial_context_if_needed(&current_context).await;
+        let previous_model = sess.previous_model().await;
+        let update_items = sess.build_settings_update_items(
+            previous_context.as_ref(),
+            previous_model.as_deref(),
+            &current_context,
+        );
+        if !update_items.is_empty() {
+            sess.record_conversation_items(&current_context, &update_items)
                 .await;
-            *previous_context = Some(current_context);
         }
+
+        sess.refresh_mcp_servers_if_requested(&current_context)
+            .await;
+        let regular_task = sess.take_startup_regular_task().await.unwrap_or_default();
+        sess.spawn_task(Arc::clone(&current_context), items, regular_task)
+            .await;
+        *previous_context = Some(current_context);
     }
 
     pub async fn run_user_shell_command(
@@ -3935,6 +4166,22 @@ mod handlers {
             .terminate_all_processes()
             .await;
         info!("Shutting down Codex instance");
+        let cwd = {
+            let state = sess.state.lock().await;
+            state.session_configuration.cwd.clone()
+        };
+        sess.hooks()
+            .dispatch(HookPayload {
+                session_id: sess.conversation_id,
+                cwd,
+                triggered_at: chrono::Utc::now(),
+                hook_event: HookEvent::SessionEnd {
+                    event: HookEventSessionEnd {
+                        thread_id: sess.conversation_id,
+                    },
+                },
+            })
+            .await;
         let history = sess.clone_history().await;
         let turn_count = history
             .raw_items()
@@ -4035,7 +4282,8 @@ async fn spawn_review_thread(
         model_info: &review_model_info,
         features: &review_features,
         web_search_mode: Some(review_web_search_mode),
-    });
+    })
+    .with_tool_policy(config.tool_policy.clone());
 
     let review_prompt = resolved.prompt.clone();
     let provider = parent_turn_context.provider.clone();
@@ -4106,7 +4354,9 @@ async fn spawn_review_thread(
         final_output_json_schema: None,
         codex_linux_sandbox_exe: parent_turn_context.codex_linux_sandbox_exe.clone(),
         tool_call_gate: Arc::new(ReadinessFlag::new()),
+        file_snapshot: OnceLock::new(),
         js_repl: Arc::clone(&sess.js_repl),
+        py_repl: Arc::clone(&sess.py_repl),
         dynamic_tools: parent_turn_context.dynamic_tools.clone(),
         truncation_policy: model_info.truncation_policy.into(),
         turn_metadata_state,
@@ -4126,6 +4376,7 @@ async fn spawn_review_thread(
     let review_request = ReviewRequest {
         target: resolved.target,
         user_facing_hint: Some(resolved.user_facing_hint),
+        paths: resolved.paths,
     };
     sess.send_event(&tc, EventMsg::EnteredReviewMode(review_request))
         .await;
@@ -4219,6 +4470,25 @@ pub(crate) async fn run_turn(
         collaboration_mode_kind: turn_context.collaboration_mode.mode,
     });
     sess.send_event(&turn_context, event).await;
+    let prompt_hook_decision = sess
+        .dispatch_user_prompt_submit_hook(&turn_context, &input)
+        .await;
+    let prompt_hook_context = match prompt_hook_decision {
+        HookDecision::Allow {
+            additional_context, ..
+        } => additional_context,
+        HookDecision::Deny { reason } => {
+            sess.send_event(
+                &turn_context,
+                EventMsg::Error(ErrorEvent {
+                    message: format!("Prompt blocked by hook: {reason}"),
+                    codex_error_info: Some(CodexErrorInfo::Other),
+                }),
+            )
+            .await;
+            return None;
+        }
+    };
     if run_pre_sampling_compact(&sess, &turn_context)
         .await
         .is_err()
@@ -4339,6 +4609,15 @@ pub(crate) async fn run_turn(
     sess.record_user_prompt_and_emit_turn_item(turn_context.as_ref(), &input, response_item)
         .await;
 
+    if !prompt_hook_context.is_empty() {
+        let hook_items = prompt_hook_context
+            .into_iter()
+            .map(|text| DeveloperInstructions::new(text).into())
+            .collect::<Vec<ResponseItem>>();
+        sess.record_conversation_items(&turn_context, &hook_items)
+            .await;
+    }
+
     if !skill_items.is_empty() {
         sess.record_conversation_items(&turn_context, &skill_items)
             .await;
@@ -4562,6 +4841,8 @@ async fn maybe_run_previous_model_inline_compact(
 }
 
 async fn run_auto_compact(sess: &Arc<Session>, turn_context: &Arc<TurnContext>) -> CodexResult<()> {
+    sess.dispatch_before_compaction_hook(turn_context, HookCompactionTrigger::Auto)
+        .await;
     if should_use_remote_compact_task(&turn_context.provider) {
         run_inline_remote_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await?;
     } else {
@@ -4915,6 +5196,12 @@ async fn built_tools(
         mcp_tools = selected_mcp_tools;
     }
 
+    mcp_tools.retain(|name, tool| {
+        turn_context
+            .tools_config
+            .is_mcp_tool_allowed(name, &tool.server_name, &tool.tool_name)
+    });
+
     let app_tools = connectors
         .as_ref()
         .map(|connectors| filter_codex_apps_mcp_tools(&mcp_tools, connectors));
@@ -5691,6 +5978,11 @@ mod tests {
     use crate::tools::router::ToolCallSource;
     use crate::turn_diff_tracker::TurnDiffTracker;
     use codex_app_server_protocol::AppInfo;
+    use codex_hooks::CommandHookConfig;
+    use codex_hooks::CommandHookOnError;
+    use codex_hooks::DEFAULT_COMMAND_HOOK_TIMEOUT;
+    use codex_hooks::HookEventKind;
+    use codex_hooks::HookMatcher;
     use codex_otel::TelemetryAuthMode;
     use codex_protocol::models::BaseInstructions;
     use codex_protocol::models::ContentItem;
@@ -7032,6 +7324,7 @@ mod tests {
             ),
             hooks: Hooks::new(HooksConfig {
                 legacy_notify_argv: config.notify.clone(),
+                command_hooks: config.hooks.clone(),
             }),
             rollout: Mutex::new(None),
             user_shell: Arc::new(default_user_shell()),
@@ -7048,6 +7341,7 @@ mod tests {
             network_proxy: None,
             network_approval: Arc::clone(&network_approval),
             state_db: None,
+            secrets: SessionSecrets::new(config.codex_home.clone()),
             model_client: ModelClient::new(
                 Some(auth_manager.clone()),
                 conversation_id,
@@ -7067,6 +7361,9 @@ mod tests {
             config.js_repl_node_path.clone(),
             config.codex_home.clone(),
         ));
+        let py_repl = Arc::new(PyReplHandle::with_python_path(
+            config.py_repl_python_path.clone(),
+        ));
 
         let turn_context = Session::make_turn_context(
             Some(Arc::clone(&auth_manager)),
@@ -7078,6 +7375,7 @@ mod tests {
             None,
             "turn_id".to_string(),
             Arc::clone(&js_repl),
+            Arc::clone(&py_repl),
         );
 
         let session = Session {
@@ -7090,6 +7388,7 @@ mod tests {
             active_turn: Mutex::new(None),
             services,
             js_repl,
+            py_repl,
             next_internal_sub_id: AtomicU64::new(0),
         };
 
@@ -7180,6 +7479,7 @@ mod tests {
             ),
             hooks: Hooks::new(HooksConfig {
                 legacy_notify_argv: config.notify.clone(),
+                command_hooks: config.hooks.clone(),
             }),
             rollout: Mutex::new(None),
             user_shell: Arc::new(default_user_shell()),
@@ -7196,6 +7496,7 @@ mod tests {
             network_proxy: None,
             network_approval: Arc::clone(&network_approval),
             state_db: None,
+            secrets: SessionSecrets::new(config.codex_home.clone()),
             model_client: ModelClient::new(
                 Some(Arc::clone(&auth_manager)),
                 conversation_id,
@@ -7215,6 +7516,9 @@ mod tests {
             config.js_repl_node_path.clone(),
             config.codex_home.clone(),
         ));
+        let py_repl = Arc::new(PyReplHandle::with_python_path(
+            config.py_repl_python_path.clone(),
+        ));
 
         let turn_context = Arc::new(Session::make_turn_context(
             Some(Arc::clone(&auth_manager)),
@@ -7226,6 +7530,7 @@ mod tests {
             None,
             "turn_id".to_string(),
             Arc::clone(&js_repl),
+            Arc::clone(&py_repl),
         ));
 
         let session = Arc::new(Session {
@@ -7238,6 +7543,7 @@ mod tests {
             active_turn: Mutex::new(None),
             services,
             js_repl,
+            py_repl,
             next_internal_sub_id: AtomicU64::new(0),
         });
 
@@ -7622,6 +7928,87 @@ mod tests {
         assert!(sess.has_pending_input().await);
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn steer_input_runs_user_prompt_submit_hooks() {
+        let (mut sess, tc) = make_session_and_context().await;
+        sess.services.hooks = Hooks::new(HooksConfig {
+            legacy_notify_argv: None,
+            command_hooks: vec![CommandHookConfig {
+                event: HookEventKind::UserPromptSubmit,
+                argv: vec![
+                    "/bin/sh".to_string(),
+                    "-c".to_string(),
+                    r#"case "$(cat)" in
+                        *forbidden*) echo '{"decision":"deny","reason":"no steering"}' ;;
+                        *) echo '{"additional_context":"steered by hook"}' ;;
+                    esac"#
+                        .to_string(),
+                ],
+                timeout: DEFAULT_COMMAND_HOOK_TIMEOUT,
+                matcher: HookMatcher::default(),
+                on_error: CommandHookOnError::Continue,
+            }],
+        });
+        let sess = Arc::new(sess);
+        let tc = Arc::new(tc);
+        sess.spawn_task(
+            Arc::clone(&tc),
+            vec![UserInput::Text {
+                text: "hello".to_string(),
+                text_elements: Vec::new(),
+            }],
+            NeverEndingTask {
+                kind: TaskKind::Regular,
+                listen_to_cancellation_token: false,
+            },
+        )
+        .await;
+
+        let err = sess
+            .steer_input(
+                vec![UserInput::Text {
+                    text: "forbidden steer".to_string(),
+                    text_elements: Vec::new(),
+                }],
+                None,
+            )
+            .await
+            .expect_err("hook should block the steer");
+        assert!(
+            matches!(&err, SteerInputError::Blocked { reason } if reason == "no steering"),
+            "unexpected error: {err:?}"
+        );
+        assert!(!sess.has_pending_input().await);
+
+        sess.steer_input(
+            vec![UserInput::Text {
+                text: "steer".to_string(),
+                text_elements: Vec::new(),
+            }],
+            None,
+        )
+        .await
+        .expect("hook should allow the steer");
+        assert_eq!(
+            sess.get_pending_input().await,
+            vec![
+                ResponseInputItem::Message {
+                    role: "user".to_string(),
+                    content: vec![ContentItem::InputText {
+                        text: "steer".to_string(),
+                    }],
+                },
+                ResponseInputItem::Message {
+                    role: "developer".to_string(),
+                    content: vec![ContentItem::InputText {
+                        text: "steered by hook".to_string(),
+                    }],
+                },
+            ]
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn abort_review_task_emits_exited_then_aborted_and_records_history() {
         let (sess, tc, rx) = make_session_and_context_with_rx().await;
@@ -9,6 +9,7 @@ use crate::protocol::Submission;
 use codex_protocol::config_types::Personality;
 use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::Checkpoint;
 use codex_protocol::protocol::SandboxPolicy;
 use codex_protocol::protocol::SessionSource;
 use codex_protocol::user_input::UserInput;
@@ -88,4 +89,23 @@ impl CodexThread {
     pub fn enabled(&self, feature: Feature) -> bool {
         self.codex.enabled(feature)
     }
+
+    /// Per-turn checkpoints, oldest first, each with the files that changed
+    /// since it was taken.
+    pub async fn list_checkpoints(&self) -> CodexResult<Vec<Checkpoint>> {
+        self.codex.list_checkpoints().await
+    }
+
+    /// Restore one file, or a single hunk of its diff when `hunk` is set,
+    /// from an earlier checkpoint without rewinding the conversation.
+    pub async fn restore_checkpoint_file(
+        &self,
+        checkpoint_id: String,
+        path: PathBuf,
+        hunk: Option<usize>,
+    ) -> CodexResult<()> {
+        self.codex
+            .restore_checkpoint_file(checkpoint_id, path, hunk)
+            .await
+    }
 }
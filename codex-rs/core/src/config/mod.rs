@@ -1,9 +1,15 @@
+use crate::agent::load_agent_roles;
 use crate::auth::AuthCredentialsStoreMode;
 use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
+use crate::config::types::AgentRoleConfig;
+use crate::config::types::AgentRoleToml;
 use crate::config::types::AppsConfigToml;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
+use crate::config::types::EditingMode;
 use crate::config::types::History;
+use crate::config::types::HookCommandToml;
+use crate::config::types::HooksToml;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerDisabledReason;
 use crate::config::types::McpServerTransportConfig;
@@ -19,6 +25,8 @@ use crate::config::types::SandboxWorkspaceWrite;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
 use crate::config::types::SkillsConfig;
+use crate::config::types::StatusLineCommand;
+use crate::config::types::ToolPolicy;
 use crate::config::types::Tui;
 use crate::config::types::UriBasedFileOpener;
 use crate::config::types::WindowsSandboxModeToml;
@@ -55,6 +63,10 @@ use crate::windows_sandbox::WindowsSandboxLevelExt;
 use crate::windows_sandbox::resolve_windows_sandbox_mode;
 use codex_app_server_protocol::Tools;
 use codex_app_server_protocol::UserSavedConfig;
+use codex_hooks::CommandHookConfig;
+use codex_hooks::DEFAULT_COMMAND_HOOK_TIMEOUT;
+use codex_hooks::HookEventKind;
+use codex_hooks::HookMatcher;
 use codex_protocol::config_types::AltScreenMode;
 use codex_protocol::config_types::ForcedLoginMethod;
 use codex_protocol::config_types::ModeKind;
@@ -78,6 +90,7 @@ use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
 #[cfg(test)]
 use tempfile::tempdir;
 #[cfg(not(target_os = "macos"))]
@@ -228,6 +241,9 @@ pub struct Config {
     /// If unset the feature is disabled.
     pub notify: Option<Vec<String>>,
 
+    /// External command hooks declared under `[[hooks.<event>]]`.
+    pub hooks: Vec<CommandHookConfig>,
+
     /// TUI notifications preference. When set, the TUI will send terminal notifications on
     /// approvals and turn completions when not focused.
     pub tui_notifications: Notifications,
@@ -255,6 +271,22 @@ pub struct Config {
     /// Ordered list of status line item identifiers for the TUI.
     pub tui_status_line: Option<Vec<String>>,
 
+    /// Command-backed status line segments from `[tui.status_line_commands]`.
+    pub tui_status_line_commands: BTreeMap<String, StatusLineCommand>,
+
+    /// Key handling style for the TUI composer.
+    pub tui_editing_mode: EditingMode,
+
+    /// Key chords for TUI actions from `[tui.keymap]`, keyed by action name.
+    pub tui_keymap: BTreeMap<String, Vec<String>>,
+
+    /// Theme name from `[tui.theme]`: a built-in theme or a file under
+    /// `$CODEX_HOME/themes`.
+    pub tui_theme: Option<String>,
+
+    /// Per-role color overrides from `[tui.theme]`.
+    pub tui_theme_colors: BTreeMap<String, String>,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
@@ -298,6 +330,14 @@ pub struct Config {
     /// Maximum number of agent threads that can be open concurrently.
     pub agent_max_threads: Option<usize>,
 
+    /// Roles available to `spawn_agent`, keyed by name. Includes the built-in
+    /// roles unless they are overridden.
+    pub agent_roles: BTreeMap<String, AgentRoleConfig>,
+
+    /// Tools exposed to the model and allowed to be called, from `tools.allow`
+    /// and `tools.deny` and narrowed further by agent roles.
+    pub tool_policy: ToolPolicy,
+
     /// Memories subsystem settings.
     pub memories: MemoriesConfig,
 
@@ -329,6 +369,9 @@ pub struct Config {
     /// Optional absolute path to the Node runtime used by `js_repl`.
     pub js_repl_node_path: Option<PathBuf>,
 
+    /// Optional absolute path to the Python runtime used by `py_repl`.
+    pub py_repl_python_path: Option<PathBuf>,
+
     /// Value to use for `reasoning.effort` when making a request using the
     /// Responses API.
     pub model_reasoning_effort: Option<ReasoningEffort>,
@@ -898,6 +941,9 @@ pub struct ConfigToml {
     #[serde(default)]
     pub notify: Option<Vec<String>>,
 
+    /// External commands run on session, turn, tool and compaction events.
+    pub hooks: Option<HooksToml>,
+
     /// System instructions.
     pub instructions: Option<String>,
 
@@ -963,6 +1009,9 @@ pub struct ConfigToml {
     /// Optional absolute path to the Node runtime used by `js_repl`.
     pub js_repl_node_path: Option<AbsolutePathBuf>,
 
+    /// Optional absolute path to the Python runtime used by `py_repl`.
+    pub py_repl_python_path: Option<AbsolutePathBuf>,
+
     /// Profile to use from the `profiles` map.
     pub profile: Option<String>,
 
@@ -1139,15 +1188,29 @@ pub struct ToolsToml {
     /// Enable the `view_image` tool that lets the agent attach local images.
     #[serde(default)]
     pub view_image: Option<bool>,
+
+    /// Glob patterns of tools the model may see and call. Built-in and
+    /// dynamic tools match by name, MCP tools also as `server/tool`. All
+    /// tools are available when unset.
+    #[serde(default)]
+    pub allow: Option<Vec<String>>,
+
+    /// Glob patterns of tools hidden from the model and rejected when called.
+    #[serde(default)]
+    pub deny: Option<Vec<String>>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, JsonSchema)]
 #[schemars(deny_unknown_fields)]
 pub struct AgentsToml {
     /// Maximum number of agent threads that can be open concurrently.
     /// When unset, no limit is enforced.
     #[schemars(range(min = 1))]
     pub max_threads: Option<usize>,
+
+    /// Custom roles for `spawn_agent`, keyed by role name.
+    #[serde(default)]
+    pub roles: BTreeMap<String, AgentRoleToml>,
 }
 
 impl From<ToolsToml> for Tools {
@@ -1351,6 +1414,91 @@ pub fn resolve_oss_provider(
     }
 }
 
+fn command_hooks_from_toml(hooks: HooksToml) -> std::io::Result<Vec<CommandHookConfig>> {
+    let HooksToml {
+        session_start,
+        session_end,
+        user_prompt_submit,
+        before_tool_use,
+        after_tool_use,
+        before_compaction,
+        after_agent,
+    } = hooks;
+    [
+        (HookEventKind::SessionStart, "session_start", session_start),
+        (HookEventKind::SessionEnd, "session_end", session_end),
+        (
+            HookEventKind::UserPromptSubmit,
+            "user_prompt_submit",
+            user_prompt_submit,
+        ),
+        (
+            HookEventKind::BeforeToolUse,
+            "before_tool_use",
+            before_tool_use,
+        ),
+        (
+            HookEventKind::AfterToolUse,
+            "after_tool_use",
+            after_tool_use,
+        ),
+        (
+            HookEventKind::BeforeCompaction,
+            "before_compaction",
+            before_compaction,
+        ),
+        (HookEventKind::AfterAgent, "after_agent", after_agent),
+    ]
+    .into_iter()
+    .flat_map(|(event, name, entries)| {
+        entries
+            .into_iter()
+            .map(move |entry| command_hook_from_toml(event, name, entry))
+    })
+    .collect()
+}
+
+fn command_hook_from_toml(
+    event: HookEventKind,
+    name: &str,
+    entry: HookCommandToml,
+) -> std::io::Result<CommandHookConfig> {
+    if entry.command.first().is_none_or(String::is_empty) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("hooks.{name}.command must name a program to run"),
+        ));
+    }
+    let is_tool_event = matches!(
+        event,
+        HookEventKind::BeforeToolUse | HookEventKind::AfterToolUse
+    );
+    if !is_tool_event
+        && (entry.tool_name.is_some() || entry.tool_kind.is_some() || entry.path.is_some())
+    {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "hooks.{name} does not run for tool calls, so tool_name, tool_kind and path do not apply"
+            ),
+        ));
+    }
+    Ok(CommandHookConfig {
+        event,
+        argv: entry.command,
+        timeout: entry
+            .timeout_sec
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_COMMAND_HOOK_TIMEOUT),
+        matcher: HookMatcher {
+            tool_name: entry.tool_name,
+            tool_kind: entry.tool_kind,
+            path: entry.path,
+        },
+        on_error: entry.on_error.map(Into::into).unwrap_or_default(),
+    })
+}
+
 /// Resolve the web search mode from explicit config and feature flags.
 fn resolve_web_search_mode(
     config_toml: &ConfigToml,
@@ -1579,6 +1727,8 @@ impl Config {
 
         let history = cfg.history.unwrap_or_default();
 
+        let hooks = command_hooks_from_toml(cfg.hooks.clone().unwrap_or_default())?;
+
         let agent_max_threads = cfg
             .agents
             .as_ref()
@@ -1590,6 +1740,27 @@ impl Config {
                 "agents.max_threads must be at least 1",
             ));
         }
+        let agent_roles = load_agent_roles(
+            &config_layer_stack,
+            cfg.agents
+                .as_ref()
+                .map(|agents| agents.roles.clone())
+                .unwrap_or_default(),
+            &mut startup_warnings,
+        );
+        let tool_policy = ToolPolicy {
+            allow: config_profile
+                .tools_allow
+                .clone()
+                .or_else(|| cfg.tools.as_ref().and_then(|tools| tools.allow.clone()))
+                .into_iter()
+                .collect(),
+            deny: config_profile
+                .tools_deny
+                .clone()
+                .or_else(|| cfg.tools.as_ref().and_then(|tools| tools.deny.clone()))
+                .unwrap_or_default(),
+        };
 
         let ghost_snapshot = {
             let mut config = GhostSnapshotConfig::default();
@@ -1674,6 +1845,10 @@ impl Config {
         let js_repl_node_path = js_repl_node_path_override
             .or(config_profile.js_repl_node_path.map(Into::into))
             .or(cfg.js_repl_node_path.map(Into::into));
+        let py_repl_python_path = config_profile
+            .py_repl_python_path
+            .map(Into::into)
+            .or(cfg.py_repl_python_path.map(Into::into));
 
         let review_model = override_review_model.or(cfg.review_model);
 
@@ -1757,6 +1932,7 @@ impl Config {
             enforce_residency: enforce_residency.value,
             did_user_set_custom_approval_policy_or_sandbox_mode,
             notify: cfg.notify,
+            hooks,
             user_instructions,
             base_instructions,
             personality,
@@ -1787,6 +1963,8 @@ impl Config {
                 .collect(),
             tool_output_token_limit: cfg.tool_output_token_limit,
             agent_max_threads,
+            agent_roles,
+            tool_policy,
             memories: cfg.memories.unwrap_or_default().into(),
             codex_home,
             log_dir,
@@ -1796,6 +1974,7 @@ impl Config {
             file_opener: cfg.file_opener.unwrap_or(UriBasedFileOpener::VsCode),
             codex_linux_sandbox_exe,
             js_repl_node_path,
+            py_repl_python_path,
 
             hide_agent_reasoning: cfg.hide_agent_reasoning.unwrap_or(false),
             show_raw_agent_reasoning: cfg
@@ -1860,6 +2039,28 @@ impl Config {
                 .map(|t| t.alternate_screen)
                 .unwrap_or_default(),
             tui_status_line: cfg.tui.as_ref().and_then(|t| t.status_line.clone()),
+            tui_status_line_commands: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.status_line_commands.clone())
+                .unwrap_or_default(),
+            tui_editing_mode: cfg.tui.as_ref().map(|t| t.editing_mode).unwrap_or_default(),
+            tui_keymap: cfg
+                .tui
+                .as_ref()
+                .map(|t| {
+                    t.keymap
+                        .iter()
+                        .map(|(action, chords)| (action.clone(), chords.clone().into_vec()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            tui_theme: cfg.tui.as_ref().and_then(|t| t.theme.name.clone()),
+            tui_theme_colors: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.theme.colors.clone())
+                .unwrap_or_default(),
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -2013,10 +2214,12 @@ mod tests {
     use crate::config::types::MemoriesToml;
     use crate::config::types::NotificationMethod;
     use crate::config::types::Notifications;
+    use crate::config::types::TuiTheme;
     use crate::config_loader::RequirementSource;
     use crate::features::Feature;
 
     use super::*;
+    use codex_hooks::CommandHookOnError;
     use core_test_support::test_absolute_path;
     use pretty_assertions::assert_eq;
 
@@ -2137,6 +2340,119 @@ phase_2_model = "gpt-5"
         );
     }
 
+    #[test]
+    fn hooks_config_builds_command_hooks_in_event_order() {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[[hooks.before_tool_use]]
+command = ["./hooks/guard.sh", "--strict"]
+timeout_sec = 3
+tool_name = "apply_patch"
+path = "generated/*"
+on_error = "deny"
+
+[[hooks.after_tool_use]]
+command = ["./hooks/audit.sh"]
+tool_kind = "local_shell"
+
+[[hooks.session_start]]
+command = ["./hooks/start.sh"]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect("load config with hooks");
+
+        assert_eq!(
+            config.hooks,
+            vec![
+                CommandHookConfig {
+                    event: HookEventKind::SessionStart,
+                    argv: vec!["./hooks/start.sh".to_string()],
+                    timeout: DEFAULT_COMMAND_HOOK_TIMEOUT,
+                    matcher: HookMatcher::default(),
+                    on_error: CommandHookOnError::Continue,
+                },
+                CommandHookConfig {
+                    event: HookEventKind::BeforeToolUse,
+                    argv: vec!["./hooks/guard.sh".to_string(), "--strict".to_string()],
+                    timeout: Duration::from_secs(3),
+                    matcher: HookMatcher {
+                        tool_name: Some("apply_patch".to_string()),
+                        tool_kind: None,
+                        path: Some("generated/*".to_string()),
+                    },
+                    on_error: CommandHookOnError::Deny,
+                },
+                CommandHookConfig {
+                    event: HookEventKind::AfterToolUse,
+                    argv: vec!["./hooks/audit.sh".to_string()],
+                    timeout: DEFAULT_COMMAND_HOOK_TIMEOUT,
+                    matcher: HookMatcher {
+                        tool_name: None,
+                        tool_kind: Some(codex_hooks::HookToolKind::LocalShell),
+                        path: None,
+                    },
+                    on_error: CommandHookOnError::Continue,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn hooks_config_rejects_empty_command() {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[[hooks.after_agent]]
+command = []
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let error = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect_err("empty hook command should be rejected");
+
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(
+            error.to_string(),
+            "hooks.after_agent.command must name a program to run"
+        );
+    }
+
+    #[test]
+    fn hooks_config_rejects_tool_matchers_on_other_events() {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[[hooks.session_start]]
+command = ["./hooks/start.sh"]
+tool_name = "shell"
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let error = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect_err("tool matcher on session_start should be rejected");
+
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(
+            error.to_string(),
+            "hooks.session_start does not run for tool calls, so tool_name, tool_kind and path do not apply"
+        );
+    }
+
     #[test]
     fn tui_config_missing_notifications_field_defaults_to_enabled() {
         let cfg = r#"
@@ -2157,10 +2473,111 @@ phase_2_model = "gpt-5"
                 experimental_mode: None,
                 alternate_screen: AltScreenMode::Auto,
                 status_line: None,
+                status_line_commands: BTreeMap::new(),
+                editing_mode: EditingMode::Emacs,
+                keymap: BTreeMap::new(),
+                theme: TuiTheme::default(),
             }
         );
     }
 
+    #[test]
+    fn tui_keymap_accepts_single_and_multiple_chords() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[tui.keymap]
+submit = "ctrl+enter"
+newline = ["enter", "shift+enter"]
+"#,
+        )
+        .expect("TUI keymap should deserialize");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.tui_keymap,
+            BTreeMap::from([
+                (
+                    "newline".to_string(),
+                    vec!["enter".to_string(), "shift+enter".to_string()],
+                ),
+                ("submit".to_string(), vec!["ctrl+enter".to_string()]),
+            ])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn tui_status_line_commands_apply_defaults() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[tui]
+status_line = ["model-name", "kube"]
+
+[tui.status_line_commands.kube]
+command = "kubectl config current-context"
+interval_secs = 30
+"#,
+        )
+        .expect("TUI status line commands should deserialize");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.tui_status_line_commands,
+            BTreeMap::from([(
+                "kube".to_string(),
+                StatusLineCommand {
+                    command: "kubectl config current-context".to_string(),
+                    interval_secs: Some(30),
+                    refresh_on_turn_complete: true,
+                    timeout_ms: None,
+                },
+            )])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn tui_theme_splits_name_from_color_overrides() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r##"
+[tui.theme]
+name = "high-contrast"
+diff_add = "light-green"
+user_message_bg = "#202830"
+"##,
+        )
+        .expect("TUI theme should deserialize");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(config.tui_theme, Some("high-contrast".to_string()));
+        assert_eq!(
+            config.tui_theme_colors,
+            BTreeMap::from([
+                ("diff_add".to_string(), "light-green".to_string()),
+                ("user_message_bg".to_string(), "#202830".to_string()),
+            ])
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_sandbox_config_parsing() {
         let sandbox_full_access = r#"
@@ -4105,6 +4522,7 @@ model_verbosity = "high"
                 did_user_set_custom_approval_policy_or_sandbox_mode: true,
                 user_instructions: None,
                 notify: None,
+                hooks: Vec::new(),
                 cwd: fixture.cwd(),
                 cli_auth_credentials_store_mode: Default::default(),
                 mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -4115,6 +4533,8 @@ model_verbosity = "high"
                 project_doc_fallback_filenames: Vec::new(),
                 tool_output_token_limit: None,
                 agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
+                agent_roles: crate::agent::builtin_agent_roles(),
+                tool_policy: ToolPolicy::default(),
                 memories: MemoriesConfig::default(),
                 codex_home: fixture.codex_home(),
                 log_dir: fixture.codex_home().join("log"),
@@ -4125,6 +4545,7 @@ model_verbosity = "high"
                 file_opener: UriBasedFileOpener::VsCode,
                 codex_linux_sandbox_exe: None,
                 js_repl_node_path: None,
+                py_repl_python_path: None,
                 hide_agent_reasoning: false,
                 show_raw_agent_reasoning: false,
                 model_reasoning_effort: Some(ReasoningEffort::High),
@@ -4159,6 +4580,11 @@ model_verbosity = "high"
                 feedback_enabled: true,
                 tui_alternate_screen: AltScreenMode::Auto,
                 tui_status_line: None,
+                tui_status_line_commands: BTreeMap::new(),
+                tui_editing_mode: EditingMode::Emacs,
+                tui_keymap: BTreeMap::new(),
+                tui_theme: None,
+                tui_theme_colors: BTreeMap::new(),
                 otel: OtelConfig::default(),
             },
             o3_profile_config
@@ -4216,6 +4642,7 @@ model_verbosity = "high"
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             user_instructions: None,
             notify: None,
+            hooks: Vec::new(),
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -4226,6 +4653,8 @@ model_verbosity = "high"
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
+            agent_roles: crate::agent::builtin_agent_roles(),
+            tool_policy: ToolPolicy::default(),
             memories: MemoriesConfig::default(),
             codex_home: fixture.codex_home(),
             log_dir: fixture.codex_home().join("log"),
@@ -4236,6 +4665,7 @@ model_verbosity = "high"
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             js_repl_node_path: None,
+            py_repl_python_path: None,
             hide_agent_reasoning: false,
             show_raw_agent_reasoning: false,
             model_reasoning_effort: None,
@@ -4270,6 +4700,11 @@ model_verbosity = "high"
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
             tui_status_line: None,
+            tui_status_line_commands: BTreeMap::new(),
+            tui_editing_mode: EditingMode::Emacs,
+            tui_keymap: BTreeMap::new(),
+            tui_theme: None,
+            tui_theme_colors: BTreeMap::new(),
             otel: OtelConfig::default(),
         };
 
@@ -4325,6 +4760,7 @@ model_verbosity = "high"
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             user_instructions: None,
             notify: None,
+            hooks: Vec::new(),
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -4335,6 +4771,8 @@ model_verbosity = "high"
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
+            agent_roles: crate::agent::builtin_agent_roles(),
+            tool_policy: ToolPolicy::default(),
             memories: MemoriesConfig::default(),
             codex_home: fixture.codex_home(),
             log_dir: fixture.codex_home().join("log"),
@@ -4345,6 +4783,7 @@ model_verbosity = "high"
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             js_repl_node_path: None,
+            py_repl_python_path: None,
             hide_agent_reasoning: false,
             show_raw_agent_reasoning: false,
             model_reasoning_effort: None,
@@ -4379,6 +4818,11 @@ model_verbosity = "high"
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
             tui_status_line: None,
+            tui_status_line_commands: BTreeMap::new(),
+            tui_editing_mode: EditingMode::Emacs,
+            tui_keymap: BTreeMap::new(),
+            tui_theme: None,
+            tui_theme_colors: BTreeMap::new(),
             otel: OtelConfig::default(),
         };
 
@@ -4420,6 +4864,7 @@ model_verbosity = "high"
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             user_instructions: None,
             notify: None,
+            hooks: Vec::new(),
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -4430,6 +4875,8 @@ model_verbosity = "high"
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
+            agent_roles: crate::agent::builtin_agent_roles(),
+            tool_policy: ToolPolicy::default(),
             memories: MemoriesConfig::default(),
             codex_home: fixture.codex_home(),
             log_dir: fixture.codex_home().join("log"),
@@ -4440,6 +4887,7 @@ model_verbosity = "high"
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             js_repl_node_path: None,
+            py_repl_python_path: None,
             hide_agent_reasoning: false,
             show_raw_agent_reasoning: false,
             model_reasoning_effort: Some(ReasoningEffort::High),
@@ -4474,6 +4922,11 @@ model_verbosity = "high"
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
             tui_status_line: None,
+            tui_status_line_commands: BTreeMap::new(),
+            tui_editing_mode: EditingMode::Emacs,
+            tui_keymap: BTreeMap::new(),
+            tui_theme: None,
+            tui_theme_colors: BTreeMap::new(),
             otel: OtelConfig::default(),
         };
 
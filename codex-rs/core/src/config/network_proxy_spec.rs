@@ -11,10 +11,12 @@ use codex_network_proxy::NetworkProxyConfig;
 use codex_network_proxy::NetworkProxyConstraints;
 use codex_network_proxy::NetworkProxyHandle;
 use codex_network_proxy::NetworkProxyState;
+use codex_network_proxy::NetworkRequestObserver;
 use codex_network_proxy::build_config_state;
 use codex_network_proxy::host_and_port_from_network_addr;
 use codex_network_proxy::validate_policy_against_constraints;
 use codex_protocol::protocol::SandboxPolicy;
+use std::path::Path;
 use std::sync::Arc;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -98,9 +100,11 @@ impl NetworkProxySpec {
 
     pub async fn start_proxy(
         &self,
+        codex_home: &Path,
         sandbox_policy: &SandboxPolicy,
         policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
         blocked_request_observer: Option<Arc<dyn BlockedRequestObserver>>,
+        request_observer: Option<Arc<dyn NetworkRequestObserver>>,
         enable_network_approval_flow: bool,
     ) -> std::io::Result<StartedNetworkProxy> {
         let state =
@@ -109,7 +113,9 @@ impl NetworkProxySpec {
             })?;
         let reloader = Arc::new(StaticNetworkProxyReloader::new(state.clone()));
         let state = NetworkProxyState::with_reloader(state, reloader);
-        let mut builder = NetworkProxy::builder().state(Arc::new(state));
+        let mut builder = NetworkProxy::builder()
+            .state(Arc::new(state))
+            .codex_home(codex_home.to_path_buf());
         if enable_network_approval_flow
             && matches!(
                 sandbox_policy,
@@ -128,6 +134,9 @@ impl NetworkProxySpec {
         if let Some(blocked_request_observer) = blocked_request_observer {
             builder = builder.blocked_request_observer_arc(blocked_request_observer);
         }
+        if let Some(request_observer) = request_observer {
+            builder = builder.request_observer_arc(request_observer);
+        }
         let proxy = builder.build().await.map_err(|err| {
             std::io::Error::other(format!("failed to build network proxy: {err}"))
         })?;
@@ -190,6 +199,10 @@ impl NetworkProxySpec {
             config.network.allow_local_binding = allow_local_binding;
             constraints.allow_local_binding = Some(allow_local_binding);
         }
+        if let Some(mitm) = requirements.mitm {
+            config.network.mitm = mitm;
+            constraints.mitm = Some(mitm);
+        }
 
         (config, constraints)
     }
@@ -7,7 +7,9 @@ use crate::config_loader::RequirementSource;
 pub use codex_protocol::config_types::AltScreenMode;
 pub use codex_protocol::config_types::ModeKind;
 pub use codex_protocol::config_types::Personality;
+use codex_protocol::config_types::SandboxMode;
 pub use codex_protocol::config_types::WebSearchMode;
+use codex_protocol::openai_models::ReasoningEffort;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
@@ -425,6 +427,83 @@ impl From<MemoriesToml> for MemoriesConfig {
     }
 }
 
+/// A `spawn_agent` role defined under `[agents.roles.<name>]` or in an
+/// `agents/<name>.md` file.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct AgentRoleToml {
+    /// Tells the parent agent when to use this role.
+    pub description: Option<String>,
+    /// Base instructions for agents spawned with this role.
+    pub instructions: Option<String>,
+    /// Model used by agents spawned with this role.
+    pub model: Option<String>,
+    /// Reasoning effort used by agents spawned with this role.
+    pub model_reasoning_effort: Option<ReasoningEffort>,
+    /// Sandbox for agents spawned with this role. It may not be less
+    /// restrictive than the sandbox of the spawning agent.
+    pub sandbox_mode: Option<SandboxMode>,
+    /// Glob patterns of tools the agent may call. All tools are available
+    /// when unset.
+    pub tools: Option<Vec<String>>,
+    /// Glob patterns of tools the agent may not call.
+    pub tools_deny: Option<Vec<String>>,
+}
+
+/// Effective settings of a `spawn_agent` role.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AgentRoleConfig {
+    pub description: String,
+    pub base_instructions: Option<String>,
+    pub model: Option<String>,
+    pub reasoning_effort: Option<ReasoningEffort>,
+    pub sandbox_mode: Option<SandboxMode>,
+    pub tools: Option<Vec<String>>,
+    pub tools_deny: Vec<String>,
+}
+
+impl From<AgentRoleToml> for AgentRoleConfig {
+    fn from(toml: AgentRoleToml) -> Self {
+        Self {
+            description: toml.description.unwrap_or_default(),
+            base_instructions: toml.instructions,
+            model: toml.model,
+            reasoning_effort: toml.model_reasoning_effort,
+            sandbox_mode: toml.sandbox_mode,
+            tools: toml.tools,
+            tools_deny: toml.tools_deny.unwrap_or_default(),
+        }
+    }
+}
+
+pub type ToolNamePattern = WildMatchPattern<'*', '?'>;
+
+/// Decides which tools a thread can see and call. Patterns are globs over
+/// tool names; MCP tools also match as `server/tool`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ToolPolicy {
+    /// A tool must match a pattern in each of these lists. Every profile or
+    /// agent role that restricts tools adds a list, so a spawned agent can
+    /// narrow the tools of its parent but never widen them.
+    pub allow: Vec<Vec<String>>,
+    /// A tool matching any of these patterns is unavailable.
+    pub deny: Vec<String>,
+}
+
+impl ToolPolicy {
+    /// Returns whether a tool known by any of `names` is available.
+    pub fn allows(&self, names: &[&str]) -> bool {
+        let matches = |pattern: &String| {
+            let pattern = ToolNamePattern::new(pattern);
+            names.iter().any(|name| pattern.matches(name))
+        };
+        self.allow
+            .iter()
+            .all(|patterns| patterns.iter().any(matches))
+            && !self.deny.iter().any(matches)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum AppDisabledReason {
@@ -463,6 +542,63 @@ pub struct AppsConfigToml {
     pub apps: HashMap<String, AppConfig>,
 }
 
+// ===== Hooks configuration =====
+
+/// External command hooks loaded from `[[hooks.<event>]]` tables.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HooksToml {
+    #[serde(default)]
+    pub session_start: Vec<HookCommandToml>,
+    #[serde(default)]
+    pub session_end: Vec<HookCommandToml>,
+    #[serde(default)]
+    pub user_prompt_submit: Vec<HookCommandToml>,
+    #[serde(default)]
+    pub before_tool_use: Vec<HookCommandToml>,
+    #[serde(default)]
+    pub after_tool_use: Vec<HookCommandToml>,
+    #[serde(default)]
+    pub before_compaction: Vec<HookCommandToml>,
+    #[serde(default)]
+    pub after_agent: Vec<HookCommandToml>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HookCommandToml {
+    /// Program and arguments to run. The hook payload is written to stdin as JSON.
+    pub command: Vec<String>,
+    /// Seconds to wait before the hook is killed and the event continues. Defaults to 10.
+    pub timeout_sec: Option<u64>,
+    /// Only run for tools whose name matches this glob (tool events only).
+    pub tool_name: Option<String>,
+    /// Only run for tools of this kind (tool events only).
+    pub tool_kind: Option<codex_hooks::HookToolKind>,
+    /// Only run when the tool call references a path matching this glob (tool events only).
+    pub path: Option<String>,
+    /// What a spawn failure, non-zero exit or timeout means. Defaults to `continue`.
+    pub on_error: Option<HookOnErrorToml>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HookOnErrorToml {
+    /// Log the failure and let the event proceed.
+    Continue,
+    /// Treat the failure as a denial for events that can be denied.
+    Deny,
+}
+
+impl From<HookOnErrorToml> for codex_hooks::CommandHookOnError {
+    fn from(on_error: HookOnErrorToml) -> Self {
+        match on_error {
+            HookOnErrorToml::Continue => Self::Continue,
+            HookOnErrorToml::Deny => Self::Deny,
+        }
+    }
+}
+
 // ===== OTEL configuration =====
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
@@ -581,6 +717,17 @@ impl fmt::Display for NotificationMethod {
     }
 }
 
+/// Key handling style for the TUI composer.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum EditingMode {
+    /// Emacs-style movement and editing keys.
+    #[default]
+    Emacs,
+    /// Modal editing with normal, insert and visual modes.
+    Vim,
+}
+
 /// Collection of settings that are specific to the TUI.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
 #[schemars(deny_unknown_fields)]
@@ -626,6 +773,81 @@ pub struct Tui {
     /// When set, the TUI renders the selected items as the status line.
     #[serde(default)]
     pub status_line: Option<Vec<String>>,
+
+    /// Custom status line segments backed by shell commands, keyed by the
+    /// identifier used in `status_line`.
+    #[serde(default)]
+    pub status_line_commands: BTreeMap<String, StatusLineCommand>,
+
+    /// Key handling style for the composer: `emacs` (default) or `vim`.
+    #[serde(default)]
+    pub editing_mode: EditingMode,
+
+    /// Key chords for TUI actions, keyed by action name. For example
+    /// `submit = "ctrl+enter"` or `newline = ["enter", "shift+enter"]`.
+    /// Actions that are not listed keep their default keys.
+    #[serde(default)]
+    pub keymap: BTreeMap<String, KeyChords>,
+
+    /// Color theme: a built-in theme name, the name of a theme file under
+    /// `$CODEX_HOME/themes`, and per-role color overrides.
+    #[serde(default)]
+    pub theme: TuiTheme,
+}
+
+/// A status line segment that shows the output of a shell command.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct StatusLineCommand {
+    /// Shell command to run. The first line of its trimmed stdout is shown,
+    /// without escape sequences or control characters; the segment is
+    /// omitted when the command fails or prints nothing.
+    pub command: String,
+
+    /// Re-run the command every this many seconds. When unset the command
+    /// only runs at startup and, if enabled, after each turn.
+    #[serde(default)]
+    pub interval_secs: Option<u64>,
+
+    /// Re-run the command when a turn completes. Defaults to `true`.
+    #[serde(default = "default_true")]
+    pub refresh_on_turn_complete: bool,
+
+    /// Kill the command, and any processes it started, after this many
+    /// milliseconds and keep showing the previous output. Defaults to `2000`.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+/// Theme selection and color overrides from `[tui.theme]`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+pub struct TuiTheme {
+    /// `default`, `high-contrast`, `colorblind`, or `<name>` to load
+    /// `$CODEX_HOME/themes/<name>.toml`. Defaults to `default`.
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// Colors keyed by semantic role, applied on top of the selected theme.
+    /// For example `diff_add = "light-green"` or `user_message_bg = "#202830"`.
+    #[serde(flatten)]
+    pub colors: BTreeMap<String, String>,
+}
+
+/// One key chord or a list of key chords bound to a TUI action.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(untagged)]
+pub enum KeyChords {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyChords {
+    pub fn into_vec(self) -> Vec<String> {
+        match self {
+            KeyChords::One(chord) => vec![chord],
+            KeyChords::Many(chords) => chords,
+        }
+    }
 }
 
 const fn default_true() -> bool {
@@ -1093,4 +1315,21 @@ mod tests {
             "unexpected error: {err}"
         );
     }
+
+    #[test]
+    fn tool_policy_requires_every_allow_list_and_no_deny_match() {
+        let policy = ToolPolicy {
+            allow: vec![
+                vec!["read_*".to_string(), "docs/*".to_string()],
+                vec!["*".to_string()],
+            ],
+            deny: vec!["docs/delete_*".to_string()],
+        };
+
+        assert!(policy.allows(&["read_file"]));
+        assert!(!policy.allows(&["apply_patch"]));
+        assert!(policy.allows(&["mcp__docs__search", "docs/search"]));
+        assert!(!policy.allows(&["mcp__docs__delete_page", "docs/delete_page"]));
+        assert!(ToolPolicy::default().allows(&["apply_patch"]));
+    }
 }
@@ -24,6 +24,7 @@ use crate::mcp::auth::compute_auth_statuses;
 use crate::mcp::with_codex_apps_mcp;
 use crate::mcp_connection_manager::DEFAULT_STARTUP_TIMEOUT;
 use crate::mcp_connection_manager::McpConnectionManager;
+use crate::secrets::SessionSecrets;
 use crate::token_data::TokenData;
 
 pub const CONNECTORS_CACHE_TTL: Duration = Duration::from_secs(3600);
@@ -109,6 +110,7 @@ pub async fn list_accessible_connectors_from_mcp_tools_with_options(
             tx_event,
             cancel_token.clone(),
             sandbox_state,
+            SessionSecrets::new(config.codex_home.clone()),
         )
         .await;
 
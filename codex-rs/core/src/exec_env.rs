@@ -1,9 +1,13 @@
 use crate::config::types::EnvironmentVariablePattern;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyInherit;
+use crate::secrets::SessionSecrets;
 use codex_protocol::ThreadId;
+use codex_secrets::contains_secret_reference;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::path::Path;
+use tracing::warn;
 
 pub const CODEX_THREAD_ID_ENV_VAR: &str = "CODEX_THREAD_ID";
 
@@ -24,6 +28,73 @@ pub fn create_env(
     populate_env(std::env::vars(), policy, thread_id)
 }
 
+/// Like [`create_env`], but also resolves `${secret:NAME}` references in the
+/// policy's `set` values against the secrets of the environment `session_cwd`
+/// belongs to. This is the session's cwd, never a command's `workdir`, which
+/// the model chooses. A variable whose secret cannot be resolved is left out
+/// of the environment.
+pub async fn create_env_with_secrets(
+    policy: &ShellEnvironmentPolicy,
+    thread_id: Option<ThreadId>,
+    secrets: &SessionSecrets,
+    session_cwd: &Path,
+) -> HashMap<String, String> {
+    let mut env_map = create_env(policy, thread_id);
+    if !policy
+        .r#set
+        .values()
+        .any(|value| contains_secret_reference(value))
+    {
+        return env_map;
+    }
+
+    // Secrets are read from the keyring and the secrets file, both blocking.
+    let blocking_policy = policy.clone();
+    let secrets = secrets.clone();
+    let session_cwd = session_cwd.to_path_buf();
+    let resolved = tokio::task::spawn_blocking(move || {
+        resolve_secret_overrides(&mut env_map, &blocking_policy, &secrets, &session_cwd);
+        env_map
+    })
+    .await;
+    match resolved {
+        Ok(env_map) => env_map,
+        Err(err) => {
+            warn!("failed to resolve shell_environment_policy secrets: {err}");
+            let mut env_map = create_env(policy, thread_id);
+            for (key, value) in &policy.r#set {
+                if contains_secret_reference(value) {
+                    env_map.remove(key);
+                }
+            }
+            env_map
+        }
+    }
+}
+
+fn resolve_secret_overrides(
+    env_map: &mut HashMap<String, String>,
+    policy: &ShellEnvironmentPolicy,
+    secrets: &SessionSecrets,
+    cwd: &Path,
+) {
+    for (key, value) in &policy.r#set {
+        // Skip overrides that `include_only` filtered out.
+        if !contains_secret_reference(value) || env_map.get(key) != Some(value) {
+            continue;
+        }
+        match secrets.resolve(value, cwd) {
+            Ok(resolved) => {
+                env_map.insert(key.clone(), resolved);
+            }
+            Err(err) => {
+                warn!("omitting shell_environment_policy.set.{key}: {err:#}");
+                env_map.remove(key);
+            }
+        }
+    }
+}
+
 fn populate_env<I>(
     vars: I,
     policy: &ShellEnvironmentPolicy,
@@ -97,7 +168,13 @@ where
 mod tests {
     use super::*;
     use crate::config::types::ShellEnvironmentPolicyInherit;
+    use codex_keyring_store::tests::MockKeyringStore;
+    use codex_secrets::SecretName;
+    use codex_secrets::SecretScope;
+    use codex_secrets::SecretsBackendKind;
+    use codex_secrets::SecretsManager;
     use maplit::hashmap;
+    use std::sync::Arc;
 
     fn make_vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
         pairs
@@ -310,4 +387,80 @@ mod tests {
         expected.insert(CODEX_THREAD_ID_ENV_VAR.to_string(), thread_id.to_string());
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn secret_references_in_set_are_resolved() -> anyhow::Result<()> {
+        let codex_home = tempfile::tempdir()?;
+        let manager = SecretsManager::new_with_keyring_store(
+            codex_home.path().to_path_buf(),
+            SecretsBackendKind::Local,
+            Arc::new(MockKeyringStore::default()),
+        );
+        manager.set(
+            &SecretScope::Global,
+            &SecretName::new("NPM_TOKEN")?,
+            "npm-1",
+        )?;
+        let secrets = SessionSecrets::from_manager(manager);
+
+        let mut policy = ShellEnvironmentPolicy {
+            inherit: ShellEnvironmentPolicyInherit::None,
+            ..Default::default()
+        };
+        policy
+            .r#set
+            .insert("NPM_TOKEN".to_string(), "${secret:NPM_TOKEN}".to_string());
+        policy
+            .r#set
+            .insert("MISSING".to_string(), "${secret:MISSING_TOKEN}".to_string());
+
+        let mut result = populate_env(Vec::new(), &policy, None);
+        resolve_secret_overrides(&mut result, &policy, &secrets, codex_home.path());
+
+        let expected: HashMap<String, String> = hashmap! {
+            "NPM_TOKEN".to_string() => "npm-1".to_string(),
+        };
+        assert_eq!(result, expected);
+        assert_eq!(secrets.redact("npm-1"), "[REDACTED:NPM_TOKEN]");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn environment_secrets_follow_the_session_cwd() -> anyhow::Result<()> {
+        let codex_home = tempfile::tempdir()?;
+        let session_cwd = tempfile::tempdir()?;
+        let other_checkout = tempfile::tempdir()?;
+        let manager = SecretsManager::new_with_keyring_store(
+            codex_home.path().to_path_buf(),
+            SecretsBackendKind::Local,
+            Arc::new(MockKeyringStore::default()),
+        );
+        let name = SecretName::new("DEPLOY_TOKEN")?;
+        manager.set(&SecretScope::Global, &name, "global")?;
+        manager.set(
+            &SecretScope::environment(codex_secrets::environment_id_from_cwd(
+                other_checkout.path(),
+            ))?,
+            &name,
+            "other-checkout",
+        )?;
+        let secrets = SessionSecrets::from_manager(manager);
+
+        let mut policy = ShellEnvironmentPolicy {
+            inherit: ShellEnvironmentPolicyInherit::None,
+            ..Default::default()
+        };
+        policy.r#set.insert(
+            "DEPLOY_TOKEN".to_string(),
+            "${secret:DEPLOY_TOKEN}".to_string(),
+        );
+
+        let env = create_env_with_secrets(&policy, None, &secrets, session_cwd.path()).await;
+
+        assert_eq!(
+            env,
+            hashmap! { "DEPLOY_TOKEN".to_string() => "global".to_string() }
+        );
+        Ok(())
+    }
 }
@@ -13,6 +13,7 @@ use codex_execpolicy::AmendError;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Error as ExecPolicyRuleError;
 use codex_execpolicy::Evaluation;
+use codex_execpolicy::PathContext;
 use codex_execpolicy::Policy;
 use codex_execpolicy::PolicyParser;
 use codex_execpolicy::RuleMatch;
@@ -86,7 +87,7 @@ static BANNED_PREFIX_SUGGESTIONS: &[&[&str]] = &[
 
 fn is_policy_match(rule_match: &RuleMatch) -> bool {
     match rule_match {
-        RuleMatch::PrefixRuleMatch { .. } => true,
+        RuleMatch::PrefixRuleMatch { .. } | RuleMatch::ArgumentRuleMatch { .. } => true,
         RuleMatch::HeuristicsRuleMatch { .. } => false,
     }
 }
@@ -133,6 +134,10 @@ pub(crate) struct ExecPolicyManager {
 
 pub(crate) struct ExecApprovalRequest<'a> {
     pub(crate) command: &'a [String],
+    /// Directory the command runs in, which the model may have chosen.
+    pub(crate) cwd: &'a Path,
+    /// The session's working directory, which `.` in `path_roots` refers to.
+    pub(crate) session_cwd: &'a Path,
     pub(crate) approval_policy: AskForApproval,
     pub(crate) sandbox_policy: &'a SandboxPolicy,
     pub(crate) sandbox_permissions: SandboxPermissions,
@@ -164,6 +169,8 @@ impl ExecPolicyManager {
     ) -> ExecApprovalRequirement {
         let ExecApprovalRequest {
             command,
+            cwd,
+            session_cwd,
             approval_policy,
             sandbox_policy,
             sandbox_permissions,
@@ -183,7 +190,11 @@ impl ExecPolicyManager {
                 sandbox_permissions,
             )
         };
-        let evaluation = exec_policy.check_multiple(commands.iter(), &exec_policy_fallback);
+        let evaluation = exec_policy.check_multiple_in(
+            commands.iter(),
+            &exec_policy_fallback,
+            path_context(&commands, used_heredoc_fallback, cwd, session_cwd),
+        );
 
         let requested_amendment =
             derive_requested_execpolicy_amendment(prefix_rule.as_ref(), &evaluation.matched_rules);
@@ -468,7 +479,32 @@ fn default_policy_path(codex_home: &Path) -> PathBuf {
     codex_home.join(RULES_DIR_NAME).join(DEFAULT_POLICY_FILE)
 }
 
-fn commands_for_exec_policy(command: &[String]) -> (Vec<Vec<String>>, bool) {
+/// Relative paths only resolve against the session cwd when the command runs
+/// there and never changes directory; otherwise `path_roots` cannot place them.
+/// The heredoc fallback only parses the first command of the script, so the
+/// rest of it may change directory too.
+fn path_context<'a>(
+    commands: &[Vec<String>],
+    used_heredoc_fallback: bool,
+    cwd: &'a Path,
+    session_cwd: &Path,
+) -> PathContext<'a> {
+    let changes_directory = commands.iter().any(|command| {
+        command
+            .first()
+            .is_some_and(|program| matches!(program.as_str(), "cd" | "pushd" | "popd"))
+    });
+    if used_heredoc_fallback || changes_directory || cwd != session_cwd {
+        PathContext::Unknown
+    } else {
+        PathContext::Cwd(cwd)
+    }
+}
+
+/// Splits `command` into the commands execpolicy evaluates. The flag is true
+/// when the script could only be reduced to its leading command (heredocs and
+/// similar), so the rest of the script was not inspected.
+pub fn commands_for_exec_policy(command: &[String]) -> (Vec<Vec<String>>, bool) {
     if let Some(commands) = parse_shell_lc_plain_commands(command)
         && !commands.is_empty()
     {
@@ -576,6 +612,13 @@ fn derive_prompt_reason(command_args: &[String], evaluation: &Evaluation) -> Opt
                 justification,
                 ..
             } => Some((matched_prefix.len(), justification.as_deref())),
+            // An argument rule inspects the whole argv, so treat it as more
+            // specific than any prefix.
+            RuleMatch::ArgumentRuleMatch {
+                decision: Decision::Prompt,
+                justification,
+                ..
+            } => Some((usize::MAX, justification.as_deref())),
             _ => None,
         })
         .max_by_key(|(matched_prefix_len, _)| *matched_prefix_len);
@@ -604,26 +647,33 @@ fn derive_forbidden_reason(command_args: &[String], evaluation: &Evaluation) ->
     let most_specific_forbidden = evaluation
         .matched_rules
         .iter()
+        .filter(|rule_match| rule_match.decision() == Decision::Forbidden)
         .filter_map(|rule_match| match rule_match {
-            RuleMatch::PrefixRuleMatch {
-                matched_prefix,
-                decision: Decision::Forbidden,
-                justification,
-                ..
-            } => Some((matched_prefix, justification.as_deref())),
-            _ => None,
+            RuleMatch::PrefixRuleMatch { matched_prefix, .. } => {
+                Some((matched_prefix.len(), rule_match))
+            }
+            RuleMatch::ArgumentRuleMatch { .. } => Some((usize::MAX, rule_match)),
+            RuleMatch::HeuristicsRuleMatch { .. } => None,
         })
-        .max_by_key(|(matched_prefix, _)| matched_prefix.len());
+        .max_by_key(|(specificity, _)| *specificity);
 
-    match most_specific_forbidden {
-        Some((_matched_prefix, Some(justification))) => {
-            format!("`{command}` rejected: {justification}")
-        }
-        Some((matched_prefix, None)) => {
+    let Some((_, rule_match)) = most_specific_forbidden else {
+        return format!("`{command}` rejected: blocked by policy");
+    };
+    if let Some(justification) = rule_match.justification() {
+        return format!("`{command}` rejected: {justification}");
+    }
+    match rule_match {
+        RuleMatch::PrefixRuleMatch { matched_prefix, .. } => {
             let prefix = render_shlex_command(matched_prefix);
             format!("`{command}` rejected: policy forbids commands starting with `{prefix}`")
         }
-        None => format!("`{command}` rejected: blocked by policy"),
+        RuleMatch::ArgumentRuleMatch {
+            matched_argument, ..
+        } => format!("`{command}` rejected: policy forbids the argument `{matched_argument}`"),
+        RuleMatch::HeuristicsRuleMatch { .. } => {
+            format!("`{command}` rejected: blocked by policy")
+        }
     }
 }
 
@@ -979,6 +1029,8 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &forbidden_script,
+                cwd: Path::new("/workspace"),
+                session_cwd: Path::new("/workspace"),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -994,6 +1046,42 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         );
     }
 
+    #[tokio::test]
+    async fn argument_rule_rejection_names_the_argument() {
+        let policy_src = r#"
+argument_rule(program="git", argument=regex("--force|-f"), decision="forbidden")
+"#;
+        let mut parser = PolicyParser::new();
+        parser
+            .parse("test.rules", policy_src)
+            .expect("parse policy");
+        let manager = ExecPolicyManager::new(Arc::new(parser.build()));
+
+        let command = vec![
+            "bash".to_string(),
+            "-lc".to_string(),
+            "git fetch && git push origin main --force".to_string(),
+        ];
+        let requirement = manager
+            .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+                command: &command,
+                cwd: Path::new("/workspace"),
+                session_cwd: Path::new("/workspace"),
+                approval_policy: AskForApproval::OnRequest,
+                sandbox_policy: &SandboxPolicy::DangerFullAccess,
+                sandbox_permissions: SandboxPermissions::UseDefault,
+                prefix_rule: None,
+            })
+            .await;
+
+        assert_eq!(
+            requirement,
+            ExecApprovalRequirement::Forbidden {
+                reason: "`bash -lc 'git fetch && git push origin main --force'` rejected: policy forbids the argument `--force`".to_string()
+            }
+        );
+    }
+
     #[test]
     fn commands_for_exec_policy_falls_back_for_empty_shell_script() {
         let command = vec!["bash".to_string(), "-lc".to_string(), "".to_string()];
@@ -1029,6 +1117,8 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         let requirement = ExecPolicyManager::new(policy)
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                session_cwd: Path::new("/workspace"),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1056,6 +1146,8 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         let requirement = ExecPolicyManager::default()
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                session_cwd: Path::new("/workspace"),
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1084,6 +1176,8 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         let requirement = ExecPolicyManager::default()
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                session_cwd: Path::new("/workspace"),
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1123,6 +1217,8 @@ prefix_rule(
                     "-rf".to_string(),
                     "/some/important/folder".to_string(),
                 ],
+                cwd: Path::new("/workspace"),
+                session_cwd: Path::new("/workspace"),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1138,6 +1234,69 @@ prefix_rule(
         );
     }
 
+    #[tokio::test]
+    async fn path_roots_do_not_bypass_sandbox_outside_the_session_cwd() {
+        let policy_src = r#"prefix_rule(pattern=["rm"], path_roots=["."])"#;
+        let mut parser = PolicyParser::new();
+        parser
+            .parse("test.rules", policy_src)
+            .expect("parse policy");
+        let manager = ExecPolicyManager::new(Arc::new(parser.build()));
+        let requirement = |command: Vec<String>, cwd: &'static str| {
+            let manager = &manager;
+            async move {
+                manager
+                    .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+                        command: &command,
+                        cwd: Path::new(cwd),
+                        session_cwd: Path::new("/workspace"),
+                        approval_policy: AskForApproval::OnRequest,
+                        sandbox_policy: &SandboxPolicy::new_read_only_policy(),
+                        sandbox_permissions: SandboxPermissions::UseDefault,
+                        prefix_rule: None,
+                    })
+                    .await
+            }
+        };
+        let bypasses_sandbox = |requirement: &ExecApprovalRequirement| {
+            matches!(
+                requirement,
+                ExecApprovalRequirement::Skip {
+                    bypass_sandbox: true,
+                    ..
+                }
+            )
+        };
+
+        let in_workspace = requirement(
+            vec!["rm".to_string(), "-rf".to_string(), "build".to_string()],
+            "/workspace",
+        )
+        .await;
+        assert!(bypasses_sandbox(&in_workspace), "{in_workspace:?}");
+
+        let changes_directory = requirement(
+            vec![
+                "bash".to_string(),
+                "-lc".to_string(),
+                "cd / && rm -rf etc".to_string(),
+            ],
+            "/workspace",
+        )
+        .await;
+        assert!(
+            !bypasses_sandbox(&changes_directory),
+            "{changes_directory:?}"
+        );
+
+        let other_workdir = requirement(
+            vec!["rm".to_string(), "-rf".to_string(), "etc".to_string()],
+            "/",
+        )
+        .await;
+        assert!(!bypasses_sandbox(&other_workdir), "{other_workdir:?}");
+    }
+
     #[tokio::test]
     async fn exec_approval_requirement_prefers_execpolicy_match() {
         let policy_src = r#"prefix_rule(pattern=["rm"], decision="prompt")"#;
@@ -1152,6 +1311,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                session_cwd: Path::new("/workspace"),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1182,6 +1343,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                session_cwd: Path::new("/workspace"),
                 approval_policy: AskForApproval::Never,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1205,6 +1368,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                session_cwd: Path::new("/workspace"),
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1229,6 +1394,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                session_cwd: Path::new("/workspace"),
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1257,6 +1424,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                session_cwd: Path::new("/workspace"),
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1287,6 +1456,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                session_cwd: Path::new("/workspace"),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::RequireEscalated,
@@ -1324,6 +1495,8 @@ prefix_rule(
             ExecPolicyManager::new(policy)
                 .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                     command: &command,
+                    cwd: Path::new("/workspace"),
+                    session_cwd: Path::new("/workspace"),
                     approval_policy: AskForApproval::UnlessTrusted,
                     sandbox_policy: &SandboxPolicy::DangerFullAccess,
                     sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1398,6 +1571,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                session_cwd: Path::new("/workspace"),
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1428,6 +1603,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                session_cwd: Path::new("/workspace"),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1455,6 +1632,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                session_cwd: Path::new("/workspace"),
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1493,6 +1672,8 @@ prefix_rule(
             ExecPolicyManager::new(policy)
                 .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                     command: &command,
+                    cwd: Path::new("/workspace"),
+                    session_cwd: Path::new("/workspace"),
                     approval_policy: AskForApproval::UnlessTrusted,
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1516,6 +1697,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                session_cwd: Path::new("/workspace"),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1546,6 +1729,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                session_cwd: Path::new("/workspace"),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1665,6 +1850,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                session_cwd: Path::new("/workspace"),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1731,6 +1918,8 @@ prefix_rule(
             policy
                 .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                     command: &sneaky_command,
+                    cwd: Path::new("/workspace"),
+                    session_cwd: Path::new("/workspace"),
                     approval_policy: AskForApproval::OnRequest,
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: permissions,
@@ -1754,6 +1943,8 @@ prefix_rule(
             policy
                 .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                     command: &dangerous_command,
+                    cwd: Path::new("/workspace"),
+                    session_cwd: Path::new("/workspace"),
                     approval_policy: AskForApproval::OnRequest,
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: permissions,
@@ -1773,6 +1964,8 @@ prefix_rule(
             policy
                 .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                     command: &dangerous_command,
+                    cwd: Path::new("/workspace"),
+                    session_cwd: Path::new("/workspace"),
                     approval_policy: AskForApproval::Never,
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: permissions,
@@ -82,6 +82,8 @@ pub enum Feature {
     JsRepl,
     /// Only expose js_repl tools directly to the model.
     JsReplToolsOnly,
+    /// Enable Python REPL tools backed by a persistent Python kernel.
+    PyRepl,
     /// Use the single unified PTY-backed exec tool.
     UnifiedExec,
     /// Include the freeform apply_patch tool.
@@ -446,6 +448,12 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::UnderDevelopment,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::PyRepl,
+        key: "py_repl",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::WebSearchRequest,
         key: "web_search_request",
@@ -10,6 +10,7 @@ pub mod api_bridge;
 mod apply_patch;
 mod apps;
 pub mod auth;
+mod checkpoints;
 mod client;
 mod client_common;
 pub mod codex;
@@ -99,6 +100,7 @@ pub mod project_doc;
 mod rollout;
 pub(crate) mod safety;
 pub mod seatbelt;
+pub mod secrets;
 pub mod shell;
 pub mod shell_snapshot;
 pub mod skills;
@@ -133,6 +135,7 @@ pub use rollout::session_index::find_thread_names_by_ids;
 mod function_tool;
 mod state;
 mod tasks;
+pub use tasks::remove_thread_file_snapshots;
 mod user_shell_command;
 pub mod util;
 pub use codex_shell_command::bash;
@@ -145,8 +148,10 @@ pub use apply_patch::CODEX_APPLY_PATCH_ARG1;
 pub use client::X_CODEX_TURN_METADATA_HEADER;
 pub use exec_policy::ExecPolicyError;
 pub use exec_policy::check_execpolicy_for_warnings;
+pub use exec_policy::commands_for_exec_policy;
 pub use exec_policy::format_exec_policy_error_with_source;
 pub use exec_policy::load_exec_policy;
+pub use exec_policy::render_decision_for_unmatched_command;
 pub use file_watcher::FileWatcherEvent;
 pub use safety::get_platform_sandbox;
 pub use tools::spec::parse_tool_input_schema;
@@ -25,6 +25,7 @@ use crate::features::Feature;
 use crate::mcp::auth::compute_auth_statuses;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::mcp_connection_manager::SandboxState;
+use crate::secrets::SessionSecrets;
 
 const MCP_TOOL_NAME_PREFIX: &str = "mcp";
 const MCP_TOOL_NAME_DELIMITER: &str = "__";
@@ -212,6 +213,7 @@ pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent
             tx_event,
             cancel_token.clone(),
             sandbox_state,
+            SessionSecrets::new(config.codex_home.clone()),
         )
         .await;
 
@@ -10,6 +10,7 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::env;
 use std::ffi::OsString;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::LazyLock;
@@ -19,6 +20,7 @@ use std::time::Instant;
 
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
 use crate::mcp::auth::McpAuthStatusEntry;
+use crate::secrets::SessionSecrets;
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::anyhow;
@@ -282,10 +284,13 @@ struct AsyncManagedClient {
 }
 
 impl AsyncManagedClient {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         server_name: String,
         config: McpServerConfig,
         store_mode: OAuthCredentialsStoreMode,
+        secrets: SessionSecrets,
+        cwd: PathBuf,
         cancel_token: CancellationToken,
         tx_event: Sender<Event>,
         elicitation_requests: ElicitationRequestManager,
@@ -296,8 +301,10 @@ impl AsyncManagedClient {
                 return Err(error.into());
             }
 
-            let client =
-                Arc::new(make_rmcp_client(&server_name, config.transport, store_mode).await?);
+            let client = Arc::new(
+                make_rmcp_client(&server_name, config.transport, store_mode, &secrets, &cwd)
+                    .await?,
+            );
             match start_server_task(
                 server_name,
                 client,
@@ -353,6 +360,7 @@ pub(crate) struct McpConnectionManager {
 }
 
 impl McpConnectionManager {
+    #[allow(clippy::too_many_arguments)]
     pub async fn initialize(
         &mut self,
         mcp_servers: &HashMap<String, McpServerConfig>,
@@ -361,6 +369,7 @@ impl McpConnectionManager {
         tx_event: Sender<Event>,
         cancel_token: CancellationToken,
         initial_sandbox_state: SandboxState,
+        secrets: SessionSecrets,
     ) {
         if cancel_token.is_cancelled() {
             return;
@@ -383,6 +392,8 @@ impl McpConnectionManager {
                 server_name.clone(),
                 cfg,
                 store_mode,
+                secrets.clone(),
+                initial_sandbox_state.sandbox_cwd.clone(),
                 cancel_token.clone(),
                 tx_event.clone(),
                 elicitation_requests.clone(),
@@ -1062,6 +1073,8 @@ async fn make_rmcp_client(
     server_name: &str,
     transport: McpServerTransportConfig,
     store_mode: OAuthCredentialsStoreMode,
+    secrets: &SessionSecrets,
+    session_cwd: &Path,
 ) -> Result<RmcpClient, StartupOutcomeError> {
     match transport {
         McpServerTransportConfig::Stdio {
@@ -1071,6 +1084,14 @@ async fn make_rmcp_client(
             env_vars,
             cwd,
         } => {
+            let env = env
+                .map(|env| secrets.resolve_map(env, session_cwd))
+                .transpose()
+                .map_err(|err| {
+                    StartupOutcomeError::from(anyhow!(
+                        "failed to resolve secrets in mcp_servers.{server_name}.env: {err:#}"
+                    ))
+                })?;
             let command_os: OsString = command.into();
             let args_os: Vec<OsString> = args.into_iter().map(Into::into).collect();
             RmcpClient::new_stdio_client(command_os, args_os, env, &env_vars, cwd)
@@ -1088,6 +1109,14 @@ async fn make_rmcp_client(
                     Ok(token) => token,
                     Err(error) => return Err(error.into()),
                 };
+            let http_headers = http_headers
+                .map(|headers| secrets.resolve_map(headers, session_cwd))
+                .transpose()
+                .map_err(|err| {
+                    StartupOutcomeError::from(anyhow!(
+                        "failed to resolve secrets in mcp_servers.{server_name}.http_headers: {err:#}"
+                    ))
+                })?;
             RmcpClient::new_streamable_http_client(
                 server_name,
                 &url,
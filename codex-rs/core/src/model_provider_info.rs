@@ -27,7 +27,6 @@ const MAX_STREAM_MAX_RETRIES: u64 = 100;
 const MAX_REQUEST_MAX_RETRIES: u64 = 100;
 
 const OPENAI_PROVIDER_NAME: &str = "OpenAI";
-const CHAT_WIRE_API_REMOVED_ERROR: &str = "`wire_api = \"chat\"` is no longer supported.\nHow to fix: set `wire_api = \"responses\"` in your provider config.\nMore info: https://github.com/openai/codex/discussions/7782";
 pub(crate) const LEGACY_OLLAMA_CHAT_PROVIDER_ID: &str = "ollama-chat";
 pub(crate) const OLLAMA_CHAT_PROVIDER_REMOVED_ERROR: &str = "`ollama-chat` is no longer supported.\nHow to fix: replace `ollama-chat` with `ollama` in `model_provider`, `oss_provider`, or `--local-provider`.\nMore info: https://github.com/openai/codex/discussions/7782";
 
@@ -38,6 +37,14 @@ pub enum WireApi {
     /// The Responses API exposed by OpenAI at `/v1/responses`.
     #[default]
     Responses,
+    /// The Chat Completions API at `/v1/chat/completions`, for OpenAI-compatible servers
+    /// (llama.cpp, vLLM, older Ollama) that do not implement Responses.
+    Chat,
+    /// The Anthropic Messages API at `/v1/messages`. The API key is sent as `x-api-key`.
+    Anthropic,
+    /// The Gemini `models/{model}:streamGenerateContent` API. The API key is sent as
+    /// `x-goog-api-key`.
+    Gemini,
 }
 
 impl<'de> Deserialize<'de> for WireApi {
@@ -48,8 +55,13 @@ impl<'de> Deserialize<'de> for WireApi {
         let value = String::deserialize(deserializer)?;
         match value.as_str() {
             "responses" => Ok(Self::Responses),
-            "chat" => Err(serde::de::Error::custom(CHAT_WIRE_API_REMOVED_ERROR)),
-            _ => Err(serde::de::Error::unknown_variant(&value, &["responses"])),
+            "chat" => Ok(Self::Chat),
+            "anthropic" => Ok(Self::Anthropic),
+            "gemini" => Ok(Self::Gemini),
+            _ => Err(serde::de::Error::unknown_variant(
+                &value,
+                &["responses", "chat", "anthropic", "gemini"],
+            )),
         }
     }
 }
@@ -427,15 +439,42 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
     }
 
     #[test]
-    fn test_deserialize_chat_wire_api_shows_helpful_error() {
+    fn test_deserialize_chat_wire_api_provider_toml() {
         let provider_toml = r#"
-name = "OpenAI using Chat Completions"
-base_url = "https://api.openai.com/v1"
-env_key = "OPENAI_API_KEY"
+name = "llama.cpp"
+base_url = "http://localhost:8080/v1"
 wire_api = "chat"
         "#;
 
-        let err = toml::from_str::<ModelProviderInfo>(provider_toml).unwrap_err();
-        assert!(err.to_string().contains(CHAT_WIRE_API_REMOVED_ERROR));
+        let provider: ModelProviderInfo = toml::from_str(provider_toml).unwrap();
+        assert_eq!(provider.wire_api, WireApi::Chat);
+
+        let err = toml::from_str::<ModelProviderInfo>(
+            &provider_toml.replace("\"chat\"", "\"completions\""),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown variant `completions`"));
+    }
+
+    #[test]
+    fn test_deserialize_anthropic_and_gemini_wire_api_provider_toml() {
+        let anthropic_toml = r#"
+name = "Anthropic"
+base_url = "https://api.anthropic.com/v1"
+env_key = "ANTHROPIC_API_KEY"
+wire_api = "anthropic"
+        "#;
+        let gemini_toml = r#"
+name = "Gemini"
+base_url = "https://generativelanguage.googleapis.com/v1beta"
+env_key = "GEMINI_API_KEY"
+wire_api = "gemini"
+        "#;
+
+        let anthropic: ModelProviderInfo = toml::from_str(anthropic_toml).unwrap();
+        let gemini: ModelProviderInfo = toml::from_str(gemini_toml).unwrap();
+
+        assert_eq!(anthropic.wire_api, WireApi::Anthropic);
+        assert_eq!(gemini.wire_api, WireApi::Gemini);
     }
 }
@@ -70,6 +70,7 @@ pub(crate) fn denied_network_policy_message(blocked: &BlockedRequest) -> Option<
         "not_allowed" => "domain is not on the allowlist for the current sandbox mode",
         "not_allowed_local" => "local/private network addresses are blocked by policy",
         "method_not_allowed" => "request method is blocked by the current network mode",
+        "denied_by_rule" => "request matches a deny rule in the network policy",
         "proxy_disabled" => "managed network proxy is disabled",
         _ => "request is blocked by network policy",
     };
@@ -224,6 +225,7 @@ mod tests {
             decision: Some("ask".to_string()),
             source: Some("decider".to_string()),
             port: Some(80),
+            matched_rule: None,
             timestamp: 0,
         };
         assert_eq!(denied_network_policy_message(&blocked), None);
@@ -242,6 +244,7 @@ mod tests {
             decision: Some("deny".to_string()),
             source: Some("baseline_policy".to_string()),
             port: Some(80),
+            matched_rule: None,
             timestamp: 0,
         };
         assert_eq!(
@@ -251,4 +254,28 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn denied_network_policy_message_for_rule_block_mentions_rule() {
+        let blocked = BlockedRequest {
+            host: "api.github.com".to_string(),
+            reason: "denied_by_rule".to_string(),
+            client: None,
+            method: Some("POST".to_string()),
+            mode: None,
+            protocol: "https".to_string(),
+            attempt_id: Some("attempt-1".to_string()),
+            decision: Some("deny".to_string()),
+            source: Some("baseline_policy".to_string()),
+            port: Some(443),
+            matched_rule: None,
+            timestamp: 0,
+        };
+        assert_eq!(
+            denied_network_policy_message(&blocked),
+            Some(
+                "Network access to \"api.github.com\" was blocked: request matches a deny rule in the network policy.".to_string()
+            )
+        );
+    }
 }
@@ -140,6 +140,9 @@ fn network_constraints_from_trusted_layers(
         if let Some(allow_local_binding) = partial.network.allow_local_binding {
             constraints.allow_local_binding = Some(allow_local_binding);
         }
+        if let Some(mitm) = partial.network.mitm {
+            constraints.mitm = Some(mitm);
+        }
     }
     Ok(constraints)
 }
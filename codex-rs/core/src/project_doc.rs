@@ -59,6 +59,21 @@ fn render_js_repl_instructions(config: &Config) -> Option<String> {
     Some(section)
 }
 
+fn render_py_repl_instructions(config: &Config) -> Option<String> {
+    if !config.features.enabled(Feature::PyRepl) {
+        return None;
+    }
+
+    let mut section = String::from("## Python REPL\n");
+    section.push_str("- Use `py_repl` for Python in a persistent kernel. Bindings and imports persist across cells until `py_repl_reset`; `codex.state` is a dict for values you want to keep explicitly.\n");
+    section.push_str("- `py_repl` is a freeform/custom tool. Direct `py_repl` calls must send raw Python tool input (optionally with first-line `# codex-py-repl: timeout_ms=15000`). Do not wrap code in JSON (for example `{\"code\":\"...\"}`), quotes, or markdown code fences.\n");
+    section.push_str("- The result has `stdout`, `stderr`, the `repr` of a trailing expression as `result`, and a traceback as `error` when the cell raises. Top-level `await` is supported.\n");
+    section.push_str("- Images are shared with the model automatically: a trailing expression with `_repr_png_`, open matplotlib figures, and files passed to `codex.show_image(path)`. Write scratch files under `codex.tmp_dir`.\n");
+    section.push_str("- A cell that exceeds its timeout resets the kernel, so all bindings are lost. Do not read from `sys.stdin`; it is empty.");
+
+    Some(section)
+}
+
 /// Combines `Config::instructions` and `AGENTS.md` (if present) into a single
 /// string of instructions.
 pub(crate) async fn get_user_instructions(
@@ -93,6 +108,13 @@ pub(crate) async fn get_user_instructions(
         output.push_str(&js_repl_section);
     }
 
+    if let Some(py_repl_section) = render_py_repl_instructions(config) {
+        if !output.is_empty() {
+            output.push_str("\n\n");
+        }
+        output.push_str(&py_repl_section);
+    }
+
     let skills_section = skills.and_then(render_skills_section);
     if let Some(skills_section) = skills_section {
         if !output.is_empty() {
@@ -434,6 +456,19 @@ mod tests {
         assert_eq!(res, expected);
     }
 
+    #[tokio::test]
+    async fn py_repl_instructions_are_appended_when_enabled() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let mut cfg = make_config(&tmp, 4096, None).await;
+        cfg.features.enable(Feature::PyRepl);
+
+        let res = get_user_instructions(&cfg, None)
+            .await
+            .expect("py_repl instructions expected");
+        let expected = "## Python REPL\n- Use `py_repl` for Python in a persistent kernel. Bindings and imports persist across cells until `py_repl_reset`; `codex.state` is a dict for values you want to keep explicitly.\n- `py_repl` is a freeform/custom tool. Direct `py_repl` calls must send raw Python tool input (optionally with first-line `# codex-py-repl: timeout_ms=15000`). Do not wrap code in JSON (for example `{\"code\":\"...\"}`), quotes, or markdown code fences.\n- The result has `stdout`, `stderr`, the `repr` of a trailing expression as `result`, and a traceback as `error` when the cell raises. Top-level `await` is supported.\n- Images are shared with the model automatically: a trailing expression with `_repr_png_`, open matplotlib figures, and files passed to `codex.show_image(path)`. Write scratch files under `codex.tmp_dir`.\n- A cell that exceeds its timeout resets the kernel, so all bindings are lost. Do not read from `sys.stdin`; it is empty.";
+        assert_eq!(res, expected);
+    }
+
     /// When both system instructions *and* a project doc are present the two
     /// should be concatenated with the separator.
     #[tokio::test]
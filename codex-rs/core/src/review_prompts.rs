@@ -2,12 +2,14 @@ use codex_git::merge_base_with_head;
 use codex_protocol::protocol::ReviewRequest;
 use codex_protocol::protocol::ReviewTarget;
 use std::path::Path;
+use std::path::PathBuf;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ResolvedReviewRequest {
     pub target: ReviewTarget,
     pub prompt: String,
     pub user_facing_hint: String,
+    pub paths: Vec<PathBuf>,
 }
 
 const UNCOMMITTED_PROMPT: &str = "Review the current code changes (staged, unstaged, and untracked files) and provide prioritized findings.";
@@ -19,20 +21,47 @@ const COMMIT_PROMPT_WITH_TITLE: &str = "Review the code changes introduced by co
 const COMMIT_PROMPT: &str =
     "Review the code changes introduced by commit {sha}. Provide prioritized, actionable findings.";
 
+const COMMIT_RANGE_PROMPT: &str = "Review the code changes introduced by the commits in {from}..{to}. Run `git log {from}..{to}` to list the commits and `git diff {from}..{to}` to inspect the combined changes. Provide prioritized, actionable findings.";
+
+const PATCH_FILE_PROMPT: &str = "Review the code changes in the patch file {path}. Read the patch to see what it changes; it may not be applied to the working tree, so use the current files only for context. Provide prioritized, actionable findings.";
+
+const PATHS_SUFFIX: &str = "Only review changes to files under these paths and ignore changes elsewhere: {paths}. For example, append `-- {pathArgs}` when running `git diff`.";
+
 pub fn resolve_review_request(
     request: ReviewRequest,
     cwd: &Path,
 ) -> anyhow::Result<ResolvedReviewRequest> {
     let target = request.target;
-    let prompt = review_prompt(&target, cwd)?;
+    let paths = request.paths;
+    let mut prompt = review_prompt(&target, cwd)?;
+    if !paths.is_empty() {
+        let display = paths
+            .iter()
+            .map(|path| format!("`{}`", path.display()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let path_args = paths
+            .iter()
+            .map(|path| path.to_string_lossy())
+            .collect::<Vec<_>>();
+        let path_args = shlex::try_join(path_args.iter().map(AsRef::as_ref))
+            .unwrap_or_else(|_| path_args.join(" "));
+        prompt.push_str("\n\n");
+        prompt.push_str(
+            &PATHS_SUFFIX
+                .replace("{paths}", &display)
+                .replace("{pathArgs}", &path_args),
+        );
+    }
     let user_facing_hint = request
         .user_facing_hint
-        .unwrap_or_else(|| user_facing_hint(&target));
+        .unwrap_or_else(|| user_facing_hint_for_paths(&target, &paths));
 
     Ok(ResolvedReviewRequest {
         target,
         prompt,
         user_facing_hint,
+        paths,
     })
 }
 
@@ -57,6 +86,16 @@ pub fn review_prompt(target: &ReviewTarget, cwd: &Path) -> anyhow::Result<String
                 Ok(COMMIT_PROMPT.replace("{sha}", sha))
             }
         }
+        ReviewTarget::CommitRange { from, to } => Ok(COMMIT_RANGE_PROMPT
+            .replace("{from}", from)
+            .replace("{to}", to)),
+        ReviewTarget::PatchFile { path } => {
+            let resolved = cwd.join(path);
+            if !resolved.is_file() {
+                anyhow::bail!("Patch file not found: {}", resolved.display());
+            }
+            Ok(PATCH_FILE_PROMPT.replace("{path}", &resolved.display().to_string()))
+        }
         ReviewTarget::Custom { instructions } => {
             let prompt = instructions.trim();
             if prompt.is_empty() {
@@ -79,15 +118,118 @@ pub fn user_facing_hint(target: &ReviewTarget) -> String {
                 format!("commit {short_sha}")
             }
         }
+        ReviewTarget::CommitRange { from, to } => {
+            let from: String = from.chars().take(7).collect();
+            let to: String = to.chars().take(7).collect();
+            format!("commits {from}..{to}")
+        }
+        ReviewTarget::PatchFile { path } => format!("patch {}", path.display()),
         ReviewTarget::Custom { instructions } => instructions.trim().to_string(),
     }
 }
 
+/// Like [`user_facing_hint`], noting the path filter when one is set.
+pub fn user_facing_hint_for_paths(target: &ReviewTarget, paths: &[PathBuf]) -> String {
+    let hint = user_facing_hint(target);
+    if paths.is_empty() {
+        return hint;
+    }
+    let paths = paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{hint} in {paths}")
+}
+
 impl From<ResolvedReviewRequest> for ReviewRequest {
     fn from(resolved: ResolvedReviewRequest) -> Self {
         ReviewRequest {
             target: resolved.target,
             user_facing_hint: Some(resolved.user_facing_hint),
+            paths: resolved.paths,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn commit_range_prompt_and_hint() {
+        let target = ReviewTarget::CommitRange {
+            from: "1234567890".to_string(),
+            to: "HEAD".to_string(),
+        };
+        let resolved = resolve_review_request(
+            ReviewRequest {
+                target: target.clone(),
+                user_facing_hint: None,
+                paths: Vec::new(),
+            },
+            Path::new("/repo"),
+        )
+        .expect("resolve commit range");
+
+        assert_eq!(
+            resolved,
+            ResolvedReviewRequest {
+                target,
+                prompt: "Review the code changes introduced by the commits in 1234567890..HEAD. Run `git log 1234567890..HEAD` to list the commits and `git diff 1234567890..HEAD` to inspect the combined changes. Provide prioritized, actionable findings.".to_string(),
+                user_facing_hint: "commits 1234567..HEAD".to_string(),
+                paths: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn patch_file_must_exist() {
+        let dir = TempDir::new().expect("tempdir");
+        std::fs::write(dir.path().join("change.patch"), "diff --git a/x b/x\n")
+            .expect("write patch");
+
+        let prompt = review_prompt(
+            &ReviewTarget::PatchFile {
+                path: PathBuf::from("change.patch"),
+            },
+            dir.path(),
+        )
+        .expect("existing patch file");
+        assert!(prompt.contains(&dir.path().join("change.patch").display().to_string()));
+
+        let missing = review_prompt(
+            &ReviewTarget::PatchFile {
+                path: PathBuf::from("missing.patch"),
+            },
+            dir.path(),
+        );
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn paths_filter_is_appended_to_prompt_and_hint() {
+        let resolved = resolve_review_request(
+            ReviewRequest {
+                target: ReviewTarget::UncommittedChanges,
+                user_facing_hint: None,
+                paths: vec![PathBuf::from("core/src"), PathBuf::from("docs/my notes.md")],
+            },
+            Path::new("/repo"),
+        )
+        .expect("resolve uncommitted");
+
+        assert_eq!(
+            resolved.prompt,
+            format!(
+                "{UNCOMMITTED_PROMPT}\n\nOnly review changes to files under these paths and ignore changes elsewhere: `core/src`, `docs/my notes.md`. For example, append `-- core/src 'docs/my notes.md'` when running `git diff`."
+            )
+        );
+        assert_eq!(
+            resolved.user_facing_hint,
+            "current changes in core/src, docs/my notes.md"
+        );
+    }
+}
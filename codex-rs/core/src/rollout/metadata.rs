@@ -90,11 +90,13 @@ pub(crate) fn builder_from_items(
     ))
 }
 
+/// Extract thread metadata from a rollout file, returning the loaded items too
+/// so callers can rebuild the thread's search index.
 pub(crate) async fn extract_metadata_from_rollout(
     rollout_path: &Path,
     default_provider: &str,
     otel: Option<&OtelManager>,
-) -> anyhow::Result<ExtractionOutcome> {
+) -> anyhow::Result<(ExtractionOutcome, Vec<RolloutItem>)> {
     let (items, _thread_id, parse_errors) =
         RolloutRecorder::load_rollout_items(rollout_path).await?;
     if items.is_empty() {
@@ -125,10 +127,13 @@ pub(crate) async fn extract_metadata_from_rollout(
             &[("stage", "extract_metadata_from_rollout")],
         );
     }
-    Ok(ExtractionOutcome {
-        metadata,
-        parse_errors,
-    })
+    Ok((
+        ExtractionOutcome {
+            metadata,
+            parse_errors,
+        },
+        items,
+    ))
 }
 
 pub(crate) async fn backfill_sessions(
@@ -259,7 +264,7 @@ pub(crate) async fn backfill_sessions(
             )
             .await
             {
-                Ok(outcome) => {
+                Ok((outcome, items)) => {
                     if outcome.parse_errors > 0
                         && let Some(otel) = otel
                     {
@@ -281,6 +286,19 @@ pub(crate) async fn backfill_sessions(
                         warn!("failed to upsert rollout {}: {err}", rollout.path.display());
                     } else {
                         stats.upserted = stats.upserted.saturating_add(1);
+                        if let Err(err) = runtime.reindex_thread_search(metadata.id, &items).await {
+                            if let Some(otel) = otel {
+                                otel.counter(
+                                    DB_ERROR_METRIC,
+                                    1,
+                                    &[("stage", "backfill_thread_search")],
+                                );
+                            }
+                            warn!(
+                                "failed to index rollout for search {}: {err}",
+                                rollout.path.display()
+                            );
+                        }
                         if let Ok(meta_line) =
                             rollout::list::read_session_meta_line(&rollout.path).await
                         {
@@ -387,6 +405,62 @@ pub(crate) async fn backfill_sessions(
     }
 }
 
+/// Indexes threads the search index has never seen, such as sessions recorded
+/// before it existed. Unlike [`backfill_sessions`] this only reads the rollouts
+/// of those threads and leaves their metadata alone.
+pub(crate) async fn backfill_thread_search(
+    runtime: &codex_state::StateRuntime,
+    otel: Option<&OtelManager>,
+) {
+    let mut after = None;
+    let mut indexed = 0usize;
+    loop {
+        let threads = match runtime
+            .threads_missing_search_index(after, BACKFILL_BATCH_SIZE)
+            .await
+        {
+            Ok(threads) => threads,
+            Err(err) => {
+                warn!("failed to list threads missing from the search index: {err}");
+                if let Some(otel) = otel {
+                    otel.counter(DB_ERROR_METRIC, 1, &[("stage", "backfill_thread_search")]);
+                }
+                return;
+            }
+        };
+        let Some((last_id, _)) = threads.last() else {
+            break;
+        };
+        after = Some(*last_id);
+        for (thread_id, rollout_path) in threads {
+            let items = match RolloutRecorder::load_rollout_items(&rollout_path).await {
+                Ok((items, _, _)) => items,
+                Err(err) => {
+                    warn!(
+                        "failed to read rollout for search {}: {err}",
+                        rollout_path.display()
+                    );
+                    continue;
+                }
+            };
+            if let Err(err) = runtime.reindex_thread_search(thread_id, &items).await {
+                if let Some(otel) = otel {
+                    otel.counter(DB_ERROR_METRIC, 1, &[("stage", "backfill_thread_search")]);
+                }
+                warn!(
+                    "failed to index rollout for search {}: {err}",
+                    rollout_path.display()
+                );
+                continue;
+            }
+            indexed = indexed.saturating_add(1);
+        }
+    }
+    if indexed > 0 {
+        info!("state db search backfill indexed={indexed}");
+    }
+}
+
 #[derive(Debug, Clone)]
 struct BackfillRolloutPath {
     watermark: String,
@@ -480,11 +554,13 @@ mod tests {
     use chrono::Utc;
     use codex_protocol::ThreadId;
     use codex_protocol::protocol::CompactedItem;
+    use codex_protocol::protocol::EventMsg;
     use codex_protocol::protocol::RolloutItem;
     use codex_protocol::protocol::RolloutLine;
     use codex_protocol::protocol::SessionMeta;
     use codex_protocol::protocol::SessionMetaLine;
     use codex_protocol::protocol::SessionSource;
+    use codex_protocol::protocol::UserMessageEvent;
     use codex_state::BackfillStatus;
     use codex_state::ThreadMetadataBuilder;
     use pretty_assertions::assert_eq;
@@ -528,7 +604,7 @@ mod tests {
         let mut file = File::create(&path).expect("create rollout");
         writeln!(file, "{json}").expect("write rollout");
 
-        let outcome = extract_metadata_from_rollout(&path, "openai", None)
+        let (outcome, _items) = extract_metadata_from_rollout(&path, "openai", None)
             .await
             .expect("extract");
 
@@ -642,6 +718,135 @@ mod tests {
         assert!(state.last_success_at.is_some());
     }
 
+    #[tokio::test]
+    async fn backfill_sessions_indexes_rollout_text_for_search() {
+        let dir = tempdir().expect("tempdir");
+        let codex_home = dir.path().to_path_buf();
+        let thread_uuid = Uuid::new_v4();
+        let path = write_rollout_in_sessions(
+            codex_home.as_path(),
+            "2026-01-27T12-34-56",
+            "2026-01-27T12:34:56Z",
+            thread_uuid,
+        );
+        let user_line = RolloutLine {
+            timestamp: "2026-01-27T12:35:00Z".to_string(),
+            item: RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                message: "fix the flaky migration".to_string(),
+                images: None,
+                local_images: Vec::new(),
+                text_elements: Vec::new(),
+            })),
+        };
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .expect("open rollout");
+        writeln!(
+            file,
+            "{}",
+            serde_json::to_string(&user_line).expect("serialize rollout")
+        )
+        .expect("write rollout");
+
+        let runtime =
+            codex_state::StateRuntime::init(codex_home.clone(), "test-provider".to_string(), None)
+                .await
+                .expect("initialize runtime");
+        let mut config = crate::config::test_config();
+        config.codex_home = codex_home.clone();
+        config.model_provider_id = "test-provider".to_string();
+        backfill_sessions(runtime.as_ref(), &config, None).await;
+
+        let hits = runtime
+            .search_threads("flaky", 10, &[], None, false)
+            .await
+            .expect("search threads");
+        assert_eq!(
+            hits.into_iter()
+                .map(|hit| (hit.metadata.id.to_string(), hit.turn_index, hit.snippet))
+                .collect::<Vec<_>>(),
+            vec![(
+                thread_uuid.to_string(),
+                0,
+                "fix the flaky migration".to_string()
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn backfill_thread_search_indexes_threads_missing_from_the_index() {
+        let dir = tempdir().expect("tempdir");
+        let codex_home = dir.path().to_path_buf();
+        let thread_uuid = Uuid::new_v4();
+        let path = write_rollout_in_sessions(
+            codex_home.as_path(),
+            "2026-01-27T12-34-56",
+            "2026-01-27T12:34:56Z",
+            thread_uuid,
+        );
+        let user_line = RolloutLine {
+            timestamp: "2026-01-27T12:35:00Z".to_string(),
+            item: RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                message: "fix the flaky migration".to_string(),
+                images: None,
+                local_images: Vec::new(),
+                text_elements: Vec::new(),
+            })),
+        };
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .expect("open rollout");
+        writeln!(
+            file,
+            "{}",
+            serde_json::to_string(&user_line).expect("serialize rollout")
+        )
+        .expect("write rollout");
+
+        let runtime =
+            codex_state::StateRuntime::init(codex_home.clone(), "test-provider".to_string(), None)
+                .await
+                .expect("initialize runtime");
+        // Metadata recorded before the search index existed.
+        let (outcome, _) = extract_metadata_from_rollout(&path, "test-provider", None)
+            .await
+            .expect("extract metadata");
+        runtime
+            .upsert_thread(&outcome.metadata)
+            .await
+            .expect("upsert thread");
+        assert_eq!(
+            runtime
+                .search_threads("flaky", 10, &[], None, false)
+                .await
+                .expect("search threads")
+                .len(),
+            0
+        );
+
+        backfill_thread_search(runtime.as_ref(), None).await;
+
+        let hits = runtime
+            .search_threads("flaky", 10, &[], None, false)
+            .await
+            .expect("search threads");
+        assert_eq!(
+            hits.into_iter()
+                .map(|hit| hit.metadata.id.to_string())
+                .collect::<Vec<_>>(),
+            vec![thread_uuid.to_string()]
+        );
+        assert_eq!(
+            runtime
+                .threads_missing_search_index(None, 10)
+                .await
+                .expect("list missing threads"),
+            Vec::new()
+        );
+    }
+
     fn write_rollout_in_sessions(
         codex_home: &Path,
         filename_ts: &str,
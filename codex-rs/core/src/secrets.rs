@@ -0,0 +1,315 @@
+//! Session-level access to the user's secret store.
+//!
+//! Config values such as `shell_environment_policy.set` and MCP server
+//! `env`/`http_headers` may embed `${secret:NAME}` references. They are
+//! resolved when the process or connection is created, never when the config
+//! is loaded, and every resolved value is remembered so it can be redacted
+//! from tool output before it is sent to the model, and from everything
+//! written to the rollout.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::RolloutItem;
+use codex_secrets::SecretRedactor;
+use codex_secrets::SecretsBackendKind;
+use codex_secrets::SecretsManager;
+use codex_secrets::contains_secret_reference;
+use codex_secrets::environment_id_from_cwd;
+use codex_secrets::resolve_secret_references;
+use serde_json::Value;
+use tracing::warn;
+
+#[derive(Clone)]
+pub struct SessionSecrets {
+    manager: SecretsManager,
+    redactor: SecretRedactor,
+}
+
+impl SessionSecrets {
+    pub fn new(codex_home: PathBuf) -> Self {
+        Self::from_manager(SecretsManager::new(codex_home, SecretsBackendKind::Local))
+    }
+
+    pub fn from_manager(manager: SecretsManager) -> Self {
+        Self {
+            manager,
+            redactor: SecretRedactor::default(),
+        }
+    }
+
+    /// Resolves every `${secret:NAME}` reference in `value`, preferring the
+    /// secrets of the environment `cwd` belongs to over global ones.
+    pub fn resolve(&self, value: &str, cwd: &Path) -> Result<String> {
+        if !contains_secret_reference(value) {
+            return Ok(value.to_string());
+        }
+        let environment_id = environment_id_from_cwd(cwd);
+        resolve_secret_references(value, |name| {
+            let secret = self
+                .manager
+                .get_for_environment(&environment_id, name)?
+                .with_context(|| format!("secret `{name}` is not set"))?;
+            self.redactor.register(name, &secret);
+            Ok(secret)
+        })
+    }
+
+    /// Resolves the values of `map`, reporting the key whose value failed.
+    pub fn resolve_map(
+        &self,
+        map: HashMap<String, String>,
+        cwd: &Path,
+    ) -> Result<HashMap<String, String>> {
+        map.into_iter()
+            .map(|(key, value)| {
+                let value = self
+                    .resolve(&value, cwd)
+                    .with_context(|| format!("failed to resolve `{key}`"))?;
+                Ok((key, value))
+            })
+            .collect()
+    }
+
+    pub fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        self.redactor.redact(text)
+    }
+
+    /// Returns `items` with resolved secret values removed from tool outputs.
+    pub(crate) fn redact_response_items<'a>(
+        &self,
+        items: &'a [ResponseItem],
+    ) -> Cow<'a, [ResponseItem]> {
+        if self.redactor.is_empty() {
+            return Cow::Borrowed(items);
+        }
+        Cow::Owned(
+            items
+                .iter()
+                .map(|item| self.redact_response_item(item))
+                .collect(),
+        )
+    }
+
+    fn redact_response_item(&self, item: &ResponseItem) -> ResponseItem {
+        let mut item = item.clone();
+        match &mut item {
+            ResponseItem::FunctionCallOutput { output, .. } => match &mut output.body {
+                FunctionCallOutputBody::Text(text) => self.redact_in_place(text),
+                FunctionCallOutputBody::ContentItems(content_items) => {
+                    for content_item in content_items {
+                        if let FunctionCallOutputContentItem::InputText { text } = content_item {
+                            self.redact_in_place(text);
+                        }
+                    }
+                }
+            },
+            ResponseItem::CustomToolCallOutput { output, .. } => self.redact_in_place(output),
+            _ => {}
+        }
+        item
+    }
+
+    /// Returns `items` with resolved secret values removed from every string
+    /// they carry: command output, MCP results, agent messages, patches and
+    /// any payload added later. An item that cannot be redacted is dropped
+    /// rather than persisted as is.
+    pub(crate) fn redact_rollout_items<'a>(
+        &self,
+        items: &'a [RolloutItem],
+    ) -> Cow<'a, [RolloutItem]> {
+        if self.redactor.is_empty() {
+            return Cow::Borrowed(items);
+        }
+        Cow::Owned(
+            items
+                .iter()
+                .filter_map(|item| self.redact_rollout_item(item))
+                .collect(),
+        )
+    }
+
+    /// Redacts through the item's JSON form so no payload type has to be
+    /// listed here; the rollout stores that same form.
+    fn redact_rollout_item(&self, item: &RolloutItem) -> Option<RolloutItem> {
+        let mut value = match serde_json::to_value(item) {
+            Ok(value) => value,
+            Err(err) => {
+                warn!("dropping rollout item that could not be serialized for redaction: {err}");
+                return None;
+            }
+        };
+        if !self.redact_json(&mut value) {
+            return Some(item.clone());
+        }
+        match serde_json::from_value(value) {
+            Ok(item) => Some(item),
+            Err(err) => {
+                warn!("dropping rollout item that could not be rebuilt after redaction: {err}");
+                None
+            }
+        }
+    }
+
+    /// Returns whether any string in `value` was redacted.
+    fn redact_json(&self, value: &mut Value) -> bool {
+        match value {
+            Value::String(text) => match self.redactor.redact(text) {
+                Cow::Owned(redacted) => {
+                    *text = redacted;
+                    true
+                }
+                Cow::Borrowed(_) => false,
+            },
+            Value::Array(values) => values
+                .iter_mut()
+                .fold(false, |redacted, value| self.redact_json(value) || redacted),
+            Value::Object(map) => map
+                .values_mut()
+                .fold(false, |redacted, value| self.redact_json(value) || redacted),
+            Value::Null | Value::Bool(_) | Value::Number(_) => false,
+        }
+    }
+
+    fn redact_in_place(&self, text: &mut String) {
+        if let Cow::Owned(redacted) = self.redactor.redact(text) {
+            *text = redacted;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_keyring_store::tests::MockKeyringStore;
+    use codex_protocol::mcp::CallToolResult;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::EventMsg;
+    use codex_protocol::protocol::McpInvocation;
+    use codex_protocol::protocol::McpToolCallEndEvent;
+    use codex_secrets::SecretName;
+    use codex_secrets::SecretScope;
+    use pretty_assertions::assert_eq;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn session_secrets(codex_home: &Path) -> (SessionSecrets, SecretsManager) {
+        let manager = SecretsManager::new_with_keyring_store(
+            codex_home.to_path_buf(),
+            SecretsBackendKind::Local,
+            Arc::new(MockKeyringStore::default()),
+        );
+        (SessionSecrets::from_manager(manager.clone()), manager)
+    }
+
+    #[test]
+    fn resolved_values_are_redacted_from_tool_output() -> Result<()> {
+        let codex_home = tempfile::tempdir()?;
+        let (secrets, manager) = session_secrets(codex_home.path());
+        manager.set(
+            &SecretScope::Global,
+            &SecretName::new("API_TOKEN")?,
+            "tok-123",
+        )?;
+
+        let resolved = secrets.resolve("Bearer ${secret:API_TOKEN}", codex_home.path())?;
+        assert_eq!(resolved, "Bearer tok-123");
+
+        let items = vec![ResponseItem::FunctionCallOutput {
+            call_id: "call-1".to_string(),
+            output: FunctionCallOutputPayload::from_text("echo: tok-123".to_string()),
+        }];
+        assert_eq!(
+            secrets.redact_response_items(&items).into_owned(),
+            vec![ResponseItem::FunctionCallOutput {
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload::from_text(
+                    "echo: [REDACTED:API_TOKEN]".to_string()
+                ),
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn resolved_values_are_redacted_from_persisted_events() -> Result<()> {
+        let codex_home = tempfile::tempdir()?;
+        let (secrets, manager) = session_secrets(codex_home.path());
+        manager.set(
+            &SecretScope::Global,
+            &SecretName::new("API_TOKEN")?,
+            "tok-123",
+        )?;
+        secrets.resolve("${secret:API_TOKEN}", codex_home.path())?;
+
+        let mcp_end = |text: &str| {
+            RolloutItem::EventMsg(EventMsg::McpToolCallEnd(McpToolCallEndEvent {
+                call_id: "call-1".to_string(),
+                invocation: McpInvocation {
+                    server: "docs".to_string(),
+                    tool: "whoami".to_string(),
+                    arguments: None,
+                },
+                duration: Duration::from_millis(5),
+                result: Ok(CallToolResult {
+                    content: vec![serde_json::json!({
+                        "type": "text",
+                        "text": text,
+                    })],
+                    structured_content: Some(serde_json::json!({ "token": text })),
+                    is_error: Some(false),
+                    meta: None,
+                }),
+            }))
+        };
+        let agent_message = |message: &str| {
+            RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+                message: message.to_string(),
+            }))
+        };
+
+        let items = vec![
+            mcp_end("token is tok-123"),
+            agent_message("your token is tok-123"),
+            agent_message("nothing to hide"),
+        ];
+        let expected = vec![
+            mcp_end("token is [REDACTED:API_TOKEN]"),
+            agent_message("your token is [REDACTED:API_TOKEN]"),
+            agent_message("nothing to hide"),
+        ];
+        // `RolloutItem` has no `PartialEq`; compare the persisted JSON instead.
+        assert_eq!(
+            serde_json::to_value(secrets.redact_rollout_items(&items))?,
+            serde_json::to_value(expected)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn missing_secret_names_the_key() -> Result<()> {
+        let codex_home = tempfile::tempdir()?;
+        let (secrets, _manager) = session_secrets(codex_home.path());
+
+        let err = secrets
+            .resolve_map(
+                HashMap::from([("TOKEN".to_string(), "${secret:MISSING}".to_string())]),
+                codex_home.path(),
+            )
+            .expect_err("missing secret should fail");
+
+        assert_eq!(
+            format!("{err:#}"),
+            "failed to resolve `TOKEN`: secret `MISSING` is not set"
+        );
+        Ok(())
+    }
+}
@@ -15,8 +15,12 @@ use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::SessionSource;
 use codex_state::DB_METRIC_COMPARE_ERROR;
 pub use codex_state::LogEntry;
+pub use codex_state::NetworkRequestQuery;
+pub use codex_state::NetworkRequestRow;
 use codex_state::STATE_DB_VERSION;
 use codex_state::ThreadMetadataBuilder;
+pub use codex_state::ThreadSearchHit;
+pub use codex_state::ThreadSearchKind;
 use serde_json::Value;
 use std::path::Path;
 use std::path::PathBuf;
@@ -65,15 +69,16 @@ pub(crate) async fn init_if_enabled(
             return None;
         }
     };
-    if backfill_state.status != codex_state::BackfillStatus::Complete {
-        let runtime_for_backfill = runtime.clone();
-        let config = config.clone();
-        let otel = otel.cloned();
-        tokio::spawn(async move {
+    let runtime_for_backfill = runtime.clone();
+    let config = config.clone();
+    let otel = otel.cloned();
+    tokio::spawn(async move {
+        if backfill_state.status != codex_state::BackfillStatus::Complete {
             metadata::backfill_sessions(runtime_for_backfill.as_ref(), &config, otel.as_ref())
                 .await;
-        });
-    }
+        }
+        metadata::backfill_thread_search(runtime_for_backfill.as_ref(), otel.as_ref()).await;
+    });
     Some(runtime)
 }
 
@@ -288,6 +293,55 @@ pub async fn list_threads_db(
     }
 }
 
+/// Full-text search over past threads using SQLite, best match per thread first.
+pub async fn search_threads_db(
+    context: Option<&codex_state::StateRuntime>,
+    query: &str,
+    limit: usize,
+    allowed_sources: &[SessionSource],
+    model_providers: Option<&[String]>,
+    archived: bool,
+) -> Option<Vec<ThreadSearchHit>> {
+    let ctx = context?;
+    let allowed_sources: Vec<String> = allowed_sources
+        .iter()
+        .map(|value| match serde_json::to_value(value) {
+            Ok(Value::String(s)) => s,
+            Ok(other) => other.to_string(),
+            Err(_) => String::new(),
+        })
+        .collect();
+    match ctx
+        .search_threads(
+            query,
+            limit,
+            allowed_sources.as_slice(),
+            model_providers,
+            archived,
+        )
+        .await
+    {
+        Ok(hits) => {
+            let mut valid_hits = Vec::with_capacity(hits.len());
+            for hit in hits {
+                if tokio::fs::try_exists(&hit.metadata.rollout_path)
+                    .await
+                    .unwrap_or(false)
+                {
+                    valid_hits.push(hit);
+                } else {
+                    record_discrepancy("search_threads_db", "stale_db_path_dropped");
+                }
+            }
+            Some(valid_hits)
+        }
+        Err(err) => {
+            warn!("state db search_threads failed: {err}");
+            None
+        }
+    }
+}
+
 /// Look up the rollout path for a thread id using SQLite.
 pub async fn find_rollout_path_by_id(
     context: Option<&codex_state::StateRuntime>,
@@ -359,9 +413,9 @@ pub async fn reconcile_rollout(
         .await;
         return;
     }
-    let outcome =
+    let (outcome, items) =
         match metadata::extract_metadata_from_rollout(rollout_path, default_provider, None).await {
-            Ok(outcome) => outcome,
+            Ok(extracted) => extracted,
             Err(err) => {
                 warn!(
                     "state db reconcile_rollout extraction failed {}: {err}",
@@ -388,6 +442,12 @@ pub async fn reconcile_rollout(
         );
         return;
     }
+    if let Err(err) = ctx.reindex_thread_search(metadata.id, &items).await {
+        warn!(
+            "state db reconcile_rollout search index failed {}: {err}",
+            rollout_path.display()
+        );
+    }
     if let Ok(meta_line) = crate::rollout::list::read_session_meta_line(rollout_path).await {
         persist_dynamic_tools(
             Some(ctx),
@@ -5,6 +5,7 @@ use super::SessionTaskContext;
 use crate::codex::TurnContext;
 use crate::state::TaskKind;
 use async_trait::async_trait;
+use codex_hooks::HookCompactionTrigger;
 use codex_protocol::user_input::UserInput;
 use tokio_util::sync::CancellationToken;
 
@@ -25,6 +26,9 @@ impl SessionTask for CompactTask {
         _cancellation_token: CancellationToken,
     ) -> Option<String> {
         let session = session.clone_session();
+        session
+            .dispatch_before_compaction_hook(&ctx, HookCompactionTrigger::Manual)
+            .await;
         if crate::compact::should_use_remote_compact_task(&ctx.provider) {
             let _ = session.services.otel_manager.counter(
                 "codex.task.compact",
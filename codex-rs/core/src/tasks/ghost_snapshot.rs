@@ -6,13 +6,21 @@ use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
 use async_trait::async_trait;
 use codex_git::CreateGhostCommitOptions;
+use codex_git::FileSnapshot;
+use codex_git::FileSnapshotReport;
+use codex_git::GhostSnapshotConfig;
 use codex_git::GhostSnapshotReport;
 use codex_git::GitToolingError;
 use codex_git::create_ghost_commit_with_report;
+use codex_git::prune_file_snapshots;
+use codex_protocol::ThreadId;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::user_input::UserInput;
 use codex_utils_readiness::Readiness;
 use codex_utils_readiness::Token;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::oneshot;
@@ -26,6 +34,12 @@ pub(crate) struct GhostSnapshotTask {
 
 const SNAPSHOT_WARNING_THRESHOLD: Duration = Duration::from_secs(240);
 
+const FILE_SNAPSHOTS_SUBDIR: &str = "file_snapshots";
+
+/// How long file snapshots are kept for undo and checkpoints. Older ones are
+/// pruned whenever a turn starts a new file snapshot.
+const FILE_SNAPSHOT_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
 #[async_trait]
 impl SessionTask for GhostSnapshotTask {
     fn kind(&self) -> TaskKind {
@@ -113,10 +127,50 @@ impl SessionTask for GhostSnapshotTask {
                             info!("ghost commit captured: {}", ghost_commit.id());
                         }
                         Ok(Err(err)) => match err {
-                            GitToolingError::NotAGitRepository { .. } => info!(
-                                sub_id = ctx_for_task.sub_id.as_str(),
-                                "skipping ghost snapshot because current directory is not a Git repository"
-                            ),
+                            GitToolingError::NotAGitRepository { .. } => {
+                                info!(
+                                    sub_id = ctx_for_task.sub_id.as_str(),
+                                    "current directory is not a Git repository; falling back to a file snapshot"
+                                );
+                                let snapshots_root =
+                                    ctx_for_task.config.codex_home.join(FILE_SNAPSHOTS_SUBDIR);
+                                let snapshot_dir = thread_file_snapshots_dir(
+                                    &ctx_for_task.config.codex_home,
+                                    session.session.conversation_id,
+                                )
+                                .join(&ctx_for_task.sub_id);
+                                let sub_id = ctx_for_task.sub_id.clone();
+                                match tokio::task::spawn_blocking(move || {
+                                    if let Err(err) =
+                                        prune_file_snapshots(&snapshots_root, FILE_SNAPSHOT_RETENTION)
+                                    {
+                                        warn!("failed to prune file snapshots: {err}");
+                                    }
+                                    FileSnapshot::create(&snapshot_dir, &sub_id, &ghost_snapshot)
+                                })
+                                .await
+                                {
+                                    Ok(Ok((file_snapshot, ghost_commit))) => {
+                                        let _ = ctx_for_task
+                                            .file_snapshot
+                                            .set(Arc::new(file_snapshot));
+                                        session
+                                            .session
+                                            .record_conversation_items(&ctx, &[ResponseItem::GhostSnapshot {
+                                                ghost_commit,
+                                            }])
+                                            .await;
+                                    }
+                                    Ok(Err(err)) => warn!(
+                                        sub_id = ctx_for_task.sub_id.as_str(),
+                                        "failed to start file snapshot: {err}"
+                                    ),
+                                    Err(err) => warn!(
+                                        sub_id = ctx_for_task.sub_id.as_str(),
+                                        "file snapshot task panicked: {err}"
+                                    ),
+                                }
+                            }
                             _ => {
                                 warn!(
                                     sub_id = ctx_for_task.sub_id.as_str(),
@@ -162,6 +216,94 @@ impl GhostSnapshotTask {
     }
 }
 
+/// Directory holding the file snapshots of `thread_id`. File snapshots for
+/// workspaces outside Git live under `CODEX_HOME/file_snapshots/<thread id>/<turn id>`.
+pub(crate) fn thread_file_snapshots_dir(codex_home: &Path, thread_id: ThreadId) -> PathBuf {
+    codex_home
+        .join(FILE_SNAPSHOTS_SUBDIR)
+        .join(thread_id.to_string())
+}
+
+/// Delete the file snapshots of `thread_id`.
+pub fn remove_thread_file_snapshots(codex_home: &Path, thread_id: ThreadId) -> io::Result<()> {
+    match std::fs::remove_dir_all(thread_file_snapshots_dir(codex_home, thread_id)) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+/// Copy the pre-write contents of `paths` into the turn's file snapshot, if
+/// the turn has one. Returns a warning when some files could not be captured.
+pub(crate) async fn capture_file_snapshot(
+    ctx: &TurnContext,
+    paths: Vec<PathBuf>,
+) -> Option<String> {
+    let file_snapshot = ctx.file_snapshot.get()?.clone();
+    let result = tokio::task::spawn_blocking(move || file_snapshot.capture(&paths)).await;
+    match result {
+        Ok(Ok(_)) if ctx.ghost_snapshot.disable_warnings => None,
+        Ok(Ok(report)) => format_file_snapshot_warning(&ctx.ghost_snapshot, &report),
+        Ok(Err(err)) => {
+            warn!(
+                sub_id = ctx.sub_id.as_str(),
+                "failed to capture file snapshot: {err}"
+            );
+            Some(format!(
+                "Failed to snapshot files before editing; undo may not restore them: {err}"
+            ))
+        }
+        Err(err) => {
+            warn!(
+                sub_id = ctx.sub_id.as_str(),
+                "file snapshot capture panicked: {err}"
+            );
+            None
+        }
+    }
+}
+
+fn format_file_snapshot_warning(
+    config: &GhostSnapshotConfig,
+    report: &FileSnapshotReport,
+) -> Option<String> {
+    if report.is_empty() {
+        return None;
+    }
+    let mut parts = Vec::new();
+    if !report.too_large.is_empty() {
+        let threshold = config.ignore_large_untracked_files.unwrap_or_default();
+        parts.push(format!(
+            "files larger than {} were not captured: {}",
+            format_bytes(threshold),
+            format_paths(&report.too_large)
+        ));
+    }
+    if !report.over_file_limit.is_empty() {
+        let threshold = config.ignore_large_untracked_dirs.unwrap_or_default();
+        parts.push(format!(
+            "the snapshot already holds {threshold} files, so these were not captured: {}",
+            format_paths(&report.over_file_limit)
+        ));
+    }
+    Some(format!(
+        "File snapshot is incomplete; {}. Undo will leave these files as they are. Adjust `ghost_snapshot.ignore_large_untracked_files` or `ghost_snapshot.ignore_large_untracked_dirs` to change this behavior.",
+        parts.join("; ")
+    ))
+}
+
+fn format_paths(paths: &[PathBuf]) -> String {
+    const MAX_FILES: usize = 3;
+    let mut parts: Vec<String> = paths
+        .iter()
+        .take(MAX_FILES)
+        .map(|path| path.display().to_string())
+        .collect();
+    if paths.len() > MAX_FILES {
+        parts.push(format!("{} more", paths.len() - MAX_FILES));
+    }
+    parts.join(", ")
+}
+
 fn format_snapshot_warnings(
     ignore_large_untracked_files: Option<i64>,
     ignore_large_untracked_dirs: Option<i64>,
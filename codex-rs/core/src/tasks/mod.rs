@@ -38,6 +38,8 @@ use codex_protocol::user_input::UserInput;
 
 pub(crate) use compact::CompactTask;
 pub(crate) use ghost_snapshot::GhostSnapshotTask;
+pub(crate) use ghost_snapshot::capture_file_snapshot;
+pub use ghost_snapshot::remove_thread_file_snapshots;
 pub(crate) use regular::RegularTask;
 pub(crate) use review::ReviewTask;
 pub(crate) use undo::UndoTask;
@@ -7,8 +7,10 @@ use crate::protocol::UndoStartedEvent;
 use crate::state::TaskKind;
 use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
+use crate::tasks::ghost_snapshot::thread_file_snapshots_dir;
 use async_trait::async_trait;
 use codex_git::RestoreGhostCommitOptions;
+use codex_git::restore_file_snapshot;
 use codex_git::restore_ghost_commit_with_options;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::user_input::UserInput;
@@ -92,20 +94,35 @@ impl SessionTask for UndoTask {
         let commit_id = ghost_commit.id().to_string();
         let repo_path = ctx.cwd.clone();
         let ghost_snapshot = ctx.ghost_snapshot.clone();
+        let snapshots_dir = thread_file_snapshots_dir(&ctx.config.codex_home, sess.conversation_id);
         let restore_result = tokio::task::spawn_blocking(move || {
+            if let Some(dir) = ghost_commit.file_snapshot_dir() {
+                return restore_file_snapshot(&snapshots_dir, dir).map(|report| report.too_large);
+            }
             let options = RestoreGhostCommitOptions::new(&repo_path).ghost_snapshot(ghost_snapshot);
-            restore_ghost_commit_with_options(&options, &ghost_commit)
+            restore_ghost_commit_with_options(&options, &ghost_commit).map(|()| Vec::new())
         })
         .await;
 
         match restore_result {
-            Ok(Ok(())) => {
+            Ok(Ok(skipped)) => {
                 items.remove(idx);
                 sess.replace_history(items).await;
                 let short_id: String = commit_id.chars().take(7).collect();
                 info!(commit_id = commit_id, "Undo restored ghost snapshot");
                 completed.success = true;
-                completed.message = Some(format!("Undo restored snapshot {short_id}."));
+                let mut message = format!("Undo restored snapshot {short_id}.");
+                if !skipped.is_empty() {
+                    let skipped = skipped
+                        .iter()
+                        .map(|path| path.display().to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    message.push_str(&format!(
+                        " Files too large to snapshot were left unchanged: {skipped}."
+                    ));
+                }
+                completed.message = Some(message);
             }
             Ok(Err(err)) => {
                 let message = format!("Failed to restore snapshot {commit_id}: {err}");
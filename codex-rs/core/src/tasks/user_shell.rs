@@ -15,7 +15,7 @@ use crate::exec::SandboxType;
 use crate::exec::StdoutStream;
 use crate::exec::StreamOutput;
 use crate::exec::execute_exec_env;
-use crate::exec_env::create_env;
+use crate::exec_env::create_env_with_secrets;
 use crate::parse_command::parse_command;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandBeginEvent;
@@ -142,13 +142,17 @@ pub(crate) async fn execute_user_shell_command(
         )
         .await;
 
+    let env = create_env_with_secrets(
+        &turn_context.shell_environment_policy,
+        Some(session.conversation_id),
+        &session.services.secrets,
+        &turn_context.cwd,
+    )
+    .await;
     let exec_env = ExecRequest {
         command: exec_command.clone(),
         cwd: cwd.clone(),
-        env: create_env(
-            &turn_context.shell_environment_policy,
-            Some(session.conversation_id),
-        ),
+        env,
         network: turn_context.network.clone(),
         network_attempt_id: None,
         // TODO(zhao-oai): Now that we have ExecExpiration::Cancellation, we
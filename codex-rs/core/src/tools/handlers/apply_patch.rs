@@ -2,6 +2,7 @@ use codex_protocol::models::FunctionCallOutputBody;
 use std::collections::BTreeMap;
 use std::path::Path;
 
+use crate::CODEX_APPLY_PATCH_ARG1;
 use crate::apply_patch;
 use crate::apply_patch::InternalApplyPatchInvocation;
 use crate::apply_patch::convert_apply_patch_to_protocol;
@@ -12,6 +13,9 @@ use crate::client_common::tools::ToolSpec;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
+use crate::protocol::EventMsg;
+use crate::protocol::WarningEvent;
+use crate::tasks::capture_file_snapshot;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
@@ -60,6 +64,24 @@ fn to_abs_path(cwd: &Path, path: &Path) -> Option<AbsolutePathBuf> {
     AbsolutePathBuf::resolve_path_against_base(path, cwd).ok()
 }
 
+/// Outside Git, undo relies on copies of the files taken before the patch
+/// touches them.
+async fn snapshot_files_before_patch(
+    session: &Session,
+    turn: &TurnContext,
+    file_paths: &[AbsolutePathBuf],
+) {
+    let paths = file_paths
+        .iter()
+        .map(AbsolutePathBuf::to_path_buf)
+        .collect();
+    if let Some(message) = capture_file_snapshot(turn, paths).await {
+        session
+            .send_event(turn, EventMsg::Warning(WarningEvent { message }))
+            .await;
+    }
+}
+
 #[async_trait]
 impl ToolHandler for ApplyPatchHandler {
     fn kind(&self) -> ToolKind {
@@ -117,6 +139,8 @@ impl ToolHandler for ApplyPatchHandler {
                     InternalApplyPatchInvocation::DelegateToExec(apply) => {
                         let changes = convert_apply_patch_to_protocol(&apply.action);
                         let file_paths = file_paths_for_action(&apply.action);
+                        snapshot_files_before_patch(session.as_ref(), turn.as_ref(), &file_paths)
+                            .await;
                         let emitter =
                             ToolEmitter::apply_patch(changes.clone(), apply.auto_approved);
                         let event_ctx = ToolEventCtx::new(
@@ -194,6 +218,13 @@ pub(crate) async fn intercept_apply_patch(
     call_id: &str,
     tool_name: &str,
 ) -> Result<Option<ToolOutput>, FunctionCallError> {
+    // A patch run through a shell is still an apply_patch call, however the
+    // command reaches the `apply_patch` executables on PATH.
+    if !turn.tools_config.is_tool_allowed("apply_patch") && mentions_apply_patch(command) {
+        return Err(FunctionCallError::RespondToModel(
+            "tool `apply_patch` is not available to this agent".to_string(),
+        ));
+    }
     match codex_apply_patch::maybe_parse_apply_patch_verified(command, cwd) {
         codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
             session
@@ -213,6 +244,7 @@ pub(crate) async fn intercept_apply_patch(
                 InternalApplyPatchInvocation::DelegateToExec(apply) => {
                     let changes = convert_apply_patch_to_protocol(&apply.action);
                     let approval_keys = file_paths_for_action(&apply.action);
+                    snapshot_files_before_patch(session, turn, &approval_keys).await;
                     let emitter = ToolEmitter::apply_patch(changes.clone(), apply.auto_approved);
                     let event_ctx =
                         ToolEventCtx::new(session, turn, call_id, tracker.as_ref().copied());
@@ -263,6 +295,24 @@ pub(crate) async fn intercept_apply_patch(
     }
 }
 
+/// Whether any word of `command`, including the words of a script passed to a
+/// shell, names one of the `apply_patch` executables or the flag that runs
+/// Codex as `apply_patch`. This is deliberately broad and also matches
+/// commands that only mention the executable, such as `grep apply_patch`.
+fn mentions_apply_patch(command: &[String]) -> bool {
+    let is_word_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | '\\');
+    command
+        .iter()
+        .flat_map(|arg| arg.split(|c: char| !is_word_char(c)))
+        .any(|word| {
+            word == CODEX_APPLY_PATCH_ARG1
+                || matches!(
+                    word.rsplit(['/', '\\']).next(),
+                    Some("apply_patch" | "applypatch" | "apply_patch.bat" | "applypatch.bat")
+                )
+        })
+}
+
 /// Returns a custom tool that can be used to edit files. Well-suited for GPT-5 models
 /// https://platform.openai.com/docs/guides/function-calling#custom-tools
 pub(crate) fn create_apply_patch_freeform_tool() -> ToolSpec {
@@ -370,6 +420,7 @@ It is important to remember:
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::types::ToolPolicy;
     use codex_apply_patch::MaybeApplyPatchVerified;
     use pretty_assertions::assert_eq;
     use tempfile::TempDir;
@@ -397,4 +448,86 @@ mod tests {
         let keys = file_paths_for_action(&action);
         assert_eq!(keys.len(), 2);
     }
+
+    #[tokio::test]
+    async fn shell_heredoc_patch_respects_tool_policy() {
+        let (session, mut turn) = crate::codex::make_session_and_context().await;
+        turn.tools_config.tool_policy = ToolPolicy {
+            allow: Vec::new(),
+            deny: vec!["apply_patch".to_string()],
+        };
+        let tmp = TempDir::new().expect("tmp");
+        let command = vec![
+            "bash".to_string(),
+            "-lc".to_string(),
+            "apply_patch <<'EOF'\n*** Begin Patch\n*** Add File: added.txt\n+hello\n*** End Patch\nEOF\n"
+                .to_string(),
+        ];
+
+        let result = intercept_apply_patch(
+            &command,
+            tmp.path(),
+            None,
+            &session,
+            &turn,
+            None,
+            "call-1",
+            "shell",
+        )
+        .await;
+
+        assert_eq!(
+            result.err(),
+            Some(FunctionCallError::RespondToModel(
+                "tool `apply_patch` is not available to this agent".to_string()
+            ))
+        );
+        assert!(!tmp.path().join("added.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn non_canonical_apply_patch_invocations_respect_tool_policy() {
+        let (session, mut turn) = crate::codex::make_session_and_context().await;
+        turn.tools_config.tool_policy = ToolPolicy {
+            allow: Vec::new(),
+            deny: vec!["apply_patch".to_string()],
+        };
+        let tmp = TempDir::new().expect("tmp");
+        let patch = "*** Begin Patch\n*** Add File: added.txt\n+hello\n*** End Patch\n";
+        let scripts = [
+            format!("printf '%s' '{patch}' | xargs -0 apply_patch"),
+            format!("cat <<'EOF' | xargs -0 /tmp/codex-arg0/applypatch\n{patch}EOF\n"),
+            format!("codex --codex-run-as-apply-patch '{patch}'"),
+        ];
+
+        for script in scripts {
+            let command = vec!["bash".to_string(), "-lc".to_string(), script.clone()];
+            let result = intercept_apply_patch(
+                &command,
+                tmp.path(),
+                None,
+                &session,
+                &turn,
+                None,
+                "call-1",
+                "shell",
+            )
+            .await;
+
+            assert_eq!(
+                result.err(),
+                Some(FunctionCallError::RespondToModel(
+                    "tool `apply_patch` is not available to this agent".to_string()
+                )),
+                "{script}"
+            );
+        }
+
+        let allowed = vec![
+            "bash".to_string(),
+            "-lc".to_string(),
+            "cat src/apply_patch.rs".to_string(),
+        ];
+        assert!(!mentions_apply_patch(&allowed));
+    }
 }
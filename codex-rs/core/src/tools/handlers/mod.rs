@@ -7,6 +7,7 @@ mod list_dir;
 mod mcp;
 mod mcp_resource;
 mod plan;
+mod py_repl;
 mod read_file;
 mod request_user_input;
 mod search_tool_bm25;
@@ -29,6 +30,8 @@ pub use list_dir::ListDirHandler;
 pub use mcp::McpHandler;
 pub use mcp_resource::McpResourceHandler;
 pub use plan::PlanHandler;
+pub use py_repl::PyReplHandler;
+pub use py_repl::PyReplResetHandler;
 pub use read_file::ReadFileHandler;
 pub use request_user_input::RequestUserInputHandler;
 pub(crate) use request_user_input::request_user_input_tool_description;
@@ -0,0 +1,327 @@
+use async_trait::async_trait;
+use codex_protocol::openai_models::InputModality;
+use serde_json::Value as JsonValue;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::exec::ExecToolCallOutput;
+use crate::exec::StreamOutput;
+use crate::features::Feature;
+use crate::function_tool::FunctionCallError;
+use crate::protocol::ExecCommandSource;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::events::ToolEmitter;
+use crate::tools::events::ToolEventCtx;
+use crate::tools::events::ToolEventFailure;
+use crate::tools::events::ToolEventStage;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::handlers::view_image::attach_local_image;
+use crate::tools::py_repl::PY_REPL_PRAGMA_PREFIX;
+use crate::tools::py_repl::PyExecResult;
+use crate::tools::py_repl::PyReplArgs;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+
+pub struct PyReplHandler;
+pub struct PyReplResetHandler;
+
+fn join_nonempty(first: &str, second: &str) -> String {
+    match (first.is_empty(), second.is_empty()) {
+        (true, _) => second.to_string(),
+        (_, true) => first.to_string(),
+        _ => format!("{first}\n{second}"),
+    }
+}
+
+fn build_py_repl_exec_output(result: &PyExecResult, duration: Duration) -> ExecToolCallOutput {
+    let stdout = join_nonempty(&result.stdout, result.result.as_deref().unwrap_or_default());
+    let stderr = join_nonempty(&result.stderr, result.error.as_deref().unwrap_or_default());
+    let aggregated_output = join_nonempty(&stdout, &stderr);
+    ExecToolCallOutput {
+        exit_code: if result.error.is_some() { 1 } else { 0 },
+        stdout: StreamOutput::new(stdout),
+        stderr: StreamOutput::new(stderr),
+        aggregated_output: StreamOutput::new(aggregated_output),
+        duration,
+        timed_out: false,
+    }
+}
+
+fn py_repl_emitter(turn: &crate::codex::TurnContext) -> ToolEmitter {
+    ToolEmitter::shell(
+        vec!["py_repl".to_string()],
+        turn.cwd.clone(),
+        ExecCommandSource::Agent,
+        false,
+    )
+}
+
+async fn emit_py_repl_exec_end(
+    session: &crate::codex::Session,
+    turn: &crate::codex::TurnContext,
+    call_id: &str,
+    result: &PyExecResult,
+    duration: Duration,
+) {
+    let exec_output = build_py_repl_exec_output(result, duration);
+    let ctx = ToolEventCtx::new(session, turn, call_id, None);
+    let stage = if result.error.is_some() {
+        ToolEventStage::Failure(ToolEventFailure::Output(exec_output))
+    } else {
+        ToolEventStage::Success(exec_output)
+    };
+    py_repl_emitter(turn).emit(ctx, stage).await;
+}
+
+#[async_trait]
+impl ToolHandler for PyReplHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    fn matches_kind(&self, payload: &ToolPayload) -> bool {
+        matches!(
+            payload,
+            ToolPayload::Function { .. } | ToolPayload::Custom { .. }
+        )
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            call_id,
+            ..
+        } = invocation;
+
+        if !session.features().enabled(Feature::PyRepl) {
+            return Err(FunctionCallError::RespondToModel(
+                "py_repl is disabled by feature flag".to_string(),
+            ));
+        }
+
+        let args = match payload {
+            ToolPayload::Function { arguments } => parse_arguments(&arguments)?,
+            ToolPayload::Custom { input } => parse_freeform_args(&input)?,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "py_repl expects custom or function payload".to_string(),
+                ));
+            }
+        };
+        let manager = turn.py_repl.manager().await?;
+        let started_at = Instant::now();
+        {
+            let ctx = ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, None);
+            py_repl_emitter(turn.as_ref())
+                .emit(ctx, ToolEventStage::Begin)
+                .await;
+        }
+        let result = manager
+            .execute(
+                Arc::clone(&turn),
+                session.conversation_id,
+                &session.services.secrets,
+                args,
+            )
+            .await;
+        let result = match result {
+            Ok(result) => result,
+            Err(err) => {
+                let failed = PyExecResult {
+                    error: Some(err.to_string()),
+                    ..Default::default()
+                };
+                emit_py_repl_exec_end(
+                    session.as_ref(),
+                    turn.as_ref(),
+                    &call_id,
+                    &failed,
+                    started_at.elapsed(),
+                )
+                .await;
+                return Err(err);
+            }
+        };
+        emit_py_repl_exec_end(
+            session.as_ref(),
+            turn.as_ref(),
+            &call_id,
+            &result,
+            started_at.elapsed(),
+        )
+        .await;
+
+        // Rendered images reach the model the same way `view_image` does.
+        if turn
+            .model_info
+            .input_modalities
+            .contains(&InputModality::Image)
+        {
+            for image in &result.images {
+                attach_local_image(
+                    session.as_ref(),
+                    turn.as_ref(),
+                    call_id.clone(),
+                    image.clone(),
+                )
+                .await?;
+            }
+        }
+
+        let text = serde_json::to_string(&result).map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to serialize py_repl output: {err}"))
+        })?;
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::ContentItems(vec![
+                FunctionCallOutputContentItem::InputText { text },
+            ]),
+            success: Some(result.error.is_none()),
+        })
+    }
+}
+
+#[async_trait]
+impl ToolHandler for PyReplResetHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        if !invocation.session.features().enabled(Feature::PyRepl) {
+            return Err(FunctionCallError::RespondToModel(
+                "py_repl is disabled by feature flag".to_string(),
+            ));
+        }
+        let manager = invocation.turn.py_repl.manager().await?;
+        manager.reset().await?;
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text("py_repl kernel reset".to_string()),
+            success: Some(true),
+        })
+    }
+}
+
+fn parse_freeform_args(input: &str) -> Result<PyReplArgs, FunctionCallError> {
+    if input.trim().is_empty() {
+        return Err(FunctionCallError::RespondToModel(
+            "py_repl expects raw Python tool input (non-empty). Provide Python source text, optionally with first-line `# codex-py-repl: ...`."
+                .to_string(),
+        ));
+    }
+
+    let (first_line, rest) = input.split_once('\n').unwrap_or((input, ""));
+    let Some(pragma) = first_line.trim_start().strip_prefix(PY_REPL_PRAGMA_PREFIX) else {
+        reject_json_or_quoted_source(input)?;
+        return Ok(PyReplArgs {
+            code: input.to_string(),
+            timeout_ms: None,
+        });
+    };
+
+    let mut timeout_ms: Option<u64> = None;
+    for token in pragma.split_whitespace() {
+        let (key, value) = token.split_once('=').ok_or_else(|| {
+            FunctionCallError::RespondToModel(format!(
+                "py_repl pragma expects space-separated key=value pairs (supported keys: timeout_ms); got `{token}`"
+            ))
+        })?;
+        if key != "timeout_ms" {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "py_repl pragma only supports timeout_ms; got `{key}`"
+            )));
+        }
+        if timeout_ms.is_some() {
+            return Err(FunctionCallError::RespondToModel(
+                "py_repl pragma specifies timeout_ms more than once".to_string(),
+            ));
+        }
+        timeout_ms = Some(value.parse::<u64>().map_err(|_| {
+            FunctionCallError::RespondToModel(format!(
+                "py_repl pragma timeout_ms must be an integer; got `{value}`"
+            ))
+        })?);
+    }
+
+    if rest.trim().is_empty() {
+        return Err(FunctionCallError::RespondToModel(
+            "py_repl pragma must be followed by Python source on subsequent lines".to_string(),
+        ));
+    }
+
+    reject_json_or_quoted_source(rest)?;
+    Ok(PyReplArgs {
+        code: rest.to_string(),
+        timeout_ms,
+    })
+}
+
+fn reject_json_or_quoted_source(code: &str) -> Result<(), FunctionCallError> {
+    let trimmed = code.trim();
+    if trimmed.starts_with("```") {
+        return Err(FunctionCallError::RespondToModel(
+            "py_repl expects raw Python source, not markdown code fences. Resend plain Python only (optional first line `# codex-py-repl: ...`)."
+                .to_string(),
+        ));
+    }
+    // A bare string or dict literal is valid Python, but as the whole input it
+    // almost always means the code was wrapped by mistake.
+    match serde_json::from_str::<JsonValue>(trimmed) {
+        Ok(JsonValue::Object(map)) if map.contains_key("code") => Err(FunctionCallError::RespondToModel(
+            "py_repl is a freeform tool and expects raw Python source. Resend plain Python only (optional first line `# codex-py-repl: ...`); do not send JSON (`{\"code\":...}`), quoted code, or markdown fences."
+                .to_string(),
+        )),
+        Ok(JsonValue::String(_)) => Err(FunctionCallError::RespondToModel(
+            "py_repl is a freeform tool and expects raw Python source. Resend plain Python only (optional first line `# codex-py-repl: ...`); do not send JSON (`{\"code\":...}`), quoted code, or markdown fences."
+                .to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_freeform_args;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_freeform_args_with_pragma() {
+        let input = "# codex-py-repl: timeout_ms=15000\nprint('ok')";
+        let args = parse_freeform_args(input).expect("parse args");
+        assert_eq!(args.code, "print('ok')");
+        assert_eq!(args.timeout_ms, Some(15_000));
+    }
+
+    #[test]
+    fn parse_freeform_args_keeps_plain_comments() {
+        let input = "# load the data\nimport json";
+        let args = parse_freeform_args(input).expect("parse args");
+        assert_eq!(args.code, input);
+        assert_eq!(args.timeout_ms, None);
+    }
+
+    #[test]
+    fn parse_freeform_args_rejects_unknown_key() {
+        let err = parse_freeform_args("# codex-py-repl: reset=true\nprint('ok')")
+            .expect_err("expected error");
+        assert_eq!(
+            err.to_string(),
+            "py_repl pragma only supports timeout_ms; got `reset`"
+        );
+    }
+
+    #[test]
+    fn parse_freeform_args_rejects_json_wrapped_code() {
+        let err = parse_freeform_args(r#"{"code":"print(1)"}"#).expect_err("expected error");
+        assert_eq!(
+            err.to_string(),
+            "py_repl is a freeform tool and expects raw Python source. Resend plain Python only (optional first line `# codex-py-repl: ...`); do not send JSON (`{\"code\":...}`), quoted code, or markdown fences."
+        );
+    }
+}
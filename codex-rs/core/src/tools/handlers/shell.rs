@@ -7,7 +7,7 @@ use std::sync::Arc;
 
 use crate::codex::TurnContext;
 use crate::exec::ExecParams;
-use crate::exec_env::create_env;
+use crate::exec_env::create_env_with_secrets;
 use crate::exec_policy::ExecApprovalRequest;
 use crate::function_tool::FunctionCallError;
 use crate::is_safe_command::is_known_safe_command;
@@ -43,16 +43,26 @@ struct RunExecLikeArgs {
 }
 
 impl ShellHandler {
-    fn to_exec_params(
+    async fn to_exec_params(
         params: &ShellToolCallParams,
+        session: &crate::codex::Session,
         turn_context: &TurnContext,
         thread_id: ThreadId,
     ) -> ExecParams {
+        let cwd = turn_context.resolve_path(params.workdir.clone());
+        let env = create_env_with_secrets(
+            &turn_context.shell_environment_policy,
+            Some(thread_id),
+            &session.services.secrets,
+            &turn_context.cwd,
+        )
+        .await;
+
         ExecParams {
             command: params.command.clone(),
-            cwd: turn_context.resolve_path(params.workdir.clone()),
+            cwd,
             expiration: params.timeout_ms.into(),
-            env: create_env(&turn_context.shell_environment_policy, Some(thread_id)),
+            env,
             network: turn_context.network.clone(),
             network_attempt_id: None,
             sandbox_permissions: params.sandbox_permissions.unwrap_or_default(),
@@ -69,7 +79,7 @@ impl ShellCommandHandler {
         shell.derive_exec_args(command, use_login_shell)
     }
 
-    fn to_exec_params(
+    async fn to_exec_params(
         params: &ShellCommandToolCallParams,
         session: &crate::codex::Session,
         turn_context: &TurnContext,
@@ -77,12 +87,20 @@ impl ShellCommandHandler {
     ) -> ExecParams {
         let shell = session.user_shell();
         let command = Self::base_command(shell.as_ref(), &params.command, params.login);
+        let cwd = turn_context.resolve_path(params.workdir.clone());
+        let env = create_env_with_secrets(
+            &turn_context.shell_environment_policy,
+            Some(thread_id),
+            &session.services.secrets,
+            &turn_context.cwd,
+        )
+        .await;
 
         ExecParams {
             command,
-            cwd: turn_context.resolve_path(params.workdir.clone()),
+            cwd,
             expiration: params.timeout_ms.into(),
-            env: create_env(&turn_context.shell_environment_policy, Some(thread_id)),
+            env,
             network: turn_context.network.clone(),
             network_attempt_id: None,
             sandbox_permissions: params.sandbox_permissions.unwrap_or_default(),
@@ -132,8 +150,13 @@ impl ToolHandler for ShellHandler {
             ToolPayload::Function { arguments } => {
                 let params: ShellToolCallParams = parse_arguments(&arguments)?;
                 let prefix_rule = params.prefix_rule.clone();
-                let exec_params =
-                    Self::to_exec_params(&params, turn.as_ref(), session.conversation_id);
+                let exec_params = Self::to_exec_params(
+                    &params,
+                    session.as_ref(),
+                    turn.as_ref(),
+                    session.conversation_id,
+                )
+                .await;
                 Self::run_exec_like(RunExecLikeArgs {
                     tool_name: tool_name.clone(),
                     exec_params,
@@ -147,8 +170,13 @@ impl ToolHandler for ShellHandler {
                 .await
             }
             ToolPayload::LocalShell { params } => {
-                let exec_params =
-                    Self::to_exec_params(&params, turn.as_ref(), session.conversation_id);
+                let exec_params = Self::to_exec_params(
+                    &params,
+                    session.as_ref(),
+                    turn.as_ref(),
+                    session.conversation_id,
+                )
+                .await;
                 Self::run_exec_like(RunExecLikeArgs {
                     tool_name: tool_name.clone(),
                     exec_params,
@@ -215,7 +243,8 @@ impl ToolHandler for ShellCommandHandler {
             session.as_ref(),
             turn.as_ref(),
             session.conversation_id,
-        );
+        )
+        .await;
         ShellHandler::run_exec_like(RunExecLikeArgs {
             tool_name,
             exec_params,
@@ -303,6 +332,8 @@ impl ShellHandler {
             .exec_policy
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &exec_params.command,
+                cwd: &exec_params.cwd,
+                session_cwd: &turn.cwd,
                 approval_policy: turn.approval_policy,
                 sandbox_policy: &turn.sandbox_policy,
                 sandbox_permissions: exec_params.sandbox_permissions,
@@ -442,7 +473,8 @@ mod tests {
             &session,
             &turn_context,
             session.conversation_id,
-        );
+        )
+        .await;
 
         // ExecParams cannot derive Eq due to the CancellationToken field, so we manually compare the fields.
         assert_eq!(exec_params.command, expected_command);
@@ -2,8 +2,11 @@ use async_trait::async_trait;
 use codex_protocol::models::FunctionCallOutputBody;
 use codex_protocol::openai_models::InputModality;
 use serde::Deserialize;
+use std::path::PathBuf;
 use tokio::fs;
 
+use crate::codex::Session;
+use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
 use crate::protocol::EventMsg;
 use crate::protocol::ViewImageToolCallEvent;
@@ -79,33 +82,8 @@ impl ToolHandler for ViewImageHandler {
                 abs_path.display()
             )));
         }
-        let event_path = abs_path.clone();
 
-        let content: Vec<ContentItem> =
-            local_image_content_items_with_label_number(&abs_path, None);
-        let input = ResponseInputItem::Message {
-            role: "user".to_string(),
-            content,
-        };
-
-        session
-            .inject_response_items(vec![input])
-            .await
-            .map_err(|_| {
-                FunctionCallError::RespondToModel(
-                    "unable to attach image (no active task)".to_string(),
-                )
-            })?;
-
-        session
-            .send_event(
-                turn.as_ref(),
-                EventMsg::ViewImageToolCall(ViewImageToolCallEvent {
-                    call_id,
-                    path: event_path,
-                }),
-            )
-            .await;
+        attach_local_image(session.as_ref(), turn.as_ref(), call_id, abs_path).await?;
 
         Ok(ToolOutput::Function {
             body: FunctionCallOutputBody::Text("attached local image path".to_string()),
@@ -113,3 +91,33 @@ impl ToolHandler for ViewImageHandler {
         })
     }
 }
+
+/// Inject a local image into the active turn so the model sees it with the
+/// next request, and announce it to clients.
+pub(crate) async fn attach_local_image(
+    session: &Session,
+    turn: &TurnContext,
+    call_id: String,
+    path: PathBuf,
+) -> Result<(), FunctionCallError> {
+    let content: Vec<ContentItem> = local_image_content_items_with_label_number(&path, None);
+    let input = ResponseInputItem::Message {
+        role: "user".to_string(),
+        content,
+    };
+
+    session
+        .inject_response_items(vec![input])
+        .await
+        .map_err(|_| {
+            FunctionCallError::RespondToModel("unable to attach image (no active task)".to_string())
+        })?;
+
+    session
+        .send_event(
+            turn,
+            EventMsg::ViewImageToolCall(ViewImageToolCallEvent { call_id, path }),
+        )
+        .await;
+    Ok(())
+}
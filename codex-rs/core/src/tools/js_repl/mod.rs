@@ -28,11 +28,12 @@ use crate::client_common::tools::ToolSpec;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::exec::ExecExpiration;
-use crate::exec_env::create_env;
+use crate::exec_env::create_env_with_secrets;
 use crate::function_tool::FunctionCallError;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::SandboxManager;
 use crate::sandboxing::SandboxPermissions;
+use crate::secrets::SessionSecrets;
 use crate::tools::ToolRouter;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::sandboxing::SandboxablePreference;
@@ -145,7 +146,7 @@ struct KernelDebugSnapshot {
     stderr_tail: String,
 }
 
-fn format_exit_status(status: std::process::ExitStatus) -> String {
+pub(crate) fn format_exit_status(status: std::process::ExitStatus) -> String {
     if let Some(code) = status.code() {
         return format!("code={code}");
     }
@@ -156,7 +157,7 @@ fn format_exit_status(status: std::process::ExitStatus) -> String {
     "unknown".to_string()
 }
 
-fn format_stderr_tail(lines: &VecDeque<String>) -> String {
+pub(crate) fn format_stderr_tail(lines: &VecDeque<String>) -> String {
     if lines.is_empty() {
         return "<empty>".to_string();
     }
@@ -197,7 +198,7 @@ fn stderr_tail_bytes_with_candidate(lines: &VecDeque<String>, line: &str) -> usi
     stderr_tail_formatted_bytes(lines) + JS_REPL_STDERR_TAIL_SEPARATOR.len() + line.len()
 }
 
-fn push_stderr_tail_line(lines: &mut VecDeque<String>, line: &str) -> String {
+pub(crate) fn push_stderr_tail_line(lines: &mut VecDeque<String>, line: &str) -> String {
     let max_line_bytes = JS_REPL_STDERR_TAIL_LINE_MAX_BYTES.min(JS_REPL_STDERR_TAIL_MAX_BYTES);
     let bounded_line = truncate_utf8_prefix_by_bytes(line, max_line_bytes);
     if bounded_line.is_empty() {
@@ -433,7 +434,11 @@ impl JsReplManager {
             let mut kernel = self.kernel.lock().await;
             if kernel.is_none() {
                 let state = self
-                    .start_kernel(Arc::clone(&turn), Some(session.conversation_id))
+                    .start_kernel(
+                        Arc::clone(&turn),
+                        Some(session.conversation_id),
+                        &session.services.secrets,
+                    )
                     .await
                     .map_err(FunctionCallError::RespondToModel)?;
                 *kernel = Some(state);
@@ -547,6 +552,7 @@ impl JsReplManager {
         &self,
         turn: Arc<TurnContext>,
         thread_id: Option<ThreadId>,
+        secrets: &SessionSecrets,
     ) -> Result<KernelState, String> {
         let node_path = resolve_node(self.node_path.as_deref()).ok_or_else(|| {
             "Node runtime not found; install Node or set CODEX_JS_REPL_NODE_PATH".to_string()
@@ -558,7 +564,13 @@ impl JsReplManager {
             .await
             .map_err(|err| err.to_string())?;
 
-        let mut env = create_env(&turn.shell_environment_policy, thread_id);
+        let mut env = create_env_with_secrets(
+            &turn.shell_environment_policy,
+            thread_id,
+            secrets,
+            &turn.cwd,
+        )
+        .await;
         env.insert(
             "CODEX_JS_TMP_DIR".to_string(),
             self.tmp_dir.path().to_string_lossy().to_string(),
@@ -584,50 +596,7 @@ impl JsReplManager {
             justification: None,
         };
 
-        let sandbox = SandboxManager::new();
-        let has_managed_network_requirements = turn
-            .config
-            .config_layer_stack
-            .requirements_toml()
-            .network
-            .is_some();
-        let sandbox_type = sandbox.select_initial(
-            &turn.sandbox_policy,
-            SandboxablePreference::Auto,
-            turn.windows_sandbox_level,
-            has_managed_network_requirements,
-        );
-        let exec_env = sandbox
-            .transform(crate::sandboxing::SandboxTransformRequest {
-                spec,
-                policy: &turn.sandbox_policy,
-                sandbox: sandbox_type,
-                enforce_managed_network: has_managed_network_requirements,
-                network: None,
-                sandbox_policy_cwd: &turn.cwd,
-                codex_linux_sandbox_exe: turn.codex_linux_sandbox_exe.as_ref(),
-                use_linux_sandbox_bwrap: turn
-                    .features
-                    .enabled(crate::features::Feature::UseLinuxSandboxBwrap),
-                windows_sandbox_level: turn.windows_sandbox_level,
-            })
-            .map_err(|err| format!("failed to configure sandbox for js_repl: {err}"))?;
-
-        let mut cmd =
-            tokio::process::Command::new(exec_env.command.first().cloned().unwrap_or_default());
-        if exec_env.command.len() > 1 {
-            cmd.args(&exec_env.command[1..]);
-        }
-        #[cfg(unix)]
-        cmd.arg0(
-            exec_env
-                .arg0
-                .clone()
-                .unwrap_or_else(|| exec_env.command.first().cloned().unwrap_or_default()),
-        );
-        cmd.current_dir(&exec_env.cwd);
-        cmd.env_clear();
-        cmd.envs(exec_env.env);
+        let mut cmd = sandboxed_kernel_command(turn.as_ref(), spec, "js_repl")?;
         cmd.stdin(std::process::Stdio::piped())
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
@@ -1090,6 +1059,60 @@ impl JsReplManager {
     }
 }
 
+/// Build the command for a REPL kernel, wrapped in the sandbox selected for the
+/// turn. Stdio is left for the caller to configure.
+pub(crate) fn sandboxed_kernel_command(
+    turn: &TurnContext,
+    spec: CommandSpec,
+    label: &str,
+) -> Result<tokio::process::Command, String> {
+    let sandbox = SandboxManager::new();
+    let has_managed_network_requirements = turn
+        .config
+        .config_layer_stack
+        .requirements_toml()
+        .network
+        .is_some();
+    let sandbox_type = sandbox.select_initial(
+        &turn.sandbox_policy,
+        SandboxablePreference::Auto,
+        turn.windows_sandbox_level,
+        has_managed_network_requirements,
+    );
+    let exec_env = sandbox
+        .transform(crate::sandboxing::SandboxTransformRequest {
+            spec,
+            policy: &turn.sandbox_policy,
+            sandbox: sandbox_type,
+            enforce_managed_network: has_managed_network_requirements,
+            network: None,
+            sandbox_policy_cwd: &turn.cwd,
+            codex_linux_sandbox_exe: turn.codex_linux_sandbox_exe.as_ref(),
+            use_linux_sandbox_bwrap: turn
+                .features
+                .enabled(crate::features::Feature::UseLinuxSandboxBwrap),
+            windows_sandbox_level: turn.windows_sandbox_level,
+        })
+        .map_err(|err| format!("failed to configure sandbox for {label}: {err}"))?;
+
+    let mut cmd =
+        tokio::process::Command::new(exec_env.command.first().cloned().unwrap_or_default());
+    if exec_env.command.len() > 1 {
+        cmd.args(&exec_env.command[1..]);
+    }
+    #[cfg(unix)]
+    cmd.arg0(
+        exec_env
+            .arg0
+            .clone()
+            .unwrap_or_else(|| exec_env.command.first().cloned().unwrap_or_default()),
+    );
+    cmd.current_dir(&exec_env.cwd);
+    cmd.env_clear();
+    cmd.envs(exec_env.env);
+    Ok(cmd)
+}
+
 fn is_freeform_tool(specs: &[ToolSpec], name: &str) -> bool {
     specs
         .iter()
@@ -5,6 +5,7 @@ pub mod js_repl;
 pub(crate) mod network_approval;
 pub mod orchestrator;
 pub mod parallel;
+pub mod py_repl;
 pub mod registry;
 pub mod router;
 pub mod runtimes;
@@ -1,6 +1,9 @@
 use crate::codex::Session;
 use crate::network_policy_decision::denied_network_policy_message;
+use crate::state_db::StateDbHandle;
 use crate::tools::sandboxing::ToolError;
+use chrono::Duration as ChronoDuration;
+use chrono::Utc;
 use codex_network_proxy::BlockedRequest;
 use codex_network_proxy::BlockedRequestObserver;
 use codex_network_proxy::NetworkDecision;
@@ -8,17 +11,27 @@ use codex_network_proxy::NetworkPolicyDecider;
 use codex_network_proxy::NetworkPolicyRequest;
 use codex_network_proxy::NetworkProtocol;
 use codex_network_proxy::NetworkProxy;
+use codex_network_proxy::NetworkRequestEvent;
+use codex_network_proxy::NetworkRequestObserver;
+use codex_protocol::ThreadId;
 use codex_protocol::approvals::NetworkApprovalContext;
 use codex_protocol::approvals::NetworkApprovalProtocol;
 use codex_protocol::protocol::ReviewDecision;
+use codex_state::NetworkRequestEntry;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
+use tokio::sync::mpsc;
+use tracing::warn;
 use uuid::Uuid;
 
+/// Audit log entries waiting for the state DB; further entries are dropped until it catches up.
+const NETWORK_REQUEST_QUEUE_CAPACITY: usize = 512;
+const NETWORK_REQUEST_RETENTION_DAYS: i64 = 90;
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub(crate) enum NetworkApprovalMode {
     Immediate,
@@ -87,18 +100,26 @@ struct NetworkApprovalAttempt {
 pub(crate) struct NetworkApprovalService {
     attempts: Mutex<HashMap<String, Arc<NetworkApprovalAttempt>>>,
     session_approved_hosts: Mutex<HashSet<String>>,
+    /// Whether proxied requests are written to the audit log, which needs attempts registered to
+    /// attribute them to an exec call.
+    audit_log_enabled: bool,
 }
 
 impl Default for NetworkApprovalService {
     fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl NetworkApprovalService {
+    pub(crate) fn new(audit_log_enabled: bool) -> Self {
         Self {
             attempts: Mutex::new(HashMap::new()),
             session_approved_hosts: Mutex::new(HashSet::new()),
+            audit_log_enabled,
         }
     }
-}
 
-impl NetworkApprovalService {
     pub(crate) async fn register_attempt(
         &self,
         attempt_id: String,
@@ -191,6 +212,18 @@ impl NetworkApprovalService {
         None
     }
 
+    /// Exec call that made a proxied request. Requests without an attempt id (SOCKS5, or clients
+    /// that drop the proxy credentials) are not attributed, since any call in flight could have
+    /// made them.
+    pub(crate) async fn call_id_for_attempt(&self, attempt_id: Option<&str>) -> Option<String> {
+        let attempt_id = attempt_id?;
+        self.attempts
+            .lock()
+            .await
+            .get(attempt_id)
+            .map(|attempt| attempt.call_id.clone())
+    }
+
     pub(crate) async fn record_blocked_request(&self, blocked: BlockedRequest) {
         let Some(message) = denied_network_policy_message(&blocked) else {
             return;
@@ -292,6 +325,78 @@ pub(crate) fn build_blocked_request_observer(
     })
 }
 
+/// Records every proxied request in the state DB audit log. Entries are handed to a writer
+/// task so a request never waits on SQLite; the writer also prunes entries past the retention
+/// window when it starts.
+pub(crate) fn build_network_request_observer(
+    network_approval: Arc<NetworkApprovalService>,
+    state_db: StateDbHandle,
+    thread_id: ThreadId,
+) -> Arc<dyn NetworkRequestObserver> {
+    let (sender, receiver) = mpsc::channel(NETWORK_REQUEST_QUEUE_CAPACITY);
+    tokio::spawn(run_network_request_writer(state_db, receiver));
+    Arc::new(move |event: NetworkRequestEvent| {
+        let network_approval = Arc::clone(&network_approval);
+        let sender = sender.clone();
+        async move {
+            let call_id = network_approval
+                .call_id_for_attempt(event.attempt_id.as_deref())
+                .await;
+            let entry = network_request_entry(event, thread_id, call_id);
+            if let Err(
+                mpsc::error::TrySendError::Full(entry) | mpsc::error::TrySendError::Closed(entry),
+            ) = sender.try_send(entry)
+            {
+                warn!("dropping network request audit entry for {}", entry.host);
+            }
+        }
+    })
+}
+
+async fn run_network_request_writer(
+    state_db: StateDbHandle,
+    mut receiver: mpsc::Receiver<NetworkRequestEntry>,
+) {
+    if let Some(cutoff) =
+        Utc::now().checked_sub_signed(ChronoDuration::days(NETWORK_REQUEST_RETENTION_DAYS))
+        && let Err(err) = state_db
+            .delete_network_requests_before(cutoff.timestamp())
+            .await
+    {
+        warn!("failed to prune the network request audit log: {err}");
+    }
+    while let Some(entry) = receiver.recv().await {
+        if let Err(err) = state_db.insert_network_request(&entry).await {
+            warn!("failed to record network request for {}: {err}", entry.host);
+        }
+    }
+}
+
+fn network_request_entry(
+    event: NetworkRequestEvent,
+    thread_id: ThreadId,
+    call_id: Option<String>,
+) -> NetworkRequestEntry {
+    NetworkRequestEntry {
+        ts: event.timestamp,
+        thread_id: Some(thread_id.to_string()),
+        call_id,
+        host: event.host,
+        port: event.port.map(i64::from),
+        protocol: event.protocol,
+        method: event.method,
+        decision: event.decision,
+        reason: event.reason,
+        source: event.source,
+        request_bytes: event
+            .request_bytes
+            .and_then(|bytes| i64::try_from(bytes).ok()),
+        response_bytes: event
+            .response_bytes
+            .and_then(|bytes| i64::try_from(bytes).ok()),
+    }
+}
+
 pub(crate) fn build_network_policy_decider(
     network_approval: Arc<NetworkApprovalService>,
     network_policy_decider_session: Arc<RwLock<std::sync::Weak<Session>>>,
@@ -318,7 +423,10 @@ pub(crate) async fn begin_network_approval(
     spec: Option<NetworkApprovalSpec>,
 ) -> Option<ActiveNetworkApproval> {
     let spec = spec?;
-    if !has_managed_network_requirements || spec.network.is_none() {
+    // Attempts also attribute audit log entries to their exec call, so they are tracked
+    // whenever the audit log is on even without managed network requirements.
+    let audit_log_enabled = session.services.network_approval.audit_log_enabled;
+    if !(has_managed_network_requirements || audit_log_enabled) || spec.network.is_none() {
         return None;
     }
 
@@ -403,6 +511,7 @@ pub(crate) async fn finish_deferred_network_approval(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_network_proxy::AllowedRequestArgs;
     use codex_network_proxy::BlockedRequestArgs;
     use codex_network_proxy::NetworkPolicyRequestArgs;
     use pretty_assertions::assert_eq;
@@ -672,4 +781,90 @@ mod tests {
             Some(NetworkApprovalOutcome::DeniedByUser)
         );
     }
+
+    #[tokio::test]
+    async fn network_request_observer_writes_in_the_background_and_prunes_old_entries() {
+        let codex_home = tempfile::tempdir().expect("create temp dir");
+        let state_db = codex_state::StateRuntime::init(
+            codex_home.path().to_path_buf(),
+            "test-provider".to_string(),
+            None,
+        )
+        .await
+        .expect("initialize state db");
+        let thread_id = ThreadId::new();
+        state_db
+            .insert_network_request(&NetworkRequestEntry {
+                ts: 0,
+                thread_id: Some(thread_id.to_string()),
+                call_id: None,
+                host: "stale.example".to_string(),
+                port: Some(443),
+                protocol: "https".to_string(),
+                method: None,
+                decision: "allow".to_string(),
+                reason: None,
+                source: None,
+                request_bytes: None,
+                response_bytes: None,
+            })
+            .await
+            .expect("insert stale entry");
+
+        let observer = build_network_request_observer(
+            Arc::new(NetworkApprovalService::default()),
+            Arc::clone(&state_db),
+            thread_id,
+        );
+        observer
+            .on_network_request(NetworkRequestEvent::allowed(AllowedRequestArgs {
+                host: "registry.npmjs.org".to_string(),
+                port: Some(443),
+                protocol: "http-connect".to_string(),
+                method: Some("CONNECT".to_string()),
+                client: None,
+                attempt_id: None,
+                request_bytes: None,
+                response_bytes: None,
+            }))
+            .await;
+
+        let hosts = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            loop {
+                let rows = state_db
+                    .query_network_requests(&codex_state::NetworkRequestQuery::default())
+                    .await
+                    .expect("query network requests");
+                if rows.iter().any(|row| row.host == "registry.npmjs.org") {
+                    break rows.into_iter().map(|row| row.host).collect::<Vec<_>>();
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("entry should be written");
+        assert_eq!(hosts, vec!["registry.npmjs.org".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn call_id_for_attempt_requires_an_attempt_id() {
+        let service = NetworkApprovalService::default();
+        service
+            .register_attempt(
+                "attempt-1".to_string(),
+                "turn-1".to_string(),
+                "call-1".to_string(),
+                vec!["curl".to_string(), "example.com".to_string()],
+                std::env::temp_dir(),
+            )
+            .await;
+
+        assert_eq!(
+            service.call_id_for_attempt(Some("attempt-1")).await,
+            Some("call-1".to_string())
+        );
+        assert_eq!(service.call_id_for_attempt(Some("missing")).await, None);
+        // SOCKS requests carry no attempt id and are never guessed onto the call in flight.
+        assert_eq!(service.call_id_for_attempt(None).await, None);
+    }
 }
@@ -0,0 +1,191 @@
+# Python kernel for py_repl.
+# Communicates over JSON lines on stdin and the original stdout.
+
+import ast
+import base64
+import contextlib
+import io
+import json
+import linecache
+import os
+import sys
+import tempfile
+import traceback
+
+if sys.version_info < (3, 8):
+    sys.stderr.write("py_repl requires Python 3.8 or newer\n")
+    sys.exit(1)
+
+# The JSON line transport owns the real stdin and a private copy of fd 1;
+# cells see captured buffers instead. fd 1 itself points at stderr, and at a
+# capture file while a cell runs, so output that bypasses sys.stdout (os.system,
+# subprocesses, C extensions) cannot corrupt the transport.
+_requests = sys.stdin
+sys.stdout.flush()
+_transport = os.fdopen(os.dup(1), "w", encoding="utf-8")
+os.dup2(2, 1)
+_tmp_dir = os.environ.get("CODEX_PY_TMP_DIR") or os.getcwd()
+_image_counter = 0
+_cell_counter = 0
+
+
+class _Codex:
+    """Helpers exposed to cells as `codex`."""
+
+    def __init__(self):
+        self.state = {}
+        self.tmp_dir = _tmp_dir
+        self._images = []
+
+    def show_image(self, path):
+        """Attach an existing image file to the result of the current cell."""
+        path = os.path.abspath(os.fspath(path))
+        if not os.path.isfile(path):
+            raise FileNotFoundError(path)
+        self._images.append(path)
+
+
+codex = _Codex()
+namespace = {"__name__": "__main__", "__builtins__": __builtins__, "codex": codex}
+
+
+def _write_image(data, extension):
+    global _image_counter
+    _image_counter += 1
+    path = os.path.join(_tmp_dir, f"py-repl-image-{_image_counter}.{extension}")
+    with open(path, "wb") as handle:
+        handle.write(data)
+    return path
+
+
+def _rich_image(value):
+    """Save `value` as an image when it knows how to render itself as one."""
+    to_png = getattr(value, "_repr_png_", None)
+    if callable(to_png):
+        data = to_png()
+        if isinstance(data, str):
+            data = base64.b64decode(data)
+        if data:
+            return _write_image(data, "png")
+    return None
+
+
+def _drain_matplotlib_figures():
+    pyplot = sys.modules.get("matplotlib.pyplot")
+    if pyplot is None:
+        return []
+    paths = []
+    for number in pyplot.get_fignums():
+        buffer = io.BytesIO()
+        pyplot.figure(number).savefig(buffer, format="png")
+        paths.append(_write_image(buffer.getvalue(), "png"))
+    pyplot.close("all")
+    return paths
+
+
+def _run_cell(code):
+    global _cell_counter
+    _cell_counter += 1
+    filename = f"<cell-{_cell_counter}>"
+    # Register the source so tracebacks can show the offending lines.
+    linecache.cache[filename] = (len(code), None, code.splitlines(True), filename)
+    tree = ast.parse(code, filename=filename, mode="exec")
+    last_expr = None
+    if tree.body and isinstance(tree.body[-1], ast.Expr):
+        last_expr = ast.Expression(tree.body.pop().value)
+    flags = ast.PyCF_ALLOW_TOP_LEVEL_AWAIT
+    result = eval(compile(tree, filename, "exec", flags=flags), namespace)
+    if result is not None and hasattr(result, "__await__"):
+        _await(result)
+    if last_expr is None:
+        return None
+    value = eval(compile(last_expr, filename, "eval", flags=flags), namespace)
+    if value is not None and hasattr(value, "__await__"):
+        value = _await(value)
+    return value
+
+
+def _await(awaitable):
+    import asyncio
+
+    async def runner():
+        return await awaitable
+
+    return asyncio.run(runner())
+
+
+def _format_error(err):
+    # Drop the kernel's own frames so the traceback starts in the cell. Errors
+    # raised before the cell ran, such as syntax errors, have no cell frames.
+    tb = err.__traceback__
+    while tb is not None and not tb.tb_frame.f_code.co_filename.startswith("<cell-"):
+        tb = tb.tb_next
+    if tb is None:
+        return "".join(traceback.format_exception_only(type(err), err))
+    return "".join(traceback.format_exception(type(err), err, tb))
+
+
+def handle_exec(message):
+    stdout = io.StringIO()
+    stderr = io.StringIO()
+    codex._images = []
+    reply = {"type": "exec_result", "id": message["id"]}
+    result = None
+    images = []
+    sys.stdin = io.StringIO()
+    fd_stdout = tempfile.TemporaryFile()
+    os.dup2(fd_stdout.fileno(), 1)
+    try:
+        with contextlib.redirect_stdout(stdout), contextlib.redirect_stderr(stderr):
+            try:
+                value = _run_cell(message.get("code") or "")
+                if value is not None:
+                    namespace["_"] = value
+                    image = _rich_image(value)
+                    if image is not None:
+                        images.append(image)
+                    else:
+                        result = repr(value)
+            except BaseException as err:  # noqa: BLE001 - report everything to the model
+                if isinstance(err, KeyboardInterrupt):
+                    raise
+                reply["error"] = _format_error(err)
+            try:
+                images.extend(_drain_matplotlib_figures())
+            except Exception as err:  # noqa: BLE001
+                print(f"failed to render matplotlib figures: {err}", file=sys.stderr)
+    finally:
+        sys.__stdout__.flush()
+        os.dup2(2, 1)
+    fd_stdout.seek(0)
+    fd_output = fd_stdout.read().decode("utf-8", "replace")
+    fd_stdout.close()
+    images = codex._images + images
+    codex._images = []
+    reply.update(
+        stdout=stdout.getvalue() + fd_output,
+        stderr=stderr.getvalue(),
+        result=result,
+        images=images,
+    )
+    _transport.write(json.dumps(reply))
+    _transport.write("\n")
+    _transport.flush()
+
+
+def main():
+    # Keep plotting libraries off any display.
+    os.environ.setdefault("MPLBACKEND", "Agg")
+    for line in _requests:
+        if not line.strip():
+            continue
+        try:
+            message = json.loads(line)
+        except ValueError:
+            continue
+        if message.get("type") == "exec":
+            handle_exec(message)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,566 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use codex_protocol::ThreadId;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::io::Lines;
+use tokio::process::Child;
+use tokio::process::ChildStdin;
+use tokio::process::ChildStdout;
+use tokio::sync::Mutex;
+use tokio::sync::OnceCell;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::codex::TurnContext;
+use crate::exec::ExecExpiration;
+use crate::exec_env::create_env_with_secrets;
+use crate::function_tool::FunctionCallError;
+use crate::sandboxing::CommandSpec;
+use crate::sandboxing::SandboxPermissions;
+use crate::secrets::SessionSecrets;
+use crate::tools::js_repl::format_exit_status;
+use crate::tools::js_repl::format_stderr_tail;
+use crate::tools::js_repl::push_stderr_tail_line;
+use crate::tools::js_repl::sandboxed_kernel_command;
+
+pub(crate) const PY_REPL_PRAGMA_PREFIX: &str = "# codex-py-repl:";
+const KERNEL_SOURCE: &str = include_str!("kernel.py");
+const PY_REPL_DEFAULT_TIMEOUT_MS: u64 = 30_000;
+const PY_REPL_STDERR_TAIL_LINE_LIMIT: usize = 20;
+
+/// Per-session py_repl handle stored on the turn context.
+pub(crate) struct PyReplHandle {
+    python_path: Option<PathBuf>,
+    cell: OnceCell<Arc<PyReplManager>>,
+}
+
+impl fmt::Debug for PyReplHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PyReplHandle").finish_non_exhaustive()
+    }
+}
+
+impl PyReplHandle {
+    pub(crate) fn with_python_path(python_path: Option<PathBuf>) -> Self {
+        Self {
+            python_path,
+            cell: OnceCell::new(),
+        }
+    }
+
+    pub(crate) async fn manager(&self) -> Result<Arc<PyReplManager>, FunctionCallError> {
+        self.cell
+            .get_or_try_init(|| async { PyReplManager::new(self.python_path.clone()) })
+            .await
+            .cloned()
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PyReplArgs {
+    pub code: String,
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+/// Outcome of one cell, as reported to the model.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct PyExecResult {
+    pub stdout: String,
+    pub stderr: String,
+    /// `repr()` of the cell's trailing expression, if it was not `None`.
+    pub result: Option<String>,
+    /// Images rendered by the cell, such as matplotlib figures or values with a
+    /// `_repr_png_` method.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<PathBuf>,
+    /// Formatted traceback when the cell raised.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+struct KernelState {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+    recent_stderr: Arc<Mutex<VecDeque<String>>>,
+    shutdown: CancellationToken,
+}
+
+pub struct PyReplManager {
+    python_path: Option<PathBuf>,
+    tmp_dir: tempfile::TempDir,
+    kernel: Mutex<Option<KernelState>>,
+}
+
+impl PyReplManager {
+    fn new(python_path: Option<PathBuf>) -> Result<Arc<Self>, FunctionCallError> {
+        let tmp_dir = tempfile::tempdir().map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to create py_repl temp dir: {err}"))
+        })?;
+        Ok(Arc::new(Self {
+            python_path,
+            tmp_dir,
+            kernel: Mutex::new(None),
+        }))
+    }
+
+    pub async fn reset(&self) -> Result<(), FunctionCallError> {
+        let state = self.kernel.lock().await.take();
+        if let Some(state) = state {
+            Self::shutdown_kernel(state, "reset").await;
+        }
+        Ok(())
+    }
+
+    pub async fn execute(
+        &self,
+        turn: Arc<TurnContext>,
+        thread_id: ThreadId,
+        secrets: &SessionSecrets,
+        args: PyReplArgs,
+    ) -> Result<PyExecResult, FunctionCallError> {
+        // Holding the kernel lock for the whole cell serializes executions.
+        let mut kernel = self.kernel.lock().await;
+        if kernel.is_none() {
+            let state = self
+                .start_kernel(turn.as_ref(), thread_id, secrets)
+                .await
+                .map_err(FunctionCallError::RespondToModel)?;
+            *kernel = Some(state);
+        }
+        let Some(state) = kernel.as_mut() else {
+            return Err(FunctionCallError::RespondToModel(
+                "py_repl kernel unavailable".to_string(),
+            ));
+        };
+
+        let exec_id = Uuid::new_v4().to_string();
+        let payload = HostToKernel::Exec {
+            id: exec_id.clone(),
+            code: args.code,
+        };
+        if let Err(err) = Self::write_message(&mut state.stdin, &payload).await {
+            let message = Self::kernel_failure_message(state, &err).await;
+            if let Some(state) = kernel.take() {
+                Self::shutdown_kernel(state, "write_failed").await;
+            }
+            return Err(FunctionCallError::RespondToModel(message));
+        }
+
+        let timeout_ms = args.timeout_ms.unwrap_or(PY_REPL_DEFAULT_TIMEOUT_MS);
+        let line =
+            tokio::time::timeout(Duration::from_millis(timeout_ms), state.stdout.next_line()).await;
+        let line = match line {
+            Ok(Ok(Some(line))) => line,
+            Ok(Ok(None)) | Ok(Err(_)) => {
+                let message =
+                    Self::kernel_failure_message(state, "py_repl kernel exited unexpectedly").await;
+                if let Some(state) = kernel.take() {
+                    Self::shutdown_kernel(state, "stdout_closed").await;
+                }
+                return Err(FunctionCallError::RespondToModel(message));
+            }
+            Err(_) => {
+                if let Some(state) = kernel.take() {
+                    Self::shutdown_kernel(state, "timeout").await;
+                }
+                return Err(FunctionCallError::RespondToModel(
+                    "py_repl execution timed out; kernel reset, rerun your request".to_string(),
+                ));
+            }
+        };
+
+        match serde_json::from_str::<KernelToHost>(&line) {
+            Ok(KernelToHost::ExecResult { id, result }) if id == exec_id => Ok(result),
+            parsed => {
+                warn!("py_repl kernel sent an unexpected response: {parsed:?} (line: {line})");
+                if let Some(state) = kernel.take() {
+                    Self::shutdown_kernel(state, "invalid_json").await;
+                }
+                Err(FunctionCallError::RespondToModel(
+                    "py_repl kernel sent an invalid response; kernel reset. Avoid writing to `sys.__stdout__`, which carries the kernel protocol.".to_string(),
+                ))
+            }
+        }
+    }
+
+    async fn start_kernel(
+        &self,
+        turn: &TurnContext,
+        thread_id: ThreadId,
+        secrets: &SessionSecrets,
+    ) -> Result<KernelState, String> {
+        let python_path = resolve_python(self.python_path.as_deref()).ok_or_else(|| {
+            "Python runtime not found; install Python 3 or set CODEX_PY_REPL_PYTHON_PATH"
+                .to_string()
+        })?;
+
+        let kernel_path = self.tmp_dir.path().join("py_repl_kernel.py");
+        tokio::fs::write(&kernel_path, KERNEL_SOURCE)
+            .await
+            .map_err(|err| format!("failed to write py_repl kernel: {err}"))?;
+
+        let mut env = create_env_with_secrets(
+            &turn.shell_environment_policy,
+            Some(thread_id),
+            secrets,
+            &turn.cwd,
+        )
+        .await;
+        env.insert(
+            "CODEX_PY_TMP_DIR".to_string(),
+            self.tmp_dir.path().to_string_lossy().to_string(),
+        );
+        env.insert("PYTHONIOENCODING".to_string(), "utf-8".to_string());
+
+        let spec = CommandSpec {
+            program: python_path.to_string_lossy().to_string(),
+            args: vec!["-u".to_string(), kernel_path.to_string_lossy().to_string()],
+            cwd: turn.cwd.clone(),
+            env,
+            expiration: ExecExpiration::DefaultTimeout,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            justification: None,
+        };
+        let mut cmd = sandboxed_kernel_command(turn, spec, "py_repl")?;
+        cmd.stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true);
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|err| format!("failed to start Python runtime: {err}"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| "py_repl kernel missing stdout".to_string())?;
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| "py_repl kernel missing stdin".to_string())?;
+
+        let shutdown = CancellationToken::new();
+        let recent_stderr = Arc::new(Mutex::new(VecDeque::with_capacity(
+            PY_REPL_STDERR_TAIL_LINE_LIMIT,
+        )));
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(Self::read_stderr(
+                stderr,
+                Arc::clone(&recent_stderr),
+                shutdown.clone(),
+            ));
+        } else {
+            warn!("py_repl kernel missing stderr");
+        }
+
+        Ok(KernelState {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout).lines(),
+            recent_stderr,
+            shutdown,
+        })
+    }
+
+    async fn write_message(stdin: &mut ChildStdin, msg: &HostToKernel) -> Result<(), String> {
+        let mut encoded = serde_json::to_string(msg)
+            .map_err(|err| format!("failed to serialize kernel message: {err}"))?;
+        encoded.push('\n');
+        stdin
+            .write_all(encoded.as_bytes())
+            .await
+            .map_err(|err| format!("failed to write to kernel: {err}"))?;
+        stdin
+            .flush()
+            .await
+            .map_err(|err| format!("failed to flush kernel message: {err}"))
+    }
+
+    /// `base_message` plus the kernel's exit status and recent stderr, which
+    /// usually carry the reason the kernel died (for example an old Python).
+    async fn kernel_failure_message(state: &mut KernelState, base_message: &str) -> String {
+        // Give the stderr reader a moment to drain the final lines.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let status = match state.child.try_wait() {
+            Ok(Some(status)) => format!("exited({})", format_exit_status(status)),
+            Ok(None) => "running".to_string(),
+            Err(err) => format!("unknown ({err})"),
+        };
+        let stderr_tail = format_stderr_tail(&*state.recent_stderr.lock().await);
+        format!("{base_message}\n\nkernel status: {status}; stderr: {stderr_tail}")
+    }
+
+    async fn shutdown_kernel(mut state: KernelState, reason: &'static str) {
+        state.shutdown.cancel();
+        if let Err(err) = state.child.start_kill() {
+            if !matches!(state.child.try_wait(), Ok(Some(_))) {
+                warn!(kill_reason = reason, error = %err, "failed to kill py_repl kernel");
+            }
+            return;
+        }
+        if tokio::time::timeout(Duration::from_secs(2), state.child.wait())
+            .await
+            .is_err()
+        {
+            warn!(
+                kill_reason = reason,
+                "timed out waiting for py_repl kernel to exit after kill"
+            );
+        }
+    }
+
+    async fn read_stderr(
+        stderr: tokio::process::ChildStderr,
+        recent_stderr: Arc<Mutex<VecDeque<String>>>,
+        shutdown: CancellationToken,
+    ) {
+        let mut reader = BufReader::new(stderr).lines();
+        loop {
+            let line = tokio::select! {
+                _ = shutdown.cancelled() => break,
+                res = reader.next_line() => match res {
+                    Ok(Some(line)) => line,
+                    Ok(None) => break,
+                    Err(err) => {
+                        warn!("py_repl kernel stderr ended: {err}");
+                        break;
+                    }
+                },
+            };
+            let trimmed = line.trim();
+            if !trimmed.is_empty() {
+                let bounded_line = push_stderr_tail_line(&mut *recent_stderr.lock().await, trimmed);
+                warn!("py_repl stderr: {bounded_line}");
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum KernelToHost {
+    ExecResult {
+        id: String,
+        #[serde(flatten)]
+        result: PyExecResult,
+    },
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum HostToKernel {
+    Exec { id: String, code: String },
+}
+
+pub(crate) fn resolve_python(config_path: Option<&Path>) -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("CODEX_PY_REPL_PYTHON_PATH") {
+        let p = PathBuf::from(path);
+        if p.exists() {
+            return Some(p);
+        }
+    }
+
+    if let Some(path) = config_path
+        && path.exists()
+    {
+        return Some(path.to_path_buf());
+    }
+
+    ["python3", "python"]
+        .into_iter()
+        .find_map(|name| which::which(name).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codex::make_session_and_context;
+    use crate::protocol::AskForApproval;
+    use crate::protocol::SandboxPolicy;
+    use pretty_assertions::assert_eq;
+
+    fn can_run_py_repl_runtime_tests() -> bool {
+        std::env::var_os("CODEX_SANDBOX").is_none() && resolve_python(None).is_some()
+    }
+
+    async fn run(
+        manager: &PyReplManager,
+        turn: &Arc<TurnContext>,
+        session: &crate::codex::Session,
+        code: &str,
+    ) -> Result<PyExecResult, FunctionCallError> {
+        manager
+            .execute(
+                Arc::clone(turn),
+                session.conversation_id,
+                &session.services.secrets,
+                PyReplArgs {
+                    code: code.to_string(),
+                    timeout_ms: Some(10_000),
+                },
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn py_repl_persists_bindings_and_structures_output() -> anyhow::Result<()> {
+        if !can_run_py_repl_runtime_tests() {
+            return Ok(());
+        }
+
+        let (session, mut turn) = make_session_and_context().await;
+        turn.approval_policy = AskForApproval::Never;
+        turn.sandbox_policy = SandboxPolicy::DangerFullAccess;
+        let turn = Arc::new(turn);
+        let manager = turn.py_repl.manager().await?;
+
+        let first = run(&manager, &turn, &session, "x = 41\nprint('hello')").await?;
+        assert_eq!(
+            first,
+            PyExecResult {
+                stdout: "hello\n".to_string(),
+                ..Default::default()
+            }
+        );
+
+        let second = run(
+            &manager,
+            &turn,
+            &session,
+            "import sys\nprint('warn', file=sys.stderr)\nx + 1",
+        )
+        .await?;
+        assert_eq!(
+            second,
+            PyExecResult {
+                stderr: "warn\n".to_string(),
+                result: Some("42".to_string()),
+                ..Default::default()
+            }
+        );
+
+        let failed = run(&manager, &turn, &session, "print('before')\n1 / 0").await?;
+        assert_eq!(failed.stdout, "before\n");
+        let error = failed.error.expect("traceback for the failed cell");
+        assert!(error.contains("ZeroDivisionError"), "{error}");
+        assert!(!error.contains("py_repl_kernel.py"), "{error}");
+
+        manager.reset().await?;
+        let after_reset = run(&manager, &turn, &session, "'x' in globals()").await?;
+        assert_eq!(after_reset.result.as_deref(), Some("False"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn py_repl_captures_output_written_to_fd_1() -> anyhow::Result<()> {
+        if !can_run_py_repl_runtime_tests() {
+            return Ok(());
+        }
+
+        let (session, mut turn) = make_session_and_context().await;
+        turn.approval_policy = AskForApproval::Never;
+        turn.sandbox_policy = SandboxPolicy::DangerFullAccess;
+        let turn = Arc::new(turn);
+        let manager = turn.py_repl.manager().await?;
+
+        let result = run(
+            &manager,
+            &turn,
+            &session,
+            "import os\nstatus = os.system('echo hi')\nstatus",
+        )
+        .await?;
+        assert_eq!(
+            result,
+            PyExecResult {
+                stdout: "hi\n".to_string(),
+                result: Some("0".to_string()),
+                ..Default::default()
+            }
+        );
+
+        // The kernel kept its state, so the transport was not corrupted.
+        let after = run(&manager, &turn, &session, "status + 1").await?;
+        assert_eq!(after.result.as_deref(), Some("1"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn py_repl_saves_rich_results_as_images() -> anyhow::Result<()> {
+        if !can_run_py_repl_runtime_tests() {
+            return Ok(());
+        }
+
+        let (session, mut turn) = make_session_and_context().await;
+        turn.approval_policy = AskForApproval::Never;
+        turn.sandbox_policy = SandboxPolicy::DangerFullAccess;
+        let turn = Arc::new(turn);
+        let manager = turn.py_repl.manager().await?;
+
+        let code = r#"
+import base64
+
+class Pixel:
+    def _repr_png_(self):
+        return base64.b64decode(
+            "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR4nGP4z8DwHwAFAAH/iZk9HQAAAABJRU5ErkJggg=="
+        )
+
+Pixel()
+"#;
+        let result = run(&manager, &turn, &session, code).await?;
+        assert_eq!(result.result, None);
+        assert_eq!(result.images.len(), 1);
+        let bytes = std::fs::read(&result.images[0])?;
+        assert!(bytes.starts_with(b"\x89PNG"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn py_repl_timeout_resets_kernel() -> anyhow::Result<()> {
+        if !can_run_py_repl_runtime_tests() {
+            return Ok(());
+        }
+
+        let (session, mut turn) = make_session_and_context().await;
+        turn.approval_policy = AskForApproval::Never;
+        turn.sandbox_policy = SandboxPolicy::DangerFullAccess;
+        let turn = Arc::new(turn);
+        let manager = turn.py_repl.manager().await?;
+
+        run(&manager, &turn, &session, "y = 1").await?;
+        let err = manager
+            .execute(
+                Arc::clone(&turn),
+                session.conversation_id,
+                &session.services.secrets,
+                PyReplArgs {
+                    code: "while True:\n    pass".to_string(),
+                    timeout_ms: Some(200),
+                },
+            )
+            .await
+            .expect_err("expected timeout error");
+        assert_eq!(
+            err.to_string(),
+            "py_repl execution timed out; kernel reset, rerun your request"
+        );
+
+        let after = run(&manager, &turn, &session, "'y' in globals()").await?;
+        assert_eq!(after.result.as_deref(), Some("False"));
+        Ok(())
+    }
+}
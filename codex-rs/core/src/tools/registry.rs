@@ -12,13 +12,15 @@ use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
 use async_trait::async_trait;
+use codex_hooks::HookDecision;
 use codex_hooks::HookEvent;
 use codex_hooks::HookEventAfterToolUse;
+use codex_hooks::HookEventBeforeToolUse;
 use codex_hooks::HookPayload;
 use codex_hooks::HookToolInput;
 use codex_hooks::HookToolInputLocalShell;
-use codex_hooks::HookToolKind;
 use codex_protocol::models::ResponseInputItem;
+use codex_protocol::models::ShellToolCallParams;
 use codex_utils_readiness::Readiness;
 use tracing::warn;
 
@@ -76,7 +78,7 @@ impl ToolRegistry {
 
     pub async fn dispatch(
         &self,
-        invocation: ToolInvocation,
+        mut invocation: ToolInvocation,
     ) -> Result<ResponseInputItem, FunctionCallError> {
         let tool_name = invocation.tool_name.clone();
         let call_id_owned = invocation.call_id.clone();
@@ -133,10 +135,72 @@ impl ToolRegistry {
             return Err(FunctionCallError::Fatal(message));
         }
 
-        let is_mutating = handler.is_mutating(&invocation).await;
+        let mut is_mutating = handler.is_mutating(&invocation).await;
+        match dispatch_before_tool_use_hook(&invocation, is_mutating).await {
+            HookDecision::Allow {
+                tool_input: None, ..
+            } => {}
+            HookDecision::Allow {
+                tool_input: Some(tool_input),
+                ..
+            } => {
+                let Some(payload) = rewritten_tool_payload(&invocation.payload, tool_input) else {
+                    // A misbehaving hook should not end the turn: report the
+                    // rejected rewrite to the model like any other failed call.
+                    let message =
+                        format!("hook rewrote tool {tool_name} with an incompatible input type");
+                    otel.tool_result_with_tags(
+                        tool_name.as_ref(),
+                        &call_id_owned,
+                        log_payload.as_ref(),
+                        Duration::ZERO,
+                        false,
+                        &message,
+                        &metric_tags,
+                    );
+                    dispatch_after_tool_use_hook(AfterToolUseHookDispatch {
+                        invocation: &invocation,
+                        output_preview: message.clone(),
+                        success: false,
+                        executed: false,
+                        duration: Duration::ZERO,
+                        mutating: is_mutating,
+                    })
+                    .await;
+                    return Err(FunctionCallError::RespondToModel(message));
+                };
+                invocation.payload = payload;
+                is_mutating = handler.is_mutating(&invocation).await;
+            }
+            HookDecision::Deny { reason } => {
+                let message = format!("tool call was blocked by a hook: {reason}");
+                otel.tool_result_with_tags(
+                    tool_name.as_ref(),
+                    &call_id_owned,
+                    log_payload.as_ref(),
+                    Duration::ZERO,
+                    false,
+                    &message,
+                    &metric_tags,
+                );
+                dispatch_after_tool_use_hook(AfterToolUseHookDispatch {
+                    invocation: &invocation,
+                    output_preview: message.clone(),
+                    success: false,
+                    executed: false,
+                    duration: Duration::ZERO,
+                    mutating: is_mutating,
+                })
+                .await;
+                return Err(FunctionCallError::RespondToModel(message));
+            }
+        }
+
         let output_cell = tokio::sync::Mutex::new(None);
         let invocation_for_tool = invocation.clone();
 
+        let payload_for_response = invocation.payload.clone();
+        let log_payload = payload_for_response.log_payload();
         let started = Instant::now();
         let result = otel
             .log_tool_result_with_tags(
@@ -321,15 +385,82 @@ impl From<&ToolPayload> for HookToolInput {
     }
 }
 
-fn hook_tool_kind(tool_input: &HookToolInput) -> HookToolKind {
-    match tool_input {
-        HookToolInput::Function { .. } => HookToolKind::Function,
-        HookToolInput::Custom { .. } => HookToolKind::Custom,
-        HookToolInput::LocalShell { .. } => HookToolKind::LocalShell,
-        HookToolInput::Mcp { .. } => HookToolKind::Mcp,
+/// Maps a hook-provided replacement back onto the runtime payload. Hooks may
+/// change the contents of a call but not its shape, so the rewritten input must
+/// use the same variant as the original payload (and, for MCP, the same tool).
+fn rewritten_tool_payload(
+    original: &ToolPayload,
+    tool_input: HookToolInput,
+) -> Option<ToolPayload> {
+    match (original, tool_input) {
+        (ToolPayload::Function { .. }, HookToolInput::Function { arguments }) => {
+            Some(ToolPayload::Function { arguments })
+        }
+        (ToolPayload::Custom { .. }, HookToolInput::Custom { input }) => {
+            Some(ToolPayload::Custom { input })
+        }
+        (ToolPayload::LocalShell { .. }, HookToolInput::LocalShell { params }) => {
+            Some(ToolPayload::LocalShell {
+                params: ShellToolCallParams {
+                    command: params.command,
+                    workdir: params.workdir,
+                    timeout_ms: params.timeout_ms,
+                    sandbox_permissions: params.sandbox_permissions,
+                    prefix_rule: params.prefix_rule,
+                    justification: params.justification,
+                },
+            })
+        }
+        (
+            ToolPayload::Mcp { server, tool, .. },
+            HookToolInput::Mcp {
+                server: new_server,
+                tool: new_tool,
+                arguments,
+            },
+        ) if *server == new_server && *tool == new_tool => Some(ToolPayload::Mcp {
+            server: new_server,
+            tool: new_tool,
+            raw_arguments: arguments,
+        }),
+        _ => None,
     }
 }
 
+async fn dispatch_before_tool_use_hook(
+    invocation: &ToolInvocation,
+    mutating: bool,
+) -> HookDecision {
+    let session = invocation.session.as_ref();
+    let turn = invocation.turn.as_ref();
+    let tool_input = HookToolInput::from(&invocation.payload);
+    session
+        .hooks()
+        .dispatch(HookPayload {
+            session_id: session.conversation_id,
+            cwd: turn.cwd.clone(),
+            triggered_at: chrono::Utc::now(),
+            hook_event: HookEvent::BeforeToolUse {
+                event: HookEventBeforeToolUse {
+                    turn_id: turn.sub_id.clone(),
+                    call_id: invocation.call_id.clone(),
+                    tool_name: invocation.tool_name.clone(),
+                    tool_kind: tool_input.kind(),
+                    tool_input,
+                    mutating,
+                    sandbox: sandbox_tag(
+                        &turn.sandbox_policy,
+                        turn.windows_sandbox_level,
+                        turn.features.enabled(Feature::UseLinuxSandboxBwrap),
+                    )
+                    .to_string(),
+                    sandbox_policy: sandbox_policy_tag(&turn.sandbox_policy).to_string(),
+                },
+            },
+        })
+        .await
+}
+
 struct AfterToolUseHookDispatch<'a> {
     invocation: &'a ToolInvocation,
     output_preview: String,
@@ -355,7 +486,7 @@ async fn dispatch_after_tool_use_hook(dispatch: AfterToolUseHookDispatch<'_>) {
                     turn_id: turn.sub_id.clone(),
                     call_id: invocation.call_id.clone(),
                     tool_name: invocation.tool_name.clone(),
-                    tool_kind: hook_tool_kind(&tool_input),
+                    tool_kind: tool_input.kind(),
                     tool_input,
                     executed: dispatch.executed,
                     success: dispatch.success,
@@ -374,3 +505,94 @@ async fn dispatch_after_tool_use_hook(dispatch: AfterToolUseHookDispatch<'_>) {
         })
         .await;
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use codex_hooks::CommandHookConfig;
+    use codex_hooks::CommandHookOnError;
+    use codex_hooks::DEFAULT_COMMAND_HOOK_TIMEOUT;
+    use codex_hooks::HookEventKind;
+    use codex_hooks::HookMatcher;
+    use codex_hooks::Hooks;
+    use codex_hooks::HooksConfig;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::codex::make_session_and_context;
+    use crate::turn_diff_tracker::TurnDiffTracker;
+
+    struct CountingHandler {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl ToolHandler for CountingHandler {
+        fn kind(&self) -> ToolKind {
+            ToolKind::Function
+        }
+
+        async fn handle(
+            &self,
+            _invocation: ToolInvocation,
+        ) -> Result<ToolOutput, FunctionCallError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(ToolOutput::Function {
+                body: codex_protocol::models::FunctionCallOutputBody::Text("ran".to_string()),
+                success: Some(true),
+            })
+        }
+    }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn incompatible_hook_rewrite_is_reported_to_the_model() {
+        let (mut session, turn) = make_session_and_context().await;
+        session.services.hooks = Hooks::new(HooksConfig {
+            legacy_notify_argv: None,
+            command_hooks: vec![CommandHookConfig {
+                event: HookEventKind::BeforeToolUse,
+                argv: vec![
+                    "/bin/sh".to_string(),
+                    "-c".to_string(),
+                    r#"echo '{"tool_input":{"input_type":"custom","input":"*** Begin Patch"}}'"#
+                        .to_string(),
+                ],
+                timeout: DEFAULT_COMMAND_HOOK_TIMEOUT,
+                matcher: HookMatcher::default(),
+                on_error: CommandHookOnError::Continue,
+            }],
+        });
+        let calls = Arc::new(AtomicUsize::new(0));
+        let registry = ToolRegistry::new(HashMap::from([(
+            "echo".to_string(),
+            Arc::new(CountingHandler {
+                calls: Arc::clone(&calls),
+            }) as Arc<dyn ToolHandler>,
+        )]));
+
+        let result = registry
+            .dispatch(ToolInvocation {
+                session: Arc::new(session),
+                turn: Arc::new(turn),
+                tracker: Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new())),
+                call_id: "call-1".to_string(),
+                tool_name: "echo".to_string(),
+                payload: ToolPayload::Function {
+                    arguments: "{}".to_string(),
+                },
+            })
+            .await;
+
+        let Err(FunctionCallError::RespondToModel(message)) = result else {
+            panic!("expected the rewrite failure to be returned to the model");
+        };
+        assert_eq!(
+            message,
+            "hook rewrote tool echo with an incompatible input type"
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+}
@@ -19,6 +19,7 @@ use codex_protocol::models::ResponseItem;
 use codex_protocol::models::ShellToolCallParams;
 use rmcp::model::Tool;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 use tracing::instrument;
 
@@ -47,8 +48,17 @@ impl ToolRouter {
         app_tools: Option<HashMap<String, ToolInfo>>,
         dynamic_tools: &[DynamicToolSpec],
     ) -> Self {
+        // MCP tools are filtered by the caller, which knows their server names.
+        let mcp_tool_names: HashSet<String> = mcp_tools
+            .as_ref()
+            .map(|tools| tools.keys().cloned().collect())
+            .unwrap_or_default();
         let builder = build_specs(config, mcp_tools, app_tools, dynamic_tools);
-        let (specs, registry) = builder.build();
+        let (mut specs, registry) = builder.build();
+        specs.retain(|spec| {
+            let name = spec.spec.name();
+            mcp_tool_names.contains(name) || config.is_tool_allowed(name)
+        });
 
         Self { registry, specs }
     }
@@ -171,6 +181,23 @@ impl ToolRouter {
             ));
         }
 
+        let tool_allowed = match &payload {
+            ToolPayload::Mcp { server, tool, .. } => turn
+                .tools_config
+                .is_mcp_tool_allowed(&tool_name, server, tool),
+            _ => turn.tools_config.is_tool_allowed(&tool_name),
+        };
+        if !tool_allowed {
+            let err = FunctionCallError::RespondToModel(format!(
+                "tool `{tool_name}` is not available to this agent"
+            ));
+            return Ok(Self::failure_response(
+                failure_call_id,
+                payload_outputs_custom,
+                err,
+            ));
+        }
+
         let invocation = ToolInvocation {
             session,
             turn,
@@ -218,6 +245,7 @@ mod tests {
     use std::sync::Arc;
 
     use crate::codex::make_session_and_context;
+    use crate::config::types::ToolPolicy;
     use crate::tools::context::ToolPayload;
     use crate::turn_diff_tracker::TurnDiffTracker;
     use codex_protocol::models::ResponseInputItem;
@@ -225,6 +253,7 @@ mod tests {
     use super::ToolCall;
     use super::ToolCallSource;
     use super::ToolRouter;
+    use pretty_assertions::assert_eq;
 
     #[tokio::test]
     async fn js_repl_tools_only_blocks_direct_tool_calls() -> anyhow::Result<()> {
@@ -331,4 +360,82 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn tool_policy_hides_and_blocks_other_tools() -> anyhow::Result<()> {
+        let (session, mut turn) = make_session_and_context().await;
+        turn.tools_config.tool_policy = ToolPolicy {
+            allow: vec![vec!["update_*".to_string(), "docs/*".to_string()]],
+            deny: vec!["docs/delete_*".to_string()],
+        };
+
+        let session = Arc::new(session);
+        let turn = Arc::new(turn);
+        let router = ToolRouter::from_config(
+            &turn.tools_config,
+            None,
+            None,
+            turn.dynamic_tools.as_slice(),
+        );
+        let names: Vec<String> = router
+            .specs()
+            .iter()
+            .map(|spec| spec.name().to_string())
+            .collect();
+        assert_eq!(names, vec!["update_plan".to_string()]);
+
+        let call = ToolCall {
+            tool_name: "shell".to_string(),
+            call_id: "call-3".to_string(),
+            payload: ToolPayload::Function {
+                arguments: "{}".to_string(),
+            },
+        };
+        let tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
+        let response = router
+            .dispatch_tool_call(
+                Arc::clone(&session),
+                Arc::clone(&turn),
+                tracker,
+                call,
+                ToolCallSource::JsRepl,
+            )
+            .await?;
+
+        match response {
+            ResponseInputItem::FunctionCallOutput { output, .. } => {
+                assert_eq!(
+                    output.text_content(),
+                    Some("tool `shell` is not available to this agent")
+                );
+            }
+            other => panic!("expected function call output, got {other:?}"),
+        }
+
+        let call = ToolCall {
+            tool_name: "mcp__docs__delete_page".to_string(),
+            call_id: "call-4".to_string(),
+            payload: ToolPayload::Mcp {
+                server: "docs".to_string(),
+                tool: "delete_page".to_string(),
+                raw_arguments: "{}".to_string(),
+            },
+        };
+        let tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
+        let response = router
+            .dispatch_tool_call(session, turn, tracker, call, ToolCallSource::Direct)
+            .await?;
+
+        match response {
+            ResponseInputItem::FunctionCallOutput { output, .. } => {
+                assert_eq!(
+                    output.text_content(),
+                    Some("tool `mcp__docs__delete_page` is not available to this agent")
+                );
+            }
+            other => panic!("expected function call output, got {other:?}"),
+        }
+
+        Ok(())
+    }
 }
@@ -1,8 +1,11 @@
-use crate::agent::AgentRole;
+use crate::agent::agent_role_enum_values;
+use crate::agent::builtin_agent_roles;
 use crate::client_common::tools::FreeformTool;
 use crate::client_common::tools::FreeformToolFormat;
 use crate::client_common::tools::ResponsesApiTool;
 use crate::client_common::tools::ToolSpec;
+use crate::config::types::AgentRoleConfig;
+use crate::config::types::ToolPolicy;
 use crate::features::Feature;
 use crate::features::Features;
 use crate::mcp_connection_manager::ToolInfo;
@@ -39,10 +42,14 @@ pub(crate) struct ToolsConfig {
     pub search_tool: bool,
     pub js_repl_enabled: bool,
     pub js_repl_tools_only: bool,
+    pub py_repl_enabled: bool,
     pub collab_tools: bool,
     pub collaboration_modes_tools: bool,
     pub request_rule_enabled: bool,
     pub experimental_supported_tools: Vec<String>,
+    pub agent_roles: BTreeMap<String, AgentRoleConfig>,
+    /// Decides which tools are exposed to the model and may be called.
+    pub tool_policy: ToolPolicy,
 }
 
 pub(crate) struct ToolsConfigParams<'a> {
@@ -62,6 +69,7 @@ impl ToolsConfig {
         let include_js_repl = features.enabled(Feature::JsRepl);
         let include_js_repl_tools_only =
             include_js_repl && features.enabled(Feature::JsReplToolsOnly);
+        let include_py_repl = features.enabled(Feature::PyRepl);
         let include_collab_tools = features.enabled(Feature::Collab);
         let include_collaboration_modes_tools = features.enabled(Feature::CollaborationModes);
         let request_rule_enabled = features.enabled(Feature::RequestRule);
@@ -99,12 +107,35 @@ impl ToolsConfig {
             search_tool: include_search_tool,
             js_repl_enabled: include_js_repl,
             js_repl_tools_only: include_js_repl_tools_only,
+            py_repl_enabled: include_py_repl,
             collab_tools: include_collab_tools,
             collaboration_modes_tools: include_collaboration_modes_tools,
             request_rule_enabled,
             experimental_supported_tools: model_info.experimental_supported_tools.clone(),
+            agent_roles: builtin_agent_roles(),
+            tool_policy: ToolPolicy::default(),
         }
     }
+
+    pub fn with_agent_roles(mut self, agent_roles: BTreeMap<String, AgentRoleConfig>) -> Self {
+        self.agent_roles = agent_roles;
+        self
+    }
+
+    pub fn with_tool_policy(mut self, tool_policy: ToolPolicy) -> Self {
+        self.tool_policy = tool_policy;
+        self
+    }
+
+    pub fn is_tool_allowed(&self, name: &str) -> bool {
+        self.tool_policy.allows(&[name])
+    }
+
+    /// MCP tools match either their model-facing name or `server/tool`.
+    pub fn is_mcp_tool_allowed(&self, name: &str, server: &str, tool: &str) -> bool {
+        self.tool_policy
+            .allows(&[name, format!("{server}/{tool}").as_str()])
+    }
 }
 
 pub(crate) fn filter_tools_for_model(tools: Vec<ToolSpec>, config: &ToolsConfig) -> Vec<ToolSpec> {
@@ -526,7 +557,7 @@ fn create_collab_input_items_schema() -> JsonSchema {
     }
 }
 
-fn create_spawn_agent_tool() -> ToolSpec {
+fn create_spawn_agent_tool(agent_roles: &BTreeMap<String, AgentRoleConfig>) -> ToolSpec {
     let properties = BTreeMap::from([
         (
             "message".to_string(),
@@ -543,7 +574,7 @@ fn create_spawn_agent_tool() -> ToolSpec {
             JsonSchema::String {
                 description: Some(format!(
                     "Optional agent type ({}). Use an explicit type when delegating.",
-                    AgentRole::enum_values().join(", ")
+                    agent_role_enum_values(agent_roles).join(", ")
                 )),
             },
         ),
@@ -1088,6 +1119,35 @@ fn create_js_repl_tool() -> ToolSpec {
     })
 }
 
+fn create_py_repl_tool() -> ToolSpec {
+    const PY_REPL_FREEFORM_GRAMMAR: &str = r#"start: /[\s\S]*/"#;
+
+    ToolSpec::Freeform(FreeformTool {
+        name: "py_repl".to_string(),
+        description: "Runs Python in a persistent kernel. Bindings persist across calls and the value of a trailing expression is returned. This is a freeform tool: send raw Python source text, optionally with a first-line pragma like `# codex-py-repl: timeout_ms=15000`; do not send JSON/quotes/markdown fences."
+            .to_string(),
+        format: FreeformToolFormat {
+            r#type: "grammar".to_string(),
+            syntax: "lark".to_string(),
+            definition: PY_REPL_FREEFORM_GRAMMAR.to_string(),
+        },
+    })
+}
+
+fn create_py_repl_reset_tool() -> ToolSpec {
+    ToolSpec::Function(ResponsesApiTool {
+        name: "py_repl_reset".to_string(),
+        description: "Restarts the py_repl kernel for this run and clears persisted bindings."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties: BTreeMap::new(),
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_js_repl_reset_tool() -> ToolSpec {
     ToolSpec::Function(ResponsesApiTool {
         name: "js_repl_reset".to_string(),
@@ -1417,6 +1477,8 @@ pub(crate) fn build_specs(
     use crate::tools::handlers::McpHandler;
     use crate::tools::handlers::McpResourceHandler;
     use crate::tools::handlers::PlanHandler;
+    use crate::tools::handlers::PyReplHandler;
+    use crate::tools::handlers::PyReplResetHandler;
     use crate::tools::handlers::ReadFileHandler;
     use crate::tools::handlers::RequestUserInputHandler;
     use crate::tools::handlers::SearchToolBm25Handler;
@@ -1442,6 +1504,8 @@ pub(crate) fn build_specs(
     let search_tool_handler = Arc::new(SearchToolBm25Handler);
     let js_repl_handler = Arc::new(JsReplHandler);
     let js_repl_reset_handler = Arc::new(JsReplResetHandler);
+    let py_repl_handler = Arc::new(PyReplHandler);
+    let py_repl_reset_handler = Arc::new(PyReplResetHandler);
 
     match &config.shell_type {
         ConfigShellToolType::Default => {
@@ -1498,6 +1562,13 @@ pub(crate) fn build_specs(
         builder.register_handler("js_repl_reset", js_repl_reset_handler);
     }
 
+    if config.py_repl_enabled {
+        builder.push_spec(create_py_repl_tool());
+        builder.push_spec(create_py_repl_reset_tool());
+        builder.register_handler("py_repl", py_repl_handler);
+        builder.register_handler("py_repl_reset", py_repl_reset_handler);
+    }
+
     if config.collaboration_modes_tools {
         builder.push_spec(create_request_user_input_tool());
         builder.register_handler("request_user_input", request_user_input_handler);
@@ -1578,7 +1649,7 @@ pub(crate) fn build_specs(
 
     if config.collab_tools {
         let collab_handler = Arc::new(CollabHandler);
-        builder.push_spec(create_spawn_agent_tool());
+        builder.push_spec(create_spawn_agent_tool(&config.agent_roles));
         builder.push_spec(create_send_input_tool());
         builder.push_spec(create_resume_agent_tool());
         builder.push_spec(create_wait_tool());
@@ -1959,6 +2030,45 @@ mod tests {
         assert_contains_tool_names(&tools, &["js_repl", "js_repl_reset"]);
     }
 
+    #[test]
+    fn py_repl_requires_feature_flag() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let features = Features::with_defaults();
+
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+
+        assert!(
+            !tools
+                .iter()
+                .any(|tool| matches!(tool.spec.name(), "py_repl" | "py_repl_reset")),
+            "py_repl tools should be disabled when the feature is off"
+        );
+    }
+
+    #[test]
+    fn py_repl_enabled_adds_tools() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::PyRepl);
+
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+        assert_contains_tool_names(&tools, &["py_repl", "py_repl_reset"]);
+    }
+
     #[test]
     fn js_repl_tools_only_filters_model_tools() {
         let config = test_config();
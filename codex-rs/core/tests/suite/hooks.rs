@@ -0,0 +1,114 @@
+#![cfg(not(target_os = "windows"))]
+
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_hooks::CommandHookConfig;
+use codex_hooks::CommandHookOnError;
+use codex_hooks::DEFAULT_COMMAND_HOOK_TIMEOUT;
+use codex_hooks::HookEventKind;
+use codex_hooks::HookMatcher;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+
+use responses::ev_assistant_message;
+use responses::ev_completed;
+use responses::sse;
+use responses::start_mock_server;
+
+fn prompt_hook(script: &str) -> CommandHookConfig {
+    CommandHookConfig {
+        event: HookEventKind::UserPromptSubmit,
+        argv: vec!["/bin/sh".to_string(), "-c".to_string(), script.to_string()],
+        timeout: DEFAULT_COMMAND_HOOK_TIMEOUT,
+        matcher: HookMatcher::default(),
+        on_error: CommandHookOnError::Continue,
+    }
+}
+
+async fn submit_text(codex: &codex_core::CodexThread, text: &str) -> anyhow::Result<()> {
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: text.into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn user_prompt_submit_hook_adds_developer_context() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let response = responses::mount_sse_once(
+        &server,
+        sse(vec![ev_assistant_message("m1", "Done"), ev_completed("r1")]),
+    )
+    .await;
+
+    let hook = prompt_hook(
+        r#"case "$(cat)" in *"fix the flaky test"*) echo '{"additional_context":"Ticket ABC-1: test_sync times out on CI"}' ;; esac"#,
+    );
+    let test = test_codex()
+        .with_config(move |cfg| cfg.hooks = vec![hook])
+        .build(&server)
+        .await?;
+    let codex = &test.codex;
+
+    submit_text(codex, "fix the flaky test").await?;
+    wait_for_event(codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    let request = response.single_request();
+    assert!(
+        request
+            .message_input_texts("developer")
+            .contains(&"Ticket ABC-1: test_sync times out on CI".to_string())
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn user_prompt_submit_hook_blocks_prompt() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let response = responses::mount_sse_once(
+        &server,
+        sse(vec![ev_assistant_message("m1", "Done"), ev_completed("r1")]),
+    )
+    .await;
+
+    let hook = prompt_hook(
+        r#"cat > /dev/null; echo '{"decision":"deny","reason":"prompts must reference a ticket"}'"#,
+    );
+    let test = test_codex()
+        .with_config(move |cfg| cfg.hooks = vec![hook])
+        .build(&server)
+        .await?;
+    let codex = &test.codex;
+
+    submit_text(codex, "do something").await?;
+    let message = wait_for_event_match(codex, |ev| match ev {
+        EventMsg::Error(err) => Some(err.message.clone()),
+        _ => None,
+    })
+    .await;
+    wait_for_event(codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    assert_eq!(
+        message,
+        "Prompt blocked by hook: prompts must reference a ticket"
+    );
+    assert!(response.requests().is_empty());
+
+    Ok(())
+}
@@ -239,6 +239,31 @@ async fn undo_restores_untracked_file_edit() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn undo_restores_files_outside_git_repository() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = undo_harness().await?;
+    let config = harness.path("config.yaml");
+    fs::write(&config, "replicas: 1\n")?;
+
+    let patch = "*** Begin Patch\n*** Update File: config.yaml\n@@\n-replicas: 1\n+replicas: 3\n*** Add File: extra.yaml\n+new: true\n*** End Patch";
+    run_apply_patch_turn(&harness, "scale up", "undo-no-git", patch, "done").await?;
+
+    let extra = harness.path("extra.yaml");
+    assert_eq!(fs::read_to_string(&config)?, "replicas: 3\n");
+    assert_eq!(fs::read_to_string(&extra)?, "new: true\n");
+
+    let codex = Arc::clone(&harness.test().codex);
+    let completed = expect_successful_undo(&codex).await?;
+    assert!(completed.success, "undo failed: {:?}", completed.message);
+
+    assert_eq!(fs::read_to_string(&config)?, "replicas: 1\n");
+    assert!(!extra.exists());
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn undo_reverts_only_latest_turn() -> Result<()> {
     skip_if_no_network!(Ok(()));
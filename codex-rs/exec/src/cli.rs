@@ -211,7 +211,7 @@ pub struct ReviewArgs {
     #[arg(
         long = "uncommitted",
         default_value_t = false,
-        conflicts_with_all = ["base", "commit", "prompt"]
+        conflicts_with_all = ["base", "commit", "range", "patch", "prompt"]
     )]
     pub uncommitted: bool,
 
@@ -219,7 +219,7 @@ pub struct ReviewArgs {
     #[arg(
         long = "base",
         value_name = "BRANCH",
-        conflicts_with_all = ["uncommitted", "commit", "prompt"]
+        conflicts_with_all = ["uncommitted", "commit", "range", "patch", "prompt"]
     )]
     pub base: Option<String>,
 
@@ -227,10 +227,30 @@ pub struct ReviewArgs {
     #[arg(
         long = "commit",
         value_name = "SHA",
-        conflicts_with_all = ["uncommitted", "base", "prompt"]
+        conflicts_with_all = ["uncommitted", "base", "range", "patch", "prompt"]
     )]
     pub commit: Option<String>,
 
+    /// Review the commits in a range, e.g. `main..HEAD` for a PR stack.
+    #[arg(
+        long = "range",
+        value_name = "FROM..TO",
+        conflicts_with_all = ["uncommitted", "base", "commit", "patch", "prompt"]
+    )]
+    pub range: Option<String>,
+
+    /// Review a diff stored in a patch file.
+    #[arg(
+        long = "patch",
+        value_name = "FILE",
+        conflicts_with_all = ["uncommitted", "base", "commit", "range", "prompt"]
+    )]
+    pub patch: Option<PathBuf>,
+
+    /// Only review changes under this path. May be repeated.
+    #[arg(long = "path", value_name = "PATH")]
+    pub paths: Vec<PathBuf>,
+
     /// Optional commit title to display in the review summary.
     #[arg(long = "title", value_name = "TITLE", requires = "commit")]
     pub commit_title: Option<String>,
@@ -238,6 +258,25 @@ pub struct ReviewArgs {
     /// Custom review instructions. If `-` is used, read from stdin.
     #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
     pub prompt: Option<String>,
+
+    /// Emit the review findings in a machine-readable format for CI.
+    #[arg(long = "output-format", value_enum, value_name = "FORMAT")]
+    pub output_format: Option<ReviewOutputFormat>,
+
+    /// Write the --output-format report to this file instead of stdout.
+    #[arg(long = "output-file", value_name = "FILE", requires = "output_format")]
+    pub output_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ReviewOutputFormat {
+    /// SARIF 2.1.0, for GitHub code scanning and similar tools.
+    Sarif,
+    /// Reviewdog diagnostic format (rdjson).
+    Rdjson,
+    /// GitHub Actions workflow commands (`::error file=...::`).
+    GithubAnnotations,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
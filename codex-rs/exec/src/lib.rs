@@ -9,10 +9,12 @@ mod event_processor;
 mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
 pub mod exec_events;
+mod review_output;
 
 pub use cli::Cli;
 pub use cli::Command;
 pub use cli::ReviewArgs;
+pub use cli::ReviewOutputFormat;
 use codex_cloud_requirements::cloud_requirements_loader;
 use codex_core::AuthManager;
 use codex_core::LMSTUDIO_OSS_PROVIDER_ID;
@@ -66,6 +68,7 @@ use uuid::Uuid;
 use crate::cli::Command as ExecCommand;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
+use crate::review_output::render_review_output;
 use codex_core::default_client::set_default_client_residency_requirement;
 use codex_core::default_client::set_default_originator;
 use codex_core::find_thread_path_by_id_str;
@@ -395,10 +398,20 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     } else {
         thread_manager.start_thread(config.clone()).await?
     };
+    let mut review_report = None;
     let (initial_operation, prompt_summary) = match (command, prompt, images) {
         (Some(ExecCommand::Review(review_cli)), _, _) => {
+            if let Some(format) = review_cli.output_format {
+                if json_mode && review_cli.output_file.is_none() {
+                    anyhow::bail!("--output-format requires --output-file when --json is set");
+                }
+                review_report = Some((format, review_cli.output_file.clone()));
+            }
             let review_request = build_review_request(review_cli)?;
-            let summary = codex_core::review_prompts::user_facing_hint(&review_request.target);
+            let summary = codex_core::review_prompts::user_facing_hint_for_paths(
+                &review_request.target,
+                &review_request.paths,
+            );
             (InitialOperation::Review { review_request }, summary)
         }
         (Some(ExecCommand::Resume(args)), root_prompt, imgs) => {
@@ -541,6 +554,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
     let mut shutdown_requested = false;
+    let mut review_output = None;
     while let Some(envelope) = rx.recv().await {
         let ThreadEventEnvelope {
             thread_id,
@@ -582,6 +596,11 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         if thread_id != primary_thread_id && matches!(&event.msg, EventMsg::TurnComplete(_)) {
             continue;
         }
+        if thread_id == primary_thread_id
+            && let EventMsg::ExitedReviewMode(ev) = &event.msg
+        {
+            review_output = ev.review_output.clone();
+        }
         let shutdown = event_processor.process_event(event);
         if thread_id != primary_thread_id && matches!(shutdown, CodexStatus::InitiateShutdown) {
             continue;
@@ -598,7 +617,34 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             CodexStatus::Shutdown => continue,
         }
     }
-    event_processor.print_final_output();
+    match review_report {
+        Some((format, output_file)) => {
+            // An empty report would read as a clean review in CI.
+            let Some(review_output) = review_output else {
+                event_processor.print_final_output();
+                anyhow::bail!(
+                    "the review finished without a structured result; no report was written"
+                );
+            };
+            let report_root = get_git_repo_root(&config.cwd).unwrap_or_else(|| config.cwd.clone());
+            let report = render_review_output(format, &review_output, &report_root);
+            match output_file {
+                Some(path) => {
+                    event_processor.print_final_output();
+                    std::fs::write(&path, report).map_err(|err| {
+                        anyhow::anyhow!(
+                            "failed to write review report to {}: {err}",
+                            path.display()
+                        )
+                    })?;
+                }
+                // Keep stdout machine-readable: the report replaces the final message.
+                #[allow(clippy::print_stdout)]
+                None => print!("{report}"),
+            }
+        }
+        None => event_processor.print_final_output(),
+    }
     if error_seen {
         std::process::exit(1);
     }
@@ -833,6 +879,20 @@ fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
             sha,
             title: args.commit_title,
         }
+    } else if let Some(range) = args.range {
+        let Some((from, to)) = range.split_once("..") else {
+            anyhow::bail!("--range must look like FROM..TO");
+        };
+        let (from, to) = (from.trim(), to.trim());
+        if from.is_empty() {
+            anyhow::bail!("--range must look like FROM..TO");
+        }
+        ReviewTarget::CommitRange {
+            from: from.to_string(),
+            to: if to.is_empty() { "HEAD" } else { to }.to_string(),
+        }
+    } else if let Some(path) = args.patch {
+        ReviewTarget::PatchFile { path }
     } else if let Some(prompt_arg) = args.prompt {
         let prompt = resolve_prompt(Some(prompt_arg)).trim().to_string();
         if prompt.is_empty() {
@@ -843,13 +903,14 @@ fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
         }
     } else {
         anyhow::bail!(
-            "Specify --uncommitted, --base, --commit, or provide custom review instructions"
+            "Specify --uncommitted, --base, --commit, --range, --patch, or provide custom review instructions"
         );
     };
 
     Ok(ReviewRequest {
         target,
         user_facing_hint: None,
+        paths: args.paths,
     })
 }
 
@@ -865,13 +926,19 @@ mod tests {
             base: None,
             commit: None,
             commit_title: None,
+            range: None,
+            patch: None,
+            paths: Vec::new(),
             prompt: None,
+            output_format: None,
+            output_file: None,
         })
         .expect("builds uncommitted review request");
 
         let expected = ReviewRequest {
             target: ReviewTarget::UncommittedChanges,
             user_facing_hint: None,
+            paths: Vec::new(),
         };
 
         assert_eq!(request, expected);
@@ -884,7 +951,12 @@ mod tests {
             base: None,
             commit: Some("123456789".to_string()),
             commit_title: Some("Add review command".to_string()),
+            range: None,
+            patch: None,
+            paths: Vec::new(),
             prompt: None,
+            output_format: None,
+            output_file: None,
         })
         .expect("builds commit review request");
 
@@ -894,6 +966,7 @@ mod tests {
                 title: Some("Add review command".to_string()),
             },
             user_facing_hint: None,
+            paths: Vec::new(),
         };
 
         assert_eq!(request, expected);
@@ -906,7 +979,12 @@ mod tests {
             base: None,
             commit: None,
             commit_title: None,
+            range: None,
+            patch: None,
+            paths: Vec::new(),
             prompt: Some("  custom review instructions  ".to_string()),
+            output_format: None,
+            output_file: None,
         })
         .expect("builds custom review request");
 
@@ -915,6 +993,35 @@ mod tests {
                 instructions: "custom review instructions".to_string(),
             },
             user_facing_hint: None,
+            paths: Vec::new(),
+        };
+
+        assert_eq!(request, expected);
+    }
+
+    #[test]
+    fn builds_commit_range_review_request_with_paths() {
+        let request = build_review_request(ReviewArgs {
+            uncommitted: false,
+            base: None,
+            commit: None,
+            commit_title: None,
+            range: Some("main..".to_string()),
+            patch: None,
+            paths: vec![PathBuf::from("core")],
+            prompt: None,
+            output_format: None,
+            output_file: None,
+        })
+        .expect("builds commit range review request");
+
+        let expected = ReviewRequest {
+            target: ReviewTarget::CommitRange {
+                from: "main".to_string(),
+                to: "HEAD".to_string(),
+            },
+            user_facing_hint: None,
+            paths: vec![PathBuf::from("core")],
         };
 
         assert_eq!(request, expected);
@@ -0,0 +1,325 @@
+//! Machine-readable renderings of a structured review result for CI tooling.
+
+use std::path::Path;
+
+use codex_core::protocol::ReviewFinding;
+use codex_core::protocol::ReviewOutputEvent;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::cli::ReviewOutputFormat;
+
+const TOOL_NAME: &str = "codex";
+const TOOL_URL: &str = "https://github.com/openai/codex";
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const RULE_ID: &str = "codex-review";
+
+/// Render `output` in `format`. File paths are made relative to `root`, the
+/// repository root, when they live inside it, which is what code scanning and
+/// reviewdog expect.
+pub(crate) fn render_review_output(
+    format: ReviewOutputFormat,
+    output: &ReviewOutputEvent,
+    root: &Path,
+) -> String {
+    match format {
+        ReviewOutputFormat::Sarif => to_pretty_json(&sarif(output, root)),
+        ReviewOutputFormat::Rdjson => to_pretty_json(&rdjson(output, root)),
+        ReviewOutputFormat::GithubAnnotations => github_annotations(output, root),
+    }
+}
+
+fn to_pretty_json(value: &Value) -> String {
+    let mut rendered = serde_json::to_string_pretty(value).unwrap_or_default();
+    rendered.push('\n');
+    rendered
+}
+
+fn sarif(output: &ReviewOutputEvent, root: &Path) -> Value {
+    let results = output
+        .findings
+        .iter()
+        .map(|finding| {
+            json!({
+                "ruleId": RULE_ID,
+                "level": Severity::of(finding).sarif_level(),
+                "message": { "text": message(finding) },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": relative_path(finding, root) },
+                        "region": {
+                            "startLine": start_line(finding),
+                            "endLine": end_line(finding),
+                        },
+                    },
+                }],
+                "properties": {
+                    "priority": finding.priority,
+                    "confidence": finding.confidence_score,
+                },
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": TOOL_NAME,
+                    "informationUri": TOOL_URL,
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": [{
+                        "id": RULE_ID,
+                        "shortDescription": { "text": "Codex code review finding" },
+                    }],
+                },
+            },
+            "results": results,
+            "properties": {
+                "overallCorrectness": output.overall_correctness,
+                "overallExplanation": output.overall_explanation,
+                "overallConfidence": output.overall_confidence_score,
+            },
+        }],
+    })
+}
+
+fn rdjson(output: &ReviewOutputEvent, root: &Path) -> Value {
+    let diagnostics = output
+        .findings
+        .iter()
+        .map(|finding| {
+            json!({
+                "message": message(finding),
+                "location": {
+                    "path": relative_path(finding, root),
+                    "range": {
+                        "start": { "line": start_line(finding) },
+                        "end": { "line": end_line(finding) },
+                    },
+                },
+                "severity": Severity::of(finding).rdjson_severity(),
+                "code": { "value": format!("P{}", finding.priority) },
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "source": { "name": TOOL_NAME, "url": TOOL_URL },
+        "diagnostics": diagnostics,
+    })
+}
+
+/// GitHub Actions workflow commands, one `::error`/`::warning`/`::notice`
+/// line per finding.
+fn github_annotations(output: &ReviewOutputEvent, root: &Path) -> String {
+    let mut rendered = String::new();
+    for finding in &output.findings {
+        rendered.push_str(&format!(
+            "::{} file={},line={},endLine={},title={}::{}\n",
+            Severity::of(finding).annotation_command(),
+            escape_property(&relative_path(finding, root)),
+            start_line(finding),
+            end_line(finding),
+            escape_property(&finding.title),
+            escape_data(finding.body.trim()),
+        ));
+    }
+    rendered
+}
+
+#[derive(Clone, Copy)]
+enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl Severity {
+    /// P0 and P1 block the change, P2 should be fixed eventually and P3 is a
+    /// nice-to-have.
+    fn of(finding: &ReviewFinding) -> Self {
+        match finding.priority {
+            i32::MIN..=1 => Self::Error,
+            2 => Self::Warning,
+            _ => Self::Note,
+        }
+    }
+
+    fn sarif_level(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Note => "note",
+        }
+    }
+
+    fn rdjson_severity(self) -> &'static str {
+        match self {
+            Self::Error => "ERROR",
+            Self::Warning => "WARNING",
+            Self::Note => "INFO",
+        }
+    }
+
+    fn annotation_command(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Note => "notice",
+        }
+    }
+}
+
+fn message(finding: &ReviewFinding) -> String {
+    let body = finding.body.trim();
+    if body.is_empty() {
+        finding.title.clone()
+    } else {
+        format!("{}\n\n{body}", finding.title)
+    }
+}
+
+fn relative_path(finding: &ReviewFinding, root: &Path) -> String {
+    let path = &finding.code_location.absolute_file_path;
+    let path = path.strip_prefix(root).unwrap_or(path);
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn start_line(finding: &ReviewFinding) -> u32 {
+    finding.code_location.line_range.start.max(1)
+}
+
+fn end_line(finding: &ReviewFinding) -> u32 {
+    finding
+        .code_location
+        .line_range
+        .end
+        .max(start_line(finding))
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::ReviewCodeLocation;
+    use codex_core::protocol::ReviewLineRange;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn review_output() -> ReviewOutputEvent {
+        ReviewOutputEvent {
+            findings: vec![
+                ReviewFinding {
+                    title: "[P1] Handle empty input".to_string(),
+                    body: "`parse` panics on an empty slice.\nReturn an error instead.".to_string(),
+                    confidence_score: 0.75,
+                    priority: 1,
+                    code_location: ReviewCodeLocation {
+                        absolute_file_path: PathBuf::from("/repo/src/lib.rs"),
+                        line_range: ReviewLineRange { start: 10, end: 12 },
+                    },
+                },
+                ReviewFinding {
+                    title: "[P3] Typo, in comment".to_string(),
+                    body: String::new(),
+                    confidence_score: 0.5,
+                    priority: 3,
+                    code_location: ReviewCodeLocation {
+                        absolute_file_path: PathBuf::from("/elsewhere/notes.md"),
+                        line_range: ReviewLineRange { start: 0, end: 0 },
+                    },
+                },
+            ],
+            overall_correctness: "patch is incorrect".to_string(),
+            overall_explanation: "Empty input crashes.".to_string(),
+            overall_confidence_score: 0.7,
+        }
+    }
+
+    #[test]
+    fn sarif_reports_relative_locations_and_levels() {
+        let rendered = render_review_output(
+            ReviewOutputFormat::Sarif,
+            &review_output(),
+            Path::new("/repo"),
+        );
+        let sarif: Value = serde_json::from_str(&rendered).expect("valid json");
+
+        assert_eq!(sarif["version"], json!("2.1.0"));
+        assert_eq!(
+            sarif["runs"][0]["results"][0],
+            json!({
+                "ruleId": "codex-review",
+                "level": "error",
+                "message": {
+                    "text": "[P1] Handle empty input\n\n`parse` panics on an empty slice.\nReturn an error instead.",
+                },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": "src/lib.rs" },
+                        "region": { "startLine": 10, "endLine": 12 },
+                    },
+                }],
+                "properties": { "priority": 1, "confidence": 0.75 },
+            })
+        );
+        assert_eq!(sarif["runs"][0]["results"][1]["level"], json!("note"));
+        assert_eq!(
+            sarif["runs"][0]["results"][1]["locations"][0]["physicalLocation"],
+            json!({
+                "artifactLocation": { "uri": "/elsewhere/notes.md" },
+                "region": { "startLine": 1, "endLine": 1 },
+            })
+        );
+    }
+
+    #[test]
+    fn rdjson_lists_diagnostics() {
+        let rendered = render_review_output(
+            ReviewOutputFormat::Rdjson,
+            &review_output(),
+            Path::new("/repo"),
+        );
+        let rdjson: Value = serde_json::from_str(&rendered).expect("valid json");
+
+        assert_eq!(
+            rdjson["diagnostics"][0],
+            json!({
+                "message": "[P1] Handle empty input\n\n`parse` panics on an empty slice.\nReturn an error instead.",
+                "location": {
+                    "path": "src/lib.rs",
+                    "range": { "start": { "line": 10 }, "end": { "line": 12 } },
+                },
+                "severity": "ERROR",
+                "code": { "value": "P1" },
+            })
+        );
+        assert_eq!(rdjson["diagnostics"][1]["severity"], json!("INFO"));
+    }
+
+    #[test]
+    fn github_annotations_escape_workflow_command_values() {
+        assert_eq!(
+            render_review_output(
+                ReviewOutputFormat::GithubAnnotations,
+                &review_output(),
+                Path::new("/repo"),
+            ),
+            "::error file=src/lib.rs,line=10,endLine=12,title=[P1] Handle empty input::`parse` panics on an empty slice.%0AReturn an error instead.\n\
+             ::notice file=/elsewhere/notes.md,line=1,endLine=1,title=[P3] Typo%2C in comment::\n"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn review_writes_sarif_report() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let file_path = test.cwd_path().join("src/lib.rs");
+
+    let review_json = json!({
+        "findings": [{
+            "title": "[P2] Avoid unwrap",
+            "body": "This panics when the config is missing.",
+            "confidence_score": 0.5,
+            "priority": 2,
+            "code_location": {
+                "absolute_file_path": file_path,
+                "line_range": {"start": 4, "end": 6}
+            }
+        }],
+        "overall_correctness": "patch is incorrect",
+        "overall_explanation": "One panic path.",
+        "overall_confidence_score": 0.5
+    })
+    .to_string();
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", &review_json),
+        responses::ev_completed("resp1"),
+    ]);
+    responses::mount_sse_once(&server, body).await;
+
+    let report_path = test.cwd_path().join("review.sarif");
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("-C")
+        .arg(test.cwd_path())
+        .arg("review")
+        .arg("--output-format")
+        .arg("sarif")
+        .arg("--output-file")
+        .arg(&report_path)
+        .arg("check error handling")
+        .assert()
+        .success();
+
+    let sarif: Value = serde_json::from_str(&std::fs::read_to_string(&report_path)?)?;
+    assert_eq!(
+        sarif["runs"][0]["results"],
+        json!([{
+            "ruleId": "codex-review",
+            "level": "warning",
+            "message": {"text": "[P2] Avoid unwrap\n\nThis panics when the config is missing."},
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": {"uri": "src/lib.rs"},
+                    "region": {"startLine": 4, "endLine": 6},
+                },
+            }],
+            "properties": {"priority": 2, "confidence": 0.5},
+        }])
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn review_report_paths_are_relative_to_the_repo_root() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    std::fs::create_dir_all(test.cwd_path().join(".git"))?;
+    let subdir = test.cwd_path().join("src");
+    std::fs::create_dir_all(&subdir)?;
+
+    let review_json = json!({
+        "findings": [{
+            "title": "[P1] Missing bounds check",
+            "body": "Indexing can go out of range.",
+            "confidence_score": 0.8,
+            "priority": 1,
+            "code_location": {
+                "absolute_file_path": subdir.join("lib.rs"),
+                "line_range": {"start": 2, "end": 2}
+            }
+        }],
+        "overall_correctness": "patch is incorrect",
+        "overall_explanation": "One out-of-range index.",
+        "overall_confidence_score": 0.8
+    })
+    .to_string();
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", &review_json),
+        responses::ev_completed("resp1"),
+    ]);
+    responses::mount_sse_once(&server, body).await;
+
+    let report_path = test.cwd_path().join("review.rdjson");
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("-C")
+        .arg(&subdir)
+        .arg("review")
+        .arg("--output-format")
+        .arg("rdjson")
+        .arg("--output-file")
+        .arg(&report_path)
+        .arg("check bounds")
+        .assert()
+        .success();
+
+    let rdjson: Value = serde_json::from_str(&std::fs::read_to_string(&report_path)?)?;
+    assert_eq!(
+        rdjson["diagnostics"][0]["location"]["path"],
+        json!("src/lib.rs")
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn review_without_structured_result_fails_without_a_report() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_completed("resp1"),
+    ]);
+    responses::mount_sse_once(&server, body).await;
+
+    let report_path = test.cwd_path().join("review.sarif");
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("-C")
+        .arg(test.cwd_path())
+        .arg("review")
+        .arg("--output-format")
+        .arg("sarif")
+        .arg("--output-file")
+        .arg(&report_path)
+        .arg("check error handling")
+        .assert()
+        .failure();
+
+    assert!(!report_path.exists());
+
+    Ok(())
+}
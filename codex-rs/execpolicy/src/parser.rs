@@ -11,6 +11,8 @@ use starlark::values::Value;
 use starlark::values::list::ListRef;
 use starlark::values::list::UnpackList;
 use starlark::values::none::NoneType;
+use starlark::values::structs::AllocStruct;
+use starlark::values::structs::StructRef;
 use std::cell::RefCell;
 use std::cell::RefMut;
 use std::sync::Arc;
@@ -18,9 +20,12 @@ use std::sync::Arc;
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
+use crate::rule::ArgumentRule;
+use crate::rule::GlobToken;
 use crate::rule::PatternToken;
 use crate::rule::PrefixPattern;
 use crate::rule::PrefixRule;
+use crate::rule::RegexToken;
 use crate::rule::RuleRef;
 use crate::rule::validate_match_examples;
 use crate::rule::validate_not_match_examples;
@@ -106,6 +111,8 @@ fn parse_pattern<'v>(pattern: UnpackList<Value<'v>>) -> Result<Vec<PatternToken>
 fn parse_pattern_token<'v>(value: Value<'v>) -> Result<PatternToken> {
     if let Some(s) = value.unpack_str() {
         Ok(PatternToken::Single(s.to_string()))
+    } else if let Some(matcher) = StructRef::from_value(value) {
+        parse_matcher_token(matcher)
     } else if let Some(list) = ListRef::from_value(value) {
         let tokens: Vec<String> = list
             .content()
@@ -132,12 +139,68 @@ fn parse_pattern_token<'v>(value: Value<'v>) -> Result<PatternToken> {
         }
     } else {
         Err(Error::InvalidPattern(format!(
-            "pattern element must be a string or list of strings (got {})",
+            "pattern element must be a string, list of strings, regex() or glob() (got {})",
             value.get_type()
         )))
     }
 }
 
+/// Decodes the structs produced by the `regex()` and `glob()` builtins.
+fn parse_matcher_token(matcher: StructRef<'_>) -> Result<PatternToken> {
+    let fields: Vec<(String, Option<String>)> = matcher
+        .iter()
+        .map(|(key, value)| {
+            (
+                key.as_str().to_string(),
+                value.unpack_str().map(str::to_string),
+            )
+        })
+        .collect();
+    match fields.as_slice() {
+        [(kind, Some(source))] if kind == "regex" => {
+            Ok(PatternToken::Regex(RegexToken::new(source)?))
+        }
+        [(kind, Some(source))] if kind == "glob" => Ok(PatternToken::Glob(GlobToken::new(source))),
+        _ => Err(Error::InvalidPattern(
+            "pattern struct must come from regex() or glob()".to_string(),
+        )),
+    }
+}
+
+fn parse_program(value: Value<'_>) -> Result<Vec<String>> {
+    let token = parse_pattern_token(value)?;
+    if !token.is_literal() {
+        return Err(Error::InvalidPattern(
+            "program must be a string or list of strings".to_string(),
+        ));
+    }
+    Ok(token.alternatives().to_vec())
+}
+
+fn parse_justification(justification: Option<&str>) -> Result<Option<String>> {
+    match justification {
+        Some(raw) if raw.trim().is_empty() => Err(Error::InvalidRule(
+            "justification cannot be empty".to_string(),
+        )),
+        Some(raw) => Ok(Some(raw.to_string())),
+        None => Ok(None),
+    }
+}
+
+fn validate_examples(
+    rules: &[RuleRef],
+    r#match: Option<UnpackList<Value<'_>>>,
+    not_match: Option<UnpackList<Value<'_>>>,
+) -> Result<()> {
+    let matches: Vec<Vec<String>> = r#match.map(parse_examples).transpose()?.unwrap_or_default();
+    let not_matches: Vec<Vec<String>> = not_match
+        .map(parse_examples)
+        .transpose()?
+        .unwrap_or_default();
+    validate_not_match_examples(rules, &not_matches)?;
+    validate_match_examples(rules, &matches)
+}
+
 fn parse_examples<'v>(examples: UnpackList<Value<'v>>) -> Result<Vec<Vec<String>>> {
     examples.items.into_iter().map(parse_example).collect()
 }
@@ -213,6 +276,7 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         r#match: Option<UnpackList<Value<'v>>>,
         not_match: Option<UnpackList<Value<'v>>>,
         justification: Option<&'v str>,
+        path_roots: Option<UnpackList<&'v str>>,
         eval: &mut Evaluator<'v, '_, '_>,
     ) -> anyhow::Result<NoneType> {
         let decision = match decision {
@@ -220,28 +284,27 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
             None => Decision::Allow,
         };
 
-        let justification = match justification {
-            Some(raw) if raw.trim().is_empty() => {
-                return Err(Error::InvalidRule("justification cannot be empty".to_string()).into());
+        let justification = parse_justification(justification)?;
+
+        let path_roots: Vec<String> = match path_roots {
+            Some(roots) if roots.items.is_empty() => {
+                return Err(Error::InvalidRule("path_roots cannot be empty".to_string()).into());
             }
-            Some(raw) => Some(raw.to_string()),
-            None => None,
+            Some(roots) => roots.items.into_iter().map(str::to_string).collect(),
+            None => Vec::new(),
         };
 
         let pattern_tokens = parse_pattern(pattern)?;
 
-        let matches: Vec<Vec<String>> =
-            r#match.map(parse_examples).transpose()?.unwrap_or_default();
-        let not_matches: Vec<Vec<String>> = not_match
-            .map(parse_examples)
-            .transpose()?
-            .unwrap_or_default();
-
-        let mut builder = policy_builder(eval);
-
         let (first_token, remaining_tokens) = pattern_tokens
             .split_first()
             .ok_or_else(|| Error::InvalidPattern("pattern cannot be empty".to_string()))?;
+        if !first_token.is_literal() {
+            return Err(Error::InvalidPattern(
+                "first pattern element must be a string or list of strings".to_string(),
+            )
+            .into());
+        }
 
         let rest: Arc<[PatternToken]> = remaining_tokens.to_vec().into();
 
@@ -256,14 +319,64 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
                     },
                     decision,
                     justification: justification.clone(),
+                    path_roots: path_roots.clone(),
+                }) as RuleRef
+            })
+            .collect();
+
+        validate_examples(&rules, r#match, not_match)?;
+
+        let mut builder = policy_builder(eval);
+        rules.into_iter().for_each(|rule| builder.add_rule(rule));
+        Ok(NoneType)
+    }
+
+    /// Matches `program` invocations where any argument, at any position,
+    /// matches `argument`.
+    fn argument_rule<'v>(
+        program: Value<'v>,
+        argument: Value<'v>,
+        decision: Option<&'v str>,
+        r#match: Option<UnpackList<Value<'v>>>,
+        not_match: Option<UnpackList<Value<'v>>>,
+        justification: Option<&'v str>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<NoneType> {
+        let decision = match decision {
+            Some(raw) => Decision::parse(raw)?,
+            None => Decision::Allow,
+        };
+        let justification = parse_justification(justification)?;
+        let programs = parse_program(program)?;
+        let argument = parse_pattern_token(argument)?;
+
+        let rules: Vec<RuleRef> = programs
+            .iter()
+            .map(|program| {
+                Arc::new(ArgumentRule {
+                    program: Arc::from(program.as_str()),
+                    argument: argument.clone(),
+                    decision,
+                    justification: justification.clone(),
                 }) as RuleRef
             })
             .collect();
 
-        validate_not_match_examples(&rules, &not_matches)?;
-        validate_match_examples(&rules, &matches)?;
+        validate_examples(&rules, r#match, not_match)?;
 
+        let mut builder = policy_builder(eval);
         rules.into_iter().for_each(|rule| builder.add_rule(rule));
         Ok(NoneType)
     }
+
+    /// A pattern element matching any token that the regex fully matches.
+    fn regex<'v>(pattern: &'v str, eval: &mut Evaluator<'v, '_, '_>) -> anyhow::Result<Value<'v>> {
+        RegexToken::new(pattern)?;
+        Ok(eval.heap().alloc(AllocStruct([("regex", pattern)])))
+    }
+
+    /// A pattern element matching any token that the `*`/`?` glob fully matches.
+    fn glob<'v>(pattern: &'v str, eval: &mut Evaluator<'v, '_, '_>) -> anyhow::Result<Value<'v>> {
+        Ok(eval.heap().alloc(AllocStruct([("glob", pattern)])))
+    }
 }
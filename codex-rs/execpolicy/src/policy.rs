@@ -1,6 +1,7 @@
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
+use crate::rule::PathContext;
 use crate::rule::PatternToken;
 use crate::rule::PrefixPattern;
 use crate::rule::PrefixRule;
@@ -39,7 +40,14 @@ impl Policy {
                 let Some(prefix_rule) = rule.as_any().downcast_ref::<PrefixRule>() else {
                     continue;
                 };
-                if prefix_rule.decision != Decision::Allow {
+                if prefix_rule.decision != Decision::Allow
+                    || !prefix_rule.path_roots.is_empty()
+                    || !prefix_rule
+                        .pattern
+                        .rest
+                        .iter()
+                        .all(PatternToken::is_literal)
+                {
                     continue;
                 }
 
@@ -71,6 +79,7 @@ impl Policy {
             },
             decision,
             justification: None,
+            path_roots: Vec::new(),
         });
 
         self.rules_by_program.insert(first_token.clone(), rule);
@@ -91,6 +100,21 @@ impl Policy {
         commands: Commands,
         heuristics_fallback: &F,
     ) -> Evaluation
+    where
+        Commands: IntoIterator,
+        Commands::Item: AsRef<[String]>,
+        F: Fn(&[String]) -> Decision,
+    {
+        self.check_multiple_in(commands, heuristics_fallback, PathContext::Lexical)
+    }
+
+    /// Like [`Policy::check_multiple`], with `path_roots` resolved in `paths`.
+    pub fn check_multiple_in<Commands, F>(
+        &self,
+        commands: Commands,
+        heuristics_fallback: &F,
+        paths: PathContext<'_>,
+    ) -> Evaluation
     where
         Commands: IntoIterator,
         Commands::Item: AsRef<[String]>,
@@ -99,7 +123,7 @@ impl Policy {
         let matched_rules: Vec<RuleMatch> = commands
             .into_iter()
             .flat_map(|command| {
-                self.matches_for_command(command.as_ref(), Some(heuristics_fallback))
+                self.matches_for_command_in(command.as_ref(), Some(heuristics_fallback), paths)
             })
             .collect();
 
@@ -117,12 +141,26 @@ impl Policy {
         &self,
         cmd: &[String],
         heuristics_fallback: HeuristicsFallback<'_>,
+    ) -> Vec<RuleMatch> {
+        self.matches_for_command_in(cmd, heuristics_fallback, PathContext::Lexical)
+    }
+
+    fn matches_for_command_in(
+        &self,
+        cmd: &[String],
+        heuristics_fallback: HeuristicsFallback<'_>,
+        paths: PathContext<'_>,
     ) -> Vec<RuleMatch> {
         let matched_rules: Vec<RuleMatch> = match cmd.first() {
             Some(first) => self
                 .rules_by_program
                 .get_vec(first)
-                .map(|rules| rules.iter().filter_map(|rule| rule.matches(cmd)).collect())
+                .map(|rules| {
+                    rules
+                        .iter()
+                        .filter_map(|rule| rule.matches_in(cmd, paths))
+                        .collect()
+                })
                 .unwrap_or_default(),
             None => Vec::new(),
         };
@@ -144,6 +182,8 @@ fn render_pattern_token(token: &PatternToken) -> String {
     match token {
         PatternToken::Single(value) => value.clone(),
         PatternToken::Alts(alternatives) => format!("[{}]", alternatives.join("|")),
+        PatternToken::Regex(regex) => format!("regex({:?})", regex.as_str()),
+        PatternToken::Glob(glob) => format!("glob({:?})", glob.as_str()),
     }
 }
 
@@ -6,31 +6,111 @@ use serde::Serialize;
 use shlex::try_join;
 use std::any::Any;
 use std::fmt::Debug;
+use std::path::Component;
+use std::path::Path;
 use std::sync::Arc;
+use wildmatch::WildMatchPattern;
 
-/// Matches a single command token, either a fixed string or one of several allowed alternatives.
+/// Matches a single command token: a fixed string, one of several allowed alternatives, or a
+/// regex/glob that must match the whole token.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum PatternToken {
     Single(String),
     Alts(Vec<String>),
+    Regex(RegexToken),
+    Glob(GlobToken),
 }
 
 impl PatternToken {
-    fn matches(&self, token: &str) -> bool {
+    pub fn matches(&self, token: &str) -> bool {
         match self {
             Self::Single(expected) => expected == token,
             Self::Alts(alternatives) => alternatives.iter().any(|alt| alt == token),
+            Self::Regex(regex) => regex.matches(token),
+            Self::Glob(glob) => glob.matches(token),
         }
     }
 
+    /// Literal strings accepted by this token; empty for regex and glob tokens.
     pub fn alternatives(&self) -> &[String] {
         match self {
             Self::Single(expected) => std::slice::from_ref(expected),
             Self::Alts(alternatives) => alternatives,
+            Self::Regex(_) | Self::Glob(_) => &[],
+        }
+    }
+
+    pub fn is_literal(&self) -> bool {
+        matches!(self, Self::Single(_) | Self::Alts(_))
+    }
+}
+
+/// A regular expression that must match an entire command token.
+#[derive(Clone, Debug)]
+pub struct RegexToken {
+    source: String,
+    regex: regex_lite::Regex,
+}
+
+impl RegexToken {
+    pub fn new(source: &str) -> Result<Self> {
+        let regex = regex_lite::Regex::new(&format!("^(?:{source})$"))
+            .map_err(|err| Error::InvalidPattern(format!("invalid regex `{source}`: {err}")))?;
+        Ok(Self {
+            source: source.to_string(),
+            regex,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    fn matches(&self, token: &str) -> bool {
+        self.regex.is_match(token)
+    }
+}
+
+impl PartialEq for RegexToken {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for RegexToken {}
+
+/// A `*`/`?` glob that must match an entire command token. `*` also matches `/`.
+#[derive(Clone, Debug)]
+pub struct GlobToken {
+    source: String,
+    pattern: WildMatchPattern<'*', '?'>,
+}
+
+impl GlobToken {
+    pub fn new(source: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            pattern: WildMatchPattern::new(source),
         }
     }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    fn matches(&self, token: &str) -> bool {
+        self.pattern.matches(token)
+    }
 }
 
+impl PartialEq for GlobToken {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for GlobToken {}
+
 /// Prefix matcher for commands with support for alternative match tokens.
 /// First token is fixed since we key by the first token in policy.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -70,6 +150,14 @@ pub enum RuleMatch {
         #[serde(skip_serializing_if = "Option::is_none")]
         justification: Option<String>,
     },
+    ArgumentRuleMatch {
+        program: String,
+        #[serde(rename = "matchedArgument")]
+        matched_argument: String,
+        decision: Decision,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        justification: Option<String>,
+    },
     HeuristicsRuleMatch {
         command: Vec<String>,
         decision: Decision,
@@ -80,9 +168,18 @@ impl RuleMatch {
     pub fn decision(&self) -> Decision {
         match self {
             Self::PrefixRuleMatch { decision, .. } => *decision,
+            Self::ArgumentRuleMatch { decision, .. } => *decision,
             Self::HeuristicsRuleMatch { decision, .. } => *decision,
         }
     }
+
+    pub fn justification(&self) -> Option<&str> {
+        match self {
+            Self::PrefixRuleMatch { justification, .. }
+            | Self::ArgumentRuleMatch { justification, .. } => justification.as_deref(),
+            Self::HeuristicsRuleMatch { .. } => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -90,12 +187,77 @@ pub struct PrefixRule {
     pub pattern: PrefixPattern,
     pub decision: Decision,
     pub justification: Option<String>,
+    /// When non-empty, restricts the rule by the path arguments after the
+    /// prefix (compared lexically; `.` is the working directory). An `allow`
+    /// rule only matches if every path lies under one of these roots; a
+    /// `prompt` or `forbidden` rule matches if any path does, so unrelated
+    /// paths cannot be used to slip past it.
+    pub path_roots: Vec<String>,
+}
+
+/// Where a command's relative path arguments resolve, for `path_roots`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PathContext<'a> {
+    /// Compare paths as written; `.` is whatever directory the command runs in.
+    #[default]
+    Lexical,
+    /// The command runs in this absolute directory, which is also the one `.`
+    /// in `path_roots` refers to. Relative paths and roots resolve against it.
+    Cwd(&'a Path),
+    /// The command runs somewhere else or changes directory, so relative
+    /// paths cannot be placed: they never satisfy an `allow` rule and always
+    /// trigger a `prompt` or `forbidden` one.
+    Unknown,
+}
+
+impl PrefixRule {
+    fn paths_within_roots(&self, args: &[String], paths: PathContext<'_>) -> bool {
+        if self.path_roots.is_empty() {
+            return true;
+        }
+        match self.decision {
+            Decision::Allow => {
+                path_arguments(args).all(|arg| self.path_within_roots(arg, paths) == Some(true))
+            }
+            Decision::Prompt | Decision::Forbidden => {
+                path_arguments(args).any(|arg| self.path_within_roots(arg, paths) != Some(false))
+            }
+        }
+    }
+
+    /// `None` when `arg` cannot be placed relative to some root.
+    fn path_within_roots(&self, arg: &str, paths: PathContext<'_>) -> Option<bool> {
+        let mut unknown = false;
+        for root in &self.path_roots {
+            match is_path_within_root_in(arg, root, paths) {
+                Some(true) => return Some(true),
+                Some(false) => {}
+                None => unknown = true,
+            }
+        }
+        (!unknown).then_some(false)
+    }
+}
+
+/// Matches when any argument after the program matches `argument`, wherever
+/// it appears in argv.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArgumentRule {
+    pub program: Arc<str>,
+    pub argument: PatternToken,
+    pub decision: Decision,
+    pub justification: Option<String>,
 }
 
 pub trait Rule: Any + Debug + Send + Sync {
     fn program(&self) -> &str;
 
-    fn matches(&self, cmd: &[String]) -> Option<RuleMatch>;
+    /// Matches `cmd` with its path arguments compared as written.
+    fn matches(&self, cmd: &[String]) -> Option<RuleMatch> {
+        self.matches_in(cmd, PathContext::Lexical)
+    }
+
+    fn matches_in(&self, cmd: &[String], paths: PathContext<'_>) -> Option<RuleMatch>;
 
     fn as_any(&self) -> &dyn Any;
 }
@@ -107,14 +269,40 @@ impl Rule for PrefixRule {
         self.pattern.first.as_ref()
     }
 
-    fn matches(&self, cmd: &[String]) -> Option<RuleMatch> {
-        self.pattern
-            .matches_prefix(cmd)
-            .map(|matched_prefix| RuleMatch::PrefixRuleMatch {
-                matched_prefix,
-                decision: self.decision,
-                justification: self.justification.clone(),
-            })
+    fn matches_in(&self, cmd: &[String], paths: PathContext<'_>) -> Option<RuleMatch> {
+        let matched_prefix = self.pattern.matches_prefix(cmd)?;
+        if !self.paths_within_roots(&cmd[matched_prefix.len()..], paths) {
+            return None;
+        }
+        Some(RuleMatch::PrefixRuleMatch {
+            matched_prefix,
+            decision: self.decision,
+            justification: self.justification.clone(),
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Rule for ArgumentRule {
+    fn program(&self) -> &str {
+        self.program.as_ref()
+    }
+
+    fn matches_in(&self, cmd: &[String], _paths: PathContext<'_>) -> Option<RuleMatch> {
+        let (program, args) = cmd.split_first()?;
+        if program != self.program.as_ref() {
+            return None;
+        }
+        let matched_argument = args.iter().find(|arg| self.argument.matches(arg))?;
+        Some(RuleMatch::ArgumentRuleMatch {
+            program: program.clone(),
+            matched_argument: matched_argument.clone(),
+            decision: self.decision,
+            justification: self.justification.clone(),
+        })
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -122,6 +310,98 @@ impl Rule for PrefixRule {
     }
 }
 
+/// Arguments that look like paths: everything that is not an option, the
+/// value of `--option=value` arguments, path-like values attached to short
+/// options (`-C/etc`), plus everything after a `--` separator.
+fn path_arguments(args: &[String]) -> impl Iterator<Item = &str> {
+    let mut options_ended = false;
+    args.iter().filter_map(move |arg| {
+        if options_ended {
+            return Some(arg.as_str());
+        }
+        if arg == "--" {
+            options_ended = true;
+            return None;
+        }
+        if let Some(option) = arg.strip_prefix("--") {
+            return option.split_once('=').map(|(_, value)| value);
+        }
+        if let Some(flags) = arg.strip_prefix('-') {
+            return flags
+                .get(1..)
+                .filter(|value| value.contains('/') || value.starts_with(['.', '~']));
+        }
+        Some(arg.as_str())
+    })
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum PathAnchor {
+    Relative,
+    Absolute,
+    Home,
+}
+
+/// Lexically normalizes `path`, returning `None` when a relative path climbs
+/// above its starting point.
+fn normalize_path(path: &str) -> Option<(PathAnchor, Vec<String>)> {
+    let (anchor, rest) = if path == "~" {
+        (PathAnchor::Home, "")
+    } else if let Some(rest) = path.strip_prefix("~/") {
+        (PathAnchor::Home, rest)
+    } else if path.starts_with('/') {
+        (PathAnchor::Absolute, path)
+    } else {
+        (PathAnchor::Relative, path)
+    };
+
+    let mut components = Vec::new();
+    for component in Path::new(rest).components() {
+        match component {
+            Component::Normal(part) => components.push(part.to_string_lossy().into_owned()),
+            Component::ParentDir => {
+                if components.pop().is_none() && anchor != PathAnchor::Absolute {
+                    return None;
+                }
+            }
+            Component::CurDir | Component::RootDir | Component::Prefix(_) => {}
+        }
+    }
+    Some((anchor, components))
+}
+
+fn is_relative(path: &str) -> bool {
+    !(path == "~" || path.starts_with("~/") || path.starts_with('/'))
+}
+
+fn is_path_within_root_in(path: &str, root: &str, paths: PathContext<'_>) -> Option<bool> {
+    match paths {
+        PathContext::Lexical => Some(is_path_within_root(path, root)),
+        PathContext::Cwd(cwd) => {
+            let resolve = |path: &str| {
+                if is_relative(path) {
+                    format!("{}/{path}", cwd.to_string_lossy())
+                } else {
+                    path.to_string()
+                }
+            };
+            Some(is_path_within_root(&resolve(path), &resolve(root)))
+        }
+        PathContext::Unknown => {
+            (!is_relative(path) && !is_relative(root)).then(|| is_path_within_root(path, root))
+        }
+    }
+}
+
+fn is_path_within_root(path: &str, root: &str) -> bool {
+    let (Some((path_anchor, path)), Some((root_anchor, root))) =
+        (normalize_path(path), normalize_path(root))
+    else {
+        return false;
+    };
+    path_anchor == root_anchor && path.starts_with(&root)
+}
+
 /// Count how many rules match each provided example and error if any example is unmatched.
 pub(crate) fn validate_match_examples(rules: &[RuleRef], matches: &[Vec<String>]) -> Result<()> {
     let mut unmatched_examples = Vec::new();
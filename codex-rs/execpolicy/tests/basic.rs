@@ -1,5 +1,6 @@
 use std::any::Any;
 use std::fs;
+use std::path::Path;
 use std::sync::Arc;
 
 use anyhow::Context;
@@ -7,6 +8,7 @@ use anyhow::Result;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Error;
 use codex_execpolicy::Evaluation;
+use codex_execpolicy::PathContext;
 use codex_execpolicy::Policy;
 use codex_execpolicy::PolicyParser;
 use codex_execpolicy::RuleMatch;
@@ -185,6 +187,7 @@ fn add_prefix_rule_extends_policy() -> Result<()> {
             },
             decision: Decision::Prompt,
             justification: None,
+            path_roots: Vec::new(),
         })],
         rules
     );
@@ -245,6 +248,7 @@ prefix_rule(
                 },
                 decision: Decision::Prompt,
                 justification: None,
+                path_roots: Vec::new(),
             }),
             RuleSnapshot::Prefix(PrefixRule {
                 pattern: PrefixPattern {
@@ -253,6 +257,7 @@ prefix_rule(
                 },
                 decision: Decision::Forbidden,
                 justification: None,
+                path_roots: Vec::new(),
             }),
         ],
         git_rules
@@ -319,6 +324,7 @@ prefix_rule(
             },
             decision: Decision::Allow,
             justification: None,
+            path_roots: Vec::new(),
         })],
         bash_rules
     );
@@ -330,6 +336,7 @@ prefix_rule(
             },
             decision: Decision::Allow,
             justification: None,
+            path_roots: Vec::new(),
         })],
         sh_rules
     );
@@ -389,6 +396,7 @@ prefix_rule(
             },
             decision: Decision::Allow,
             justification: None,
+            path_roots: Vec::new(),
         })],
         rules
     );
@@ -572,3 +580,285 @@ fn heuristics_match_is_returned_when_no_policy_matches() {
         evaluation
     );
 }
+
+#[test]
+fn regex_and_glob_tokens_match_whole_arguments() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["git", regex("log|show"), glob("--format=*")],
+    match = ["git log --format=oneline", "git show --format=%H"],
+    not_match = ["git blame --format=x", "git log -p --format=x", "git logs --format=x"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(&tokens(&["git", "log", "--format=%s", "-n1"]), &prompt_all);
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Allow,
+            matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                matched_prefix: tokens(&["git", "log", "--format=%s"]),
+                decision: Decision::Allow,
+                justification: None,
+            }],
+        },
+        evaluation
+    );
+    Ok(())
+}
+
+#[test]
+fn pattern_matchers_are_rejected_as_program() {
+    let policy_src = r#"
+prefix_rule(pattern = [regex("git|jj"), "status"])
+    "#;
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse("test.rules", policy_src)
+        .expect_err("expected parse error");
+    assert!(
+        err.to_string()
+            .contains("first pattern element must be a string or list of strings")
+    );
+}
+
+#[test]
+fn invalid_regex_is_rejected() {
+    let policy_src = r#"
+prefix_rule(pattern = ["grep", regex("(unclosed")])
+    "#;
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse("test.rules", policy_src)
+        .expect_err("expected parse error");
+    assert!(err.to_string().contains("invalid regex `(unclosed`"));
+}
+
+#[test]
+fn path_roots_constrain_path_arguments() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["rm"],
+    path_roots = [".", "/tmp"],
+    match = [
+        "rm -rf build",
+        "rm -f ./target/debug/app /tmp/scratch.txt",
+        "rm -- -weird-name",
+        "rm src/../notes.txt",
+    ],
+    not_match = [
+        "rm -rf /",
+        "rm -rf ../sibling",
+        "rm src/../../escape",
+        "rm ~/notes.txt",
+        "rm -- /etc/passwd",
+        "rm /tmpfoo",
+    ],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(&tokens(&["rm", "-rf", "build", "/etc"]), &prompt_all);
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Prompt,
+            matched_rules: vec![RuleMatch::HeuristicsRuleMatch {
+                command: tokens(&["rm", "-rf", "build", "/etc"]),
+                decision: Decision::Prompt,
+            }],
+        },
+        evaluation
+    );
+    Ok(())
+}
+
+#[test]
+fn path_roots_on_forbidden_rules_match_any_path() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["rm"],
+    decision = "forbidden",
+    path_roots = ["/etc"],
+    match = [
+        "rm /etc/passwd",
+        "rm -f /tmp/scratch.txt /etc/passwd",
+        "rm ./build -- /etc/hosts",
+    ],
+    not_match = [
+        "rm /tmp/scratch.txt",
+        "rm -rf build",
+    ],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let command = tokens(&["rm", "/tmp/scratch.txt", "/etc/passwd"]);
+    let evaluation = policy.check(&command, &allow_all);
+    assert_eq!(Decision::Forbidden, evaluation.decision);
+    Ok(())
+}
+
+#[test]
+fn path_roots_check_option_values() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["cp"],
+    path_roots = ["."],
+    match = ["cp a.txt --target-directory=out"],
+    not_match = ["cp a.txt --target-directory=/etc"],
+)
+prefix_rule(
+    pattern = ["tar"],
+    decision = "forbidden",
+    path_roots = ["/etc"],
+    match = ["tar -x --directory=/etc/ssh archive.tar", "tar -x -C/etc archive.tar"],
+    not_match = ["tar -x --directory=out archive.tar", "tar -xf archive.tar"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(
+        &tokens(&["cp", "a.txt", "--target-directory=/etc"]),
+        &prompt_all,
+    );
+    assert_eq!(Decision::Prompt, evaluation.decision);
+    Ok(())
+}
+
+#[test]
+fn path_roots_resolve_relative_paths_in_the_path_context() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(pattern = ["rm"], path_roots = ["."])
+prefix_rule(pattern = ["rm"], decision = "forbidden", path_roots = ["/etc"])
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+    let check = |command: &[&str], paths: PathContext<'_>| {
+        policy
+            .check_multiple_in([tokens(command)], &prompt_all, paths)
+            .decision
+    };
+
+    let repo = PathContext::Cwd(Path::new("/repo"));
+    assert_eq!(Decision::Allow, check(&["rm", "build", "/repo/dist"], repo));
+    assert_eq!(Decision::Prompt, check(&["rm", "/elsewhere"], repo));
+    let root = PathContext::Cwd(Path::new("/"));
+    assert_eq!(Decision::Forbidden, check(&["rm", "etc/passwd"], root));
+
+    // Without a known cwd, relative paths satisfy no allow rule but still
+    // trigger forbidden ones, since they could point anywhere.
+    assert_eq!(
+        Decision::Forbidden,
+        check(&["rm", "build"], PathContext::Unknown)
+    );
+    assert_eq!(
+        Decision::Allow,
+        check(&["rm", "build"], PathContext::Lexical)
+    );
+    Ok(())
+}
+
+#[test]
+fn path_roots_cannot_be_empty() {
+    let policy_src = r#"
+prefix_rule(pattern = ["rm"], path_roots = [])
+    "#;
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse("test.rules", policy_src)
+        .expect_err("expected parse error");
+    assert!(
+        err.to_string()
+            .contains("invalid rule: path_roots cannot be empty")
+    );
+}
+
+#[test]
+fn argument_rule_matches_anywhere_in_argv() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(pattern = ["git", "push"])
+argument_rule(
+    program = ["git", "jj"],
+    argument = regex("--force(-with-lease)?|-f"),
+    decision = "forbidden",
+    justification = "force pushes rewrite shared history",
+    match = ["git push origin main --force", "jj git push -f"],
+    not_match = ["git push origin main", "git commit --fixup HEAD"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(
+        &tokens(&["git", "push", "origin", "--force-with-lease"]),
+        &allow_all,
+    );
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Forbidden,
+            matched_rules: vec![
+                RuleMatch::PrefixRuleMatch {
+                    matched_prefix: tokens(&["git", "push"]),
+                    decision: Decision::Allow,
+                    justification: None,
+                },
+                RuleMatch::ArgumentRuleMatch {
+                    program: "git".to_string(),
+                    matched_argument: "--force-with-lease".to_string(),
+                    decision: Decision::Forbidden,
+                    justification: Some("force pushes rewrite shared history".to_string()),
+                },
+            ],
+        },
+        evaluation
+    );
+    Ok(())
+}
+
+#[test]
+fn argument_rule_examples_are_enforced() {
+    let policy_src = r#"
+argument_rule(
+    program = "curl",
+    argument = glob("*://localhost*"),
+    decision = "prompt",
+    not_match = ["curl http://localhost:8080/health"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse("test.rules", policy_src)
+        .expect_err("expected parse error");
+    assert!(
+        err.to_string()
+            .contains("expected example to not match rule")
+    );
+}
+
+#[test]
+fn allowed_prefixes_skip_conditional_rules() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(pattern = ["cargo", "test"])
+prefix_rule(pattern = ["cargo", regex("b(uild)?")])
+prefix_rule(pattern = ["rm"], path_roots = ["."])
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    assert_eq!(
+        vec![tokens(&["cargo", "test"])],
+        policy.get_allowed_prefixes()
+    );
+    Ok(())
+}
@@ -0,0 +1,548 @@
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::io::AsyncWriteExt;
+use tracing::warn;
+use wildmatch::WildMatchPattern;
+
+use crate::Hook;
+use crate::HookEvent;
+use crate::HookEventKind;
+use crate::HookOutcome;
+use crate::HookPayload;
+use crate::HookToolInput;
+use crate::HookToolKind;
+use crate::command_from_argv;
+
+pub const DEFAULT_COMMAND_HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+type GlobPattern = WildMatchPattern<'*', '?'>;
+
+/// An external program run for a single lifecycle event.
+///
+/// The program receives the [`HookPayload`] as JSON on stdin and may print a
+/// JSON [`CommandHookResponse`] on stdout. Empty output means "continue".
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandHookConfig {
+    pub event: HookEventKind,
+    pub argv: Vec<String>,
+    pub timeout: Duration,
+    pub matcher: HookMatcher,
+    pub on_error: CommandHookOnError,
+}
+
+/// How to treat a hook that cannot produce a decision: it failed to spawn,
+/// exited non-zero, printed invalid JSON or timed out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CommandHookOnError {
+    /// Log the failure and let the event proceed (fail open).
+    #[default]
+    Continue,
+    /// Treat the failure as a denial (fail closed). Only events that honor
+    /// `Deny` are blocked; others proceed as with `Continue`.
+    Deny,
+}
+
+/// Restricts a command hook to tool events that match every populated field.
+/// Events without tool information only match an empty matcher.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HookMatcher {
+    /// Glob over the tool name, e.g. `apply_patch` or `mcp__docs__*`.
+    pub tool_name: Option<String>,
+    pub tool_kind: Option<HookToolKind>,
+    /// Glob over the paths referenced by the tool call, relative to the cwd
+    /// when possible, e.g. `generated/*`.
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandHookDecision {
+    #[default]
+    #[serde(alias = "continue")]
+    Allow,
+    Deny,
+    Stop,
+}
+
+/// JSON a command hook may print on stdout.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct CommandHookResponse {
+    pub decision: CommandHookDecision,
+    pub reason: Option<String>,
+    /// Replacement tool input for `before_tool_use` hooks.
+    pub tool_input: Option<HookToolInput>,
+    /// Developer context appended to the turn by `user_prompt_submit` hooks.
+    pub additional_context: Option<String>,
+}
+
+impl CommandHookResponse {
+    fn into_outcome(self, program: &str) -> HookOutcome {
+        match self.decision {
+            CommandHookDecision::Deny => HookOutcome::Deny {
+                reason: self
+                    .reason
+                    .unwrap_or_else(|| format!("denied by hook `{program}`")),
+            },
+            CommandHookDecision::Stop => HookOutcome::Stop,
+            CommandHookDecision::Allow => match (self.additional_context, self.tool_input) {
+                (Some(text), _) => HookOutcome::AddContext { text },
+                (None, Some(tool_input)) => HookOutcome::Rewrite { tool_input },
+                (None, None) => HookOutcome::Continue,
+            },
+        }
+    }
+}
+
+impl HookMatcher {
+    pub fn matches(&self, payload: &HookPayload) -> bool {
+        if self.tool_name.is_none() && self.tool_kind.is_none() && self.path.is_none() {
+            return true;
+        }
+        let Some((tool_name, tool_input)) = tool_call(&payload.hook_event) else {
+            return false;
+        };
+        if let Some(pattern) = &self.tool_name
+            && !GlobPattern::new(pattern).matches(tool_name)
+        {
+            return false;
+        }
+        if let Some(kind) = &self.tool_kind
+            && tool_input.kind() != *kind
+        {
+            return false;
+        }
+        if let Some(pattern) = &self.path {
+            let pattern = GlobPattern::new(pattern);
+            return referenced_paths(tool_input).iter().any(|path| {
+                pattern.matches(path)
+                    || relative_to(&payload.cwd, path).is_some_and(|rel| pattern.matches(&rel))
+            });
+        }
+        true
+    }
+}
+
+pub fn command_hook(config: CommandHookConfig) -> Hook {
+    let config = Arc::new(config);
+    Hook {
+        func: Arc::new(move |payload: &HookPayload| {
+            let config = Arc::clone(&config);
+            Box::pin(async move {
+                if !config.matcher.matches(payload) {
+                    return HookOutcome::Continue;
+                }
+                let program = config.argv.first().cloned().unwrap_or_default();
+                match tokio::time::timeout(config.timeout, run_command_hook(&config.argv, payload))
+                    .await
+                {
+                    Ok(Ok(response)) => response.into_outcome(&program),
+                    Ok(Err(err)) => {
+                        let reason = format!("hook `{program}` failed: {err}");
+                        warn!("{reason}");
+                        failure_outcome(config.on_error, reason)
+                    }
+                    Err(_) => {
+                        let reason = format!(
+                            "hook `{program}` timed out after {}ms",
+                            config.timeout.as_millis()
+                        );
+                        warn!("{reason}");
+                        failure_outcome(config.on_error, reason)
+                    }
+                }
+            })
+        }),
+    }
+}
+
+fn failure_outcome(on_error: CommandHookOnError, reason: String) -> HookOutcome {
+    match on_error {
+        CommandHookOnError::Continue => HookOutcome::Continue,
+        CommandHookOnError::Deny => HookOutcome::Deny { reason },
+    }
+}
+
+async fn run_command_hook(
+    argv: &[String],
+    payload: &HookPayload,
+) -> std::io::Result<CommandHookResponse> {
+    let mut command = command_from_argv(argv)
+        .ok_or_else(|| std::io::Error::other("hook command must not be empty"))?;
+    command
+        .current_dir(&payload.cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = command.spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        let json = serde_json::to_vec(payload).map_err(std::io::Error::other)?;
+        stdin.write_all(&json).await?;
+    }
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(std::io::Error::other(format!(
+            "exited with {}: {}",
+            output.status,
+            stderr.trim()
+        )));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stdout = stdout.trim();
+    if stdout.is_empty() {
+        return Ok(CommandHookResponse::default());
+    }
+    let response: CommandHookResponse =
+        serde_json::from_str(stdout).map_err(std::io::Error::other)?;
+    // The two fields belong to different events; accepting both would mean
+    // silently dropping one of them.
+    if response.additional_context.is_some() && response.tool_input.is_some() {
+        return Err(std::io::Error::other(
+            "response must not set both `additional_context` and `tool_input`",
+        ));
+    }
+    Ok(response)
+}
+
+fn tool_call(hook_event: &HookEvent) -> Option<(&str, &HookToolInput)> {
+    match hook_event {
+        HookEvent::BeforeToolUse { event } => Some((&event.tool_name, &event.tool_input)),
+        HookEvent::AfterToolUse { event } => Some((&event.tool_name, &event.tool_input)),
+        _ => None,
+    }
+}
+
+fn relative_to(cwd: &Path, path: &str) -> Option<String> {
+    Path::new(path)
+        .strip_prefix(cwd)
+        .ok()
+        .map(|rel| rel.to_string_lossy().into_owned())
+}
+
+/// Best-effort list of filesystem paths a tool call refers to: `apply_patch`
+/// file headers, shell arguments and well-known path-like JSON arguments.
+fn referenced_paths(tool_input: &HookToolInput) -> Vec<String> {
+    let mut paths = Vec::new();
+    match tool_input {
+        HookToolInput::Custom { input } => paths.extend(patch_paths(input)),
+        HookToolInput::LocalShell { params } => {
+            paths.extend(
+                params
+                    .command
+                    .iter()
+                    .skip(1)
+                    .flat_map(|arg| shell_words(arg)),
+            );
+            paths.extend(params.workdir.clone());
+        }
+        HookToolInput::Function { arguments } | HookToolInput::Mcp { arguments, .. } => {
+            if let Ok(Value::Object(map)) = serde_json::from_str::<Value>(arguments) {
+                for (key, value) in map {
+                    match (key.as_str(), value) {
+                        ("input" | "patch", Value::String(text)) => {
+                            paths.extend(patch_paths(&text));
+                        }
+                        ("command" | "cmd", Value::String(text)) => {
+                            paths.extend(shell_words(&text));
+                        }
+                        ("command" | "cmd", Value::Array(args)) => {
+                            paths.extend(
+                                args.iter()
+                                    .skip(1)
+                                    .filter_map(Value::as_str)
+                                    .flat_map(shell_words),
+                            );
+                        }
+                        ("path" | "file_path" | "dir_path" | "workdir", Value::String(path)) => {
+                            paths.push(path);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+    paths
+}
+
+fn patch_paths(patch: &str) -> impl Iterator<Item = String> + '_ {
+    const HEADERS: [&str; 4] = [
+        "*** Add File: ",
+        "*** Update File: ",
+        "*** Delete File: ",
+        "*** Move to: ",
+    ];
+    patch.lines().filter_map(|line| {
+        HEADERS
+            .iter()
+            .find_map(|header| line.strip_prefix(header))
+            .map(|path| path.trim().to_string())
+    })
+}
+
+fn shell_words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|word| word.trim_matches(|c| c == '"' || c == '\''))
+        .filter(|word| !word.is_empty() && !word.starts_with('-'))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use chrono::TimeZone;
+    use chrono::Utc;
+    use codex_protocol::ThreadId;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::HookEventBeforeToolUse;
+    use crate::HookToolInputLocalShell;
+
+    fn before_tool_use(tool_name: &str, tool_input: HookToolInput) -> HookPayload {
+        HookPayload {
+            session_id: ThreadId::new(),
+            cwd: PathBuf::from("/repo"),
+            triggered_at: Utc
+                .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+                .single()
+                .expect("valid timestamp"),
+            hook_event: HookEvent::BeforeToolUse {
+                event: HookEventBeforeToolUse {
+                    turn_id: "turn-1".to_string(),
+                    call_id: "call-1".to_string(),
+                    tool_name: tool_name.to_string(),
+                    tool_kind: tool_input.kind(),
+                    tool_input,
+                    mutating: true,
+                    sandbox: "none".to_string(),
+                    sandbox_policy: "workspace-write".to_string(),
+                },
+            },
+        }
+    }
+
+    fn patch_payload(path: &str) -> HookPayload {
+        before_tool_use(
+            "apply_patch",
+            HookToolInput::Custom {
+                input: format!("*** Begin Patch\n*** Add File: {path}\n+hi\n*** End Patch"),
+            },
+        )
+    }
+
+    #[test]
+    fn empty_matcher_matches_everything() {
+        assert!(HookMatcher::default().matches(&patch_payload("src/lib.rs")));
+    }
+
+    #[test]
+    fn path_matcher_checks_patch_headers_relative_to_cwd() {
+        let matcher = HookMatcher {
+            path: Some("generated/*".to_string()),
+            ..HookMatcher::default()
+        };
+        assert!(matcher.matches(&patch_payload("generated/api.rs")));
+        assert!(matcher.matches(&patch_payload("/repo/generated/nested/api.rs")));
+        assert!(!matcher.matches(&patch_payload("src/lib.rs")));
+    }
+
+    #[test]
+    fn path_matcher_checks_shell_arguments() {
+        let matcher = HookMatcher {
+            tool_kind: Some(HookToolKind::LocalShell),
+            path: Some("generated/*".to_string()),
+            ..HookMatcher::default()
+        };
+        let payload = before_tool_use(
+            "local_shell",
+            HookToolInput::LocalShell {
+                params: HookToolInputLocalShell {
+                    command: vec![
+                        "bash".to_string(),
+                        "-lc".to_string(),
+                        "rm -rf generated/out".to_string(),
+                    ],
+                    workdir: None,
+                    timeout_ms: None,
+                    sandbox_permissions: None,
+                    prefix_rule: None,
+                    justification: None,
+                },
+            },
+        );
+        assert!(matcher.matches(&payload));
+    }
+
+    #[test]
+    fn tool_name_matcher_supports_globs() {
+        let matcher = HookMatcher {
+            tool_name: Some("mcp__docs__*".to_string()),
+            ..HookMatcher::default()
+        };
+        let payload = before_tool_use(
+            "mcp__docs__search",
+            HookToolInput::Mcp {
+                server: "docs".to_string(),
+                tool: "search".to_string(),
+                arguments: "{}".to_string(),
+            },
+        );
+        assert!(matcher.matches(&payload));
+        assert!(!matcher.matches(&patch_payload("src/lib.rs")));
+    }
+
+    #[test]
+    fn response_maps_to_outcome() {
+        let deny: CommandHookResponse =
+            serde_json::from_str(r#"{"decision":"deny"}"#).expect("parse response");
+        assert_eq!(
+            deny.into_outcome("check"),
+            HookOutcome::Deny {
+                reason: "denied by hook `check`".to_string(),
+            }
+        );
+
+        let rewrite: CommandHookResponse = serde_json::from_str(
+            r#"{"tool_input":{"input_type":"custom","input":"*** Begin Patch"}}"#,
+        )
+        .expect("parse response");
+        assert_eq!(
+            rewrite.into_outcome("check"),
+            HookOutcome::Rewrite {
+                tool_input: HookToolInput::Custom {
+                    input: "*** Begin Patch".to_string(),
+                },
+            }
+        );
+
+        let context: CommandHookResponse =
+            serde_json::from_str(r#"{"additional_context":"CI is red on main"}"#)
+                .expect("parse response");
+        assert_eq!(
+            context.into_outcome("check"),
+            HookOutcome::AddContext {
+                text: "CI is red on main".to_string(),
+            }
+        );
+    }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn command_hook_reads_payload_from_stdin() {
+        let hook = command_hook(CommandHookConfig {
+            event: HookEventKind::BeforeToolUse,
+            argv: vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                r#"if grep -q generated/; then echo '{"decision":"deny","reason":"generated/ is read-only"}'; fi"#
+                    .to_string(),
+            ],
+            timeout: DEFAULT_COMMAND_HOOK_TIMEOUT,
+            matcher: HookMatcher::default(),
+            on_error: CommandHookOnError::Continue,
+        });
+
+        let mut denied = patch_payload("generated/api.rs");
+        denied.cwd = std::env::temp_dir();
+        let mut allowed = patch_payload("src/lib.rs");
+        allowed.cwd = std::env::temp_dir();
+
+        assert_eq!(
+            hook.execute(&denied).await,
+            HookOutcome::Deny {
+                reason: "generated/ is read-only".to_string(),
+            }
+        );
+        assert_eq!(hook.execute(&allowed).await, HookOutcome::Continue);
+    }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn command_hook_times_out_and_continues() {
+        let hook = command_hook(CommandHookConfig {
+            event: HookEventKind::BeforeToolUse,
+            argv: vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                r#"sleep 5; echo '{"decision":"deny"}'"#.to_string(),
+            ],
+            timeout: Duration::from_millis(100),
+            matcher: HookMatcher::default(),
+            on_error: CommandHookOnError::Continue,
+        });
+
+        let mut payload = patch_payload("generated/api.rs");
+        payload.cwd = std::env::temp_dir();
+
+        assert_eq!(hook.execute(&payload).await, HookOutcome::Continue);
+    }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn command_hook_fails_closed_when_configured() {
+        let failing = |argv: Vec<&str>, timeout| {
+            command_hook(CommandHookConfig {
+                event: HookEventKind::BeforeToolUse,
+                argv: argv.into_iter().map(str::to_string).collect(),
+                timeout,
+                matcher: HookMatcher::default(),
+                on_error: CommandHookOnError::Deny,
+            })
+        };
+        let mut payload = patch_payload("src/lib.rs");
+        payload.cwd = std::env::temp_dir();
+
+        let cases = [
+            failing(
+                vec!["/nonexistent/codex-hook"],
+                DEFAULT_COMMAND_HOOK_TIMEOUT,
+            ),
+            failing(
+                vec!["/bin/sh", "-c", "exit 3"],
+                DEFAULT_COMMAND_HOOK_TIMEOUT,
+            ),
+            failing(vec!["/bin/sh", "-c", "sleep 5"], Duration::from_millis(100)),
+        ];
+        for hook in cases {
+            assert!(matches!(
+                hook.execute(&payload).await,
+                HookOutcome::Deny { .. }
+            ));
+        }
+    }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn command_hook_rejects_context_combined_with_rewrite() {
+        let hook = command_hook(CommandHookConfig {
+            event: HookEventKind::BeforeToolUse,
+            argv: vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                r#"echo '{"additional_context":"note","tool_input":{"input_type":"custom","input":"x"}}'"#
+                    .to_string(),
+            ],
+            timeout: DEFAULT_COMMAND_HOOK_TIMEOUT,
+            matcher: HookMatcher::default(),
+            on_error: CommandHookOnError::Deny,
+        });
+
+        let mut payload = patch_payload("src/lib.rs");
+        payload.cwd = std::env::temp_dir();
+
+        let HookOutcome::Deny { reason } = hook.execute(&payload).await else {
+            panic!("expected the ambiguous response to be treated as a failure");
+        };
+        assert!(reason.contains("`additional_context` and `tool_input`"));
+    }
+}
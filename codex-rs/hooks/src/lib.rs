@@ -1,16 +1,33 @@
+mod command_hook;
 mod registry;
 mod types;
 mod user_notification;
 
+pub use command_hook::CommandHookConfig;
+pub use command_hook::CommandHookDecision;
+pub use command_hook::CommandHookOnError;
+pub use command_hook::CommandHookResponse;
+pub use command_hook::DEFAULT_COMMAND_HOOK_TIMEOUT;
+pub use command_hook::HookMatcher;
+pub use command_hook::command_hook;
 pub use registry::Hooks;
 pub use registry::HooksConfig;
 pub use registry::command_from_argv;
 pub use types::Hook;
+pub use types::HookCompactionTrigger;
+pub use types::HookDecision;
 pub use types::HookEvent;
 pub use types::HookEventAfterAgent;
 pub use types::HookEventAfterToolUse;
+pub use types::HookEventBeforeCompaction;
+pub use types::HookEventBeforeToolUse;
+pub use types::HookEventKind;
+pub use types::HookEventSessionEnd;
+pub use types::HookEventSessionStart;
+pub use types::HookEventUserPromptSubmit;
 pub use types::HookOutcome;
 pub use types::HookPayload;
+pub use types::HookSessionStartSource;
 pub use types::HookToolInput;
 pub use types::HookToolInputLocalShell;
 pub use types::HookToolKind;
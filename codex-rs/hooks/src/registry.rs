@@ -1,18 +1,28 @@
 use tokio::process::Command;
 
+use crate::command_hook::CommandHookConfig;
 use crate::types::Hook;
+use crate::types::HookDecision;
 use crate::types::HookEvent;
+use crate::types::HookEventKind;
 use crate::types::HookOutcome;
 use crate::types::HookPayload;
 
 #[derive(Default, Clone)]
 pub struct HooksConfig {
     pub legacy_notify_argv: Option<Vec<String>>,
+    /// External command hooks from `[[hooks.<event>]]`, in declaration order.
+    pub command_hooks: Vec<CommandHookConfig>,
 }
 
 #[derive(Clone)]
 pub struct Hooks {
+    session_start: Vec<Hook>,
+    session_end: Vec<Hook>,
+    user_prompt_submit: Vec<Hook>,
+    before_compaction: Vec<Hook>,
     after_agent: Vec<Hook>,
+    before_tool_use: Vec<Hook>,
     after_tool_use: Vec<Hook>,
 }
 
@@ -32,27 +42,89 @@ impl Hooks {
             .map(crate::notify_hook)
             .into_iter()
             .collect();
-        Self {
+        let mut hooks = Self {
+            session_start: Vec::new(),
+            session_end: Vec::new(),
+            user_prompt_submit: Vec::new(),
+            before_compaction: Vec::new(),
             after_agent,
+            before_tool_use: Vec::new(),
             after_tool_use: Vec::new(),
+        };
+        for command_hook in config.command_hooks {
+            if command_hook.argv.first().is_none_or(String::is_empty) {
+                continue;
+            }
+            let event = command_hook.event;
+            hooks
+                .hooks_for_kind_mut(event)
+                .push(crate::command_hook(command_hook));
+        }
+        hooks
+    }
+
+    fn hooks_for_kind_mut(&mut self, kind: HookEventKind) -> &mut Vec<Hook> {
+        match kind {
+            HookEventKind::SessionStart => &mut self.session_start,
+            HookEventKind::SessionEnd => &mut self.session_end,
+            HookEventKind::UserPromptSubmit => &mut self.user_prompt_submit,
+            HookEventKind::BeforeCompaction => &mut self.before_compaction,
+            HookEventKind::AfterAgent => &mut self.after_agent,
+            HookEventKind::BeforeToolUse => &mut self.before_tool_use,
+            HookEventKind::AfterToolUse => &mut self.after_tool_use,
         }
     }
 
     fn hooks_for_event(&self, hook_event: &HookEvent) -> &[Hook] {
-        match hook_event {
-            HookEvent::AfterAgent { .. } => &self.after_agent,
-            HookEvent::AfterToolUse { .. } => &self.after_tool_use,
+        match hook_event.kind() {
+            HookEventKind::SessionStart => &self.session_start,
+            HookEventKind::SessionEnd => &self.session_end,
+            HookEventKind::UserPromptSubmit => &self.user_prompt_submit,
+            HookEventKind::BeforeCompaction => &self.before_compaction,
+            HookEventKind::AfterAgent => &self.after_agent,
+            HookEventKind::BeforeToolUse => &self.before_tool_use,
+            HookEventKind::AfterToolUse => &self.after_tool_use,
         }
     }
 
-    pub async fn dispatch(&self, hook_payload: HookPayload) {
-        // TODO(gt): support interrupting program execution by returning a result here.
+    /// Runs every hook registered for the payload's event in order.
+    ///
+    /// `Deny` and `Rewrite` outcomes are only honored for events that happen
+    /// before the action they describe (currently `BeforeToolUse`); for other
+    /// events they are treated like `Continue`.
+    pub async fn dispatch(&self, mut hook_payload: HookPayload) -> HookDecision {
+        let mut rewritten_input = None;
+        let mut additional_context = Vec::new();
         for hook in self.hooks_for_event(&hook_payload.hook_event) {
-            let outcome = hook.execute(&hook_payload).await;
-            if matches!(outcome, HookOutcome::Stop) {
-                break;
+            match hook.execute(&hook_payload).await {
+                HookOutcome::Continue => {}
+                HookOutcome::Stop => break,
+                HookOutcome::Deny { reason } => {
+                    if matches!(
+                        hook_payload.hook_event,
+                        HookEvent::BeforeToolUse { .. } | HookEvent::UserPromptSubmit { .. }
+                    ) {
+                        return HookDecision::Deny { reason };
+                    }
+                }
+                HookOutcome::AddContext { text } => {
+                    if matches!(hook_payload.hook_event, HookEvent::UserPromptSubmit { .. }) {
+                        additional_context.push(text);
+                    }
+                }
+                HookOutcome::Rewrite { tool_input } => {
+                    if let HookEvent::BeforeToolUse { event } = &mut hook_payload.hook_event {
+                        event.tool_kind = tool_input.kind();
+                        event.tool_input = tool_input.clone();
+                        rewritten_input = Some(tool_input);
+                    }
+                }
             }
         }
+        HookDecision::Allow {
+            tool_input: rewritten_input,
+            additional_context,
+        }
     }
 }
 
@@ -88,6 +160,8 @@ mod tests {
     use super::*;
     use crate::types::HookEventAfterAgent;
     use crate::types::HookEventAfterToolUse;
+    use crate::types::HookEventBeforeToolUse;
+    use crate::types::HookEventUserPromptSubmit;
     use crate::types::HookToolInput;
     use crate::types::HookToolKind;
 
@@ -118,6 +192,7 @@ mod tests {
         Hook {
             func: Arc::new(move |_| {
                 let calls = Arc::clone(&calls);
+                let outcome = outcome.clone();
                 Box::pin(async move {
                     calls.fetch_add(1, Ordering::SeqCst);
                     outcome
@@ -155,6 +230,49 @@ mod tests {
         }
     }
 
+    fn before_tool_use_payload(label: &str) -> HookPayload {
+        HookPayload {
+            session_id: ThreadId::new(),
+            cwd: PathBuf::from(CWD),
+            triggered_at: Utc
+                .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+                .single()
+                .expect("valid timestamp"),
+            hook_event: HookEvent::BeforeToolUse {
+                event: HookEventBeforeToolUse {
+                    turn_id: format!("turn-{label}"),
+                    call_id: format!("call-{label}"),
+                    tool_name: "apply_patch".to_string(),
+                    tool_kind: HookToolKind::Custom,
+                    tool_input: HookToolInput::Custom {
+                        input: "*** Begin Patch".to_string(),
+                    },
+                    mutating: true,
+                    sandbox: "none".to_string(),
+                    sandbox_policy: "danger-full-access".to_string(),
+                },
+            },
+        }
+    }
+
+    fn user_prompt_submit_payload(label: &str) -> HookPayload {
+        HookPayload {
+            session_id: ThreadId::new(),
+            cwd: PathBuf::from(CWD),
+            triggered_at: Utc
+                .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+                .single()
+                .expect("valid timestamp"),
+            hook_event: HookEvent::UserPromptSubmit {
+                event: HookEventUserPromptSubmit {
+                    thread_id: ThreadId::new(),
+                    turn_id: format!("turn-{label}"),
+                    input_messages: vec!["fix the flaky test".to_string()],
+                },
+            },
+        }
+    }
+
     #[test]
     fn command_from_argv_returns_none_for_empty_args() {
         assert!(command_from_argv(&[]).is_none());
@@ -187,6 +305,7 @@ mod tests {
         assert!(
             Hooks::new(HooksConfig {
                 legacy_notify_argv: Some(vec![]),
+                ..HooksConfig::default()
             })
             .after_agent
             .is_empty()
@@ -194,6 +313,7 @@ mod tests {
         assert!(
             Hooks::new(HooksConfig {
                 legacy_notify_argv: Some(vec!["".to_string()]),
+                ..HooksConfig::default()
             })
             .after_agent
             .is_empty()
@@ -201,6 +321,7 @@ mod tests {
         assert_eq!(
             Hooks::new(HooksConfig {
                 legacy_notify_argv: Some(vec!["notify-send".to_string()]),
+                ..HooksConfig::default()
             })
             .after_agent
             .len(),
@@ -208,6 +329,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hooks_new_registers_command_hooks_by_event() {
+        let command_hook = |event, program: &str| CommandHookConfig {
+            event,
+            argv: vec![program.to_string()],
+            timeout: crate::DEFAULT_COMMAND_HOOK_TIMEOUT,
+            matcher: crate::HookMatcher::default(),
+            on_error: crate::CommandHookOnError::Continue,
+        };
+        let hooks = Hooks::new(HooksConfig {
+            legacy_notify_argv: Some(vec!["notify-send".to_string()]),
+            command_hooks: vec![
+                command_hook(HookEventKind::BeforeToolUse, "lint"),
+                command_hook(HookEventKind::BeforeToolUse, "audit"),
+                command_hook(HookEventKind::AfterAgent, "notify"),
+                command_hook(HookEventKind::SessionStart, ""),
+            ],
+        });
+
+        assert_eq!(hooks.before_tool_use.len(), 2);
+        assert_eq!(hooks.after_agent.len(), 2);
+        assert!(hooks.session_start.is_empty());
+    }
+
     #[tokio::test]
     async fn dispatch_executes_hook() {
         let calls = Arc::new(AtomicUsize::new(0));
@@ -269,6 +414,179 @@ mod tests {
         assert_eq!(calls.load(Ordering::SeqCst), 1);
     }
 
+    #[tokio::test]
+    async fn dispatch_returns_deny_from_before_tool_use_hook() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let hooks = Hooks {
+            before_tool_use: vec![
+                counting_hook(
+                    &calls,
+                    HookOutcome::Deny {
+                        reason: "generated/ is read-only".to_string(),
+                    },
+                ),
+                counting_hook(&calls, HookOutcome::Continue),
+            ],
+            ..Hooks::default()
+        };
+
+        let decision = hooks.dispatch(before_tool_use_payload("deny")).await;
+        assert_eq!(
+            decision,
+            HookDecision::Deny {
+                reason: "generated/ is read-only".to_string(),
+            }
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn dispatch_passes_rewritten_input_to_later_hooks() {
+        let rewritten = HookToolInput::Custom {
+            input: "*** Begin Patch\n*** End Patch".to_string(),
+        };
+        let seen = Arc::new(std::sync::Mutex::new(None));
+        let seen_by_hook = Arc::clone(&seen);
+        let observer = Hook {
+            func: Arc::new(move |payload: &HookPayload| {
+                let seen = Arc::clone(&seen_by_hook);
+                let tool_input = match &payload.hook_event {
+                    HookEvent::BeforeToolUse { event } => Some(event.tool_input.clone()),
+                    _ => None,
+                };
+                Box::pin(async move {
+                    *seen.lock().expect("lock") = tool_input;
+                    HookOutcome::Continue
+                })
+            }),
+        };
+        let calls = Arc::new(AtomicUsize::new(0));
+        let hooks = Hooks {
+            before_tool_use: vec![
+                counting_hook(
+                    &calls,
+                    HookOutcome::Rewrite {
+                        tool_input: rewritten.clone(),
+                    },
+                ),
+                observer,
+            ],
+            ..Hooks::default()
+        };
+
+        let decision = hooks.dispatch(before_tool_use_payload("rewrite")).await;
+        assert_eq!(
+            decision,
+            HookDecision::Allow {
+                tool_input: Some(rewritten.clone()),
+                additional_context: Vec::new(),
+            }
+        );
+        assert_eq!(*seen.lock().expect("lock"), Some(rewritten));
+    }
+
+    #[tokio::test]
+    async fn dispatch_collects_context_from_user_prompt_submit_hooks() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let hooks = Hooks {
+            user_prompt_submit: vec![
+                counting_hook(
+                    &calls,
+                    HookOutcome::AddContext {
+                        text: "Ticket ABC-1: flaky on CI".to_string(),
+                    },
+                ),
+                counting_hook(&calls, HookOutcome::Continue),
+                counting_hook(
+                    &calls,
+                    HookOutcome::AddContext {
+                        text: "CI status: failing".to_string(),
+                    },
+                ),
+            ],
+            ..Hooks::default()
+        };
+
+        let decision = hooks.dispatch(user_prompt_submit_payload("context")).await;
+        assert_eq!(
+            decision,
+            HookDecision::Allow {
+                tool_input: None,
+                additional_context: vec![
+                    "Ticket ABC-1: flaky on CI".to_string(),
+                    "CI status: failing".to_string(),
+                ],
+            }
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn dispatch_returns_deny_from_user_prompt_submit_hook() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let hooks = Hooks {
+            user_prompt_submit: vec![
+                counting_hook(
+                    &calls,
+                    HookOutcome::AddContext {
+                        text: "ignored".to_string(),
+                    },
+                ),
+                counting_hook(
+                    &calls,
+                    HookOutcome::Deny {
+                        reason: "prompts must reference a ticket".to_string(),
+                    },
+                ),
+            ],
+            ..Hooks::default()
+        };
+
+        let decision = hooks.dispatch(user_prompt_submit_payload("deny")).await;
+        assert_eq!(
+            decision,
+            HookDecision::Deny {
+                reason: "prompts must reference a ticket".to_string(),
+            }
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn dispatch_ignores_context_for_tool_events() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let hooks = Hooks {
+            before_tool_use: vec![counting_hook(
+                &calls,
+                HookOutcome::AddContext {
+                    text: "not a prompt".to_string(),
+                },
+            )],
+            ..Hooks::default()
+        };
+
+        let decision = hooks.dispatch(before_tool_use_payload("context")).await;
+        assert_eq!(decision, HookDecision::allow());
+    }
+
+    #[tokio::test]
+    async fn dispatch_ignores_deny_for_after_events() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let hooks = Hooks {
+            after_tool_use: vec![counting_hook(
+                &calls,
+                HookOutcome::Deny {
+                    reason: "too late".to_string(),
+                },
+            )],
+            ..Hooks::default()
+        };
+
+        let decision = hooks.dispatch(after_tool_use_payload("late")).await;
+        assert_eq!(decision, HookDecision::allow());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
     #[cfg(not(windows))]
     #[tokio::test]
     async fn hook_executes_program_with_payload_argument_unix() -> Result<()> {
@@ -7,6 +7,8 @@ use chrono::Utc;
 use codex_protocol::ThreadId;
 use codex_protocol::models::SandboxPermissions;
 use futures::future::BoxFuture;
+use schemars::JsonSchema;
+use serde::Deserialize;
 use serde::Serialize;
 use serde::Serializer;
 
@@ -41,6 +43,51 @@ pub struct HookPayload {
     pub hook_event: HookEvent,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HookSessionStartSource {
+    New,
+    Resumed,
+    Forked,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventSessionStart {
+    pub thread_id: ThreadId,
+    pub model: String,
+    pub source: HookSessionStartSource,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventSessionEnd {
+    pub thread_id: ThreadId,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventUserPromptSubmit {
+    pub thread_id: ThreadId,
+    pub turn_id: String,
+    pub input_messages: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HookCompactionTrigger {
+    Auto,
+    Manual,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventBeforeCompaction {
+    pub thread_id: ThreadId,
+    pub turn_id: String,
+    pub trigger: HookCompactionTrigger,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub struct HookEventAfterAgent {
@@ -50,7 +97,7 @@ pub struct HookEventAfterAgent {
     pub last_assistant_message: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum HookToolKind {
     Function,
@@ -59,7 +106,7 @@ pub enum HookToolKind {
     Mcp,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct HookToolInputLocalShell {
     pub command: Vec<String>,
@@ -70,7 +117,7 @@ pub struct HookToolInputLocalShell {
     pub justification: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "input_type", rename_all = "snake_case")]
 pub enum HookToolInput {
     Function {
@@ -89,6 +136,30 @@ pub enum HookToolInput {
     },
 }
 
+impl HookToolInput {
+    pub fn kind(&self) -> HookToolKind {
+        match self {
+            HookToolInput::Function { .. } => HookToolKind::Function,
+            HookToolInput::Custom { .. } => HookToolKind::Custom,
+            HookToolInput::LocalShell { .. } => HookToolKind::LocalShell,
+            HookToolInput::Mcp { .. } => HookToolKind::Mcp,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventBeforeToolUse {
+    pub turn_id: String,
+    pub call_id: String,
+    pub tool_name: String,
+    pub tool_kind: HookToolKind,
+    pub tool_input: HookToolInput,
+    pub mutating: bool,
+    pub sandbox: String,
+    pub sandbox_policy: String,
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct HookEventAfterToolUse {
@@ -116,21 +187,108 @@ where
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "event_type", rename_all = "snake_case")]
 pub enum HookEvent {
+    SessionStart {
+        #[serde(flatten)]
+        event: HookEventSessionStart,
+    },
+    SessionEnd {
+        #[serde(flatten)]
+        event: HookEventSessionEnd,
+    },
+    UserPromptSubmit {
+        #[serde(flatten)]
+        event: HookEventUserPromptSubmit,
+    },
+    BeforeCompaction {
+        #[serde(flatten)]
+        event: HookEventBeforeCompaction,
+    },
     AfterAgent {
         #[serde(flatten)]
         event: HookEventAfterAgent,
     },
+    BeforeToolUse {
+        #[serde(flatten)]
+        event: HookEventBeforeToolUse,
+    },
     AfterToolUse {
         #[serde(flatten)]
         event: HookEventAfterToolUse,
     },
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Identifies a lifecycle event without its payload, e.g. to attach hooks
+/// from configuration.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEventKind {
+    SessionStart,
+    SessionEnd,
+    UserPromptSubmit,
+    BeforeCompaction,
+    AfterAgent,
+    BeforeToolUse,
+    AfterToolUse,
+}
+
+impl HookEvent {
+    pub fn kind(&self) -> HookEventKind {
+        match self {
+            HookEvent::SessionStart { .. } => HookEventKind::SessionStart,
+            HookEvent::SessionEnd { .. } => HookEventKind::SessionEnd,
+            HookEvent::UserPromptSubmit { .. } => HookEventKind::UserPromptSubmit,
+            HookEvent::BeforeCompaction { .. } => HookEventKind::BeforeCompaction,
+            HookEvent::AfterAgent { .. } => HookEventKind::AfterAgent,
+            HookEvent::BeforeToolUse { .. } => HookEventKind::BeforeToolUse,
+            HookEvent::AfterToolUse { .. } => HookEventKind::AfterToolUse,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum HookOutcome {
     Continue,
     #[allow(dead_code)]
     Stop,
+    /// Veto the pending action. Honored for `BeforeToolUse`, where the reason
+    /// is reported back to the model in place of the tool output, and for
+    /// `UserPromptSubmit`, where the prompt is rejected and the reason is shown
+    /// to the user.
+    Deny {
+        reason: String,
+    },
+    /// Append developer context to the submitted turn. Only honored for
+    /// `UserPromptSubmit`.
+    AddContext {
+        text: String,
+    },
+    /// Replace the pending tool call input. Only honored for `BeforeToolUse`;
+    /// later hooks observe the rewritten input.
+    Rewrite {
+        tool_input: HookToolInput,
+    },
+}
+
+/// Aggregated result of running every hook registered for an event.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HookDecision {
+    /// Proceed. `tool_input` is set when a hook rewrote the tool call;
+    /// `additional_context` holds developer context added by prompt hooks.
+    Allow {
+        tool_input: Option<HookToolInput>,
+        additional_context: Vec<String>,
+    },
+    /// Do not proceed; `reason` explains why.
+    Deny { reason: String },
+}
+
+impl HookDecision {
+    pub fn allow() -> Self {
+        Self::Allow {
+            tool_input: None,
+            additional_context: Vec::new(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -144,9 +302,12 @@ mod tests {
     use pretty_assertions::assert_eq;
     use serde_json::json;
 
+    use super::HookCompactionTrigger;
     use super::HookEvent;
     use super::HookEventAfterAgent;
     use super::HookEventAfterToolUse;
+    use super::HookEventBeforeCompaction;
+    use super::HookEventBeforeToolUse;
     use super::HookPayload;
     use super::HookToolInput;
     use super::HookToolInputLocalShell;
@@ -261,4 +422,94 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn before_tool_use_payload_serializes_stable_wire_shape() {
+        let session_id = ThreadId::new();
+        let payload = HookPayload {
+            session_id,
+            cwd: PathBuf::from("tmp"),
+            triggered_at: Utc
+                .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+                .single()
+                .expect("valid timestamp"),
+            hook_event: HookEvent::BeforeToolUse {
+                event: HookEventBeforeToolUse {
+                    turn_id: "turn-3".to_string(),
+                    call_id: "call-2".to_string(),
+                    tool_name: "mcp__docs__search".to_string(),
+                    tool_kind: HookToolKind::Mcp,
+                    tool_input: HookToolInput::Mcp {
+                        server: "docs".to_string(),
+                        tool: "search".to_string(),
+                        arguments: "{\"q\":\"hooks\"}".to_string(),
+                    },
+                    mutating: false,
+                    sandbox: "none".to_string(),
+                    sandbox_policy: "read-only".to_string(),
+                },
+            },
+        };
+
+        let actual = serde_json::to_value(payload).expect("serialize hook payload");
+        let expected = json!({
+            "session_id": session_id.to_string(),
+            "cwd": "tmp",
+            "triggered_at": "2025-01-01T00:00:00Z",
+            "hook_event": {
+                "event_type": "before_tool_use",
+                "turn_id": "turn-3",
+                "call_id": "call-2",
+                "tool_name": "mcp__docs__search",
+                "tool_kind": "mcp",
+                "tool_input": {
+                    "input_type": "mcp",
+                    "server": "docs",
+                    "tool": "search",
+                    "arguments": "{\"q\":\"hooks\"}",
+                },
+                "mutating": false,
+                "sandbox": "none",
+                "sandbox_policy": "read-only",
+            },
+        });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn before_compaction_payload_serializes_stable_wire_shape() {
+        let session_id = ThreadId::new();
+        let thread_id = ThreadId::new();
+        let payload = HookPayload {
+            session_id,
+            cwd: PathBuf::from("tmp"),
+            triggered_at: Utc
+                .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+                .single()
+                .expect("valid timestamp"),
+            hook_event: HookEvent::BeforeCompaction {
+                event: HookEventBeforeCompaction {
+                    thread_id,
+                    turn_id: "turn-4".to_string(),
+                    trigger: HookCompactionTrigger::Auto,
+                },
+            },
+        };
+
+        let actual = serde_json::to_value(payload).expect("serialize hook payload");
+        let expected = json!({
+            "session_id": session_id.to_string(),
+            "cwd": "tmp",
+            "triggered_at": "2025-01-01T00:00:00Z",
+            "hook_event": {
+                "event_type": "before_compaction",
+                "thread_id": thread_id.to_string(),
+                "turn_id": "turn-4",
+                "trigger": "auto",
+            },
+        });
+
+        assert_eq!(actual, expected);
+    }
 }
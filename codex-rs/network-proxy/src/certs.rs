@@ -0,0 +1,351 @@
+//! Local certificate authority used when `network.mitm` is enabled.
+//!
+//! The CA is generated once and stored under `CODEX_HOME/network_proxy/`. Sandboxed processes are
+//! pointed at `ca-bundle.pem` (the system roots plus `ca.pem`) through the usual CA bundle
+//! environment variables so they trust the leaf certificates the proxy mints for each intercepted
+//! host without losing the roots they need for everything else.
+
+use anyhow::Context;
+use anyhow::Result;
+use lru::LruCache;
+use rcgen::BasicConstraints;
+use rcgen::Certificate;
+use rcgen::CertificateParams;
+use rcgen::DnType;
+use rcgen::ExtendedKeyUsagePurpose;
+use rcgen::IsCa;
+use rcgen::KeyPair;
+use rcgen::KeyUsagePurpose;
+use rustls::ServerConfig;
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::PrivateKeyDer;
+use rustls::pki_types::PrivatePkcs8KeyDer;
+use rustls::pki_types::pem::PemObject;
+use std::fs;
+use std::io::Write;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use time::Duration;
+use time::OffsetDateTime;
+use tracing::warn;
+
+pub const MITM_CA_DIR: &str = "network_proxy";
+const CA_CERT_FILE: &str = "ca.pem";
+const CA_KEY_FILE: &str = "ca-key.pem";
+const CA_BUNDLE_FILE: &str = "ca-bundle.pem";
+const CA_COMMON_NAME: &str = "Codex Network Proxy CA";
+const CA_VALIDITY: Duration = Duration::days(3650);
+const LEAF_VALIDITY: Duration = Duration::days(30);
+/// Leaf configs kept for reuse; the least recently used host is re-minted on demand.
+const SERVER_CONFIG_CACHE_CAPACITY: usize = 256;
+
+/// Environment variables that replace a TLS client's trust store with the given bundle, so they
+/// are pointed at the combined system + proxy CA bundle.
+pub const MITM_CA_ENV_KEYS: &[&str] = &[
+    "SSL_CERT_FILE",
+    "REQUESTS_CA_BUNDLE",
+    "CURL_CA_BUNDLE",
+    "GIT_SSL_CAINFO",
+    "PIP_CERT",
+    "NPM_CONFIG_CAFILE",
+    "npm_config_cafile",
+    "CARGO_HTTP_CAINFO",
+    "AWS_CA_BUNDLE",
+    "DENO_CERT",
+];
+
+/// Environment variables whose certificates are added to the client's built-in roots, so they
+/// only need the proxy CA.
+pub const MITM_EXTRA_CA_ENV_KEYS: &[&str] = &["NODE_EXTRA_CA_CERTS"];
+
+/// Well-known locations of the system CA bundle, in the order OpenSSL-based tools probe them.
+const SYSTEM_CA_BUNDLE_PATHS: &[&str] = &[
+    "/etc/ssl/certs/ca-certificates.crt",
+    "/etc/pki/tls/certs/ca-bundle.crt",
+    "/etc/ssl/ca-bundle.pem",
+    "/etc/pki/tls/cacert.pem",
+    "/etc/ssl/cert.pem",
+];
+
+pub struct MitmCertificateAuthority {
+    cert_path: PathBuf,
+    bundle_path: PathBuf,
+    /// The persisted `ca.pem`, sent as the chain's root so clients see exactly what they trust.
+    cert_der: CertificateDer<'static>,
+    /// Issuer handle rcgen signs leaves with; see [`MitmCertificateAuthority::load_or_create`].
+    issuer: Certificate,
+    key: KeyPair,
+    server_configs: Mutex<LruCache<String, Arc<ServerConfig>>>,
+}
+
+impl std::fmt::Debug for MitmCertificateAuthority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print the key material.
+        f.debug_struct("MitmCertificateAuthority")
+            .field("cert_path", &self.cert_path)
+            .field("bundle_path", &self.bundle_path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MitmCertificateAuthority {
+    /// Loads the CA from `codex_home`, generating and persisting a new one on first use, and
+    /// refreshes the combined trust bundle next to it.
+    pub fn load_or_create(codex_home: &Path) -> Result<Self> {
+        let dir = codex_home.join(MITM_CA_DIR);
+        let cert_path = dir.join(CA_CERT_FILE);
+        let key_path = dir.join(CA_KEY_FILE);
+        let bundle_path = dir.join(CA_BUNDLE_FILE);
+
+        let (key, cert_pem) = if key_path.exists() && cert_path.exists() {
+            let key_pem = fs::read_to_string(&key_path)
+                .with_context(|| format!("failed to read {}", key_path.display()))?;
+            let key = KeyPair::from_pem(&key_pem)
+                .with_context(|| format!("invalid MITM CA key in {}", key_path.display()))?;
+            let cert_pem = fs::read_to_string(&cert_path)
+                .with_context(|| format!("failed to read {}", cert_path.display()))?;
+            (key, cert_pem)
+        } else {
+            fs::create_dir_all(&dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+            let key = KeyPair::generate().context("failed to generate MITM CA key")?;
+            let cert = ca_params()?
+                .self_signed(&key)
+                .context("failed to self-sign MITM CA")?;
+            write_private_file(&key_path, key.serialize_pem().as_bytes())?;
+            fs::write(&cert_path, cert.pem())
+                .with_context(|| format!("failed to write {}", cert_path.display()))?;
+            (key, cert.pem())
+        };
+
+        let cert_der = CertificateDer::from_pem_slice(cert_pem.as_bytes())
+            .with_context(|| format!("invalid MITM CA certificate in {}", cert_path.display()))?;
+        // rcgen can only sign with a `Certificate` issuer, so derive one from the persisted
+        // certificate's own parameters (name, validity, serial) and key. It is never sent to
+        // clients; leaves chain to the `ca.pem` above by name and key identifier.
+        let issuer = CertificateParams::from_ca_cert_pem(&cert_pem)
+            .with_context(|| format!("invalid MITM CA certificate in {}", cert_path.display()))?
+            .self_signed(&key)
+            .context("failed to load MITM CA")?;
+
+        write_ca_bundle(&bundle_path, &cert_pem)?;
+
+        Ok(Self {
+            cert_path,
+            bundle_path,
+            cert_der,
+            issuer,
+            key,
+            server_configs: Mutex::new(LruCache::new(
+                NonZeroUsize::new(SERVER_CONFIG_CACHE_CAPACITY).unwrap_or(NonZeroUsize::MIN),
+            )),
+        })
+    }
+
+    /// Path of the PEM-encoded CA certificate.
+    pub fn cert_path(&self) -> &Path {
+        &self.cert_path
+    }
+
+    /// Path of the system roots plus the CA certificate, handed to sandboxed processes.
+    pub fn bundle_path(&self) -> &Path {
+        &self.bundle_path
+    }
+
+    /// Returns a TLS server config presenting a leaf certificate for `host`, minting and caching
+    /// one on first use. At most [`SERVER_CONFIG_CACHE_CAPACITY`] hosts are cached.
+    pub(crate) fn server_config_for_host(&self, host: &str) -> Result<Arc<ServerConfig>> {
+        let mut server_configs = self
+            .server_configs
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(config) = server_configs.get(host) {
+            return Ok(Arc::clone(config));
+        }
+
+        let config = Arc::new(self.build_server_config(host)?);
+        server_configs.put(host.to_string(), Arc::clone(&config));
+        Ok(config)
+    }
+
+    fn build_server_config(&self, host: &str) -> Result<ServerConfig> {
+        let mut params = CertificateParams::new(vec![host.to_string()])
+            .with_context(|| format!("invalid host for MITM certificate: {host}"))?;
+        params.distinguished_name.push(DnType::CommonName, host);
+        params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+        params.use_authority_key_identifier_extension = true;
+        let now = OffsetDateTime::now_utc();
+        params.not_before = now - Duration::days(1);
+        params.not_after = now + LEAF_VALIDITY;
+
+        let leaf_key = KeyPair::generate().context("failed to generate MITM leaf key")?;
+        let leaf = params
+            .signed_by(&leaf_key, &self.issuer, &self.key)
+            .with_context(|| format!("failed to sign MITM certificate for {host}"))?;
+
+        let chain: Vec<CertificateDer<'static>> = vec![leaf.der().clone(), self.cert_der.clone()];
+        let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(leaf_key.serialize_der()));
+        let mut config = ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(chain, key)
+            .context("failed to build MITM TLS config")?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(config)
+    }
+}
+
+fn ca_params() -> Result<CertificateParams> {
+    let mut params = CertificateParams::new(Vec::new()).context("invalid MITM CA parameters")?;
+    params
+        .distinguished_name
+        .push(DnType::CommonName, CA_COMMON_NAME);
+    params.is_ca = IsCa::Ca(BasicConstraints::Constrained(0));
+    params.key_usages = vec![
+        KeyUsagePurpose::KeyCertSign,
+        KeyUsagePurpose::CrlSign,
+        KeyUsagePurpose::DigitalSignature,
+    ];
+    let now = OffsetDateTime::now_utc();
+    params.not_before = now - Duration::days(1);
+    params.not_after = now + CA_VALIDITY;
+    Ok(params)
+}
+
+/// Writes the system trust roots followed by `ca_pem`. Clients that replace their trust store
+/// with this file can then still reach hosts the proxy tunnels without interception.
+fn write_ca_bundle(path: &Path, ca_pem: &str) -> Result<()> {
+    let mut bundle = match system_ca_bundle(path) {
+        Some(system_roots) => system_roots,
+        None => {
+            warn!(
+                "no system CA bundle found; {} only trusts the network proxy CA",
+                path.display()
+            );
+            String::new()
+        }
+    };
+    if !bundle.is_empty() && !bundle.ends_with('\n') {
+        bundle.push('\n');
+    }
+    bundle.push_str(ca_pem);
+    fs::write(path, bundle).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Reads the bundle named by `SSL_CERT_FILE`, or the first well-known system bundle. `own_bundle`
+/// is skipped so a nested session does not fold the proxy CA into itself again.
+fn system_ca_bundle(own_bundle: &Path) -> Option<String> {
+    std::env::var_os("SSL_CERT_FILE")
+        .map(PathBuf::from)
+        .into_iter()
+        .chain(SYSTEM_CA_BUNDLE_PATHS.iter().map(PathBuf::from))
+        .filter(|path| path != own_bundle)
+        .find_map(|path| {
+            let contents = fs::read_to_string(path).ok()?;
+            contents
+                .contains("-----BEGIN CERTIFICATE-----")
+                .then_some(contents)
+        })
+}
+
+fn write_private_file(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("failed to create {}", path.display()))?;
+    file.write_all(contents)
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn load_or_create_persists_and_reuses_the_ca() {
+        let codex_home = tempfile::tempdir().unwrap();
+
+        let first = MitmCertificateAuthority::load_or_create(codex_home.path()).unwrap();
+        let ca_pem = fs::read_to_string(first.cert_path()).unwrap();
+        let key_pem =
+            fs::read_to_string(codex_home.path().join(MITM_CA_DIR).join(CA_KEY_FILE)).unwrap();
+
+        let second = MitmCertificateAuthority::load_or_create(codex_home.path()).unwrap();
+        assert_eq!(second.cert_path(), first.cert_path());
+        assert_eq!(fs::read_to_string(second.cert_path()).unwrap(), ca_pem);
+        assert_eq!(second.key.serialize_pem(), key_pem);
+        // Leaves must chain to the certificate on disk, not a freshly re-signed copy.
+        assert_eq!(
+            second.cert_der,
+            CertificateDer::from_pem_slice(ca_pem.as_bytes()).unwrap()
+        );
+        assert_eq!(second.cert_der, first.cert_der);
+    }
+
+    #[test]
+    fn bundle_ends_with_the_ca_certificate() {
+        let codex_home = tempfile::tempdir().unwrap();
+        let ca = MitmCertificateAuthority::load_or_create(codex_home.path()).unwrap();
+
+        let ca_pem = fs::read_to_string(ca.cert_path()).unwrap();
+        let bundle = fs::read_to_string(ca.bundle_path()).unwrap();
+        assert!(bundle.ends_with(&ca_pem));
+        assert_eq!(bundle.matches(&ca_pem).count(), 1);
+    }
+
+    #[test]
+    fn server_config_for_host_is_cached_per_host() {
+        let codex_home = tempfile::tempdir().unwrap();
+        let ca = MitmCertificateAuthority::load_or_create(codex_home.path()).unwrap();
+
+        let first = ca.server_config_for_host("registry.npmjs.org").unwrap();
+        let again = ca.server_config_for_host("registry.npmjs.org").unwrap();
+        let other = ca.server_config_for_host("pypi.org").unwrap();
+
+        assert!(Arc::ptr_eq(&first, &again));
+        assert!(!Arc::ptr_eq(&first, &other));
+    }
+
+    #[test]
+    fn server_config_cache_evicts_least_recently_used_host() {
+        let codex_home = tempfile::tempdir().unwrap();
+        let ca = MitmCertificateAuthority::load_or_create(codex_home.path()).unwrap();
+
+        let first = ca.server_config_for_host("host-0.example").unwrap();
+        for index in 1..=SERVER_CONFIG_CACHE_CAPACITY {
+            ca.server_config_for_host(&format!("host-{index}.example"))
+                .unwrap();
+        }
+
+        let server_configs = ca.server_configs.lock().unwrap();
+        assert_eq!(server_configs.len(), SERVER_CONFIG_CACHE_CAPACITY);
+        assert!(!server_configs.contains("host-0.example"));
+        drop(server_configs);
+        let reminted = ca.server_config_for_host("host-0.example").unwrap();
+        assert!(!Arc::ptr_eq(&first, &reminted));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn ca_key_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let codex_home = tempfile::tempdir().unwrap();
+        MitmCertificateAuthority::load_or_create(codex_home.path()).unwrap();
+
+        let mode = fs::metadata(codex_home.path().join(MITM_CA_DIR).join(CA_KEY_FILE))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
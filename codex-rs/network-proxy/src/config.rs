@@ -34,10 +34,16 @@ pub struct NetworkProxySettings {
     pub dangerously_allow_non_loopback_admin: bool,
     #[serde(default)]
     pub mode: NetworkMode,
+    /// Terminate HTTPS CONNECTs with a local CA so method policy also applies to HTTPS requests.
+    #[serde(default)]
+    pub mitm: bool,
     #[serde(default)]
     pub allowed_domains: Vec<String>,
     #[serde(default)]
     pub denied_domains: Vec<String>,
+    /// Host-scoped rules that allow or deny requests by method and path.
+    #[serde(default)]
+    pub rules: Vec<NetworkRule>,
     #[serde(default)]
     pub allow_unix_sockets: Vec<String>,
     pub allow_local_binding: bool,
@@ -56,22 +62,53 @@ impl Default for NetworkProxySettings {
             dangerously_allow_non_loopback_proxy: false,
             dangerously_allow_non_loopback_admin: false,
             mode: NetworkMode::default(),
+            mitm: false,
             allowed_domains: Vec::new(),
             denied_domains: Vec::new(),
+            rules: Vec::new(),
             allow_unix_sockets: Vec::new(),
             allow_local_binding: true,
         }
     }
 }
 
+/// A policy entry such as "allow `GET registry.npmjs.org/*`" or
+/// "deny `POST api.github.com/repos/*/issues`".
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NetworkRule {
+    /// Domain pattern, using the same syntax as `allowed_domains`.
+    pub host: String,
+    /// Path the request must start with. `*` matches any run of characters, including `/`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_prefix: Option<String>,
+    /// Methods the rule applies to; empty means every method.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub methods: Vec<String>,
+    pub decision: NetworkRuleDecision,
+}
+
+impl NetworkRule {
+    /// Rules without a method or path condition can be enforced on opaque tunnels.
+    pub fn is_host_only(&self) -> bool {
+        self.path_prefix.is_none() && self.methods.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkRuleDecision {
+    Allow,
+    Deny,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum NetworkMode {
     /// Limited (read-only) access: only GET/HEAD/OPTIONS are allowed for HTTP. HTTPS CONNECT is
     /// blocked unless MITM is enabled so the proxy can enforce method policy on inner requests.
     Limited,
-    /// Full network access: all HTTP methods are allowed, and HTTPS CONNECTs are tunneled without
-    /// MITM interception.
+    /// Full network access: all HTTP methods are allowed, and HTTPS CONNECTs are tunneled (or
+    /// intercepted without method checks when MITM is enabled).
     #[default]
     Full,
 }
@@ -341,8 +378,10 @@ mod tests {
                 dangerously_allow_non_loopback_proxy: false,
                 dangerously_allow_non_loopback_admin: false,
                 mode: NetworkMode::Full,
+                mitm: false,
                 allowed_domains: Vec::new(),
                 denied_domains: Vec::new(),
+                rules: Vec::new(),
                 allow_unix_sockets: Vec::new(),
                 allow_local_binding: true,
             }
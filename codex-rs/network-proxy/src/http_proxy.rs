@@ -1,3 +1,4 @@
+use crate::certs::MitmCertificateAuthority;
 use crate::config::NetworkMode;
 use crate::metadata::attempt_id_from_proxy_authorization;
 use crate::network_policy::NetworkDecision;
@@ -7,6 +8,8 @@ use crate::network_policy::NetworkPolicyDecision;
 use crate::network_policy::NetworkPolicyRequest;
 use crate::network_policy::NetworkPolicyRequestArgs;
 use crate::network_policy::NetworkProtocol;
+use crate::network_policy::PolicyEvaluation;
+use crate::network_policy::RuleScope;
 use crate::network_policy::evaluate_host_policy;
 use crate::policy::normalize_host;
 use crate::reasons::REASON_METHOD_NOT_ALLOWED;
@@ -17,6 +20,7 @@ use crate::responses::blocked_header_value;
 use crate::responses::blocked_message_with_policy;
 use crate::responses::blocked_text_response_with_policy;
 use crate::responses::json_response;
+use crate::runtime::AllowedRequestArgs;
 use crate::runtime::unix_socket_permissions_supported;
 use crate::state::BlockedRequest;
 use crate::state::BlockedRequestArgs;
@@ -63,6 +67,8 @@ use rama_tcp::client::service::TcpConnector;
 use rama_tcp::server::TcpListener;
 use rama_tls_rustls::client::TlsConnectorDataBuilder;
 use rama_tls_rustls::client::TlsConnectorLayer;
+use rama_tls_rustls::server::TlsAcceptorData;
+use rama_tls_rustls::server::TlsAcceptorLayer;
 use serde::Serialize;
 use std::convert::Infallible;
 use std::net::SocketAddr;
@@ -76,6 +82,7 @@ pub async fn run_http_proxy(
     state: Arc<NetworkProxyState>,
     addr: SocketAddr,
     policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
+    mitm: Option<Arc<MitmCertificateAuthority>>,
 ) -> Result<()> {
     let listener = TcpListener::build()
         .bind(addr)
@@ -88,23 +95,25 @@ pub async fn run_http_proxy(
         .map_err(anyhow::Error::from)
         .with_context(|| format!("bind HTTP proxy: {addr}"))?;
 
-    run_http_proxy_with_listener(state, listener, policy_decider).await
+    run_http_proxy_with_listener(state, listener, policy_decider, mitm).await
 }
 
 pub async fn run_http_proxy_with_std_listener(
     state: Arc<NetworkProxyState>,
     listener: StdTcpListener,
     policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
+    mitm: Option<Arc<MitmCertificateAuthority>>,
 ) -> Result<()> {
     let listener =
         TcpListener::try_from(listener).context("convert std listener to HTTP proxy listener")?;
-    run_http_proxy_with_listener(state, listener, policy_decider).await
+    run_http_proxy_with_listener(state, listener, policy_decider, mitm).await
 }
 
 async fn run_http_proxy_with_listener(
     state: Arc<NetworkProxyState>,
     listener: TcpListener,
     policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
+    mitm: Option<Arc<MitmCertificateAuthority>>,
 ) -> Result<()> {
     let addr = listener
         .local_addr()
@@ -116,7 +125,7 @@ async fn run_http_proxy_with_listener(
                 MethodMatcher::CONNECT,
                 service_fn({
                     let policy_decider = policy_decider.clone();
-                    move |req| http_connect_accept(policy_decider.clone(), req)
+                    move |req| http_connect_accept(policy_decider.clone(), mitm.clone(), req)
                 }),
                 service_fn(http_connect_proxy),
             ),
@@ -136,8 +145,17 @@ async fn run_http_proxy_with_listener(
     Ok(())
 }
 
+/// Marks an accepted CONNECT whose TLS session the proxy terminates itself.
+#[derive(Clone)]
+struct MitmConnect {
+    ca: Arc<MitmCertificateAuthority>,
+    policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
+    attempt_id: Option<String>,
+}
+
 async fn http_connect_accept(
     policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
+    mitm: Option<Arc<MitmCertificateAuthority>>,
     mut req: Request,
 ) -> Result<(Response, Request), Response> {
     let app_state = req
@@ -191,11 +209,22 @@ async fn http_connect_accept(
         attempt_id: network_attempt_id.clone(),
     });
 
-    match evaluate_host_policy(&app_state, policy_decider.as_ref(), &request).await {
-        Ok(NetworkDecision::Deny {
-            reason,
-            source,
-            decision,
+    // Requests inside an intercepted tunnel are matched against `network.rules` again, so the
+    // tunnel itself only has to honor rules that apply regardless of method and path.
+    let scope = if mitm.is_some() {
+        RuleScope::InterceptedTunnel
+    } else {
+        RuleScope::Tunnel
+    };
+    match evaluate_host_policy(&app_state, policy_decider.as_ref(), &request, scope).await {
+        Ok(PolicyEvaluation {
+            decision:
+                NetworkDecision::Deny {
+                    reason,
+                    source,
+                    decision,
+                },
+            matched_rule,
         }) => {
             let details = PolicyDecisionDetails {
                 decision,
@@ -206,24 +235,30 @@ async fn http_connect_accept(
                 port: authority.port,
             };
             let _ = app_state
-                .record_blocked(BlockedRequest::new(BlockedRequestArgs {
-                    host: host.clone(),
-                    reason: reason.clone(),
-                    client: client.clone(),
-                    method: Some("CONNECT".to_string()),
-                    mode: None,
-                    protocol: "http-connect".to_string(),
-                    attempt_id: network_attempt_id.clone(),
-                    decision: Some(details.decision.as_str().to_string()),
-                    source: Some(details.source.as_str().to_string()),
-                    port: Some(authority.port),
-                }))
+                .record_blocked(
+                    BlockedRequest::new(BlockedRequestArgs {
+                        host: host.clone(),
+                        reason: reason.clone(),
+                        client: client.clone(),
+                        method: Some("CONNECT".to_string()),
+                        mode: None,
+                        protocol: "http-connect".to_string(),
+                        attempt_id: network_attempt_id.clone(),
+                        decision: Some(details.decision.as_str().to_string()),
+                        source: Some(details.source.as_str().to_string()),
+                        port: Some(authority.port),
+                    })
+                    .with_matched_rule(matched_rule),
+                )
                 .await;
             let client = client.as_deref().unwrap_or_default();
             warn!("CONNECT blocked (client={client}, host={host}, reason={reason})");
             return Err(blocked_text_with_details(&reason, &details));
         }
-        Ok(NetworkDecision::Allow) => {
+        Ok(PolicyEvaluation {
+            decision: NetworkDecision::Allow,
+            ..
+        }) => {
             let client = client.as_deref().unwrap_or_default();
             info!("CONNECT allowed (client={client}, host={host})");
         }
@@ -238,7 +273,8 @@ async fn http_connect_accept(
         .await
         .map_err(|err| internal_error("failed to read network mode", err))?;
 
-    if mode == NetworkMode::Limited {
+    // With MITM enabled the tunnel is terminated locally and limited mode is enforced per request.
+    if mode == NetworkMode::Limited && mitm.is_none() {
         let details = PolicyDecisionDetails {
             decision: NetworkPolicyDecision::Deny,
             reason: REASON_METHOD_NOT_ALLOWED,
@@ -269,8 +305,30 @@ async fn http_connect_accept(
         ));
     }
 
+    let port = authority.port;
     req.extensions_mut().insert(ProxyTarget(authority));
     req.extensions_mut().insert(mode);
+    if let Some(ca) = mitm {
+        // Intercepted tunnels are recorded request by request in `mitm_request`.
+        req.extensions_mut().insert(MitmConnect {
+            ca,
+            policy_decider,
+            attempt_id: network_attempt_id,
+        });
+    } else {
+        app_state
+            .record_allowed(AllowedRequestArgs {
+                host,
+                port: Some(port),
+                protocol: "http-connect".to_string(),
+                method: Some("CONNECT".to_string()),
+                client,
+                attempt_id: network_attempt_id,
+                request_bytes: None,
+                response_bytes: None,
+            })
+            .await;
+    }
 
     Ok((
         Response::builder()
@@ -311,7 +369,12 @@ async fn http_connect_proxy(upgraded: Upgraded) -> Result<(), Infallible> {
         None
     };
 
-    if let Err(err) = forward_connect_tunnel(upgraded, proxy).await {
+    let mitm = upgraded.extensions().get::<MitmConnect>().cloned();
+    let result = match mitm {
+        Some(mitm) => intercept_connect_tunnel(upgraded, mitm, allow_upstream_proxy).await,
+        None => forward_connect_tunnel(upgraded, proxy).await,
+    };
+    if let Err(err) = result {
         warn!("tunnel error: {err}");
     }
     Ok(())
@@ -362,6 +425,257 @@ async fn forward_connect_tunnel(
         })
 }
 
+/// Everything needed to police and forward requests read from an intercepted CONNECT tunnel.
+struct MitmTarget {
+    state: Arc<NetworkProxyState>,
+    authority: String,
+    host: String,
+    port: u16,
+    client: Option<String>,
+    policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
+    attempt_id: Option<String>,
+    upstream: UpstreamClient,
+}
+
+async fn intercept_connect_tunnel(
+    upgraded: Upgraded,
+    mitm: MitmConnect,
+    allow_upstream_proxy: bool,
+) -> Result<(), BoxError> {
+    let authority = upgraded
+        .extensions()
+        .get::<ProxyTarget>()
+        .map(|target| target.0.clone())
+        .ok_or_else(|| OpaqueError::from_display("missing forward authority").into_boxed())?;
+    let state = upgraded
+        .extensions()
+        .get::<Arc<NetworkProxyState>>()
+        .cloned()
+        .ok_or_else(|| OpaqueError::from_display("missing app state").into_boxed())?;
+
+    let host = normalize_host(&authority.host.to_string());
+    let server_config = mitm.ca.server_config_for_host(&host).map_err(|err| {
+        OpaqueError::from_display(format!("mint certificate for {host}: {err:#}")).into_boxed()
+    })?;
+    let target = Arc::new(MitmTarget {
+        state,
+        authority: authority.to_string(),
+        host,
+        port: authority.port,
+        client: client_addr(&upgraded),
+        policy_decider: mitm.policy_decider,
+        attempt_id: mitm.attempt_id,
+        upstream: if allow_upstream_proxy {
+            UpstreamClient::from_env_proxy()
+        } else {
+            UpstreamClient::direct()
+        },
+    });
+
+    let http_service = HttpServer::auto(Executor::new()).service(service_fn(move |req| {
+        mitm_request(Arc::clone(&target), req)
+    }));
+    TlsAcceptorLayer::new(TlsAcceptorData::from(server_config))
+        .into_layer(http_service)
+        .serve(upgraded)
+        .await
+        .map_err(|err| {
+            OpaqueError::from_boxed(err.into())
+                .with_context(|| format!("intercept CONNECT tunnel to {authority}"))
+                .into_boxed()
+        })
+}
+
+async fn mitm_request(target: Arc<MitmTarget>, mut req: Request) -> Result<Response, Infallible> {
+    let client = target.client.as_deref().unwrap_or_default();
+    let method = req.method().as_str().to_string();
+
+    // The leaf certificate only vouches for the CONNECT host; refuse requests aimed elsewhere so
+    // an allowed tunnel cannot be reused to reach a different origin.
+    let request_host = RequestContext::try_from(&req)
+        .ok()
+        .map(|ctx| normalize_host(&ctx.host_with_port().host.to_string()));
+    if let Some(request_host) = request_host
+        && request_host != target.host
+    {
+        warn!(
+            "intercepted request blocked; host mismatch (client={client}, host={}, request_host={request_host})",
+            target.host
+        );
+        return Ok(text_response(
+            StatusCode::MISDIRECTED_REQUEST,
+            "request host does not match CONNECT target",
+        ));
+    }
+
+    // Read the mode per request: it can change while the tunnel stays open.
+    let mode = match target
+        .state
+        .network_mode()
+        .await
+        .map_err(|err| internal_error("failed to read network mode", err))
+    {
+        Ok(mode) => mode,
+        Err(resp) => return Ok(resp),
+    };
+    if !mode.allows_method(&method) {
+        let details = PolicyDecisionDetails {
+            decision: NetworkPolicyDecision::Deny,
+            reason: REASON_METHOD_NOT_ALLOWED,
+            source: NetworkDecisionSource::ModeGuard,
+            protocol: NetworkProtocol::HttpsConnect,
+            host: &target.host,
+            port: target.port,
+        };
+        let _ = target
+            .state
+            .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                host: target.host.clone(),
+                reason: REASON_METHOD_NOT_ALLOWED.to_string(),
+                client: target.client.clone(),
+                method: Some(method.clone()),
+                mode: Some(mode),
+                protocol: "https".to_string(),
+                attempt_id: target.attempt_id.clone(),
+                decision: Some(details.decision.as_str().to_string()),
+                source: Some(details.source.as_str().to_string()),
+                port: Some(target.port),
+            }))
+            .await;
+        warn!(
+            "intercepted request blocked by method policy (client={client}, host={}, method={method}, mode={mode:?})",
+            target.host
+        );
+        return Ok(json_blocked(
+            &target.host,
+            REASON_METHOD_NOT_ALLOWED,
+            Some(&details),
+        ));
+    }
+
+    let path = req
+        .uri()
+        .path_and_query()
+        .map(rama_http::uri::PathAndQuery::as_str)
+        .unwrap_or("/")
+        .to_string();
+    let request = NetworkPolicyRequest::new(NetworkPolicyRequestArgs {
+        protocol: NetworkProtocol::HttpsConnect,
+        host: target.host.clone(),
+        port: target.port,
+        client_addr: target.client.clone(),
+        method: Some(method.clone()),
+        command: None,
+        exec_policy_hint: None,
+        attempt_id: target.attempt_id.clone(),
+    });
+    let scope = RuleScope::Request {
+        method: &method,
+        path: &path,
+    };
+    match evaluate_host_policy(
+        &target.state,
+        target.policy_decider.as_ref(),
+        &request,
+        scope,
+    )
+    .await
+    {
+        Ok(PolicyEvaluation {
+            decision:
+                NetworkDecision::Deny {
+                    reason,
+                    source,
+                    decision,
+                },
+            matched_rule,
+        }) => {
+            let details = PolicyDecisionDetails {
+                decision,
+                reason: &reason,
+                source,
+                protocol: NetworkProtocol::HttpsConnect,
+                host: &target.host,
+                port: target.port,
+            };
+            let _ = target
+                .state
+                .record_blocked(
+                    BlockedRequest::new(BlockedRequestArgs {
+                        host: target.host.clone(),
+                        reason: reason.clone(),
+                        client: target.client.clone(),
+                        method: Some(method.clone()),
+                        mode: None,
+                        protocol: "https".to_string(),
+                        attempt_id: target.attempt_id.clone(),
+                        decision: Some(details.decision.as_str().to_string()),
+                        source: Some(details.source.as_str().to_string()),
+                        port: Some(target.port),
+                    })
+                    .with_matched_rule(matched_rule),
+                )
+                .await;
+            warn!(
+                "intercepted request blocked (client={client}, host={}, method={method}, path={path}, reason={reason})",
+                target.host
+            );
+            return Ok(json_blocked(&target.host, &reason, Some(&details)));
+        }
+        Ok(PolicyEvaluation {
+            decision: NetworkDecision::Allow,
+            ..
+        }) => {}
+        Err(err) => {
+            error!(
+                "failed to evaluate intercepted request to {}: {err}",
+                target.host
+            );
+            return Ok(text_response(StatusCode::INTERNAL_SERVER_ERROR, "error"));
+        }
+    }
+
+    info!(
+        "intercepted request allowed (client={client}, host={}, method={method})",
+        target.host
+    );
+
+    *req.uri_mut() = match format!("https://{}{path}", target.authority).parse() {
+        Ok(uri) => uri,
+        Err(err) => {
+            warn!("invalid intercepted request path: {err}");
+            return Ok(text_response(
+                StatusCode::BAD_REQUEST,
+                "invalid request path",
+            ));
+        }
+    };
+    remove_hop_by_hop_request_headers(req.headers_mut());
+    let mut allowed_request = AllowedRequestArgs {
+        host: target.host.clone(),
+        port: Some(target.port),
+        protocol: "https".to_string(),
+        method: Some(method),
+        client: target.client.clone(),
+        attempt_id: target.attempt_id.clone(),
+        request_bytes: content_length(req.headers()),
+        response_bytes: None,
+    };
+    let response = target.upstream.serve(req).await;
+    allowed_request.response_bytes = response
+        .as_ref()
+        .ok()
+        .and_then(|resp| content_length(resp.headers()));
+    target.state.record_allowed(allowed_request).await;
+    match response {
+        Ok(resp) => Ok(resp),
+        Err(err) => {
+            warn!("upstream request failed: {err}");
+            Ok(text_response(StatusCode::BAD_GATEWAY, "upstream failure"))
+        }
+    }
+}
+
 async fn http_plain_proxy(
     policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
     mut req: Request,
@@ -507,11 +821,25 @@ async fn http_plain_proxy(
         attempt_id: network_attempt_id.clone(),
     });
 
-    match evaluate_host_policy(&app_state, policy_decider.as_ref(), &request).await {
-        Ok(NetworkDecision::Deny {
-            reason,
-            source,
-            decision,
+    let path = req
+        .uri()
+        .path_and_query()
+        .map(rama_http::uri::PathAndQuery::as_str)
+        .unwrap_or("/")
+        .to_string();
+    let scope = RuleScope::Request {
+        method: req.method().as_str(),
+        path: &path,
+    };
+    match evaluate_host_policy(&app_state, policy_decider.as_ref(), &request, scope).await {
+        Ok(PolicyEvaluation {
+            decision:
+                NetworkDecision::Deny {
+                    reason,
+                    source,
+                    decision,
+                },
+            matched_rule,
         }) => {
             let details = PolicyDecisionDetails {
                 decision,
@@ -522,24 +850,30 @@ async fn http_plain_proxy(
                 port,
             };
             let _ = app_state
-                .record_blocked(BlockedRequest::new(BlockedRequestArgs {
-                    host: host.clone(),
-                    reason: reason.clone(),
-                    client: client.clone(),
-                    method: Some(req.method().as_str().to_string()),
-                    mode: None,
-                    protocol: "http".to_string(),
-                    attempt_id: network_attempt_id.clone(),
-                    decision: Some(details.decision.as_str().to_string()),
-                    source: Some(details.source.as_str().to_string()),
-                    port: Some(port),
-                }))
+                .record_blocked(
+                    BlockedRequest::new(BlockedRequestArgs {
+                        host: host.clone(),
+                        reason: reason.clone(),
+                        client: client.clone(),
+                        method: Some(req.method().as_str().to_string()),
+                        mode: None,
+                        protocol: "http".to_string(),
+                        attempt_id: network_attempt_id.clone(),
+                        decision: Some(details.decision.as_str().to_string()),
+                        source: Some(details.source.as_str().to_string()),
+                        port: Some(port),
+                    })
+                    .with_matched_rule(matched_rule),
+                )
                 .await;
             let client = client.as_deref().unwrap_or_default();
             warn!("request blocked (client={client}, host={host}, reason={reason})");
             return Ok(json_blocked(&host, &reason, Some(&details)));
         }
-        Ok(NetworkDecision::Allow) => {}
+        Ok(PolicyEvaluation {
+            decision: NetworkDecision::Allow,
+            ..
+        }) => {}
         Err(err) => {
             error!("failed to evaluate host for {host}: {err}");
             return Ok(text_response(StatusCode::INTERNAL_SERVER_ERROR, "error"));
@@ -581,6 +915,16 @@ async fn http_plain_proxy(
         ));
     }
 
+    let mut allowed_request = AllowedRequestArgs {
+        host: host.clone(),
+        port: Some(port),
+        protocol: "http".to_string(),
+        method: Some(req.method().as_str().to_string()),
+        client: client.clone(),
+        attempt_id: network_attempt_id,
+        request_bytes: content_length(req.headers()),
+        response_bytes: None,
+    };
     let client = client.as_deref().unwrap_or_default();
     let method = req.method();
     info!("request allowed (client={client}, host={host}, method={method})");
@@ -601,7 +945,13 @@ async fn http_plain_proxy(
 
     // Strip hop-by-hop headers only after extracting metadata used for policy correlation.
     remove_hop_by_hop_request_headers(req.headers_mut());
-    match client.serve(req).await {
+    let response = client.serve(req).await;
+    allowed_request.response_bytes = response
+        .as_ref()
+        .ok()
+        .and_then(|resp| content_length(resp.headers()));
+    app_state.record_allowed(allowed_request).await;
+    match response {
         Ok(resp) => Ok(resp),
         Err(err) => {
             warn!("upstream request failed: {err}");
@@ -645,6 +995,16 @@ fn client_addr<T: ExtensionsRef>(input: &T) -> Option<String> {
         .map(|info| info.peer_addr().to_string())
 }
 
+/// Body size announced by `content-length`; chunked bodies are not counted.
+fn content_length(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(header::CONTENT_LENGTH)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+}
+
 fn request_network_attempt_id(req: &Request) -> Option<String> {
     // Some HTTP stacks normalize proxy credentials into `authorization`; accept both.
     attempt_id_from_proxy_authorization(req.headers().get("proxy-authorization"))
@@ -820,7 +1180,7 @@ mod tests {
             .unwrap();
         req.extensions_mut().insert(state);
 
-        let response = http_connect_accept(None, req).await.unwrap_err();
+        let response = http_connect_accept(None, None, req).await.unwrap_err();
         assert_eq!(response.status(), StatusCode::FORBIDDEN);
         assert_eq!(
             response.headers().get("x-proxy-error").unwrap(),
@@ -828,6 +1188,42 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn http_connect_accept_intercepts_in_limited_mode_with_mitm() {
+        let codex_home = tempfile::tempdir().unwrap();
+        let ca = Arc::new(MitmCertificateAuthority::load_or_create(codex_home.path()).unwrap());
+        let policy = NetworkProxySettings {
+            allowed_domains: vec!["example.com".to_string()],
+            ..Default::default()
+        };
+        let state = Arc::new(network_proxy_state_for_policy(policy));
+        state.set_network_mode(NetworkMode::Limited).await.unwrap();
+
+        let mut req = Request::builder()
+            .method(Method::CONNECT)
+            .uri("https://example.com:443")
+            .header("host", "example.com:443")
+            .body(Body::empty())
+            .unwrap();
+        req.extensions_mut().insert(state);
+
+        let (response, req) = http_connect_accept(None, Some(ca), req)
+            .await
+            .unwrap_or_else(|_| panic!("CONNECT should be accepted for interception"));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(req.extensions().get::<MitmConnect>().is_some());
+    }
+
+    #[test]
+    fn content_length_reads_declared_body_size() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(content_length(&headers), None);
+        headers.insert(header::CONTENT_LENGTH, HeaderValue::from_static("512"));
+        assert_eq!(content_length(&headers), Some(512));
+        headers.insert(header::CONTENT_LENGTH, HeaderValue::from_static("chunked"));
+        assert_eq!(content_length(&headers), None);
+    }
+
     #[test]
     fn request_network_attempt_id_reads_proxy_authorization_header() {
         let encoded = STANDARD.encode("codex-net-attempt-attempt-1:");
@@ -1,6 +1,7 @@
 #![deny(clippy::print_stdout, clippy::print_stderr)]
 
 mod admin;
+mod certs;
 mod config;
 mod http_proxy;
 mod metadata;
@@ -14,6 +15,8 @@ mod socks5;
 mod state;
 mod upstream;
 
+pub use certs::MITM_CA_ENV_KEYS;
+pub use certs::MITM_EXTRA_CA_ENV_KEYS;
 pub use config::NetworkMode;
 pub use config::NetworkProxyConfig;
 pub use config::host_and_port_from_network_addr;
@@ -35,12 +38,15 @@ pub use proxy::NetworkProxyHandle;
 pub use proxy::PROXY_URL_ENV_KEYS;
 pub use proxy::has_proxy_url_env_vars;
 pub use proxy::proxy_url_env_value;
+pub use runtime::AllowedRequestArgs;
 pub use runtime::BlockedRequest;
 pub use runtime::BlockedRequestArgs;
 pub use runtime::BlockedRequestObserver;
 pub use runtime::ConfigReloader;
 pub use runtime::ConfigState;
 pub use runtime::NetworkProxyState;
+pub use runtime::NetworkRequestEvent;
+pub use runtime::NetworkRequestObserver;
 pub use state::NetworkProxyConstraintError;
 pub use state::NetworkProxyConstraints;
 pub use state::PartialNetworkConfig;
@@ -1,4 +1,9 @@
+use crate::config::NetworkRule;
+use crate::config::NetworkRuleDecision;
+use crate::policy::NetworkRuleMatcher;
+use crate::policy::normalize_request_path;
 use crate::reasons::REASON_POLICY_DENIED;
+use crate::reasons::REASON_RULE_DENIED;
 use crate::runtime::HostBlockDecision;
 use crate::runtime::HostBlockReason;
 use crate::state::NetworkProxyState;
@@ -186,28 +191,135 @@ where
     }
 }
 
+/// How much of a request the proxy can see when matching `network.rules`.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum RuleScope<'a> {
+    /// Plain HTTP, or a request read from an intercepted HTTPS tunnel.
+    Request { method: &'a str, path: &'a str },
+    /// An opaque tunnel (CONNECT without MITM, SOCKS5); only the host is known.
+    Tunnel,
+    /// A CONNECT that will be intercepted; each request inside it is matched again.
+    InterceptedTunnel,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PolicyEvaluation {
+    pub(crate) decision: NetworkDecision,
+    pub(crate) matched_rule: Option<NetworkRule>,
+}
+
+impl From<NetworkDecision> for PolicyEvaluation {
+    fn from(decision: NetworkDecision) -> Self {
+        Self {
+            decision,
+            matched_rule: None,
+        }
+    }
+}
+
 pub(crate) async fn evaluate_host_policy(
     state: &NetworkProxyState,
     decider: Option<&Arc<dyn NetworkPolicyDecider>>,
     request: &NetworkPolicyRequest,
-) -> Result<NetworkDecision> {
-    match state.host_blocked(&request.host, request.port).await? {
-        HostBlockDecision::Allowed => Ok(NetworkDecision::Allow),
+    scope: RuleScope<'_>,
+) -> Result<PolicyEvaluation> {
+    // Decision order matters:
+    //  1) explicit deny and local/private protection always win
+    //  2) the first matching deny rule, else the first matching allow rule
+    //  3) the allowlist, with the decider consulted on misses
+    let host_decision = state.host_blocked(&request.host, request.port).await?;
+    if let HostBlockDecision::Blocked(
+        reason @ (HostBlockReason::Denied | HostBlockReason::NotAllowedLocal),
+    ) = host_decision
+    {
+        return Ok(NetworkDecision::deny_with_source(
+            reason.as_str(),
+            NetworkDecisionSource::BaselinePolicy,
+        )
+        .into());
+    }
+
+    let rules = state.network_rules().await?;
+    if let Some(rule) = match_network_rule(&rules, &request.host, scope) {
+        let decision = match rule.decision {
+            NetworkRuleDecision::Allow => NetworkDecision::Allow,
+            NetworkRuleDecision::Deny => NetworkDecision::deny_with_source(
+                REASON_RULE_DENIED,
+                NetworkDecisionSource::BaselinePolicy,
+            ),
+        };
+        return Ok(PolicyEvaluation {
+            decision,
+            matched_rule: Some(rule.clone()),
+        });
+    }
+
+    let decision = match host_decision {
+        HostBlockDecision::Allowed => NetworkDecision::Allow,
         HostBlockDecision::Blocked(HostBlockReason::NotAllowed) => {
             if let Some(decider) = decider {
-                Ok(map_decider_decision(decider.decide(request.clone()).await))
+                map_decider_decision(decider.decide(request.clone()).await)
             } else {
-                Ok(NetworkDecision::deny_with_source(
+                NetworkDecision::deny_with_source(
                     HostBlockReason::NotAllowed.as_str(),
                     NetworkDecisionSource::BaselinePolicy,
-                ))
+                )
             }
         }
-        HostBlockDecision::Blocked(reason) => Ok(NetworkDecision::deny_with_source(
+        HostBlockDecision::Blocked(reason) => NetworkDecision::deny_with_source(
             reason.as_str(),
             NetworkDecisionSource::BaselinePolicy,
-        )),
+        ),
+    };
+    Ok(decision.into())
+}
+
+/// Returns the rule that decides a request to `host`: a matching deny rule wins over any matching
+/// allow rule, and otherwise rules are considered in configuration order.
+fn match_network_rule<'a>(
+    rules: &'a [NetworkRuleMatcher],
+    host: &str,
+    scope: RuleScope<'_>,
+) -> Option<&'a NetworkRule> {
+    // Paths are matched as the origin server will resolve them. One that cannot be normalized (it
+    // escapes the root or is not UTF-8) is treated like an opaque tunnel, so conditional denies
+    // still block it and conditional allows do not open it.
+    let normalized_path = match scope {
+        RuleScope::Request { path, .. } => normalize_request_path(path),
+        RuleScope::Tunnel | RuleScope::InterceptedTunnel => None,
+    };
+    let scope = match (scope, normalized_path.as_deref()) {
+        (RuleScope::Request { method, .. }, Some(path)) => RuleScope::Request { method, path },
+        (RuleScope::Request { .. }, None) => RuleScope::Tunnel,
+        (scope, _) => scope,
+    };
+    let mut allow = None;
+    for matcher in rules.iter().filter(|matcher| matcher.matches_host(host)) {
+        let rule = matcher.rule();
+        let applies = match (scope, rule.decision) {
+            (RuleScope::Request { method, path }, _) => {
+                matcher.matches_method(method) && matcher.matches_path(path)
+            }
+            // A method or path condition cannot be checked inside an opaque tunnel, so a
+            // conditional deny blocks the whole tunnel and a conditional allow does not open it.
+            (RuleScope::Tunnel, NetworkRuleDecision::Deny) => true,
+            (RuleScope::Tunnel, NetworkRuleDecision::Allow) => rule.is_host_only(),
+            // Intercepted requests are matched individually, so only unconditional denies apply to
+            // the tunnel itself.
+            (RuleScope::InterceptedTunnel, NetworkRuleDecision::Deny) => rule.is_host_only(),
+            (RuleScope::InterceptedTunnel, NetworkRuleDecision::Allow) => true,
+        };
+        if !applies {
+            continue;
+        }
+        match rule.decision {
+            NetworkRuleDecision::Deny => return Some(rule),
+            NetworkRuleDecision::Allow => {
+                allow.get_or_insert(rule);
+            }
+        }
     }
+    allow
 }
 
 fn map_decider_decision(decision: NetworkDecision) -> NetworkDecision {
@@ -230,6 +342,7 @@ mod tests {
     use crate::reasons::REASON_DENIED;
     use crate::reasons::REASON_NOT_ALLOWED;
     use crate::reasons::REASON_NOT_ALLOWED_LOCAL;
+    use crate::reasons::REASON_RULE_DENIED;
     use crate::state::network_proxy_state_for_policy;
     use pretty_assertions::assert_eq;
     use std::sync::Arc;
@@ -261,9 +374,10 @@ mod tests {
             attempt_id: None,
         });
 
-        let decision = evaluate_host_policy(&state, Some(&decider), &request)
+        let decision = evaluate_host_policy(&state, Some(&decider), &request, RuleScope::Tunnel)
             .await
-            .unwrap();
+            .unwrap()
+            .decision;
         assert_eq!(decision, NetworkDecision::Allow);
         assert_eq!(calls.load(Ordering::SeqCst), 1);
     }
@@ -295,9 +409,10 @@ mod tests {
             attempt_id: None,
         });
 
-        let decision = evaluate_host_policy(&state, Some(&decider), &request)
+        let decision = evaluate_host_policy(&state, Some(&decider), &request, RuleScope::Tunnel)
             .await
-            .unwrap();
+            .unwrap()
+            .decision;
         assert_eq!(
             decision,
             NetworkDecision::Deny {
@@ -336,9 +451,10 @@ mod tests {
             attempt_id: None,
         });
 
-        let decision = evaluate_host_policy(&state, Some(&decider), &request)
+        let decision = evaluate_host_policy(&state, Some(&decider), &request, RuleScope::Tunnel)
             .await
-            .unwrap();
+            .unwrap()
+            .decision;
         assert_eq!(
             decision,
             NetworkDecision::Deny {
@@ -350,6 +466,264 @@ mod tests {
         assert_eq!(calls.load(Ordering::SeqCst), 0);
     }
 
+    fn rule(
+        host: &str,
+        path_prefix: Option<&str>,
+        methods: &[&str],
+        decision: NetworkRuleDecision,
+    ) -> NetworkRule {
+        NetworkRule {
+            host: host.to_string(),
+            path_prefix: path_prefix.map(ToString::to_string),
+            methods: methods.iter().map(ToString::to_string).collect(),
+            decision,
+        }
+    }
+
+    fn https_request(host: &str) -> NetworkPolicyRequest {
+        NetworkPolicyRequest::new(NetworkPolicyRequestArgs {
+            protocol: NetworkProtocol::HttpsConnect,
+            host: host.to_string(),
+            port: 443,
+            client_addr: None,
+            method: Some("CONNECT".to_string()),
+            command: None,
+            exec_policy_hint: None,
+            attempt_id: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn evaluate_host_policy_applies_rules_by_method_and_path() {
+        let npm_get = rule(
+            "registry.npmjs.org",
+            Some("/*"),
+            &["GET"],
+            NetworkRuleDecision::Allow,
+        );
+        let issue_post = rule(
+            "api.github.com",
+            Some("/repos/*/issues"),
+            &["POST"],
+            NetworkRuleDecision::Deny,
+        );
+        let state = network_proxy_state_for_policy(NetworkProxySettings {
+            allowed_domains: vec!["api.github.com".to_string()],
+            rules: vec![npm_get.clone(), issue_post.clone()],
+            ..NetworkProxySettings::default()
+        });
+
+        let npm = https_request("registry.npmjs.org");
+        let allowed = evaluate_host_policy(
+            &state,
+            None,
+            &npm,
+            RuleScope::Request {
+                method: "GET",
+                path: "/left-pad",
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            allowed,
+            PolicyEvaluation {
+                decision: NetworkDecision::Allow,
+                matched_rule: Some(npm_get),
+            }
+        );
+
+        // A request no rule covers falls back to the allowlist.
+        let publish = evaluate_host_policy(
+            &state,
+            None,
+            &npm,
+            RuleScope::Request {
+                method: "PUT",
+                path: "/left-pad",
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            publish.decision,
+            NetworkDecision::deny_with_source(
+                REASON_NOT_ALLOWED,
+                NetworkDecisionSource::BaselinePolicy
+            )
+        );
+
+        let github = https_request("api.github.com");
+        let issue = evaluate_host_policy(
+            &state,
+            None,
+            &github,
+            RuleScope::Request {
+                method: "POST",
+                path: "/repos/openai/codex/issues",
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            issue,
+            PolicyEvaluation {
+                decision: NetworkDecision::deny_with_source(
+                    REASON_RULE_DENIED,
+                    NetworkDecisionSource::BaselinePolicy
+                ),
+                matched_rule: Some(issue_post),
+            }
+        );
+
+        let read = evaluate_host_policy(
+            &state,
+            None,
+            &github,
+            RuleScope::Request {
+                method: "GET",
+                path: "/repos/openai/codex/issues",
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(read, NetworkDecision::Allow.into());
+    }
+
+    #[tokio::test]
+    async fn evaluate_host_policy_matches_rules_against_normalized_paths() {
+        let admin_deny = rule(
+            "api.example.com",
+            Some("/admin"),
+            &[],
+            NetworkRuleDecision::Deny,
+        );
+        let public_allow = rule(
+            "api.example.com",
+            Some("/public"),
+            &[],
+            NetworkRuleDecision::Allow,
+        );
+        let state = network_proxy_state_for_policy(NetworkProxySettings {
+            rules: vec![admin_deny.clone(), public_allow],
+            ..NetworkProxySettings::default()
+        });
+        let request = https_request("api.example.com");
+        let rule_denied = PolicyEvaluation {
+            decision: NetworkDecision::deny_with_source(
+                REASON_RULE_DENIED,
+                NetworkDecisionSource::BaselinePolicy,
+            ),
+            matched_rule: Some(admin_deny),
+        };
+
+        for path in [
+            "//admin",
+            "/./admin",
+            "/%61dmin",
+            "/public/../admin",
+            "/public/%2e%2e/admin",
+            "/public/..%2fadmin",
+            // Paths that escape the root are refused like an opaque tunnel.
+            "/../public",
+            "/public/%2e%2e/%2e%2e/admin",
+        ] {
+            let evaluation = evaluate_host_policy(
+                &state,
+                None,
+                &request,
+                RuleScope::Request {
+                    method: "GET",
+                    path,
+                },
+            )
+            .await
+            .unwrap();
+            assert_eq!(evaluation, rule_denied, "{path}");
+        }
+    }
+
+    #[tokio::test]
+    async fn evaluate_host_policy_applies_conditional_rules_conservatively_to_tunnels() {
+        let npm_get = rule(
+            "registry.npmjs.org",
+            Some("/*"),
+            &["GET"],
+            NetworkRuleDecision::Allow,
+        );
+        let issue_post = rule(
+            "api.github.com",
+            Some("/repos/*/issues"),
+            &["POST"],
+            NetworkRuleDecision::Deny,
+        );
+        let state = network_proxy_state_for_policy(NetworkProxySettings {
+            allowed_domains: vec!["api.github.com".to_string()],
+            rules: vec![npm_get.clone(), issue_post.clone()],
+            ..NetworkProxySettings::default()
+        });
+        let npm = https_request("registry.npmjs.org");
+        let github = https_request("api.github.com");
+
+        // Without interception neither rule can be checked, so the allow rule does not open the
+        // tunnel and the deny rule closes it.
+        let npm_tunnel = evaluate_host_policy(&state, None, &npm, RuleScope::Tunnel)
+            .await
+            .unwrap();
+        assert_eq!(npm_tunnel.matched_rule, None);
+        assert_eq!(
+            npm_tunnel.decision,
+            NetworkDecision::deny_with_source(
+                REASON_NOT_ALLOWED,
+                NetworkDecisionSource::BaselinePolicy
+            )
+        );
+        let github_tunnel = evaluate_host_policy(&state, None, &github, RuleScope::Tunnel)
+            .await
+            .unwrap();
+        assert_eq!(github_tunnel.matched_rule, Some(issue_post));
+
+        // With interception each request is matched later, so both tunnels open.
+        let npm_tunnel = evaluate_host_policy(&state, None, &npm, RuleScope::InterceptedTunnel)
+            .await
+            .unwrap();
+        assert_eq!(
+            npm_tunnel,
+            PolicyEvaluation {
+                decision: NetworkDecision::Allow,
+                matched_rule: Some(npm_get),
+            }
+        );
+        let github_tunnel =
+            evaluate_host_policy(&state, None, &github, RuleScope::InterceptedTunnel)
+                .await
+                .unwrap();
+        assert_eq!(github_tunnel, NetworkDecision::Allow.into());
+    }
+
+    #[tokio::test]
+    async fn evaluate_host_policy_denylist_wins_over_allow_rules() {
+        let state = network_proxy_state_for_policy(NetworkProxySettings {
+            denied_domains: vec!["evil.example".to_string()],
+            rules: vec![rule("evil.example", None, &[], NetworkRuleDecision::Allow)],
+            ..NetworkProxySettings::default()
+        });
+
+        let evaluation = evaluate_host_policy(
+            &state,
+            None,
+            &https_request("evil.example"),
+            RuleScope::Tunnel,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            evaluation,
+            NetworkDecision::deny_with_source(REASON_DENIED, NetworkDecisionSource::BaselinePolicy)
+                .into()
+        );
+    }
+
     #[test]
     fn ask_uses_decider_source_and_ask_decision() {
         assert_eq!(
@@ -1,5 +1,6 @@
 #[cfg(test)]
 use crate::config::NetworkMode;
+use crate::config::NetworkRule;
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::ensure;
@@ -168,6 +169,103 @@ pub(crate) fn compile_globset(patterns: &[String]) -> Result<GlobSet> {
     Ok(builder.build()?)
 }
 
+/// A `network.rules` entry with its host pattern compiled.
+#[derive(Debug, Clone)]
+pub(crate) struct NetworkRuleMatcher {
+    rule: NetworkRule,
+    host_set: GlobSet,
+}
+
+impl NetworkRuleMatcher {
+    pub(crate) fn rule(&self) -> &NetworkRule {
+        &self.rule
+    }
+
+    pub(crate) fn matches_host(&self, host: &str) -> bool {
+        self.host_set.is_match(host)
+    }
+
+    pub(crate) fn matches_method(&self, method: &str) -> bool {
+        self.rule.methods.is_empty()
+            || self
+                .rule
+                .methods
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(method))
+    }
+
+    pub(crate) fn matches_path(&self, path: &str) -> bool {
+        self.rule
+            .path_prefix
+            .as_deref()
+            .is_none_or(|prefix| path_matches_prefix(prefix, path))
+    }
+}
+
+pub(crate) fn compile_rules(rules: &[NetworkRule]) -> Result<Vec<NetworkRuleMatcher>> {
+    rules
+        .iter()
+        .map(|rule| {
+            if let Some(prefix) = rule.path_prefix.as_deref() {
+                ensure!(
+                    prefix.starts_with('/'),
+                    "invalid network rule for {}: path_prefix must start with `/`: {prefix}",
+                    rule.host
+                );
+            }
+            let host_set = compile_globset(std::slice::from_ref(&rule.host))
+                .with_context(|| format!("invalid network rule host: {}", rule.host))?;
+            Ok(NetworkRuleMatcher {
+                rule: rule.clone(),
+                host_set,
+            })
+        })
+        .collect()
+}
+
+/// Returns the path an origin server will resolve for a request target: percent-escapes decoded,
+/// `.` and `..` segments applied and repeated slashes collapsed, with the query dropped. Returns
+/// `None` when the path climbs above the root or does not decode to UTF-8, so callers can refuse
+/// it instead of letting `/public/..%2fadmin` slip past a `/admin` rule.
+pub(crate) fn normalize_request_path(path: &str) -> Option<String> {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let decoded = urlencoding::decode(path).ok()?;
+    let mut segments = Vec::new();
+    for segment in decoded.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop()?;
+            }
+            segment => segments.push(segment),
+        }
+    }
+    let mut normalized = format!("/{}", segments.join("/"));
+    if !segments.is_empty() && matches!(decoded.rsplit('/').next(), Some("" | "." | "..")) {
+        normalized.push('/');
+    }
+    Some(normalized)
+}
+
+/// Returns true when `path` starts with `prefix`, where each `*` in `prefix` matches any run of
+/// characters (including `/`). `path` is expected to come from [`normalize_request_path`].
+fn path_matches_prefix(prefix: &str, path: &str) -> bool {
+    let mut parts = prefix.split('*');
+    let Some(first) = parts.next() else {
+        return true;
+    };
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
 #[derive(Debug, Clone)]
 pub(crate) enum DomainPattern {
     Any,
@@ -362,6 +460,80 @@ mod tests {
         assert_eq!(true, set.is_match("::1"));
     }
 
+    #[test]
+    fn path_matches_prefix_expands_wildcards() {
+        assert!(path_matches_prefix("/", "/left-pad"));
+        assert!(path_matches_prefix(
+            "/repos/*/issues",
+            "/repos/openai/codex/issues"
+        ));
+        assert!(path_matches_prefix(
+            "/repos/*/issues",
+            "/repos/openai/codex/issues/12?x=1"
+        ));
+        assert!(!path_matches_prefix(
+            "/repos/*/issues",
+            "/repos/openai/codex/pulls"
+        ));
+        assert!(!path_matches_prefix("/repos/*/issues", "/users/openai"));
+    }
+
+    #[test]
+    fn normalize_request_path_resolves_encoded_and_dot_segments() {
+        let normalize = |path| normalize_request_path(path);
+        assert_eq!(
+            normalize("/repos/openai?x=1"),
+            Some("/repos/openai".to_string())
+        );
+        assert_eq!(normalize("/?next=/repos"), Some("/".to_string()));
+        assert_eq!(normalize("//admin"), Some("/admin".to_string()));
+        assert_eq!(
+            normalize("/./admin/./users"),
+            Some("/admin/users".to_string())
+        );
+        assert_eq!(normalize("/public/../admin"), Some("/admin".to_string()));
+        assert_eq!(
+            normalize("/public/%2e%2e/admin"),
+            Some("/admin".to_string())
+        );
+        assert_eq!(normalize("/public/..%2Fadmin"), Some("/admin".to_string()));
+        assert_eq!(normalize("/%61dmin/"), Some("/admin/".to_string()));
+        assert_eq!(normalize("/admin/.."), Some("/".to_string()));
+        assert_eq!(normalize("/../admin"), None);
+        assert_eq!(normalize("/public/%2e%2e/%2e%2e/admin"), None);
+        assert_eq!(normalize("/%ff"), None);
+    }
+
+    #[test]
+    fn compile_rules_rejects_relative_path_prefix() {
+        let err = compile_rules(&[NetworkRule {
+            host: "api.github.com".to_string(),
+            path_prefix: Some("repos".to_string()),
+            methods: Vec::new(),
+            decision: crate::config::NetworkRuleDecision::Deny,
+        }])
+        .unwrap_err();
+        assert!(err.to_string().contains("path_prefix must start with `/`"));
+    }
+
+    #[test]
+    fn network_rule_matcher_checks_host_method_and_path() {
+        let rules = compile_rules(&[NetworkRule {
+            host: "*.github.com".to_string(),
+            path_prefix: Some("/repos/*/issues".to_string()),
+            methods: vec!["post".to_string()],
+            decision: crate::config::NetworkRuleDecision::Deny,
+        }])
+        .unwrap();
+        let rule = &rules[0];
+
+        assert!(rule.matches_host("api.github.com"));
+        assert!(!rule.matches_host("github.com"));
+        assert!(rule.matches_method("POST"));
+        assert!(!rule.matches_method("GET"));
+        assert!(rule.matches_path("/repos/openai/codex/issues"));
+    }
+
     #[test]
     fn is_loopback_host_handles_localhost_variants() {
         assert!(is_loopback_host(&Host::parse("localhost").unwrap()));
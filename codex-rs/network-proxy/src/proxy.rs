@@ -1,9 +1,13 @@
 use crate::admin;
+use crate::certs::MITM_CA_ENV_KEYS;
+use crate::certs::MITM_EXTRA_CA_ENV_KEYS;
+use crate::certs::MitmCertificateAuthority;
 use crate::config;
 use crate::http_proxy;
 use crate::metadata::proxy_username_for_attempt_id;
 use crate::network_policy::NetworkPolicyDecider;
 use crate::runtime::BlockedRequestObserver;
+use crate::runtime::NetworkRequestObserver;
 use crate::runtime::unix_socket_permissions_supported;
 use crate::socks5;
 use crate::state::NetworkProxyState;
@@ -14,6 +18,8 @@ use codex_utils_rustls_provider::ensure_rustls_crypto_provider;
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::net::TcpListener as StdTcpListener;
+use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
 use tokio::task::JoinHandle;
@@ -71,8 +77,10 @@ pub struct NetworkProxyBuilder {
     socks_addr: Option<SocketAddr>,
     admin_addr: Option<SocketAddr>,
     managed_by_codex: bool,
+    codex_home: Option<PathBuf>,
     policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
     blocked_request_observer: Option<Arc<dyn BlockedRequestObserver>>,
+    request_observer: Option<Arc<dyn NetworkRequestObserver>>,
 }
 
 impl Default for NetworkProxyBuilder {
@@ -83,8 +91,10 @@ impl Default for NetworkProxyBuilder {
             socks_addr: None,
             admin_addr: None,
             managed_by_codex: true,
+            codex_home: None,
             policy_decider: None,
             blocked_request_observer: None,
+            request_observer: None,
         }
     }
 }
@@ -115,6 +125,13 @@ impl NetworkProxyBuilder {
         self
     }
 
+    /// Directory under which the MITM certificate authority is stored when `network.mitm` is
+    /// enabled.
+    pub fn codex_home(mut self, codex_home: PathBuf) -> Self {
+        self.codex_home = Some(codex_home);
+        self
+    }
+
     pub fn policy_decider<D>(mut self, decider: D) -> Self
     where
         D: NetworkPolicyDecider,
@@ -144,6 +161,20 @@ impl NetworkProxyBuilder {
         self
     }
 
+    /// Observer notified of every allowed and blocked request, e.g. to persist an audit log.
+    pub fn request_observer<O>(mut self, observer: O) -> Self
+    where
+        O: NetworkRequestObserver,
+    {
+        self.request_observer = Some(Arc::new(observer));
+        self
+    }
+
+    pub fn request_observer_arc(mut self, observer: Arc<dyn NetworkRequestObserver>) -> Self {
+        self.request_observer = Some(observer);
+        self
+    }
+
     pub async fn build(self) -> Result<NetworkProxy> {
         let state = self.state.ok_or_else(|| {
             anyhow::anyhow!(
@@ -153,6 +184,9 @@ impl NetworkProxyBuilder {
         state
             .set_blocked_request_observer(self.blocked_request_observer.clone())
             .await;
+        state
+            .set_request_observer(self.request_observer.clone())
+            .await;
         let current_cfg = state.current_cfg().await?;
         let (requested_http_addr, requested_socks_addr, requested_admin_addr, reserved_listeners) =
             if self.managed_by_codex {
@@ -201,6 +235,21 @@ impl NetworkProxyBuilder {
             &current_cfg.network,
         );
 
+        // The CA is resolved once so the trust bundle handed to child processes stays stable for
+        // the lifetime of the proxy, even if `network.mitm` changes on reload.
+        let mitm = if current_cfg.network.mitm {
+            let codex_home = self.codex_home.as_deref().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "network.mitm requires a CODEX_HOME; supply one via builder.codex_home(...)"
+                )
+            })?;
+            let ca = MitmCertificateAuthority::load_or_create(codex_home)
+                .context("load network proxy MITM certificate authority")?;
+            Some(Arc::new(ca))
+        } else {
+            None
+        };
+
         Ok(NetworkProxy {
             state,
             http_addr,
@@ -210,6 +259,7 @@ impl NetworkProxyBuilder {
             admin_addr,
             reserved_listeners,
             policy_decider: self.policy_decider,
+            mitm,
         })
     }
 }
@@ -244,6 +294,7 @@ pub struct NetworkProxy {
     admin_addr: SocketAddr,
     reserved_listeners: Option<Arc<ReservedListeners>>,
     policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
+    mitm: Option<Arc<MitmCertificateAuthority>>,
 }
 
 impl std::fmt::Debug for NetworkProxy {
@@ -403,6 +454,17 @@ fn apply_proxy_env_overrides(
     }
 }
 
+fn apply_mitm_ca_env_overrides(
+    env: &mut HashMap<String, String>,
+    ca_cert_path: &Path,
+    ca_bundle_path: &Path,
+) {
+    // Intercepted HTTPS traffic is signed by the proxy CA, so point every common TLS client at it.
+    // Variables that replace the trust store get the bundle that still includes the system roots.
+    set_env_keys(env, MITM_CA_ENV_KEYS, &ca_bundle_path.to_string_lossy());
+    set_env_keys(env, MITM_EXTRA_CA_ENV_KEYS, &ca_cert_path.to_string_lossy());
+}
+
 impl NetworkProxy {
     pub fn builder() -> NetworkProxyBuilder {
         NetworkProxyBuilder::default()
@@ -439,6 +501,14 @@ impl NetworkProxy {
             self.allow_local_binding,
             network_attempt_id,
         );
+        if let Some(mitm) = self.mitm.as_ref() {
+            apply_mitm_ca_env_overrides(env, mitm.cert_path(), mitm.bundle_path());
+        }
+    }
+
+    /// Path of the CA certificate that signs intercepted HTTPS traffic, when MITM is enabled.
+    pub fn mitm_ca_cert_path(&self) -> Option<&Path> {
+        self.mitm.as_ref().map(|mitm| mitm.cert_path())
     }
 
     pub async fn run(&self) -> Result<NetworkProxyHandle> {
@@ -461,14 +531,22 @@ impl NetworkProxy {
 
         let http_state = self.state.clone();
         let http_decider = self.policy_decider.clone();
+        let http_mitm = self.mitm.clone();
         let http_addr = self.http_addr;
         let http_task = tokio::spawn(async move {
             match http_listener {
                 Some(listener) => {
-                    http_proxy::run_http_proxy_with_std_listener(http_state, listener, http_decider)
-                        .await
+                    http_proxy::run_http_proxy_with_std_listener(
+                        http_state,
+                        listener,
+                        http_decider,
+                        http_mitm,
+                    )
+                    .await
+                }
+                None => {
+                    http_proxy::run_http_proxy(http_state, http_addr, http_decider, http_mitm).await
                 }
-                None => http_proxy::run_http_proxy(http_state, http_addr, http_decider).await,
             }
         });
 
@@ -823,6 +901,187 @@ mod tests {
         assert_eq!(env.get("GIT_SSH_COMMAND"), None);
     }
 
+    #[test]
+    fn apply_mitm_ca_env_overrides_points_tls_clients_at_ca() {
+        let mut env =
+            HashMap::from([("SSL_CERT_FILE".to_string(), "/etc/ssl/cert.pem".to_string())]);
+        apply_mitm_ca_env_overrides(
+            &mut env,
+            Path::new("/home/user/.codex/network_proxy/ca.pem"),
+            Path::new("/home/user/.codex/network_proxy/ca-bundle.pem"),
+        );
+
+        for key in ["SSL_CERT_FILE", "REQUESTS_CA_BUNDLE", "GIT_SSL_CAINFO"] {
+            assert_eq!(
+                env.get(key),
+                Some(&"/home/user/.codex/network_proxy/ca-bundle.pem".to_string()),
+                "{key}"
+            );
+        }
+        assert_eq!(
+            env.get("NODE_EXTRA_CA_CERTS"),
+            Some(&"/home/user/.codex/network_proxy/ca.pem".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn managed_proxy_builder_creates_mitm_ca_under_codex_home() {
+        let codex_home = tempfile::tempdir().unwrap();
+        let settings = NetworkProxySettings {
+            proxy_url: "http://127.0.0.1:43130".to_string(),
+            socks_url: "http://127.0.0.1:48082".to_string(),
+            admin_url: "http://127.0.0.1:48083".to_string(),
+            mitm: true,
+            ..NetworkProxySettings::default()
+        };
+        let state = Arc::new(network_proxy_state_for_policy(settings));
+        let proxy = NetworkProxy::builder()
+            .state(state)
+            .managed_by_codex(false)
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .unwrap();
+
+        let ca_path = codex_home.path().join("network_proxy").join("ca.pem");
+        assert_eq!(proxy.mitm_ca_cert_path(), Some(ca_path.as_path()));
+        assert!(ca_path.exists());
+
+        let mut env = HashMap::new();
+        proxy.apply_to_env(&mut env);
+        assert_eq!(
+            env.get("NODE_EXTRA_CA_CERTS"),
+            Some(&ca_path.to_string_lossy().into_owned())
+        );
+        assert_eq!(
+            env.get("SSL_CERT_FILE"),
+            Some(
+                &codex_home
+                    .path()
+                    .join("network_proxy")
+                    .join("ca-bundle.pem")
+                    .to_string_lossy()
+                    .into_owned()
+            )
+        );
+    }
+
+    /// Reads an HTTP response head (status line and headers) one byte at a time.
+    fn read_response_head(reader: &mut impl std::io::Read) -> std::io::Result<String> {
+        let mut head = Vec::new();
+        let mut byte = [0u8; 1];
+        while !head.ends_with(b"\r\n\r\n") {
+            if reader.read(&mut byte)? == 0 {
+                break;
+            }
+            head.push(byte[0]);
+        }
+        Ok(String::from_utf8_lossy(&head).into_owned())
+    }
+
+    #[tokio::test]
+    async fn mitm_proxy_intercepts_https_connect_and_applies_method_policy() {
+        use rustls::pki_types::CertificateDer;
+        use rustls::pki_types::ServerName;
+        use rustls::pki_types::pem::PemObject;
+        use std::io::Write;
+
+        let codex_home = tempfile::tempdir().unwrap();
+        let settings = NetworkProxySettings {
+            allowed_domains: vec!["example.com".to_string()],
+            mitm: true,
+            ..NetworkProxySettings::default()
+        };
+        let state = Arc::new(network_proxy_state_for_policy(settings));
+        state
+            .set_network_mode(crate::config::NetworkMode::Limited)
+            .await
+            .unwrap();
+        let proxy = match NetworkProxy::builder()
+            .state(state)
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+        {
+            Ok(proxy) => proxy,
+            Err(err) => {
+                if err
+                    .chain()
+                    .any(|cause| cause.to_string().contains("Operation not permitted"))
+                {
+                    return;
+                }
+                panic!("failed to build managed proxy: {err:#}");
+            }
+        };
+        let _handle = proxy.run().await.unwrap();
+
+        let http_addr = proxy.http_addr();
+        let ca_pem = std::fs::read(proxy.mitm_ca_cert_path().unwrap()).unwrap();
+        let (connect_head, response_head) = tokio::task::spawn_blocking(move || {
+            let mut stream = std::net::TcpStream::connect(http_addr).unwrap();
+            stream
+                .write_all(b"CONNECT example.com:443 HTTP/1.1\r\nHost: example.com:443\r\n\r\n")
+                .unwrap();
+            let connect_head = read_response_head(&mut stream).unwrap();
+
+            // Trust only the proxy CA: the handshake succeeds only if the proxy terminated TLS
+            // with a certificate it minted for the CONNECT host.
+            let mut roots = rustls::RootCertStore::empty();
+            for cert in CertificateDer::pem_slice_iter(&ca_pem) {
+                roots.add(cert.unwrap()).unwrap();
+            }
+            let config = rustls::ClientConfig::builder()
+                .with_root_certificates(roots)
+                .with_no_client_auth();
+            let server_name = ServerName::try_from("example.com").unwrap();
+            let connection = rustls::ClientConnection::new(Arc::new(config), server_name).unwrap();
+            let mut tls = rustls::StreamOwned::new(connection, stream);
+            tls.write_all(
+                b"POST /upload HTTP/1.1\r\nHost: example.com\r\nContent-Length: 0\r\n\r\n",
+            )
+            .unwrap();
+            let response_head = read_response_head(&mut tls).unwrap();
+            (connect_head, response_head)
+        })
+        .await
+        .unwrap();
+
+        assert!(
+            connect_head.starts_with("HTTP/1.1 200"),
+            "unexpected CONNECT response: {connect_head}"
+        );
+        // Limited mode only allows GET/HEAD/OPTIONS; the intercepted POST never leaves the proxy.
+        assert!(
+            response_head.starts_with("HTTP/1.1 403"),
+            "unexpected intercepted response: {response_head}"
+        );
+        assert!(
+            response_head.contains("blocked-by-method-policy"),
+            "unexpected intercepted response: {response_head}"
+        );
+    }
+
+    #[tokio::test]
+    async fn mitm_without_codex_home_fails_to_build() {
+        let settings = NetworkProxySettings {
+            mitm: true,
+            ..NetworkProxySettings::default()
+        };
+        let state = Arc::new(network_proxy_state_for_policy(settings));
+        let err = NetworkProxy::builder()
+            .state(state)
+            .managed_by_codex(false)
+            .build()
+            .await
+            .unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("network.mitm requires a CODEX_HOME")
+        );
+    }
+
     #[cfg(target_os = "macos")]
     #[test]
     fn apply_proxy_env_overrides_preserves_existing_git_ssh_command() {
@@ -4,3 +4,4 @@ pub(crate) const REASON_NOT_ALLOWED: &str = "not_allowed";
 pub(crate) const REASON_NOT_ALLOWED_LOCAL: &str = "not_allowed_local";
 pub(crate) const REASON_POLICY_DENIED: &str = "policy_denied";
 pub(crate) const REASON_PROXY_DISABLED: &str = "proxy_disabled";
+pub(crate) const REASON_RULE_DENIED: &str = "denied_by_rule";
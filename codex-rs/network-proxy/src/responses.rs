@@ -5,6 +5,7 @@ use crate::reasons::REASON_DENIED;
 use crate::reasons::REASON_METHOD_NOT_ALLOWED;
 use crate::reasons::REASON_NOT_ALLOWED;
 use crate::reasons::REASON_NOT_ALLOWED_LOCAL;
+use crate::reasons::REASON_RULE_DENIED;
 use rama_http::Body;
 use rama_http::Response;
 use rama_http::StatusCode;
@@ -51,6 +52,7 @@ pub fn blocked_header_value(reason: &str) -> &'static str {
         REASON_NOT_ALLOWED | REASON_NOT_ALLOWED_LOCAL => "blocked-by-allowlist",
         REASON_DENIED => "blocked-by-denylist",
         REASON_METHOD_NOT_ALLOWED => "blocked-by-method-policy",
+        REASON_RULE_DENIED => "blocked-by-rule",
         _ => "blocked-by-policy",
     }
 }
@@ -67,6 +69,9 @@ pub fn blocked_message(reason: &str) -> &'static str {
         REASON_METHOD_NOT_ALLOWED => {
             "Codex blocked this request: method not allowed in limited mode."
         }
+        REASON_RULE_DENIED => {
+            "Codex blocked this request: matched a deny rule in the network policy."
+        }
         _ => "Codex blocked this request by network policy.",
     }
 }
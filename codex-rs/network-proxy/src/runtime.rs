@@ -1,6 +1,8 @@
 use crate::config::NetworkMode;
 use crate::config::NetworkProxyConfig;
+use crate::config::NetworkRule;
 use crate::policy::Host;
+use crate::policy::NetworkRuleMatcher;
 use crate::policy::is_loopback_host;
 use crate::policy::is_non_public_ip;
 use crate::policy::normalize_host;
@@ -82,6 +84,9 @@ pub struct BlockedRequest {
     pub source: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub port: Option<u16>,
+    /// The `network.rules` entry that decided the request, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_rule: Option<NetworkRule>,
     pub timestamp: i64,
 }
 
@@ -123,11 +128,102 @@ impl BlockedRequest {
             decision,
             source,
             port,
+            matched_rule: None,
+            timestamp: unix_timestamp(),
+        }
+    }
+
+    pub fn with_matched_rule(mut self, matched_rule: Option<NetworkRule>) -> Self {
+        self.matched_rule = matched_rule;
+        self
+    }
+}
+
+/// A request the proxy made a policy decision on, allowed or blocked. Observers use these to keep
+/// an audit trail of everything sandboxed processes contacted.
+#[derive(Clone, Debug, Serialize)]
+pub struct NetworkRequestEvent {
+    pub host: String,
+    pub port: Option<u16>,
+    pub protocol: String,
+    pub method: Option<String>,
+    pub client: Option<String>,
+    pub attempt_id: Option<String>,
+    /// `allow`, `deny` or `ask`.
+    pub decision: String,
+    pub reason: Option<String>,
+    pub source: Option<String>,
+    /// The request's `content-length` header (plain HTTP and intercepted HTTPS) or SOCKS5 UDP
+    /// datagram size. Not a count of relayed bytes: chunked bodies and opaque tunnels leave it
+    /// unset.
+    pub request_bytes: Option<u64>,
+    /// The response's `content-length` header, with the same limits as `request_bytes`.
+    pub response_bytes: Option<u64>,
+    pub timestamp: i64,
+}
+
+pub struct AllowedRequestArgs {
+    pub host: String,
+    pub port: Option<u16>,
+    pub protocol: String,
+    pub method: Option<String>,
+    pub client: Option<String>,
+    pub attempt_id: Option<String>,
+    pub request_bytes: Option<u64>,
+    pub response_bytes: Option<u64>,
+}
+
+impl NetworkRequestEvent {
+    pub fn allowed(args: AllowedRequestArgs) -> Self {
+        let AllowedRequestArgs {
+            host,
+            port,
+            protocol,
+            method,
+            client,
+            attempt_id,
+            request_bytes,
+            response_bytes,
+        } = args;
+        Self {
+            host,
+            port,
+            protocol,
+            method,
+            client,
+            attempt_id,
+            decision: "allow".to_string(),
+            reason: None,
+            source: None,
+            request_bytes,
+            response_bytes,
             timestamp: unix_timestamp(),
         }
     }
 }
 
+impl From<&BlockedRequest> for NetworkRequestEvent {
+    fn from(blocked: &BlockedRequest) -> Self {
+        Self {
+            host: blocked.host.clone(),
+            port: blocked.port,
+            protocol: blocked.protocol.clone(),
+            method: blocked.method.clone(),
+            client: blocked.client.clone(),
+            attempt_id: blocked.attempt_id.clone(),
+            decision: blocked
+                .decision
+                .clone()
+                .unwrap_or_else(|| "deny".to_string()),
+            reason: Some(blocked.reason.clone()),
+            source: blocked.source.clone(),
+            request_bytes: None,
+            response_bytes: None,
+            timestamp: blocked.timestamp,
+        }
+    }
+}
+
 fn blocked_request_violation_log_line(entry: &BlockedRequest) -> String {
     match serde_json::to_string(entry) {
         Ok(json) => format!("{NETWORK_POLICY_VIOLATION_PREFIX} {json}"),
@@ -146,6 +242,7 @@ pub struct ConfigState {
     pub config: NetworkProxyConfig,
     pub allow_set: GlobSet,
     pub deny_set: GlobSet,
+    pub(crate) rules: Vec<NetworkRuleMatcher>,
     pub constraints: NetworkProxyConstraints,
     pub blocked: VecDeque<BlockedRequest>,
     pub blocked_total: u64,
@@ -186,10 +283,34 @@ where
     }
 }
 
+#[async_trait]
+pub trait NetworkRequestObserver: Send + Sync + 'static {
+    async fn on_network_request(&self, event: NetworkRequestEvent);
+}
+
+#[async_trait]
+impl<O: NetworkRequestObserver + ?Sized> NetworkRequestObserver for Arc<O> {
+    async fn on_network_request(&self, event: NetworkRequestEvent) {
+        (**self).on_network_request(event).await
+    }
+}
+
+#[async_trait]
+impl<F, Fut> NetworkRequestObserver for F
+where
+    F: Fn(NetworkRequestEvent) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    async fn on_network_request(&self, event: NetworkRequestEvent) {
+        (self)(event).await
+    }
+}
+
 pub struct NetworkProxyState {
     state: Arc<RwLock<ConfigState>>,
     reloader: Arc<dyn ConfigReloader>,
     blocked_request_observer: Arc<RwLock<Option<Arc<dyn BlockedRequestObserver>>>>,
+    request_observer: Arc<RwLock<Option<Arc<dyn NetworkRequestObserver>>>>,
 }
 
 impl std::fmt::Debug for NetworkProxyState {
@@ -206,6 +327,7 @@ impl Clone for NetworkProxyState {
             state: self.state.clone(),
             reloader: self.reloader.clone(),
             blocked_request_observer: self.blocked_request_observer.clone(),
+            request_observer: self.request_observer.clone(),
         }
     }
 }
@@ -224,6 +346,7 @@ impl NetworkProxyState {
             state: Arc::new(RwLock::new(state)),
             reloader,
             blocked_request_observer: Arc::new(RwLock::new(blocked_request_observer)),
+            request_observer: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -235,6 +358,14 @@ impl NetworkProxyState {
         *observer = blocked_request_observer;
     }
 
+    pub async fn set_request_observer(
+        &self,
+        request_observer: Option<Arc<dyn NetworkRequestObserver>>,
+    ) {
+        let mut observer = self.request_observer.write().await;
+        *observer = request_observer;
+    }
+
     pub async fn current_cfg(&self) -> Result<NetworkProxyConfig> {
         // Callers treat `NetworkProxyState` as a live view of policy. We reload-on-demand so edits to
         // `config.toml` (including Codex-managed writes) take effect without a restart.
@@ -353,10 +484,17 @@ impl NetworkProxyState {
         }
     }
 
+    pub(crate) async fn network_rules(&self) -> Result<Vec<NetworkRuleMatcher>> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        Ok(guard.rules.clone())
+    }
+
     pub async fn record_blocked(&self, entry: BlockedRequest) -> Result<()> {
         self.reload_if_needed().await?;
         let blocked_for_observer = entry.clone();
         let blocked_request_observer = self.blocked_request_observer.read().await.clone();
+        let request_event = NetworkRequestEvent::from(&entry);
         let violation_line = blocked_request_violation_log_line(&entry);
         let mut guard = self.state.write().await;
         let host = entry.host.clone();
@@ -390,9 +528,24 @@ impl NetworkProxyState {
         if let Some(observer) = blocked_request_observer {
             observer.on_blocked_request(blocked_for_observer).await;
         }
+        self.notify_request_observer(request_event).await;
         Ok(())
     }
 
+    /// Reports a request the policy allowed to the request observer, if any. Blocked requests are
+    /// reported by [`Self::record_blocked`].
+    pub async fn record_allowed(&self, args: AllowedRequestArgs) {
+        self.notify_request_observer(NetworkRequestEvent::allowed(args))
+            .await;
+    }
+
+    async fn notify_request_observer(&self, event: NetworkRequestEvent) {
+        let request_observer = self.request_observer.read().await.clone();
+        if let Some(observer) = request_observer {
+            observer.on_network_request(event).await;
+        }
+    }
+
     /// Returns a snapshot of buffered blocked-request entries without consuming
     /// them.
     pub async fn blocked_snapshot(&self) -> Result<Vec<BlockedRequest>> {
@@ -557,6 +710,13 @@ fn log_policy_changes(previous: &NetworkProxyConfig, next: &NetworkProxyConfig)
         &previous.network.denied_domains,
         &next.network.denied_domains,
     );
+    if previous.network.rules != next.network.rules {
+        info!(
+            "network rules updated ({} -> {} rules)",
+            previous.network.rules.len(),
+            next.network.rules.len()
+        );
+    }
 }
 
 fn log_domain_list_changes(list_name: &str, previous: &[String], next: &[String]) {
@@ -650,6 +810,7 @@ mod tests {
 
     use crate::config::NetworkProxyConfig;
     use crate::config::NetworkProxySettings;
+    use crate::config::NetworkRuleDecision;
     use crate::policy::compile_globset;
     use crate::state::NetworkProxyConstraints;
     use crate::state::validate_policy_against_constraints;
@@ -755,6 +916,85 @@ mod tests {
         assert_eq!(blocked[0].host, "example5.com");
     }
 
+    #[tokio::test]
+    async fn request_observer_sees_allowed_and_blocked_requests() {
+        let state = network_proxy_state_for_policy(NetworkProxySettings::default());
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let observer: Arc<dyn NetworkRequestObserver> = Arc::new({
+            let events = Arc::clone(&events);
+            move |event: NetworkRequestEvent| {
+                events
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+                    .push(event);
+                async {}
+            }
+        });
+        state.set_request_observer(Some(observer)).await;
+
+        state
+            .record_allowed(AllowedRequestArgs {
+                host: "registry.npmjs.org".to_string(),
+                port: Some(443),
+                protocol: "https".to_string(),
+                method: Some("GET".to_string()),
+                client: None,
+                attempt_id: Some("attempt-1".to_string()),
+                request_bytes: Some(0),
+                response_bytes: Some(512),
+            })
+            .await;
+        state
+            .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                host: "evil.example".to_string(),
+                reason: "denied".to_string(),
+                client: None,
+                method: None,
+                mode: None,
+                protocol: "http-connect".to_string(),
+                attempt_id: Some("attempt-1".to_string()),
+                decision: Some("deny".to_string()),
+                source: Some("baseline_policy".to_string()),
+                port: Some(443),
+            }))
+            .await
+            .expect("entry should be recorded");
+
+        let events = events
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .iter()
+            .map(|event| {
+                (
+                    event.host.clone(),
+                    event.decision.clone(),
+                    event.reason.clone(),
+                    event.attempt_id.clone(),
+                    event.response_bytes,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                (
+                    "registry.npmjs.org".to_string(),
+                    "allow".to_string(),
+                    None,
+                    Some("attempt-1".to_string()),
+                    Some(512),
+                ),
+                (
+                    "evil.example".to_string(),
+                    "deny".to_string(),
+                    Some("denied".to_string()),
+                    Some("attempt-1".to_string()),
+                    None,
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn blocked_request_violation_log_line_serializes_payload() {
         let entry = BlockedRequest {
@@ -768,6 +1008,7 @@ mod tests {
             decision: Some("ask".to_string()),
             source: Some("decider".to_string()),
             port: Some(80),
+            matched_rule: None,
             timestamp: 1_735_689_600,
         };
 
@@ -942,6 +1183,41 @@ mod tests {
         assert!(validate_policy_against_constraints(&config, &constraints).is_err());
     }
 
+    #[test]
+    fn validate_policy_against_constraints_limits_allow_rules_to_managed_allowlist() {
+        let constraints = NetworkProxyConstraints {
+            allowed_domains: Some(vec!["*.example.com".to_string()]),
+            ..NetworkProxyConstraints::default()
+        };
+        let rule = |host: &str, decision| NetworkRule {
+            host: host.to_string(),
+            path_prefix: Some("/upload".to_string()),
+            methods: vec!["POST".to_string()],
+            decision,
+        };
+
+        let narrowing = NetworkProxyConfig {
+            network: NetworkProxySettings {
+                enabled: true,
+                rules: vec![
+                    rule("api.example.com", NetworkRuleDecision::Allow),
+                    rule("evil.com", NetworkRuleDecision::Deny),
+                ],
+                ..NetworkProxySettings::default()
+            },
+        };
+        assert!(validate_policy_against_constraints(&narrowing, &constraints).is_ok());
+
+        let widening = NetworkProxyConfig {
+            network: NetworkProxySettings {
+                enabled: true,
+                rules: vec![rule("evil.com", NetworkRuleDecision::Allow)],
+                ..NetworkProxySettings::default()
+            },
+        };
+        assert!(validate_policy_against_constraints(&widening, &constraints).is_err());
+    }
+
     #[test]
     fn validate_policy_against_constraints_disallows_widening_mode() {
         let constraints = NetworkProxyConstraints {
@@ -1031,6 +1307,24 @@ mod tests {
         assert!(validate_policy_against_constraints(&config, &constraints).is_err());
     }
 
+    #[test]
+    fn validate_policy_against_constraints_pins_managed_mitm() {
+        let constraints = NetworkProxyConstraints {
+            mitm: Some(true),
+            ..NetworkProxyConstraints::default()
+        };
+        let config = |mitm| NetworkProxyConfig {
+            network: NetworkProxySettings {
+                enabled: true,
+                mitm,
+                ..NetworkProxySettings::default()
+            },
+        };
+
+        assert!(validate_policy_against_constraints(&config(true), &constraints).is_ok());
+        assert!(validate_policy_against_constraints(&config(false), &constraints).is_err());
+    }
+
     #[test]
     fn validate_policy_against_constraints_disallows_allow_local_binding_when_managed_disabled() {
         let constraints = NetworkProxyConstraints {
@@ -6,12 +6,15 @@ use crate::network_policy::NetworkPolicyDecision;
 use crate::network_policy::NetworkPolicyRequest;
 use crate::network_policy::NetworkPolicyRequestArgs;
 use crate::network_policy::NetworkProtocol;
+use crate::network_policy::PolicyEvaluation;
+use crate::network_policy::RuleScope;
 use crate::network_policy::evaluate_host_policy;
 use crate::policy::normalize_host;
 use crate::reasons::REASON_METHOD_NOT_ALLOWED;
 use crate::reasons::REASON_PROXY_DISABLED;
 use crate::responses::PolicyDecisionDetails;
 use crate::responses::blocked_message_with_policy;
+use crate::runtime::AllowedRequestArgs;
 use crate::state::BlockedRequest;
 use crate::state::BlockedRequestArgs;
 use crate::state::NetworkProxyState;
@@ -232,11 +235,22 @@ async fn handle_socks5_tcp(
         attempt_id: None,
     });
 
-    match evaluate_host_policy(&app_state, policy_decider.as_ref(), &request).await {
-        Ok(NetworkDecision::Deny {
-            reason,
-            source,
-            decision,
+    match evaluate_host_policy(
+        &app_state,
+        policy_decider.as_ref(),
+        &request,
+        RuleScope::Tunnel,
+    )
+    .await
+    {
+        Ok(PolicyEvaluation {
+            decision:
+                NetworkDecision::Deny {
+                    reason,
+                    source,
+                    decision,
+                },
+            matched_rule,
         }) => {
             let details = PolicyDecisionDetails {
                 decision,
@@ -247,24 +261,42 @@ async fn handle_socks5_tcp(
                 port,
             };
             let _ = app_state
-                .record_blocked(BlockedRequest::new(BlockedRequestArgs {
-                    host: host.clone(),
-                    reason: reason.clone(),
-                    client: client.clone(),
-                    method: None,
-                    mode: None,
-                    protocol: "socks5".to_string(),
-                    attempt_id: None,
-                    decision: Some(details.decision.as_str().to_string()),
-                    source: Some(details.source.as_str().to_string()),
-                    port: Some(port),
-                }))
+                .record_blocked(
+                    BlockedRequest::new(BlockedRequestArgs {
+                        host: host.clone(),
+                        reason: reason.clone(),
+                        client: client.clone(),
+                        method: None,
+                        mode: None,
+                        protocol: "socks5".to_string(),
+                        attempt_id: None,
+                        decision: Some(details.decision.as_str().to_string()),
+                        source: Some(details.source.as_str().to_string()),
+                        port: Some(port),
+                    })
+                    .with_matched_rule(matched_rule),
+                )
                 .await;
             let client = client.as_deref().unwrap_or_default();
             warn!("SOCKS blocked (client={client}, host={host}, reason={reason})");
             return Err(policy_denied_error(&reason, &details).into());
         }
-        Ok(NetworkDecision::Allow) => {
+        Ok(PolicyEvaluation {
+            decision: NetworkDecision::Allow,
+            ..
+        }) => {
+            app_state
+                .record_allowed(AllowedRequestArgs {
+                    host: host.clone(),
+                    port: Some(port),
+                    protocol: "socks5".to_string(),
+                    method: None,
+                    client: client.clone(),
+                    attempt_id: None,
+                    request_bytes: None,
+                    response_bytes: None,
+                })
+                .await;
             let client = client.as_deref().unwrap_or_default();
             info!("SOCKS allowed (client={client}, host={host}, port={port})");
         }
@@ -378,11 +410,15 @@ async fn inspect_socks5_udp(
         attempt_id: None,
     });
 
-    match evaluate_host_policy(&state, policy_decider.as_ref(), &request).await {
-        Ok(NetworkDecision::Deny {
-            reason,
-            source,
-            decision,
+    match evaluate_host_policy(&state, policy_decider.as_ref(), &request, RuleScope::Tunnel).await {
+        Ok(PolicyEvaluation {
+            decision:
+                NetworkDecision::Deny {
+                    reason,
+                    source,
+                    decision,
+                },
+            matched_rule,
         }) => {
             let details = PolicyDecisionDetails {
                 decision,
@@ -393,27 +429,47 @@ async fn inspect_socks5_udp(
                 port,
             };
             let _ = state
-                .record_blocked(BlockedRequest::new(BlockedRequestArgs {
-                    host: host.clone(),
-                    reason: reason.clone(),
-                    client: client.clone(),
-                    method: None,
-                    mode: None,
-                    protocol: "socks5-udp".to_string(),
-                    attempt_id: None,
-                    decision: Some(details.decision.as_str().to_string()),
-                    source: Some(details.source.as_str().to_string()),
-                    port: Some(port),
-                }))
+                .record_blocked(
+                    BlockedRequest::new(BlockedRequestArgs {
+                        host: host.clone(),
+                        reason: reason.clone(),
+                        client: client.clone(),
+                        method: None,
+                        mode: None,
+                        protocol: "socks5-udp".to_string(),
+                        attempt_id: None,
+                        decision: Some(details.decision.as_str().to_string()),
+                        source: Some(details.source.as_str().to_string()),
+                        port: Some(port),
+                    })
+                    .with_matched_rule(matched_rule),
+                )
                 .await;
             let client = client.as_deref().unwrap_or_default();
             warn!("SOCKS UDP blocked (client={client}, host={host}, reason={reason})");
             Err(policy_denied_error(&reason, &details))
         }
-        Ok(NetworkDecision::Allow) => Ok(RelayResponse {
-            maybe_payload: Some(payload),
-            extensions,
-        }),
+        Ok(PolicyEvaluation {
+            decision: NetworkDecision::Allow,
+            ..
+        }) => {
+            state
+                .record_allowed(AllowedRequestArgs {
+                    host,
+                    port: Some(port),
+                    protocol: "socks5-udp".to_string(),
+                    method: None,
+                    client,
+                    attempt_id: None,
+                    request_bytes: Some(payload.len() as u64),
+                    response_bytes: None,
+                })
+                .await;
+            Ok(RelayResponse {
+                maybe_payload: Some(payload),
+                extensions,
+            })
+        }
         Err(err) => {
             error!("failed to evaluate UDP host: {err}");
             Err(io::Error::other("proxy error"))
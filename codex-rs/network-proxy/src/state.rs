@@ -1,7 +1,9 @@
 use crate::config::NetworkMode;
 use crate::config::NetworkProxyConfig;
+use crate::config::NetworkRuleDecision;
 use crate::policy::DomainPattern;
 use crate::policy::compile_globset;
+use crate::policy::compile_rules;
 use crate::runtime::ConfigState;
 use serde::Deserialize;
 use std::collections::HashSet;
@@ -23,6 +25,7 @@ pub struct NetworkProxyConstraints {
     pub denied_domains: Option<Vec<String>>,
     pub allow_unix_sockets: Option<Vec<String>>,
     pub allow_local_binding: Option<bool>,
+    pub mitm: Option<bool>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -46,6 +49,8 @@ pub struct PartialNetworkConfig {
     pub allow_unix_sockets: Option<Vec<String>>,
     #[serde(default)]
     pub allow_local_binding: Option<bool>,
+    #[serde(default)]
+    pub mitm: Option<bool>,
 }
 
 pub fn build_config_state(
@@ -54,10 +59,12 @@ pub fn build_config_state(
 ) -> anyhow::Result<ConfigState> {
     let deny_set = compile_globset(&config.network.denied_domains)?;
     let allow_set = compile_globset(&config.network.allowed_domains)?;
+    let rules = compile_rules(&config.network.rules)?;
     Ok(ConfigState {
         config,
         allow_set,
         deny_set,
+        rules,
         constraints,
         blocked: std::collections::VecDeque::new(),
         blocked_total: 0,
@@ -187,11 +194,28 @@ pub fn validate_policy_against_constraints(
         })?;
     }
 
+    // Managed config pins interception either way: turning it off would blind method policy
+    // to HTTPS, turning it on changes which CA sandboxed processes must trust.
+    if let Some(required_mitm) = constraints.mitm {
+        validate(config.network.mitm, move |candidate| {
+            if *candidate != required_mitm {
+                Err(invalid_value(
+                    "network.mitm",
+                    candidate.to_string(),
+                    format!("{required_mitm} (set by managed config)"),
+                ))
+            } else {
+                Ok(())
+            }
+        })?;
+    }
+
     if let Some(allowed_domains) = &constraints.allowed_domains {
         let managed_patterns: Vec<DomainPattern> = allowed_domains
             .iter()
             .map(|entry| DomainPattern::parse_for_constraints(entry))
             .collect();
+        let managed_patterns = &managed_patterns;
         validate(config.network.allowed_domains.clone(), move |candidate| {
             let mut invalid = Vec::new();
             for entry in candidate {
@@ -213,6 +237,30 @@ pub fn validate_policy_against_constraints(
                 ))
             }
         })?;
+        // An allow rule opens its host just like an allowlist entry, so it is held to the same
+        // managed allowlist.
+        validate(config.network.rules.clone(), move |candidate| {
+            let invalid: Vec<String> = candidate
+                .iter()
+                .filter(|rule| rule.decision == NetworkRuleDecision::Allow)
+                .filter(|rule| {
+                    let candidate_pattern = DomainPattern::parse_for_constraints(&rule.host);
+                    !managed_patterns
+                        .iter()
+                        .any(|managed| managed.allows(&candidate_pattern))
+                })
+                .map(|rule| rule.host.clone())
+                .collect();
+            if invalid.is_empty() {
+                Ok(())
+            } else {
+                Err(invalid_value(
+                    "network.rules",
+                    format!("{invalid:?}"),
+                    "allow rules limited to managed allowed_domains",
+                ))
+            }
+        })?;
     }
 
     if let Some(denied_domains) = &constraints.denied_domains {
@@ -5,6 +5,7 @@ mod url;
 
 pub use client::OllamaClient;
 use codex_core::ModelProviderInfo;
+use codex_core::WireApi;
 use codex_core::config::Config;
 pub use pull::CliProgressReporter;
 pub use pull::PullEvent;
@@ -58,8 +59,13 @@ fn supports_responses(version: &Version) -> bool {
 
 /// Ensure the running Ollama server is new enough to support the Responses API.
 ///
-/// Returns `Ok(())` when the version endpoint is missing or unparsable.
+/// Returns `Ok(())` when the version endpoint is missing or unparsable, or when the provider
+/// talks to Ollama over Chat Completions instead.
 pub async fn ensure_responses_supported(provider: &ModelProviderInfo) -> std::io::Result<()> {
+    if provider.wire_api == WireApi::Chat {
+        return Ok(());
+    }
+
     let client = crate::OllamaClient::try_from_provider(provider).await?;
     let Some(version) = client.fetch_version().await? else {
         return Ok(());
@@ -71,7 +77,7 @@ pub async fn ensure_responses_supported(provider: &ModelProviderInfo) -> std::io
 
     let min = min_responses_version();
     Err(std::io::Error::other(format!(
-        "Ollama {version} is too old. Codex requires Ollama {min} or newer."
+        "Ollama {version} is too old. Codex requires Ollama {min} or newer, or a custom provider with `wire_api = \"chat\"`."
     )))
 }
 
@@ -2030,6 +2030,16 @@ pub enum ReviewTarget {
         title: Option<String>,
     },
 
+    /// Review the commits in `from..to` (e.g. a whole PR stack).
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    CommitRange { from: String, to: String },
+
+    /// Review a diff produced elsewhere, stored in a patch file.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    PatchFile { path: PathBuf },
+
     /// Arbitrary instructions provided by the user.
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
@@ -2043,6 +2053,10 @@ pub struct ReviewRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[ts(optional)]
     pub user_facing_hint: Option<String>,
+    /// Restrict the review to changes under these paths. Empty reviews every
+    /// changed file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<PathBuf>,
 }
 
 /// Structured review result produced by a child review session.
@@ -2241,6 +2255,36 @@ pub struct UndoCompletedEvent {
     pub message: Option<String>,
 }
 
+/// Snapshot taken at the start of a turn, with the files that changed since.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct Checkpoint {
+    /// Snapshot id, used to restore files from this checkpoint.
+    pub id: String,
+    /// The user message that started the turn, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_message: Option<String>,
+    pub files: Vec<CheckpointFile>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct CheckpointFile {
+    pub path: PathBuf,
+    pub status: CheckpointFileStatus,
+    /// Diff from the checkpoint to the current contents, with three lines of
+    /// context. Hunks are numbered from zero in the order they appear. Empty
+    /// for binary files, which can only be restored whole.
+    pub unified_diff: String,
+}
+
+/// How a file changed since the checkpoint.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckpointFileStatus {
+    Added,
+    Deleted,
+    Modified,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ThreadRolledBackEvent {
     /// Number of user turns that were removed from context.
@@ -13,8 +13,13 @@ use sha2::Digest;
 use sha2::Sha256;
 
 mod local;
+mod redact;
+mod reference;
 
 pub use local::LocalSecretsBackend;
+pub use redact::SecretRedactor;
+pub use reference::contains_secret_reference;
+pub use reference::resolve_secret_references;
 
 const KEYRING_SERVICE: &str = "codex";
 
@@ -134,6 +139,20 @@ impl SecretsManager {
     pub fn list(&self, scope_filter: Option<&SecretScope>) -> Result<Vec<SecretListEntry>> {
         self.backend.list(scope_filter)
     }
+
+    /// Looks `name` up in the given environment's scope first and falls back
+    /// to the global scope.
+    pub fn get_for_environment(
+        &self,
+        environment_id: &str,
+        name: &SecretName,
+    ) -> Result<Option<String>> {
+        let scope = SecretScope::environment(environment_id)?;
+        if let Some(value) = self.get(&scope, name)? {
+            return Ok(Some(value));
+        }
+        self.get(&SecretScope::Global, name)
+    }
 }
 
 pub fn environment_id_from_cwd(cwd: &Path) -> String {
@@ -240,4 +259,33 @@ mod tests {
         assert_eq!(manager.get(&scope, &name)?, None);
         Ok(())
     }
+
+    #[test]
+    fn environment_scope_shadows_global_scope() -> Result<()> {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let keyring = Arc::new(MockKeyringStore::default());
+        let manager = SecretsManager::new_with_keyring_store(
+            codex_home.path().to_path_buf(),
+            SecretsBackendKind::Local,
+            keyring,
+        );
+        let name = SecretName::new("DEPLOY_KEY")?;
+
+        manager.set(&SecretScope::Global, &name, "global-key")?;
+        assert_eq!(
+            manager.get_for_environment("repo", &name)?,
+            Some("global-key".to_string())
+        );
+
+        manager.set(&SecretScope::environment("repo")?, &name, "repo-key")?;
+        assert_eq!(
+            manager.get_for_environment("repo", &name)?,
+            Some("repo-key".to_string())
+        );
+        assert_eq!(
+            manager.get_for_environment("other", &name)?,
+            Some("global-key".to_string())
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,74 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+
+use crate::SecretName;
+
+/// Remembers secret values that were handed to child processes so they can be
+/// scrubbed from any text that flows back out of them.
+#[derive(Clone, Debug, Default)]
+pub struct SecretRedactor {
+    values: Arc<RwLock<BTreeMap<String, SecretName>>>,
+}
+
+impl SecretRedactor {
+    pub fn register(&self, name: &SecretName, value: &str) {
+        if value.is_empty() {
+            return;
+        }
+        self.values
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(value.to_string(), name.clone());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_empty()
+    }
+
+    /// Replaces every registered secret value in `text` with
+    /// `[REDACTED:NAME]`. Longer values are replaced first so a secret that
+    /// contains another one is never partially revealed.
+    pub fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let values = self.values.read().unwrap_or_else(PoisonError::into_inner);
+        let mut by_length: Vec<(&String, &SecretName)> = values
+            .iter()
+            .filter(|(value, _)| text.contains(value.as_str()))
+            .collect();
+        if by_length.is_empty() {
+            return Cow::Borrowed(text);
+        }
+        by_length.sort_by_key(|(value, _)| std::cmp::Reverse(value.len()));
+
+        let mut redacted = text.to_string();
+        for (value, name) in by_length {
+            redacted = redacted.replace(value.as_str(), &format!("[REDACTED:{name}]"));
+        }
+        Cow::Owned(redacted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn redacts_registered_values_longest_first() -> anyhow::Result<()> {
+        let redactor = SecretRedactor::default();
+        redactor.register(&SecretName::new("SHORT")?, "abc");
+        redactor.register(&SecretName::new("LONG")?, "abc123");
+
+        assert_eq!(
+            redactor.redact("token=abc123 prefix=abc"),
+            "token=[REDACTED:LONG] prefix=[REDACTED:SHORT]"
+        );
+        assert!(matches!(redactor.redact("nothing here"), Cow::Borrowed(_)));
+        Ok(())
+    }
+}
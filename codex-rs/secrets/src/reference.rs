@@ -0,0 +1,65 @@
+use anyhow::Result;
+
+use crate::SecretName;
+
+const REFERENCE_OPEN: &str = "${secret:";
+const REFERENCE_CLOSE: char = '}';
+
+/// Returns true when `value` contains at least one `${secret:NAME}` reference.
+pub fn contains_secret_reference(value: &str) -> bool {
+    value.contains(REFERENCE_OPEN)
+}
+
+/// Replaces every `${secret:NAME}` reference in `value` with the result of
+/// `lookup`. Text outside references is copied verbatim.
+pub fn resolve_secret_references<F>(value: &str, mut lookup: F) -> Result<String>
+where
+    F: FnMut(&SecretName) -> Result<String>,
+{
+    let mut resolved = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find(REFERENCE_OPEN) {
+        resolved.push_str(&rest[..start]);
+        let after_open = &rest[start + REFERENCE_OPEN.len()..];
+        let Some(end) = after_open.find(REFERENCE_CLOSE) else {
+            anyhow::bail!("unterminated secret reference in `{value}`");
+        };
+        let name = SecretName::new(&after_open[..end])?;
+        resolved.push_str(&lookup(&name)?);
+        rest = &after_open[end + REFERENCE_CLOSE.len_utf8()..];
+    }
+    resolved.push_str(rest);
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn resolves_embedded_references() -> Result<()> {
+        let resolved =
+            resolve_secret_references("Bearer ${secret:API_TOKEN} (${secret:ORG})", |name| {
+                Ok(format!("<{name}>"))
+            })?;
+        assert_eq!(resolved, "Bearer <API_TOKEN> (<ORG>)");
+        Ok(())
+    }
+
+    #[test]
+    fn leaves_plain_values_untouched() -> Result<()> {
+        assert!(!contains_secret_reference("$HOME/bin"));
+        let resolved = resolve_secret_references("$HOME/bin", |_| unreachable!())?;
+        assert_eq!(resolved, "$HOME/bin");
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_malformed_references() {
+        let unterminated = resolve_secret_references("${secret:TOKEN", |_| Ok(String::new()));
+        assert!(unterminated.is_err());
+        let invalid_name = resolve_secret_references("${secret:lower}", |_| Ok(String::new()));
+        assert!(invalid_name.is_err());
+    }
+}
@@ -0,0 +1,18 @@
+CREATE TABLE network_requests (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    ts INTEGER NOT NULL,
+    thread_id TEXT,
+    call_id TEXT,
+    host TEXT NOT NULL,
+    port INTEGER,
+    protocol TEXT NOT NULL,
+    method TEXT,
+    decision TEXT NOT NULL,
+    reason TEXT,
+    source TEXT,
+    request_bytes INTEGER,
+    response_bytes INTEGER
+);
+
+CREATE INDEX idx_network_requests_thread_id ON network_requests(thread_id, id);
+CREATE INDEX idx_network_requests_ts ON network_requests(ts DESC, id DESC);
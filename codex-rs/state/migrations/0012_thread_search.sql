@@ -0,0 +1,18 @@
+CREATE VIRTUAL TABLE thread_search USING fts5(
+    text,
+    thread_id UNINDEXED,
+    turn_id UNINDEXED,
+    turn_index UNINDEXED,
+    kind UNINDEXED,
+    tokenize = 'porter unicode61'
+);
+
+-- Turn position reached by the incremental indexer, so items appended to a
+-- rollout later are attributed to the right turn. Threads without a row here
+-- have never been indexed and are picked up by the search backfill.
+CREATE TABLE thread_search_progress (
+    thread_id TEXT PRIMARY KEY,
+    user_messages INTEGER NOT NULL,
+    turn_id TEXT,
+    FOREIGN KEY(thread_id) REFERENCES threads(id) ON DELETE CASCADE
+);
@@ -1,4 +1,8 @@
 use crate::model::ThreadMetadata;
+use crate::model::ThreadSearchEntry;
+use crate::model::ThreadSearchKind;
+use crate::model::ThreadSearchPosition;
+use codex_protocol::models::LocalShellAction;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::RolloutItem;
@@ -85,6 +89,68 @@ fn apply_response_item(_metadata: &mut ThreadMetadata, _item: &ResponseItem) {
     // Title and first_user_message are derived from EventMsg::UserMessage only.
 }
 
+/// Extract the searchable text of a rollout item, advancing `position` past it.
+///
+/// User and assistant messages are indexed from their events, and commands from
+/// the shell tool calls recorded as response items so they are found whatever
+/// the rollout persistence mode.
+pub(crate) fn thread_search_entry(
+    position: &mut ThreadSearchPosition,
+    item: &RolloutItem,
+) -> Option<ThreadSearchEntry> {
+    let (kind, text) = match item {
+        RolloutItem::EventMsg(EventMsg::TurnStarted(event)) => {
+            position.turn_id = Some(event.turn_id.clone());
+            return None;
+        }
+        RolloutItem::EventMsg(EventMsg::UserMessage(user)) => {
+            position.user_messages = position.user_messages.saturating_add(1);
+            (
+                ThreadSearchKind::User,
+                strip_user_message_prefix(user.message.as_str()).to_string(),
+            )
+        }
+        RolloutItem::EventMsg(EventMsg::AgentMessage(agent)) => (
+            ThreadSearchKind::Assistant,
+            agent.message.trim().to_string(),
+        ),
+        RolloutItem::ResponseItem(ResponseItem::FunctionCall { arguments, .. }) => (
+            ThreadSearchKind::Command,
+            command_from_arguments(arguments)?,
+        ),
+        RolloutItem::ResponseItem(ResponseItem::LocalShellCall {
+            action: LocalShellAction::Exec(exec),
+            ..
+        }) => (ThreadSearchKind::Command, exec.command.join(" ")),
+        _ => return None,
+    };
+    if text.is_empty() {
+        return None;
+    }
+    Some(ThreadSearchEntry {
+        turn_id: position.turn_id.clone(),
+        turn_index: position.turn_index(),
+        kind,
+        text,
+    })
+}
+
+/// Read the command line from shell tool arguments (`command` as an argv array
+/// or a string, or `cmd` for unified exec).
+fn command_from_arguments(arguments: &str) -> Option<String> {
+    let arguments: Value = serde_json::from_str(arguments).ok()?;
+    match arguments.get("command").or_else(|| arguments.get("cmd"))? {
+        Value::String(command) => Some(command.trim().to_string()),
+        Value::Array(argv) => Some(
+            argv.iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        _ => None,
+    }
+}
+
 fn strip_user_message_prefix(text: &str) -> &str {
     match text.find(USER_MESSAGE_BEGIN) {
         Some(idx) => text[idx + USER_MESSAGE_BEGIN.len()..].trim(),
@@ -119,11 +185,17 @@ pub(crate) fn enum_to_string<T: Serialize>(value: &T) -> String {
 #[cfg(test)]
 mod tests {
     use super::apply_rollout_item;
+    use super::thread_search_entry;
     use crate::model::ThreadMetadata;
+    use crate::model::ThreadSearchKind;
+    use crate::model::ThreadSearchPosition;
     use chrono::DateTime;
     use chrono::Utc;
     use codex_protocol::ThreadId;
     use codex_protocol::models::ContentItem;
+    use codex_protocol::models::LocalShellAction;
+    use codex_protocol::models::LocalShellExecAction;
+    use codex_protocol::models::LocalShellStatus;
     use codex_protocol::models::ResponseItem;
     use codex_protocol::protocol::EventMsg;
     use codex_protocol::protocol::RolloutItem;
@@ -207,6 +279,59 @@ mod tests {
         assert_eq!(metadata.title, "");
     }
 
+    #[test]
+    fn thread_search_entries_follow_user_turns() {
+        let mut position = ThreadSearchPosition::default();
+        let items = [
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "exec_command".to_string(),
+                arguments: r#"{"cmd":"git status"}"#.to_string(),
+                call_id: "call-0".to_string(),
+            }),
+            RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                message: format!("{USER_MESSAGE_BEGIN} run the tests"),
+                images: None,
+                local_images: vec![],
+                text_elements: vec![],
+            })),
+            RolloutItem::ResponseItem(ResponseItem::LocalShellCall {
+                id: None,
+                call_id: Some("call-1".to_string()),
+                status: LocalShellStatus::Completed,
+                action: LocalShellAction::Exec(LocalShellExecAction {
+                    command: vec!["cargo".to_string(), "test".to_string()],
+                    timeout_ms: None,
+                    working_directory: None,
+                    env: None,
+                    user: None,
+                }),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "view_image".to_string(),
+                arguments: r#"{"path":"a.png"}"#.to_string(),
+                call_id: "call-2".to_string(),
+            }),
+        ];
+
+        let entries = items
+            .iter()
+            .filter_map(|item| thread_search_entry(&mut position, item))
+            .map(|entry| (entry.turn_index, entry.kind, entry.text))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            entries,
+            vec![
+                (0, ThreadSearchKind::Command, "git status".to_string()),
+                (0, ThreadSearchKind::User, "run the tests".to_string()),
+                (0, ThreadSearchKind::Command, "cargo test".to_string()),
+            ]
+        );
+        assert_eq!(position.user_messages, 1);
+    }
+
     fn metadata_for_test() -> ThreadMetadata {
         let id = ThreadId::from_string(&Uuid::from_u128(42).to_string()).expect("thread id");
         let created_at = DateTime::<Utc>::from_timestamp(1_735_689_600, 0).expect("timestamp");
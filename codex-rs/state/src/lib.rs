@@ -14,6 +14,9 @@ mod runtime;
 pub use model::LogEntry;
 pub use model::LogQuery;
 pub use model::LogRow;
+pub use model::NetworkRequestEntry;
+pub use model::NetworkRequestQuery;
+pub use model::NetworkRequestRow;
 pub use model::Phase2JobClaimOutcome;
 /// Preferred entrypoint: owns configuration and metrics.
 pub use runtime::StateRuntime;
@@ -34,6 +37,8 @@ pub use model::Stage1Output;
 pub use model::Stage1StartupClaimParams;
 pub use model::ThreadMetadata;
 pub use model::ThreadMetadataBuilder;
+pub use model::ThreadSearchHit;
+pub use model::ThreadSearchKind;
 pub use model::ThreadsPage;
 pub use runtime::state_db_filename;
 pub use runtime::state_db_path;
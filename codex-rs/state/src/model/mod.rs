@@ -1,7 +1,9 @@
 mod backfill_state;
 mod log;
 mod memories;
+mod network_request;
 mod thread_metadata;
+mod thread_search;
 
 pub use backfill_state::BackfillState;
 pub use backfill_state::BackfillStatus;
@@ -13,6 +15,9 @@ pub use memories::Stage1JobClaim;
 pub use memories::Stage1JobClaimOutcome;
 pub use memories::Stage1Output;
 pub use memories::Stage1StartupClaimParams;
+pub use network_request::NetworkRequestEntry;
+pub use network_request::NetworkRequestQuery;
+pub use network_request::NetworkRequestRow;
 pub use thread_metadata::Anchor;
 pub use thread_metadata::BackfillStats;
 pub use thread_metadata::ExtractionOutcome;
@@ -20,8 +25,12 @@ pub use thread_metadata::SortKey;
 pub use thread_metadata::ThreadMetadata;
 pub use thread_metadata::ThreadMetadataBuilder;
 pub use thread_metadata::ThreadsPage;
+pub use thread_search::ThreadSearchHit;
+pub use thread_search::ThreadSearchKind;
 
 pub(crate) use memories::Stage1OutputRow;
 pub(crate) use thread_metadata::ThreadRow;
 pub(crate) use thread_metadata::anchor_from_item;
 pub(crate) use thread_metadata::datetime_to_epoch_seconds;
+pub(crate) use thread_search::ThreadSearchEntry;
+pub(crate) use thread_search::ThreadSearchPosition;
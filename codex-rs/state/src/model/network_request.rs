@@ -0,0 +1,52 @@
+use serde::Serialize;
+use sqlx::FromRow;
+
+/// One request seen by the managed network proxy, attributed to the thread
+/// and exec call that made it when known.
+#[derive(Clone, Debug, Serialize)]
+pub struct NetworkRequestEntry {
+    pub ts: i64,
+    pub thread_id: Option<String>,
+    pub call_id: Option<String>,
+    pub host: String,
+    pub port: Option<i64>,
+    pub protocol: String,
+    pub method: Option<String>,
+    /// `allow`, `deny` or `ask`.
+    pub decision: String,
+    pub reason: Option<String>,
+    pub source: Option<String>,
+    pub request_bytes: Option<i64>,
+    pub response_bytes: Option<i64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, FromRow, Serialize)]
+pub struct NetworkRequestRow {
+    pub id: i64,
+    pub ts: i64,
+    pub thread_id: Option<String>,
+    pub call_id: Option<String>,
+    pub host: String,
+    pub port: Option<i64>,
+    pub protocol: String,
+    pub method: Option<String>,
+    pub decision: String,
+    pub reason: Option<String>,
+    pub source: Option<String>,
+    pub request_bytes: Option<i64>,
+    pub response_bytes: Option<i64>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct NetworkRequestQuery {
+    pub thread_ids: Vec<String>,
+    pub call_id: Option<String>,
+    /// Substring match on the host.
+    pub host_like: Option<String>,
+    pub decision: Option<String>,
+    pub from_ts: Option<i64>,
+    pub to_ts: Option<i64>,
+    pub after_id: Option<i64>,
+    pub limit: Option<usize>,
+    pub descending: bool,
+}
@@ -0,0 +1,73 @@
+use anyhow::Result;
+
+use super::ThreadMetadata;
+
+/// The kind of rollout text a search entry was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadSearchKind {
+    /// A message typed by the user.
+    User,
+    /// A final message from the assistant.
+    Assistant,
+    /// The command line of a shell tool call.
+    Command,
+}
+
+impl ThreadSearchKind {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ThreadSearchKind::User => "user",
+            ThreadSearchKind::Assistant => "assistant",
+            ThreadSearchKind::Command => "command",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "user" => Ok(Self::User),
+            "assistant" => Ok(Self::Assistant),
+            "command" => Ok(Self::Command),
+            _ => Err(anyhow::anyhow!("invalid thread search kind: {value}")),
+        }
+    }
+}
+
+/// One piece of searchable text extracted from a rollout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ThreadSearchEntry {
+    pub turn_id: Option<String>,
+    /// Zero-based index of the user message that started the turn.
+    pub turn_index: i64,
+    pub kind: ThreadSearchKind,
+    pub text: String,
+}
+
+/// The turn position reached while extracting search entries from a rollout.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ThreadSearchPosition {
+    /// Number of user messages seen so far.
+    pub user_messages: i64,
+    /// Id of the most recently started turn, when the rollout records one.
+    pub turn_id: Option<String>,
+}
+
+impl ThreadSearchPosition {
+    pub(crate) fn turn_index(&self) -> i64 {
+        self.user_messages.saturating_sub(1).max(0)
+    }
+}
+
+/// The best full-text match within one thread.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadSearchHit {
+    /// Metadata of the matching thread.
+    pub metadata: ThreadMetadata,
+    /// Id of the matching turn, when the rollout records turn ids.
+    pub turn_id: Option<String>,
+    /// Zero-based index of the matching turn, counted by user messages.
+    pub turn_index: usize,
+    /// The kind of text that matched.
+    pub kind: ThreadSearchKind,
+    /// A short excerpt of the matching text around the query terms.
+    pub snippet: String,
+}
@@ -41,6 +41,10 @@ use uuid::Uuid;
 
 mod memories;
 // Memory-specific CRUD and phase job lifecycle methods live in `runtime/memories.rs`.
+mod network_requests;
+// The managed network proxy audit log lives in `runtime/network_requests.rs`.
+mod thread_search;
+// The full-text session search index lives in `runtime/thread_search.rs`.
 
 #[derive(Clone)]
 pub struct StateRuntime {
@@ -599,6 +603,12 @@ ON CONFLICT(thread_id, position) DO NOTHING
             }
             return Err(err);
         }
+        if let Err(err) = self.append_thread_search(builder.id, items).await {
+            if let Some(otel) = otel {
+                otel.counter(DB_ERROR_METRIC, 1, &[("stage", "append_thread_search")]);
+            }
+            return Err(err);
+        }
         Ok(())
     }
 
@@ -649,12 +659,25 @@ ON CONFLICT(thread_id, position) DO NOTHING
         self.upsert_thread(&metadata).await
     }
 
-    /// Delete a thread metadata row by id.
+    /// Delete a thread metadata row, its search index entries and its network
+    /// request audit log by id.
     pub async fn delete_thread(&self, thread_id: ThreadId) -> anyhow::Result<u64> {
+        let thread_id = thread_id.to_string();
+        let mut tx = self.pool.begin().await?;
+        // FTS tables cannot carry foreign keys, so search rows are removed by hand.
+        sqlx::query("DELETE FROM thread_search WHERE thread_id = ?")
+            .bind(thread_id.as_str())
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM network_requests WHERE thread_id = ?")
+            .bind(thread_id.as_str())
+            .execute(&mut *tx)
+            .await?;
         let result = sqlx::query("DELETE FROM threads WHERE id = ?")
-            .bind(thread_id.to_string())
-            .execute(self.pool.as_ref())
+            .bind(thread_id.as_str())
+            .execute(&mut *tx)
             .await?;
+        tx.commit().await?;
         Ok(result.rows_affected())
     }
 
@@ -906,8 +929,13 @@ mod tests {
     use super::StateRuntime;
     use super::ThreadMetadata;
     use super::state_db_filename;
+    use crate::NetworkRequestEntry;
+    use crate::NetworkRequestQuery;
+    use crate::NetworkRequestRow;
     use crate::STATE_DB_FILENAME;
     use crate::STATE_DB_VERSION;
+    use crate::ThreadMetadataBuilder;
+    use crate::ThreadSearchKind;
     use crate::model::Phase2JobClaimOutcome;
     use crate::model::Stage1JobClaimOutcome;
     use crate::model::Stage1StartupClaimParams;
@@ -915,8 +943,16 @@ mod tests {
     use chrono::Duration;
     use chrono::Utc;
     use codex_protocol::ThreadId;
+    use codex_protocol::config_types::ModeKind;
+    use codex_protocol::models::ResponseItem;
+    use codex_protocol::protocol::AgentMessageEvent;
     use codex_protocol::protocol::AskForApproval;
+    use codex_protocol::protocol::EventMsg;
+    use codex_protocol::protocol::RolloutItem;
     use codex_protocol::protocol::SandboxPolicy;
+    use codex_protocol::protocol::SessionSource;
+    use codex_protocol::protocol::TurnStartedEvent;
+    use codex_protocol::protocol::UserMessageEvent;
     use pretty_assertions::assert_eq;
     use sqlx::Row;
     use std::path::Path;
@@ -2495,6 +2531,279 @@ VALUES (?, ?, ?, ?, ?)
         let _ = tokio::fs::remove_dir_all(codex_home).await;
     }
 
+    #[tokio::test]
+    async fn network_requests_are_pruned_by_age_and_with_their_thread() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home.clone(), "test-provider".to_string(), None)
+            .await
+            .expect("initialize runtime");
+        let deleted_thread = ThreadId::from_string(&Uuid::new_v4().to_string()).expect("thread id");
+        let kept_thread = ThreadId::from_string(&Uuid::new_v4().to_string()).expect("thread id");
+
+        let entry = |thread_id: ThreadId, ts: i64, host: &str| NetworkRequestEntry {
+            ts,
+            thread_id: Some(thread_id.to_string()),
+            call_id: None,
+            host: host.to_string(),
+            port: Some(443),
+            protocol: "https".to_string(),
+            method: Some("GET".to_string()),
+            decision: "allow".to_string(),
+            reason: None,
+            source: None,
+            request_bytes: None,
+            response_bytes: None,
+        };
+        for entry in [
+            entry(kept_thread, 1_600_000_000, "stale.example"),
+            entry(kept_thread, 1_700_000_000, "kept.example"),
+            entry(deleted_thread, 1_700_000_000, "deleted.example"),
+        ] {
+            runtime
+                .insert_network_request(&entry)
+                .await
+                .expect("insert network request");
+        }
+
+        let pruned = runtime
+            .delete_network_requests_before(1_650_000_000)
+            .await
+            .expect("prune network requests");
+        assert_eq!(pruned, 1);
+        runtime
+            .delete_thread(deleted_thread)
+            .await
+            .expect("delete thread");
+
+        let hosts = runtime
+            .query_network_requests(&NetworkRequestQuery::default())
+            .await
+            .expect("query network requests")
+            .into_iter()
+            .map(|row| row.host)
+            .collect::<Vec<_>>();
+        assert_eq!(hosts, vec!["kept.example".to_string()]);
+
+        let _ = tokio::fs::remove_dir_all(codex_home).await;
+    }
+
+    #[tokio::test]
+    async fn network_requests_are_filtered_by_thread_call_and_decision() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home.clone(), "test-provider".to_string(), None)
+            .await
+            .expect("initialize runtime");
+
+        let entry = |thread_id: &str, call_id: Option<&str>, host: &str, decision: &str| {
+            NetworkRequestEntry {
+                ts: 1_700_000_000,
+                thread_id: Some(thread_id.to_string()),
+                call_id: call_id.map(ToString::to_string),
+                host: host.to_string(),
+                port: Some(443),
+                protocol: "https".to_string(),
+                method: Some("GET".to_string()),
+                decision: decision.to_string(),
+                reason: (decision == "deny").then(|| "not_allowed".to_string()),
+                source: None,
+                request_bytes: Some(12),
+                response_bytes: None,
+            }
+        };
+        for entry in [
+            entry("thread-a", Some("call-1"), "registry.npmjs.org", "allow"),
+            entry("thread-a", Some("call-1"), "evil.example", "deny"),
+            entry("thread-a", None, "pypi.org", "allow"),
+            entry("thread-b", Some("call-2"), "registry.npmjs.org", "allow"),
+        ] {
+            runtime
+                .insert_network_request(&entry)
+                .await
+                .expect("insert network request");
+        }
+
+        let hosts = |rows: Vec<NetworkRequestRow>| {
+            rows.into_iter()
+                .map(|row| (row.id, row.host))
+                .collect::<Vec<_>>()
+        };
+        let thread_a = runtime
+            .query_network_requests(&NetworkRequestQuery {
+                thread_ids: vec!["thread-a".to_string()],
+                ..Default::default()
+            })
+            .await
+            .expect("query thread");
+        assert_eq!(
+            hosts(thread_a),
+            vec![
+                (1, "registry.npmjs.org".to_string()),
+                (2, "evil.example".to_string()),
+                (3, "pypi.org".to_string()),
+            ]
+        );
+
+        let denied = runtime
+            .query_network_requests(&NetworkRequestQuery {
+                call_id: Some("call-1".to_string()),
+                decision: Some("deny".to_string()),
+                ..Default::default()
+            })
+            .await
+            .expect("query denied");
+        assert_eq!(
+            denied,
+            vec![NetworkRequestRow {
+                id: 2,
+                ts: 1_700_000_000,
+                thread_id: Some("thread-a".to_string()),
+                call_id: Some("call-1".to_string()),
+                host: "evil.example".to_string(),
+                port: Some(443),
+                protocol: "https".to_string(),
+                method: Some("GET".to_string()),
+                decision: "deny".to_string(),
+                reason: Some("not_allowed".to_string()),
+                source: None,
+                request_bytes: Some(12),
+                response_bytes: None,
+            }]
+        );
+
+        let latest_npm = runtime
+            .query_network_requests(&NetworkRequestQuery {
+                host_like: Some("npmjs".to_string()),
+                limit: Some(1),
+                descending: true,
+                ..Default::default()
+            })
+            .await
+            .expect("query host");
+        assert_eq!(
+            hosts(latest_npm),
+            vec![(4, "registry.npmjs.org".to_string())]
+        );
+
+        let _ = tokio::fs::remove_dir_all(codex_home).await;
+    }
+
+    #[tokio::test]
+    async fn thread_search_indexes_incremental_batches_by_turn() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home.clone(), "test-provider".to_string(), None)
+            .await
+            .expect("initialize runtime");
+        let thread_id = ThreadId::from_string(&Uuid::new_v4().to_string()).expect("thread id");
+        let created_at = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).expect("timestamp");
+        let builder = ThreadMetadataBuilder::new(
+            thread_id,
+            codex_home.join(format!("rollout-{thread_id}.jsonl")),
+            created_at,
+            SessionSource::Cli,
+        );
+        let turn_started = |turn_id: &str| {
+            RolloutItem::EventMsg(EventMsg::TurnStarted(TurnStartedEvent {
+                turn_id: turn_id.to_string(),
+                model_context_window: None,
+                collaboration_mode_kind: ModeKind::default(),
+            }))
+        };
+        let user_message = |message: &str| {
+            RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                message: message.to_string(),
+                images: None,
+                local_images: Vec::new(),
+                text_elements: Vec::new(),
+            }))
+        };
+
+        runtime
+            .apply_rollout_items(
+                &builder,
+                &[
+                    turn_started("turn-1"),
+                    user_message("why does the users migration fail on CI?"),
+                    RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+                        message: "The migration races with the seed job.".to_string(),
+                    })),
+                ],
+                None,
+            )
+            .await
+            .expect("apply first turn");
+        runtime
+            .apply_rollout_items(
+                &builder,
+                &[
+                    turn_started("turn-2"),
+                    user_message("fix the flaky migration"),
+                    RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                        id: None,
+                        name: "shell".to_string(),
+                        arguments: r#"{"command":["cargo","nextest","run","-p","migrations"]}"#
+                            .to_string(),
+                        call_id: "call-1".to_string(),
+                    }),
+                ],
+                None,
+            )
+            .await
+            .expect("apply second turn");
+
+        let search = |query: &'static str| {
+            let runtime = runtime.clone();
+            async move {
+                runtime
+                    .search_threads(query, 10, &[], None, false)
+                    .await
+                    .expect("search threads")
+                    .into_iter()
+                    .map(|hit| (hit.turn_id, hit.turn_index, hit.kind, hit.snippet))
+                    .collect::<Vec<_>>()
+            }
+        };
+        assert_eq!(
+            search("flaky migr").await,
+            vec![(
+                Some("turn-2".to_string()),
+                1,
+                ThreadSearchKind::User,
+                "fix the flaky migration".to_string(),
+            )]
+        );
+        assert_eq!(
+            search("nextest").await,
+            vec![(
+                Some("turn-2".to_string()),
+                1,
+                ThreadSearchKind::Command,
+                "cargo nextest run -p migrations".to_string(),
+            )]
+        );
+        assert_eq!(
+            search("seed \"job").await,
+            vec![(
+                Some("turn-1".to_string()),
+                0,
+                ThreadSearchKind::Assistant,
+                "The migration races with the seed job.".to_string(),
+            )]
+        );
+        assert_eq!(search("deploy").await, Vec::new());
+
+        runtime
+            .delete_thread(thread_id)
+            .await
+            .expect("delete thread");
+        let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM thread_search")
+            .fetch_one(runtime.pool.as_ref())
+            .await
+            .expect("count search rows");
+        assert_eq!(remaining, 0);
+
+        let _ = tokio::fs::remove_dir_all(codex_home).await;
+    }
+
     fn test_thread_metadata(
         codex_home: &Path,
         thread_id: ThreadId,
@@ -0,0 +1,107 @@
+use super::*;
+use crate::NetworkRequestEntry;
+use crate::NetworkRequestQuery;
+use crate::NetworkRequestRow;
+
+impl StateRuntime {
+    /// Append one managed network proxy request to the audit log.
+    pub async fn insert_network_request(&self, entry: &NetworkRequestEntry) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+INSERT INTO network_requests (
+    ts,
+    thread_id,
+    call_id,
+    host,
+    port,
+    protocol,
+    method,
+    decision,
+    reason,
+    source,
+    request_bytes,
+    response_bytes
+) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(entry.ts)
+        .bind(&entry.thread_id)
+        .bind(&entry.call_id)
+        .bind(&entry.host)
+        .bind(entry.port)
+        .bind(&entry.protocol)
+        .bind(&entry.method)
+        .bind(&entry.decision)
+        .bind(&entry.reason)
+        .bind(&entry.source)
+        .bind(entry.request_bytes)
+        .bind(entry.response_bytes)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Query the network request audit log with optional filters.
+    pub async fn query_network_requests(
+        &self,
+        query: &NetworkRequestQuery,
+    ) -> anyhow::Result<Vec<NetworkRequestRow>> {
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            "SELECT id, ts, thread_id, call_id, host, port, protocol, method, decision, reason, source, request_bytes, response_bytes FROM network_requests WHERE 1 = 1",
+        );
+        if !query.thread_ids.is_empty() {
+            builder.push(" AND thread_id IN (");
+            let mut separated = builder.separated(", ");
+            for thread_id in &query.thread_ids {
+                separated.push_bind(thread_id.as_str());
+            }
+            separated.push_unseparated(")");
+        }
+        if let Some(call_id) = query.call_id.as_ref() {
+            builder.push(" AND call_id = ").push_bind(call_id.as_str());
+        }
+        if let Some(host_like) = query.host_like.as_ref() {
+            builder
+                .push(" AND host LIKE '%' || ")
+                .push_bind(host_like.as_str())
+                .push(" || '%'");
+        }
+        if let Some(decision) = query.decision.as_ref() {
+            builder
+                .push(" AND decision = ")
+                .push_bind(decision.as_str());
+        }
+        if let Some(from_ts) = query.from_ts {
+            builder.push(" AND ts >= ").push_bind(from_ts);
+        }
+        if let Some(to_ts) = query.to_ts {
+            builder.push(" AND ts <= ").push_bind(to_ts);
+        }
+        if let Some(after_id) = query.after_id {
+            builder.push(" AND id > ").push_bind(after_id);
+        }
+        if query.descending {
+            builder.push(" ORDER BY id DESC");
+        } else {
+            builder.push(" ORDER BY id ASC");
+        }
+        if let Some(limit) = query.limit {
+            builder.push(" LIMIT ").push_bind(limit as i64);
+        }
+
+        let rows = builder
+            .build_query_as::<NetworkRequestRow>()
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        Ok(rows)
+    }
+
+    /// Delete audit log entries recorded before `cutoff_ts`.
+    pub async fn delete_network_requests_before(&self, cutoff_ts: i64) -> anyhow::Result<u64> {
+        let result = sqlx::query("DELETE FROM network_requests WHERE ts < ?")
+            .bind(cutoff_ts)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
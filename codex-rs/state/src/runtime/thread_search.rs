@@ -0,0 +1,244 @@
+use super::*;
+use crate::ThreadSearchHit;
+use crate::ThreadSearchKind;
+use crate::extract::thread_search_entry;
+use crate::model::ThreadSearchPosition;
+
+const SNIPPET_TOKENS: i64 = 16;
+
+impl StateRuntime {
+    /// Index the searchable text in newly appended rollout items, continuing
+    /// from the turn position recorded by earlier batches for the thread.
+    pub(crate) async fn append_thread_search(
+        &self,
+        thread_id: ThreadId,
+        items: &[RolloutItem],
+    ) -> anyhow::Result<()> {
+        let thread_id = thread_id.to_string();
+        let mut tx = self.pool.begin().await?;
+        let row = sqlx::query(
+            "SELECT user_messages, turn_id FROM thread_search_progress WHERE thread_id = ?",
+        )
+        .bind(thread_id.as_str())
+        .fetch_optional(&mut *tx)
+        .await?;
+        let position = match row {
+            Some(row) => ThreadSearchPosition {
+                user_messages: row.try_get("user_messages")?,
+                turn_id: row.try_get("turn_id")?,
+            },
+            None => ThreadSearchPosition::default(),
+        };
+        write_thread_search(&mut tx, thread_id.as_str(), position, items).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// List threads the search index has never seen, ordered by id and
+    /// starting after `after`, so sessions recorded before the index existed
+    /// can be indexed without re-running the rollout backfill.
+    pub async fn threads_missing_search_index(
+        &self,
+        after: Option<ThreadId>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<(ThreadId, PathBuf)>> {
+        let after = after.map(|thread_id| thread_id.to_string());
+        let rows = sqlx::query(
+            r#"
+SELECT id, rollout_path
+FROM threads
+WHERE id > ?
+  AND NOT EXISTS (
+      SELECT 1 FROM thread_search_progress WHERE thread_search_progress.thread_id = threads.id
+  )
+ORDER BY id
+LIMIT ?
+            "#,
+        )
+        .bind(after.unwrap_or_default())
+        .bind(limit as i64)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.into_iter()
+            .map(|row| {
+                let id: String = row.try_get("id")?;
+                let rollout_path: String = row.try_get("rollout_path")?;
+                Ok((ThreadId::try_from(id)?, PathBuf::from(rollout_path)))
+            })
+            .collect()
+    }
+
+    /// Rebuild the search index of a thread from its complete rollout.
+    pub async fn reindex_thread_search(
+        &self,
+        thread_id: ThreadId,
+        items: &[RolloutItem],
+    ) -> anyhow::Result<()> {
+        let thread_id = thread_id.to_string();
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM thread_search WHERE thread_id = ?")
+            .bind(thread_id.as_str())
+            .execute(&mut *tx)
+            .await?;
+        write_thread_search(
+            &mut tx,
+            thread_id.as_str(),
+            ThreadSearchPosition::default(),
+            items,
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Search user messages, assistant messages and commands of past threads.
+    ///
+    /// Every whitespace-separated term of `query` must match, and the last one
+    /// also matches as a prefix. Returns the best match per thread, best first.
+    pub async fn search_threads(
+        &self,
+        query: &str,
+        limit: usize,
+        allowed_sources: &[String],
+        model_providers: Option<&[String]>,
+        archived_only: bool,
+    ) -> anyhow::Result<Vec<ThreadSearchHit>> {
+        let Some(match_expr) = fts_match_expression(query) else {
+            return Ok(Vec::new());
+        };
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            r#"
+WITH matches AS (
+    SELECT
+        thread_id,
+        turn_id,
+        turn_index,
+        kind,
+        snippet(thread_search, 0, '', '', '…', "#,
+        );
+        builder.push(SNIPPET_TOKENS);
+        builder.push(
+            r#") AS snippet,
+        bm25(thread_search) AS rank
+    FROM thread_search
+    WHERE thread_search MATCH "#,
+        );
+        builder.push_bind(match_expr);
+        builder.push(
+            r#"
+),
+best AS (
+    SELECT
+        *,
+        ROW_NUMBER() OVER (PARTITION BY thread_id ORDER BY rank, turn_index) AS match_rank
+    FROM matches
+)
+SELECT
+    threads.id,
+    threads.rollout_path,
+    threads.created_at,
+    threads.updated_at,
+    threads.source,
+    threads.model_provider,
+    threads.cwd,
+    threads.cli_version,
+    threads.title,
+    threads.sandbox_policy,
+    threads.approval_mode,
+    threads.tokens_used,
+    threads.first_user_message,
+    threads.archived_at,
+    threads.git_sha,
+    threads.git_branch,
+    threads.git_origin_url,
+    best.turn_id,
+    best.turn_index,
+    best.kind,
+    best.snippet
+FROM threads
+JOIN best ON best.thread_id = threads.id AND best.match_rank = 1
+            "#,
+        );
+        push_thread_filters(
+            &mut builder,
+            archived_only,
+            allowed_sources,
+            model_providers,
+            None,
+            SortKey::UpdatedAt,
+        );
+        builder.push(" ORDER BY best.rank, threads.updated_at DESC LIMIT ");
+        builder.push_bind(limit as i64);
+
+        let rows = builder.build().fetch_all(self.pool.as_ref()).await?;
+        rows.into_iter()
+            .map(|row| {
+                let metadata = ThreadRow::try_from_row(&row).and_then(ThreadMetadata::try_from)?;
+                let turn_index: i64 = row.try_get("turn_index")?;
+                let kind: String = row.try_get("kind")?;
+                Ok(ThreadSearchHit {
+                    metadata,
+                    turn_id: row.try_get("turn_id")?,
+                    turn_index: usize::try_from(turn_index).unwrap_or_default(),
+                    kind: ThreadSearchKind::parse(kind.as_str())?,
+                    snippet: row.try_get("snippet")?,
+                })
+            })
+            .collect()
+    }
+}
+
+async fn write_thread_search(
+    tx: &mut sqlx::Transaction<'_, Sqlite>,
+    thread_id: &str,
+    mut position: ThreadSearchPosition,
+    items: &[RolloutItem],
+) -> anyhow::Result<()> {
+    for item in items {
+        let Some(entry) = thread_search_entry(&mut position, item) else {
+            continue;
+        };
+        sqlx::query(
+            r#"
+INSERT INTO thread_search (text, thread_id, turn_id, turn_index, kind)
+VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(entry.text)
+        .bind(thread_id)
+        .bind(entry.turn_id)
+        .bind(entry.turn_index)
+        .bind(entry.kind.as_str())
+        .execute(&mut **tx)
+        .await?;
+    }
+    sqlx::query(
+        r#"
+INSERT INTO thread_search_progress (thread_id, user_messages, turn_id)
+VALUES (?, ?, ?)
+ON CONFLICT(thread_id) DO UPDATE SET
+    user_messages = excluded.user_messages,
+    turn_id = excluded.turn_id
+        "#,
+    )
+    .bind(thread_id)
+    .bind(position.user_messages)
+    .bind(position.turn_id)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Quote each term so FTS5 operators in user input are matched literally.
+/// Terms without any word characters would tokenize to nothing and are dropped.
+fn fts_match_expression(query: &str) -> Option<String> {
+    let terms = query
+        .split_whitespace()
+        .filter(|term| term.chars().any(char::is_alphanumeric))
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>();
+    if terms.is_empty() {
+        return None;
+    }
+    Some(format!("{}*", terms.join(" ")))
+}
@@ -11,19 +11,24 @@ use crate::bottom_pane::SelectionViewParams;
 use crate::bottom_pane::popup_consts::standard_popup_hint_line;
 use crate::chatwidget::ChatWidget;
 use crate::chatwidget::ExternalEditorState;
+use crate::checkpoints;
 use crate::cwd_prompt::CwdPromptAction;
 use crate::diff_render::DiffSummary;
+use crate::diff_render::display_path_for;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::external_editor;
 use crate::file_search::FileSearchManager;
 use crate::history_cell;
 use crate::history_cell::HistoryCell;
 use crate::i18n::tr;
+use crate::keymap::KeymapAction;
+use crate::keymap::keymap;
 #[cfg(not(debug_assertions))]
 use crate::history_cell::UpdateAvailableHistoryCell;
 use crate::model_migration::ModelMigrationOutcome;
 use crate::model_migration::migration_copy_for_models;
 use crate::model_migration::run_model_migration_prompt;
+use crate::pager_overlay::DiffSource;
 use crate::pager_overlay::Overlay;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::Renderable;
@@ -237,6 +242,26 @@ fn emit_project_config_warnings(app_event_tx: &AppEventSender, config: &Config)
     )));
 }
 
+fn emit_keymap_warnings(app_event_tx: &AppEventSender, config: &Config) {
+    for warning in crate::keymap::init(&config.tui_keymap) {
+        app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+            history_cell::new_warning_event(warning),
+        )));
+    }
+}
+
+fn emit_theme_warnings(app_event_tx: &AppEventSender, config: &Config) {
+    for warning in crate::theme::init(
+        config.tui_theme.as_deref(),
+        &config.codex_home,
+        &config.tui_theme_colors,
+    ) {
+        app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+            history_cell::new_warning_event(warning),
+        )));
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct SessionSummary {
     usage_line: String,
@@ -865,6 +890,70 @@ impl App {
         });
     }
 
+    async fn open_checkpoints(&mut self) {
+        let Some(thread_id) = self.chat_widget.thread_id() else {
+            self.chat_widget.add_error_message(
+                tr("Checkpoints are available once the session has started.", "会话开始后才能使用检查点。")
+                    .to_string(),
+            );
+            return;
+        };
+        let thread = match self.server.get_thread(thread_id).await {
+            Ok(thread) => thread,
+            Err(err) => {
+                self.chat_widget
+                    .add_error_message(format!("Failed to list checkpoints: {err}"));
+                return;
+            }
+        };
+        // Listing snapshots the working tree, which can take a moment in large repositories.
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let result = thread
+                .list_checkpoints()
+                .await
+                .map_err(|err| err.to_string());
+            tx.send(AppEvent::CheckpointsLoaded(result));
+        });
+    }
+
+    async fn restore_checkpoint_file(
+        &mut self,
+        checkpoint_id: String,
+        path: PathBuf,
+        hunk: Option<usize>,
+    ) {
+        let Some(thread_id) = self.chat_widget.thread_id() else {
+            return;
+        };
+        let result = match self.server.get_thread(thread_id).await {
+            Ok(thread) => {
+                thread
+                    .restore_checkpoint_file(checkpoint_id, path.clone(), hunk)
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+        let display_path = display_path_for(&path, &self.config.cwd);
+        match result {
+            Ok(()) => {
+                let message = match hunk {
+                    Some(hunk) => format!(
+                        "{} {} {display_path}",
+                        tr("Restored hunk", "已恢复片段"),
+                        hunk + 1
+                    ),
+                    None => format!("{} {display_path}", tr("Restored", "已恢复")),
+                };
+                self.chat_widget.add_info_message(message, None);
+            }
+            Err(err) => {
+                self.chat_widget
+                    .add_error_message(format!("Failed to restore {display_path}: {err}"));
+            }
+        }
+    }
+
     async fn select_agent_thread(&mut self, tui: &mut tui::Tui, thread_id: ThreadId) -> Result<()> {
         if self.active_thread_id == Some(thread_id) {
             return Ok(());
@@ -1007,6 +1096,8 @@ impl App {
         let (app_event_tx, mut app_event_rx) = unbounded_channel();
         let app_event_tx = AppEventSender::new(app_event_tx);
         emit_project_config_warnings(&app_event_tx, &config);
+        emit_keymap_warnings(&app_event_tx, &config);
+        emit_theme_warnings(&app_event_tx, &config);
         tui.set_notification_method(config.tui_notification_method);
 
         let harness_overrides =
@@ -1078,6 +1169,7 @@ impl App {
         let status_line_invalid_items_warned = Arc::new(AtomicBool::new(false));
 
         let enhanced_keys_supported = tui.enhanced_keys_supported();
+        let (session_selection, search_jump) = session_selection.take_search_jump();
         let mut chat_widget = match session_selection {
             SessionSelection::StartFresh | SessionSelection::Exit => {
                 let init = crate::chatwidget::ChatWidgetInit {
@@ -1102,7 +1194,7 @@ impl App {
                 };
                 ChatWidget::new(init, thread_manager.clone())
             }
-            SessionSelection::Resume(path) => {
+            SessionSelection::Resume(path) | SessionSelection::ResumeAtTurn { path, .. } => {
                 let resumed = thread_manager
                     .resume_thread_from_rollout(config.clone(), path.clone(), auth_manager.clone())
                     .await
@@ -1194,7 +1286,10 @@ impl App {
             has_emitted_history_lines: false,
             commit_anim_running: Arc::new(AtomicBool::new(false)),
             status_line_invalid_items_warned: status_line_invalid_items_warned.clone(),
-            backtrack: BacktrackState::default(),
+            backtrack: BacktrackState {
+                pending_search_jump: search_jump,
+                ..BacktrackState::default()
+            },
             backtrack_render_pending: false,
             feedback: feedback.clone(),
             feedback_audience,
@@ -1419,7 +1514,11 @@ impl App {
                 tui.frame_requester().schedule_frame();
             }
             AppEvent::OpenResumePicker => {
-                match crate::resume_picker::run_resume_picker(tui, &self.config, false).await? {
+                let (selection, search_jump) =
+                    crate::resume_picker::run_resume_picker(tui, &self.config, false, None)
+                        .await?
+                        .take_search_jump();
+                match selection {
                     SessionSelection::Resume(path) => {
                         let current_cwd = self.config.cwd.clone();
                         let resume_cwd = match crate::resolve_cwd_for_resume_or_fork(
@@ -1478,6 +1577,7 @@ impl App {
                                     resumed.session_configured,
                                 );
                                 self.reset_thread_event_state();
+                                self.backtrack.pending_search_jump = search_jump;
                                 if let Some(summary) = summary {
                                     let mut lines: Vec<Line<'static>> =
                                         vec![summary.usage_line.clone().into()];
@@ -1501,6 +1601,7 @@ impl App {
                     }
                     SessionSelection::Exit
                     | SessionSelection::StartFresh
+                    | SessionSelection::ResumeAtTurn { .. }
                     | SessionSelection::Fork(_) => {}
                 }
 
@@ -1606,6 +1707,7 @@ impl App {
                         tui.insert_history_lines(display);
                     }
                 }
+                self.maybe_reveal_search_match(tui);
             }
             AppEvent::ApplyThreadRollback { num_turns } => {
                 if self.apply_non_pending_thread_rollback(num_turns) {
@@ -1659,19 +1761,87 @@ impl App {
             AppEvent::DiffResult(text) => {
                 // Clear the in-progress state in the bottom pane
                 self.chat_widget.on_diff_complete();
-                // Enter alternate screen using TUI helper and build pager lines
+                // Enter alternate screen using TUI helper and build the diff overlay
                 let _ = tui.enter_alt_screen();
-                let pager_lines: Vec<ratatui::text::Line<'static>> = if text.trim().is_empty() {
-                    vec!["No changes detected.".italic().into()]
+                let sources = [
+                    DiffSource::parse(tr("working tree", "工作区"), &text),
+                    self.chat_widget
+                        .last_turn_diff()
+                        .and_then(|diff| DiffSource::parse(tr("last turn", "上一轮"), diff)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+                self.overlay = Some(if sources.is_empty() {
+                    // Nothing to browse: show the message (not a git repo, an error, or no
+                    // changes) in a plain pager.
+                    let pager_lines: Vec<ratatui::text::Line<'static>> = if text.trim().is_empty() {
+                        vec!["No changes detected.".italic().into()]
+                    } else {
+                        text.lines().map(ansi_escape_line).collect()
+                    };
+                    Overlay::new_static_with_lines(pager_lines, "D I F F".to_string())
                 } else {
-                    text.lines().map(ansi_escape_line).collect()
-                };
+                    Overlay::new_diff(sources, self.app_event_tx.clone())
+                });
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::OpenCheckpoints => {
+                self.open_checkpoints().await;
+            }
+            AppEvent::CheckpointsLoaded(result) => match result {
+                Ok(checkpoints) if checkpoints.is_empty() => {
+                    self.chat_widget.add_info_message(
+                        tr("No checkpoints yet.", "当前还没有检查点。").to_string(),
+                        Some(
+                            tr(
+                                "A checkpoint is taken at the start of each turn when undo is enabled.",
+                                "启用 undo 后，每轮开始时都会创建检查点。",
+                            )
+                            .to_string(),
+                        ),
+                    );
+                }
+                Ok(checkpoints) => {
+                    self.chat_widget
+                        .show_selection_view(checkpoints::checkpoints_popup_params(checkpoints));
+                }
+                Err(err) => {
+                    self.chat_widget
+                        .add_error_message(format!("Failed to list checkpoints: {err}"));
+                }
+            },
+            AppEvent::OpenCheckpointFiles(checkpoint) => {
+                self.chat_widget.show_selection_view(
+                    checkpoints::checkpoint_files_popup_params(checkpoint, &self.config.cwd),
+                );
+            }
+            AppEvent::OpenCheckpointFile {
+                checkpoint_id,
+                file,
+            } => {
+                self.chat_widget
+                    .show_selection_view(checkpoints::checkpoint_file_popup_params(
+                        checkpoint_id,
+                        file,
+                        &self.config.cwd,
+                    ));
+            }
+            AppEvent::ShowCheckpointDiff(file) => {
+                let _ = tui.enter_alt_screen();
                 self.overlay = Some(Overlay::new_static_with_lines(
-                    pager_lines,
-                    "D I F F".to_string(),
+                    checkpoints::checkpoint_diff_lines(&file),
+                    display_path_for(&file.path, &self.config.cwd),
                 ));
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::RestoreCheckpointFile {
+                checkpoint_id,
+                path,
+                hunk,
+            } => {
+                self.restore_checkpoint_file(checkpoint_id, path, hunk).await;
+            }
             AppEvent::OpenAppLink {
                 app_id,
                 title,
@@ -2445,6 +2615,21 @@ impl App {
             AppEvent::OpenReviewCustomPrompt => {
                 self.chat_widget.show_review_custom_prompt();
             }
+            AppEvent::OpenReviewCommitRangePicker(cwd) => {
+                self.chat_widget.show_review_commit_range_picker(&cwd).await;
+            }
+            AppEvent::OpenReviewPatchFilePrompt => {
+                self.chat_widget.show_review_patch_file_prompt();
+            }
+            AppEvent::OpenReviewPathsPrompt => {
+                self.chat_widget.show_review_paths_prompt();
+            }
+            AppEvent::SetReviewPaths(paths) => {
+                self.chat_widget.set_review_paths(paths);
+            }
+            AppEvent::QueueUserMessage(text) => {
+                self.chat_widget.queue_user_message(text.into());
+            }
             AppEvent::SubmitUserMessageWithMode {
                 text,
                 collaboration_mode,
@@ -2518,6 +2703,10 @@ impl App {
                 self.chat_widget.set_status_line_branch(cwd, branch);
                 self.refresh_status_line();
             }
+            AppEvent::StatusLineCommandUpdated { name, output } => {
+                self.chat_widget.set_status_line_command_output(&name, output);
+                self.refresh_status_line();
+            }
             AppEvent::StatusLineSetupCancelled => {
                 self.chat_widget.cancel_status_line_setup();
             }
@@ -2799,23 +2988,13 @@ impl App {
 
     async fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) {
         match key_event {
-            KeyEvent {
-                code: KeyCode::Char('t'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
-                kind: KeyEventKind::Press,
-                ..
-            } => {
+            key_event if keymap().is_press(KeymapAction::Transcript, key_event) => {
                 // Enter alternate screen and set viewport to full size.
                 let _ = tui.enter_alt_screen();
                 self.overlay = Some(Overlay::new_transcript(self.transcript_cells.clone()));
                 tui.frame_requester().schedule_frame();
             }
-            KeyEvent {
-                code: KeyCode::Char('g'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
-                kind: KeyEventKind::Press,
-                ..
-            } => {
+            key_event if keymap().is_press(KeymapAction::OpenEditor, key_event) => {
                 // Only launch the external editor if there is no overlay and the bottom pane is not in use.
                 // Note that it can be launched while a task is running to enable editing while the previous turn is ongoing.
                 if self.overlay.is_none()
@@ -66,6 +66,10 @@ pub(crate) struct BacktrackState {
     /// This acts as a guardrail: once we request a rollback, we block additional backtrack
     /// submissions until core responds with either a success or failure event.
     pub(crate) pending_rollback: Option<PendingBacktrackRollback>,
+    /// User message to reveal once a session resumed from a search match has replayed it.
+    ///
+    /// Counted like `nth_user_message`; cleared once the transcript overlay has been opened.
+    pub(crate) pending_search_jump: Option<usize>,
 }
 
 /// A user-visible backtrack choice that can be confirmed into a rollback request.
@@ -110,6 +114,11 @@ impl App {
         tui: &mut tui::Tui,
         event: TuiEvent,
     ) -> Result<bool> {
+        // The diff overlay uses Esc itself, to close or to cancel a hunk comment.
+        if matches!(self.overlay, Some(Overlay::Diff(_))) {
+            self.overlay_forward_event(tui, event)?;
+            return Ok(true);
+        }
         if self.backtrack.overlay_preview_active {
             match event {
                 TuiEvent::Key(KeyEvent {
@@ -261,6 +270,24 @@ impl App {
         }
     }
 
+    /// Open the transcript overlay on the user message of a search match once it is replayed.
+    pub(crate) fn maybe_reveal_search_match(&mut self, tui: &mut tui::Tui) {
+        let Some(nth_user_message) = self.backtrack.pending_search_jump else {
+            return;
+        };
+        if self.overlay.is_some() {
+            return;
+        }
+        let Some(cell_idx) = nth_user_position(&self.transcript_cells, nth_user_message) else {
+            return;
+        };
+        self.backtrack.pending_search_jump = None;
+        self.open_transcript_overlay(tui);
+        if let Some(Overlay::Transcript(t)) = &mut self.overlay {
+            t.set_highlight_cell(Some(cell_idx));
+        }
+    }
+
     /// Initialize backtrack state and show composer hint.
     fn prime_backtrack(&mut self) {
         self.backtrack.primed = true;
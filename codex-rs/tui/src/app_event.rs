@@ -11,6 +11,8 @@
 use std::path::PathBuf;
 
 use codex_chatgpt::connectors::AppInfo;
+use codex_core::protocol::Checkpoint;
+use codex_core::protocol::CheckpointFile;
 use codex_core::protocol::Event;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_file_search::FileMatch;
@@ -19,7 +21,7 @@ use codex_protocol::openai_models::ModelPreset;
 use codex_utils_approval_presets::ApprovalPreset;
 
 use crate::bottom_pane::ApprovalRequest;
-use crate::bottom_pane::StatusLineItem;
+use crate::bottom_pane::StatusLineSegment;
 use crate::history_cell::HistoryCell;
 
 use codex_core::features::Feature;
@@ -100,6 +102,31 @@ pub(crate) enum AppEvent {
     /// Result of computing a `/diff` command.
     DiffResult(String),
 
+    /// Fetch the checkpoints of the active thread for `/checkpoints`.
+    OpenCheckpoints,
+
+    /// Result of listing the checkpoints of the active thread.
+    CheckpointsLoaded(Result<Vec<Checkpoint>, String>),
+
+    /// List the files that changed since a checkpoint.
+    OpenCheckpointFiles(Checkpoint),
+
+    /// Show the restore options for one file of a checkpoint.
+    OpenCheckpointFile {
+        checkpoint_id: String,
+        file: CheckpointFile,
+    },
+
+    /// Show the diff from a checkpoint to the current file in a pager.
+    ShowCheckpointDiff(CheckpointFile),
+
+    /// Restore a file, or a single hunk of it, from a checkpoint.
+    RestoreCheckpointFile {
+        checkpoint_id: String,
+        path: PathBuf,
+        hunk: Option<usize>,
+    },
+
     /// Open the app link view in the bottom pane.
     OpenAppLink {
         app_id: String,
@@ -314,6 +341,22 @@ pub(crate) enum AppEvent {
     /// Open the custom prompt option from the review popup.
     OpenReviewCustomPrompt,
 
+    /// Open the base-commit picker for a commit range review.
+    OpenReviewCommitRangePicker(PathBuf),
+
+    /// Open the patch file prompt from the review popup.
+    OpenReviewPatchFilePrompt,
+
+    /// Open the path filter prompt from the review popup.
+    OpenReviewPathsPrompt,
+
+    /// Restrict the next review to these paths and reopen the review popup.
+    SetReviewPaths(Vec<PathBuf>),
+
+    /// Queue a user message as if it had been typed in the composer, such as a
+    /// comment on a hunk from the diff overlay.
+    QueueUserMessage(String),
+
     /// Submit a user message with an explicit collaboration mask.
     SubmitUserMessageWithMode {
         text: String,
@@ -342,9 +385,15 @@ pub(crate) enum AppEvent {
         cwd: PathBuf,
         branch: Option<String>,
     },
+    /// Async result of a command-backed status line segment. `None` means the
+    /// command timed out or could not start.
+    StatusLineCommandUpdated {
+        name: String,
+        output: Option<String>,
+    },
     /// Apply a user-confirmed status-line item ordering/selection.
     StatusLineSetup {
-        items: Vec<StatusLineItem>,
+        items: Vec<StatusLineSegment>,
     },
     /// Dismiss the status-line setup UI without changing config.
     StatusLineSetupCancelled,
@@ -15,6 +15,8 @@ use crate::i18n::tr;
 use crate::i18n::use_zh_cn;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap::KeymapAction;
+use crate::keymap::keymap;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::Renderable;
@@ -474,6 +476,11 @@ impl ApprovalOption {
     }
 }
 
+/// Keys for an approval choice from `[tui.keymap]`.
+fn approval_shortcuts(action: KeymapAction) -> Vec<KeyBinding> {
+    keymap().bindings(action).to_vec()
+}
+
 fn exec_options(
     proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
     network_approval_context: Option<&NetworkApprovalContext>,
@@ -484,7 +491,7 @@ fn exec_options(
                 label: tr("Yes, just this once", "是，仅本次").to_string(),
                 decision: ApprovalDecision::Review(ReviewDecision::Approved),
                 display_shortcut: None,
-                additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+                additional_shortcuts: approval_shortcuts(KeymapAction::ApprovalAccept),
             },
             ApprovalOption {
                 label: tr(
@@ -494,7 +501,7 @@ fn exec_options(
                 .to_string(),
                 decision: ApprovalDecision::Review(ReviewDecision::ApprovedForSession),
                 display_shortcut: None,
-                additional_shortcuts: vec![key_hint::plain(KeyCode::Char('a'))],
+                additional_shortcuts: approval_shortcuts(KeymapAction::ApprovalAcceptForSession),
             },
             ApprovalOption {
                 label: tr(
@@ -504,7 +511,7 @@ fn exec_options(
                 .to_string(),
                 decision: ApprovalDecision::Review(ReviewDecision::Abort),
                 display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
-                additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+                additional_shortcuts: approval_shortcuts(KeymapAction::ApprovalDeny),
             },
         ];
     }
@@ -513,7 +520,7 @@ fn exec_options(
         label: tr("Yes, proceed", "是，继续").to_string(),
         decision: ApprovalDecision::Review(ReviewDecision::Approved),
         display_shortcut: None,
-        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+        additional_shortcuts: approval_shortcuts(KeymapAction::ApprovalAccept),
     }]
     .into_iter()
     .chain(proposed_execpolicy_amendment.and_then(|prefix| {
@@ -543,7 +550,7 @@ fn exec_options(
         .to_string(),
         decision: ApprovalDecision::Review(ReviewDecision::Abort),
         display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
-        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+        additional_shortcuts: approval_shortcuts(KeymapAction::ApprovalDeny),
     }])
     .collect()
 }
@@ -554,7 +561,7 @@ fn patch_options() -> Vec<ApprovalOption> {
             label: tr("Yes, proceed", "是，继续").to_string(),
             decision: ApprovalDecision::Review(ReviewDecision::Approved),
             display_shortcut: None,
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+            additional_shortcuts: approval_shortcuts(KeymapAction::ApprovalAccept),
         },
         ApprovalOption {
             label: tr(
@@ -564,7 +571,7 @@ fn patch_options() -> Vec<ApprovalOption> {
             .to_string(),
             decision: ApprovalDecision::Review(ReviewDecision::ApprovedForSession),
             display_shortcut: None,
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('a'))],
+            additional_shortcuts: approval_shortcuts(KeymapAction::ApprovalAcceptForSession),
         },
         ApprovalOption {
             label: tr(
@@ -574,7 +581,7 @@ fn patch_options() -> Vec<ApprovalOption> {
             .to_string(),
             decision: ApprovalDecision::Review(ReviewDecision::Abort),
             display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+            additional_shortcuts: approval_shortcuts(KeymapAction::ApprovalDeny),
         },
     ]
 }
@@ -585,13 +592,13 @@ fn elicitation_options() -> Vec<ApprovalOption> {
             label: tr("Yes, provide the requested info", "是，提供所需信息").to_string(),
             decision: ApprovalDecision::McpElicitation(ElicitationAction::Accept),
             display_shortcut: None,
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+            additional_shortcuts: approval_shortcuts(KeymapAction::ApprovalAccept),
         },
         ApprovalOption {
             label: tr("No, but continue without it", "否，但继续执行").to_string(),
             decision: ApprovalDecision::McpElicitation(ElicitationAction::Decline),
             display_shortcut: None,
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+            additional_shortcuts: approval_shortcuts(KeymapAction::ApprovalDeny),
         },
         ApprovalOption {
             label: tr("Cancel this request", "取消本次请求").to_string(),
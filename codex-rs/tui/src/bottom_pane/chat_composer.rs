@@ -114,6 +114,8 @@ use crate::bottom_pane::selection_popup_common::truncate_line_with_ellipsis_if_o
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::key_hint::has_ctrl_or_alt;
+use crate::keymap::KeymapAction;
+use crate::keymap::keymap;
 use crate::ui_consts::FOOTER_INDENT_COLS;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
@@ -154,6 +156,7 @@ use super::footer::render_context_right;
 use super::footer::render_footer_from_props;
 use super::footer::render_footer_hint_items;
 use super::footer::render_footer_line;
+use super::footer::render_vim_mode_indicator;
 use super::footer::reset_mode_after_activity;
 use super::footer::single_line_footer_layout;
 use super::footer::toggle_shortcut_mode;
@@ -162,6 +165,8 @@ use super::paste_burst::PasteBurst;
 use super::skill_popup::MentionItem;
 use super::skill_popup::SkillPopup;
 use super::slash_commands;
+use super::vim::VimMode;
+use super::vim::VimState;
 use crate::bottom_pane::paste_burst::FlushResult;
 use crate::bottom_pane::prompt_args::expand_custom_prompt;
 use crate::bottom_pane::prompt_args::expand_if_numeric_with_positional_args;
@@ -338,6 +343,8 @@ pub(crate) struct ChatComposer {
     windows_degraded_sandbox_active: bool,
     status_line_value: Option<Line<'static>>,
     status_line_enabled: bool,
+    /// Modal editing state when `tui.editing_mode = "vim"`.
+    vim: Option<VimState>,
 }
 
 #[derive(Clone, Debug)]
@@ -438,6 +445,7 @@ impl ChatComposer {
             windows_degraded_sandbox_active: false,
             status_line_value: None,
             status_line_enabled: false,
+            vim: None,
         };
         // Apply configuration via the setter to keep side-effects centralized.
         this.set_disable_paste_burst(disable_paste_burst);
@@ -509,7 +517,12 @@ impl ChatComposer {
     }
     /// Centralized feature gating keeps config checks out of call sites.
     fn popups_enabled(&self) -> bool {
+        // Popups follow typed text, so they only make sense while inserting.
         self.config.popups_enabled
+            && self
+                .vim
+                .as_ref()
+                .is_none_or(|vim| vim.mode() == VimMode::Insert)
     }
 
     fn slash_commands_enabled(&self) -> bool {
@@ -1130,6 +1143,18 @@ impl ChatComposer {
             return (InputResult::None, false);
         }
 
+        // Insert mode leaves keys to the popups first, so `Esc` closes a popup
+        // before it switches to normal mode.
+        let popup_active = self.popup_active();
+        if let Some(vim) = self.vim.as_mut()
+            && (vim.mode() != VimMode::Insert || !popup_active)
+            && vim.handle_key(&mut self.textarea, key_event)
+        {
+            self.footer_mode = reset_mode_after_activity(self.footer_mode);
+            self.sync_popups();
+            return (InputResult::None, true);
+        }
+
         let result = match &mut self.active_popup {
             ActivePopup::Command(_) => self.handle_key_event_with_slash_popup(key_event),
             ActivePopup::File(_) => self.handle_key_event_with_file_popup(key_event),
@@ -1140,6 +1165,14 @@ impl ChatComposer {
         // Update (or hide/show) popup after processing the key.
         self.sync_popups();
 
+        if matches!(
+            result.0,
+            InputResult::Submitted { .. } | InputResult::Queued { .. }
+        ) && let Some(vim) = self.vim.as_mut()
+        {
+            vim.reset(&mut self.textarea);
+        }
+
         result
     }
 
@@ -2535,26 +2568,20 @@ impl ChatComposer {
             // empty or when the cursor is at the correct position, to avoid
             // interfering with normal cursor movement.
             // -------------------------------------------------------------
-            KeyEvent {
-                code: KeyCode::Up | KeyCode::Down,
-                ..
-            }
-            | KeyEvent {
-                code: KeyCode::Char('p') | KeyCode::Char('n'),
-                modifiers: KeyModifiers::CONTROL,
-                ..
-            } => {
+            key_event
+                if keymap().is_press(KeymapAction::HistoryPrevious, key_event)
+                    || keymap().is_press(KeymapAction::HistoryNext, key_event) =>
+            {
                 if self
                     .history
                     .should_handle_navigation(self.textarea.text(), self.textarea.cursor())
                 {
-                    let replace_entry = match key_event.code {
-                        KeyCode::Up => self.history.navigate_up(&self.app_event_tx),
-                        KeyCode::Down => self.history.navigate_down(&self.app_event_tx),
-                        KeyCode::Char('p') => self.history.navigate_up(&self.app_event_tx),
-                        KeyCode::Char('n') => self.history.navigate_down(&self.app_event_tx),
-                        _ => unreachable!(),
-                    };
+                    let replace_entry =
+                        if keymap().is_press(KeymapAction::HistoryPrevious, key_event) {
+                            self.history.navigate_up(&self.app_event_tx)
+                        } else {
+                            self.history.navigate_down(&self.app_event_tx)
+                        };
                     if let Some(entry) = replace_entry {
                         self.apply_history_entry(entry);
                         return (InputResult::None, true);
@@ -2562,30 +2589,29 @@ impl ChatComposer {
                 }
                 self.handle_input_basic(key_event)
             }
-            KeyEvent {
-                code: KeyCode::Tab,
-                modifiers: KeyModifiers::NONE,
-                kind: KeyEventKind::Press,
-                ..
-            } if self.steer_enabled && !self.is_bang_shell_command() => {
+            key_event
+                if keymap().is_press(KeymapAction::QueueMessage, key_event)
+                    && self.steer_enabled
+                    && !self.is_bang_shell_command() =>
+            {
                 self.handle_submission(self.is_task_running)
             }
-            KeyEvent {
-                code: KeyCode::Tab,
-                modifiers: KeyModifiers::NONE,
-                kind: KeyEventKind::Press,
-                ..
-            } if self.is_task_running && !self.is_bang_shell_command() => {
+            key_event
+                if keymap().is_press(KeymapAction::QueueMessage, key_event)
+                    && self.is_task_running
+                    && !self.is_bang_shell_command() =>
+            {
                 self.handle_submission(true)
             }
-            KeyEvent {
-                code: KeyCode::Enter,
-                modifiers: KeyModifiers::NONE,
-                ..
-            } => {
+            key_event if keymap().is_press(KeymapAction::Submit, key_event) => {
                 let should_queue = !self.steer_enabled;
                 self.handle_submission(should_queue)
             }
+            // The textarea inserts a newline for shift+enter, so custom newline
+            // chords are routed through the same path.
+            key_event if keymap().is_press(KeymapAction::Newline, key_event) => {
+                self.handle_input_basic(KeyEvent::new(KeyCode::Enter, KeyModifiers::SHIFT))
+            }
             input => self.handle_input_basic(input),
         }
     }
@@ -3361,6 +3387,21 @@ impl ChatComposer {
         self.status_line_enabled = enabled;
         true
     }
+
+    pub(crate) fn set_vim_mode_enabled(&mut self, enabled: bool) -> bool {
+        if self.vim.is_some() == enabled {
+            return false;
+        }
+        self.vim = enabled.then(VimState::new);
+        self.textarea.set_selection(None);
+        true
+    }
+
+    /// True when vim mode wants `Esc` for itself (to leave insert or visual
+    /// mode), so it must not interrupt a task or start backtracking.
+    pub(crate) fn vim_captures_esc(&self) -> bool {
+        self.vim.as_ref().is_some_and(VimState::captures_esc)
+    }
 }
 
 fn skill_display_name(skill: &SkillMetadata) -> &str {
@@ -3508,6 +3549,12 @@ impl ChatComposer {
                 } else {
                     popup_rect
                 };
+                let hint_rect = match self.vim.as_ref() {
+                    Some(vim) if footer_props.mode != FooterMode::ShortcutOverlay => {
+                        render_vim_mode_indicator(hint_rect, buf, vim.mode())
+                    }
+                    _ => hint_rect,
+                };
                 let available_width =
                     hint_rect.width.saturating_sub(FOOTER_INDENT_COLS as u16) as usize;
                 let status_line = footer_props
@@ -5813,6 +5860,42 @@ mod tests {
         assert!(composer.textarea.is_empty());
     }
 
+    #[test]
+    fn vim_mode_edits_in_normal_mode_and_returns_to_insert_after_submit() {
+        use crossterm::event::KeyCode;
+        use crossterm::event::KeyEvent;
+        use crossterm::event::KeyModifiers;
+
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.set_steer_enabled(true);
+        assert!(composer.set_vim_mode_enabled(true));
+
+        type_chars_humanlike(&mut composer, &['h', 'e', 'y', '/']);
+        composer.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(!composer.vim_captures_esc());
+        assert!(!composer.popup_active());
+        for ch in ['0', 'x', 'x'] {
+            composer.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+        assert_eq!(composer.textarea.text(), "y/");
+
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(
+            result,
+            InputResult::Submitted { ref text, .. } if text == "y/"
+        ));
+        assert!(composer.vim_captures_esc());
+    }
+
     #[test]
     fn tab_does_not_submit_for_bang_shell_command_in_steer_mode() {
         use crossterm::event::KeyCode;
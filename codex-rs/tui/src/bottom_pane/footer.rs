@@ -32,9 +32,12 @@
 //! In short: `single_line_footer_layout` chooses *what* best fits, and the two
 //! render helpers choose whether to draw the chosen line or the default
 //! `FooterProps` mapping.
+use crate::bottom_pane::vim::VimMode;
 use crate::i18n::tr;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap::KeymapAction;
+use crate::keymap::keymap;
 use crate::render::line_utils::prefix_lines;
 use crate::status::format_tokens_compact;
 use crate::ui_consts::FOOTER_INDENT_COLS;
@@ -263,12 +266,16 @@ fn left_side_line(
             line.push_span(tr(" for shortcuts", " 查看快捷键").dim());
         }
         SummaryHintKind::QueueMessage => {
-            line.push_span(key_hint::plain(KeyCode::Tab));
-            line.push_span(tr(" to queue message", " 将消息加入队列").dim());
+            if let Some(key) = keymap().hint(KeymapAction::QueueMessage) {
+                line.push_span(key);
+                line.push_span(tr(" to queue message", " 将消息加入队列").dim());
+            }
         }
         SummaryHintKind::QueueShort => {
-            line.push_span(key_hint::plain(KeyCode::Tab));
-            line.push_span(tr(" to queue", " 加入队列").dim());
+            if let Some(key) = keymap().hint(KeymapAction::QueueMessage) {
+                line.push_span(key);
+                line.push_span(tr(" to queue", " 加入队列").dim());
+            }
         }
     };
 
@@ -536,6 +543,23 @@ pub(crate) fn render_context_right(area: Rect, buf: &mut Buffer, line: &Line<'st
     }
 }
 
+/// Render the vim mode label at the start of the footer's first line and
+/// return the area left for the regular footer content.
+pub(crate) fn render_vim_mode_indicator(area: Rect, buf: &mut Buffer, mode: VimMode) -> Rect {
+    let label = mode.indicator();
+    let label_width = label.width() as u16;
+    let offset = FOOTER_INDENT_COLS as u16 + label_width;
+    if area.is_empty() || area.width <= offset {
+        return area;
+    }
+    buf.set_span(area.x + FOOTER_INDENT_COLS as u16, area.y, &label, label_width);
+    Rect {
+        x: area.x + offset,
+        width: area.width - offset,
+        ..area
+    }
+}
+
 pub(crate) fn inset_footer_hint_area(mut area: Rect) -> Rect {
     if area.width > 2 {
         area.x += 2;
@@ -860,6 +884,33 @@ impl ShortcutDescriptor {
         self.bindings.iter().find(|binding| binding.matches(state))
     }
 
+    /// The `[tui.keymap]` action behind this shortcut, if it is configurable.
+    fn keymap_action(&self) -> Option<KeymapAction> {
+        match self.id {
+            ShortcutId::InsertNewline => Some(KeymapAction::Newline),
+            ShortcutId::QueueMessageTab => Some(KeymapAction::QueueMessage),
+            ShortcutId::ExternalEditor => Some(KeymapAction::OpenEditor),
+            ShortcutId::ShowTranscript => Some(KeymapAction::Transcript),
+            ShortcutId::Commands
+            | ShortcutId::ShellCommands
+            | ShortcutId::FilePaths
+            | ShortcutId::PasteImage
+            | ShortcutId::EditPrevious
+            | ShortcutId::Quit
+            | ShortcutId::ChangeMode => None,
+        }
+    }
+
+    /// Key shown for this shortcut. A customized keymap entry wins over the
+    /// built-in per-terminal choice; an unbound action hides the shortcut.
+    fn key_for(&self, state: ShortcutsState) -> Option<KeyBinding> {
+        let binding = self.binding_for(state)?;
+        match self.keymap_action() {
+            Some(action) if keymap().is_customized(action) => keymap().hint(action),
+            _ => Some(binding.key),
+        }
+    }
+
     fn localized_label(&self) -> &'static str {
         match self.id {
             ShortcutId::Commands => tr(" for commands", " 显示命令"),
@@ -877,8 +928,8 @@ impl ShortcutDescriptor {
     }
 
     fn overlay_entry(&self, state: ShortcutsState) -> Option<Line<'static>> {
-        let binding = self.binding_for(state)?;
-        let mut line = Line::from(vec![self.prefix.into(), binding.key.into()]);
+        let key = self.key_for(state)?;
+        let mut line = Line::from(vec![self.prefix.into(), key.into()]);
         match self.id {
             ShortcutId::EditPrevious => {
                 if state.esc_backtrack_hint {
@@ -22,6 +22,8 @@ use crate::bottom_pane::unified_exec_footer::UnifiedExecFooter;
 use crate::i18n::tr;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap::KeymapAction;
+use crate::keymap::keymap;
 use crate::render::renderable::FlexRenderable;
 use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableItem;
@@ -87,6 +89,7 @@ pub(crate) use feedback_view::feedback_upload_consent_params;
 pub(crate) use skills_toggle_view::SkillsToggleItem;
 pub(crate) use skills_toggle_view::SkillsToggleView;
 pub(crate) use status_line_setup::StatusLineItem;
+pub(crate) use status_line_setup::StatusLineSegment;
 pub(crate) use status_line_setup::StatusLineSetupView;
 mod paste_burst;
 pub mod popup_consts;
@@ -95,6 +98,7 @@ mod scroll_state;
 mod selection_popup_common;
 mod textarea;
 mod unified_exec_footer;
+mod vim;
 pub(crate) use feedback_view::FeedbackNoteView;
 
 /// How long the "press again to quit" hint stays visible.
@@ -361,12 +365,14 @@ impl BottomPane {
             self.request_redraw();
             InputResult::None
         } else {
-            // If a task is running and a status line is visible, allow Esc to
-            // send an interrupt even while the composer has focus.
+            // If a task is running and a status line is visible, allow the
+            // interrupt key (Esc by default) to send an interrupt even while the
+            // composer has focus.
             // When a popup is active, prefer dismissing it over interrupting the task.
-            if key_event.code == KeyCode::Esc
+            if keymap().is_press(KeymapAction::Interrupt, key_event)
                 && self.is_task_running
                 && !self.composer.popup_active()
+                && !self.composer.vim_captures_esc()
                 && let Some(status) = &self.status
             {
                 // Send Op::Interrupt
@@ -757,7 +763,10 @@ impl BottomPane {
     /// overlays or popups and not running a task. This is the safe context to
     /// use Esc-Esc for backtracking from the main view.
     pub(crate) fn is_normal_backtrack_mode(&self) -> bool {
-        !self.is_task_running && self.view_stack.is_empty() && !self.composer.popup_active()
+        !self.is_task_running
+            && self.view_stack.is_empty()
+            && !self.composer.popup_active()
+            && !self.composer.vim_captures_esc()
     }
 
     /// Return true when no popups or modal views are active, regardless of task state.
@@ -968,6 +977,12 @@ impl BottomPane {
             self.request_redraw();
         }
     }
+
+    pub(crate) fn set_vim_mode_enabled(&mut self, enabled: bool) {
+        if self.composer.set_vim_mode_enabled(enabled) {
+            self.request_redraw();
+        }
+    }
 }
 
 impl Renderable for BottomPane {
@@ -16,11 +16,16 @@
 //! - Usage limits (5-hour, weekly)
 //! - Session info (ID, tokens used)
 //! - Application version
+//! - Custom segments backed by shell commands (`[tui.status_line_commands]`)
 
+use codex_core::config::types::StatusLineCommand;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::text::Line;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::collections::HashSet;
+use std::fmt;
 use strum::IntoEnumIterator;
 use strum_macros::Display;
 use strum_macros::EnumIter;
@@ -183,6 +188,39 @@ impl StatusLineItem {
     }
 }
 
+/// One entry of the configured status line: a built-in item, or a segment
+/// showing the output of a command from `[tui.status_line_commands]`.
+///
+/// Both are referenced by id in `tui.status_line`; built-in ids win when a
+/// command is named like one.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum StatusLineSegment {
+    Item(StatusLineItem),
+    Command(String),
+}
+
+impl StatusLineSegment {
+    /// Resolves a configured id, or `None` when it names neither a built-in
+    /// item nor a configured command.
+    pub(crate) fn parse(id: &str, commands: &BTreeMap<String, StatusLineCommand>) -> Option<Self> {
+        if let Ok(item) = id.parse::<StatusLineItem>() {
+            return Some(StatusLineSegment::Item(item));
+        }
+        commands
+            .contains_key(id)
+            .then(|| StatusLineSegment::Command(id.to_string()))
+    }
+}
+
+impl fmt::Display for StatusLineSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatusLineSegment::Item(item) => write!(f, "{item}"),
+            StatusLineSegment::Command(name) => f.write_str(name),
+        }
+    }
+}
+
 /// Interactive view for configuring which items appear in the status line.
 ///
 /// Wraps a [`MultiSelectPicker`] with status-line-specific behavior:
@@ -202,35 +240,51 @@ impl StatusLineSetupView {
     ///
     /// * `status_line_items` - Currently configured item IDs (in display order),
     ///   or `None` to start with all items disabled
+    /// * `commands` - Command-backed segments from `[tui.status_line_commands]`
+    /// * `command_outputs` - Last known output of each command, used in the preview
     /// * `app_event_tx` - Event sender for dispatching configuration changes
     ///
     /// Items from `status_line_items` are shown first (in order) and marked as
-    /// enabled. Remaining items are appended and marked as disabled.
-    pub(crate) fn new(status_line_items: Option<&[String]>, app_event_tx: AppEventSender) -> Self {
+    /// enabled. Remaining built-in items and then command segments are
+    /// appended and marked as disabled.
+    pub(crate) fn new(
+        status_line_items: Option<&[String]>,
+        commands: &BTreeMap<String, StatusLineCommand>,
+        command_outputs: HashMap<String, String>,
+        app_event_tx: AppEventSender,
+    ) -> Self {
         let mut used_ids = HashSet::new();
         let mut items = Vec::new();
 
         if let Some(selected_items) = status_line_items.as_ref() {
             for id in *selected_items {
-                let Ok(item) = id.parse::<StatusLineItem>() else {
+                let Some(segment) = StatusLineSegment::parse(id, commands) else {
                     continue;
                 };
-                let item_id = item.to_string();
+                let item_id = segment.to_string();
                 if !used_ids.insert(item_id.clone()) {
                     continue;
                 }
-                items.push(Self::status_line_select_item(item, true));
+                items.push(Self::status_line_select_item(&segment, commands, true));
             }
         }
 
-        for item in StatusLineItem::iter() {
-            let item_id = item.to_string();
-            if used_ids.contains(&item_id) {
+        let available = StatusLineItem::iter().map(StatusLineSegment::Item).chain(
+            commands
+                .keys()
+                .filter_map(|name| StatusLineSegment::parse(name, commands)),
+        );
+        for segment in available {
+            let item_id = segment.to_string();
+            if !used_ids.insert(item_id) {
                 continue;
             }
-            items.push(Self::status_line_select_item(item, false));
+            items.push(Self::status_line_select_item(&segment, commands, false));
         }
 
+        let preview_commands = commands.clone();
+        let confirm_commands = commands.clone();
+
         Self {
             picker: MultiSelectPicker::builder(
                 tr("Configure Status Line", "配置状态栏").to_string(),
@@ -246,12 +300,19 @@ impl StatusLineSetupView {
             ])
             .items(items)
             .enable_ordering()
-            .on_preview(|items| {
+            .on_preview(move |items| {
                 let preview = items
                     .iter()
                     .filter(|item| item.enabled)
-                    .filter_map(|item| item.id.parse::<StatusLineItem>().ok())
-                    .map(|item| item.render())
+                    .filter_map(|item| StatusLineSegment::parse(&item.id, &preview_commands))
+                    .map(|segment| match segment {
+                        StatusLineSegment::Item(item) => item.render().to_string(),
+                        StatusLineSegment::Command(name) => command_outputs
+                            .get(&name)
+                            .filter(|output| !output.is_empty())
+                            .cloned()
+                            .unwrap_or(name),
+                    })
                     .collect::<Vec<_>>()
                     .join(" · ");
                 if preview.is_empty() {
@@ -260,11 +321,11 @@ impl StatusLineSetupView {
                     Some(Line::from(preview))
                 }
             })
-            .on_confirm(|ids, app_event| {
+            .on_confirm(move |ids, app_event| {
                 let items = ids
                     .iter()
-                    .map(|id| id.parse::<StatusLineItem>())
-                    .collect::<Result<Vec<_>, _>>()
+                    .map(|id| StatusLineSegment::parse(id, &confirm_commands))
+                    .collect::<Option<Vec<_>>>()
                     .unwrap_or_default();
                 app_event.send(AppEvent::StatusLineSetup { items });
             })
@@ -275,12 +336,26 @@ impl StatusLineSetupView {
         }
     }
 
-    /// Converts a [`StatusLineItem`] into a [`MultiSelectItem`] for the picker.
-    fn status_line_select_item(item: StatusLineItem, enabled: bool) -> MultiSelectItem {
+    /// Converts a [`StatusLineSegment`] into a [`MultiSelectItem`] for the picker.
+    fn status_line_select_item(
+        segment: &StatusLineSegment,
+        commands: &BTreeMap<String, StatusLineCommand>,
+        enabled: bool,
+    ) -> MultiSelectItem {
+        let description = match segment {
+            StatusLineSegment::Item(item) => item.description().to_string(),
+            StatusLineSegment::Command(name) => {
+                let command = commands
+                    .get(name)
+                    .map(|command| command.command.as_str())
+                    .unwrap_or_default();
+                format!("{} {command}", tr("Output of", "命令输出："))
+            }
+        };
         MultiSelectItem {
-            id: item.to_string(),
-            name: item.to_string(),
-            description: Some(item.description().to_string()),
+            id: segment.to_string(),
+            name: segment.to_string(),
+            description: Some(description),
             enabled,
         }
     }
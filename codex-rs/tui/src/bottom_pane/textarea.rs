@@ -8,6 +8,7 @@ use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Color;
 use ratatui::style::Style;
+use ratatui::style::Stylize;
 use ratatui::widgets::StatefulWidgetRef;
 use ratatui::widgets::WidgetRef;
 use std::cell::Ref;
@@ -17,6 +18,9 @@ use textwrap::Options;
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
+/// Upper bound on saved undo states so long sessions do not grow without limit.
+const MAX_UNDO_STATES: usize = 100;
+
 const WORD_SEPARATORS: &str = "`~!@#$%^&*()-=+[{]}\\|;:'\",.<>/?";
 
 fn is_word_separator(ch: char) -> bool {
@@ -45,6 +49,18 @@ pub(crate) struct TextArea {
     elements: Vec<TextElement>,
     next_element_id: u64,
     kill_buffer: String,
+    undo_stack: Vec<UndoState>,
+    redo_stack: Vec<UndoState>,
+    /// Byte range highlighted as a selection (used by vim visual mode).
+    selection: Option<Range<usize>>,
+}
+
+/// Text, cursor and element ranges captured by [`TextArea::push_undo_state`].
+#[derive(Debug, Clone)]
+struct UndoState {
+    text: String,
+    cursor_pos: usize,
+    elements: Vec<TextElement>,
 }
 
 #[derive(Debug, Clone)]
@@ -69,6 +85,9 @@ impl TextArea {
             elements: Vec::new(),
             next_element_id: 1,
             kill_buffer: String::new(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            selection: None,
         }
     }
 
@@ -110,6 +129,9 @@ impl TextArea {
         self.wrap_cache.replace(None);
         self.preferred_col = None;
         self.kill_buffer.clear();
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.selection = None;
     }
 
     pub fn text(&self) -> &str {
@@ -577,7 +599,24 @@ impl TextArea {
         self.insert_str(&text);
     }
 
-    fn kill_range(&mut self, range: Range<usize>) {
+    pub(crate) fn kill_buffer(&self) -> &str {
+        &self.kill_buffer
+    }
+
+    pub(crate) fn set_kill_buffer(&mut self, text: &str) {
+        self.kill_buffer = text.to_string();
+    }
+
+    /// Copy `range`, widened to whole text elements, into the kill buffer
+    /// without editing the text.
+    pub(crate) fn copy_range(&mut self, range: Range<usize>) {
+        let range = self.expand_range_to_element_boundaries(range);
+        if range.start < range.end {
+            self.kill_buffer = self.text[range].to_string();
+        }
+    }
+
+    pub(crate) fn kill_range(&mut self, range: Range<usize>) {
         let range = self.expand_range_to_element_boundaries(range);
         if range.start >= range.end {
             return;
@@ -592,6 +631,74 @@ impl TextArea {
         self.replace_range_raw(range, "");
     }
 
+    /// Save the current text so a later [`TextArea::undo`] can return to it.
+    ///
+    /// Callers decide the granularity: vim mode saves once before each
+    /// normal-mode change and once when entering insert mode.
+    pub(crate) fn push_undo_state(&mut self) {
+        let state = self.undo_state();
+        if self
+            .undo_stack
+            .last()
+            .is_some_and(|last| last.text == state.text)
+        {
+            return;
+        }
+        if self.undo_stack.len() == MAX_UNDO_STATES {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack.push(state);
+        self.redo_stack.clear();
+    }
+
+    /// Restore the most recently saved state. Returns false when there is
+    /// nothing to undo.
+    pub(crate) fn undo(&mut self) -> bool {
+        let Some(state) = self.undo_stack.pop() else {
+            return false;
+        };
+        self.redo_stack.push(self.undo_state());
+        self.restore_undo_state(state);
+        true
+    }
+
+    /// Re-apply the most recently undone state. Returns false when there is
+    /// nothing to redo.
+    pub(crate) fn redo(&mut self) -> bool {
+        let Some(state) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.undo_stack.push(self.undo_state());
+        self.restore_undo_state(state);
+        true
+    }
+
+    fn undo_state(&self) -> UndoState {
+        UndoState {
+            text: self.text.clone(),
+            cursor_pos: self.cursor_pos,
+            elements: self.elements.clone(),
+        }
+    }
+
+    fn restore_undo_state(&mut self, state: UndoState) {
+        self.text = state.text;
+        self.elements = state.elements;
+        self.cursor_pos = state.cursor_pos.min(self.text.len());
+        self.cursor_pos = self.clamp_pos_to_nearest_boundary(self.cursor_pos);
+        self.wrap_cache.replace(None);
+        self.preferred_col = None;
+    }
+
+    /// Highlight `selection` when rendering, or clear the highlight.
+    pub(crate) fn set_selection(&mut self, selection: Option<Range<usize>>) {
+        self.selection = selection.map(|range| {
+            let start = self.clamp_pos_to_char_boundary(range.start.min(self.text.len()));
+            let end = self.clamp_pos_to_char_boundary(range.end.min(self.text.len()));
+            start..end.max(start)
+        });
+    }
+
     /// Move the cursor left by a single grapheme cluster.
     pub fn move_cursor_left(&mut self) {
         self.cursor_pos = self.prev_atomic_boundary(self.cursor_pos);
@@ -1051,7 +1158,7 @@ impl TextArea {
         self.shift_elements(start, end.saturating_sub(start), inserted_len);
     }
 
-    fn prev_atomic_boundary(&self, pos: usize) -> usize {
+    pub(crate) fn prev_atomic_boundary(&self, pos: usize) -> usize {
         if pos == 0 {
             return 0;
         }
@@ -1077,7 +1184,7 @@ impl TextArea {
         }
     }
 
-    fn next_atomic_boundary(&self, pos: usize) -> usize {
+    pub(crate) fn next_atomic_boundary(&self, pos: usize) -> usize {
         if pos >= self.text.len() {
             return self.text.len();
         }
@@ -1279,6 +1386,19 @@ impl TextArea {
                 let style = Style::default().fg(Color::Cyan);
                 buf.set_string(area.x + x_off, y, styled, style);
             }
+
+            if let Some(selection) = &self.selection {
+                let overlap_start = selection.start.max(line_range.start);
+                let overlap_end = selection.end.min(line_range.end);
+                if overlap_start < overlap_end {
+                    let x_off = self.text[line_range.start..overlap_start].width() as u16;
+                    let width = self.text[overlap_start..overlap_end].width() as u16;
+                    buf.set_style(
+                        Rect::new(area.x + x_off, y, width, 1).intersection(area),
+                        Style::default().reversed(),
+                    );
+                }
+            }
         }
     }
 
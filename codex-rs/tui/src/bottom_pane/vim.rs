@@ -0,0 +1,1238 @@
+//! Vim-style modal editing for the composer textarea.
+//!
+//! Enabled with `tui.editing_mode = "vim"`. The composer starts in insert mode, where keys reach
+//! the textarea as usual; `Esc` switches to normal mode. Normal and visual mode keys are handled
+//! here and edit the [`TextArea`] directly. Yanks and deletes share the textarea kill buffer, so
+//! `Ctrl+Y` and `p` put the same text.
+//!
+//! Keys this module does not claim (for example `Enter`, `Up`/`Down`, or `Esc` in normal mode
+//! with nothing pending) fall through to the composer so submit, history and interrupt keep
+//! working.
+
+use std::ops::Range;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use ratatui::style::Stylize;
+use ratatui::text::Span;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use super::textarea::TextArea;
+use crate::i18n::tr;
+
+/// Upper bound for typed counts, so `99999999x` cannot stall the UI.
+const MAX_COUNT: usize = 9_999;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum VimMode {
+    Insert,
+    Normal,
+    Visual,
+    VisualLine,
+}
+
+impl VimMode {
+    /// Mode label rendered at the start of the footer.
+    pub(crate) fn indicator(self) -> Span<'static> {
+        match self {
+            VimMode::Insert => Span::from(tr("INSERT", "插入")).dim(),
+            VimMode::Normal => Span::from(tr("NORMAL", "普通")).cyan().bold(),
+            VimMode::Visual => Span::from(tr("VISUAL", "可视")).cyan().bold(),
+            VimMode::VisualLine => Span::from(tr("V-LINE", "可视行")).cyan().bold(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operator {
+    Delete,
+    Change,
+    Yank,
+}
+
+impl Operator {
+    fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            'd' => Some(Operator::Delete),
+            'c' => Some(Operator::Change),
+            'y' => Some(Operator::Yank),
+            _ => None,
+        }
+    }
+}
+
+/// A multi-key command that is waiting for its next key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Pending {
+    None,
+    /// `d`, `c` or `y` waiting for a motion or text object.
+    Operator {
+        op: Operator,
+        count: Option<usize>,
+    },
+    /// `i`/`a` waiting for the text object kind. `op` is `None` in visual mode.
+    TextObject {
+        op: Option<Operator>,
+        inner: bool,
+    },
+    /// `g` waiting for its second key (only `gg` is supported).
+    G {
+        op: Option<Operator>,
+        count: Option<usize>,
+    },
+    /// `r` waiting for the replacement character.
+    Replace,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Motion {
+    Left,
+    Right,
+    Up,
+    Down,
+    LineStart,
+    FirstNonBlank,
+    LineEnd,
+    WordStart { big: bool },
+    WordBackward { big: bool },
+    WordEnd { big: bool },
+    FirstLine,
+    LastLine,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MotionKind {
+    /// The range stops before the target (`dw`, `dh`).
+    Exclusive,
+    /// The range includes the character at the target (`de`, `d$`).
+    Inclusive,
+    /// The range covers whole lines (`dj`, `dG`).
+    Linewise,
+}
+
+impl Motion {
+    fn from_char(ch: char) -> Option<Self> {
+        Some(match ch {
+            'h' => Motion::Left,
+            'l' | ' ' => Motion::Right,
+            'k' => Motion::Up,
+            'j' => Motion::Down,
+            '0' => Motion::LineStart,
+            '^' => Motion::FirstNonBlank,
+            '$' => Motion::LineEnd,
+            'w' => Motion::WordStart { big: false },
+            'W' => Motion::WordStart { big: true },
+            'b' => Motion::WordBackward { big: false },
+            'B' => Motion::WordBackward { big: true },
+            'e' => Motion::WordEnd { big: false },
+            'E' => Motion::WordEnd { big: true },
+            'G' => Motion::LastLine,
+            _ => return None,
+        })
+    }
+
+    fn kind(self) -> MotionKind {
+        match self {
+            Motion::Left
+            | Motion::Right
+            | Motion::LineStart
+            | Motion::FirstNonBlank
+            | Motion::WordStart { .. }
+            | Motion::WordBackward { .. } => MotionKind::Exclusive,
+            Motion::LineEnd | Motion::WordEnd { .. } => MotionKind::Inclusive,
+            Motion::Up | Motion::Down | Motion::FirstLine | Motion::LastLine => {
+                MotionKind::Linewise
+            }
+        }
+    }
+
+    /// Byte offset the motion moves to from the textarea cursor. `count` is
+    /// `None` when the user did not type one.
+    fn target(self, textarea: &TextArea, count: Option<usize>) -> usize {
+        let text = textarea.text();
+        let pos = textarea.cursor();
+        let n = count.unwrap_or(1);
+        match self {
+            Motion::Left => {
+                let bol = line_start(text, pos);
+                let mut target = pos;
+                for _ in 0..n {
+                    if target <= bol {
+                        break;
+                    }
+                    target = textarea.prev_atomic_boundary(target);
+                }
+                target
+            }
+            Motion::Right => {
+                let eol = line_end(text, pos);
+                let mut target = pos;
+                for _ in 0..n {
+                    if target >= eol {
+                        break;
+                    }
+                    target = textarea.next_atomic_boundary(target).min(eol);
+                }
+                target
+            }
+            Motion::Up => vertical_target(text, pos, n, false),
+            Motion::Down => vertical_target(text, pos, n, true),
+            Motion::LineStart => line_start(text, pos),
+            Motion::FirstNonBlank => first_non_blank(text, pos),
+            Motion::LineEnd => {
+                let mut eol = line_end(text, pos);
+                for _ in 1..n {
+                    if eol >= text.len() {
+                        break;
+                    }
+                    eol = line_end(text, eol + 1);
+                }
+                eol
+            }
+            Motion::WordStart { big } => (0..n).fold(pos, |p, _| next_word_start(text, p, big)),
+            Motion::WordBackward { big } => (0..n).fold(pos, |p, _| prev_word_start(text, p, big)),
+            Motion::WordEnd { big } => (0..n).fold(pos, |p, _| next_word_end(text, p, big)),
+            Motion::FirstLine => first_non_blank(text, nth_line_start(text, n)),
+            Motion::LastLine => {
+                let bol = match count {
+                    Some(n) => nth_line_start(text, n),
+                    None => line_start(text, text.len()),
+                };
+                first_non_blank(text, bol)
+            }
+        }
+    }
+}
+
+/// Modal editing state owned by the composer when vim mode is enabled.
+#[derive(Debug)]
+pub(crate) struct VimState {
+    mode: VimMode,
+    pending: Pending,
+    count: Option<usize>,
+    visual_anchor: usize,
+    /// Kill buffer contents from the last linewise yank or delete, so `p`
+    /// can tell whole lines apart from a charwise kill.
+    linewise_register: Option<String>,
+}
+
+impl VimState {
+    pub(crate) fn new() -> Self {
+        Self {
+            mode: VimMode::Insert,
+            pending: Pending::None,
+            count: None,
+            visual_anchor: 0,
+            linewise_register: None,
+        }
+    }
+
+    pub(crate) fn mode(&self) -> VimMode {
+        self.mode
+    }
+
+    /// True when `Esc` belongs to vim (leaving insert or visual mode, or
+    /// cancelling a pending command) rather than to interrupt or backtrack.
+    pub(crate) fn captures_esc(&self) -> bool {
+        self.mode != VimMode::Normal || self.pending != Pending::None || self.count.is_some()
+    }
+
+    /// Return to insert mode, e.g. after the composer submits its text.
+    pub(crate) fn reset(&mut self, textarea: &mut TextArea) {
+        self.mode = VimMode::Insert;
+        self.clear_pending();
+        textarea.set_selection(None);
+    }
+
+    /// Handle `key` if it is a vim command in the current mode. Returns false
+    /// when the key should go through the composer's regular handling.
+    pub(crate) fn handle_key(&mut self, textarea: &mut TextArea, key: KeyEvent) -> bool {
+        if !matches!(key.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
+            return false;
+        }
+        match self.mode {
+            VimMode::Insert => self.handle_insert_key(textarea, key),
+            VimMode::Normal => self.handle_normal_key(textarea, key),
+            VimMode::Visual | VimMode::VisualLine => self.handle_visual_key(textarea, key),
+        }
+    }
+
+    fn handle_insert_key(&mut self, textarea: &mut TextArea, key: KeyEvent) -> bool {
+        if key.code != KeyCode::Esc {
+            return false;
+        }
+        self.mode = VimMode::Normal;
+        // Like vim, leaving insert mode steps back onto the last inserted character.
+        if textarea.cursor() > line_start(textarea.text(), textarea.cursor()) {
+            textarea.move_cursor_left();
+        }
+        true
+    }
+
+    fn handle_normal_key(&mut self, textarea: &mut TextArea, key: KeyEvent) -> bool {
+        let Some(ch) = command_char(key) else {
+            return match key.code {
+                KeyCode::Esc if self.captures_esc() => {
+                    self.clear_pending();
+                    true
+                }
+                KeyCode::Char('r') if key.modifiers == KeyModifiers::CONTROL => {
+                    self.clear_pending();
+                    textarea.redo();
+                    clamp_normal_cursor(textarea);
+                    true
+                }
+                _ => false,
+            };
+        };
+
+        if self.push_count_digit(ch) {
+            return true;
+        }
+        let count = self.count.take();
+        let pending = std::mem::replace(&mut self.pending, Pending::None);
+        match pending {
+            Pending::None => self.normal_command(textarea, ch, count),
+            Pending::Operator {
+                op,
+                count: op_count,
+            } => {
+                // `2d3w` deletes six words.
+                let count = match (op_count, count) {
+                    (None, None) => None,
+                    (a, b) => Some((a.unwrap_or(1) * b.unwrap_or(1)).min(MAX_COUNT)),
+                };
+                self.operator_command(textarea, op, ch, count);
+            }
+            Pending::TextObject { op, inner } => {
+                if let Some(op) = op
+                    && let Some(range) = text_object(textarea.text(), textarea.cursor(), ch, inner)
+                {
+                    self.apply_operator(textarea, op, range);
+                }
+            }
+            Pending::G { op, count: g_count } => {
+                if ch == 'g' {
+                    self.run_motion(textarea, op, Motion::FirstLine, g_count.or(count));
+                }
+            }
+            Pending::Replace => self.replace_chars(textarea, ch, count.unwrap_or(1)),
+        }
+        if self.mode == VimMode::Normal {
+            clamp_normal_cursor(textarea);
+        }
+        true
+    }
+
+    fn normal_command(&mut self, textarea: &mut TextArea, ch: char, count: Option<usize>) {
+        let n = count.unwrap_or(1);
+        let pos = textarea.cursor();
+        match ch {
+            'i' => self.enter_insert(textarea),
+            'a' => {
+                if pos < line_end(textarea.text(), pos) {
+                    textarea.move_cursor_right();
+                }
+                self.enter_insert(textarea);
+            }
+            'I' => {
+                textarea.set_cursor(first_non_blank(textarea.text(), pos));
+                self.enter_insert(textarea);
+            }
+            'A' => {
+                textarea.set_cursor(line_end(textarea.text(), pos));
+                self.enter_insert(textarea);
+            }
+            'o' => {
+                textarea.push_undo_state();
+                let eol = line_end(textarea.text(), pos);
+                textarea.insert_str_at(eol, "\n");
+                textarea.set_cursor(eol + 1);
+                self.mode = VimMode::Insert;
+            }
+            'O' => {
+                textarea.push_undo_state();
+                let bol = line_start(textarea.text(), pos);
+                textarea.insert_str_at(bol, "\n");
+                textarea.set_cursor(bol);
+                self.mode = VimMode::Insert;
+            }
+            'x' => {
+                let end = Motion::Right.target(textarea, Some(n));
+                self.apply_operator(textarea, Operator::Delete, TextRange::chars(pos..end));
+            }
+            'X' => {
+                let start = Motion::Left.target(textarea, Some(n));
+                self.apply_operator(textarea, Operator::Delete, TextRange::chars(start..pos));
+            }
+            's' => {
+                let end = Motion::Right.target(textarea, Some(n));
+                self.apply_operator(textarea, Operator::Change, TextRange::chars(pos..end));
+            }
+            'D' | 'C' => {
+                let op = if ch == 'D' {
+                    Operator::Delete
+                } else {
+                    Operator::Change
+                };
+                let eol = Motion::LineEnd.target(textarea, Some(n));
+                self.apply_operator(textarea, op, TextRange::chars(pos..eol));
+            }
+            'S' => self.operate_on_lines(textarea, Operator::Change, n),
+            'Y' => self.operate_on_lines(textarea, Operator::Yank, n),
+            'p' => self.put(textarea, false, n),
+            'P' => self.put(textarea, true, n),
+            'u' => {
+                for _ in 0..n {
+                    if !textarea.undo() {
+                        break;
+                    }
+                }
+            }
+            'r' => self.pending = Pending::Replace,
+            'v' => self.enter_visual(textarea, VimMode::Visual),
+            'V' => self.enter_visual(textarea, VimMode::VisualLine),
+            'g' => self.pending = Pending::G { op: None, count },
+            _ => {
+                if let Some(op) = Operator::from_char(ch) {
+                    self.pending = Pending::Operator { op, count };
+                } else if let Some(motion) = Motion::from_char(ch) {
+                    self.run_motion(textarea, None, motion, count);
+                }
+            }
+        }
+    }
+
+    fn operator_command(
+        &mut self,
+        textarea: &mut TextArea,
+        op: Operator,
+        ch: char,
+        count: Option<usize>,
+    ) {
+        // `dd`, `cc` and `yy` act on whole lines.
+        if Operator::from_char(ch) == Some(op) {
+            self.operate_on_lines(textarea, op, count.unwrap_or(1));
+            return;
+        }
+        match ch {
+            'i' | 'a' => {
+                self.pending = Pending::TextObject {
+                    op: Some(op),
+                    inner: ch == 'i',
+                };
+            }
+            'g' => {
+                self.pending = Pending::G {
+                    op: Some(op),
+                    count,
+                };
+            }
+            _ => {
+                if let Some(motion) = Motion::from_char(ch) {
+                    self.run_motion(textarea, Some(op), motion, count);
+                }
+            }
+        }
+    }
+
+    /// Move the cursor by `motion`, or apply `op` over the text it covers.
+    fn run_motion(
+        &mut self,
+        textarea: &mut TextArea,
+        op: Option<Operator>,
+        motion: Motion,
+        count: Option<usize>,
+    ) {
+        // `cw` behaves like `ce` when the cursor is on a word, as in vim.
+        let motion = match (op, motion) {
+            (Some(Operator::Change), Motion::WordStart { big })
+                if textarea.text()[textarea.cursor()..]
+                    .chars()
+                    .next()
+                    .is_some_and(|ch| !ch.is_whitespace()) =>
+            {
+                Motion::WordEnd { big }
+            }
+            _ => motion,
+        };
+        let target = motion.target(textarea, count);
+        let Some(op) = op else {
+            textarea.set_cursor(target);
+            return;
+        };
+
+        let text = textarea.text();
+        let pos = textarea.cursor();
+        let range = match motion.kind() {
+            MotionKind::Linewise => TextRange::lines(text, pos.min(target), pos.max(target)),
+            MotionKind::Inclusive => {
+                let start = pos.min(target);
+                let end = pos.max(target);
+                let end = if end < line_end(text, end) {
+                    textarea.next_atomic_boundary(end)
+                } else {
+                    end
+                };
+                TextRange::chars(start..end)
+            }
+            MotionKind::Exclusive => {
+                let mut end = target;
+                // `dw` on the last word of a line stops at the line end instead of
+                // joining the next line.
+                if matches!(motion, Motion::WordStart { .. })
+                    && end > pos
+                    && let Some(newline) = text[pos..end].rfind('\n')
+                    && pos + newline > pos
+                {
+                    end = pos + newline;
+                }
+                TextRange::chars(pos.min(end)..pos.max(end))
+            }
+        };
+        self.apply_operator(textarea, op, range);
+    }
+
+    fn operate_on_lines(&mut self, textarea: &mut TextArea, op: Operator, count: usize) {
+        let text = textarea.text();
+        let pos = textarea.cursor();
+        let end = vertical_target(text, pos, count.saturating_sub(1), true);
+        let range = TextRange::lines(text, pos, end);
+        self.apply_operator(textarea, op, range);
+    }
+
+    fn apply_operator(&mut self, textarea: &mut TextArea, op: Operator, range: TextRange) {
+        match range {
+            TextRange::Chars(range) => {
+                if range.is_empty() {
+                    if op == Operator::Change {
+                        self.enter_insert(textarea);
+                    }
+                    return;
+                }
+                match op {
+                    Operator::Yank => textarea.copy_range(range.clone()),
+                    Operator::Delete | Operator::Change => {
+                        textarea.push_undo_state();
+                        textarea.kill_range(range.clone());
+                    }
+                }
+                self.linewise_register = None;
+                textarea.set_cursor(range.start);
+                if op == Operator::Change {
+                    self.mode = VimMode::Insert;
+                }
+            }
+            TextRange::Lines { lines } => {
+                let register = format!("{}\n", &textarea.text()[lines.clone()]);
+                let text_len = textarea.text().len();
+                match op {
+                    Operator::Yank => {
+                        textarea.set_cursor(lines.start);
+                    }
+                    Operator::Delete => {
+                        textarea.push_undo_state();
+                        let range = if lines.end < text_len {
+                            lines.start..lines.end + 1
+                        } else if lines.start > 0 {
+                            lines.start - 1..lines.end
+                        } else {
+                            lines.clone()
+                        };
+                        textarea.kill_range(range);
+                        let text = textarea.text();
+                        let bol = line_start(text, lines.start.min(text.len()));
+                        textarea.set_cursor(first_non_blank(text, bol));
+                    }
+                    Operator::Change => {
+                        textarea.push_undo_state();
+                        textarea.kill_range(lines.clone());
+                        textarea.set_cursor(lines.start);
+                        self.mode = VimMode::Insert;
+                    }
+                }
+                textarea.set_kill_buffer(&register);
+                self.linewise_register = Some(register);
+            }
+        }
+    }
+
+    fn put(&mut self, textarea: &mut TextArea, before: bool, count: usize) {
+        let register = textarea.kill_buffer().to_string();
+        if register.is_empty() {
+            return;
+        }
+        textarea.push_undo_state();
+        let pos = textarea.cursor();
+        let bol = line_start(textarea.text(), pos);
+        let eol = line_end(textarea.text(), pos);
+        let text_len = textarea.text().len();
+        let content = register.repeat(count);
+        if self.linewise_register.as_deref() == Some(register.as_str()) {
+            let line_start_pos = if before {
+                textarea.insert_str_at(bol, &content);
+                bol
+            } else if eol < text_len {
+                textarea.insert_str_at(eol + 1, &content);
+                eol + 1
+            } else {
+                // The last line has no trailing newline to put after.
+                let body = content.strip_suffix('\n').unwrap_or(&content);
+                textarea.insert_str_at(eol, &format!("\n{body}"));
+                eol + 1
+            };
+            textarea.set_cursor(first_non_blank(textarea.text(), line_start_pos));
+        } else {
+            let at = if before || pos >= eol {
+                pos
+            } else {
+                textarea.next_atomic_boundary(pos)
+            };
+            textarea.insert_str_at(at, &content);
+            // Leave the cursor on the last put character.
+            textarea.set_cursor(at + content.len());
+            textarea.move_cursor_left();
+        }
+    }
+
+    fn replace_chars(&mut self, textarea: &mut TextArea, ch: char, count: usize) {
+        let pos = textarea.cursor();
+        let end = Motion::Right.target(textarea, Some(count));
+        let replaced = textarea.text()[pos..end].graphemes(true).count();
+        if replaced < count || ch == '\n' {
+            return;
+        }
+        textarea.push_undo_state();
+        let replacement = ch.to_string().repeat(count);
+        textarea.replace_range(pos..end, &replacement);
+        textarea.set_cursor(pos + replacement.len());
+        textarea.move_cursor_left();
+    }
+
+    fn handle_visual_key(&mut self, textarea: &mut TextArea, key: KeyEvent) -> bool {
+        let Some(ch) = command_char(key) else {
+            if key.code == KeyCode::Esc {
+                self.exit_visual(textarea);
+                return true;
+            }
+            return false;
+        };
+
+        if self.push_count_digit(ch) {
+            return true;
+        }
+        let count = self.count.take();
+        let pending = std::mem::replace(&mut self.pending, Pending::None);
+        match pending {
+            Pending::TextObject { inner, .. } => {
+                if let Some(TextRange::Chars(range)) =
+                    text_object(textarea.text(), textarea.cursor(), ch, inner)
+                    && !range.is_empty()
+                {
+                    self.visual_anchor = range.start;
+                    textarea.set_cursor(textarea.prev_atomic_boundary(range.end));
+                }
+            }
+            Pending::G { count: g_count, .. } => {
+                if ch == 'g' {
+                    self.run_motion(textarea, None, Motion::FirstLine, g_count.or(count));
+                }
+            }
+            Pending::None | Pending::Operator { .. } | Pending::Replace => {
+                self.visual_command(textarea, ch, count)
+            }
+        }
+        match self.mode {
+            VimMode::Visual | VimMode::VisualLine => {
+                clamp_normal_cursor(textarea);
+                textarea.set_selection(Some(self.selection(textarea).range()));
+            }
+            VimMode::Normal => clamp_normal_cursor(textarea),
+            VimMode::Insert => {}
+        }
+        true
+    }
+
+    fn visual_command(&mut self, textarea: &mut TextArea, ch: char, count: Option<usize>) {
+        match ch {
+            'v' | 'V' => {
+                let mode = if ch == 'v' {
+                    VimMode::Visual
+                } else {
+                    VimMode::VisualLine
+                };
+                if self.mode == mode {
+                    self.exit_visual(textarea);
+                } else {
+                    self.mode = mode;
+                }
+            }
+            'o' => {
+                let cursor = textarea.cursor();
+                textarea.set_cursor(self.visual_anchor);
+                self.visual_anchor = cursor;
+            }
+            'i' | 'a' => {
+                self.pending = Pending::TextObject {
+                    op: None,
+                    inner: ch == 'i',
+                };
+            }
+            'g' => self.pending = Pending::G { op: None, count },
+            'd' | 'x' | 'y' | 'c' | 's' => {
+                let op = match ch {
+                    'y' => Operator::Yank,
+                    'c' | 's' => Operator::Change,
+                    _ => Operator::Delete,
+                };
+                let selection = self.selection(textarea);
+                self.exit_visual(textarea);
+                self.apply_operator(textarea, op, selection);
+            }
+            _ => {
+                if let Some(motion) = Motion::from_char(ch) {
+                    self.run_motion(textarea, None, motion, count);
+                }
+            }
+        }
+    }
+
+    fn selection(&self, textarea: &TextArea) -> TextRange {
+        let text = textarea.text();
+        let start = self.visual_anchor.min(textarea.cursor()).min(text.len());
+        let end = self.visual_anchor.max(textarea.cursor()).min(text.len());
+        if self.mode == VimMode::VisualLine {
+            return TextRange::lines(text, start, end);
+        }
+        let end = if end < text.len() {
+            textarea.next_atomic_boundary(end)
+        } else {
+            end
+        };
+        TextRange::chars(start..end)
+    }
+
+    fn enter_visual(&mut self, textarea: &mut TextArea, mode: VimMode) {
+        self.mode = mode;
+        self.visual_anchor = textarea.cursor();
+        textarea.set_selection(Some(self.selection(textarea).range()));
+    }
+
+    fn exit_visual(&mut self, textarea: &mut TextArea) {
+        self.mode = VimMode::Normal;
+        self.clear_pending();
+        textarea.set_selection(None);
+    }
+
+    fn enter_insert(&mut self, textarea: &mut TextArea) {
+        // One undo step covers everything typed before returning to normal mode.
+        textarea.push_undo_state();
+        self.mode = VimMode::Insert;
+    }
+
+    /// Accumulate a typed count. `0` only counts once a count has started;
+    /// otherwise it is the line-start motion.
+    fn push_count_digit(&mut self, ch: char) -> bool {
+        if self.pending == Pending::Replace {
+            return false;
+        }
+        let Some(digit) = ch.to_digit(10) else {
+            return false;
+        };
+        if digit == 0 && self.count.is_none() {
+            return false;
+        }
+        let count = self.count.unwrap_or(0);
+        self.count = Some((count * 10 + digit as usize).min(MAX_COUNT));
+        true
+    }
+
+    fn clear_pending(&mut self) {
+        self.pending = Pending::None;
+        self.count = None;
+    }
+}
+
+/// Text covered by an operator or selection.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum TextRange {
+    Chars(Range<usize>),
+    /// Whole lines from the start of the first to the end of the last,
+    /// excluding the final newline.
+    Lines {
+        lines: Range<usize>,
+    },
+}
+
+impl TextRange {
+    fn chars(range: Range<usize>) -> Self {
+        TextRange::Chars(range)
+    }
+
+    fn lines(text: &str, start: usize, end: usize) -> Self {
+        TextRange::Lines {
+            lines: line_start(text, start)..line_end(text, end),
+        }
+    }
+
+    fn range(&self) -> Range<usize> {
+        match self {
+            TextRange::Chars(range) => range.clone(),
+            TextRange::Lines { lines } => lines.clone(),
+        }
+    }
+}
+
+/// The character a key stands for in normal and visual mode. Arrow and
+/// editing keys map to their vim equivalents; chords with Ctrl or Alt are
+/// left to the composer.
+fn command_char(key: KeyEvent) -> Option<char> {
+    if key
+        .modifiers
+        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    {
+        return None;
+    }
+    match key.code {
+        KeyCode::Char(ch) => Some(ch),
+        KeyCode::Left | KeyCode::Backspace => Some('h'),
+        KeyCode::Right => Some('l'),
+        KeyCode::Home => Some('0'),
+        KeyCode::End => Some('$'),
+        KeyCode::Delete => Some('x'),
+        _ => None,
+    }
+}
+
+fn clamp_normal_cursor(textarea: &mut TextArea) {
+    let pos = textarea.cursor();
+    let text = textarea.text();
+    // Normal mode keeps the cursor on a character, never past the end of a non-empty line.
+    if pos == line_end(text, pos) && pos > line_start(text, pos) {
+        textarea.set_cursor(textarea.prev_atomic_boundary(pos));
+    }
+}
+
+fn line_start(text: &str, pos: usize) -> usize {
+    text[..pos].rfind('\n').map_or(0, |idx| idx + 1)
+}
+
+fn line_end(text: &str, pos: usize) -> usize {
+    text[pos..].find('\n').map_or(text.len(), |idx| pos + idx)
+}
+
+fn first_non_blank(text: &str, pos: usize) -> usize {
+    let bol = line_start(text, pos);
+    let eol = line_end(text, pos);
+    text[bol..eol]
+        .find(|ch: char| !ch.is_whitespace())
+        .map_or(eol, |idx| bol + idx)
+}
+
+/// Start of the 1-based line `n`, or of the last line when there are fewer.
+fn nth_line_start(text: &str, n: usize) -> usize {
+    let mut bol = 0;
+    for _ in 1..n {
+        let eol = line_end(text, bol);
+        if eol >= text.len() {
+            break;
+        }
+        bol = eol + 1;
+    }
+    bol
+}
+
+/// Move `lines` logical lines up or down, keeping the display column where
+/// the target line is long enough.
+fn vertical_target(text: &str, pos: usize, lines: usize, down: bool) -> usize {
+    let mut bol = line_start(text, pos);
+    let col = text[bol..pos].width();
+    for _ in 0..lines {
+        if down {
+            let eol = line_end(text, bol);
+            if eol >= text.len() {
+                break;
+            }
+            bol = eol + 1;
+        } else {
+            if bol == 0 {
+                break;
+            }
+            bol = line_start(text, bol - 1);
+        }
+    }
+    let eol = line_end(text, bol);
+    let mut width = 0;
+    for (idx, grapheme) in text[bol..eol].grapheme_indices(true) {
+        width += grapheme.width();
+        if width > col {
+            return bol + idx;
+        }
+    }
+    eol
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CharClass {
+    Blank,
+    Word,
+    Punctuation,
+}
+
+fn char_class(ch: char, big: bool) -> CharClass {
+    if ch.is_whitespace() {
+        CharClass::Blank
+    } else if big || ch.is_alphanumeric() || ch == '_' {
+        CharClass::Word
+    } else {
+        CharClass::Punctuation
+    }
+}
+
+fn next_word_start(text: &str, pos: usize, big: bool) -> usize {
+    let mut chars = text[pos..].char_indices().map(|(idx, ch)| (pos + idx, ch));
+    let Some((_, first)) = chars.next() else {
+        return text.len();
+    };
+    let start_class = char_class(first, big);
+    let mut seen_blank = start_class == CharClass::Blank;
+    let mut prev = first;
+    for (idx, ch) in chars {
+        let class = char_class(ch, big);
+        if class == CharClass::Blank {
+            // An empty line counts as a word of its own.
+            if ch == '\n' && prev == '\n' {
+                return idx;
+            }
+            seen_blank = true;
+        } else if seen_blank || class != start_class {
+            return idx;
+        }
+        prev = ch;
+    }
+    text.len()
+}
+
+fn next_word_end(text: &str, pos: usize, big: bool) -> usize {
+    let mut chars = text[pos..]
+        .char_indices()
+        .map(|(idx, ch)| (pos + idx, ch))
+        .skip(1)
+        .skip_while(|(_, ch)| ch.is_whitespace());
+    let Some((mut end, first)) = chars.next() else {
+        return pos;
+    };
+    let class = char_class(first, big);
+    for (idx, ch) in chars {
+        if char_class(ch, big) != class {
+            break;
+        }
+        end = idx;
+    }
+    end
+}
+
+fn prev_word_start(text: &str, pos: usize, big: bool) -> usize {
+    let mut chars = text[..pos]
+        .char_indices()
+        .rev()
+        .skip_while(|(_, ch)| ch.is_whitespace());
+    let Some((mut start, first)) = chars.next() else {
+        return 0;
+    };
+    let class = char_class(first, big);
+    for (idx, ch) in chars {
+        if char_class(ch, big) != class {
+            break;
+        }
+        start = idx;
+    }
+    start
+}
+
+/// Range selected by the text object `object` (`w`, `W`, quotes or
+/// brackets), `inner` for `i` and outer for `a`.
+fn text_object(text: &str, pos: usize, object: char, inner: bool) -> Option<TextRange> {
+    let range = match object {
+        'w' => word_object(text, pos, false, inner),
+        'W' => word_object(text, pos, true, inner),
+        '"' | '\'' | '`' => quote_object(text, pos, object, inner),
+        '(' | ')' | 'b' => bracket_object(text, pos, '(', ')', inner),
+        '[' | ']' => bracket_object(text, pos, '[', ']', inner),
+        '{' | '}' | 'B' => bracket_object(text, pos, '{', '}', inner),
+        '<' | '>' => bracket_object(text, pos, '<', '>', inner),
+        _ => None,
+    }?;
+    Some(TextRange::chars(range))
+}
+
+fn word_object(text: &str, pos: usize, big: bool, inner: bool) -> Option<Range<usize>> {
+    let bol = line_start(text, pos);
+    let eol = line_end(text, pos);
+    let ch = text[pos..eol].chars().next()?;
+    let class = char_class(ch, big);
+    let same_class = |ch: char| char_class(ch, big) == class && ch != '\n';
+    let start = text[bol..pos]
+        .char_indices()
+        .rev()
+        .take_while(|(_, ch)| same_class(*ch))
+        .last()
+        .map_or(pos, |(idx, _)| bol + idx);
+    let end = text[pos..eol]
+        .char_indices()
+        .find(|(_, ch)| !same_class(*ch))
+        .map_or(eol, |(idx, _)| pos + idx);
+    if inner {
+        return Some(start..end);
+    }
+    let is_blank = |ch: char| ch.is_whitespace() && ch != '\n';
+    if class == CharClass::Blank {
+        // `aw` on blanks takes the blanks and the following word.
+        let word_class = text[end..eol].chars().next().map(|ch| char_class(ch, big));
+        let word_end = text[end..eol]
+            .char_indices()
+            .find(|(_, ch)| Some(char_class(*ch, big)) != word_class)
+            .map_or(eol, |(idx, _)| end + idx);
+        return Some(start..word_end);
+    }
+    let trailing = text[end..eol]
+        .char_indices()
+        .find(|(_, ch)| !is_blank(*ch))
+        .map_or(eol, |(idx, _)| end + idx);
+    if trailing > end {
+        return Some(start..trailing);
+    }
+    // Without trailing blanks, `aw` takes the leading ones instead.
+    let leading = text[bol..start]
+        .char_indices()
+        .rev()
+        .take_while(|(_, ch)| is_blank(*ch))
+        .last()
+        .map_or(start, |(idx, _)| bol + idx);
+    Some(leading..end)
+}
+
+fn quote_object(text: &str, pos: usize, quote: char, inner: bool) -> Option<Range<usize>> {
+    let bol = line_start(text, pos);
+    let eol = line_end(text, pos);
+    let line = &text[bol..eol];
+    let mut quotes = Vec::new();
+    let mut escaped = false;
+    for (idx, ch) in line.char_indices() {
+        if ch == quote && !escaped {
+            quotes.push(bol + idx);
+        }
+        escaped = ch == '\\' && !escaped;
+    }
+    let (open, close) = quotes
+        .chunks_exact(2)
+        .map(|pair| (pair[0], pair[1]))
+        // The pair around the cursor, or else the first pair after it.
+        .find(|&(_, close)| pos <= close)?;
+    let quote_len = quote.len_utf8();
+    if inner {
+        Some(open + quote_len..close)
+    } else {
+        Some(open..close + quote_len)
+    }
+}
+
+fn bracket_object(
+    text: &str,
+    pos: usize,
+    open: char,
+    close: char,
+    inner: bool,
+) -> Option<Range<usize>> {
+    // Find the unmatched opening bracket at or before the cursor.
+    let mut depth = 0usize;
+    let mut open_pos = None;
+    let at_cursor = text[pos..].chars().next();
+    let search_end = if at_cursor == Some(open) {
+        pos + open.len_utf8()
+    } else {
+        pos
+    };
+    for (idx, ch) in text[..search_end].char_indices().rev() {
+        if ch == close {
+            depth += 1;
+        } else if ch == open {
+            if depth == 0 {
+                open_pos = Some(idx);
+                break;
+            }
+            depth -= 1;
+        }
+    }
+    let open_pos = open_pos?;
+    let mut depth = 0usize;
+    let after_open = open_pos + open.len_utf8();
+    let close_pos = text[after_open..].char_indices().find_map(|(idx, ch)| {
+        if ch == open {
+            depth += 1;
+        } else if ch == close {
+            if depth == 0 {
+                return Some(after_open + idx);
+            }
+            depth -= 1;
+        }
+        None
+    })?;
+    if inner {
+        Some(after_open..close_pos)
+    } else {
+        Some(open_pos..close_pos + close.len_utf8())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn vim_with(text: &str, cursor: usize) -> (VimState, TextArea) {
+        let mut textarea = TextArea::new();
+        textarea.insert_str(text);
+        textarea.set_cursor(cursor);
+        let mut vim = VimState::new();
+        vim.handle_key(&mut textarea, KeyEvent::from(KeyCode::Esc));
+        textarea.set_cursor(cursor);
+        (vim, textarea)
+    }
+
+    fn type_keys(vim: &mut VimState, textarea: &mut TextArea, keys: &str) {
+        for ch in keys.chars() {
+            let key = match ch {
+                '\u{1b}' => KeyEvent::from(KeyCode::Esc),
+                ch => KeyEvent::from(KeyCode::Char(ch)),
+            };
+            if !vim.handle_key(textarea, key) {
+                textarea.input(key);
+            }
+        }
+    }
+
+    #[test]
+    fn esc_leaves_insert_mode_on_last_character() {
+        let mut textarea = TextArea::new();
+        let mut vim = VimState::new();
+        type_keys(&mut vim, &mut textarea, "hello\u{1b}");
+        assert_eq!(vim.mode(), VimMode::Normal);
+        assert_eq!(textarea.cursor(), 4);
+        assert!(!vim.captures_esc());
+        assert!(!vim.handle_key(&mut textarea, KeyEvent::from(KeyCode::Esc)));
+    }
+
+    #[test]
+    fn word_and_line_motions() {
+        let (mut vim, mut textarea) = vim_with("foo.bar baz\n  qux", 0);
+        type_keys(&mut vim, &mut textarea, "w");
+        assert_eq!(textarea.cursor(), 3);
+        type_keys(&mut vim, &mut textarea, "W");
+        assert_eq!(textarea.cursor(), 8);
+        type_keys(&mut vim, &mut textarea, "e");
+        assert_eq!(textarea.cursor(), 10);
+        type_keys(&mut vim, &mut textarea, "2b");
+        assert_eq!(textarea.cursor(), 4);
+        type_keys(&mut vim, &mut textarea, "$");
+        assert_eq!(textarea.cursor(), 10);
+        type_keys(&mut vim, &mut textarea, "j^");
+        assert_eq!(textarea.cursor(), 14);
+        type_keys(&mut vim, &mut textarea, "gg");
+        assert_eq!(textarea.cursor(), 0);
+        type_keys(&mut vim, &mut textarea, "G");
+        assert_eq!(textarea.cursor(), 14);
+    }
+
+    #[test]
+    fn operators_with_motions_and_counts() {
+        let (mut vim, mut textarea) = vim_with("one two three four", 0);
+        type_keys(&mut vim, &mut textarea, "dw");
+        assert_eq!(textarea.text(), "two three four");
+        type_keys(&mut vim, &mut textarea, "2dw");
+        assert_eq!(textarea.text(), "four");
+        type_keys(&mut vim, &mut textarea, "cwfive\u{1b}");
+        assert_eq!(textarea.text(), "five");
+        assert_eq!(vim.mode(), VimMode::Normal);
+        type_keys(&mut vim, &mut textarea, "0d$");
+        assert_eq!(textarea.text(), "");
+    }
+
+    #[test]
+    fn linewise_yank_and_put() {
+        let (mut vim, mut textarea) = vim_with("alpha\nbeta", 0);
+        type_keys(&mut vim, &mut textarea, "yyjp");
+        assert_eq!(textarea.text(), "alpha\nbeta\nalpha");
+        assert_eq!(textarea.cursor(), 11);
+        type_keys(&mut vim, &mut textarea, "ggddP");
+        assert_eq!(textarea.text(), "alpha\nbeta\nalpha");
+        type_keys(&mut vim, &mut textarea, "Gdd");
+        assert_eq!(textarea.text(), "alpha\nbeta");
+        assert_eq!(textarea.kill_buffer(), "alpha\n");
+    }
+
+    #[test]
+    fn charwise_put_shares_the_kill_buffer() {
+        let (mut vim, mut textarea) = vim_with("abc", 0);
+        type_keys(&mut vim, &mut textarea, "xp");
+        assert_eq!(textarea.text(), "bac");
+        // Emacs-style kills land in the same buffer.
+        textarea.set_cursor(0);
+        textarea.kill_to_end_of_line();
+        type_keys(&mut vim, &mut textarea, "P");
+        assert_eq!(textarea.text(), "bac");
+    }
+
+    #[test]
+    fn text_objects() {
+        let (mut vim, mut textarea) = vim_with(r#"call(foo, "a b") end"#, 11);
+        type_keys(&mut vim, &mut textarea, "ci\"x\u{1b}");
+        assert_eq!(textarea.text(), r#"call(foo, "x") end"#);
+        type_keys(&mut vim, &mut textarea, "da(");
+        assert_eq!(textarea.text(), "call end");
+        type_keys(&mut vim, &mut textarea, "0daw");
+        assert_eq!(textarea.text(), "end");
+    }
+
+    #[test]
+    fn visual_mode_selects_and_deletes() {
+        let (mut vim, mut textarea) = vim_with("one two\nthree", 0);
+        type_keys(&mut vim, &mut textarea, "vey");
+        assert_eq!(textarea.kill_buffer(), "one");
+        assert_eq!(vim.mode(), VimMode::Normal);
+        type_keys(&mut vim, &mut textarea, "Vjd");
+        assert_eq!(textarea.text(), "");
+        type_keys(&mut vim, &mut textarea, "u");
+        assert_eq!(textarea.text(), "one two\nthree");
+    }
+
+    #[test]
+    fn undo_groups_insert_sessions() {
+        let (mut vim, mut textarea) = vim_with("a", 0);
+        type_keys(&mut vim, &mut textarea, "Abc\u{1b}x");
+        assert_eq!(textarea.text(), "ab");
+        type_keys(&mut vim, &mut textarea, "u");
+        assert_eq!(textarea.text(), "abc");
+        type_keys(&mut vim, &mut textarea, "u");
+        assert_eq!(textarea.text(), "a");
+        vim.handle_key(
+            &mut textarea,
+            KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL),
+        );
+        assert_eq!(textarea.text(), "abc");
+    }
+
+    #[test]
+    fn normal_mode_passes_through_submit_and_history_keys() {
+        let (mut vim, mut textarea) = vim_with("text", 0);
+        assert!(!vim.handle_key(&mut textarea, KeyEvent::from(KeyCode::Enter)));
+        assert!(!vim.handle_key(&mut textarea, KeyEvent::from(KeyCode::Up)));
+        assert!(vim.handle_key(&mut textarea, KeyEvent::from(KeyCode::Char('d'))));
+        assert!(vim.captures_esc());
+        assert!(vim.handle_key(&mut textarea, KeyEvent::from(KeyCode::Esc)));
+        assert_eq!(textarea.text(), "text");
+    }
+}
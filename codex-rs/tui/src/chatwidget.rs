@@ -38,6 +38,7 @@ use std::time::Duration;
 use std::time::Instant;
 
 use crate::bottom_pane::StatusLineItem;
+use crate::bottom_pane::StatusLineSegment;
 use crate::bottom_pane::StatusLineSetupView;
 use crate::i18n::tr;
 use crate::i18n::use_zh_cn;
@@ -45,6 +46,8 @@ use crate::status::RateLimitWindowDisplay;
 use crate::status::format_directory_display;
 use crate::status::format_tokens_compact;
 use crate::status::rate_limit_snapshot_display_for_limit;
+use crate::status_line_command::StatusLineCommandState;
+use crate::status_line_command::StatusLineCommandTrigger;
 use crate::text_formatting::proper_join;
 use crate::version::CODEX_CLI_VERSION;
 use codex_app_server_protocol::ConfigLayerSource;
@@ -52,6 +55,7 @@ use codex_backend_client::Client as BackendClient;
 use codex_chatgpt::connectors;
 use codex_core::config::Config;
 use codex_core::config::ConstraintResult;
+use codex_core::config::types::EditingMode;
 use codex_core::config::types::Notifications;
 use codex_core::config::types::WindowsSandboxModeToml;
 use codex_core::config_loader::ConfigLayerStackOrdering;
@@ -159,6 +163,7 @@ const PLAN_IMPLEMENTATION_YES: &str = "Yes, implement this plan";
 const PLAN_IMPLEMENTATION_NO: &str = "No, stay in Plan mode";
 const PLAN_IMPLEMENTATION_CODING_MESSAGE: &str = "Implement the plan.";
 const CONNECTORS_SELECTION_VIEW_ID: &str = "connectors-selection";
+const REVIEW_PRESETS_VIEW_ID: &str = "review-presets";
 
 use crate::app_event::AppEvent;
 use crate::app_event::ConnectorsSnapshot;
@@ -542,6 +547,8 @@ pub(crate) struct ChatWidget {
     mcp_startup_status: Option<HashMap<String, McpStartupStatus>>,
     connectors_cache: ConnectorsCacheState,
     connectors_prefetch_in_flight: bool,
+    // Paths that reviews started from the /review popup are restricted to.
+    review_paths: Vec<PathBuf>,
     // Queue of interruptive UI events deferred during an active write cycle
     interrupts: InterruptManager,
     // Accumulates the current reasoning block text to extract a header
@@ -629,6 +636,10 @@ pub(crate) struct ChatWidget {
     status_line_branch_pending: bool,
     // True once we've attempted a branch lookup for the current CWD.
     status_line_branch_lookup_complete: bool,
+    // Cached outputs and in-flight runs of command-backed status line segments.
+    status_line_commands: StatusLineCommandState,
+    // Latest aggregated diff of the current or last turn, offered by the `/diff` overlay.
+    last_turn_diff: Option<String>,
     external_editor_state: ExternalEditorState,
 }
 
@@ -892,8 +903,8 @@ impl ChatWidget {
     ///
     /// This method is the status-line orchestrator: it parses configured item identifiers,
     /// warns once per session about invalid items, updates whether status-line mode is enabled,
-    /// schedules async git-branch lookup when needed, and renders only values that are currently
-    /// available.
+    /// schedules async git-branch lookup and first runs of command segments when needed, and
+    /// renders only values that are currently available.
     ///
     /// The omission behavior is intentional. If selected items are unavailable (for example before
     /// a session id exists or before branch lookup completes), those items are skipped without
@@ -918,11 +929,20 @@ impl ChatWidget {
             );
             self.on_warning(message);
         }
-        if !items.contains(&StatusLineItem::GitBranch) {
+        let git_branch = StatusLineSegment::Item(StatusLineItem::GitBranch);
+        if !items.contains(&git_branch) {
             self.status_line_branch = None;
             self.status_line_branch_pending = false;
             self.status_line_branch_lookup_complete = false;
         }
+        let command_names = items
+            .iter()
+            .filter_map(|item| match item {
+                StatusLineSegment::Command(name) => Some(name.as_str()),
+                StatusLineSegment::Item(_) => None,
+            })
+            .collect::<HashSet<_>>();
+        self.status_line_commands.retain(&command_names);
         let enabled = !items.is_empty();
         self.bottom_pane.set_status_line_enabled(enabled);
         if !enabled {
@@ -933,9 +953,10 @@ impl ChatWidget {
         let cwd = self.status_line_cwd().to_path_buf();
         self.sync_status_line_branch_state(&cwd);
 
-        if items.contains(&StatusLineItem::GitBranch) && !self.status_line_branch_lookup_complete {
-            self.request_status_line_branch(cwd);
+        if items.contains(&git_branch) && !self.status_line_branch_lookup_complete {
+            self.request_status_line_branch(cwd.clone());
         }
+        self.request_status_line_commands(&items, StatusLineCommandTrigger::FirstRun, &cwd);
 
         let mut parts = Vec::new();
         for item in items {
@@ -964,7 +985,7 @@ impl ChatWidget {
     ///
     /// An empty selection is normalized to `None` so the status line is fully disabled and the
     /// behavior matches an unset `tui.status_line` config value.
-    pub(crate) fn setup_status_line(&mut self, items: Vec<StatusLineItem>) {
+    pub(crate) fn setup_status_line(&mut self, items: Vec<StatusLineSegment>) {
         tracing::info!("status line setup confirmed with items: {items:#?}");
         let ids = items.iter().map(ToString::to_string).collect::<Vec<_>>();
         self.config.tui_status_line = if ids.is_empty() { None } else { Some(ids) };
@@ -985,17 +1006,57 @@ impl ChatWidget {
         self.status_line_branch_lookup_complete = true;
     }
 
-    /// Forces a new git-branch lookup when `GitBranch` is part of the configured status line.
+    /// Stores the result of a command segment run and schedules its next interval run.
+    pub(crate) fn set_status_line_command_output(&mut self, name: &str, output: Option<String>) {
+        self.status_line_commands.set_output(name, output);
+        let (items, _) = self.status_line_items_with_invalids();
+        let segment = StatusLineSegment::Command(name.to_string());
+        if !items.contains(&segment) {
+            return;
+        }
+        let cwd = self.status_line_cwd().to_path_buf();
+        self.request_status_line_commands(
+            std::slice::from_ref(&segment),
+            StatusLineCommandTrigger::Interval,
+            &cwd,
+        );
+    }
+
+    /// Forces a new git-branch lookup when `GitBranch` is part of the configured status line,
+    /// and re-runs command segments that refresh on turn completion.
     fn request_status_line_branch_refresh(&mut self) {
         let (items, _) = self.status_line_items_with_invalids();
-        if items.is_empty() || !items.contains(&StatusLineItem::GitBranch) {
+        if items.is_empty() {
             return;
         }
         let cwd = self.status_line_cwd().to_path_buf();
+        self.request_status_line_commands(&items, StatusLineCommandTrigger::TurnComplete, &cwd);
+        if !items.contains(&StatusLineSegment::Item(StatusLineItem::GitBranch)) {
+            return;
+        }
         self.sync_status_line_branch_state(&cwd);
         self.request_status_line_branch(cwd);
     }
 
+    /// Asks each command segment in `items` to run for `trigger`.
+    fn request_status_line_commands(
+        &mut self,
+        items: &[StatusLineSegment],
+        trigger: StatusLineCommandTrigger,
+        cwd: &Path,
+    ) {
+        for item in items {
+            let StatusLineSegment::Command(name) = item else {
+                continue;
+            };
+            let Some(command) = self.config.tui_status_line_commands.get(name) else {
+                continue;
+            };
+            self.status_line_commands
+                .request(name, command, trigger, cwd, &self.app_event_tx);
+        }
+    }
+
     fn collect_runtime_metrics_delta(&mut self) {
         if let Some(delta) = self.otel_manager.runtime_metrics_summary() {
             self.apply_runtime_metrics_delta(delta);
@@ -2162,9 +2223,15 @@ impl ChatWidget {
 
     fn on_turn_diff(&mut self, unified_diff: String) {
         debug!("TurnDiffEvent: {unified_diff}");
+        self.last_turn_diff = (!unified_diff.trim().is_empty()).then_some(unified_diff);
         self.refresh_status_line();
     }
 
+    /// The latest turn diff, if the session has produced one.
+    pub(crate) fn last_turn_diff(&self) -> Option<&str> {
+        self.last_turn_diff.as_deref()
+    }
+
     fn on_deprecation_notice(&mut self, event: DeprecationNoticeEvent) {
         let DeprecationNoticeEvent { summary, details } = event;
         self.add_to_history(history_cell::new_deprecation_notice(summary, details));
@@ -2689,6 +2756,7 @@ impl ChatWidget {
             agent_turn_running: false,
             mcp_startup_status: None,
             connectors_cache: ConnectorsCacheState::default(),
+            review_paths: Vec::new(),
             connectors_prefetch_in_flight: false,
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
@@ -2728,6 +2796,8 @@ impl ChatWidget {
             status_line_branch_cwd: None,
             status_line_branch_pending: false,
             status_line_branch_lookup_complete: false,
+            status_line_commands: StatusLineCommandState::default(),
+            last_turn_diff: None,
             external_editor_state: ExternalEditorState::Closed,
         };
 
@@ -2742,6 +2812,9 @@ impl ChatWidget {
                 .as_ref()
                 .is_some_and(|items| !items.is_empty()),
         );
+        widget
+            .bottom_pane
+            .set_vim_mode_enabled(widget.config.tui_editing_mode == EditingMode::Vim);
         widget.bottom_pane.set_collaboration_modes_enabled(
             widget.config.features.enabled(Feature::CollaborationModes),
         );
@@ -2859,6 +2932,7 @@ impl ChatWidget {
             agent_turn_running: false,
             mcp_startup_status: None,
             connectors_cache: ConnectorsCacheState::default(),
+            review_paths: Vec::new(),
             connectors_prefetch_in_flight: false,
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
@@ -2898,6 +2972,8 @@ impl ChatWidget {
             status_line_branch_cwd: None,
             status_line_branch_pending: false,
             status_line_branch_lookup_complete: false,
+            status_line_commands: StatusLineCommandState::default(),
+            last_turn_diff: None,
             external_editor_state: ExternalEditorState::Closed,
         };
 
@@ -2912,6 +2988,9 @@ impl ChatWidget {
                 .as_ref()
                 .is_some_and(|items| !items.is_empty()),
         );
+        widget
+            .bottom_pane
+            .set_vim_mode_enabled(widget.config.tui_editing_mode == EditingMode::Vim);
         widget.bottom_pane.set_collaboration_modes_enabled(
             widget.config.features.enabled(Feature::CollaborationModes),
         );
@@ -3018,6 +3097,7 @@ impl ChatWidget {
             agent_turn_running: false,
             mcp_startup_status: None,
             connectors_cache: ConnectorsCacheState::default(),
+            review_paths: Vec::new(),
             connectors_prefetch_in_flight: false,
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
@@ -3057,6 +3137,8 @@ impl ChatWidget {
             status_line_branch_cwd: None,
             status_line_branch_pending: false,
             status_line_branch_lookup_complete: false,
+            status_line_commands: StatusLineCommandState::default(),
+            last_turn_diff: None,
             external_editor_state: ExternalEditorState::Closed,
         };
 
@@ -3071,6 +3153,9 @@ impl ChatWidget {
                 .as_ref()
                 .is_some_and(|items| !items.is_empty()),
         );
+        widget
+            .bottom_pane
+            .set_vim_mode_enabled(widget.config.tui_editing_mode == EditingMode::Vim);
         widget.bottom_pane.set_collaboration_modes_enabled(
             widget.config.features.enabled(Feature::CollaborationModes),
         );
@@ -3329,6 +3414,7 @@ impl ChatWidget {
                 self.app_event_tx.send(AppEvent::CodexOp(Op::Compact));
             }
             SlashCommand::Review => {
+                self.review_paths.clear();
                 self.open_review_popup();
             }
             SlashCommand::Rename => {
@@ -3468,6 +3554,9 @@ impl ChatWidget {
                     tx.send(AppEvent::DiffResult(text));
                 });
             }
+            SlashCommand::Checkpoints => {
+                self.app_event_tx.send(AppEvent::OpenCheckpoints);
+            }
             SlashCommand::Mention => {
                 self.insert_str("@");
             }
@@ -3634,6 +3723,7 @@ impl ChatWidget {
                             instructions: prepared_args,
                         },
                         user_facing_hint: None,
+                        paths: Vec::new(),
                     },
                 });
                 self.bottom_pane.drain_pending_submission_state();
@@ -3736,7 +3826,7 @@ impl ChatWidget {
         self.app_event_tx.send(AppEvent::InsertHistoryCell(cell));
     }
 
-    fn queue_user_message(&mut self, user_message: UserMessage) {
+    pub(crate) fn queue_user_message(&mut self, user_message: UserMessage) {
         if !self.is_session_configured()
             || self.bottom_pane.is_task_running()
             || self.is_review_mode
@@ -4410,15 +4500,18 @@ impl ChatWidget {
     fn open_status_line_setup(&mut self) {
         let view = StatusLineSetupView::new(
             self.config.tui_status_line.as_deref(),
+            &self.config.tui_status_line_commands,
+            self.status_line_commands.outputs(),
             self.app_event_tx.clone(),
         );
         self.bottom_pane.show_view(Box::new(view));
     }
 
-    /// Parses configured status-line ids into known items and collects unknown ids.
+    /// Parses configured status-line ids into built-in items or command segments and collects
+    /// unknown ids.
     ///
     /// Unknown ids are deduplicated in insertion order for warning messages.
-    fn status_line_items_with_invalids(&self) -> (Vec<StatusLineItem>, Vec<String>) {
+    fn status_line_items_with_invalids(&self) -> (Vec<StatusLineSegment>, Vec<String>) {
         let mut invalid = Vec::new();
         let mut invalid_seen = HashSet::new();
         let mut items = Vec::new();
@@ -4426,9 +4519,9 @@ impl ChatWidget {
             return (items, invalid);
         };
         for id in config_items {
-            match id.parse::<StatusLineItem>() {
-                Ok(item) => items.push(item),
-                Err(_) => {
+            match StatusLineSegment::parse(id, &self.config.tui_status_line_commands) {
+                Some(item) => items.push(item),
+                None => {
                     if invalid_seen.insert(id.clone()) {
                         invalid.push(format!(r#""{id}""#));
                     }
@@ -4507,7 +4600,11 @@ impl ChatWidget {
     /// Returning `None` means "omit this item for now", not "configuration error". Callers rely on
     /// this to keep partially available status lines readable while waiting for session, token, or
     /// git metadata.
-    fn status_line_value_for_item(&self, item: &StatusLineItem) -> Option<String> {
+    fn status_line_value_for_item(&self, item: &StatusLineSegment) -> Option<String> {
+        let item = match item {
+            StatusLineSegment::Item(item) => item,
+            StatusLineSegment::Command(name) => return self.status_line_commands.output(name),
+        };
         match item {
             StatusLineItem::ModelName => Some(self.model_display_name().to_string()),
             StatusLineItem::ModelWithReasoning => {
@@ -7256,7 +7353,13 @@ impl ChatWidget {
     }
 
     pub(crate) fn open_review_popup(&mut self) {
+        let params = self.review_popup_params();
+        self.bottom_pane.show_selection_view(params);
+    }
+
+    fn review_popup_params(&self) -> SelectionViewParams {
         let mut items: Vec<SelectionItem> = Vec::new();
+        let paths = self.review_paths.clone();
 
         items.push(SelectionItem {
             name: tr("Review against a base branch", "基于目标分支审查").to_string(),
@@ -7273,13 +7376,17 @@ impl ChatWidget {
 
         items.push(SelectionItem {
             name: tr("Review uncommitted changes", "审查未提交改动").to_string(),
-            actions: vec![Box::new(move |tx: &AppEventSender| {
-                tx.send(AppEvent::CodexOp(Op::Review {
-                    review_request: ReviewRequest {
-                        target: ReviewTarget::UncommittedChanges,
-                        user_facing_hint: None,
-                    },
-                }));
+            actions: vec![Box::new({
+                let paths = paths.clone();
+                move |tx: &AppEventSender| {
+                    tx.send(AppEvent::CodexOp(Op::Review {
+                        review_request: ReviewRequest {
+                            target: ReviewTarget::UncommittedChanges,
+                            user_facing_hint: None,
+                            paths: paths.clone(),
+                        },
+                    }));
+                }
             })],
             dismiss_on_select: true,
             ..Default::default()
@@ -7307,12 +7414,57 @@ impl ChatWidget {
             ..Default::default()
         });
 
-        self.bottom_pane.show_selection_view(SelectionViewParams {
+        items.push(SelectionItem {
+            name: tr("Review a commit range", "审查提交范围").to_string(),
+            description: Some(tr("(base commit..HEAD)", "（基准提交..HEAD）").into()),
+            actions: vec![Box::new({
+                let cwd = self.config.cwd.clone();
+                move |tx| {
+                    tx.send(AppEvent::OpenReviewCommitRangePicker(cwd.clone()));
+                }
+            })],
+            dismiss_on_select: false,
+            ..Default::default()
+        });
+
+        items.push(SelectionItem {
+            name: tr("Review a patch file", "审查补丁文件").to_string(),
+            actions: vec![Box::new(move |tx| {
+                tx.send(AppEvent::OpenReviewPatchFilePrompt);
+            })],
+            dismiss_on_select: false,
+            ..Default::default()
+        });
+
+        let paths_description = if paths.is_empty() {
+            tr("(all files)", "（所有文件）").to_string()
+        } else {
+            format!(
+                "({})",
+                paths
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        };
+        items.push(SelectionItem {
+            name: tr("Limit review to paths", "限定审查路径").to_string(),
+            description: Some(paths_description),
+            actions: vec![Box::new(move |tx| {
+                tx.send(AppEvent::OpenReviewPathsPrompt);
+            })],
+            dismiss_on_select: false,
+            ..Default::default()
+        });
+
+        SelectionViewParams {
+            view_id: Some(REVIEW_PRESETS_VIEW_ID),
             title: Some(tr("Select a review preset", "选择审查预设").into()),
             footer_hint: Some(standard_popup_hint_line()),
             items,
             ..Default::default()
-        });
+        }
     }
 
     pub(crate) async fn show_review_branch_picker(&mut self, cwd: &Path) {
@@ -7324,6 +7476,7 @@ impl ChatWidget {
 
         for option in branches {
             let branch = option.clone();
+            let paths = self.review_paths.clone();
             items.push(SelectionItem {
                 name: format!("{current_branch} -> {branch}"),
                 actions: vec![Box::new(move |tx3: &AppEventSender| {
@@ -7333,6 +7486,7 @@ impl ChatWidget {
                                 branch: branch.clone(),
                             },
                             user_facing_hint: None,
+                            paths: paths.clone(),
                         },
                     }));
                 })],
@@ -7360,6 +7514,7 @@ impl ChatWidget {
             let subject = entry.subject.clone();
             let sha = entry.sha.clone();
             let search_val = format!("{subject} {sha}");
+            let paths = self.review_paths.clone();
 
             items.push(SelectionItem {
                 name: subject.clone(),
@@ -7371,6 +7526,7 @@ impl ChatWidget {
                                 title: Some(subject.clone()),
                             },
                             user_facing_hint: None,
+                            paths: paths.clone(),
                         },
                     }));
                 })],
@@ -7390,8 +7546,120 @@ impl ChatWidget {
         });
     }
 
+    /// Pick the base commit of a `base..HEAD` range review.
+    pub(crate) async fn show_review_commit_range_picker(&mut self, cwd: &Path) {
+        let commits = codex_core::git_info::recent_commits(cwd, 100).await;
+
+        let mut items: Vec<SelectionItem> = Vec::with_capacity(commits.len());
+        for entry in commits {
+            let subject = entry.subject.clone();
+            let sha = entry.sha.clone();
+            let search_val = format!("{subject} {sha}");
+            let paths = self.review_paths.clone();
+
+            items.push(SelectionItem {
+                name: subject,
+                actions: vec![Box::new(move |tx3: &AppEventSender| {
+                    tx3.send(AppEvent::CodexOp(Op::Review {
+                        review_request: ReviewRequest {
+                            target: ReviewTarget::CommitRange {
+                                from: sha.clone(),
+                                to: "HEAD".to_string(),
+                            },
+                            user_facing_hint: None,
+                            paths: paths.clone(),
+                        },
+                    }));
+                })],
+                dismiss_on_select: true,
+                search_value: Some(search_val),
+                ..Default::default()
+            });
+        }
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some(
+                tr(
+                    "Select the base commit (reviews base..HEAD)",
+                    "选择基准提交（审查 基准..HEAD）",
+                )
+                .to_string(),
+            ),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some(tr("Type to search commits", "输入以搜索提交").to_string()),
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn show_review_patch_file_prompt(&mut self) {
+        let tx = self.app_event_tx.clone();
+        let paths = self.review_paths.clone();
+        let view = CustomPromptView::new(
+            tr("Review a patch file", "审查补丁文件").to_string(),
+            tr(
+                "Type the patch file path and press Enter",
+                "输入补丁文件路径后按 Enter",
+            )
+            .to_string(),
+            None,
+            Box::new(move |path: String| {
+                let trimmed = path.trim();
+                if trimmed.is_empty() {
+                    return;
+                }
+                tx.send(AppEvent::CodexOp(Op::Review {
+                    review_request: ReviewRequest {
+                        target: ReviewTarget::PatchFile {
+                            path: PathBuf::from(trimmed),
+                        },
+                        user_facing_hint: None,
+                        paths: paths.clone(),
+                    },
+                }));
+            }),
+        );
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    pub(crate) fn show_review_paths_prompt(&mut self) {
+        let tx = self.app_event_tx.clone();
+        let view = CustomPromptView::new(
+            tr("Limit review to paths", "限定审查路径").to_string(),
+            tr(
+                "Type space-separated paths (empty for all files) and press Enter",
+                "输入以空格分隔的路径（留空表示所有文件）后按 Enter",
+            )
+            .to_string(),
+            None,
+            Box::new(move |input: String| {
+                let paths = shlex::split(&input)
+                    .unwrap_or_else(|| input.split_whitespace().map(str::to_string).collect())
+                    .into_iter()
+                    .map(PathBuf::from)
+                    .collect();
+                tx.send(AppEvent::SetReviewPaths(paths));
+            }),
+        );
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    /// Apply a path filter to the review presets and show them again.
+    pub(crate) fn set_review_paths(&mut self, paths: Vec<PathBuf>) {
+        self.review_paths = paths;
+        let params = self.review_popup_params();
+        if !self
+            .bottom_pane
+            .replace_selection_view_if_active(REVIEW_PRESETS_VIEW_ID, params)
+        {
+            self.open_review_popup();
+        }
+    }
+
     pub(crate) fn show_review_custom_prompt(&mut self) {
         let tx = self.app_event_tx.clone();
+        let paths = self.review_paths.clone();
         let view = CustomPromptView::new(
             tr("Custom review instructions", "自定义审查指令").to_string(),
             tr("Type instructions and press Enter", "输入指令后按 Enter").to_string(),
@@ -7407,6 +7675,7 @@ impl ChatWidget {
                             instructions: trimmed,
                         },
                         user_facing_hint: None,
+                        paths: paths.clone(),
                     },
                 }));
             }),
@@ -7697,6 +7966,7 @@ pub(crate) fn show_review_commit_picker_with_entries(
                             title: Some(subject.clone()),
                         },
                         user_facing_hint: None,
+                        paths: Vec::new(),
                     },
                 }));
             })],
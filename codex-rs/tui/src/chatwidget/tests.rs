@@ -1351,6 +1351,7 @@ async fn entered_review_mode_uses_request_hint() {
                 branch: "feature".to_string(),
             },
             user_facing_hint: Some("feature branch".to_string()),
+            paths: Vec::new(),
         }),
     });
 
@@ -1370,6 +1371,7 @@ async fn entered_review_mode_defaults_to_current_changes_banner() {
         msg: EventMsg::EnteredReviewMode(ReviewRequest {
             target: ReviewTarget::UncommittedChanges,
             user_facing_hint: None,
+            paths: Vec::new(),
         }),
     });
 
@@ -1404,6 +1406,7 @@ async fn review_restores_context_window_indicator() {
                 branch: "feature".to_string(),
             },
             user_facing_hint: Some("feature branch".to_string()),
+            paths: Vec::new(),
         }),
     });
 
@@ -1626,6 +1629,7 @@ async fn make_chatwidget_manual(
         agent_turn_running: false,
         mcp_startup_status: None,
         connectors_cache: ConnectorsCacheState::default(),
+        review_paths: Vec::new(),
         connectors_prefetch_in_flight: false,
         interrupts: InterruptManager::new(),
         reasoning_buffer: String::new(),
@@ -1666,6 +1670,8 @@ async fn make_chatwidget_manual(
         status_line_branch_cwd: None,
         status_line_branch_pending: false,
         status_line_branch_lookup_complete: false,
+        status_line_commands: StatusLineCommandState::default(),
+        last_turn_diff: None,
         external_editor_state: ExternalEditorState::Closed,
     };
     widget.set_model(&resolved_model);
@@ -3497,6 +3503,43 @@ async fn unified_exec_non_empty_then_empty_snapshots() {
     assert_snapshot!("unified_exec_non_empty_then_empty_after", combined);
 }
 
+/// A path filter set from the review popup is carried by the selected preset.
+#[tokio::test]
+async fn review_popup_path_filter_applies_to_selected_preset() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.open_review_popup();
+    chat.set_review_paths(vec![PathBuf::from("core/src")]);
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        popup.contains("(core/src)"),
+        "expected path filter in popup: {popup:?}"
+    );
+
+    // Second item: "Review uncommitted changes".
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let mut review_request = None;
+    while let Ok(ev) = rx.try_recv() {
+        if let AppEvent::CodexOp(Op::Review {
+            review_request: request,
+        }) = ev
+        {
+            review_request = Some(request);
+        }
+    }
+    assert_eq!(
+        review_request,
+        Some(ReviewRequest {
+            target: ReviewTarget::UncommittedChanges,
+            user_facing_hint: None,
+            paths: vec![PathBuf::from("core/src")],
+        })
+    );
+}
+
 /// Selecting the custom prompt option from the review popup sends
 /// OpenReviewCustomPrompt to the app event channel.
 #[tokio::test]
@@ -4185,6 +4228,7 @@ async fn custom_prompt_submit_sends_review_op() {
                         instructions: "please audit dependencies".to_string(),
                     },
                     user_facing_hint: None,
+                    paths: Vec::new(),
                 }
             );
         }
@@ -6867,6 +6911,41 @@ async fn status_line_branch_refreshes_after_interrupt() {
     assert!(chat.status_line_branch_pending);
 }
 
+#[tokio::test]
+async fn status_line_command_segments_render_cached_output_and_refresh_after_turn() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.config.tui_status_line_commands = BTreeMap::from([(
+        "kube".to_string(),
+        codex_core::config::types::StatusLineCommand {
+            command: "true".to_string(),
+            interval_secs: None,
+            refresh_on_turn_complete: true,
+            timeout_ms: None,
+        },
+    )]);
+    chat.config.tui_status_line = Some(vec!["kube".to_string()]);
+
+    chat.refresh_status_line();
+    assert!(chat.status_line_commands.is_pending("kube"));
+
+    chat.set_status_line_command_output("kube", Some("prod".to_string()));
+    assert_eq!(
+        chat.status_line_value_for_item(&StatusLineSegment::Command("kube".to_string())),
+        Some("prod".to_string())
+    );
+    assert!(!chat.status_line_commands.is_pending("kube"));
+
+    chat.handle_codex_event(Event {
+        id: "turn-1".into(),
+        msg: EventMsg::TurnComplete(TurnCompleteEvent {
+            turn_id: "turn-1".to_string(),
+            last_agent_message: None,
+        }),
+    });
+
+    assert!(chat.status_line_commands.is_pending("kube"));
+}
+
 #[tokio::test]
 async fn stream_recovery_restores_previous_status_header() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -7326,6 +7405,7 @@ async fn review_queues_user_messages_snapshot() {
         msg: EventMsg::EnteredReviewMode(ReviewRequest {
             target: ReviewTarget::UncommittedChanges,
             user_facing_hint: Some("current changes".to_string()),
+            paths: Vec::new(),
         }),
     });
     let _ = drain_insert_history(&mut rx);
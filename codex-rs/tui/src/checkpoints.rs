@@ -0,0 +1,255 @@
+//! Popups and diff rendering for the `/checkpoints` browser.
+//!
+//! Each turn's snapshot is listed newest first. Picking one lists the files
+//! that changed since, and picking a file offers its diff, a whole-file
+//! restore and one restore entry per hunk.
+
+use std::path::Path;
+
+use codex_core::protocol::Checkpoint;
+use codex_core::protocol::CheckpointFile;
+use codex_core::protocol::CheckpointFileStatus;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+
+use crate::app_event::AppEvent;
+use crate::bottom_pane::SelectionItem;
+use crate::bottom_pane::SelectionViewParams;
+use crate::bottom_pane::popup_consts::standard_popup_hint_line;
+use crate::diff_render::calculate_add_remove_from_diff;
+use crate::diff_render::display_path_for;
+use crate::i18n::tr;
+use crate::text_formatting::truncate_text;
+
+const USER_MESSAGE_PREVIEW_GRAPHEMES: usize = 60;
+
+pub(crate) fn checkpoints_popup_params(checkpoints: Vec<Checkpoint>) -> SelectionViewParams {
+    let items = checkpoints
+        .into_iter()
+        .enumerate()
+        .rev()
+        .map(|(idx, checkpoint)| {
+            let message = checkpoint
+                .user_message
+                .as_deref()
+                .map(|message| message.lines().next().unwrap_or_default())
+                .map(|line| truncate_text(line, USER_MESSAGE_PREVIEW_GRAPHEMES))
+                .unwrap_or_default();
+            let name = format!("{} {} {message}", tr("Turn", "轮次"), idx + 1);
+            let file_count = checkpoint.files.len();
+            let description = match file_count {
+                0 => tr("no changes since", "此后无改动").to_string(),
+                1 => tr("1 file changed since", "此后改动 1 个文件").to_string(),
+                n => format!("{} {n}", tr("files changed since:", "此后改动的文件数：")),
+            };
+            SelectionItem {
+                search_value: Some(name.clone()),
+                name,
+                description: Some(description),
+                is_disabled: file_count == 0,
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::OpenCheckpointFiles(checkpoint.clone()));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    SelectionViewParams {
+        title: Some(tr("Checkpoints", "检查点").to_string()),
+        subtitle: Some(
+            tr(
+                "Select a turn to restore files from its snapshot",
+                "选择一轮对话，从其快照中恢复文件",
+            )
+            .to_string(),
+        ),
+        footer_hint: Some(standard_popup_hint_line()),
+        items,
+        is_searchable: true,
+        ..Default::default()
+    }
+}
+
+pub(crate) fn checkpoint_files_popup_params(
+    checkpoint: Checkpoint,
+    cwd: &Path,
+) -> SelectionViewParams {
+    let Checkpoint {
+        id: checkpoint_id,
+        user_message,
+        files,
+    } = checkpoint;
+    let items = files
+        .into_iter()
+        .map(|file| {
+            let name = display_path_for(&file.path, cwd);
+            let (added, removed) = calculate_add_remove_from_diff(&file.unified_diff);
+            let description = format!("{} +{added} -{removed}", status_label(file.status));
+            let checkpoint_id = checkpoint_id.clone();
+            SelectionItem {
+                search_value: Some(name.clone()),
+                name,
+                description: Some(description),
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::OpenCheckpointFile {
+                        checkpoint_id: checkpoint_id.clone(),
+                        file: file.clone(),
+                    });
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    SelectionViewParams {
+        title: Some(tr("Changed since checkpoint", "检查点之后的改动").to_string()),
+        subtitle: user_message
+            .as_deref()
+            .map(|message| truncate_text(message, USER_MESSAGE_PREVIEW_GRAPHEMES)),
+        footer_hint: Some(standard_popup_hint_line()),
+        items,
+        is_searchable: true,
+        ..Default::default()
+    }
+}
+
+pub(crate) fn checkpoint_file_popup_params(
+    checkpoint_id: String,
+    file: CheckpointFile,
+    cwd: &Path,
+) -> SelectionViewParams {
+    let mut items = Vec::new();
+    if !file.unified_diff.is_empty() {
+        let diff_file = file.clone();
+        items.push(SelectionItem {
+            name: tr("View diff", "查看 diff").to_string(),
+            actions: vec![Box::new(move |tx| {
+                tx.send(AppEvent::ShowCheckpointDiff(diff_file.clone()));
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+    }
+
+    let path = file.path.clone();
+    let whole_file_checkpoint_id = checkpoint_id.clone();
+    items.push(SelectionItem {
+        name: tr("Restore whole file", "恢复整个文件").to_string(),
+        description: Some(match file.status {
+            CheckpointFileStatus::Added => tr("delete the file", "删除该文件").to_string(),
+            CheckpointFileStatus::Deleted | CheckpointFileStatus::Modified => {
+                tr("put back the checkpoint contents", "恢复为检查点中的内容").to_string()
+            }
+        }),
+        actions: vec![Box::new(move |tx| {
+            tx.send(AppEvent::RestoreCheckpointFile {
+                checkpoint_id: whole_file_checkpoint_id.clone(),
+                path: path.clone(),
+                hunk: None,
+            });
+        })],
+        dismiss_on_select: true,
+        ..Default::default()
+    });
+
+    // Added and deleted files have a single hunk that spans the whole file.
+    if file.status == CheckpointFileStatus::Modified {
+        for (hunk, header) in hunk_headers(&file.unified_diff).into_iter().enumerate() {
+            let path = file.path.clone();
+            let checkpoint_id = checkpoint_id.clone();
+            items.push(SelectionItem {
+                name: format!("{} {}", tr("Restore hunk", "恢复片段"), hunk + 1),
+                description: Some(header.to_string()),
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::RestoreCheckpointFile {
+                        checkpoint_id: checkpoint_id.clone(),
+                        path: path.clone(),
+                        hunk: Some(hunk),
+                    });
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+    }
+
+    SelectionViewParams {
+        title: Some(display_path_for(&file.path, cwd)),
+        subtitle: Some(status_label(file.status).to_string()),
+        footer_hint: Some(standard_popup_hint_line()),
+        items,
+        ..Default::default()
+    }
+}
+
+/// Colored lines of a checkpoint diff for the pager overlay.
+pub(crate) fn checkpoint_diff_lines(file: &CheckpointFile) -> Vec<Line<'static>> {
+    file.unified_diff
+        .lines()
+        .map(|line| {
+            let owned = line.to_string();
+            if line.starts_with("@@") {
+                owned.cyan().into()
+            } else if line.starts_with('+') {
+                owned.green().into()
+            } else if line.starts_with('-') {
+                owned.red().into()
+            } else {
+                owned.into()
+            }
+        })
+        .collect()
+}
+
+fn hunk_headers(unified_diff: &str) -> Vec<&str> {
+    unified_diff
+        .lines()
+        .filter(|line| line.starts_with("@@"))
+        .collect()
+}
+
+fn status_label(status: CheckpointFileStatus) -> &'static str {
+    match status {
+        CheckpointFileStatus::Added => tr("added", "新增"),
+        CheckpointFileStatus::Deleted => tr("deleted", "删除"),
+        CheckpointFileStatus::Modified => tr("modified", "修改"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[test]
+    fn modified_file_offers_one_restore_per_hunk() {
+        let cwd = PathBuf::from("/repo");
+        let file = CheckpointFile {
+            path: cwd.join("src/lib.rs"),
+            status: CheckpointFileStatus::Modified,
+            unified_diff: "@@ -1,1 +1,1 @@\n-a\n+b\n@@ -20,1 +20,1 @@\n-c\n+d\n".to_string(),
+        };
+
+        let params = checkpoint_file_popup_params("ghost".to_string(), file, &cwd);
+
+        let names = params
+            .items
+            .iter()
+            .map(|item| item.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                tr("View diff", "查看 diff"),
+                tr("Restore whole file", "恢复整个文件"),
+                &format!("{} 1", tr("Restore hunk", "恢复片段")),
+                &format!("{} 2", tr("Restore hunk", "恢复片段")),
+            ]
+        );
+        assert_eq!(params.title.as_deref(), Some("src/lib.rs"));
+    }
+}
@@ -340,6 +340,7 @@ fn format_network_constraints(network: &NetworkConstraints) -> String {
         denied_domains,
         allow_unix_sockets,
         allow_local_binding,
+        mitm,
     } = network;
 
     if let Some(enabled) = enabled {
@@ -379,6 +380,9 @@ fn format_network_constraints(network: &NetworkConstraints) -> String {
     if let Some(allow_local_binding) = allow_local_binding {
         parts.push(format!("allow_local_binding={allow_local_binding}"));
     }
+    if let Some(mitm) = mitm {
+        parts.push(format!("mitm={mitm}"));
+    }
 
     join_or_empty(parts)
 }
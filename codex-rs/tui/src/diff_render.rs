@@ -1,8 +1,6 @@
 use diffy::Hunk;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Color;
-use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
 use ratatui::text::Line as RtLine;
@@ -18,6 +16,8 @@ use crate::render::line_utils::prefix_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
+use crate::theme::ThemeRole;
+use crate::theme::theme;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::protocol::FileChange;
 
@@ -126,9 +126,9 @@ fn collect_rows(changes: &HashMap<PathBuf, FileChange>) -> Vec<Row> {
 fn render_line_count_summary(added: usize, removed: usize) -> Vec<RtSpan<'static>> {
     let mut spans = Vec::new();
     spans.push("(".into());
-    spans.push(format!("+{added}").green());
+    spans.push(RtSpan::styled(format!("+{added}"), style_add()));
     spans.push(" ".into());
-    spans.push(format!("-{removed}").red());
+    spans.push(RtSpan::styled(format!("-{removed}"), style_del()));
     spans.push(")".into());
     spans
 }
@@ -412,7 +412,7 @@ fn line_number_width(max_line_number: usize) -> usize {
 }
 
 fn style_gutter() -> Style {
-    Style::default().add_modifier(Modifier::DIM)
+    theme().style(ThemeRole::Dim)
 }
 
 fn style_context() -> Style {
@@ -420,16 +420,19 @@ fn style_context() -> Style {
 }
 
 fn style_add() -> Style {
-    Style::default().fg(Color::Green)
+    theme().style(ThemeRole::DiffAdd)
 }
 
 fn style_del() -> Style {
-    Style::default().fg(Color::Red)
+    theme().style(ThemeRole::DiffRemove)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::theme::BuiltinTheme;
+    use crate::theme::Theme;
+    use crate::theme::with_theme;
     use insta::assert_snapshot;
     use pretty_assertions::assert_eq;
     use ratatui::Terminal;
@@ -471,6 +474,37 @@ mod tests {
         assert_snapshot!(name, text);
     }
 
+    #[test]
+    fn ui_snapshot_diff_lines_under_builtin_themes() {
+        for builtin in [BuiltinTheme::HighContrast, BuiltinTheme::Colorblind] {
+            let buf = with_theme(Theme::builtin(builtin), || {
+                let mut lines =
+                    push_wrapped_diff_line(1, DiffLineType::Insert, "let x = 1;", 16, 1);
+                lines.extend(push_wrapped_diff_line(
+                    2,
+                    DiffLineType::Delete,
+                    "let y = 2;",
+                    16,
+                    1,
+                ));
+                lines.extend(push_wrapped_diff_line(
+                    3,
+                    DiffLineType::Context,
+                    "done",
+                    16,
+                    1,
+                ));
+                lines.push(RtLine::from(render_line_count_summary(1, 1)));
+                let area = Rect::new(0, 0, 16, 4);
+                let mut buf = Buffer::empty(area);
+                Paragraph::new(Text::from(lines)).render_ref(area, &mut buf);
+                buf
+            });
+            let name = format!("diff_lines_theme_{}", builtin.as_ref().replace('-', "_"));
+            assert_snapshot!(name.as_str(), format!("{buf:?}"));
+        }
+    }
+
     #[test]
     fn display_path_prefers_cwd_without_git_repo() {
         let cwd = if cfg!(windows) {
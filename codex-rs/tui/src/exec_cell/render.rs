@@ -9,6 +9,8 @@ use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::line_utils::prefix_lines;
 use crate::render::line_utils::push_owned_lines;
 use crate::shimmer::shimmer_spans;
+use crate::theme::ThemeRole;
+use crate::theme::theme;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
 use crate::wrapping::word_wrap_lines;
@@ -19,7 +21,6 @@ use codex_protocol::parse_command::ParsedCommand;
 use codex_utils_elapsed::format_duration;
 use itertools::Itertools;
 use ratatui::prelude::*;
-use ratatui::style::Modifier;
 use ratatui::style::Stylize;
 use textwrap::WordSplitter;
 use unicode_width::UnicodeWidthStr;
@@ -139,7 +140,7 @@ pub(crate) fn output_lines(
         };
         line.spans.insert(0, prefix.into());
         line.spans.iter_mut().for_each(|span| {
-            span.style = span.style.add_modifier(Modifier::DIM);
+            span.style = span.style.patch(theme().style(ThemeRole::Dim));
         });
         out.push(line);
     }
@@ -166,7 +167,7 @@ pub(crate) fn output_lines(
             line.spans.insert(0, "    ".into());
         }
         line.spans.iter_mut().for_each(|span| {
-            span.style = span.style.add_modifier(Modifier::DIM);
+            span.style = span.style.patch(theme().style(ThemeRole::Dim));
         });
         out.push(line);
     }
@@ -236,10 +237,10 @@ impl HistoryCell for ExecCell {
                     .map(format_duration)
                     .unwrap_or_else(|| "unknown".to_string());
                 let mut result: Line = if output.exit_code == 0 {
-                    Line::from("✓".green().bold())
+                    Line::from(Span::styled("✓", theme().style(ThemeRole::ExecSuccess)).bold())
                 } else {
                     Line::from(vec![
-                        "✗".red().bold(),
+                        Span::styled("✗", theme().style(ThemeRole::ExecFailure)).bold(),
                         format!(" ({})", output.exit_code).into(),
                     ])
                 };
@@ -362,8 +363,8 @@ impl ExecCell {
         let layout = EXEC_DISPLAY_LAYOUT;
         let success = call.output.as_ref().map(|o| o.exit_code == 0);
         let bullet = match success {
-            Some(true) => "•".green().bold(),
-            Some(false) => "•".red().bold(),
+            Some(true) => Span::styled("•", theme().style(ThemeRole::ExecSuccess)).bold(),
+            Some(false) => Span::styled("•", theme().style(ThemeRole::ExecFailure)).bold(),
             None => spinner(call.start_time, self.animations_enabled()),
         };
         let is_interaction = call.is_unified_exec_interaction();
@@ -0,0 +1,362 @@
+//! Key chords for TUI actions, configurable through `[tui.keymap]`.
+//!
+//! The keymap is resolved once at startup and read through [`keymap`], so key
+//! handlers and the hints rendered in the footer, overlays and approval prompts
+//! always agree on the active chords. Until [`init`] runs (for example in
+//! tests) the default chords are used.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use ratatui::text::Span;
+use strum::IntoEnumIterator;
+use strum_macros::AsRefStr;
+use strum_macros::EnumIter;
+use strum_macros::EnumString;
+
+use crate::key_hint;
+use crate::key_hint::KeyBinding;
+
+static KEYMAP: OnceLock<Keymap> = OnceLock::new();
+
+/// Actions whose key chords can be changed in `[tui.keymap]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, EnumIter, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum KeymapAction {
+    Submit,
+    Newline,
+    QueueMessage,
+    OpenEditor,
+    Interrupt,
+    Transcript,
+    HistoryPrevious,
+    HistoryNext,
+    ApprovalAccept,
+    ApprovalAcceptForSession,
+    ApprovalDeny,
+    PagerScrollUp,
+    PagerScrollDown,
+    PagerPageUp,
+    PagerPageDown,
+    PagerClose,
+}
+
+/// Where an action is handled. Two actions only conflict when they share a
+/// context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeymapContext {
+    Composer,
+    Approval,
+    Pager,
+}
+
+impl KeymapAction {
+    fn default_bindings(self) -> Vec<KeyBinding> {
+        match self {
+            KeymapAction::Submit => vec![key_hint::plain(KeyCode::Enter)],
+            KeymapAction::Newline => vec![
+                key_hint::shift(KeyCode::Enter),
+                key_hint::ctrl(KeyCode::Char('j')),
+            ],
+            KeymapAction::QueueMessage => vec![key_hint::plain(KeyCode::Tab)],
+            KeymapAction::OpenEditor => vec![key_hint::ctrl(KeyCode::Char('g'))],
+            KeymapAction::Interrupt => vec![key_hint::plain(KeyCode::Esc)],
+            KeymapAction::Transcript => vec![key_hint::ctrl(KeyCode::Char('t'))],
+            KeymapAction::HistoryPrevious => vec![
+                key_hint::plain(KeyCode::Up),
+                key_hint::ctrl(KeyCode::Char('p')),
+            ],
+            KeymapAction::HistoryNext => vec![
+                key_hint::plain(KeyCode::Down),
+                key_hint::ctrl(KeyCode::Char('n')),
+            ],
+            KeymapAction::ApprovalAccept => vec![key_hint::plain(KeyCode::Char('y'))],
+            KeymapAction::ApprovalAcceptForSession => vec![key_hint::plain(KeyCode::Char('a'))],
+            KeymapAction::ApprovalDeny => vec![key_hint::plain(KeyCode::Char('n'))],
+            KeymapAction::PagerScrollUp => vec![
+                key_hint::plain(KeyCode::Up),
+                key_hint::plain(KeyCode::Char('k')),
+            ],
+            KeymapAction::PagerScrollDown => vec![
+                key_hint::plain(KeyCode::Down),
+                key_hint::plain(KeyCode::Char('j')),
+            ],
+            KeymapAction::PagerPageUp => vec![
+                key_hint::plain(KeyCode::PageUp),
+                key_hint::shift(KeyCode::Char(' ')),
+                key_hint::ctrl(KeyCode::Char('b')),
+            ],
+            KeymapAction::PagerPageDown => vec![
+                key_hint::plain(KeyCode::PageDown),
+                key_hint::plain(KeyCode::Char(' ')),
+                key_hint::ctrl(KeyCode::Char('f')),
+            ],
+            KeymapAction::PagerClose => vec![key_hint::plain(KeyCode::Char('q'))],
+        }
+    }
+
+    fn contexts(self) -> &'static [KeymapContext] {
+        match self {
+            KeymapAction::Submit
+            | KeymapAction::Newline
+            | KeymapAction::QueueMessage
+            | KeymapAction::OpenEditor
+            | KeymapAction::Interrupt
+            | KeymapAction::HistoryPrevious
+            | KeymapAction::HistoryNext => &[KeymapContext::Composer],
+            // The transcript chord opens the overlay from the composer and
+            // closes it again from inside the pager.
+            KeymapAction::Transcript => &[KeymapContext::Composer, KeymapContext::Pager],
+            KeymapAction::ApprovalAccept
+            | KeymapAction::ApprovalAcceptForSession
+            | KeymapAction::ApprovalDeny => &[KeymapContext::Approval],
+            KeymapAction::PagerScrollUp
+            | KeymapAction::PagerScrollDown
+            | KeymapAction::PagerPageUp
+            | KeymapAction::PagerPageDown
+            | KeymapAction::PagerClose => &[KeymapContext::Pager],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Keymap {
+    bindings: HashMap<KeymapAction, Vec<KeyBinding>>,
+    customized: HashSet<KeymapAction>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: KeymapAction::iter()
+                .map(|action| (action, action.default_bindings()))
+                .collect(),
+            customized: HashSet::new(),
+        }
+    }
+}
+
+impl Keymap {
+    /// Builds a keymap from `[tui.keymap]`, returning warnings for unknown
+    /// actions, unparsable chords and chords bound to more than one action.
+    pub(crate) fn from_config(overrides: &BTreeMap<String, Vec<String>>) -> (Self, Vec<String>) {
+        let mut keymap = Self::default();
+        let mut warnings = Vec::new();
+        for (name, chords) in overrides {
+            let Ok(action) = KeymapAction::from_str(name) else {
+                warnings.push(format!("[tui.keymap]: unknown action `{name}`"));
+                continue;
+            };
+            let mut bindings = Vec::new();
+            for chord in chords {
+                match parse_key_chord(chord) {
+                    Some(binding) => bindings.push(binding),
+                    None => warnings.push(format!(
+                        "[tui.keymap]: cannot parse key `{chord}` for `{name}`"
+                    )),
+                }
+            }
+            // A list made only of typos keeps the defaults rather than
+            // silently unbinding the action; an empty list unbinds it.
+            if bindings.is_empty() && !chords.is_empty() {
+                continue;
+            }
+            keymap.bindings.insert(action, bindings);
+            keymap.customized.insert(action);
+        }
+        warnings.extend(keymap.conflicts());
+        (keymap, warnings)
+    }
+
+    fn conflicts(&self) -> Vec<String> {
+        let mut conflicts = Vec::new();
+        let actions: Vec<KeymapAction> = KeymapAction::iter().collect();
+        for (index, first) in actions.iter().enumerate() {
+            for second in &actions[index + 1..] {
+                let shares_context = first
+                    .contexts()
+                    .iter()
+                    .any(|context| second.contexts().contains(context));
+                if !shares_context {
+                    continue;
+                }
+                for binding in self.bindings(*first) {
+                    if self.bindings(*second).contains(binding) {
+                        let chord = Span::from(binding).content;
+                        conflicts.push(format!(
+                            "[tui.keymap]: `{chord}` is bound to both `{}` and `{}`",
+                            first.as_ref(),
+                            second.as_ref()
+                        ));
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+
+    pub(crate) fn bindings(&self, action: KeymapAction) -> &[KeyBinding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// The chord shown in hints for `action`, if it has one.
+    pub(crate) fn hint(&self, action: KeymapAction) -> Option<KeyBinding> {
+        self.bindings(action).first().copied()
+    }
+
+    pub(crate) fn is_customized(&self, action: KeymapAction) -> bool {
+        self.customized.contains(&action)
+    }
+
+    pub(crate) fn is_press(&self, action: KeymapAction, event: KeyEvent) -> bool {
+        self.bindings(action)
+            .iter()
+            .any(|binding| binding.is_press(event))
+    }
+}
+
+/// Resolves `[tui.keymap]` for the rest of the process and returns the
+/// warnings to show. Later calls keep the first keymap.
+pub(crate) fn init(overrides: &BTreeMap<String, Vec<String>>) -> Vec<String> {
+    let (keymap, warnings) = Keymap::from_config(overrides);
+    let _ = KEYMAP.set(keymap);
+    warnings
+}
+
+pub(crate) fn keymap() -> &'static Keymap {
+    KEYMAP.get_or_init(Keymap::default)
+}
+
+/// Parses chords such as `enter`, `ctrl+j`, `alt+shift+up` or `f5`.
+fn parse_key_chord(chord: &str) -> Option<KeyBinding> {
+    let chord = chord.trim().to_ascii_lowercase();
+    let (modifier_names, key) = match chord.rsplit_once('+') {
+        // `ctrl++` binds the plus key itself.
+        Some((rest, "")) => (rest.strip_suffix('+').unwrap_or(rest), "+"),
+        Some((rest, key)) => (rest, key),
+        None => ("", chord.as_str()),
+    };
+    let mut modifiers = KeyModifiers::NONE;
+    for name in modifier_names
+        .split('+')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        modifiers |= match name {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" | "option" | "meta" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return None,
+        };
+    }
+    let key = match key.trim() {
+        "enter" | "return" => KeyCode::Enter,
+        // Terminals report shift+tab as a separate back-tab key.
+        "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+        "tab" => KeyCode::Tab,
+        "esc" | "escape" => KeyCode::Esc,
+        "space" => KeyCode::Char(' '),
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        key => {
+            let mut chars = key.chars();
+            match (chars.next(), chars.next()) {
+                (Some(ch), None) => KeyCode::Char(ch),
+                _ => KeyCode::F(key.strip_prefix('f')?.parse().ok()?),
+            }
+        }
+    };
+    Some(KeyBinding::new(key, modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_key_chords() {
+        assert_eq!(
+            parse_key_chord("enter"),
+            Some(key_hint::plain(KeyCode::Enter))
+        );
+        assert_eq!(
+            parse_key_chord("Ctrl+J"),
+            Some(key_hint::ctrl(KeyCode::Char('j')))
+        );
+        assert_eq!(
+            parse_key_chord("ctrl + alt + v"),
+            Some(key_hint::ctrl_alt(KeyCode::Char('v')))
+        );
+        assert_eq!(
+            parse_key_chord("shift+tab"),
+            Some(key_hint::shift(KeyCode::BackTab))
+        );
+        assert_eq!(
+            parse_key_chord("ctrl++"),
+            Some(key_hint::ctrl(KeyCode::Char('+')))
+        );
+        assert_eq!(parse_key_chord("f5"), Some(key_hint::plain(KeyCode::F(5))));
+        assert_eq!(parse_key_chord("hyper+x"), None);
+        assert_eq!(parse_key_chord("enterr"), None);
+    }
+
+    #[test]
+    fn overrides_replace_defaults_and_report_problems() {
+        let overrides = BTreeMap::from([
+            (
+                "submit".to_string(),
+                vec!["ctrl+enter".to_string(), "ctrl+j".to_string()],
+            ),
+            ("open_editor".to_string(), vec!["ctrl+nope".to_string()]),
+            ("approval_deny".to_string(), Vec::new()),
+            ("launch_rockets".to_string(), vec!["f1".to_string()]),
+        ]);
+
+        let (keymap, warnings) = Keymap::from_config(&overrides);
+
+        assert_eq!(
+            keymap.bindings(KeymapAction::Submit),
+            &[
+                key_hint::ctrl(KeyCode::Enter),
+                key_hint::ctrl(KeyCode::Char('j')),
+            ]
+        );
+        assert!(keymap.is_customized(KeymapAction::Submit));
+        assert_eq!(
+            keymap.bindings(KeymapAction::OpenEditor),
+            &[key_hint::ctrl(KeyCode::Char('g'))]
+        );
+        assert_eq!(keymap.hint(KeymapAction::ApprovalDeny), None);
+        assert_eq!(
+            warnings,
+            vec![
+                "[tui.keymap]: unknown action `launch_rockets`".to_string(),
+                "[tui.keymap]: cannot parse key `ctrl+nope` for `open_editor`".to_string(),
+                "[tui.keymap]: `ctrl + j` is bound to both `submit` and `newline`".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn default_keymap_has_no_conflicts() {
+        let (keymap, warnings) = Keymap::from_config(&BTreeMap::new());
+        assert_eq!(keymap, Keymap::default());
+        assert_eq!(warnings, Vec::<String>::new());
+    }
+}
@@ -65,6 +65,7 @@ mod app_event_sender;
 mod ascii_animation;
 mod bottom_pane;
 mod chatwidget;
+mod checkpoints;
 mod cli;
 mod clipboard_paste;
 mod collab;
@@ -84,6 +85,7 @@ mod history_cell;
 mod i18n;
 pub mod insert_history;
 mod key_hint;
+mod keymap;
 pub mod live_wrap;
 mod markdown;
 mod markdown_render;
@@ -104,10 +106,12 @@ mod skills_helpers;
 mod slash_command;
 mod status;
 mod status_indicator_widget;
+mod status_line_command;
 mod streaming;
 mod style;
 mod terminal_palette;
 mod text_formatting;
+mod theme;
 mod tooltips;
 mod tui;
 mod ui_consts;
@@ -649,7 +653,14 @@ async fn run_ratatui_app(
             _ => resume_picker::SessionSelection::StartFresh,
         }
     } else if cli.resume_picker {
-        match resume_picker::run_resume_picker(&mut tui, &config, cli.resume_show_all).await? {
+        match resume_picker::run_resume_picker(
+            &mut tui,
+            &config,
+            cli.resume_show_all,
+            cli.resume_search.clone(),
+        )
+        .await?
+        {
             resume_picker::SessionSelection::Exit => {
                 restore();
                 session_log::log_session_end();
@@ -670,7 +681,10 @@ async fn run_ratatui_app(
     let current_cwd = config.cwd.clone();
     let allow_prompt = cli.cwd.is_none();
     let action_and_path_if_resume_or_fork = match &session_selection {
-        resume_picker::SessionSelection::Resume(path) => Some((CwdPromptAction::Resume, path)),
+        resume_picker::SessionSelection::Resume(path)
+        | resume_picker::SessionSelection::ResumeAtTurn { path, .. } => {
+            Some((CwdPromptAction::Resume, path))
+        }
         resume_picker::SessionSelection::Fork(path) => Some((CwdPromptAction::Fork, path)),
         _ => None,
     };
@@ -683,7 +697,9 @@ async fn run_ratatui_app(
     };
 
     let mut config = match &session_selection {
-        resume_picker::SessionSelection::Resume(_) | resume_picker::SessionSelection::Fork(_) => {
+        resume_picker::SessionSelection::Resume(_)
+        | resume_picker::SessionSelection::ResumeAtTurn { .. }
+        | resume_picker::SessionSelection::Fork(_) => {
             load_config_or_exit_with_fallback_cwd(
                 cli_kv_overrides.clone(),
                 overrides.clone(),
@@ -1,4 +1,6 @@
 use crate::render::line_utils::line_to_static;
+use crate::theme::ThemeRole;
+use crate::theme::theme;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
 use pulldown_cmark::CodeBlockKind;
@@ -48,7 +50,7 @@ impl Default for MarkdownStyles {
             strikethrough: Style::new().crossed_out(),
             ordered_list_marker: Style::new().light_blue(),
             unordered_list_marker: Style::new(),
-            link: Style::new().cyan().underlined(),
+            link: theme().style(ThemeRole::Link),
             blockquote: Style::new().green(),
         }
     }
@@ -14,16 +14,22 @@
 //! `TranscriptOverlay::sync_live_tail` uses the key to decide when the cached tail must be
 //! recomputed. `ChatWidget` is responsible for producing a key that changes when the active cell
 //! mutates in place or when its transcript output is time-dependent.
+//!
+//! The diff overlay (`/diff`) lives in [`diff_overlay`] and reuses the pager view for its diff
+//! pane.
 
 use std::io::Result;
 use std::sync::Arc;
 
+use crate::app_event_sender::AppEventSender;
 use crate::chatwidget::ActiveCellTranscriptKey;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::UserHistoryCell;
 use crate::i18n::tr;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap::KeymapAction;
+use crate::keymap::keymap;
 use crate::render::Insets;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
@@ -46,9 +52,15 @@ use ratatui::widgets::Widget;
 use ratatui::widgets::WidgetRef;
 use ratatui::widgets::Wrap;
 
+mod diff_overlay;
+
+pub(crate) use diff_overlay::DiffOverlay;
+pub(crate) use diff_overlay::DiffSource;
+
 pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
     Static(StaticOverlay),
+    Diff(DiffOverlay),
 }
 
 impl Overlay {
@@ -67,10 +79,15 @@ impl Overlay {
         Self::Static(StaticOverlay::with_renderables(renderables, title))
     }
 
+    pub(crate) fn new_diff(sources: Vec<DiffSource>, app_event_tx: AppEventSender) -> Self {
+        Self::Diff(DiffOverlay::new(sources, app_event_tx))
+    }
+
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match self {
             Overlay::Transcript(o) => o.handle_event(tui, event),
             Overlay::Static(o) => o.handle_event(tui, event),
+            Overlay::Diff(o) => o.handle_event(tui, event),
         }
     }
 
@@ -78,38 +95,43 @@ impl Overlay {
         match self {
             Overlay::Transcript(o) => o.is_done(),
             Overlay::Static(o) => o.is_done(),
+            Overlay::Diff(o) => o.is_done(),
         }
     }
 }
 
-const KEY_UP: KeyBinding = key_hint::plain(KeyCode::Up);
-const KEY_DOWN: KeyBinding = key_hint::plain(KeyCode::Down);
-const KEY_K: KeyBinding = key_hint::plain(KeyCode::Char('k'));
-const KEY_J: KeyBinding = key_hint::plain(KeyCode::Char('j'));
-const KEY_PAGE_UP: KeyBinding = key_hint::plain(KeyCode::PageUp);
-const KEY_PAGE_DOWN: KeyBinding = key_hint::plain(KeyCode::PageDown);
-const KEY_SPACE: KeyBinding = key_hint::plain(KeyCode::Char(' '));
-const KEY_SHIFT_SPACE: KeyBinding = key_hint::shift(KeyCode::Char(' '));
 const KEY_HOME: KeyBinding = key_hint::plain(KeyCode::Home);
 const KEY_END: KeyBinding = key_hint::plain(KeyCode::End);
 const KEY_LEFT: KeyBinding = key_hint::plain(KeyCode::Left);
 const KEY_RIGHT: KeyBinding = key_hint::plain(KeyCode::Right);
-const KEY_CTRL_F: KeyBinding = key_hint::ctrl(KeyCode::Char('f'));
 const KEY_CTRL_D: KeyBinding = key_hint::ctrl(KeyCode::Char('d'));
-const KEY_CTRL_B: KeyBinding = key_hint::ctrl(KeyCode::Char('b'));
 const KEY_CTRL_U: KeyBinding = key_hint::ctrl(KeyCode::Char('u'));
-const KEY_Q: KeyBinding = key_hint::plain(KeyCode::Char('q'));
 const KEY_ESC: KeyBinding = key_hint::plain(KeyCode::Esc);
 const KEY_ENTER: KeyBinding = key_hint::plain(KeyCode::Enter);
-const KEY_CTRL_T: KeyBinding = key_hint::ctrl(KeyCode::Char('t'));
 const KEY_CTRL_C: KeyBinding = key_hint::ctrl(KeyCode::Char('c'));
 
+// Keys shown in hints for the given actions, following `[tui.keymap]`.
+fn keymap_hint_keys(actions: &[KeymapAction]) -> Vec<KeyBinding> {
+    actions
+        .iter()
+        .filter_map(|action| keymap().hint(*action))
+        .collect()
+}
+
 // Common pager navigation hints rendered on the first line
-const PAGER_KEY_HINTS: &[(&[KeyBinding], &str)] = &[
-    (&[KEY_UP, KEY_DOWN], "to scroll"),
-    (&[KEY_PAGE_UP, KEY_PAGE_DOWN], "to page"),
-    (&[KEY_HOME, KEY_END], "to jump"),
-];
+fn render_pager_key_hints(area: Rect, buf: &mut Buffer) {
+    let scroll = keymap_hint_keys(&[KeymapAction::PagerScrollUp, KeymapAction::PagerScrollDown]);
+    let page = keymap_hint_keys(&[KeymapAction::PagerPageUp, KeymapAction::PagerPageDown]);
+    render_key_hints(
+        area,
+        buf,
+        &[
+            (&scroll, "to scroll"),
+            (&page, "to page"),
+            (&[KEY_HOME, KEY_END], "to jump"),
+        ],
+    );
+}
 
 fn localized_pager_hint(desc: &str) -> String {
     match desc {
@@ -120,6 +142,14 @@ fn localized_pager_hint(desc: &str) -> String {
         "to edit prev" => tr("to edit prev", "编辑上一条").to_string(),
         "to edit next" => tr("to edit next", "编辑下一条").to_string(),
         "to edit message" => tr("to edit message", "编辑消息").to_string(),
+        "to jump hunks" => tr("to jump hunks", "切换代码块").to_string(),
+        "to jump files" => tr("to jump files", "切换文件").to_string(),
+        "to show side by side" => tr("to show side by side", "并排显示").to_string(),
+        "to show unified" => tr("to show unified", "合并显示").to_string(),
+        "to switch diff" => tr("to switch diff", "切换差异来源").to_string(),
+        "to comment on hunk" => tr("to comment on hunk", "评论此代码块").to_string(),
+        "to send" => tr("to send", "发送").to_string(),
+        "to cancel" => tr("to cancel", "取消").to_string(),
         _ => desc.to_string(),
     }
 }
@@ -129,6 +159,9 @@ fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&[KeyBinding], &str)
     let mut spans: Vec<Span<'static>> = vec![" ".into()];
     let mut first = true;
     for (keys, desc) in pairs {
+        if keys.is_empty() {
+            continue;
+        }
         if !first {
             spans.push("   ".into());
         }
@@ -154,6 +187,8 @@ struct PagerView {
     last_rendered_height: Option<usize>,
     /// If set, on next render ensure this chunk is visible.
     pending_scroll_chunk: Option<usize>,
+    /// If set, on next render scroll so this chunk starts at the top.
+    pending_top_chunk: Option<usize>,
 }
 
 impl PagerView {
@@ -165,6 +200,7 @@ impl PagerView {
             last_content_height: None,
             last_rendered_height: None,
             pending_scroll_chunk: None,
+            pending_top_chunk: None,
         }
     }
 
@@ -187,6 +223,9 @@ impl PagerView {
         if let Some(idx) = self.pending_scroll_chunk.take() {
             self.ensure_chunk_visible(idx, content_area);
         }
+        if let Some(idx) = self.pending_top_chunk.take() {
+            self.scroll_offset = self.chunk_top(idx, content_area.width);
+        }
         self.scroll_offset = self
             .scroll_offset
             .min(content_height.saturating_sub(content_area.height as usize));
@@ -274,20 +313,17 @@ impl PagerView {
 
     fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) -> Result<()> {
         match key_event {
-            e if KEY_UP.is_press(e) || KEY_K.is_press(e) => {
+            e if keymap().is_press(KeymapAction::PagerScrollUp, e) => {
                 self.scroll_offset = self.scroll_offset.saturating_sub(1);
             }
-            e if KEY_DOWN.is_press(e) || KEY_J.is_press(e) => {
+            e if keymap().is_press(KeymapAction::PagerScrollDown, e) => {
                 self.scroll_offset = self.scroll_offset.saturating_add(1);
             }
-            e if KEY_PAGE_UP.is_press(e)
-                || KEY_SHIFT_SPACE.is_press(e)
-                || KEY_CTRL_B.is_press(e) =>
-            {
+            e if keymap().is_press(KeymapAction::PagerPageUp, e) => {
                 let page_height = self.page_height(tui.terminal.viewport_area);
                 self.scroll_offset = self.scroll_offset.saturating_sub(page_height);
             }
-            e if KEY_PAGE_DOWN.is_press(e) || KEY_SPACE.is_press(e) || KEY_CTRL_F.is_press(e) => {
+            e if keymap().is_press(KeymapAction::PagerPageDown, e) => {
                 let page_height = self.page_height(tui.terminal.viewport_area);
                 self.scroll_offset = self.scroll_offset.saturating_add(page_height);
             }
@@ -364,6 +400,20 @@ impl PagerView {
         self.pending_scroll_chunk = Some(chunk_index);
     }
 
+    /// Request that the given chunk be scrolled to the top of the view on next render.
+    fn scroll_chunk_to_top(&mut self, chunk_index: usize) {
+        self.pending_top_chunk = Some(chunk_index);
+    }
+
+    /// Row at which the given chunk starts when rendered at `width`.
+    fn chunk_top(&self, idx: usize, width: u16) -> usize {
+        self.renderables
+            .iter()
+            .take(idx)
+            .map(|r| r.desired_height(width) as usize)
+            .sum()
+    }
+
     fn ensure_chunk_visible(&mut self, idx: usize, area: Rect) {
         if area.height == 0 || idx >= self.renderables.len() {
             return;
@@ -670,9 +720,10 @@ impl TranscriptOverlay {
     fn render_hints(&self, area: Rect, buf: &mut Buffer) {
         let line1 = Rect::new(area.x, area.y, area.width, 1);
         let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
-        render_key_hints(line1, buf, PAGER_KEY_HINTS);
+        render_pager_key_hints(line1, buf);
 
-        let mut pairs: Vec<(&[KeyBinding], &str)> = vec![(&[KEY_Q], "to quit")];
+        let close = keymap_hint_keys(&[KeymapAction::PagerClose]);
+        let mut pairs: Vec<(&[KeyBinding], &str)> = vec![(&close, "to quit")];
         if self.highlight_cell.is_some() {
             pairs.push((&[KEY_ESC, KEY_LEFT], "to edit prev"));
             pairs.push((&[KEY_RIGHT], "to edit next"));
@@ -696,7 +747,10 @@ impl TranscriptOverlay {
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match event {
             TuiEvent::Key(key_event) => match key_event {
-                e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) || KEY_CTRL_T.is_press(e) => {
+                e if keymap().is_press(KeymapAction::PagerClose, e)
+                    || KEY_CTRL_C.is_press(e)
+                    || keymap().is_press(KeymapAction::Transcript, e) =>
+                {
                     self.is_done = true;
                     Ok(())
                 }
@@ -742,8 +796,9 @@ impl StaticOverlay {
     fn render_hints(&self, area: Rect, buf: &mut Buffer) {
         let line1 = Rect::new(area.x, area.y, area.width, 1);
         let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
-        render_key_hints(line1, buf, PAGER_KEY_HINTS);
-        let pairs: Vec<(&[KeyBinding], &str)> = vec![(&[KEY_Q], "to quit")];
+        render_pager_key_hints(line1, buf);
+        let close = keymap_hint_keys(&[KeymapAction::PagerClose]);
+        let pairs: Vec<(&[KeyBinding], &str)> = vec![(&close, "to quit")];
         render_key_hints(line2, buf, &pairs);
     }
 
@@ -760,7 +815,7 @@ impl StaticOverlay {
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match event {
             TuiEvent::Key(key_event) => match key_event {
-                e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) => {
+                e if keymap().is_press(KeymapAction::PagerClose, e) || KEY_CTRL_C.is_press(e) => {
                     self.is_done = true;
                     Ok(())
                 }
@@ -0,0 +1,1155 @@
+//! Full-screen diff viewer opened by `/diff`.
+//!
+//! The overlay parses unified diffs (the working tree diff and, when one is known, the latest
+//! turn diff) into files and hunks. A file tree on the left selects the file shown on the right,
+//! which renders through the shared [`PagerView`] with one renderable per hunk so hunk jumps can
+//! scroll the selected hunk to the top.
+//!
+//! Hunks render unified or side by side. Each removed line is paired with the added line that
+//! replaced it, and the tokens that differ between the two are emphasized. Tokens follow
+//! identifier, whitespace and punctuation boundaries so a renamed variable lights up as a whole
+//! rather than character by character.
+//!
+//! Pressing `c` opens a one-line prompt below the diff; its text is queued as the next user
+//! message together with the hunk it refers to.
+
+use std::io::Result;
+use std::ops::Range;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+use ratatui::widgets::WidgetRef;
+use ratatui::widgets::Wrap;
+use similar::Algorithm;
+use similar::DiffTag;
+use unicode_width::UnicodeWidthChar;
+
+use super::CachedRenderable;
+use super::KEY_CTRL_C;
+use super::KEY_ENTER;
+use super::KEY_ESC;
+use super::PagerView;
+use super::keymap_hint_keys;
+use super::render_key_hints;
+use super::render_pager_key_hints;
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::i18n::tr;
+use crate::key_hint;
+use crate::key_hint::KeyBinding;
+use crate::keymap::KeymapAction;
+use crate::keymap::keymap;
+use crate::render::renderable::Renderable;
+use crate::theme::ThemeRole;
+use crate::theme::theme;
+use crate::tui;
+use crate::tui::TuiEvent;
+
+const KEY_NEXT_HUNK: KeyBinding = key_hint::plain(KeyCode::Char('n'));
+const KEY_PREV_HUNK: KeyBinding = key_hint::plain(KeyCode::Char('p'));
+const KEY_NEXT_FILE: KeyBinding = key_hint::plain(KeyCode::Char(']'));
+const KEY_PREV_FILE: KeyBinding = key_hint::plain(KeyCode::Char('['));
+const KEY_TOGGLE_LAYOUT: KeyBinding = key_hint::plain(KeyCode::Char('s'));
+const KEY_TOGGLE_SOURCE: KeyBinding = key_hint::plain(KeyCode::Char('t'));
+const KEY_COMMENT: KeyBinding = key_hint::plain(KeyCode::Char('c'));
+const KEY_BACKSPACE: KeyBinding = key_hint::plain(KeyCode::Backspace);
+
+/// Below this width the file tree is hidden and the diff uses the full width.
+const MIN_WIDTH_FOR_TREE: u16 = 60;
+/// Paired lines sharing less than this fraction of their text are shown as a plain removal and
+/// addition, since emphasizing nearly every token would only add noise.
+const MIN_INTRA_LINE_SIMILARITY: f32 = 0.4;
+/// Longer lines skip the token diff to keep rendering cheap.
+const MAX_INTRA_LINE_LEN: usize = 500;
+const TAB_WIDTH: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffLineKind {
+    Context,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DiffLine {
+    kind: DiffLineKind,
+    text: String,
+    old_line: Option<usize>,
+    new_line: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DiffHunk {
+    header: String,
+    lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DiffFile {
+    path: String,
+    hunks: Vec<DiffHunk>,
+}
+
+impl DiffFile {
+    fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            hunks: Vec::new(),
+        }
+    }
+
+    /// Number of added and removed lines across all hunks.
+    fn line_counts(&self) -> (usize, usize) {
+        let lines = self.hunks.iter().flat_map(|hunk| &hunk.lines);
+        lines.fold((0, 0), |(added, removed), line| match line.kind {
+            DiffLineKind::Added => (added + 1, removed),
+            DiffLineKind::Removed => (added, removed + 1),
+            DiffLineKind::Context => (added, removed),
+        })
+    }
+}
+
+/// One diff the overlay can show, such as the working tree or the last turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DiffSource {
+    label: String,
+    files: Vec<DiffFile>,
+}
+
+impl DiffSource {
+    /// Parses a unified diff, returning `None` when it changes no file.
+    pub(crate) fn parse(label: impl Into<String>, diff: &str) -> Option<Self> {
+        let mut files = parse_unified_diff(diff);
+        if files.is_empty() {
+            return None;
+        }
+        // Keep files in tree order so file jumps walk the tree top to bottom.
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Some(Self {
+            label: label.into(),
+            files,
+        })
+    }
+}
+
+/// Line positions while consuming the body of a hunk.
+#[derive(Debug, Default)]
+struct HunkBody {
+    old_line: usize,
+    new_line: usize,
+    old_remaining: usize,
+    new_remaining: usize,
+}
+
+impl HunkBody {
+    fn is_open(&self) -> bool {
+        self.old_remaining > 0 || self.new_remaining > 0
+    }
+
+    /// Consumes one body line, or returns `None` when `line` cannot be part of a hunk.
+    fn next_line(&mut self, line: &str) -> Option<DiffLine> {
+        let (kind, text) = match line.as_bytes().first() {
+            Some(b'+') => (DiffLineKind::Added, &line[1..]),
+            Some(b'-') => (DiffLineKind::Removed, &line[1..]),
+            Some(b' ') => (DiffLineKind::Context, &line[1..]),
+            // Some tools strip the leading space from empty context lines.
+            None => (DiffLineKind::Context, ""),
+            Some(_) => return None,
+        };
+        let old_line = (kind != DiffLineKind::Added).then(|| {
+            self.old_remaining = self.old_remaining.saturating_sub(1);
+            self.old_line += 1;
+            self.old_line - 1
+        });
+        let new_line = (kind != DiffLineKind::Removed).then(|| {
+            self.new_remaining = self.new_remaining.saturating_sub(1);
+            self.new_line += 1;
+            self.new_line - 1
+        });
+        Some(DiffLine {
+            kind,
+            text: text.to_string(),
+            old_line,
+            new_line,
+        })
+    }
+}
+
+/// Splits a unified diff, as printed by `git diff`, into files and hunks.
+///
+/// Hunk bodies are consumed using the line counts in their `@@` headers, so removed lines that
+/// themselves start with `--` are not mistaken for file headers. Files without hunks, such as
+/// binary files and pure renames, are dropped.
+fn parse_unified_diff(diff: &str) -> Vec<DiffFile> {
+    let mut files: Vec<DiffFile> = Vec::new();
+    let mut body = HunkBody::default();
+    for line in diff.lines() {
+        if body.is_open() {
+            if line.starts_with('\\') {
+                // "\ No newline at end of file"
+                continue;
+            }
+            if let Some(diff_line) = body.next_line(line)
+                && let Some(hunk) = files.last_mut().and_then(|file| file.hunks.last_mut())
+            {
+                hunk.lines.push(diff_line);
+                continue;
+            }
+            body = HunkBody::default();
+        }
+
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            let path = rest.rsplit_once(" b/").map_or(rest, |(_, path)| path);
+            files.push(DiffFile::new(path));
+        } else if let Some(path) = line.strip_prefix("--- ") {
+            // Plain unified diffs start a file here; git diffs already did at `diff --git`.
+            match files.last_mut() {
+                Some(file) if file.hunks.is_empty() => {
+                    if file.path.is_empty() {
+                        file.path = header_path(path).unwrap_or_default().to_string();
+                    }
+                }
+                _ => files.push(DiffFile::new(header_path(path).unwrap_or_default())),
+            }
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            if let Some(path) = header_path(path)
+                && let Some(file) = files.last_mut()
+            {
+                file.path = path.to_string();
+            }
+        } else if line.starts_with("@@ ") {
+            let Some((old_start, old_count, new_start, new_count)) = parse_hunk_header(line) else {
+                continue;
+            };
+            if files.is_empty() {
+                files.push(DiffFile::new(""));
+            }
+            if let Some(file) = files.last_mut() {
+                file.hunks.push(DiffHunk {
+                    header: line.to_string(),
+                    lines: Vec::new(),
+                });
+            }
+            body = HunkBody {
+                old_line: old_start,
+                new_line: new_start,
+                old_remaining: old_count,
+                new_remaining: new_count,
+            };
+        }
+    }
+    files.retain(|file| !file.hunks.is_empty());
+    files
+}
+
+/// The path named in a `---`/`+++` header without its `a/`/`b/` prefix, or `None` for
+/// `/dev/null`.
+fn header_path(path: &str) -> Option<&str> {
+    // Plain diffs may append a tab-separated timestamp.
+    let path = path.split('\t').next().unwrap_or(path).trim_end();
+    if path == "/dev/null" {
+        return None;
+    }
+    Some(
+        path.strip_prefix("a/")
+            .or_else(|| path.strip_prefix("b/"))
+            .unwrap_or(path),
+    )
+}
+
+/// Parses `@@ -12,3 +12,4 @@` into the old start and count and the new start and count.
+fn parse_hunk_header(line: &str) -> Option<(usize, usize, usize, usize)> {
+    let mut parts = line.strip_prefix("@@ ")?.split_whitespace();
+    let (old_start, old_count) = parse_hunk_range(parts.next()?.strip_prefix('-')?)?;
+    let (new_start, new_count) = parse_hunk_range(parts.next()?.strip_prefix('+')?)?;
+    Some((old_start, old_count, new_start, new_count))
+}
+
+fn parse_hunk_range(range: &str) -> Option<(usize, usize)> {
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Word,
+    Space,
+    Punctuation,
+}
+
+fn char_class(ch: char) -> CharClass {
+    if ch.is_alphanumeric() || ch == '_' {
+        CharClass::Word
+    } else if ch.is_whitespace() {
+        CharClass::Space
+    } else {
+        CharClass::Punctuation
+    }
+}
+
+/// Splits a line into identifier runs, whitespace runs and single punctuation characters.
+fn tokenize(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, ch)) = chars.next() {
+        let class = char_class(ch);
+        let mut end = start + ch.len_utf8();
+        if class != CharClass::Punctuation {
+            while let Some(&(index, next)) = chars.peek() {
+                if char_class(next) != class {
+                    break;
+                }
+                end = index + next.len_utf8();
+                chars.next();
+            }
+        }
+        tokens.push(&text[start..end]);
+    }
+    tokens
+}
+
+/// Byte ranges of the tokens that differ between a removed line and the added line that
+/// replaced it, or `None` when the lines have too little in common for emphasis to help.
+fn changed_ranges(old: &str, new: &str) -> Option<(Vec<Range<usize>>, Vec<Range<usize>>)> {
+    if old.len() > MAX_INTRA_LINE_LEN || new.len() > MAX_INTRA_LINE_LEN {
+        return None;
+    }
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+    let old_offsets = token_offsets(&old_tokens);
+    let new_offsets = token_offsets(&new_tokens);
+    let mut old_ranges = Vec::new();
+    let mut new_ranges = Vec::new();
+    let mut unchanged = 0;
+    for op in similar::capture_diff_slices(Algorithm::Myers, &old_tokens, &new_tokens) {
+        let (tag, old_range, new_range) = op.as_tag_tuple();
+        let old_bytes = old_offsets[old_range.start]..old_offsets[old_range.end];
+        let new_bytes = new_offsets[new_range.start]..new_offsets[new_range.end];
+        if tag == DiffTag::Equal {
+            unchanged += old_bytes.len();
+            continue;
+        }
+        if !old_bytes.is_empty() {
+            old_ranges.push(old_bytes);
+        }
+        if !new_bytes.is_empty() {
+            new_ranges.push(new_bytes);
+        }
+    }
+    let longest = old.len().max(new.len());
+    if longest > 0 && (unchanged as f32) < longest as f32 * MIN_INTRA_LINE_SIMILARITY {
+        return None;
+    }
+    Some((old_ranges, new_ranges))
+}
+
+/// Byte offset of each token start, followed by the total length.
+fn token_offsets(tokens: &[&str]) -> Vec<usize> {
+    let mut offsets = Vec::with_capacity(tokens.len() + 1);
+    let mut offset = 0;
+    offsets.push(offset);
+    for token in tokens {
+        offset += token.len();
+        offsets.push(offset);
+    }
+    offsets
+}
+
+/// Splits `text` into spans, emphasizing `ranges` on top of `style`.
+fn emphasized_spans(text: &str, ranges: &[Range<usize>], style: Style) -> Vec<Span<'static>> {
+    let emphasis = style.add_modifier(Modifier::REVERSED);
+    let mut spans = Vec::new();
+    let mut position = 0;
+    for range in ranges {
+        if range.start > position {
+            spans.push(Span::styled(text[position..range.start].to_string(), style));
+        }
+        spans.push(Span::styled(text[range.clone()].to_string(), emphasis));
+        position = range.end;
+    }
+    if position < text.len() {
+        spans.push(Span::styled(text[position..].to_string(), style));
+    }
+    spans
+}
+
+fn line_style(kind: DiffLineKind) -> Style {
+    match kind {
+        DiffLineKind::Added => theme().style(ThemeRole::DiffAdd),
+        DiffLineKind::Removed => theme().style(ThemeRole::DiffRemove),
+        DiffLineKind::Context => Style::default(),
+    }
+}
+
+fn sign(kind: DiffLineKind) -> char {
+    match kind {
+        DiffLineKind::Added => '+',
+        DiffLineKind::Removed => '-',
+        DiffLineKind::Context => ' ',
+    }
+}
+
+/// Pairs each removed line with the added line at the same position of the change that follows
+/// it. Context lines pair with themselves; unmatched lines pair with nothing.
+fn pair_rows(lines: &[DiffLine]) -> Vec<(Option<usize>, Option<usize>)> {
+    let mut rows = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        if lines[index].kind == DiffLineKind::Context {
+            rows.push((Some(index), Some(index)));
+            index += 1;
+            continue;
+        }
+        let removed_start = index;
+        while index < lines.len() && lines[index].kind == DiffLineKind::Removed {
+            index += 1;
+        }
+        let added_start = index;
+        while index < lines.len() && lines[index].kind == DiffLineKind::Added {
+            index += 1;
+        }
+        let removed = added_start - removed_start;
+        let added = index - added_start;
+        for offset in 0..removed.max(added) {
+            rows.push((
+                (offset < removed).then_some(removed_start + offset),
+                (offset < added).then_some(added_start + offset),
+            ));
+        }
+    }
+    rows
+}
+
+/// A hunk prepared for rendering: styled line contents and side-by-side rows.
+struct HunkRenderable {
+    header: String,
+    lines: Vec<DiffLine>,
+    contents: Vec<Vec<Span<'static>>>,
+    rows: Vec<(Option<usize>, Option<usize>)>,
+    number_width: usize,
+    layout: DiffLayout,
+    selected: bool,
+}
+
+impl HunkRenderable {
+    fn new(hunk: &DiffHunk, layout: DiffLayout, selected: bool) -> Self {
+        let lines: Vec<DiffLine> = hunk
+            .lines
+            .iter()
+            .map(|line| DiffLine {
+                text: line.text.replace('\t', &" ".repeat(TAB_WIDTH)),
+                ..line.clone()
+            })
+            .collect();
+        let rows = pair_rows(&lines);
+        let mut emphasis: Vec<Vec<Range<usize>>> = vec![Vec::new(); lines.len()];
+        for (left, right) in &rows {
+            if let (Some(left), Some(right)) = (*left, *right)
+                && left != right
+                && let Some((old, new)) = changed_ranges(&lines[left].text, &lines[right].text)
+            {
+                emphasis[left] = old;
+                emphasis[right] = new;
+            }
+        }
+        let contents = lines
+            .iter()
+            .zip(&emphasis)
+            .map(|(line, ranges)| emphasized_spans(&line.text, ranges, line_style(line.kind)))
+            .collect();
+        let max_line_number = lines
+            .iter()
+            .flat_map(|line| [line.old_line, line.new_line])
+            .flatten()
+            .max()
+            .unwrap_or(0);
+        Self {
+            header: hunk.header.clone(),
+            lines,
+            contents,
+            rows,
+            number_width: max_line_number.to_string().len(),
+            layout,
+            selected,
+        }
+    }
+
+    fn header_line(&self) -> Line<'static> {
+        if self.selected {
+            Line::from(vec![
+                "› ".into(),
+                Span::styled(self.header.clone(), theme().style(ThemeRole::Selection)),
+            ])
+        } else {
+            Line::from(vec![
+                "  ".into(),
+                Span::styled(self.header.clone(), theme().style(ThemeRole::Dim)),
+            ])
+        }
+    }
+
+    fn number(&self, number: Option<usize>) -> String {
+        let width = self.number_width;
+        match number {
+            Some(number) => format!("{number:>width$}"),
+            None => " ".repeat(width),
+        }
+    }
+
+    fn unified_line(&self, index: usize) -> Line<'static> {
+        let line = &self.lines[index];
+        let gutter = format!(
+            "{} {} ",
+            self.number(line.old_line),
+            self.number(line.new_line)
+        );
+        let mut spans = vec![
+            Span::styled(gutter, theme().style(ThemeRole::Dim)),
+            Span::styled(sign(line.kind).to_string(), line_style(line.kind)),
+        ];
+        spans.extend(self.contents[index].iter().cloned());
+        Line::from(spans)
+    }
+
+    /// One side of a side-by-side row, truncated and padded to exactly `width` columns.
+    fn side_spans(&self, index: Option<usize>, new_side: bool, width: usize) -> Vec<Span<'static>> {
+        let mut spans = Vec::new();
+        if let Some(index) = index {
+            let line = &self.lines[index];
+            let number = if new_side {
+                line.new_line
+            } else {
+                line.old_line
+            };
+            spans.push(Span::styled(
+                format!("{} ", self.number(number)),
+                theme().style(ThemeRole::Dim),
+            ));
+            spans.push(Span::styled(
+                sign(line.kind).to_string(),
+                line_style(line.kind),
+            ));
+            spans.extend(self.contents[index].iter().cloned());
+        }
+        fit_spans(spans, width)
+    }
+
+    fn lines(&self, width: u16) -> Vec<Line<'static>> {
+        let mut lines = vec![self.header_line()];
+        match self.layout {
+            DiffLayout::Unified => {
+                lines.extend((0..self.lines.len()).map(|index| self.unified_line(index)));
+            }
+            DiffLayout::SideBySide => {
+                let column = (width.saturating_sub(1) / 2) as usize;
+                for (left, right) in &self.rows {
+                    let mut spans = self.side_spans(*left, false, column);
+                    spans.push(Span::styled("│", theme().style(ThemeRole::Dim)));
+                    spans.extend(self.side_spans(*right, true, column));
+                    lines.push(Line::from(spans));
+                }
+            }
+        }
+        lines.push(Line::default());
+        lines
+    }
+
+    fn paragraph(&self, width: u16) -> Paragraph<'static> {
+        let paragraph = Paragraph::new(self.lines(width));
+        match self.layout {
+            DiffLayout::Unified => paragraph.wrap(Wrap { trim: false }),
+            // Side-by-side rows are truncated so both columns stay aligned.
+            DiffLayout::SideBySide => paragraph,
+        }
+    }
+}
+
+impl Renderable for HunkRenderable {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        self.paragraph(area.width).render_ref(area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        self.paragraph(width).line_count(width) as u16
+    }
+}
+
+/// Truncates `spans` to `width` columns and pads them with spaces to exactly that width.
+fn fit_spans(spans: Vec<Span<'static>>, width: usize) -> Vec<Span<'static>> {
+    let mut fitted = Vec::new();
+    let mut used = 0;
+    'spans: for span in spans {
+        let mut content = String::new();
+        for ch in span.content.chars() {
+            let ch_width = ch.width().unwrap_or(0);
+            if used + ch_width > width {
+                fitted.push(Span::styled(content, span.style));
+                break 'spans;
+            }
+            used += ch_width;
+            content.push(ch);
+        }
+        fitted.push(Span::styled(content, span.style));
+    }
+    if used < width {
+        fitted.push(Span::from(" ".repeat(width - used)));
+    }
+    fitted
+}
+
+/// An entry of the file tree: a directory, or a file by its index in the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TreeEntry {
+    depth: usize,
+    label: String,
+    file: Option<usize>,
+}
+
+/// Lays out sorted file paths as an indented tree with one entry per directory.
+fn file_tree(files: &[DiffFile]) -> Vec<TreeEntry> {
+    let mut entries = Vec::new();
+    let mut open_dirs: Vec<&str> = Vec::new();
+    for (index, file) in files.iter().enumerate() {
+        let mut components: Vec<&str> = file.path.split('/').collect();
+        let name = components.pop().unwrap_or_default();
+        let shared = open_dirs
+            .iter()
+            .zip(&components)
+            .take_while(|(open, component)| open == component)
+            .count();
+        open_dirs.truncate(shared);
+        for dir in &components[shared..] {
+            entries.push(TreeEntry {
+                depth: open_dirs.len(),
+                label: format!("{dir}/"),
+                file: None,
+            });
+            open_dirs.push(dir);
+        }
+        entries.push(TreeEntry {
+            depth: open_dirs.len(),
+            label: name.to_string(),
+            file: Some(index),
+        });
+    }
+    entries
+}
+
+/// The user message sent for a comment on `hunk`.
+fn hunk_comment_message(path: &str, hunk: &DiffHunk, comment: &str) -> String {
+    let mut body = String::new();
+    for line in &hunk.lines {
+        body.push(sign(line.kind));
+        body.push_str(&line.text);
+        body.push('\n');
+    }
+    format!(
+        "Review comment on `{path}`:\n\n```diff\n{}\n{body}```\n\n{comment}",
+        hunk.header
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffLayout {
+    Unified,
+    SideBySide,
+}
+
+/// Full-screen diff viewer with a file tree, hunk navigation and hunk comments.
+pub(crate) struct DiffOverlay {
+    sources: Vec<DiffSource>,
+    source: usize,
+    file: usize,
+    hunk: usize,
+    layout: DiffLayout,
+    view: PagerView,
+    /// Text of the hunk comment being typed, if the prompt is open.
+    comment: Option<String>,
+    /// One-shot message shown in place of the key hints, such as a sent comment.
+    notice: Option<String>,
+    app_event_tx: AppEventSender,
+    is_done: bool,
+}
+
+impl DiffOverlay {
+    /// Creates an overlay showing the first of `sources`, which must not be empty.
+    pub(crate) fn new(sources: Vec<DiffSource>, app_event_tx: AppEventSender) -> Self {
+        let mut overlay = Self {
+            sources,
+            source: 0,
+            file: 0,
+            hunk: 0,
+            layout: DiffLayout::Unified,
+            view: PagerView::new(Vec::new(), String::new(), 0),
+            comment: None,
+            notice: None,
+            app_event_tx,
+            is_done: false,
+        };
+        overlay.rebuild_view();
+        overlay
+    }
+
+    fn files(&self) -> &[DiffFile] {
+        self.sources
+            .get(self.source)
+            .map_or(&[], |source| source.files.as_slice())
+    }
+
+    fn current_file(&self) -> Option<&DiffFile> {
+        self.files().get(self.file)
+    }
+
+    /// Rebuilds the hunk renderables for the current selection and scrolls the selected hunk
+    /// to the top.
+    fn rebuild_view(&mut self) {
+        let label = self
+            .sources
+            .get(self.source)
+            .map(|source| source.label.clone())
+            .unwrap_or_default();
+        let Some(file) = self.current_file() else {
+            self.view = PagerView::new(Vec::new(), tr("D I F F", "差 异").to_string(), 0);
+            return;
+        };
+        let renderables = file
+            .hunks
+            .iter()
+            .enumerate()
+            .map(|(index, hunk)| {
+                let hunk = HunkRenderable::new(hunk, self.layout, index == self.hunk);
+                Box::new(CachedRenderable::new(hunk)) as Box<dyn Renderable>
+            })
+            .collect();
+        let title = format!("{} · {label} · {}", tr("D I F F", "差 异"), file.path);
+        self.view = PagerView::new(renderables, title, 0);
+        self.view.scroll_chunk_to_top(self.hunk);
+    }
+
+    fn select(&mut self, file: usize, hunk: usize) {
+        self.file = file;
+        self.hunk = hunk;
+        self.rebuild_view();
+    }
+
+    fn next_hunk(&mut self) {
+        let hunks = self.current_file().map_or(0, |file| file.hunks.len());
+        if self.hunk + 1 < hunks {
+            self.select(self.file, self.hunk + 1);
+        } else if self.file + 1 < self.files().len() {
+            self.select(self.file + 1, 0);
+        }
+    }
+
+    fn previous_hunk(&mut self) {
+        if self.hunk > 0 {
+            self.select(self.file, self.hunk - 1);
+        } else if self.file > 0 {
+            let file = self.file - 1;
+            let last = self.files()[file].hunks.len().saturating_sub(1);
+            self.select(file, last);
+        }
+    }
+
+    fn next_file(&mut self) {
+        if self.file + 1 < self.files().len() {
+            self.select(self.file + 1, 0);
+        }
+    }
+
+    fn previous_file(&mut self) {
+        if self.file > 0 {
+            self.select(self.file - 1, 0);
+        }
+    }
+
+    fn toggle_layout(&mut self) {
+        self.layout = match self.layout {
+            DiffLayout::Unified => DiffLayout::SideBySide,
+            DiffLayout::SideBySide => DiffLayout::Unified,
+        };
+        self.rebuild_view();
+    }
+
+    fn toggle_source(&mut self) {
+        if self.sources.len() < 2 {
+            return;
+        }
+        self.source = (self.source + 1) % self.sources.len();
+        self.select(0, 0);
+    }
+
+    fn submit_comment(&mut self) {
+        let Some(comment) = self.comment.take() else {
+            return;
+        };
+        let comment = comment.trim();
+        let Some(file) = self.current_file() else {
+            return;
+        };
+        let Some(hunk) = file.hunks.get(self.hunk) else {
+            return;
+        };
+        if comment.is_empty() {
+            return;
+        }
+        let message = hunk_comment_message(&file.path, hunk, comment);
+        self.app_event_tx.send(AppEvent::QueueUserMessage(message));
+        self.notice = Some(tr("Comment queued for the agent.", "评论已加入发送队列。").to_string());
+    }
+
+    fn handle_comment_key(&mut self, key_event: KeyEvent) {
+        match key_event {
+            e if KEY_ENTER.is_press(e) => self.submit_comment(),
+            e if KEY_ESC.is_press(e) || KEY_CTRL_C.is_press(e) => self.comment = None,
+            e if KEY_BACKSPACE.is_press(e) => {
+                if let Some(comment) = self.comment.as_mut() {
+                    comment.pop();
+                }
+            }
+            KeyEvent {
+                code: KeyCode::Char(ch),
+                modifiers,
+                kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                ..
+            } if !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                if let Some(comment) = self.comment.as_mut() {
+                    comment.push(ch);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn render_tree(&self, area: Rect, buf: &mut Buffer) {
+        let files = self.files();
+        let entries = file_tree(files);
+        let height = area.height.saturating_sub(1) as usize;
+        let selected_row = entries
+            .iter()
+            .position(|entry| entry.file == Some(self.file))
+            .unwrap_or(0);
+        let start = (selected_row + 1).saturating_sub(height);
+        let dim = theme().style(ThemeRole::Dim);
+        let mut lines = vec![Line::from(Span::styled(
+            format!(" {} ({})", tr("Files", "文件"), files.len()),
+            dim,
+        ))];
+        for entry in entries.iter().skip(start).take(height) {
+            let indent = "  ".repeat(entry.depth);
+            let Some(index) = entry.file else {
+                lines.push(Line::from(Span::styled(
+                    format!(" {indent}{}", entry.label),
+                    dim,
+                )));
+                continue;
+            };
+            let (added, removed) = files[index].line_counts();
+            let (marker, name_style) = if index == self.file {
+                ("›", theme().style(ThemeRole::Selection))
+            } else {
+                (" ", Style::default())
+            };
+            lines.push(Line::from(vec![
+                marker.into(),
+                indent.into(),
+                Span::styled(entry.label.clone(), name_style),
+                " ".into(),
+                Span::styled(format!("+{added}"), line_style(DiffLineKind::Added)),
+                " ".into(),
+                Span::styled(format!("-{removed}"), line_style(DiffLineKind::Removed)),
+            ]));
+        }
+        Paragraph::new(lines).render_ref(area, buf);
+    }
+
+    fn render_hints(&self, area: Rect, buf: &mut Buffer) {
+        let line1 = Rect::new(area.x, area.y, area.width, 1);
+        let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
+        let line3 = Rect::new(area.x, area.y.saturating_add(2), area.width, 1);
+        render_pager_key_hints(line1, buf);
+
+        if let Some(comment) = &self.comment {
+            let prompt = format!(" {} ", tr("Comment on hunk:", "评论此块："));
+            Paragraph::new(Line::from(vec![
+                prompt.bold(),
+                comment.clone().into(),
+                "▌".dim(),
+            ]))
+            .render_ref(line2, buf);
+            render_key_hints(
+                line3,
+                buf,
+                &[(&[KEY_ENTER], "to send"), (&[KEY_ESC], "to cancel")],
+            );
+            return;
+        }
+
+        if let Some(notice) = &self.notice {
+            Paragraph::new(Line::from(format!(" {notice}")).dim()).render_ref(line2, buf);
+        } else {
+            let layout = match self.layout {
+                DiffLayout::Unified => "to show side by side",
+                DiffLayout::SideBySide => "to show unified",
+            };
+            let mut pairs: Vec<(&[KeyBinding], &str)> = vec![
+                (&[KEY_PREV_HUNK, KEY_NEXT_HUNK], "to jump hunks"),
+                (&[KEY_PREV_FILE, KEY_NEXT_FILE], "to jump files"),
+                (&[KEY_TOGGLE_LAYOUT], layout),
+            ];
+            if self.sources.len() > 1 {
+                pairs.push((&[KEY_TOGGLE_SOURCE], "to switch diff"));
+            }
+            render_key_hints(line2, buf, &pairs);
+        }
+        let close = keymap_hint_keys(&[KeymapAction::PagerClose]);
+        render_key_hints(
+            line3,
+            buf,
+            &[(&close, "to quit"), (&[KEY_COMMENT], "to comment on hunk")],
+        );
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let top_h = area.height.saturating_sub(3);
+        let top = Rect::new(area.x, area.y, area.width, top_h);
+        let bottom = Rect::new(area.x, area.y + top_h, area.width, 3);
+        let tree_width = if area.width >= MIN_WIDTH_FOR_TREE {
+            (area.width / 4).clamp(20, 40)
+        } else {
+            0
+        };
+        if tree_width > 0 {
+            let tree = Rect::new(top.x, top.y, tree_width - 1, top.height);
+            self.render_tree(tree, buf);
+            let separator_x = top.x + tree_width - 1;
+            for y in top.y..top.bottom() {
+                buf[(separator_x, y)]
+                    .set_symbol("│")
+                    .set_style(theme().style(ThemeRole::Dim));
+            }
+        }
+        let diff = Rect::new(
+            top.x + tree_width,
+            top.y,
+            top.width.saturating_sub(tree_width),
+            top.height,
+        );
+        self.view.render(diff, buf);
+        self.render_hints(bottom, buf);
+    }
+
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        match event {
+            TuiEvent::Key(key_event) if self.comment.is_some() => {
+                self.handle_comment_key(key_event);
+            }
+            TuiEvent::Paste(text) => {
+                let Some(comment) = self.comment.as_mut() else {
+                    return Ok(());
+                };
+                comment.push_str(&text.replace(['\r', '\n'], " "));
+            }
+            TuiEvent::Key(key_event) => {
+                self.notice = None;
+                match key_event {
+                    e if keymap().is_press(KeymapAction::PagerClose, e)
+                        || KEY_ESC.is_press(e)
+                        || KEY_CTRL_C.is_press(e) =>
+                    {
+                        self.is_done = true;
+                        return Ok(());
+                    }
+                    e if KEY_NEXT_HUNK.is_press(e) => self.next_hunk(),
+                    e if KEY_PREV_HUNK.is_press(e) => self.previous_hunk(),
+                    e if KEY_NEXT_FILE.is_press(e) => self.next_file(),
+                    e if KEY_PREV_FILE.is_press(e) => self.previous_file(),
+                    e if KEY_TOGGLE_LAYOUT.is_press(e) => self.toggle_layout(),
+                    e if KEY_TOGGLE_SOURCE.is_press(e) => self.toggle_source(),
+                    e if KEY_COMMENT.is_press(e) => self.comment = Some(String::new()),
+                    other => return self.view.handle_key_event(tui, other),
+                }
+            }
+            TuiEvent::Draw => {
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                return Ok(());
+            }
+        }
+        tui.frame_requester()
+            .schedule_frame_in(crate::tui::TARGET_FRAME_INTERVAL);
+        Ok(())
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.is_done
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn main() {
+-    let count = 1;
++    let total = 1;
+ }
+@@ -10 +10,2 @@ fn helper() {
+--- removed dashes
++++ added pluses
++tail
+diff --git a/README.md b/README.md
+new file mode 100644
+--- /dev/null
++++ b/README.md
+@@ -0,0 +1 @@
++# Title
+\\ No newline at end of file
+";
+
+    fn buffer_text(buf: &Buffer) -> String {
+        let area = buf.area();
+        (area.top()..area.bottom())
+            .map(|y| {
+                (area.left()..area.right())
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn parses_files_hunks_and_dash_prefixed_lines() {
+        let files = parse_unified_diff(DIFF);
+        assert_eq!(
+            files
+                .iter()
+                .map(|file| (file.path.as_str(), file.hunks.len(), file.line_counts()))
+                .collect::<Vec<_>>(),
+            vec![("src/lib.rs", 2, (3, 2)), ("README.md", 1, (1, 0))]
+        );
+        let second = &files[0].hunks[1];
+        assert_eq!(
+            second.lines[0],
+            DiffLine {
+                kind: DiffLineKind::Removed,
+                text: "-- removed dashes".to_string(),
+                old_line: Some(10),
+                new_line: None,
+            }
+        );
+        assert_eq!(second.lines[2].new_line, Some(11));
+    }
+
+    #[test]
+    fn intra_line_emphasis_follows_token_boundaries() {
+        assert_eq!(
+            changed_ranges("    let count = 1;", "    let total = 1;"),
+            Some((vec![8..13], vec![8..13]))
+        );
+        assert_eq!(
+            changed_ranges("alpha beta", "something else entirely"),
+            None
+        );
+    }
+
+    #[test]
+    fn file_tree_groups_files_under_directories() {
+        let files = ["src/app.rs", "src/ui/view.rs", "tests/it.rs"]
+            .into_iter()
+            .map(DiffFile::new)
+            .collect::<Vec<_>>();
+        let labels = file_tree(&files)
+            .into_iter()
+            .map(|entry| (entry.depth, entry.label, entry.file))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            vec![
+                (0, "src/".to_string(), None),
+                (1, "app.rs".to_string(), Some(0)),
+                (1, "ui/".to_string(), None),
+                (2, "view.rs".to_string(), Some(1)),
+                (0, "tests/".to_string(), None),
+                (1, "it.rs".to_string(), Some(2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn hunk_navigation_crosses_files_and_comments_quote_the_hunk() {
+        let (tx, mut rx) = unbounded_channel();
+        let source = DiffSource::parse("working tree", DIFF).expect("diff has files");
+        let mut overlay = DiffOverlay::new(vec![source], AppEventSender::new(tx));
+        assert_eq!(
+            overlay.current_file().map(|file| file.path.as_str()),
+            Some("README.md")
+        );
+
+        overlay.next_hunk();
+        assert_eq!((overlay.file, overlay.hunk), (1, 0));
+        overlay.next_hunk();
+        assert_eq!((overlay.file, overlay.hunk), (1, 1));
+        overlay.previous_hunk();
+        overlay.previous_hunk();
+        assert_eq!((overlay.file, overlay.hunk), (0, 0));
+
+        overlay.comment = Some("  Title should be H2  ".to_string());
+        overlay.submit_comment();
+        let Ok(AppEvent::QueueUserMessage(message)) = rx.try_recv() else {
+            panic!("expected a queued user message");
+        };
+        assert_eq!(
+            message,
+            "Review comment on `README.md`:\n\n```diff\n@@ -0,0 +1 @@\n+# Title\n```\n\nTitle should be H2"
+        );
+        assert!(overlay.comment.is_none());
+    }
+
+    #[test]
+    fn side_by_side_renders_tree_and_both_columns() {
+        let (tx, _rx) = unbounded_channel();
+        let source = DiffSource::parse("working tree", DIFF).expect("diff has files");
+        let mut overlay = DiffOverlay::new(vec![source], AppEventSender::new(tx));
+        overlay.next_file();
+        overlay.toggle_layout();
+
+        let area = Rect::new(0, 0, 100, 14);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        let text = buffer_text(&buf);
+
+        assert!(text.contains("›  lib.rs +3 -2"), "{text}");
+        assert!(text.contains("src/"), "{text}");
+        let row = text
+            .lines()
+            .find(|line| line.contains("let count"))
+            .unwrap_or_default();
+        assert!(row.contains("let total"), "{text}");
+    }
+}
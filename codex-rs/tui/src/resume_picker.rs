@@ -22,6 +22,7 @@ use codex_core::ThreadsPage;
 use codex_core::config::Config;
 use codex_core::find_thread_names_by_ids;
 use codex_core::path_utils;
+use codex_core::state_db::ThreadSearchHit;
 use codex_protocol::ThreadId;
 use color_eyre::eyre::Result;
 use crossterm::event::KeyCode;
@@ -40,14 +41,34 @@ use unicode_width::UnicodeWidthStr;
 
 const PAGE_SIZE: usize = 25;
 const LOAD_NEAR_THRESHOLD: usize = 5;
+const SEARCH_LIMIT: usize = 50;
 #[derive(Debug, Clone)]
 pub enum SessionSelection {
     StartFresh,
     Resume(PathBuf),
+    /// Resume a session picked from a full-text search match and reveal the
+    /// user message of the matching turn.
+    ResumeAtTurn {
+        path: PathBuf,
+        nth_user_message: usize,
+    },
     Fork(PathBuf),
     Exit,
 }
 
+impl SessionSelection {
+    /// Splits a search-match resume into a plain resume and the user message to reveal.
+    pub(crate) fn take_search_jump(self) -> (Self, Option<usize>) {
+        match self {
+            SessionSelection::ResumeAtTurn {
+                path,
+                nth_user_message,
+            } => (SessionSelection::Resume(path), Some(nth_user_message)),
+            other => (other, None),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum SessionPickerAction {
     Resume,
@@ -69,10 +90,15 @@ impl SessionPickerAction {
         }
     }
 
-    fn selection(self, path: PathBuf) -> SessionSelection {
-        match self {
-            SessionPickerAction::Resume => SessionSelection::Resume(path),
-            SessionPickerAction::Fork => SessionSelection::Fork(path),
+    fn selection(self, row: &Row) -> SessionSelection {
+        let path = row.path.clone();
+        match (self, row.search_match.as_ref()) {
+            (SessionPickerAction::Resume, Some(search_match)) => SessionSelection::ResumeAtTurn {
+                path,
+                nth_user_message: search_match.nth_user_message,
+            },
+            (SessionPickerAction::Resume, None) => SessionSelection::Resume(path),
+            (SessionPickerAction::Fork, _) => SessionSelection::Fork(path),
         }
     }
 }
@@ -88,12 +114,25 @@ struct PageLoadRequest {
 
 type PageLoader = Arc<dyn Fn(PageLoadRequest) + Send + Sync>;
 
+#[derive(Clone)]
+struct ThreadSearchRequest {
+    query: String,
+    default_provider: String,
+}
+
+/// Runs a full-text search over the state DB; only available when SQLite is enabled.
+type ThreadSearchLoader = Arc<dyn Fn(ThreadSearchRequest) + Send + Sync>;
+
 enum BackgroundEvent {
     PageLoaded {
         request_token: usize,
         search_token: Option<usize>,
         page: std::io::Result<ThreadsPage>,
     },
+    SearchLoaded {
+        query: String,
+        hits: Vec<ThreadSearchHit>,
+    },
 }
 
 /// Interactive session picker that lists recorded rollout files with simple
@@ -112,12 +151,25 @@ enum BackgroundEvent {
 /// 1. Provider and source filtering at the backend (only interactive CLI sessions
 ///    for the current model provider).
 /// 2. Working-directory filtering at the picker (unless `--all` is passed).
+///
+/// When the SQLite state DB is available, typing a query also runs a full-text
+/// search over user messages, assistant messages and commands. Matching
+/// sessions are listed first with a snippet of the match, and resuming one
+/// jumps to the matching turn. `initial_query` prefills the search box.
 pub async fn run_resume_picker(
     tui: &mut Tui,
     config: &Config,
     show_all: bool,
+    initial_query: Option<String>,
 ) -> Result<SessionSelection> {
-    run_session_picker(tui, config, show_all, SessionPickerAction::Resume).await
+    run_session_picker(
+        tui,
+        config,
+        show_all,
+        SessionPickerAction::Resume,
+        initial_query,
+    )
+    .await
 }
 
 pub async fn run_fork_picker(
@@ -125,7 +177,7 @@ pub async fn run_fork_picker(
     config: &Config,
     show_all: bool,
 ) -> Result<SessionSelection> {
-    run_session_picker(tui, config, show_all, SessionPickerAction::Fork).await
+    run_session_picker(tui, config, show_all, SessionPickerAction::Fork, None).await
 }
 
 async fn run_session_picker(
@@ -133,6 +185,7 @@ async fn run_session_picker(
     config: &Config,
     show_all: bool,
     action: SessionPickerAction,
+    initial_query: Option<String>,
 ) -> Result<SessionSelection> {
     let alt = AltScreenGuard::enter(tui);
     let (bg_tx, bg_rx) = mpsc::unbounded_channel();
@@ -145,6 +198,35 @@ async fn run_session_picker(
         std::env::current_dir().ok()
     };
 
+    let thread_search_loader =
+        codex_core::state_db::get_state_db(config, None)
+            .await
+            .map(|state_db| {
+                let search_tx = bg_tx.clone();
+                let loader: ThreadSearchLoader = Arc::new(move |request: ThreadSearchRequest| {
+                    let tx = search_tx.clone();
+                    let state_db = state_db.clone();
+                    tokio::spawn(async move {
+                        let provider_filter = vec![request.default_provider];
+                        let hits = codex_core::state_db::search_threads_db(
+                            Some(state_db.as_ref()),
+                            request.query.as_str(),
+                            SEARCH_LIMIT,
+                            INTERACTIVE_SESSION_SOURCES,
+                            Some(provider_filter.as_slice()),
+                            false,
+                        )
+                        .await
+                        .unwrap_or_default();
+                        let _ = tx.send(BackgroundEvent::SearchLoaded {
+                            query: request.query,
+                            hits,
+                        });
+                    });
+                });
+                loader
+            });
+
     let config = config.clone();
     let loader_tx = bg_tx.clone();
     let page_loader: PageLoader = Arc::new(move |request: PageLoadRequest| {
@@ -179,6 +261,10 @@ async fn run_session_picker(
         filter_cwd,
         action,
     );
+    state.thread_search_loader = thread_search_loader;
+    if let Some(query) = initial_query {
+        state.set_query(query);
+    }
     state.start_initial_load();
     state.request_frame();
 
@@ -266,6 +352,10 @@ struct PickerState {
     action: SessionPickerAction,
     sort_key: ThreadSortKey,
     thread_name_cache: HashMap<ThreadId, Option<String>>,
+    thread_search_loader: Option<ThreadSearchLoader>,
+    /// Full-text search matches for the current query, listed before local matches.
+    search_rows: Vec<Row>,
+    thread_search_pending: bool,
 }
 
 struct PaginationState {
@@ -327,10 +417,21 @@ struct Row {
     updated_at: Option<DateTime<Utc>>,
     cwd: Option<PathBuf>,
     git_branch: Option<String>,
+    search_match: Option<SearchMatch>,
+}
+
+#[derive(Clone)]
+struct SearchMatch {
+    snippet: String,
+    /// Zero-based index of the user message that started the matching turn.
+    nth_user_message: usize,
 }
 
 impl Row {
     fn display_preview(&self) -> &str {
+        if let Some(search_match) = self.search_match.as_ref() {
+            return &search_match.snippet;
+        }
         self.thread_name.as_deref().unwrap_or(&self.preview)
     }
 
@@ -383,6 +484,9 @@ impl PickerState {
             action,
             sort_key: ThreadSortKey::CreatedAt,
             thread_name_cache: HashMap::new(),
+            thread_search_loader: None,
+            search_rows: Vec::new(),
+            thread_search_pending: false,
         }
     }
 
@@ -402,7 +506,7 @@ impl PickerState {
             }
             KeyCode::Enter => {
                 if let Some(row) = self.filtered_rows.get(self.selected) {
-                    return Ok(Some(self.action.selection(row.path.clone())));
+                    return Ok(Some(self.action.selection(row)));
                 }
             }
             KeyCode::Up => {
@@ -517,6 +621,23 @@ impl PickerState {
                 let completed_token = pending.search_token.or(search_token);
                 self.continue_search_if_token_matches(completed_token);
             }
+            BackgroundEvent::SearchLoaded { query, hits } => {
+                if query != self.query {
+                    return Ok(());
+                }
+                self.thread_search_pending = false;
+                self.search_rows = hits
+                    .into_iter()
+                    .map(|hit| {
+                        let mut row = search_hit_to_row(hit);
+                        row.thread_name = row
+                            .thread_id
+                            .and_then(|id| self.thread_name_cache.get(&id).cloned().flatten());
+                        row
+                    })
+                    .collect();
+                self.apply_filter();
+            }
         }
         Ok(())
     }
@@ -603,7 +724,19 @@ impl PickerState {
             self.filtered_rows = base_iter.cloned().collect();
         } else {
             let q = self.query.to_lowercase();
-            self.filtered_rows = base_iter.filter(|r| r.matches_query(&q)).cloned().collect();
+            let mut rows: Vec<Row> = self
+                .search_rows
+                .iter()
+                .filter(|row| self.row_matches_filter(row))
+                .cloned()
+                .collect();
+            let search_paths: HashSet<PathBuf> = rows.iter().map(|row| row.path.clone()).collect();
+            rows.extend(
+                base_iter
+                    .filter(|r| !search_paths.contains(&r.path) && r.matches_query(&q))
+                    .cloned(),
+            );
+            self.filtered_rows = rows;
         }
         if self.selected >= self.filtered_rows.len() {
             self.selected = self.filtered_rows.len().saturating_sub(1);
@@ -634,6 +767,8 @@ impl PickerState {
         }
         self.query = new_query;
         self.selected = 0;
+        self.search_rows.clear();
+        self.request_thread_search();
         self.apply_filter();
         if self.query.is_empty() {
             self.search_state = SearchState::Idle;
@@ -652,6 +787,21 @@ impl PickerState {
         self.load_more_if_needed(LoadTrigger::Search { token });
     }
 
+    fn request_thread_search(&mut self) {
+        self.thread_search_pending = false;
+        if self.query.trim().is_empty() {
+            return;
+        }
+        let Some(loader) = self.thread_search_loader.as_ref() else {
+            return;
+        };
+        self.thread_search_pending = true;
+        loader(ThreadSearchRequest {
+            query: self.query.clone(),
+            default_provider: self.default_provider.clone(),
+        });
+    }
+
     fn continue_search_if_needed(&mut self) {
         let Some(token) = self.search_state.active_token() else {
             return;
@@ -821,6 +971,35 @@ fn head_to_row(item: &ThreadItem) -> Row {
         updated_at,
         cwd: item.cwd.clone(),
         git_branch: item.git_branch.clone(),
+        search_match: None,
+    }
+}
+
+fn search_hit_to_row(hit: ThreadSearchHit) -> Row {
+    let metadata = hit.metadata;
+    let preview = metadata
+        .first_user_message
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| tr("(no message yet)", "（暂无消息）").to_string());
+    // Snippets may span several lines of the original message.
+    let snippet = hit.snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    Row {
+        path: metadata.rollout_path,
+        preview,
+        thread_id: Some(metadata.id),
+        thread_name: None,
+        created_at: Some(metadata.created_at),
+        updated_at: Some(metadata.updated_at),
+        cwd: Some(metadata.cwd),
+        git_branch: metadata.git_branch,
+        search_match: Some(SearchMatch {
+            snippet,
+            nth_user_message: hit.turn_index,
+        }),
     }
 }
 
@@ -1040,6 +1219,7 @@ fn render_list(
 fn render_empty_state_line(state: &PickerState) -> Line<'static> {
     if !state.query.is_empty() {
         if state.search_state.is_active()
+            || state.thread_search_pending
             || (state.pagination.loading.is_pending() && state.pagination.next_cursor.is_some())
         {
             return vec!["Searching…".italic().dim()].into();
@@ -1557,6 +1737,7 @@ mod tests {
             updated_at: None,
             cwd: None,
             git_branch: None,
+            search_match: None,
         };
 
         assert_eq!(row.display_preview(), "My session");
@@ -1591,6 +1772,7 @@ mod tests {
                 updated_at: Some(now - Duration::seconds(42)),
                 cwd: None,
                 git_branch: None,
+                search_match: None,
             },
             Row {
                 path: PathBuf::from("/tmp/b.jsonl"),
@@ -1601,6 +1783,7 @@ mod tests {
                 updated_at: Some(now - Duration::minutes(35)),
                 cwd: None,
                 git_branch: None,
+                search_match: None,
             },
             Row {
                 path: PathBuf::from("/tmp/c.jsonl"),
@@ -1611,6 +1794,7 @@ mod tests {
                 updated_at: Some(now - Duration::hours(2)),
                 cwd: None,
                 git_branch: None,
+                search_match: None,
             },
         ];
         state.all_rows = rows.clone();
@@ -1869,6 +2053,7 @@ mod tests {
                 updated_at: Some(now - Duration::days(2)),
                 cwd: None,
                 git_branch: None,
+                search_match: None,
             },
             Row {
                 path: PathBuf::from("/tmp/b.jsonl"),
@@ -1879,6 +2064,7 @@ mod tests {
                 updated_at: Some(now - Duration::days(3)),
                 cwd: None,
                 git_branch: None,
+                search_match: None,
             },
         ];
         state.all_rows = rows.clone();
@@ -2294,4 +2480,110 @@ mod tests {
         assert!(!state.search_state.is_active());
         assert!(state.pagination.reached_scan_cap);
     }
+
+    fn search_hit(path: &str, snippet: &str, turn_index: usize) -> ThreadSearchHit {
+        let ts = DateTime::parse_from_rfc3339("2025-01-02T00:00:00Z")
+            .expect("timestamp")
+            .with_timezone(&Utc);
+        ThreadSearchHit {
+            metadata: codex_state::ThreadMetadata {
+                id: ThreadId::new(),
+                rollout_path: PathBuf::from(path),
+                created_at: ts,
+                updated_at: ts,
+                source: "cli".to_string(),
+                model_provider: "openai".to_string(),
+                cwd: PathBuf::from("/tmp"),
+                cli_version: "0.0.0".to_string(),
+                title: String::new(),
+                sandbox_policy: "read-only".to_string(),
+                approval_mode: "on-request".to_string(),
+                tokens_used: 0,
+                first_user_message: Some("fix the tests".to_string()),
+                archived_at: None,
+                git_sha: None,
+                git_branch: None,
+                git_origin_url: None,
+            },
+            turn_id: None,
+            turn_index,
+            kind: codex_core::state_db::ThreadSearchKind::Assistant,
+            snippet: snippet.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn thread_search_hits_are_listed_first_and_resume_at_turn() {
+        let loader: PageLoader = Arc::new(|_| {});
+        let searches: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let search_sink = searches.clone();
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            loader,
+            String::from("openai"),
+            true,
+            None,
+            SessionPickerAction::Resume,
+        );
+        state.thread_search_loader = Some(Arc::new(move |req: ThreadSearchRequest| {
+            search_sink.lock().unwrap().push(req.query);
+        }));
+        state.reset_pagination();
+        state.ingest_page(page(
+            vec![
+                make_item("/tmp/local.jsonl", "2025-01-01T00:00:00Z", "flaky test"),
+                make_item("/tmp/both.jsonl", "2025-01-01T00:00:00Z", "flaky again"),
+            ],
+            None,
+            2,
+            false,
+        ));
+
+        state.set_query("flaky".to_string());
+        assert_eq!(*searches.lock().unwrap(), vec!["flaky".to_string()]);
+        assert!(state.thread_search_pending);
+
+        // Results for an outdated query are ignored.
+        state
+            .handle_background_event(BackgroundEvent::SearchLoaded {
+                query: "flak".to_string(),
+                hits: vec![search_hit("/tmp/stale.jsonl", "stale", 0)],
+            })
+            .await
+            .unwrap();
+        assert!(state.thread_search_pending);
+
+        state
+            .handle_background_event(BackgroundEvent::SearchLoaded {
+                query: "flaky".to_string(),
+                hits: vec![search_hit(
+                    "/tmp/both.jsonl",
+                    "fixed the flaky\nmigration",
+                    2,
+                )],
+            })
+            .await
+            .unwrap();
+
+        assert!(!state.thread_search_pending);
+        let previews: Vec<&str> = state
+            .filtered_rows
+            .iter()
+            .map(Row::display_preview)
+            .collect();
+        assert_eq!(previews, vec!["fixed the flaky migration", "flaky test"]);
+
+        let selection = state
+            .handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+            .await
+            .unwrap();
+        assert!(matches!(
+            selection,
+            Some(SessionSelection::ResumeAtTurn {
+                ref path,
+                nth_user_message: 2,
+            }) if path == Path::new("/tmp/both.jsonl")
+        ));
+    }
 }
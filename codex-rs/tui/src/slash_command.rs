@@ -35,6 +35,7 @@ pub enum SlashCommand {
     Agent,
     // Undo,
     Diff,
+    Checkpoints,
     Mention,
     Status,
     DebugConfig,
@@ -78,6 +79,10 @@ impl SlashCommand {
             // SlashCommand::Undo => "ask Codex to undo a turn",
             SlashCommand::Quit | SlashCommand::Exit => tr("exit Codex", "退出 Codex"),
             SlashCommand::Diff => tr("show git diff (including untracked files)", "显示 git diff（含未跟踪文件）"),
+            SlashCommand::Checkpoints => tr(
+                "restore files or hunks from an earlier turn",
+                "从之前的轮次恢复文件或片段",
+            ),
             SlashCommand::Mention => tr("mention a file", "引用文件"),
             SlashCommand::Skills => tr(
                 "use skills to improve how Codex performs specific tasks",
@@ -151,6 +156,7 @@ impl SlashCommand {
             | SlashCommand::Experimental
             | SlashCommand::Review
             | SlashCommand::Plan
+            | SlashCommand::Checkpoints
             | SlashCommand::Logout
             | SlashCommand::MemoryDrop
             | SlashCommand::MemoryUpdate => false,
@@ -8,7 +8,6 @@ use std::time::Duration;
 use std::time::Instant;
 
 use codex_core::protocol::Op;
-use crossterm::event::KeyCode;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Stylize;
@@ -22,7 +21,8 @@ use unicode_width::UnicodeWidthStr;
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 use crate::exec_cell::spinner;
-use crate::key_hint;
+use crate::keymap::KeymapAction;
+use crate::keymap::keymap;
 use crate::render::renderable::Renderable;
 use crate::shimmer::shimmer_spans;
 use crate::text_formatting::capitalize_first;
@@ -234,10 +234,12 @@ impl Renderable for StatusIndicatorWidget {
             spans.push(self.header.clone().into());
         }
         spans.push(" ".into());
-        if self.show_interrupt_hint {
+        if self.show_interrupt_hint
+            && let Some(interrupt_key) = keymap().hint(KeymapAction::Interrupt)
+        {
             spans.extend(vec![
                 format!("({pretty_elapsed} • ").dim(),
-                key_hint::plain(KeyCode::Esc).into(),
+                interrupt_key.into(),
                 " to interrupt)".dim(),
             ]);
         } else {
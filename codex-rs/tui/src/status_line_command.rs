@@ -0,0 +1,322 @@
+//! Status line segments backed by shell commands from `[tui.status_line_commands]`.
+//!
+//! Commands run in the background and their output is cached here, so the
+//! status line renders the last known value without waiting on a process.
+//! A command runs once when its segment first appears, again every
+//! `interval_secs` if set, and after each turn when
+//! `refresh_on_turn_complete` is enabled.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use codex_core::config::types::StatusLineCommand;
+use codex_utils_pty::process_group::kill_child_process_group;
+use tokio::io::AsyncReadExt;
+use tokio::process::Child;
+use tokio::process::Command;
+use tokio::task::JoinHandle;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_millis(2000);
+/// Only the first line is shown, so there is no reason to buffer more.
+const MAX_OUTPUT_BYTES: usize = 4096;
+
+/// Why a command segment is being run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StatusLineCommandTrigger {
+    /// The segment has no output yet.
+    FirstRun,
+    /// The previous run finished and the command has an interval.
+    Interval,
+    /// A turn just completed.
+    TurnComplete,
+}
+
+/// Cached outputs and in-flight runs for command segments.
+#[derive(Debug, Default)]
+pub(crate) struct StatusLineCommandState {
+    outputs: HashMap<String, String>,
+    started: HashSet<String>,
+    pending: HashMap<String, PendingRun>,
+}
+
+#[derive(Debug)]
+struct PendingRun {
+    handle: JoinHandle<()>,
+    /// True while the run waits out `interval_secs` before starting.
+    delayed: bool,
+}
+
+impl StatusLineCommandState {
+    /// The last output of `name`, or `None` when it has not produced any.
+    pub(crate) fn output(&self, name: &str) -> Option<String> {
+        self.outputs
+            .get(name)
+            .filter(|output| !output.is_empty())
+            .cloned()
+    }
+
+    /// A copy of all cached outputs, for the setup popup preview.
+    pub(crate) fn outputs(&self) -> HashMap<String, String> {
+        self.outputs.clone()
+    }
+
+    pub(crate) fn is_pending(&self, name: &str) -> bool {
+        self.pending.contains_key(name)
+    }
+
+    /// Starts a background run of `name` if `trigger` applies to it and no run
+    /// is already in flight. Interval runs wait out the interval first; an
+    /// immediate trigger replaces a run that is still waiting.
+    pub(crate) fn request(
+        &mut self,
+        name: &str,
+        command: &StatusLineCommand,
+        trigger: StatusLineCommandTrigger,
+        cwd: &Path,
+        tx: &AppEventSender,
+    ) {
+        let delay = match trigger {
+            StatusLineCommandTrigger::FirstRun if self.started.contains(name) => return,
+            StatusLineCommandTrigger::FirstRun => Duration::ZERO,
+            StatusLineCommandTrigger::Interval => match command.interval_secs {
+                Some(secs) if secs > 0 => Duration::from_secs(secs),
+                _ => return,
+            },
+            StatusLineCommandTrigger::TurnComplete if command.refresh_on_turn_complete => {
+                Duration::ZERO
+            }
+            StatusLineCommandTrigger::TurnComplete => return,
+        };
+        let delayed = !delay.is_zero();
+        if let Some(run) = self.pending.get(name) {
+            if delayed || !run.delayed {
+                return;
+            }
+            run.handle.abort();
+        }
+        self.started.insert(name.to_string());
+        let key = name.to_string();
+        let name = name.to_string();
+        let shell_command = command.command.clone();
+        let timeout = command
+            .timeout_ms
+            .map_or(DEFAULT_TIMEOUT, Duration::from_millis);
+        let cwd = cwd.to_path_buf();
+        let tx = tx.clone();
+        let handle = tokio::spawn(async move {
+            if delayed {
+                tokio::time::sleep(delay).await;
+            }
+            let output = run_status_line_command(&shell_command, &cwd, timeout).await;
+            tx.send(AppEvent::StatusLineCommandUpdated { name, output });
+        });
+        self.pending.insert(key, PendingRun { handle, delayed });
+    }
+
+    /// Records the result of a run. `None` (a timeout or a command that could
+    /// not start) keeps the previous output.
+    pub(crate) fn set_output(&mut self, name: &str, output: Option<String>) {
+        self.pending.remove(name);
+        if let Some(output) = output {
+            self.outputs.insert(name.to_string(), output);
+        }
+    }
+
+    /// Forgets commands that are no longer part of the status line so they
+    /// start fresh if they are added back.
+    pub(crate) fn retain(&mut self, names: &HashSet<&str>) {
+        self.outputs.retain(|name, _| names.contains(name.as_str()));
+        self.started.retain(|name| names.contains(name.as_str()));
+        self.pending.retain(|name, run| {
+            let keep = names.contains(name.as_str());
+            if !keep {
+                run.handle.abort();
+            }
+            keep
+        });
+    }
+}
+
+/// Runs `command` through the platform shell and returns the first non-empty
+/// line of its stdout, trimmed and stripped of escape sequences and control
+/// characters. Only the first [`MAX_OUTPUT_BYTES`] of stdout are read. A
+/// failing command yields an empty string; a timeout or spawn error yields
+/// `None`. On Unix the command runs in its own process group, which is killed
+/// on timeout so the shell's children do not outlive it.
+pub(crate) async fn run_status_line_command(
+    command: &str,
+    cwd: &Path,
+    timeout: Duration,
+) -> Option<String> {
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C");
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c");
+        cmd
+    };
+    cmd.arg(command)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true);
+    #[cfg(unix)]
+    cmd.process_group(0);
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(err) => {
+            tracing::debug!(error = %err, command, "status line command failed to start");
+            return None;
+        }
+    };
+    let (success, stdout) = match tokio::time::timeout(timeout, read_output(&mut child)).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(err)) => {
+            tracing::debug!(error = %err, command, "status line command failed");
+            let _ = kill_child_process_group(&mut child);
+            return None;
+        }
+        Err(_) => {
+            tracing::debug!(command, "status line command timed out");
+            let _ = kill_child_process_group(&mut child);
+            return None;
+        }
+    };
+    if !success {
+        return Some(String::new());
+    }
+    let stdout = String::from_utf8_lossy(&stdout);
+    let first_line = stdout
+        .lines()
+        .map(|line| sanitize_output(line).trim().to_string())
+        .find(|line| !line.is_empty());
+    Some(first_line.unwrap_or_default())
+}
+
+/// Reads up to [`MAX_OUTPUT_BYTES`] of stdout and waits for the command.
+/// Output that reaches the cap stops the command and counts as a success, so
+/// a chatty command still shows its first line.
+async fn read_output(child: &mut Child) -> std::io::Result<(bool, Vec<u8>)> {
+    let mut stdout = Vec::new();
+    if let Some(pipe) = child.stdout.take() {
+        pipe.take(MAX_OUTPUT_BYTES as u64)
+            .read_to_end(&mut stdout)
+            .await?;
+    }
+    let truncated = stdout.len() >= MAX_OUTPUT_BYTES;
+    if truncated {
+        let _ = kill_child_process_group(child);
+    }
+    let status = child.wait().await?;
+    Ok((truncated || status.success(), stdout))
+}
+
+/// Drops ANSI escape sequences and control characters so command output
+/// cannot move the cursor, restyle or otherwise corrupt the terminal. Tabs
+/// become spaces.
+fn sanitize_output(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\u{1b}' => match chars.next() {
+                Some('[') => skip_csi(&mut chars),
+                // OSC, DCS, SOS, PM and APC strings run until BEL or ST.
+                Some(']' | 'P' | 'X' | '^' | '_') => {
+                    while let Some(ch) = chars.next() {
+                        if ch == '\u{7}' || (ch == '\u{1b}' && chars.next_if_eq(&'\\').is_some()) {
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            '\u{9b}' => skip_csi(&mut chars),
+            '\t' => out.push(' '),
+            ch if ch.is_control() => {}
+            ch => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Skips the parameters and final byte of a control sequence.
+fn skip_csi(chars: &mut impl Iterator<Item = char>) {
+    for ch in chars {
+        if ('\u{40}'..='\u{7e}').contains(&ch) {
+            break;
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    async fn run(command: &str, timeout: Duration) -> Option<String> {
+        let cwd = std::env::temp_dir();
+        run_status_line_command(command, &cwd, timeout).await
+    }
+
+    #[tokio::test]
+    async fn shows_first_line_of_trimmed_stdout() {
+        assert_eq!(
+            run("printf '  PROJ-123  \\nsecond\\n'", DEFAULT_TIMEOUT).await,
+            Some("PROJ-123".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn strips_escape_sequences_and_caps_output() {
+        assert_eq!(
+            run(
+                "printf '\\033[31mred\\033[0m\\033]0;title\\007\\tok\\r\\033[2J\\n'",
+                DEFAULT_TIMEOUT
+            )
+            .await,
+            Some("red ok".to_string())
+        );
+        assert_eq!(
+            run("yes PROJ-123", DEFAULT_TIMEOUT).await,
+            Some("PROJ-123".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn timeouts_kill_the_commands_children() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let marker = dir.path().join("marker");
+        let command = format!("(sleep 1; touch '{}') & wait", marker.display());
+
+        assert_eq!(run(&command, Duration::from_millis(100)).await, None);
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+
+        assert!(!marker.exists());
+    }
+
+    #[tokio::test]
+    async fn failing_commands_clear_and_timeouts_keep_output() {
+        assert_eq!(
+            run("echo oops; exit 1", DEFAULT_TIMEOUT).await,
+            Some(String::new())
+        );
+        assert_eq!(run("sleep 5", Duration::from_millis(50)).await, None);
+
+        let mut state = StatusLineCommandState::default();
+        state.set_output("kube", Some("prod".to_string()));
+        state.set_output("kube", None);
+        assert_eq!(state.output("kube"), Some("prod".to_string()));
+        state.set_output("kube", Some(String::new()));
+        assert_eq!(state.output("kube"), None);
+    }
+}
@@ -0,0 +1,310 @@
+//! Colors for semantic roles, configurable through `[tui.theme]`.
+//!
+//! A theme starts from a built-in palette or a theme file in
+//! `$CODEX_HOME/themes/<name>.toml`, then applies the per-role colors from
+//! `[tui.theme]`. It is resolved once at startup and read through [`theme`].
+//! Until [`init`] runs (for example in tests) the default theme is used.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use strum::IntoEnumIterator;
+use strum_macros::AsRefStr;
+use strum_macros::EnumIter;
+use strum_macros::EnumString;
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Roles whose color can be changed by a theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, EnumIter, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum ThemeRole {
+    /// Background behind user messages. Unset means a shade blended from the
+    /// terminal background.
+    UserMessageBg,
+    DiffAdd,
+    DiffRemove,
+    ExecSuccess,
+    ExecFailure,
+    /// Highlighted option in approval prompts and selection lists.
+    Selection,
+    /// Secondary text such as diff gutters and command output.
+    Dim,
+    Link,
+}
+
+impl ThemeRole {
+    fn with_color(self, style: Style, color: Color) -> Style {
+        match self {
+            ThemeRole::UserMessageBg => Style::default().bg(color),
+            // A colored dim role replaces the DIM modifier instead of
+            // stacking on it, since dimmed colors are what it is fixing.
+            ThemeRole::Dim => Style::default().fg(color),
+            _ => style.fg(color),
+        }
+    }
+}
+
+/// Themes that ship with the TUI, selected with `[tui.theme] name`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, EnumIter, AsRefStr)]
+#[strum(serialize_all = "kebab-case")]
+pub(crate) enum BuiltinTheme {
+    Default,
+    HighContrast,
+    /// Keeps additions and removals apart without relying on red and green.
+    Colorblind,
+}
+
+impl BuiltinTheme {
+    fn style(self, role: ThemeRole) -> Style {
+        let style = Style::default();
+        match (self, role) {
+            (_, ThemeRole::UserMessageBg) => style,
+            (BuiltinTheme::Default, ThemeRole::DiffAdd | ThemeRole::ExecSuccess) => {
+                style.fg(Color::Green)
+            }
+            (BuiltinTheme::Default, ThemeRole::DiffRemove | ThemeRole::ExecFailure) => {
+                style.fg(Color::Red)
+            }
+            (BuiltinTheme::Default | BuiltinTheme::Colorblind, ThemeRole::Selection) => {
+                style.fg(Color::Cyan)
+            }
+            (BuiltinTheme::Default | BuiltinTheme::Colorblind, ThemeRole::Dim) => {
+                style.add_modifier(Modifier::DIM)
+            }
+            (BuiltinTheme::Default | BuiltinTheme::Colorblind, ThemeRole::Link) => {
+                style.fg(Color::Cyan).add_modifier(Modifier::UNDERLINED)
+            }
+            (BuiltinTheme::HighContrast, ThemeRole::DiffAdd) => {
+                style.fg(Color::LightGreen).add_modifier(Modifier::BOLD)
+            }
+            (BuiltinTheme::HighContrast, ThemeRole::DiffRemove) => {
+                style.fg(Color::LightRed).add_modifier(Modifier::BOLD)
+            }
+            (BuiltinTheme::HighContrast, ThemeRole::ExecSuccess) => style.fg(Color::LightGreen),
+            (BuiltinTheme::HighContrast, ThemeRole::ExecFailure) => style.fg(Color::LightRed),
+            (BuiltinTheme::HighContrast, ThemeRole::Selection) => {
+                style.fg(Color::Cyan).add_modifier(Modifier::REVERSED)
+            }
+            // Dimmed text is the first thing to disappear on low-contrast
+            // displays, so secondary text keeps the default foreground.
+            (BuiltinTheme::HighContrast, ThemeRole::Dim) => style,
+            (BuiltinTheme::HighContrast, ThemeRole::Link) => style
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::UNDERLINED),
+            (BuiltinTheme::Colorblind, ThemeRole::DiffAdd | ThemeRole::ExecSuccess) => {
+                style.fg(Color::Cyan)
+            }
+            (BuiltinTheme::Colorblind, ThemeRole::DiffRemove | ThemeRole::ExecFailure) => {
+                style.fg(Color::Magenta)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Theme {
+    styles: HashMap<ThemeRole, Style>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::builtin(BuiltinTheme::Default)
+    }
+}
+
+impl Theme {
+    pub(crate) fn builtin(builtin: BuiltinTheme) -> Self {
+        Self {
+            styles: ThemeRole::iter()
+                .map(|role| (role, builtin.style(role)))
+                .collect(),
+        }
+    }
+
+    /// Builds the theme named in `[tui.theme]` and applies its color
+    /// overrides, returning warnings for unknown themes, unknown roles and
+    /// unparsable colors.
+    pub(crate) fn from_config(
+        name: Option<&str>,
+        codex_home: &Path,
+        colors: &BTreeMap<String, String>,
+    ) -> (Self, Vec<String>) {
+        let mut warnings = Vec::new();
+        let mut theme = Self::default();
+        if let Some(name) = name {
+            if let Ok(builtin) = BuiltinTheme::from_str(name) {
+                theme = Self::builtin(builtin);
+            } else {
+                let path = codex_home.join("themes").join(format!("{name}.toml"));
+                match read_theme_file(&path) {
+                    Ok(file_colors) => {
+                        let source = path.display().to_string();
+                        theme.apply_colors(&file_colors, &source, &mut warnings);
+                    }
+                    Err(err) => warnings.push(format!(
+                        "[tui.theme]: cannot load theme `{name}` from {}: {err}",
+                        path.display()
+                    )),
+                }
+            }
+        }
+        theme.apply_colors(colors, "[tui.theme]", &mut warnings);
+        (theme, warnings)
+    }
+
+    fn apply_colors(
+        &mut self,
+        colors: &BTreeMap<String, String>,
+        source: &str,
+        warnings: &mut Vec<String>,
+    ) {
+        for (name, color) in colors {
+            let Ok(role) = ThemeRole::from_str(name) else {
+                warnings.push(format!("{source}: unknown theme role `{name}`"));
+                continue;
+            };
+            let Ok(color) = Color::from_str(color.trim()) else {
+                warnings.push(format!(
+                    "{source}: cannot parse color `{color}` for `{name}`"
+                ));
+                continue;
+            };
+            let style = role.with_color(self.style(role), color);
+            self.styles.insert(role, style);
+        }
+    }
+
+    pub(crate) fn style(&self, role: ThemeRole) -> Style {
+        self.styles.get(&role).copied().unwrap_or_default()
+    }
+
+    /// The configured user message background, or `None` to blend one from
+    /// the terminal background.
+    pub(crate) fn user_message_bg(&self) -> Option<Color> {
+        self.style(ThemeRole::UserMessageBg).bg
+    }
+}
+
+fn read_theme_file(path: &Path) -> Result<BTreeMap<String, String>, String> {
+    let contents = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    toml::from_str(&contents).map_err(|err| err.to_string())
+}
+
+/// Resolves `[tui.theme]` for the rest of the process and returns the
+/// warnings to show. Later calls keep the first theme.
+pub(crate) fn init(
+    name: Option<&str>,
+    codex_home: &Path,
+    colors: &BTreeMap<String, String>,
+) -> Vec<String> {
+    let (theme, warnings) = Theme::from_config(name, codex_home, colors);
+    let _ = THEME.set(theme);
+    warnings
+}
+
+#[cfg(test)]
+thread_local! {
+    static TEST_THEME: std::cell::Cell<Option<&'static Theme>> =
+        const { std::cell::Cell::new(None) };
+}
+
+pub(crate) fn theme() -> &'static Theme {
+    #[cfg(test)]
+    if let Some(theme) = TEST_THEME.with(std::cell::Cell::get) {
+        return theme;
+    }
+    THEME.get_or_init(Theme::default)
+}
+
+/// Runs `f` with [`theme`] returning `theme` on the current thread, so tests
+/// can render widgets under a non-default theme.
+#[cfg(test)]
+pub(crate) fn with_theme<R>(theme: Theme, f: impl FnOnce() -> R) -> R {
+    let theme: &'static Theme = Box::leak(Box::new(theme));
+    let previous = TEST_THEME.with(|cell| cell.replace(Some(theme)));
+    let result = f();
+    TEST_THEME.with(|cell| cell.set(previous));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn config_colors_override_the_named_theme() {
+        let codex_home = tempdir().expect("tempdir");
+        let colors = BTreeMap::from([
+            ("diff_add".to_string(), "light-blue".to_string()),
+            ("dim".to_string(), "gray".to_string()),
+            ("user_message_bg".to_string(), "black".to_string()),
+        ]);
+
+        let (theme, warnings) =
+            Theme::from_config(Some("high-contrast"), codex_home.path(), &colors);
+
+        assert_eq!(warnings, Vec::<String>::new());
+        assert_eq!(
+            theme.style(ThemeRole::DiffAdd),
+            Style::default()
+                .fg(Color::LightBlue)
+                .add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(
+            theme.style(ThemeRole::Dim),
+            Style::default().fg(Color::Gray)
+        );
+        assert_eq!(theme.user_message_bg(), Some(Color::Black));
+        assert_eq!(
+            theme.style(ThemeRole::DiffRemove),
+            BuiltinTheme::HighContrast.style(ThemeRole::DiffRemove)
+        );
+    }
+
+    #[test]
+    fn loads_theme_files_from_codex_home() {
+        let codex_home = tempdir().expect("tempdir");
+        let themes = codex_home.path().join("themes");
+        std::fs::create_dir_all(&themes).expect("create themes dir");
+        std::fs::write(
+            themes.join("ocean.toml"),
+            "diff_add = \"cyan\"\nsparkles = \"red\"\n",
+        )
+        .expect("write theme");
+
+        let (theme, warnings) =
+            Theme::from_config(Some("ocean"), codex_home.path(), &BTreeMap::new());
+
+        assert_eq!(
+            theme.style(ThemeRole::DiffAdd),
+            Style::default().fg(Color::Cyan)
+        );
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("unknown theme role `sparkles`"));
+    }
+
+    #[test]
+    fn warns_and_falls_back_for_missing_themes_and_bad_colors() {
+        let codex_home = tempdir().expect("tempdir");
+        let colors = BTreeMap::from([("link".to_string(), "not-a-color".to_string())]);
+
+        let (theme, warnings) = Theme::from_config(Some("missing"), codex_home.path(), &colors);
+
+        assert_eq!(theme, Theme::default());
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("cannot load theme `missing`"));
+        assert_eq!(
+            warnings[1],
+            "[tui.theme]: cannot parse color `not-a-color` for `link`"
+        );
+    }
+}
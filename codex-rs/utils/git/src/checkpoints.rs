@@ -0,0 +1,261 @@
+//! Read access to ghost commits for browsing per-turn checkpoints and
+//! restoring individual files from them.
+
+use std::ffi::OsString;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::GhostCommit;
+use crate::GhostSnapshotConfig;
+use crate::GitToolingError;
+use crate::ghost_commits::snapshot_untracked_files;
+use crate::operations::ensure_git_repository;
+use crate::operations::repo_subdir;
+use crate::operations::resolve_repository_root;
+use crate::operations::run_git_for_bytes;
+use crate::operations::run_git_for_stdout;
+use crate::operations::run_git_for_stdout_all;
+
+/// Contents of a file as recorded by a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotFile {
+    /// The file did not exist when the snapshot was taken.
+    Missing,
+    Contents(Vec<u8>),
+}
+
+/// Absolute paths whose contents differ between two snapshots, limited to
+/// `repo_path` when it is a subdirectory of the repository. Pass a fresh
+/// snapshot as `to` to compare against the working tree.
+pub fn ghost_commit_changed_paths(
+    repo_path: &Path,
+    from: &GhostCommit,
+    to: &GhostCommit,
+) -> Result<Vec<PathBuf>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    let repo_prefix = repo_subdir(repo_root.as_path(), repo_path);
+
+    let mut args = vec![
+        OsString::from("diff"),
+        OsString::from("--name-only"),
+        OsString::from("--no-renames"),
+        OsString::from("-z"),
+        OsString::from(from.id()),
+        OsString::from(to.id()),
+    ];
+    if let Some(prefix) = repo_prefix.as_deref() {
+        args.push(OsString::from("--"));
+        args.push(prefix.as_os_str().to_os_string());
+    }
+    let output = run_git_for_stdout_all(repo_root.as_path(), args, None)?;
+    Ok(output
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(|path| repo_root.join(path))
+        .collect())
+}
+
+/// Absolute paths that may differ between `commit` and the working tree,
+/// found without taking a new snapshot: tracked paths whose contents differ
+/// and the untracked files a snapshot taken now would record. Untracked files
+/// are listed even when `commit` holds the same contents, so callers compare
+/// contents before reporting a change.
+pub fn ghost_commit_worktree_changed_paths(
+    repo_path: &Path,
+    commit: &GhostCommit,
+    ghost_snapshot: &GhostSnapshotConfig,
+) -> Result<Vec<PathBuf>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    let repo_prefix = repo_subdir(repo_root.as_path(), repo_path);
+
+    let mut args = vec![
+        OsString::from("diff"),
+        OsString::from("--name-only"),
+        OsString::from("--no-renames"),
+        OsString::from("-z"),
+        OsString::from(commit.id()),
+    ];
+    if let Some(prefix) = repo_prefix.as_deref() {
+        args.push(OsString::from("--"));
+        args.push(prefix.as_os_str().to_os_string());
+    }
+    let output = run_git_for_stdout_all(repo_root.as_path(), args, None)?;
+    let mut paths = output
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .collect::<Vec<_>>();
+    paths.extend(snapshot_untracked_files(
+        repo_root.as_path(),
+        repo_prefix.as_deref(),
+        ghost_snapshot,
+    )?);
+    paths.sort();
+    paths.dedup();
+    Ok(paths.into_iter().map(|path| repo_root.join(path)).collect())
+}
+
+/// Contents of `path` in `commit`. `path` is either absolute or relative to
+/// `repo_path`, and may not contain `..` or point into `.git`.
+pub fn ghost_commit_file(
+    repo_path: &Path,
+    commit: &GhostCommit,
+    path: &Path,
+) -> Result<SnapshotFile, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    let relative = repo_relative_path(repo_root.as_path(), repo_path, path)?;
+    let relative = relative.to_string_lossy().replace('\\', "/");
+
+    let listing = run_git_for_stdout(
+        repo_root.as_path(),
+        [
+            "ls-tree",
+            "--name-only",
+            commit.id(),
+            "--",
+            relative.as_str(),
+        ],
+        None,
+    )?;
+    if listing.is_empty() {
+        return Ok(SnapshotFile::Missing);
+    }
+    let spec = format!("{}:{relative}", commit.id());
+    let contents = run_git_for_bytes(repo_root.as_path(), ["cat-file", "blob", &spec], None)?;
+    Ok(SnapshotFile::Contents(contents))
+}
+
+fn repo_relative_path(
+    repo_root: &Path,
+    repo_path: &Path,
+    path: &Path,
+) -> Result<PathBuf, GitToolingError> {
+    let absolute = repo_path.join(path);
+    if path.components().any(|component| {
+        component == Component::ParentDir || component == Component::Normal(".git".as_ref())
+    }) {
+        return Err(GitToolingError::PathEscapesRepository { path: absolute });
+    }
+    if let Ok(relative) = absolute.strip_prefix(repo_root) {
+        return Ok(relative.to_path_buf());
+    }
+    // `git rev-parse --show-toplevel` reports the resolved path, so compare
+    // against the canonical form when the caller used a symlinked one.
+    let canonical_parent = absolute
+        .parent()
+        .map(Path::canonicalize)
+        .transpose()?
+        .unwrap_or_default();
+    let file_name = absolute.file_name().unwrap_or_default();
+    canonical_parent
+        .join(file_name)
+        .strip_prefix(repo_root)
+        .map(Path::to_path_buf)
+        .map_err(|_| GitToolingError::PathEscapesRepository { path: absolute })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CreateGhostCommitOptions;
+    use crate::create_ghost_commit_with_report;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    fn run_git_in(repo_path: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(repo_path)
+            .args(args)
+            .status()
+            .expect("git command");
+        assert!(status.success(), "git command failed: {args:?}");
+    }
+
+    #[test]
+    fn lists_changed_paths_and_reads_checkpoint_contents() -> Result<(), GitToolingError> {
+        let temp = tempdir()?;
+        let repo = temp.path();
+        run_git_in(repo, &["init", "--initial-branch=main"]);
+        run_git_in(repo, &["config", "core.autocrlf", "false"]);
+        fs::write(repo.join("tracked.txt"), "before\n")?;
+        fs::write(repo.join("same.txt"), "same\n")?;
+        run_git_in(repo, &["add", "."]);
+        run_git_in(
+            repo,
+            &[
+                "-c",
+                "user.name=Tester",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                "init",
+            ],
+        );
+        fs::write(repo.join("notes.txt"), "untracked\n")?;
+
+        let options =
+            CreateGhostCommitOptions::new(repo).ghost_snapshot(GhostSnapshotConfig::default());
+        let (checkpoint, _) = create_ghost_commit_with_report(&options)?;
+
+        fs::write(repo.join("tracked.txt"), "after\n")?;
+        fs::write(repo.join("notes.txt"), "edited\n")?;
+        fs::write(repo.join("created.txt"), "new\n")?;
+
+        let (current, _) = create_ghost_commit_with_report(&options)?;
+        let mut changed = ghost_commit_changed_paths(repo, &checkpoint, &current)?
+            .into_iter()
+            .map(|path| {
+                path.file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+        changed.sort();
+        assert_eq!(changed, vec!["created.txt", "notes.txt", "tracked.txt"]);
+        // Without a fresh snapshot, the untracked notes file is listed whether
+        // or not it changed; the other paths come from the diff.
+        let worktree_changed = ghost_commit_worktree_changed_paths(
+            repo,
+            &checkpoint,
+            &GhostSnapshotConfig::default(),
+        )?
+        .into_iter()
+        .map(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+        assert_eq!(
+            worktree_changed,
+            vec!["created.txt", "notes.txt", "tracked.txt"]
+        );
+
+        assert_eq!(
+            ghost_commit_file(repo, &checkpoint, Path::new("tracked.txt"))?,
+            SnapshotFile::Contents(b"before\n".to_vec())
+        );
+        assert_eq!(
+            ghost_commit_file(repo, &checkpoint, &repo.join("notes.txt"))?,
+            SnapshotFile::Contents(b"untracked\n".to_vec())
+        );
+        assert_eq!(
+            ghost_commit_file(repo, &checkpoint, Path::new("created.txt"))?,
+            SnapshotFile::Missing
+        );
+        for escaping in ["../outside.txt", ".git/config"] {
+            assert!(matches!(
+                ghost_commit_file(repo, &checkpoint, Path::new(escaping)),
+                Err(GitToolingError::PathEscapesRepository { .. })
+            ));
+        }
+        Ok(())
+    }
+}
@@ -26,6 +26,10 @@ pub enum GitToolingError {
     NonRelativePath { path: PathBuf },
     #[error("path {path:?} escapes the repository root")]
     PathEscapesRepository { path: PathBuf },
+    #[error("file snapshot {path:?} no longer exists")]
+    FileSnapshotMissing { path: PathBuf },
+    #[error("file snapshot {path:?} is not inside {root:?}")]
+    FileSnapshotOutsideRoot { path: PathBuf, root: PathBuf },
     #[error("failed to process path inside worktree")]
     PathPrefix(#[from] std::path::StripPrefixError),
     #[error(transparent)]
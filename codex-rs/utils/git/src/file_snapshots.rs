@@ -0,0 +1,499 @@
+//! Snapshot backend for workspaces that are not git repositories.
+//!
+//! Instead of a ghost commit, a file snapshot keeps a pre-turn copy of every
+//! file the agent is about to write. Copies are taken lazily, the first time a
+//! path is written during the turn, and stored next to a JSON manifest in a
+//! directory owned by the caller (typically under `CODEX_HOME`). The manifest
+//! is written as soon as the snapshot starts, so a missing manifest means the
+//! snapshot was deleted rather than that nothing was captured.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::GhostCommit;
+use crate::GhostSnapshotConfig;
+use crate::GitToolingError;
+use crate::SnapshotFile;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const BLOBS_DIR: &str = "files";
+
+/// Pre-turn copies of the files written during one turn.
+#[derive(Debug)]
+pub struct FileSnapshot {
+    dir: PathBuf,
+    config: GhostSnapshotConfig,
+    manifest: Mutex<FileSnapshotManifest>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct FileSnapshotManifest {
+    entries: Vec<FileSnapshotEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FileSnapshotEntry {
+    path: PathBuf,
+    original: OriginalFile,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum OriginalFile {
+    /// The file did not exist before the turn; restoring removes it.
+    Missing,
+    /// The file's contents are stored in the blob with this name.
+    Stored {
+        blob: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        mode: Option<u32>,
+    },
+    /// The file exceeded the size limit and was not copied.
+    TooLarge { byte_size: u64 },
+}
+
+/// Files a capture or restore could not handle.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FileSnapshotReport {
+    /// Files larger than `ignore_large_untracked_files`; they are left as is on restore.
+    pub too_large: Vec<PathBuf>,
+    /// Files written after the snapshot already held `ignore_large_untracked_dirs` files.
+    pub over_file_limit: Vec<PathBuf>,
+}
+
+impl FileSnapshotReport {
+    pub fn is_empty(&self) -> bool {
+        self.too_large.is_empty() && self.over_file_limit.is_empty()
+    }
+}
+
+impl FileSnapshot {
+    /// Start an empty snapshot stored in `dir` and return the ghost commit
+    /// that records it in the conversation history.
+    pub fn create(
+        dir: &Path,
+        id: &str,
+        config: &GhostSnapshotConfig,
+    ) -> Result<(Self, GhostCommit), GitToolingError> {
+        let snapshot = Self {
+            dir: dir.to_path_buf(),
+            config: config.clone(),
+            manifest: Mutex::new(FileSnapshotManifest::default()),
+        };
+        snapshot.write_manifest(&FileSnapshotManifest::default())?;
+        let ghost_commit = GhostCommit::file_snapshot(id.to_string(), dir.to_path_buf());
+        Ok((snapshot, ghost_commit))
+    }
+
+    /// Copy the current contents of `paths` unless they were captured earlier
+    /// in this snapshot. Paths must be absolute.
+    pub fn capture(&self, paths: &[PathBuf]) -> Result<FileSnapshotReport, GitToolingError> {
+        let mut manifest = self
+            .manifest
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let mut seen: HashSet<PathBuf> = manifest
+            .entries
+            .iter()
+            .map(|entry| entry.path.clone())
+            .collect();
+        let max_files = self
+            .config
+            .ignore_large_untracked_dirs
+            .filter(|limit| *limit > 0)
+            .map(|limit| limit as usize);
+        let max_bytes = self
+            .config
+            .ignore_large_untracked_files
+            .filter(|limit| *limit > 0)
+            .map(|limit| limit as u64);
+
+        let mut report = FileSnapshotReport::default();
+        let mut changed = false;
+        for path in paths {
+            if !seen.insert(path.clone()) {
+                continue;
+            }
+            if max_files.is_some_and(|max| manifest.entries.len() >= max) {
+                report.over_file_limit.push(path.clone());
+                continue;
+            }
+            let original = match fs::metadata(path) {
+                Ok(metadata) if max_bytes.is_some_and(|max| metadata.len() > max) => {
+                    report.too_large.push(path.clone());
+                    OriginalFile::TooLarge {
+                        byte_size: metadata.len(),
+                    }
+                }
+                Ok(metadata) => {
+                    let blobs_dir = self.dir.join(BLOBS_DIR);
+                    fs::create_dir_all(&blobs_dir)?;
+                    let blob = manifest.entries.len().to_string();
+                    fs::copy(path, blobs_dir.join(&blob))?;
+                    OriginalFile::Stored {
+                        blob,
+                        mode: file_mode(&metadata),
+                    }
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => OriginalFile::Missing,
+                Err(err) => return Err(err.into()),
+            };
+            manifest.entries.push(FileSnapshotEntry {
+                path: path.clone(),
+                original,
+            });
+            changed = true;
+        }
+
+        if changed {
+            self.write_manifest(&manifest)?;
+        }
+        Ok(report)
+    }
+
+    fn write_manifest(&self, manifest: &FileSnapshotManifest) -> Result<(), GitToolingError> {
+        let contents = serde_json::to_vec_pretty(manifest).map_err(io::Error::other)?;
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.dir.join(MANIFEST_FILE), contents)?;
+        Ok(())
+    }
+}
+
+/// Put every file recorded in the snapshot at `dir` back into its pre-turn
+/// state and delete the snapshot. Files that were too large to copy are left
+/// untouched and returned in the report.
+///
+/// `dir` usually comes from conversation history, so it must resolve to a
+/// directory inside `root`, the directory holding the snapshots of the thread.
+pub fn restore_file_snapshot(
+    root: &Path,
+    dir: &Path,
+) -> Result<FileSnapshotReport, GitToolingError> {
+    let dir = resolve_snapshot_dir(root, dir)?;
+    let dir = dir.as_path();
+    let manifest = read_manifest(dir)?;
+
+    let mut report = FileSnapshotReport::default();
+    for entry in manifest.entries.iter().rev() {
+        match &entry.original {
+            OriginalFile::Missing => match fs::remove_file(&entry.path) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            },
+            OriginalFile::Stored { blob, mode } => {
+                if let Some(parent) = entry.path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(dir.join(BLOBS_DIR).join(blob), &entry.path)?;
+                set_file_mode(&entry.path, *mode)?;
+            }
+            OriginalFile::TooLarge { .. } => report.too_large.push(entry.path.clone()),
+        }
+    }
+
+    match fs::remove_dir_all(dir) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+    Ok(report)
+}
+
+/// Delete the turn snapshots under `root` (laid out as `<root>/<thread>/<turn>`)
+/// that were last written more than `max_age` ago, along with thread
+/// directories left empty.
+pub fn prune_file_snapshots(root: &Path, max_age: Duration) -> Result<(), GitToolingError> {
+    let threads = match fs::read_dir(root) {
+        Ok(threads) => threads,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err.into()),
+    };
+    let now = SystemTime::now();
+    for thread in threads {
+        let thread = thread?.path();
+        if !thread.is_dir() {
+            continue;
+        }
+        for turn in fs::read_dir(&thread)? {
+            let turn = turn?.path();
+            // The manifest is rewritten on every capture, so its mtime is the last write.
+            let modified = fs::metadata(turn.join(MANIFEST_FILE))
+                .or_else(|_| fs::metadata(&turn))?
+                .modified()?;
+            if now.duration_since(modified).is_ok_and(|age| age > max_age) {
+                fs::remove_dir_all(&turn)?;
+            }
+        }
+        if fs::read_dir(&thread)?.next().is_none() {
+            fs::remove_dir(&thread)?;
+        }
+    }
+    Ok(())
+}
+
+/// Paths recorded by the file snapshot at `dir`, in capture order.
+pub fn file_snapshot_paths(dir: &Path) -> Result<Vec<PathBuf>, GitToolingError> {
+    Ok(read_manifest(dir)?
+        .entries
+        .into_iter()
+        .map(|entry| entry.path)
+        .collect())
+}
+
+/// Pre-turn contents of `path` in the file snapshot at `dir`, or `None` when
+/// the snapshot did not copy the file.
+pub fn file_snapshot_file(
+    dir: &Path,
+    path: &Path,
+) -> Result<Option<SnapshotFile>, GitToolingError> {
+    let manifest = read_manifest(dir)?;
+    let Some(entry) = manifest
+        .entries
+        .into_iter()
+        .find(|entry| entry.path == path)
+    else {
+        return Ok(None);
+    };
+    match entry.original {
+        OriginalFile::Missing => Ok(Some(SnapshotFile::Missing)),
+        OriginalFile::Stored { blob, .. } => Ok(Some(SnapshotFile::Contents(fs::read(
+            dir.join(BLOBS_DIR).join(blob),
+        )?))),
+        OriginalFile::TooLarge { .. } => Ok(None),
+    }
+}
+
+fn read_manifest(dir: &Path) -> Result<FileSnapshotManifest, GitToolingError> {
+    let contents = fs::read(dir.join(MANIFEST_FILE)).map_err(|err| missing_snapshot(dir, err))?;
+    Ok(serde_json::from_slice(&contents).map_err(io::Error::other)?)
+}
+
+/// Canonical form of `dir`, which must lie strictly inside `root`.
+fn resolve_snapshot_dir(root: &Path, dir: &Path) -> Result<PathBuf, GitToolingError> {
+    let resolved = fs::canonicalize(dir).map_err(|err| missing_snapshot(dir, err))?;
+    let root = fs::canonicalize(root).map_err(|err| missing_snapshot(dir, err))?;
+    if resolved == root || !resolved.starts_with(&root) {
+        return Err(GitToolingError::FileSnapshotOutsideRoot {
+            path: dir.to_path_buf(),
+            root,
+        });
+    }
+    Ok(resolved)
+}
+
+fn missing_snapshot(dir: &Path, err: io::Error) -> GitToolingError {
+    if err.kind() == io::ErrorKind::NotFound {
+        GitToolingError::FileSnapshotMissing {
+            path: dir.to_path_buf(),
+        }
+    } else {
+        err.into()
+    }
+}
+
+#[cfg(unix)]
+fn file_mode(metadata: &fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode())
+}
+
+#[cfg(not(unix))]
+fn file_mode(_metadata: &fs::Metadata) -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+fn set_file_mode(path: &Path, mode: Option<u32>) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    match mode {
+        Some(mode) => fs::set_permissions(path, fs::Permissions::from_mode(mode)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(not(unix))]
+fn set_file_mode(_path: &Path, _mode: Option<u32>) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn restore_reverts_edits_and_removes_new_files() -> Result<(), GitToolingError> {
+        let workspace = tempdir()?;
+        let store = tempdir()?;
+        let snapshot_dir = store.path().join("turn-1");
+        let edited = workspace.path().join("config.yaml");
+        let created = workspace.path().join("nested/new.txt");
+        fs::write(&edited, "replicas: 1\n")?;
+
+        let (snapshot, ghost_commit) =
+            FileSnapshot::create(&snapshot_dir, "turn-1", &GhostSnapshotConfig::default())?;
+        assert_eq!(
+            ghost_commit.file_snapshot_dir(),
+            Some(snapshot_dir.as_path())
+        );
+
+        snapshot.capture(&[edited.clone(), created.clone()])?;
+        fs::write(&edited, "replicas: 3\n")?;
+        fs::create_dir_all(created.parent().expect("parent"))?;
+        fs::write(&created, "new\n")?;
+        // A second capture of the same path keeps the pre-turn copy.
+        snapshot.capture(std::slice::from_ref(&edited))?;
+        assert_eq!(
+            file_snapshot_paths(&snapshot_dir)?,
+            vec![edited.clone(), created.clone()]
+        );
+        assert_eq!(
+            file_snapshot_file(&snapshot_dir, &edited)?,
+            Some(SnapshotFile::Contents(b"replicas: 1\n".to_vec()))
+        );
+        assert_eq!(
+            file_snapshot_file(&snapshot_dir, &created)?,
+            Some(SnapshotFile::Missing)
+        );
+
+        let report = restore_file_snapshot(store.path(), &snapshot_dir)?;
+
+        assert_eq!(report, FileSnapshotReport::default());
+        assert_eq!(fs::read_to_string(&edited)?, "replicas: 1\n");
+        assert!(!created.exists());
+        assert!(!snapshot_dir.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn snapshot_without_captures_restores_nothing() -> Result<(), GitToolingError> {
+        let store = tempdir()?;
+        let snapshot_dir = store.path().join("thread-1/turn-1");
+
+        let (snapshot, _) =
+            FileSnapshot::create(&snapshot_dir, "turn-1", &GhostSnapshotConfig::default())?;
+        snapshot.capture(&[])?;
+
+        assert_eq!(file_snapshot_paths(&snapshot_dir)?, Vec::<PathBuf>::new());
+        assert_eq!(
+            restore_file_snapshot(store.path(), &snapshot_dir)?,
+            FileSnapshotReport::default()
+        );
+        assert!(!snapshot_dir.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn restore_rejects_deleted_and_foreign_snapshots() -> Result<(), GitToolingError> {
+        let workspace = tempdir()?;
+        let store = tempdir()?;
+        let elsewhere = tempdir()?;
+        let file = workspace.path().join("notes.txt");
+        fs::write(&file, "notes")?;
+        let config = GhostSnapshotConfig::default();
+
+        let snapshot_dir = store.path().join("turn-1");
+        let (snapshot, _) = FileSnapshot::create(&snapshot_dir, "turn-1", &config)?;
+        snapshot.capture(std::slice::from_ref(&file))?;
+        restore_file_snapshot(store.path(), &snapshot_dir)?;
+        assert!(matches!(
+            restore_file_snapshot(store.path(), &snapshot_dir),
+            Err(GitToolingError::FileSnapshotMissing { .. })
+        ));
+
+        let foreign_dir = elsewhere.path().join("turn-1");
+        let (snapshot, _) = FileSnapshot::create(&foreign_dir, "turn-1", &config)?;
+        snapshot.capture(std::slice::from_ref(&file))?;
+        fs::write(&file, "changed")?;
+        let escaping_dir = store.path().join("..").join(
+            elsewhere
+                .path()
+                .file_name()
+                .expect("temp dir name")
+                .to_os_string(),
+        );
+        for dir in [&foreign_dir, &escaping_dir.join("turn-1")] {
+            assert!(matches!(
+                restore_file_snapshot(store.path(), dir),
+                Err(GitToolingError::FileSnapshotOutsideRoot { .. })
+            ));
+        }
+        assert!(foreign_dir.exists());
+        assert_eq!(fs::read_to_string(&file)?, "changed");
+        Ok(())
+    }
+
+    #[test]
+    fn prune_removes_expired_turns_and_empty_threads() -> Result<(), GitToolingError> {
+        let workspace = tempdir()?;
+        let store = tempdir()?;
+        let file = workspace.path().join("notes.txt");
+        fs::write(&file, "notes")?;
+        let config = GhostSnapshotConfig::default();
+        let old_turn = store.path().join("thread-1/turn-1");
+        let recent_turn = store.path().join("thread-2/turn-1");
+        for dir in [&old_turn, &recent_turn] {
+            let (snapshot, _) = FileSnapshot::create(dir, "turn-1", &config)?;
+            snapshot.capture(std::slice::from_ref(&file))?;
+        }
+        let two_days_ago = SystemTime::now() - Duration::from_secs(2 * 24 * 60 * 60);
+        fs::File::options()
+            .write(true)
+            .open(old_turn.join(MANIFEST_FILE))?
+            .set_modified(two_days_ago)?;
+
+        prune_file_snapshots(store.path(), Duration::from_secs(24 * 60 * 60))?;
+
+        assert!(!store.path().join("thread-1").exists());
+        assert!(recent_turn.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn capture_respects_size_and_file_limits() -> Result<(), GitToolingError> {
+        let workspace = tempdir()?;
+        let store = tempdir()?;
+        let snapshot_dir = store.path().join("turn-1");
+        let large = workspace.path().join("large.bin");
+        let small = workspace.path().join("small.txt");
+        let extra = workspace.path().join("extra.txt");
+        fs::write(&large, vec![0u8; 32])?;
+        fs::write(&small, "small")?;
+        fs::write(&extra, "extra")?;
+        let config = GhostSnapshotConfig {
+            ignore_large_untracked_files: Some(16),
+            ignore_large_untracked_dirs: Some(2),
+            disable_warnings: false,
+        };
+
+        let (snapshot, _) = FileSnapshot::create(&snapshot_dir, "turn-1", &config)?;
+        let report = snapshot.capture(&[large.clone(), small.clone(), extra.clone()])?;
+        assert_eq!(
+            report,
+            FileSnapshotReport {
+                too_large: vec![large.clone()],
+                over_file_limit: vec![extra],
+            }
+        );
+
+        fs::write(&large, "changed")?;
+        fs::write(&small, "changed")?;
+        let report = restore_file_snapshot(store.path(), &snapshot_dir)?;
+
+        assert_eq!(report.too_large, vec![large.clone()]);
+        assert_eq!(fs::read_to_string(&large)?, "changed");
+        assert_eq!(fs::read_to_string(&small)?, "small");
+        Ok(())
+    }
+}
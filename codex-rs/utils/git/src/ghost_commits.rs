@@ -687,6 +687,23 @@ fn capture_existing_untracked(
     .untracked)
 }
 
+/// Untracked files under `repo_root` (limited by `repo_prefix`) that a ghost
+/// snapshot taken now would record, relative to `repo_root`.
+pub(crate) fn snapshot_untracked_files(
+    repo_root: &Path,
+    repo_prefix: Option<&Path>,
+    ghost_snapshot: &GhostSnapshotConfig,
+) -> Result<Vec<PathBuf>, GitToolingError> {
+    Ok(capture_existing_untracked(
+        repo_root,
+        repo_prefix,
+        ghost_snapshot.ignore_large_untracked_files,
+        ghost_snapshot.ignore_large_untracked_dirs,
+        &[],
+    )?
+    .untracked_files_for_index)
+}
+
 fn extract_status_path_after_fields(record: &str, fields_before_path: i64) -> Option<&str> {
     if fields_before_path <= 0 {
         return None;
@@ -1,9 +1,12 @@
 use std::fmt;
+use std::path::Path;
 use std::path::PathBuf;
 
 mod apply;
 mod branch;
+mod checkpoints;
 mod errors;
+mod file_snapshots;
 mod ghost_commits;
 mod operations;
 mod platform;
@@ -15,7 +18,17 @@ pub use apply::extract_paths_from_patch;
 pub use apply::parse_git_apply_output;
 pub use apply::stage_paths;
 pub use branch::merge_base_with_head;
+pub use checkpoints::SnapshotFile;
+pub use checkpoints::ghost_commit_changed_paths;
+pub use checkpoints::ghost_commit_file;
+pub use checkpoints::ghost_commit_worktree_changed_paths;
 pub use errors::GitToolingError;
+pub use file_snapshots::FileSnapshot;
+pub use file_snapshots::FileSnapshotReport;
+pub use file_snapshots::file_snapshot_file;
+pub use file_snapshots::file_snapshot_paths;
+pub use file_snapshots::prune_file_snapshots;
+pub use file_snapshots::restore_file_snapshot;
 pub use ghost_commits::CreateGhostCommitOptions;
 pub use ghost_commits::GhostSnapshotConfig;
 pub use ghost_commits::GhostSnapshotReport;
@@ -43,6 +56,11 @@ pub struct GhostCommit {
     parent: Option<CommitID>,
     preexisting_untracked_files: Vec<PathBuf>,
     preexisting_untracked_dirs: Vec<PathBuf>,
+    /// Set when the snapshot was taken outside a Git repository and lives in
+    /// a file snapshot directory instead of the object database.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    file_snapshot_dir: Option<PathBuf>,
 }
 
 impl GhostCommit {
@@ -58,6 +76,18 @@ impl GhostCommit {
             parent,
             preexisting_untracked_files,
             preexisting_untracked_dirs,
+            file_snapshot_dir: None,
+        }
+    }
+
+    /// Create a wrapper for a snapshot stored as file copies in `dir`.
+    pub fn file_snapshot(id: CommitID, dir: PathBuf) -> Self {
+        Self {
+            id,
+            parent: None,
+            preexisting_untracked_files: Vec::new(),
+            preexisting_untracked_dirs: Vec::new(),
+            file_snapshot_dir: Some(dir),
         }
     }
 
@@ -80,6 +110,11 @@ impl GhostCommit {
     pub fn preexisting_untracked_dirs(&self) -> &[PathBuf] {
         &self.preexisting_untracked_dirs
     }
+
+    /// Directory holding the file snapshot, for snapshots taken outside Git.
+    pub fn file_snapshot_dir(&self) -> Option<&Path> {
+        self.file_snapshot_dir.as_deref()
+    }
 }
 
 impl fmt::Display for GhostCommit {
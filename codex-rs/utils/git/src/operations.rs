@@ -182,6 +182,20 @@ where
     })
 }
 
+/// Executes `git` and returns the raw stdout bytes, for blob contents that
+/// may not be UTF-8.
+pub(crate) fn run_git_for_bytes<I, S>(
+    dir: &Path,
+    args: I,
+    env: Option<&[(OsString, OsString)]>,
+) -> Result<Vec<u8>, GitToolingError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    Ok(run_git(dir, args, env)?.output.stdout)
+}
+
 fn run_git<I, S>(
     dir: &Path,
     args: I,